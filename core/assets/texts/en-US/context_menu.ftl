@@ -0,0 +1,7 @@
+context-menu-play = Play
+context-menu-rewind = Rewind
+context-menu-forward = Forward
+context-menu-back = Back
+context-menu-quality-low = Quality: Low
+context-menu-quality-medium = Quality: Medium
+context-menu-quality-high = Quality: High
@@ -0,0 +1,5 @@
+script-timeout-message =
+    A script in this movie is causing Ruffle to run slowly.
+    If it continues to run, your computer may become unresponsive.
+
+    Do you want to abort the script?
@@ -0,0 +1,7 @@
+context-menu-play = Lire
+context-menu-rewind = Rembobiner
+context-menu-forward = En avant
+context-menu-back = En arrière
+context-menu-quality-low = Qualité : basse
+context-menu-quality-medium = Qualité : moyenne
+context-menu-quality-high = Qualité : haute
@@ -0,0 +1,5 @@
+script-timeout-message =
+    Un script de cette animation ralentit Ruffle.
+    S'il continue à s'exécuter, votre ordinateur pourrait ne plus répondre.
+
+    Voulez-vous arrêter le script ?
@@ -0,0 +1,15 @@
+//! Verifies an ABC file and all of its method bodies, as done before loading
+//! them and before running each method.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use ruffle_core::swf::avm2::read::Reader;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(abc) = Reader::new(data).read() else {
+        return;
+    };
+
+    let _ = ruffle_core::verify_abc_standalone(&abc);
+});
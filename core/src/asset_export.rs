@@ -0,0 +1,350 @@
+//! Exporting the assets embedded in a movie, such as its bitmaps and sounds,
+//! so that they can be preserved or reused outside of the movie.
+//!
+//! The library of a running movie only keeps the decoded form of its characters,
+//! which is often on the GPU or in the audio backend, so the assets are read from
+//! the definition tags of the movie instead.
+//!
+//! Each asset is written to a file named after its export name, or its character ID
+//! if it has none, which is also how `crate::patch` manifests refer to characters:
+//! an edited asset can be loaded back into the movie with a patch.
+//!
+//! - Bitmaps are written as the JPEG, PNG or GIF file they were embedded as, or as
+//!   a PNG file if they were stored as raw pixels or with a separate alpha channel.
+//! - MP3 sounds are written as MP3 files, and other sounds as 16-bit WAV files.
+//! - Shapes are written as SVG files. Bitmap fills are drawn in gray.
+//! - Sprites have no file, but are listed in the manifest with the characters
+//!   placed on their timeline.
+//!
+//! A `manifest.json` file lists every asset, including those that couldn't be exported.
+
+use crate::tag_utils::SwfMovie;
+use ruffle_render::bitmap::{Bitmap, BitmapFormat};
+use ruffle_render::shape_utils::DistilledShape;
+use ruffle_render::svg::shape_to_svg;
+use ruffle_render::utils::{self, JpegTagFormat};
+use serde::Serialize;
+use std::collections::{BTreeSet, HashMap};
+use swf::{AudioCompression, CharacterId, PlaceObjectAction, Tag};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum ExportError {
+    #[error("Couldn't read the movie: {0}")]
+    InvalidMovie(String),
+
+    #[error("Couldn't write {0}: {1}")]
+    Write(String, #[source] std::io::Error),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AssetKind {
+    Bitmap,
+    Sound,
+    Shape,
+    Sprite,
+}
+
+/// An asset of a movie, as listed in the export manifest.
+#[derive(Clone, Debug, Serialize)]
+pub struct Asset {
+    pub id: CharacterId,
+    pub kind: AssetKind,
+
+    /// The name the asset was exported with, with `ExportAssets` or `SymbolClass`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub export_name: Option<String>,
+
+    /// The file the asset was written to, or `None` if it has no file or couldn't be exported.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
+
+    /// Why the asset couldn't be exported.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+
+    /// The number of frames of a sprite.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub num_frames: Option<u16>,
+
+    /// The characters placed on the timeline of a sprite.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<CharacterId>,
+}
+
+impl Asset {
+    fn new(id: CharacterId, kind: AssetKind) -> Self {
+        Self {
+            id,
+            kind,
+            export_name: None,
+            file: None,
+            error: None,
+            num_frames: None,
+            children: vec![],
+        }
+    }
+}
+
+/// The encoded data of an asset, before its file name is known.
+type Encoded = Result<(&'static str, Vec<u8>), String>;
+
+/// Lists the bitmaps, sounds, shapes and sprites defined by a movie.
+pub fn list_assets(movie: &SwfMovie) -> Result<Vec<Asset>, ExportError> {
+    Ok(read_assets(movie)?
+        .into_iter()
+        .map(|(asset, _)| asset)
+        .collect())
+}
+
+/// Exports the assets of a movie, calling `write` with the name and contents of each file.
+///
+/// Assets that can't be decoded are skipped, with the reason recorded in the manifest,
+/// which is written last. The exported assets are returned.
+pub fn export_assets(
+    movie: &SwfMovie,
+    mut write: impl FnMut(&str, &[u8]) -> Result<(), std::io::Error>,
+) -> Result<Vec<Asset>, ExportError> {
+    let mut assets = vec![];
+    for (mut asset, encoded) in read_assets(movie)? {
+        match encoded {
+            Some(Ok((extension, data))) => {
+                let stem = asset
+                    .export_name
+                    .as_deref()
+                    .map(sanitize_file_name)
+                    .unwrap_or_else(|| asset.id.to_string());
+                let file = format!("{stem}.{extension}");
+                write(&file, &data).map_err(|e| ExportError::Write(file.clone(), e))?;
+                asset.file = Some(file);
+            }
+            Some(Err(error)) => {
+                tracing::warn!("Couldn't export character {}: {}", asset.id, error);
+                asset.error = Some(error);
+            }
+            None => (),
+        }
+        assets.push(asset);
+    }
+
+    let manifest = serde_json::to_vec_pretty(&assets).expect("Assets are serializable");
+    write("manifest.json", &manifest)
+        .map_err(|e| ExportError::Write("manifest.json".to_owned(), e))?;
+    Ok(assets)
+}
+
+/// Reads the assets of a movie from its tags, with their encoded data if they have a file.
+fn read_assets(movie: &SwfMovie) -> Result<Vec<(Asset, Option<Encoded>)>, ExportError> {
+    let mut reader = swf::read::Reader::new(movie.data(), movie.version());
+    let mut jpeg_tables = None;
+    let mut export_names = HashMap::new();
+    let mut assets = vec![];
+
+    loop {
+        let tag = match reader.read_tag() {
+            Ok(Tag::End) => break,
+            Ok(tag) => tag,
+            // Content often ends with a truncated or broken tag, keep what was read before.
+            Err(e) if !assets.is_empty() => {
+                tracing::warn!("Stopped reading the movie's assets: {}", e);
+                break;
+            }
+            Err(e) => return Err(ExportError::InvalidMovie(e.to_string())),
+        };
+
+        let asset = match tag {
+            Tag::JpegTables(data) => {
+                jpeg_tables = Some(data);
+                continue;
+            }
+            Tag::ExportAssets(exports) => {
+                for export in exports {
+                    let name = export.name.to_str_lossy(movie.encoding()).into_owned();
+                    export_names.insert(export.id, name);
+                }
+                continue;
+            }
+            Tag::SymbolClass(links) => {
+                for link in links {
+                    let name = link.class_name.to_str_lossy(movie.encoding()).into_owned();
+                    export_names.entry(link.id).or_insert(name);
+                }
+                continue;
+            }
+            Tag::DefineBits { id, jpeg_data } => {
+                let data = utils::glue_tables_to_jpeg(jpeg_data, jpeg_tables);
+                let data = utils::remove_invalid_jpeg_data(&data);
+                let encoded = Ok(("jpg", data.into_owned()));
+                (Asset::new(id, AssetKind::Bitmap), Some(encoded))
+            }
+            Tag::DefineBitsJpeg2 { id, jpeg_data } => {
+                let encoded = encode_embedded_image(jpeg_data, None);
+                (Asset::new(id, AssetKind::Bitmap), Some(encoded))
+            }
+            Tag::DefineBitsJpeg3(jpeg) => {
+                let encoded = encode_embedded_image(jpeg.data, Some(jpeg.alpha_data));
+                (Asset::new(jpeg.id, AssetKind::Bitmap), Some(encoded))
+            }
+            Tag::DefineBitsLossless(lossless) => {
+                let encoded = utils::decode_define_bits_lossless(&lossless)
+                    .map_err(|e| e.to_string())
+                    .and_then(encode_png);
+                (Asset::new(lossless.id, AssetKind::Bitmap), Some(encoded))
+            }
+            Tag::DefineSound(sound) => {
+                let encoded = encode_sound(&sound);
+                (Asset::new(sound.id, AssetKind::Sound), Some(encoded))
+            }
+            Tag::DefineShape(shape) => {
+                let svg = shape_to_svg(&DistilledShape::from(&shape));
+                let asset = Asset::new(shape.id, AssetKind::Shape);
+                (asset, Some(Ok(("svg", svg.into_bytes()))))
+            }
+            Tag::DefineSprite(sprite) => {
+                let mut asset = Asset::new(sprite.id, AssetKind::Sprite);
+                asset.num_frames = Some(sprite.num_frames);
+                let children: BTreeSet<_> = sprite
+                    .tags
+                    .iter()
+                    .filter_map(|tag| match tag {
+                        Tag::PlaceObject(place_object) => match place_object.action {
+                            PlaceObjectAction::Place(id) | PlaceObjectAction::Replace(id) => {
+                                Some(id)
+                            }
+                            PlaceObjectAction::Modify => None,
+                        },
+                        _ => None,
+                    })
+                    .collect();
+                asset.children = children.into_iter().collect();
+                (asset, None)
+            }
+            _ => continue,
+        };
+        assets.push(asset);
+    }
+
+    for (asset, _) in &mut assets {
+        asset.export_name = export_names.get(&asset.id).cloned();
+    }
+    Ok(assets)
+}
+
+/// Encodes the image of a DefineBitsJPEG2 or DefineBitsJPEG3 tag, keeping
+/// the original file unless it has a separate alpha channel.
+fn encode_embedded_image(data: &[u8], alpha_data: Option<&[u8]>) -> Encoded {
+    let format = utils::determine_jpeg_tag_format(data);
+    match (format, alpha_data) {
+        (JpegTagFormat::Jpeg, Some(alpha_data)) if !alpha_data.is_empty() => {
+            utils::decode_define_bits_jpeg(data, Some(alpha_data))
+                .map_err(|e| e.to_string())
+                .and_then(encode_png)
+        }
+        (JpegTagFormat::Jpeg, _) => Ok(("jpg", utils::remove_invalid_jpeg_data(data).into_owned())),
+        (JpegTagFormat::Png, _) => Ok(("png", data.to_vec())),
+        (JpegTagFormat::Gif, _) => Ok(("gif", data.to_vec())),
+        (JpegTagFormat::Unknown, _) => Err("Unknown image format".to_owned()),
+    }
+}
+
+fn encode_png(bitmap: Bitmap) -> Encoded {
+    let (color_type, data) = match bitmap.format() {
+        BitmapFormat::Rgb => (png::ColorType::Rgb, bitmap.data().to_vec()),
+        _ => {
+            let bitmap = bitmap.to_rgba();
+            let mut data = bitmap.data().to_vec();
+            utils::unmultiply_alpha_rgba(&mut data);
+            (png::ColorType::Rgba, data)
+        }
+    };
+
+    let mut png = vec![];
+    let mut encoder = png::Encoder::new(&mut png, bitmap.width(), bitmap.height());
+    encoder.set_color(color_type);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder
+        .write_header()
+        .and_then(|mut writer| writer.write_image_data(&data))
+        .map_err(|e| e.to_string())?;
+    Ok(("png", png))
+}
+
+fn encode_sound(sound: &swf::Sound) -> Encoded {
+    match sound.format.compression {
+        // The MP3 frames follow the number of samples to skip at the start.
+        AudioCompression::Mp3 => Ok(("mp3", sound.data.get(2..).unwrap_or_default().to_vec())),
+        #[cfg(feature = "audio")]
+        _ => {
+            let decoder = crate::backend::audio::decoders::make_decoder(
+                &sound.format,
+                std::io::Cursor::new(sound.data.to_vec()),
+            )
+            .map_err(|e| e.to_string())?;
+            let sample_rate = decoder.sample_rate();
+            let frames: Vec<_> = decoder.collect();
+            Ok(("wav", encode_wav(&frames, sample_rate)))
+        }
+        #[cfg(not(feature = "audio"))]
+        compression => Err(format!("Can't decode {compression:?} sounds")),
+    }
+}
+
+/// Encodes stereo 16-bit samples as a WAV file.
+fn encode_wav(frames: &[[i16; 2]], sample_rate: u16) -> Vec<u8> {
+    const NUM_CHANNELS: u16 = 2;
+    const BYTES_PER_FRAME: u16 = NUM_CHANNELS * 2;
+    let data_len = frames.len() as u32 * u32::from(BYTES_PER_FRAME);
+
+    let mut wav = Vec::with_capacity(44 + data_len as usize);
+    wav.extend_from_slice(b"RIFF");
+    wav.extend_from_slice(&(36 + data_len).to_le_bytes());
+    wav.extend_from_slice(b"WAVEfmt ");
+    wav.extend_from_slice(&16u32.to_le_bytes());
+    wav.extend_from_slice(&1u16.to_le_bytes()); // PCM
+    wav.extend_from_slice(&NUM_CHANNELS.to_le_bytes());
+    wav.extend_from_slice(&u32::from(sample_rate).to_le_bytes());
+    wav.extend_from_slice(&(u32::from(sample_rate) * u32::from(BYTES_PER_FRAME)).to_le_bytes());
+    wav.extend_from_slice(&BYTES_PER_FRAME.to_le_bytes());
+    wav.extend_from_slice(&16u16.to_le_bytes());
+    wav.extend_from_slice(b"data");
+    wav.extend_from_slice(&data_len.to_le_bytes());
+    for frame in frames {
+        wav.extend_from_slice(&frame[0].to_le_bytes());
+        wav.extend_from_slice(&frame[1].to_le_bytes());
+    }
+    wav
+}
+
+/// Replaces the characters of an export name that aren't allowed in file names.
+fn sanitize_file_name(name: &str) -> String {
+    name.chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wav_header() {
+        let wav = encode_wav(&[[1, -1], [2, -2]], 44100);
+        assert_eq!(wav.len(), 44 + 8);
+        assert_eq!(&wav[0..4], b"RIFF");
+        assert_eq!(u32::from_le_bytes(wav[4..8].try_into().unwrap()), 36 + 8);
+        assert_eq!(u32::from_le_bytes(wav[24..28].try_into().unwrap()), 44100);
+        assert_eq!(u32::from_le_bytes(wav[40..44].try_into().unwrap()), 8);
+        assert_eq!(i16::from_le_bytes(wav[46..48].try_into().unwrap()), -1);
+    }
+
+    #[test]
+    fn sanitized_file_names() {
+        assert_eq!(sanitize_file_name("game.Hero"), "game.Hero");
+        assert_eq!(sanitize_file_name("a/b:c"), "a_b_c");
+    }
+}
@@ -12,6 +12,7 @@ mod callable_value;
 mod clamp;
 mod debug;
 mod error;
+pub(crate) mod filters;
 mod fscommand;
 pub(crate) mod globals;
 mod object;
@@ -38,7 +39,7 @@ pub use object::script_object::ScriptObject;
 pub use object::sound_object::SoundObject;
 pub use object::stage_object::StageObject;
 pub use object::xml_node_object::XmlNodeObject;
-pub use object::{Object, ObjectPtr, TObject};
+pub use object::{NativeObject, Object, ObjectPtr, TObject};
 pub use property::Attribute;
 pub use property_map::PropertyMap;
 pub use runtime::Avm1;
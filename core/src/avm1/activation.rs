@@ -10,6 +10,7 @@ use crate::backend::navigator::{NavigationMethod, Request};
 use crate::context::UpdateContext;
 use crate::display_object::{DisplayObject, MovieClip, TDisplayObject, TDisplayObjectContainer};
 use crate::ecma_conversions::f64_to_wrapping_u32;
+use crate::limits::ScriptTimeout;
 use crate::string::{AvmString, SwfStrExt as _, WStr, WString};
 use crate::tag_utils::SwfSlice;
 use crate::vminterface::Instantiator;
@@ -439,7 +440,7 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         *self.context.actions_since_timeout_check += 1;
         if *self.context.actions_since_timeout_check >= 2000 {
             *self.context.actions_since_timeout_check = 0;
-            if self.context.update_start.elapsed() >= self.context.max_execution_duration {
+            if self.context.check_script_timeout() != ScriptTimeout::None {
                 return Err(Error::ExecutionTimeout);
             }
         }
@@ -1197,14 +1198,15 @@ impl<'a, 'gc> Activation<'a, 'gc> {
 
                     if url.is_empty() {
                         //Blank URL on movie loads = unload!
-                        if let Some(mut mc) = level.as_movie_clip() {
-                            mc.replace_with_movie(&mut self.context, None, None)
+                        if let Some(mc) = level.as_movie_clip() {
+                            mc.avm1_unload_movie(&mut self.context)
                         }
                     } else {
                         let future = self.context.load_manager.load_movie_into_clip(
                             self.context.player.clone(),
                             level,
                             Request::get(url.to_string()),
+                            self.base_clip().movie(),
                             None,
                             None,
                             None,
@@ -1313,6 +1315,7 @@ impl<'a, 'gc> Activation<'a, 'gc> {
                         self.context.player.clone(),
                         target_obj,
                         request,
+                        self.base_clip().movie(),
                     );
                     self.context.navigator.spawn_future(future);
                 }
@@ -1323,8 +1326,8 @@ impl<'a, 'gc> Activation<'a, 'gc> {
             if let Some(clip_target) = clip_target {
                 if url.is_empty() {
                     // Blank URL on movie loads = unload!
-                    if let Some(mut mc) = clip_target.as_movie_clip() {
-                        mc.replace_with_movie(&mut self.context, None, None)
+                    if let Some(mc) = clip_target.as_movie_clip() {
+                        mc.avm1_unload_movie(&mut self.context)
                     }
                 } else {
                     let request = self.locals_into_request(
@@ -1335,6 +1338,7 @@ impl<'a, 'gc> Activation<'a, 'gc> {
                         self.context.player.clone(),
                         clip_target,
                         request,
+                        self.base_clip().movie(),
                         None,
                         None,
                         None,
@@ -1348,14 +1352,15 @@ impl<'a, 'gc> Activation<'a, 'gc> {
             if let Some(clip_target) = clip_target {
                 if url.is_empty() {
                     // Blank URL on movie loads = unload!
-                    if let Some(mut mc) = clip_target.as_movie_clip() {
-                        mc.replace_with_movie(&mut self.context, None, None)
+                    if let Some(mc) = clip_target.as_movie_clip() {
+                        mc.avm1_unload_movie(&mut self.context)
                     }
                 } else {
                     let future = self.context.load_manager.load_movie_into_clip(
                         self.context.player.clone(),
                         clip_target,
                         Request::get(url.to_utf8_lossy().into_owned()),
+                        self.base_clip().movie(),
                         None,
                         None,
                         None,
@@ -2195,7 +2200,8 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         } else {
             val.coerce_to_string(self)?
         };
-        self.context.avm_trace(&out.to_utf8_lossy());
+        let movie = self.base_clip().movie();
+        self.context.movie_trace(&movie, &out.to_utf8_lossy());
         Ok(FrameControl::Continue)
     }
 
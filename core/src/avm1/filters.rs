@@ -0,0 +1,566 @@
+//! Conversions between AVM1 filter objects and the filters of display objects.
+
+use crate::avm1::object::NativeObject;
+use crate::avm1::{Activation, ArrayObject, Error, Object, TObject, Value};
+use crate::display_object::{DisplayObject, TDisplayObject};
+use ruffle_render::filters::{DisplacementMapFilter, DisplacementMapFilterMode, Filter};
+use swf::{
+    BevelFilter, BevelFilterFlags, BlurFilter, BlurFilterFlags, Color, ColorMatrixFilter,
+    ConvolutionFilter, ConvolutionFilterFlags, DropShadowFilter, DropShadowFilterFlags, Fixed16,
+    Fixed8, GlowFilter, GlowFilterFlags, GradientFilter, GradientFilterFlags, GradientRecord,
+};
+
+pub trait FilterAvm1Ext {
+    /// Reads the filter described by an AVM1 object, if it's a filter.
+    fn from_avm1_object<'gc>(
+        activation: &mut Activation<'_, 'gc>,
+        object: Object<'gc>,
+    ) -> Result<Option<Filter>, Error<'gc>>;
+
+    /// Creates a new AVM1 object describing this filter.
+    fn as_avm1_object<'gc>(
+        &self,
+        activation: &mut Activation<'_, 'gc>,
+    ) -> Result<Object<'gc>, Error<'gc>>;
+}
+
+impl FilterAvm1Ext for Filter {
+    fn from_avm1_object<'gc>(
+        activation: &mut Activation<'_, 'gc>,
+        object: Object<'gc>,
+    ) -> Result<Option<Filter>, Error<'gc>> {
+        let filter = match object.native() {
+            NativeObject::BevelFilter(_) => avm1_to_bevel_filter(activation, object)?,
+            NativeObject::BlurFilter(_) => avm1_to_blur_filter(activation, object)?,
+            NativeObject::ColorMatrixFilter(_) => avm1_to_color_matrix_filter(activation, object)?,
+            NativeObject::DropShadowFilter(_) => avm1_to_drop_shadow_filter(activation, object)?,
+            NativeObject::GlowFilter(_) => avm1_to_glow_filter(activation, object)?,
+            _ if object.as_convolution_filter_object().is_some() => {
+                avm1_to_convolution_filter(activation, object)?
+            }
+            _ if object.as_displacement_map_filter_object().is_some() => {
+                avm1_to_displacement_map_filter(activation, object)?
+            }
+            _ if object.as_gradient_bevel_filter_object().is_some() => {
+                Filter::GradientBevelFilter(avm1_to_gradient_filter(activation, object)?)
+            }
+            _ if object.as_gradient_glow_filter_object().is_some() => {
+                Filter::GradientGlowFilter(avm1_to_gradient_filter(activation, object)?)
+            }
+            _ => return Ok(None),
+        };
+        Ok(Some(filter))
+    }
+
+    fn as_avm1_object<'gc>(
+        &self,
+        activation: &mut Activation<'_, 'gc>,
+    ) -> Result<Object<'gc>, Error<'gc>> {
+        let prototypes = activation.context.avm1.prototypes().clone();
+        let (constructor, args) = match self {
+            Filter::BevelFilter(filter) => (
+                prototypes.bevel_filter_constructor,
+                vec![
+                    filter.distance.to_f64().into(),
+                    filter.angle.to_f64().to_degrees().into(),
+                    filter.highlight_color.to_rgb().into(),
+                    alpha(filter.highlight_color),
+                    filter.shadow_color.to_rgb().into(),
+                    alpha(filter.shadow_color),
+                    filter.blur_x.to_f64().into(),
+                    filter.blur_y.to_f64().into(),
+                    filter.strength.to_f64().into(),
+                    filter.num_passes().into(),
+                    bevel_type(filter.is_on_top(), filter.is_inner()),
+                    filter.is_knockout().into(),
+                ],
+            ),
+            Filter::BlurFilter(filter) => (
+                prototypes.blur_filter_constructor,
+                vec![
+                    filter.blur_x.to_f64().into(),
+                    filter.blur_y.to_f64().into(),
+                    filter.num_passes().into(),
+                ],
+            ),
+            Filter::ColorMatrixFilter(filter) => {
+                let matrix = ArrayObject::new(
+                    activation.context.gc_context,
+                    prototypes.array,
+                    filter.matrix.iter().map(|&value| value.into()),
+                );
+                (
+                    prototypes.color_matrix_filter_constructor,
+                    vec![matrix.into()],
+                )
+            }
+            Filter::ConvolutionFilter(filter) => {
+                let matrix = ArrayObject::new(
+                    activation.context.gc_context,
+                    prototypes.array,
+                    filter.matrix.iter().map(|value| value.to_f64().into()),
+                );
+                (
+                    prototypes.convolution_filter_constructor,
+                    vec![
+                        filter.num_matrix_cols.into(),
+                        filter.num_matrix_rows.into(),
+                        matrix.into(),
+                        filter.divisor.to_f64().into(),
+                        filter.bias.to_f64().into(),
+                        filter.is_preserve_alpha().into(),
+                        filter.is_clamped().into(),
+                        filter.default_color.to_rgb().into(),
+                        alpha(filter.default_color),
+                    ],
+                )
+            }
+            Filter::DisplacementMapFilter(filter) => {
+                let map_point = prototypes.point_constructor.construct(
+                    activation,
+                    &[filter.map_point.0.into(), filter.map_point.1.into()],
+                )?;
+                let mode = match filter.mode {
+                    DisplacementMapFilterMode::Clamp => "clamp",
+                    DisplacementMapFilterMode::Color => "color",
+                    DisplacementMapFilterMode::Ignore => "ignore",
+                    DisplacementMapFilterMode::Wrap => "wrap",
+                };
+                (
+                    prototypes.displacement_map_filter_constructor,
+                    vec![
+                        // The map is only kept as a texture.
+                        Value::Undefined,
+                        map_point,
+                        filter.component_x.into(),
+                        filter.component_y.into(),
+                        filter.scale_x.into(),
+                        filter.scale_y.into(),
+                        mode.into(),
+                        filter.color.to_rgb().into(),
+                        alpha(filter.color),
+                    ],
+                )
+            }
+            Filter::DropShadowFilter(filter) => (
+                prototypes.drop_shadow_filter_constructor,
+                vec![
+                    filter.distance.to_f64().into(),
+                    filter.angle.to_f64().to_degrees().into(),
+                    filter.color.to_rgb().into(),
+                    alpha(filter.color),
+                    filter.blur_x.to_f64().into(),
+                    filter.blur_y.to_f64().into(),
+                    filter.strength.to_f64().into(),
+                    filter.num_passes().into(),
+                    filter.is_inner().into(),
+                    filter.is_knockout().into(),
+                    filter.hide_object().into(),
+                ],
+            ),
+            Filter::GlowFilter(filter) => (
+                prototypes.glow_filter_constructor,
+                vec![
+                    filter.color.to_rgb().into(),
+                    alpha(filter.color),
+                    filter.blur_x.to_f64().into(),
+                    filter.blur_y.to_f64().into(),
+                    filter.strength.to_f64().into(),
+                    filter.num_passes().into(),
+                    filter.is_inner().into(),
+                    filter.is_knockout().into(),
+                ],
+            ),
+            Filter::GradientBevelFilter(filter) => (
+                prototypes.gradient_bevel_filter_constructor,
+                gradient_filter_args(activation, filter),
+            ),
+            Filter::GradientGlowFilter(filter) => (
+                prototypes.gradient_glow_filter_constructor,
+                gradient_filter_args(activation, filter),
+            ),
+        };
+        match constructor.construct(activation, &args)? {
+            Value::Object(object) => Ok(object),
+            value => Ok(value.coerce_to_object(activation)),
+        }
+    }
+}
+
+/// Implements the `filters` getter of display objects, which returns copies of their filters.
+pub fn get_filters<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    object: DisplayObject<'gc>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let filters = object
+        .filters()
+        .iter()
+        .map(|filter| Ok(filter.as_avm1_object(activation)?.into()))
+        .collect::<Result<Vec<Value<'gc>>, Error<'gc>>>()?;
+    Ok(ArrayObject::new(
+        activation.context.gc_context,
+        activation.context.avm1.prototypes().array,
+        filters,
+    )
+    .into())
+}
+
+/// Implements the `filters` setter of display objects, which copies the filters of an array.
+///
+/// Elements that aren't filters are ignored, and values that aren't arrays remove the filters.
+pub fn set_filters<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    object: DisplayObject<'gc>,
+    value: Value<'gc>,
+) -> Result<(), Error<'gc>> {
+    let mut filters = Vec::new();
+    if let Value::Object(array) = value {
+        for index in 0..array.length(activation)? {
+            if let Value::Object(filter) = array.get_element(activation, index) {
+                if let Some(filter) = Filter::from_avm1_object(activation, filter)? {
+                    filters.push(filter);
+                }
+            }
+        }
+    }
+    object.set_filters(activation.context.gc_context, filters);
+    Ok(())
+}
+
+fn get_f64<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    object: Object<'gc>,
+    name: &'static str,
+) -> Result<f64, Error<'gc>> {
+    object.get(name, activation)?.coerce_to_f64(activation)
+}
+
+fn get_u32<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    object: Object<'gc>,
+    name: &'static str,
+) -> Result<u32, Error<'gc>> {
+    object.get(name, activation)?.coerce_to_u32(activation)
+}
+
+fn get_bool<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    object: Object<'gc>,
+    name: &'static str,
+) -> Result<bool, Error<'gc>> {
+    let value = object.get(name, activation)?;
+    Ok(value.as_bool(activation.swf_version()))
+}
+
+/// Reads the numbers of an array property, which is empty if it isn't an array.
+fn get_numbers<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    object: Object<'gc>,
+    name: &'static str,
+) -> Result<Vec<f64>, Error<'gc>> {
+    let mut numbers = Vec::new();
+    if let Value::Object(array) = object.get(name, activation)? {
+        for index in 0..array.length(activation)? {
+            numbers.push(
+                array
+                    .get_element(activation, index)
+                    .coerce_to_f64(activation)?,
+            );
+        }
+    }
+    Ok(numbers)
+}
+
+fn get_color<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    object: Object<'gc>,
+    color: &'static str,
+    alpha: &'static str,
+) -> Result<Color, Error<'gc>> {
+    let rgb = get_u32(activation, object, color)?;
+    let alpha = get_f64(activation, object, alpha)?;
+    Ok(Color::from_rgb(rgb, (alpha * 255.0) as u8))
+}
+
+fn alpha<'gc>(color: Color) -> Value<'gc> {
+    (f64::from(color.a) / 255.0).into()
+}
+
+fn bevel_type<'gc>(is_on_top: bool, is_inner: bool) -> Value<'gc> {
+    if is_on_top {
+        "full"
+    } else if is_inner {
+        "inner"
+    } else {
+        "outer"
+    }
+    .into()
+}
+
+fn avm1_to_bevel_filter<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    object: Object<'gc>,
+) -> Result<Filter, Error<'gc>> {
+    let bevel_type = object
+        .get("type", activation)?
+        .coerce_to_string(activation)?;
+    let mut flags = BevelFilterFlags::COMPOSITE_SOURCE;
+    if &bevel_type == b"inner" {
+        flags |= BevelFilterFlags::INNER_SHADOW;
+    } else if &bevel_type != b"outer" {
+        flags |= BevelFilterFlags::ON_TOP;
+    }
+    flags.set(
+        BevelFilterFlags::KNOCKOUT,
+        get_bool(activation, object, "knockout")?,
+    );
+    let quality = get_u32(activation, object, "quality")?;
+    flags |= BevelFilterFlags::from_passes(quality.clamp(1, 15) as u8);
+    Ok(Filter::BevelFilter(BevelFilter {
+        shadow_color: get_color(activation, object, "shadowColor", "shadowAlpha")?,
+        highlight_color: get_color(activation, object, "highlightColor", "highlightAlpha")?,
+        blur_x: Fixed16::from_f64(get_f64(activation, object, "blurX")?.max(0.0)),
+        blur_y: Fixed16::from_f64(get_f64(activation, object, "blurY")?.max(0.0)),
+        angle: Fixed16::from_f64(get_f64(activation, object, "angle")?.to_radians()),
+        distance: Fixed16::from_f64(get_f64(activation, object, "distance")?),
+        strength: Fixed8::from_f64(get_f64(activation, object, "strength")?.clamp(0.0, 255.0)),
+        flags,
+    }))
+}
+
+fn avm1_to_blur_filter<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    object: Object<'gc>,
+) -> Result<Filter, Error<'gc>> {
+    let quality = get_u32(activation, object, "quality")?;
+    Ok(Filter::BlurFilter(BlurFilter {
+        blur_x: Fixed16::from_f64(get_f64(activation, object, "blurX")?.max(0.0)),
+        blur_y: Fixed16::from_f64(get_f64(activation, object, "blurY")?.max(0.0)),
+        flags: BlurFilterFlags::from_passes(quality.clamp(1, 15) as u8),
+    }))
+}
+
+fn avm1_to_color_matrix_filter<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    object: Object<'gc>,
+) -> Result<Filter, Error<'gc>> {
+    let mut matrix = [0.0; 20];
+    for (value, number) in matrix
+        .iter_mut()
+        .zip(get_numbers(activation, object, "matrix")?)
+    {
+        *value = number as f32;
+    }
+    Ok(Filter::ColorMatrixFilter(ColorMatrixFilter { matrix }))
+}
+
+fn avm1_to_convolution_filter<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    object: Object<'gc>,
+) -> Result<Filter, Error<'gc>> {
+    let matrix_x = get_u32(activation, object, "matrixX")?.min(255);
+    let matrix_y = get_u32(activation, object, "matrixY")?.min(255);
+    let mut matrix: Vec<_> = get_numbers(activation, object, "matrix")?
+        .into_iter()
+        .map(Fixed16::from_f64)
+        .collect();
+    matrix.resize((matrix_x * matrix_y) as usize, Fixed16::ZERO);
+    let mut flags = ConvolutionFilterFlags::empty();
+    flags.set(
+        ConvolutionFilterFlags::CLAMP,
+        get_bool(activation, object, "clamp")?,
+    );
+    flags.set(
+        ConvolutionFilterFlags::PRESERVE_ALPHA,
+        get_bool(activation, object, "preserveAlpha")?,
+    );
+    Ok(Filter::ConvolutionFilter(ConvolutionFilter {
+        bias: Fixed16::from_f64(get_f64(activation, object, "bias")?),
+        default_color: get_color(activation, object, "color", "alpha")?,
+        divisor: Fixed16::from_f64(get_f64(activation, object, "divisor")?),
+        matrix,
+        num_matrix_cols: matrix_x as u8,
+        num_matrix_rows: matrix_y as u8,
+        flags,
+    }))
+}
+
+fn avm1_to_displacement_map_filter<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    object: Object<'gc>,
+) -> Result<Filter, Error<'gc>> {
+    let map_point = if let Value::Object(point) = object.get("mapPoint", activation)? {
+        (
+            point.get("x", activation)?.coerce_to_i32(activation)?,
+            point.get("y", activation)?.coerce_to_i32(activation)?,
+        )
+    } else {
+        (0, 0)
+    };
+    let mode = object
+        .get("mode", activation)?
+        .coerce_to_string(activation)?;
+    let mode = if &mode == b"clamp" {
+        DisplacementMapFilterMode::Clamp
+    } else if &mode == b"ignore" {
+        DisplacementMapFilterMode::Ignore
+    } else if &mode == b"color" {
+        DisplacementMapFilterMode::Color
+    } else {
+        DisplacementMapFilterMode::Wrap
+    };
+    let map_bitmap = match object.get("mapBitmap", activation)? {
+        Value::Object(bitmap) => bitmap.as_bitmap_data_object().map(|bitmap| {
+            bitmap
+                .bitmap_data()
+                .bitmap_handle(activation.context.gc_context, activation.context.renderer)
+        }),
+        _ => None,
+    };
+    Ok(Filter::DisplacementMapFilter(DisplacementMapFilter {
+        color: get_color(activation, object, "color", "alpha")?,
+        component_x: get_u32(activation, object, "componentX")? as u8,
+        component_y: get_u32(activation, object, "componentY")? as u8,
+        map_bitmap,
+        map_point,
+        mode,
+        scale_x: get_f64(activation, object, "scaleX")? as f32,
+        scale_y: get_f64(activation, object, "scaleY")? as f32,
+    }))
+}
+
+fn avm1_to_drop_shadow_filter<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    object: Object<'gc>,
+) -> Result<Filter, Error<'gc>> {
+    let mut flags = DropShadowFilterFlags::empty();
+    if !get_bool(activation, object, "hideObject")? {
+        flags |= DropShadowFilterFlags::COMPOSITE_SOURCE;
+    }
+    flags.set(
+        DropShadowFilterFlags::INNER_SHADOW,
+        get_bool(activation, object, "inner")?,
+    );
+    flags.set(
+        DropShadowFilterFlags::KNOCKOUT,
+        get_bool(activation, object, "knockout")?,
+    );
+    let quality = get_u32(activation, object, "quality")?;
+    flags |= DropShadowFilterFlags::from_passes(quality.clamp(1, 15) as u8);
+    Ok(Filter::DropShadowFilter(DropShadowFilter {
+        color: get_color(activation, object, "color", "alpha")?,
+        angle: Fixed16::from_f64(get_f64(activation, object, "angle")?.to_radians()),
+        blur_x: Fixed16::from_f64(get_f64(activation, object, "blurX")?.max(0.0)),
+        blur_y: Fixed16::from_f64(get_f64(activation, object, "blurY")?.max(0.0)),
+        distance: Fixed16::from_f64(get_f64(activation, object, "distance")?),
+        strength: Fixed8::from_f64(get_f64(activation, object, "strength")?.clamp(0.0, 255.0)),
+        flags,
+    }))
+}
+
+fn avm1_to_glow_filter<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    object: Object<'gc>,
+) -> Result<Filter, Error<'gc>> {
+    let mut flags = GlowFilterFlags::COMPOSITE_SOURCE;
+    flags.set(
+        GlowFilterFlags::INNER_GLOW,
+        get_bool(activation, object, "inner")?,
+    );
+    flags.set(
+        GlowFilterFlags::KNOCKOUT,
+        get_bool(activation, object, "knockout")?,
+    );
+    let quality = get_u32(activation, object, "quality")?;
+    flags |= GlowFilterFlags::from_passes(quality.clamp(1, 15) as u8);
+    Ok(Filter::GlowFilter(GlowFilter {
+        color: get_color(activation, object, "color", "alpha")?,
+        blur_x: Fixed16::from_f64(get_f64(activation, object, "blurX")?.max(0.0)),
+        blur_y: Fixed16::from_f64(get_f64(activation, object, "blurY")?.max(0.0)),
+        strength: Fixed8::from_f64(get_f64(activation, object, "strength")?.clamp(0.0, 255.0)),
+        flags,
+    }))
+}
+
+fn avm1_to_gradient_filter<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    object: Object<'gc>,
+) -> Result<GradientFilter, Error<'gc>> {
+    // Only as many records as the shortest of the arrays are kept.
+    let colors = get_numbers(activation, object, "colors")?;
+    let alphas = get_numbers(activation, object, "alphas")?;
+    let ratios = get_numbers(activation, object, "ratios")?;
+    let colors = colors
+        .into_iter()
+        .zip(alphas)
+        .zip(ratios)
+        .map(|((color, alpha), ratio)| GradientRecord {
+            ratio: ratio.clamp(0.0, 255.0) as u8,
+            color: Color::from_rgb(color as u32, (alpha * 255.0) as u8),
+        })
+        .collect();
+
+    let bevel_type = object
+        .get("type", activation)?
+        .coerce_to_string(activation)?;
+    let mut flags = GradientFilterFlags::COMPOSITE_SOURCE;
+    if &bevel_type == b"inner" {
+        flags |= GradientFilterFlags::INNER_SHADOW;
+    } else if &bevel_type != b"outer" {
+        flags |= GradientFilterFlags::ON_TOP;
+    }
+    flags.set(
+        GradientFilterFlags::KNOCKOUT,
+        get_bool(activation, object, "knockout")?,
+    );
+    let quality = get_u32(activation, object, "quality")?;
+    flags |= GradientFilterFlags::from_passes(quality.clamp(1, 15) as u8);
+    Ok(GradientFilter {
+        colors,
+        blur_x: Fixed16::from_f64(get_f64(activation, object, "blurX")?.max(0.0)),
+        blur_y: Fixed16::from_f64(get_f64(activation, object, "blurY")?.max(0.0)),
+        angle: Fixed16::from_f64(get_f64(activation, object, "angle")?.to_radians()),
+        distance: Fixed16::from_f64(get_f64(activation, object, "distance")?),
+        strength: Fixed8::from_f64(get_f64(activation, object, "strength")?.clamp(0.0, 255.0)),
+        flags,
+    })
+}
+
+fn gradient_filter_args<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    filter: &GradientFilter,
+) -> Vec<Value<'gc>> {
+    let gc_context = activation.context.gc_context;
+    let array_proto = activation.context.avm1.prototypes().array;
+    let colors = ArrayObject::new(
+        gc_context,
+        array_proto,
+        filter
+            .colors
+            .iter()
+            .map(|record| record.color.to_rgb().into()),
+    );
+    let alphas = ArrayObject::new(
+        gc_context,
+        array_proto,
+        filter.colors.iter().map(|record| alpha(record.color)),
+    );
+    let ratios = ArrayObject::new(
+        gc_context,
+        array_proto,
+        filter.colors.iter().map(|record| record.ratio.into()),
+    );
+    vec![
+        filter.distance.to_f64().into(),
+        filter.angle.to_f64().to_degrees().into(),
+        colors.into(),
+        alphas.into(),
+        ratios.into(),
+        filter.blur_x.to_f64().into(),
+        filter.blur_y.to_f64().into(),
+        filter.strength.to_f64().into(),
+        filter.num_passes().into(),
+        bevel_type(filter.is_on_top(), filter.is_inner()),
+        filter.is_knockout().into(),
+    ]
+}
@@ -5,7 +5,7 @@ use crate::avm1::property::Attribute;
 use crate::avm1::property_decl::{define_properties_on, Declaration};
 use crate::avm1::{Object, ScriptObject, TObject, Value};
 use crate::context::GcContext;
-use crate::display_object::{DisplayObject, TDisplayObject, TDisplayObjectContainer};
+use crate::display_object::{Depth, DisplayObject, TDisplayObject, TDisplayObjectContainer};
 use crate::string::{AvmString, WStr, WString};
 use gc_arena::Collect;
 use std::str;
@@ -19,6 +19,7 @@ mod bitmap_filter;
 pub(crate) mod blur_filter;
 pub(crate) mod boolean;
 pub(crate) mod button;
+mod camera;
 mod color;
 pub(crate) mod color_matrix_filter;
 pub(crate) mod color_transform;
@@ -36,7 +37,7 @@ pub mod gradient_bevel_filter;
 pub mod gradient_glow_filter;
 mod key;
 mod load_vars;
-mod local_connection;
+pub(crate) mod local_connection;
 mod math;
 mod matrix;
 pub(crate) mod mouse;
@@ -46,12 +47,14 @@ pub(crate) mod netstream;
 pub(crate) mod number;
 mod object;
 mod point;
+mod print_job;
 mod rectangle;
 mod selection;
 pub(crate) mod shared_object;
 pub(crate) mod sound;
 mod stage;
 pub(crate) mod string;
+mod style_sheet;
 pub(crate) mod system;
 pub(crate) mod system_capabilities;
 pub(crate) mod system_ime;
@@ -62,6 +65,7 @@ mod transform;
 mod video;
 mod xml;
 mod xml_node;
+pub(crate) mod xml_socket;
 
 const GLOBAL_DECLS: &[Declaration] = declare_properties! {
     "trace" => method(trace; DONT_ENUM);
@@ -93,7 +97,8 @@ pub fn trace<'gc>(
         .get(0)
         .unwrap_or(&Value::Undefined)
         .coerce_to_string(activation)?;
-    activation.context.avm_trace(&out.to_utf8_lossy());
+    let movie = activation.base_clip().movie();
+    activation.context.movie_trace(&movie, &out.to_utf8_lossy());
     Ok(Value::Undefined)
 }
 
@@ -291,13 +296,19 @@ pub fn create_timer<'gc>(
     is_timeout: bool,
 ) -> Result<Value<'gc>, Error<'gc>> {
     // `setInterval` was added in Flash Player 6 but is not version-gated.
-    use crate::timer::TimerCallback;
+    use crate::timer::{TimerCallback, Timers};
 
+    // Callbacks run in the movie that created the timer, even if it was created
+    // through a function of another movie.
+    let base_clip = activation.base_clip();
+
+    // `setInterval(func, interval, args...)` or `setInterval(object, methodName, interval, args...)`
     let (callback, interval) = match args.get(0) {
         Some(Value::Object(o)) if o.as_executable().is_some() => (
             TimerCallback::Avm1Function {
                 func: *o,
                 params: args.get(2..).unwrap_or_default().to_vec(),
+                base_clip,
             },
             args.get(1),
         ),
@@ -309,16 +320,19 @@ pub fn create_timer<'gc>(
                     .unwrap_or(&Value::Undefined)
                     .coerce_to_string(activation)?,
                 params: args.get(3..).map(|s| s.to_vec()).unwrap_or_default(),
+                base_clip,
             },
             args.get(2),
         ),
         _ => return Ok(Value::Undefined),
     };
 
+    // Intervals may be fractional.
     let interval = match interval.unwrap_or(&Value::Undefined) {
         Value::Undefined => return Ok(Value::Undefined),
-        value => value.coerce_to_i32(activation)?,
+        value => value.coerce_to_f64(activation)?,
     };
+    let min_interval = Timers::avm1_min_interval(activation.swf_version());
 
     // If `is_timeout` is true, then set a repeat count of 1.
     // Otherwise, set a repeat count of 0 (repeat indefinitely)
@@ -327,7 +341,7 @@ pub fn create_timer<'gc>(
     let id = activation
         .context
         .timers
-        .add_timer(callback, interval, is_timeout);
+        .add_timer(callback, interval, min_interval, is_timeout);
 
     Ok(id.into())
 }
@@ -499,6 +513,11 @@ pub struct SystemPrototypes<'gc> {
     pub context_menu_item_constructor: Object<'gc>,
     pub bitmap_filter: Object<'gc>,
     pub bitmap_filter_constructor: Object<'gc>,
+    pub blur_filter_constructor: Object<'gc>,
+    pub bevel_filter_constructor: Object<'gc>,
+    pub glow_filter_constructor: Object<'gc>,
+    pub drop_shadow_filter_constructor: Object<'gc>,
+    pub color_matrix_filter_constructor: Object<'gc>,
     pub displacement_map_filter: Object<'gc>,
     pub displacement_map_filter_constructor: Object<'gc>,
     pub convolution_filter: Object<'gc>,
@@ -547,6 +566,8 @@ pub fn create_globals<'gc>(
     let xmlnode_proto = xml_node::create_proto(context, object_proto, function_proto);
 
     let xml_proto = xml::create_proto(context, xmlnode_proto, function_proto);
+    let xml_socket_proto = xml_socket::create_proto(context, object_proto, function_proto);
+    let print_job_proto = print_job::create_proto(context, object_proto, function_proto);
 
     let string_proto = string::create_proto(context, object_proto, function_proto);
     let number_proto = number::create_proto(context, object_proto, function_proto);
@@ -584,6 +605,8 @@ pub fn create_globals<'gc>(
 
     let video_proto = video::create_proto(context, object_proto, function_proto);
     let netstream_proto = netstream::create_proto(context, object_proto, function_proto);
+    let style_sheet_proto = style_sheet::create_proto(context, object_proto, function_proto);
+    let camera_proto = camera::create_proto(context, object_proto, function_proto);
 
     //TODO: These need to be constructors and should also set `.prototype` on each one
     let object = object::create_object_object(context, object_proto, function_proto);
@@ -678,11 +701,33 @@ pub fn create_globals<'gc>(
         function_proto,
         xml_proto,
     );
+    let xml_socket = FunctionObject::constructor(
+        gc_context,
+        Executable::Native(xml_socket::constructor),
+        constructor_to_fn!(xml_socket::constructor),
+        function_proto,
+        xml_socket_proto,
+    );
+    let print_job = FunctionObject::constructor(
+        gc_context,
+        Executable::Native(print_job::constructor),
+        constructor_to_fn!(print_job::constructor),
+        function_proto,
+        print_job_proto,
+    );
     let string = string::create_string_object(context, string_proto, function_proto);
     let number = number::create_number_object(context, number_proto, function_proto);
     let boolean = boolean::create_boolean_object(context, boolean_proto, function_proto);
     let date = date::create_constructor(context, object_proto, function_proto);
     let netstream = netstream::create_class(context, netstream_proto, function_proto);
+    let style_sheet = style_sheet::create_class(context, style_sheet_proto, function_proto);
+    let camera = camera::create_class(context, camera_proto, function_proto);
+    text_field.define_value(
+        gc_context,
+        "StyleSheet",
+        style_sheet.into(),
+        Attribute::DONT_ENUM,
+    );
 
     let flash = ScriptObject::new(gc_context, Some(object_proto));
 
@@ -947,6 +992,18 @@ pub fn create_globals<'gc>(
     );
     globals.define_value(gc_context, "XMLNode", xmlnode.into(), Attribute::DONT_ENUM);
     globals.define_value(gc_context, "XML", xml.into(), Attribute::DONT_ENUM);
+    globals.define_value(
+        gc_context,
+        "XMLSocket",
+        xml_socket.into(),
+        Attribute::DONT_ENUM,
+    );
+    globals.define_value(
+        gc_context,
+        "PrintJob",
+        print_job.into(),
+        Attribute::DONT_ENUM,
+    );
     globals.define_value(gc_context, "String", string.into(), Attribute::DONT_ENUM);
     globals.define_value(gc_context, "Number", number.into(), Attribute::DONT_ENUM);
     globals.define_value(gc_context, "Boolean", boolean.into(), Attribute::DONT_ENUM);
@@ -1083,6 +1140,7 @@ pub fn create_globals<'gc>(
         netstream.into(),
         Attribute::DONT_ENUM,
     );
+    globals.define_value(gc_context, "Camera", camera.into(), Attribute::DONT_ENUM);
 
     define_properties_on(GLOBAL_DECLS, context, globals, function_proto);
 
@@ -1121,6 +1179,11 @@ pub fn create_globals<'gc>(
             context_menu_item_constructor: context_menu_item,
             bitmap_filter: bitmap_filter_proto,
             bitmap_filter_constructor: bitmap_filter,
+            blur_filter_constructor: blur_filter,
+            bevel_filter_constructor: bevel_filter,
+            glow_filter_constructor: glow_filter,
+            drop_shadow_filter_constructor: drop_shadow_filter,
+            color_matrix_filter_constructor: color_matrix_filter,
             displacement_map_filter: displacement_map_filter_proto,
             displacement_map_filter_constructor: displacement_map_filter,
             convolution_filter: convolution_filter_proto,
@@ -1154,6 +1217,15 @@ const AVM_MAX_DEPTH: i32 = 2_130_706_428;
 /// What is the derivation of this number...?
 const AVM_MAX_REMOVE_DEPTH: i32 = 2_130_706_416;
 
+/// Whether a (biased) depth is one that ActionScript is allowed to place
+/// clips at, e.g. via `attachMovie` or `swapDepths`.
+///
+/// Negative depths are reserved for clips that are pending removal, and
+/// depths above `AVM_MAX_DEPTH` are never used.
+pub fn is_valid_avm_depth(depth: Depth) -> bool {
+    (0..=AVM_MAX_DEPTH).contains(&depth)
+}
+
 fn get_depth<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
@@ -1344,7 +1344,8 @@ pub fn load_bitmap<'gc>(
 
     let library = &*activation.context.library;
 
-    let movie = activation.target_clip_or_root().movie();
+    // The linkage name is looked up in the library of the movie that the calling code belongs to.
+    let movie = activation.base_clip().movie();
 
     let character = library
         .library_for_movie(movie)
@@ -1359,7 +1360,12 @@ pub fn load_bitmap<'gc>(
         let width = bitmap.width() as u32;
         let height = bitmap.height() as u32;
 
-        let pixels: Vec<_> = bitmap.bitmap_data().read().pixels().to_vec();
+        // Bitmaps without an alpha channel, such as JPEGs, create opaque `BitmapData`s.
+        let source = bitmap.bitmap_data();
+        let source = source.read();
+        let transparency = source.transparency();
+        let pixels: Vec<_> = source.pixels().to_vec();
+        drop(source);
         let (sync, _) = new_bitmap_data
             .as_bitmap_data_object()
             .unwrap()
@@ -1367,7 +1373,7 @@ pub fn load_bitmap<'gc>(
             .overwrite_cpu_pixels_from_gpu(activation.context.gc_context);
 
         sync.write(activation.context.gc_context)
-            .set_pixels(width, height, true, pixels);
+            .set_pixels(width, height, transparency, pixels);
 
         return Ok(new_bitmap_data.into());
     }
@@ -2,10 +2,11 @@
 
 use crate::avm1::activation::Activation;
 use crate::avm1::error::Error;
+use crate::avm1::globals::movie_clip::{new_rectangle, object_to_rectangle};
 use crate::avm1::property_decl::{define_properties_on, Declaration};
-use crate::avm1::{globals, Object, ScriptObject, TObject, Value};
+use crate::avm1::{self, globals, Object, ScriptObject, TObject, Value};
 use crate::context::GcContext;
-use crate::display_object::{Avm1Button, TDisplayObject};
+use crate::display_object::{Avm1Button, ButtonTracking, TDisplayObject};
 use crate::string::AvmString;
 use std::str::FromStr;
 use swf::BlendMode;
@@ -41,7 +42,10 @@ const PROTO_DECLS: &[Declaration] = declare_properties! {
     "enabled" => property(button_getter!(enabled), button_setter!(set_enabled));
     "getDepth" => method(globals::get_depth; DONT_ENUM | DONT_DELETE | READ_ONLY | VERSION_6);
     "useHandCursor" => property(button_getter!(use_hand_cursor), button_setter!(set_use_hand_cursor));
+    "trackAsMenu" => property(button_getter!(track_as_menu), button_setter!(set_track_as_menu); DONT_DELETE | DONT_ENUM);
     "blendMode" => property(button_getter!(blend_mode), button_setter!(set_blend_mode); DONT_DELETE | DONT_ENUM);
+    "scale9Grid" => property(button_getter!(scale_9_grid), button_setter!(set_scale_9_grid); DONT_DELETE | DONT_ENUM | VERSION_8);
+    "filters" => property(button_getter!(filters), button_setter!(set_filters); DONT_DELETE | DONT_ENUM | VERSION_8);
 };
 
 pub fn create_proto<'gc>(
@@ -102,6 +106,27 @@ fn set_use_hand_cursor<'gc>(
     Ok(())
 }
 
+fn track_as_menu<'gc>(
+    this: Avm1Button<'gc>,
+    _activation: &mut Activation<'_, 'gc>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok((this.button_tracking() == ButtonTracking::Menu).into())
+}
+
+fn set_track_as_menu<'gc>(
+    this: Avm1Button<'gc>,
+    activation: &mut Activation<'_, 'gc>,
+    value: Value<'gc>,
+) -> Result<(), Error<'gc>> {
+    let tracking = if value.as_bool(activation.swf_version()) {
+        ButtonTracking::Menu
+    } else {
+        ButtonTracking::Push
+    };
+    this.set_button_tracking(&mut activation.context, tracking);
+    Ok(())
+}
+
 fn blend_mode<'gc>(
     this: Avm1Button<'gc>,
     activation: &mut Activation<'_, 'gc>,
@@ -125,3 +150,45 @@ fn set_blend_mode<'gc>(
     }
     Ok(())
 }
+
+fn scale_9_grid<'gc>(
+    this: Avm1Button<'gc>,
+    activation: &mut Activation<'_, 'gc>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let rect = this.scaling_grid();
+    if rect.is_valid() {
+        new_rectangle(activation, rect)
+    } else {
+        Ok(Value::Undefined)
+    }
+}
+
+fn set_scale_9_grid<'gc>(
+    this: Avm1Button<'gc>,
+    activation: &mut Activation<'_, 'gc>,
+    value: Value<'gc>,
+) -> Result<(), Error<'gc>> {
+    if let Value::Object(object) = value {
+        if let Some(rectangle) = object_to_rectangle(activation, object)? {
+            this.set_scaling_grid(activation.context.gc_context, rectangle);
+        }
+    } else {
+        this.set_scaling_grid(activation.context.gc_context, Default::default());
+    }
+    Ok(())
+}
+
+fn filters<'gc>(
+    this: Avm1Button<'gc>,
+    activation: &mut Activation<'_, 'gc>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    avm1::filters::get_filters(activation, this.into())
+}
+
+fn set_filters<'gc>(
+    this: Avm1Button<'gc>,
+    activation: &mut Activation<'_, 'gc>,
+    value: Value<'gc>,
+) -> Result<(), Error<'gc>> {
+    avm1::filters::set_filters(activation, this.into(), value)
+}
@@ -0,0 +1,210 @@
+//! Camera object
+
+use crate::avm1::function::{Executable, FunctionObject};
+use crate::avm1::object::NativeObject;
+use crate::avm1::property_decl::{define_properties_on, Declaration};
+use crate::avm1::{Activation, ArrayObject, Error, Object, ScriptObject, TObject, Value};
+use crate::backend::video_capture::CaptureMode;
+use crate::camera::Camera;
+use crate::context::GcContext;
+use crate::streams::StreamManager;
+use crate::string::AvmString;
+
+macro_rules! camera_method {
+    ($name:ident) => {
+        |activation, this, args| {
+            if let NativeObject::Camera(camera) = this.native() {
+                return $name(activation, camera, args);
+            }
+            Ok(Value::Undefined)
+        }
+    };
+}
+
+const PROTO_DECLS: &[Declaration] = declare_properties! {
+    "setMode" => method(camera_method!(set_mode); DONT_ENUM | DONT_DELETE);
+    "name" => property(camera_method!(name); DONT_ENUM | DONT_DELETE | READ_ONLY);
+    "index" => property(camera_method!(index); DONT_ENUM | DONT_DELETE | READ_ONLY);
+    "width" => property(camera_method!(width); DONT_ENUM | DONT_DELETE | READ_ONLY);
+    "height" => property(camera_method!(height); DONT_ENUM | DONT_DELETE | READ_ONLY);
+    "fps" => property(camera_method!(fps); DONT_ENUM | DONT_DELETE | READ_ONLY);
+    "currentFps" => property(camera_method!(current_fps); DONT_ENUM | DONT_DELETE | READ_ONLY);
+    "muted" => property(camera_method!(muted); DONT_ENUM | DONT_DELETE | READ_ONLY);
+};
+
+const OBJECT_DECLS: &[Declaration] = declare_properties! {
+    "get" => method(get; DONT_ENUM | DONT_DELETE);
+    "names" => property(names; DONT_ENUM | DONT_DELETE | READ_ONLY);
+};
+
+/// Implements `Camera.get`, which returns the same object for each call with a camera.
+fn get<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let index = match args.get(0) {
+        None | Some(Value::Undefined) | Some(Value::Null) => None,
+        Some(index) => {
+            let index = index.coerce_to_f64(activation)?;
+            if index < 0.0 || index.is_nan() {
+                return Ok(Value::Null);
+            }
+            Some(index as usize)
+        }
+    };
+
+    let Some(camera) = StreamManager::camera(&mut activation.context, index) else {
+        return Ok(Value::Null);
+    };
+    if let Some(object) = camera.avm1_object() {
+        return Ok(object.into());
+    }
+
+    let proto = this
+        .get("prototype", activation)?
+        .coerce_to_object(activation);
+    let object = ScriptObject::new(activation.context.gc_context, Some(proto));
+    object.set_native(activation.context.gc_context, NativeObject::Camera(camera));
+    camera.set_avm1_object(activation.context.gc_context, object.into());
+    Ok(object.into())
+}
+
+fn names<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let gc_context = activation.context.gc_context;
+    Ok(ArrayObject::new(
+        gc_context,
+        activation.context.avm1.prototypes().array,
+        activation
+            .context
+            .video_capture
+            .devices()
+            .into_iter()
+            .map(|name| AvmString::new_utf8(gc_context, name).into()),
+    )
+    .into())
+}
+
+fn set_mode<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    camera: Camera<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let mode = camera.mode();
+    let width = match args.get(0) {
+        Some(width) => width.coerce_to_i32(activation)?.max(1) as u32,
+        None => mode.width,
+    };
+    let height = match args.get(1) {
+        Some(height) => height.coerce_to_i32(activation)?.max(1) as u32,
+        None => mode.height,
+    };
+    let fps = match args.get(2) {
+        Some(fps) => fps.coerce_to_f64(activation)?,
+        None => mode.fps,
+    };
+    let mode = CaptureMode {
+        width,
+        height,
+        fps: if fps > 0.0 { fps } else { mode.fps },
+    };
+    camera.set_mode(&mut activation.context, mode);
+    Ok(Value::Undefined)
+}
+
+fn name<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    camera: Camera<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(AvmString::new_utf8(activation.context.gc_context, camera.name()).into())
+}
+
+fn index<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    camera: Camera<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(camera.index().into())
+}
+
+fn width<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    camera: Camera<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(camera.mode().width.into())
+}
+
+fn height<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    camera: Camera<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(camera.mode().height.into())
+}
+
+fn fps<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    camera: Camera<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(camera.mode().fps.into())
+}
+
+fn current_fps<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    camera: Camera<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(camera.current_fps().into())
+}
+
+fn muted<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    _camera: Camera<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    // Movies are always allowed to use the camera.
+    Ok(false.into())
+}
+
+/// `Camera` can't be constructed, only returned by `Camera.get`.
+pub fn constructor<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(Value::Undefined)
+}
+
+pub fn create_proto<'gc>(
+    context: &mut GcContext<'_, 'gc>,
+    proto: Object<'gc>,
+    fn_proto: Object<'gc>,
+) -> Object<'gc> {
+    let object = ScriptObject::new(context.gc_context, Some(proto));
+    define_properties_on(PROTO_DECLS, context, object, fn_proto);
+    object.into()
+}
+
+pub fn create_class<'gc>(
+    context: &mut GcContext<'_, 'gc>,
+    camera_proto: Object<'gc>,
+    fn_proto: Object<'gc>,
+) -> Object<'gc> {
+    let camera = FunctionObject::constructor(
+        context.gc_context,
+        Executable::Native(constructor),
+        constructor_to_fn!(constructor),
+        fn_proto,
+        camera_proto,
+    );
+    let object = camera.raw_script_object();
+    define_properties_on(OBJECT_DECLS, context, object, fn_proto);
+    camera
+}
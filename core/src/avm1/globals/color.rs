@@ -1,10 +1,8 @@
 //! Color object
-//!
-//! TODO: This should change when `ColorTransform` changes to match Flash's representation
-//! (See GitHub #193)
 
 use crate::avm1::activation::Activation;
 use crate::avm1::error::Error;
+use crate::avm1::globals::color_transform::multiplier_to_fixed8;
 use crate::avm1::property::Attribute;
 use crate::avm1::property_decl::{define_properties_on, Declaration};
 use crate::avm1::{Object, ScriptObject, TObject, Value};
@@ -166,9 +164,7 @@ fn set_transform<'gc>(
             let n = transform
                 .get(property, activation)?
                 .coerce_to_f64(activation)?;
-            *out = Fixed8::from_bits(crate::ecma_conversions::f64_to_wrapping_i16(
-                n * 256.0 / 100.0,
-            ));
+            *out = multiplier_to_fixed8(n / 100.0);
         }
         Ok(())
     }
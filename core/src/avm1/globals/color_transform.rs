@@ -4,6 +4,7 @@ use crate::avm1::object::NativeObject;
 use crate::avm1::property_decl::{define_properties_on, Declaration};
 use crate::avm1::{Activation, Error, Object, ScriptObject, TObject, Value};
 use crate::context::GcContext;
+use crate::ecma_conversions::f64_to_wrapping_i16;
 use crate::string::AvmString;
 use gc_arena::{Collect, GcCell};
 use swf::{ColorTransform, Fixed8};
@@ -65,17 +66,24 @@ impl<'gc> ColorTransformObject {
     }
 }
 
+/// Converts a color multiplier to the 8.8 fixed-point number that Flash stores.
+///
+/// Values out of range wrap around, as they do for the offsets.
+pub fn multiplier_to_fixed8(multiplier: f64) -> Fixed8 {
+    Fixed8::from_bits(f64_to_wrapping_i16(multiplier * 256.0))
+}
+
 impl From<ColorTransformObject> for ColorTransform {
     fn from(object: ColorTransformObject) -> Self {
         Self {
-            r_multiply: Fixed8::from_f64(object.red_multiplier),
-            g_multiply: Fixed8::from_f64(object.green_multiplier),
-            b_multiply: Fixed8::from_f64(object.blue_multiplier),
-            a_multiply: Fixed8::from_f64(object.alpha_multiplier),
-            r_add: object.red_offset as i16,
-            g_add: object.green_offset as i16,
-            b_add: object.blue_offset as i16,
-            a_add: object.alpha_offset as i16,
+            r_multiply: multiplier_to_fixed8(object.red_multiplier),
+            g_multiply: multiplier_to_fixed8(object.green_multiplier),
+            b_multiply: multiplier_to_fixed8(object.blue_multiplier),
+            a_multiply: multiplier_to_fixed8(object.alpha_multiplier),
+            r_add: f64_to_wrapping_i16(object.red_offset),
+            g_add: f64_to_wrapping_i16(object.green_offset),
+            b_add: f64_to_wrapping_i16(object.blue_offset),
+            a_add: f64_to_wrapping_i16(object.alpha_offset),
         }
     }
 }
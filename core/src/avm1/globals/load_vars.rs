@@ -267,6 +267,7 @@ fn spawn_load_var_fetch<'gc>(
         activation.context.player.clone(),
         loader_object,
         request,
+        activation.base_clip().movie(),
     );
     activation.context.navigator.spawn_future(future);
 
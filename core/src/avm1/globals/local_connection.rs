@@ -1,39 +1,259 @@
 //! LocalConnection class
 
-use crate::avm1::activation::Activation;
+use crate::avm1::activation::{Activation, ActivationIdentifier};
 use crate::avm1::error::Error;
+use crate::avm1::function::ExecutionReason;
+use crate::avm1::globals::shared_object::{deserialize_value, serialize_value};
 use crate::avm1::property_decl::{define_properties_on, Declaration};
-use crate::avm1::{Object, ScriptObject, Value};
-use crate::context::GcContext;
+use crate::avm1::{Object, ScriptObject, TObject, Value};
+use crate::backend::local_connection::LocalConnectionMessage;
+use crate::context::{ActionType, GcContext, UpdateContext};
 use crate::display_object::TDisplayObject;
 use crate::string::AvmString;
+use flash_lso::types::{AMFVersion, Value as AmfValue};
 
 const PROTO_DECLS: &[Declaration] = declare_properties! {
     "domain" => method(domain; DONT_DELETE | READ_ONLY);
+    "connect" => method(connect; DONT_DELETE | DONT_ENUM);
+    "send" => method(send; DONT_DELETE | DONT_ENUM);
+    "close" => method(close; DONT_DELETE | DONT_ENUM);
 };
 
+/// Methods that can't be called through a connection.
+const RESERVED_METHODS: &[&str] = &[
+    "send",
+    "connect",
+    "close",
+    "allowDomain",
+    "allowInsecureDomain",
+    "domain",
+];
+
+/// The domain of the movie running the activation, or `None` if its URL is invalid.
+fn movie_domain(activation: &Activation<'_, '_>) -> Option<String> {
+    let movie = activation.base_clip().movie();
+    let url = url::Url::parse(movie.url()).ok()?;
+    if url.scheme() == "file" {
+        return Some("localhost".to_owned());
+    }
+    Some(url.domain().unwrap_or("localhost").to_owned())
+}
+
+/// The full name of a connection, which is what connections are matched by.
+///
+/// Names starting with `_` are shared by every domain, and names already
+/// prefixed with a domain (`domain:name`) are kept. Other names are prefixed
+/// with the domain of the movie using them.
+fn full_connection_name(domain: &str, name: &str) -> String {
+    let name = if name.starts_with('_') || name.contains(':') {
+        name.to_owned()
+    } else {
+        format!("{domain}:{name}")
+    };
+    name.to_lowercase()
+}
+
 pub fn domain<'gc>(
     activation: &mut Activation<'_, 'gc>,
     _this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    let movie = activation.base_clip().movie();
-
-    let domain = if let Ok(url) = url::Url::parse(movie.url()) {
-        if url.scheme() == "file" {
-            "localhost".into()
-        } else if let Some(domain) = url.domain() {
-            AvmString::new_utf8(activation.context.gc_context, domain)
-        } else {
-            // no domain?
-            "localhost".into()
-        }
-    } else {
+    let Some(domain) = movie_domain(activation) else {
         tracing::error!("LocalConnection::domain: Unable to parse movie URL");
         return Ok(Value::Null);
     };
 
-    Ok(Value::String(domain))
+    Ok(AvmString::new_utf8(activation.context.gc_context, domain).into())
+}
+
+pub fn connect<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let name = match args.get(0) {
+        Some(Value::String(name)) if !name.is_empty() => name.to_utf8_lossy().into_owned(),
+        _ => return Ok(false.into()),
+    };
+    // Movies can only listen to the connections of their own domain.
+    if name.contains(':') {
+        return Ok(false.into());
+    }
+    if activation
+        .context
+        .avm1
+        .local_connection_name(this)
+        .is_some()
+    {
+        // The object already listens to a connection.
+        return Ok(false.into());
+    }
+    let Some(domain) = movie_domain(activation) else {
+        return Ok(false.into());
+    };
+
+    let connection_name = full_connection_name(&domain, &name);
+    if !activation
+        .context
+        .local_connections
+        .connect(&connection_name)
+    {
+        return Ok(false.into());
+    }
+    activation
+        .context
+        .avm1
+        .register_local_connection(connection_name, this, domain);
+    Ok(true.into())
+}
+
+pub fn send<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let (name, method_name) = match args {
+        [Value::String(name), Value::String(method_name), ..]
+            if !name.is_empty() && !method_name.is_empty() =>
+        {
+            (name.to_utf8_lossy(), method_name.to_utf8_lossy())
+        }
+        _ => return Ok(false.into()),
+    };
+    if RESERVED_METHODS.contains(&&*method_name) {
+        return Ok(false.into());
+    }
+    let Some(domain) = movie_domain(activation) else {
+        return Ok(false.into());
+    };
+
+    let arguments = args[2..]
+        .iter()
+        .map(|argument| {
+            serialize_value(activation, *argument, AMFVersion::AMF0).unwrap_or(AmfValue::Undefined)
+        })
+        .collect();
+    let message = LocalConnectionMessage {
+        connection_name: full_connection_name(&domain, &name),
+        sender_domain: domain,
+        method_name: method_name.into_owned(),
+        arguments,
+    };
+    let delivered = activation.context.local_connections.send(message);
+
+    // Whether the message was delivered is reported asynchronously.
+    let info = ScriptObject::new(
+        activation.context.gc_context,
+        Some(activation.context.avm1.prototypes().object),
+    );
+    let level = if delivered { "status" } else { "error" };
+    info.set("level", level.into(), activation)?;
+    activation.context.action_queue.queue_action(
+        activation.base_clip(),
+        ActionType::Method {
+            object: this,
+            name: "onStatus",
+            args: vec![info.into()],
+        },
+        false,
+    );
+    Ok(true.into())
+}
+
+pub fn close<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(connection_name) = activation
+        .context
+        .avm1
+        .local_connection_name(this)
+        .map(str::to_owned)
+    {
+        activation.context.local_connections.close(&connection_name);
+        activation
+            .context
+            .avm1
+            .unregister_local_connection(&connection_name);
+    }
+    Ok(Value::Undefined)
+}
+
+/// Calls the methods that the messages sent to the connections of the
+/// player ask for.
+pub fn receive_messages(context: &mut UpdateContext<'_, '_>) {
+    let messages = context.local_connections.poll();
+    if messages.is_empty() {
+        return;
+    }
+
+    let Some(mut activation) = Activation::try_from_stub(
+        context.reborrow(),
+        ActivationIdentifier::root("[LocalConnection]"),
+    ) else {
+        return;
+    };
+
+    for message in messages {
+        let Some((this, domain)) = activation
+            .context
+            .avm1
+            .local_connection(&message.connection_name)
+            .map(|(this, domain)| (this, domain.to_owned()))
+        else {
+            continue;
+        };
+        if let Err(e) = receive_message(&mut activation, this, &domain, message) {
+            tracing::error!("Error receiving a LocalConnection message: {:?}", e);
+        }
+    }
+}
+
+fn receive_message<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    domain: &str,
+    message: LocalConnectionMessage,
+) -> Result<(), Error<'gc>> {
+    // Messages from other domains are only received if `allowDomain` accepts them.
+    if domain != message.sender_domain {
+        let sender_domain =
+            AvmString::new_utf8(activation.context.gc_context, &message.sender_domain);
+        let allowed = match this.get("allowDomain", activation)? {
+            Value::Object(allow_domain) if allow_domain.as_executable().is_some() => this
+                .call_method(
+                    "allowDomain".into(),
+                    &[sender_domain.into()],
+                    activation,
+                    ExecutionReason::Special,
+                )?
+                .as_bool(activation.swf_version()),
+            _ => false,
+        };
+        if !allowed {
+            tracing::warn!(
+                "LocalConnection: {} didn't allow a message from {}",
+                message.connection_name,
+                message.sender_domain
+            );
+            return Ok(());
+        }
+    }
+
+    let arguments: Vec<_> = message
+        .arguments
+        .iter()
+        .map(|argument| deserialize_value(activation, argument))
+        .collect();
+    let method_name = AvmString::new_utf8(activation.context.gc_context, message.method_name);
+    this.call_method(
+        method_name,
+        &arguments,
+        activation,
+        ExecutionReason::Special,
+    )?;
+    Ok(())
 }
 
 pub fn constructor<'gc>(
@@ -3,7 +3,7 @@
 use crate::avm1::activation::Activation;
 use crate::avm1::error::Error;
 use crate::avm1::globals::matrix::gradient_object_to_matrix;
-use crate::avm1::globals::{self, AVM_DEPTH_BIAS, AVM_MAX_DEPTH};
+use crate::avm1::globals::{self, is_valid_avm_depth, AVM_DEPTH_BIAS};
 use crate::avm1::property_decl::{define_properties_on, Declaration};
 use crate::avm1::{self, Object, ScriptObject, TObject, Value};
 use crate::avm_error;
@@ -11,7 +11,8 @@ use crate::avm_warn;
 use crate::backend::navigator::NavigationMethod;
 use crate::context::GcContext;
 use crate::display_object::{
-    Bitmap, DisplayObject, EditText, MovieClip, TDisplayObject, TDisplayObjectContainer,
+    Bitmap, DisplayObject, EditText, MovieClip, PixelSnapping, TDisplayObject,
+    TDisplayObjectContainer, TInteractiveObject,
 };
 use crate::ecma_conversions::f64_to_wrapping_i32;
 use crate::prelude::*;
@@ -110,8 +111,11 @@ const PROTO_DECLS: &[Declaration] = declare_properties! {
     "enabled" => property(mc_getter!(enabled), mc_setter!(set_enabled); DONT_DELETE | DONT_ENUM);
     "_lockroot" => property(mc_getter!(lock_root), mc_setter!(set_lock_root); DONT_DELETE | DONT_ENUM);
     "useHandCursor" => property(mc_getter!(use_hand_cursor), mc_setter!(set_use_hand_cursor); DONT_DELETE | DONT_ENUM);
+    "trackAsMenu" => property(mc_getter!(track_as_menu), mc_setter!(set_track_as_menu); DONT_DELETE | DONT_ENUM);
     "blendMode" => property(mc_getter!(blend_mode), mc_setter!(set_blend_mode); DONT_DELETE | DONT_ENUM);
     "scrollRect" => property(mc_getter!(scroll_rect), mc_setter!(set_scroll_rect); DONT_DELETE | DONT_ENUM | VERSION_8);
+    "scale9Grid" => property(mc_getter!(scale_9_grid), mc_setter!(set_scale_9_grid); DONT_DELETE | DONT_ENUM | VERSION_8);
+    "filters" => property(mc_getter!(filters), mc_setter!(set_filters); DONT_DELETE | DONT_ENUM | VERSION_8);
     // NOTE: `focusEnabled` is not a built-in property of MovieClip.
 };
 
@@ -124,7 +128,7 @@ pub fn constructor<'gc>(
     Ok(this.into())
 }
 
-fn new_rectangle<'gc>(
+pub(crate) fn new_rectangle<'gc>(
     activation: &mut Activation<'_, 'gc>,
     rectangle: Rectangle<Twips>,
 ) -> Result<Value<'gc>, Error<'gc>> {
@@ -137,7 +141,7 @@ fn new_rectangle<'gc>(
     proto.construct(activation, args)
 }
 
-fn object_to_rectangle<'gc>(
+pub(crate) fn object_to_rectangle<'gc>(
     activation: &mut Activation<'_, 'gc>,
     object: Object<'gc>,
 ) -> Result<Option<Rectangle<Twips>>, Error<'gc>> {
@@ -185,6 +189,48 @@ fn set_scroll_rect<'gc>(
     Ok(())
 }
 
+fn scale_9_grid<'gc>(
+    this: MovieClip<'gc>,
+    activation: &mut Activation<'_, 'gc>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let rect = this.scaling_grid();
+    if rect.is_valid() {
+        new_rectangle(activation, rect)
+    } else {
+        Ok(Value::Undefined)
+    }
+}
+
+fn set_scale_9_grid<'gc>(
+    this: MovieClip<'gc>,
+    activation: &mut Activation<'_, 'gc>,
+    value: Value<'gc>,
+) -> Result<(), Error<'gc>> {
+    if let Value::Object(object) = value {
+        if let Some(rectangle) = object_to_rectangle(activation, object)? {
+            this.set_scaling_grid(activation.context.gc_context, rectangle);
+        }
+    } else {
+        this.set_scaling_grid(activation.context.gc_context, Default::default());
+    };
+    Ok(())
+}
+
+fn filters<'gc>(
+    this: MovieClip<'gc>,
+    activation: &mut Activation<'_, 'gc>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    avm1::filters::get_filters(activation, this.into())
+}
+
+fn set_filters<'gc>(
+    this: MovieClip<'gc>,
+    activation: &mut Activation<'_, 'gc>,
+    value: Value<'gc>,
+) -> Result<(), Error<'gc>> {
+    avm1::filters::set_filters(activation, this.into(), value)
+}
+
 #[allow(clippy::comparison_chain)]
 pub fn hit_test<'gc>(
     movie_clip: MovieClip<'gc>,
@@ -254,11 +300,12 @@ fn attach_bitmap<'gc>(
                     .coerce_to_i32(activation)?
                     .wrapping_add(AVM_DEPTH_BIAS);
 
-                // TODO: Implement pixel snapping
-                let _pixel_snapping = args
+                let pixel_snapping = args
                     .get(2)
                     .unwrap_or(&Value::Undefined)
-                    .as_bool(activation.swf_version());
+                    .coerce_to_string(activation)?
+                    .parse()
+                    .unwrap_or(PixelSnapping::Auto);
 
                 let smoothing = args
                     .get(3)
@@ -272,6 +319,7 @@ fn attach_bitmap<'gc>(
                     bitmap_data,
                     smoothing,
                 );
+                display_object.set_pixel_snapping(activation.context.gc_context, pixel_snapping);
                 movie_clip.replace_at_depth(&mut activation.context, display_object.into(), depth);
                 display_object.post_instantiation(
                     &mut activation.context,
@@ -649,9 +697,7 @@ fn attach_movie<'gc>(
     };
     let init_object = args.get(3);
 
-    // TODO: What is the derivation of this max value? It shows up a few times in the AVM...
-    // 2^31 - 16777220
-    if depth < 0 || depth > AVM_MAX_DEPTH {
+    if !is_valid_avm_depth(depth) {
         return Ok(Value::Undefined);
     }
 
@@ -705,6 +751,10 @@ fn create_empty_movie_clip<'gc>(
         }
     };
 
+    if !is_valid_avm_depth(depth) {
+        return Ok(Value::Undefined);
+    }
+
     // Create empty movie clip.
     let swf_movie = movie_clip.movie();
     let new_clip = MovieClip::new(swf_movie, activation.context.gc_context);
@@ -808,21 +858,32 @@ pub fn duplicate_movie_clip_with_bias<'gc>(
         return Ok(Value::Undefined);
     };
 
-    // TODO: What is the derivation of this max value? It shows up a few times in the AVM...
-    // 2^31 - 16777220
-    if depth < 0 || depth > AVM_MAX_DEPTH {
+    if !is_valid_avm_depth(depth) {
         return Ok(Value::Undefined);
     }
 
-    let movie = parent.movie();
+    // The duplicate comes from the same movie as the original, which isn't
+    // necessarily the parent's movie (e.g. the root of a loaded movie).
+    let movie = movie_clip.movie();
     let new_clip = if movie_clip.id() != 0 {
         // Clip from SWF; instantiate a new copy.
-        let library = activation.context.library.library_for_movie(movie).unwrap();
-        library
-            .instantiate_by_id(movie_clip.id(), activation.context.gc_context)
-            .unwrap()
-            .as_movie_clip()
-            .unwrap()
+        let gc_context = activation.context.gc_context;
+        let new_clip = activation
+            .context
+            .library
+            .library_for_movie(movie)
+            .and_then(|l| l.instantiate_by_id(movie_clip.id(), gc_context).ok())
+            .and_then(|o| o.as_movie_clip());
+        if let Some(new_clip) = new_clip {
+            new_clip
+        } else {
+            avm_warn!(
+                activation,
+                "MovieClip.duplicateMovieClip: Unable to instantiate character {}",
+                movie_clip.id()
+            );
+            return Ok(Value::Undefined);
+        }
     } else {
         // Dynamically created clip; create a new empty movie clip.
         MovieClip::new(movie, activation.context.gc_context)
@@ -842,12 +903,18 @@ pub fn duplicate_movie_clip_with_bias<'gc>(
     let clip_actions = movie_clip.clip_actions().to_vec();
     new_clip.set_clip_event_handlers(activation.context.gc_context, clip_actions);
 
+    // Content drawn with the drawing API is duplicated along with the clip.
     *new_clip.drawing(activation.context.gc_context) =
         movie_clip.drawing(activation.context.gc_context).clone();
     // TODO: Any other properties we should copy...?
     // Definitely not ScriptObject properties.
 
-    let init_object = init_object.map(|v| v.coerce_to_object(activation));
+    // As with `attachMovie`, only objects are used as init objects.
+    let init_object = if let Some(Value::Object(init_object)) = init_object {
+        Some(*init_object)
+    } else {
+        None
+    };
     new_clip.post_instantiation(
         &mut activation.context,
         init_object,
@@ -915,6 +982,9 @@ fn get_next_highest_depth<'gc>(
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     if activation.swf_version() >= 7 {
+        // Timeline children live at negative AS depths, so they never raise
+        // the result above 0. Clips at very high depths (such as those
+        // reserved by the v2 component `DepthManager`) do count, as in Flash.
         let depth = movie_clip
             .highest_depth()
             .wrapping_sub(AVM_DEPTH_BIAS - 1)
@@ -1136,7 +1206,7 @@ fn swap_depths<'gc>(
     };
 
     if let Some(depth) = depth {
-        if depth < 0 || depth > AVM_MAX_DEPTH {
+        if !is_valid_avm_depth(depth) {
             // Depth out of range; no action.
             return Ok(Value::Undefined);
         }
@@ -1337,6 +1407,7 @@ fn load_movie<'gc>(
         activation.context.player.clone(),
         DisplayObject::MovieClip(target),
         request,
+        activation.base_clip().movie(),
         None,
         None,
         None,
@@ -1361,6 +1432,7 @@ fn load_variables<'gc>(
         activation.context.player.clone(),
         target,
         request,
+        activation.base_clip().movie(),
     );
     activation.context.navigator.spawn_future(future);
 
@@ -1368,12 +1440,11 @@ fn load_variables<'gc>(
 }
 
 fn unload_movie<'gc>(
-    mut target: MovieClip<'gc>,
+    target: MovieClip<'gc>,
     activation: &mut Activation<'_, 'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    target.avm1_unload(&mut activation.context);
-    target.replace_with_movie(&mut activation.context, None, None);
+    target.avm1_unload_movie(&mut activation.context);
 
     Ok(Value::Undefined)
 }
@@ -1394,7 +1465,7 @@ fn set_transform<'gc>(
 ) -> Result<(), Error<'gc>> {
     if let Value::Object(object) = value {
         if let Some(transform) = object.as_transform_object() {
-            if let Some(clip) = transform.clip() {
+            if let Some(clip) = transform.clip(activation) {
                 let matrix = *clip.base().matrix();
                 this.set_matrix(activation.context.gc_context, matrix);
 
@@ -1465,6 +1536,23 @@ fn set_use_hand_cursor<'gc>(
     Ok(())
 }
 
+fn track_as_menu<'gc>(
+    this: MovieClip<'gc>,
+    _activation: &mut Activation<'_, 'gc>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(this.tracks_as_menu().into())
+}
+
+fn set_track_as_menu<'gc>(
+    this: MovieClip<'gc>,
+    activation: &mut Activation<'_, 'gc>,
+    value: Value<'gc>,
+) -> Result<(), Error<'gc>> {
+    let track_as_menu = value.as_bool(activation.swf_version());
+    this.set_tracks_as_menu(activation.context.gc_context, track_as_menu);
+    Ok(())
+}
+
 fn blend_mode<'gc>(
     this: MovieClip<'gc>,
     activation: &mut Activation<'_, 'gc>,
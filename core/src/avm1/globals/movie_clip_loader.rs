@@ -64,6 +64,7 @@ fn load_clip<'gc>(
                     activation.context.player.clone(),
                     target,
                     Request::get(url.to_utf8_lossy().into_owned()),
+                    activation.base_clip().movie(),
                     None,
                     Some(MovieLoaderEventHandler::Avm1Broadcast(this)),
                     None,
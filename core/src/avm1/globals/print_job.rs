@@ -0,0 +1,106 @@
+//! PrintJob class
+
+use crate::avm1::activation::Activation;
+use crate::avm1::error::Error;
+use crate::avm1::property_decl::{define_properties_on, Declaration};
+use crate::avm1::{Object, ScriptObject, TObject, Value};
+use crate::avm1_stub;
+use crate::context::GcContext;
+use crate::printing;
+use swf::{Rectangle, Twips};
+
+const PROTO_DECLS: &[Declaration] = declare_properties! {
+    "start" => method(start; DONT_DELETE | DONT_ENUM);
+    "addPage" => method(add_page; DONT_DELETE | DONT_ENUM);
+    "send" => method(send; DONT_DELETE | DONT_ENUM);
+};
+
+pub fn start<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(format) = printing::start_job(&mut activation.context) else {
+        return Ok(false.into());
+    };
+    this.set("paperWidth", format.paper_width.into(), activation)?;
+    this.set("paperHeight", format.paper_height.into(), activation)?;
+    this.set("pageWidth", format.page_width.into(), activation)?;
+    this.set("pageHeight", format.page_height.into(), activation)?;
+    this.set(
+        "orientation",
+        format.orientation.as_str().into(),
+        activation,
+    )?;
+    Ok(true.into())
+}
+
+pub fn add_page<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    // The target is a level number or a movie clip.
+    let target = match args.get(0).copied().unwrap_or(Value::Undefined) {
+        Value::Number(level) => Some(activation.resolve_level(level as i32)),
+        target => {
+            let start = activation.target_clip_or_root();
+            activation.resolve_target_display_object(start, target, false)?
+        }
+    };
+    let Some(target) = target else {
+        return Ok(false.into());
+    };
+
+    // The area to print is given in pixels, in the coordinates of the target.
+    let area = match args.get(1) {
+        Some(Value::Object(area)) => {
+            let mut edge = |name: &'static str| -> Result<Twips, Error<'gc>> {
+                let value = area.get(name, activation)?.coerce_to_f64(activation)?;
+                Ok(Twips::from_pixels(value))
+            };
+            Some(Rectangle {
+                x_min: edge("xMin")?,
+                x_max: edge("xMax")?,
+                y_min: edge("yMin")?,
+                y_max: edge("yMax")?,
+            })
+        }
+        _ => None,
+    };
+
+    // The current frame of the target is always printed.
+    if matches!(args.get(3), Some(Value::Number(_))) {
+        avm1_stub!(activation, "PrintJob", "addPage", "with frameNum");
+    }
+
+    let added = printing::add_page(&mut activation.context, target, area);
+    Ok(added.into())
+}
+
+pub fn send<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    printing::send_job(&mut activation.context);
+    Ok(Value::Undefined)
+}
+
+pub fn constructor<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(this.into())
+}
+
+pub fn create_proto<'gc>(
+    context: &mut GcContext<'_, 'gc>,
+    proto: Object<'gc>,
+    fn_proto: Object<'gc>,
+) -> Object<'gc> {
+    let object = ScriptObject::new(context.gc_context, Some(proto));
+    define_properties_on(PROTO_DECLS, context, object, fn_proto);
+    object.into()
+}
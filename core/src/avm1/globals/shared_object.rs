@@ -1,19 +1,23 @@
-use crate::avm1::activation::Activation;
+use crate::avm1::activation::{Activation, ActivationIdentifier};
 use crate::avm1::error::Error;
-use crate::avm1::function::{Executable, FunctionObject};
-use crate::avm1::object::shared_object::SharedObject;
+use crate::avm1::function::{Executable, ExecutionReason, FunctionObject};
+use crate::avm1::object::shared_object::{RemoteState, SharedObject};
 use crate::avm1::object::NativeObject;
 use crate::avm1::property::Attribute;
 use crate::avm1::property_decl::{define_properties_on, Declaration};
-use crate::avm1::{Object, ScriptObject, TObject, Value};
+use crate::avm1::{ArrayObject, Object, ScriptObject, TObject, Value};
 use crate::avm1_stub;
-use crate::context::GcContext;
+use crate::backend::remote_shared_object::RemoteSharedObjectEvent;
+use crate::context::{ActionType, GcContext, UpdateContext};
 use crate::display_object::TDisplayObject;
+use crate::settings::DomainSettings;
 use crate::string::AvmString;
+use enumset::EnumSet;
 use flash_lso::types::Value as AmfValue;
-use flash_lso::types::{AMFVersion, Element, Lso};
+use flash_lso::types::{AMFVersion, Attribute as AmfAttribute, ClassDefinition, Element, Lso};
 
 use std::borrow::Cow;
+use std::collections::HashMap;
 
 const PROTO_DECLS: &[Declaration] = declare_properties! {
     "clear" => method(clear; DONT_ENUM | DONT_DELETE);
@@ -35,6 +39,7 @@ const OBJECT_DECLS: &[Declaration] = declare_properties! {
     "getMaxSize" => method(get_max_size);
     "addListener" => method(add_listener);
     "removeListener" => method(remove_listener);
+    "defaultObjectEncoding" => int(0);
 };
 
 pub fn delete_all<'gc>(
@@ -46,19 +51,38 @@ pub fn delete_all<'gc>(
     Ok(Value::Undefined)
 }
 
+/// The domain that the shared objects of the running movie are stored under,
+/// and whose storage quota they share.
+fn storage_domain(activation: &Activation<'_, '_>) -> String {
+    DomainSettings::domain_of(activation.base_clip().movie().url())
+}
+
+/// How many bytes the domain may store, or `None` for no limit.
+fn storage_quota(activation: &Activation<'_, '_>, domain: &str) -> Option<u64> {
+    DomainSettings::load(&*activation.context.storage, domain).storage_quota
+}
+
 pub fn get_disk_usage<'gc>(
     activation: &mut Activation<'_, 'gc>,
     _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm1_stub!(activation, "SharedObject", "getDiskUsage");
-    Ok(Value::Undefined)
+    let domain = match args.get(0) {
+        Some(Value::String(url)) => DomainSettings::domain_of(&url.to_utf8_lossy()),
+        _ => storage_domain(activation),
+    };
+    let usage = activation
+        .context
+        .storage
+        .get_disk_usage(&format!("{domain}/"));
+    Ok((usage as f64).into())
 }
 
 /// Serialize a Value to an AmfValue
-fn serialize_value<'gc>(
+pub(crate) fn serialize_value<'gc>(
     activation: &mut Activation<'_, 'gc>,
     elem: Value<'gc>,
+    amf_version: AMFVersion,
 ) -> Option<AmfValue> {
     match elem {
         Value::Undefined | Value::MovieClip(_) => Some(AmfValue::Undefined),
@@ -75,11 +99,14 @@ fn serialize_value<'gc>(
                 Some(AmfValue::Undefined)
             } else if o.as_array_object().is_some() {
                 let mut values = Vec::new();
-                recursive_serialize(activation, o, &mut values);
+                recursive_serialize(activation, o, &mut values, amf_version);
 
                 // TODO: What happens if an exception is thrown here?
                 let length = o.length(activation).unwrap();
-                Some(AmfValue::ECMAArray(vec![], values, length as u32))
+                match amf_version {
+                    AMFVersion::AMF0 => Some(AmfValue::ECMAArray(vec![], values, length as u32)),
+                    AMFVersion::AMF3 => Some(serialize_amf3_array(values, length as usize)),
+                }
             } else if let Some(xml_node) = o.as_xml_node() {
                 // TODO: What happens if an exception is thrown here?
                 let string = xml_node.into_string(activation).unwrap();
@@ -88,23 +115,61 @@ fn serialize_value<'gc>(
                 Some(AmfValue::Date(date.read().time(), None))
             } else {
                 let mut object_body = Vec::new();
-                recursive_serialize(activation, o, &mut object_body);
-                Some(AmfValue::Object(object_body, None))
+                recursive_serialize(activation, o, &mut object_body, amf_version);
+                let class_definition = match amf_version {
+                    AMFVersion::AMF0 => None,
+                    AMFVersion::AMF3 => Some(ClassDefinition {
+                        name: "".to_string(),
+                        attributes: EnumSet::only(AmfAttribute::Dynamic),
+                        static_properties: Vec::new(),
+                    }),
+                };
+                Some(AmfValue::Object(object_body, class_definition))
             }
         }
     }
 }
 
+/// Builds an AMF3 array out of the serialized properties of an array, which
+/// keeps the elements from index 0 onwards apart from the other properties.
+fn serialize_amf3_array(values: Vec<Element>, length: usize) -> AmfValue {
+    let mut indexed = Vec::new();
+    let mut sparse = Vec::new();
+    for elem in values {
+        match elem.name.parse::<usize>() {
+            Ok(i) if i.to_string() == elem.name => indexed.push((i, elem)),
+            _ => sparse.push(elem),
+        }
+    }
+    indexed.sort_by_key(|(i, _)| *i);
+
+    let mut dense = Vec::new();
+    for (i, elem) in indexed {
+        if i == dense.len() {
+            dense.push(elem.value);
+        } else {
+            sparse.push(elem);
+        }
+    }
+
+    if sparse.is_empty() && dense.len() == length {
+        AmfValue::StrictArray(dense)
+    } else {
+        AmfValue::ECMAArray(dense, sparse, length as u32)
+    }
+}
+
 /// Serialize an Object and any children to a JSON object
 fn recursive_serialize<'gc>(
     activation: &mut Activation<'_, 'gc>,
     obj: Object<'gc>,
     elements: &mut Vec<Element>,
+    amf_version: AMFVersion,
 ) {
     // Reversed to match flash player ordering
     for element_name in obj.get_keys(activation).into_iter().rev() {
         if let Ok(elem) = obj.get(element_name, activation) {
-            if let Some(v) = serialize_value(activation, elem) {
+            if let Some(v) = serialize_value(activation, elem, amf_version) {
                 elements.push(Element::new(element_name.to_utf8_lossy(), v));
             }
         }
@@ -112,18 +177,27 @@ fn recursive_serialize<'gc>(
 }
 
 /// Deserialize a AmfValue to a Value
-fn deserialize_value<'gc>(activation: &mut Activation<'_, 'gc>, val: &AmfValue) -> Value<'gc> {
+pub(crate) fn deserialize_value<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    val: &AmfValue,
+) -> Value<'gc> {
     match val {
         AmfValue::Null => Value::Null,
         AmfValue::Undefined => Value::Undefined,
         AmfValue::Number(f) => (*f).into(),
+        AmfValue::Integer(i) => (*i).into(),
         AmfValue::String(s) => Value::String(AvmString::new_utf8(activation.context.gc_context, s)),
         AmfValue::Bool(b) => (*b).into(),
-        AmfValue::ECMAArray(_, associative, len) => {
+        AmfValue::ECMAArray(dense, associative, len) => {
             let array_constructor = activation.context.avm1.prototypes().array_constructor;
             if let Ok(Value::Object(obj)) =
                 array_constructor.construct(activation, &[(*len).into()])
             {
+                for (i, value) in dense.iter().enumerate() {
+                    let value = deserialize_value(activation, value);
+                    obj.set_element(activation, i as i32, value).unwrap();
+                }
+
                 for entry in associative {
                     let value = deserialize_value(activation, entry.value());
 
@@ -144,6 +218,38 @@ fn deserialize_value<'gc>(activation: &mut Activation<'_, 'gc>, val: &AmfValue)
                 Value::Undefined
             }
         }
+        // AVM1 has no typed arrays, so vectors become arrays.
+        AmfValue::StrictArray(values) | AmfValue::VectorObject(values, _, _) => {
+            let values: Vec<_> = values
+                .iter()
+                .map(|value| deserialize_value(activation, value))
+                .collect();
+            ArrayObject::new(
+                activation.context.gc_context,
+                activation.context.avm1.prototypes().array,
+                values,
+            )
+            .into()
+        }
+        AmfValue::VectorInt(values, _) => ArrayObject::new(
+            activation.context.gc_context,
+            activation.context.avm1.prototypes().array,
+            values.iter().map(|&value| value.into()),
+        )
+        .into(),
+        AmfValue::VectorUInt(values, _) => ArrayObject::new(
+            activation.context.gc_context,
+            activation.context.avm1.prototypes().array,
+            values.iter().map(|&value| value.into()),
+        )
+        .into(),
+        AmfValue::VectorDouble(values, _) => ArrayObject::new(
+            activation.context.gc_context,
+            activation.context.avm1.prototypes().array,
+            values.iter().map(|&value| value.into()),
+        )
+        .into(),
+        AmfValue::AMF3(value) => deserialize_value(activation, value),
         AmfValue::Object(elements, _) => {
             // Deserialize Object
             let obj = ScriptObject::new(
@@ -347,11 +453,17 @@ pub fn get_local<'gc>(
     obj_so.set_name(activation.context.gc_context, full_name.clone());
 
     let mut data = Value::Undefined;
+    let mut object_encoding = constructor.get("defaultObjectEncoding", activation)?;
 
     // Load the data object from storage if it existed prior
     if let Some(saved) = activation.context.storage.get(&full_name) {
         if let Ok(lso) = flash_lso::read::Reader::default().parse(&saved) {
             data = deserialize_lso(activation, &lso)?.into();
+            // Keep storing the data the way it was stored.
+            object_encoding = match lso.header.format_version {
+                AMFVersion::AMF0 => 0.into(),
+                AMFVersion::AMF3 => 3.into(),
+            };
         }
     }
 
@@ -370,6 +482,12 @@ pub fn get_local<'gc>(
         data,
         Attribute::DONT_DELETE,
     );
+    this.define_value(
+        activation.context.gc_context,
+        "objectEncoding",
+        object_encoding,
+        Attribute::DONT_ENUM,
+    );
 
     activation
         .context
@@ -382,10 +500,288 @@ pub fn get_local<'gc>(
 pub fn get_remote<'gc>(
     activation: &mut Activation<'_, 'gc>,
     _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm1_stub!(activation, "SharedObject", "getRemote");
-    Ok(Value::Undefined)
+    let name = args
+        .get(0)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_string(activation)?
+        .to_string();
+    let remote_path = args
+        .get(1)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_string(activation)?
+        .to_string();
+    // The persistence is either a boolean, or the local path that a copy of the
+    // shared object is kept at. Either way, the server keeps it.
+    let persistent = match args.get(2) {
+        None | Some(Value::Undefined) | Some(Value::Null) => false,
+        Some(Value::Bool(persistent)) => *persistent,
+        Some(_) => true,
+    };
+
+    // Remote paths are URIs, so these never clash with the names of local shared objects.
+    let full_name = format!("{remote_path}/{name}");
+    if let Some(so) = activation.context.avm1_shared_objects.get(&full_name) {
+        return Ok((*so).into());
+    }
+
+    let constructor = activation
+        .context
+        .avm1
+        .prototypes()
+        .shared_object_constructor;
+    let this = constructor
+        .construct(activation, &[])?
+        .coerce_to_object(activation);
+
+    let obj_so = this.as_shared_object().unwrap();
+    obj_so.set_name(activation.context.gc_context, name);
+    obj_so.set_remote(
+        activation.context.gc_context,
+        Some(RemoteState {
+            remote_path,
+            persistent,
+            ..Default::default()
+        }),
+    );
+
+    // The data is received from the server once connected.
+    let data = ScriptObject::new(
+        activation.context.gc_context,
+        Some(activation.context.avm1.prototypes().object),
+    );
+    this.define_value(
+        activation.context.gc_context,
+        "data",
+        data.into(),
+        Attribute::DONT_DELETE,
+    );
+
+    activation
+        .context
+        .avm1_shared_objects
+        .insert(full_name, this);
+
+    Ok(this.into())
+}
+
+/// Creates the info object that `onSync` receives in a list for each change.
+fn sync_info<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    code: &'static str,
+    name: Option<&str>,
+    old_value: Option<Value<'gc>>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let info = ScriptObject::new(
+        activation.context.gc_context,
+        Some(activation.context.avm1.prototypes().object),
+    );
+    info.set("code", code.into(), activation)?;
+    if let Some(name) = name {
+        let name = AvmString::new_utf8(activation.context.gc_context, name);
+        info.set("name", name.into(), activation)?;
+    }
+    if let Some(old_value) = old_value {
+        info.set("oldValue", old_value, activation)?;
+    }
+    Ok(info.into())
+}
+
+/// Applies an event of the server to a remote shared object, returning the
+/// change to report to `onSync`, if any.
+fn apply_remote_event<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    event: RemoteSharedObjectEvent,
+) -> Result<Option<Value<'gc>>, Error<'gc>> {
+    let so = this.as_shared_object().unwrap();
+    let data = this.get("data", activation)?.coerce_to_object(activation);
+    let gc_context = activation.context.gc_context;
+
+    match event {
+        RemoteSharedObjectEvent::Connected { success: true, .. } => {
+            // The data of the server replaces whatever was there before.
+            for k in &data.get_keys(activation) {
+                data.delete(activation, *k);
+            }
+            if let Some(mut remote) = so.remote_mut(gc_context) {
+                remote.connected = true;
+                remote.synced.clear();
+            }
+            sync_info(activation, "clear", None, None).map(Some)
+        }
+        RemoteSharedObjectEvent::Connected { success: false, .. } => {
+            if let Some(mut remote) = so.remote_mut(gc_context) {
+                remote.handle = None;
+            }
+            let info = ScriptObject::new(
+                activation.context.gc_context,
+                Some(activation.context.avm1.prototypes().object),
+            );
+            info.set("level", "error".into(), activation)?;
+            info.set("code", "NetConnection.Connect.Failed".into(), activation)?;
+            this.call_method(
+                "onStatus".into(),
+                &[info.into()],
+                activation,
+                ExecutionReason::Special,
+            )?;
+            Ok(None)
+        }
+        RemoteSharedObjectEvent::Changed { name, value, .. } => {
+            let key = AvmString::new_utf8(gc_context, &name);
+            let old_value = data.get(key, activation)?;
+            let new_value = deserialize_value(activation, &value);
+            data.set(key, new_value, activation)?;
+            if let Some(mut remote) = so.remote_mut(gc_context) {
+                remote.synced.insert(name.clone(), value);
+            }
+            sync_info(activation, "change", Some(&name), Some(old_value)).map(Some)
+        }
+        RemoteSharedObjectEvent::Deleted { name, .. } => {
+            let key = AvmString::new_utf8(gc_context, &name);
+            let old_value = data.get(key, activation)?;
+            data.delete(activation, key);
+            if let Some(mut remote) = so.remote_mut(gc_context) {
+                remote.synced.remove(&name);
+            }
+            sync_info(activation, "delete", Some(&name), Some(old_value)).map(Some)
+        }
+        RemoteSharedObjectEvent::Accepted { name, .. } => {
+            sync_info(activation, "success", Some(&name), None).map(Some)
+        }
+        RemoteSharedObjectEvent::Message {
+            handler, arguments, ..
+        } => {
+            let arguments: Vec<_> = arguments
+                .iter()
+                .map(|argument| deserialize_value(activation, argument))
+                .collect();
+            let handler = AvmString::new_utf8(gc_context, handler);
+            this.call_method(handler, &arguments, activation, ExecutionReason::Special)?;
+            Ok(None)
+        }
+    }
+}
+
+/// Sends the properties of the data of a remote shared object that changed
+/// since the last synchronization to the server.
+fn send_local_changes<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+) -> Result<(), Error<'gc>> {
+    let so = this.as_shared_object().unwrap();
+    let handle = match so.remote().as_deref() {
+        Some(RemoteState {
+            handle: Some(handle),
+            connected: true,
+            ..
+        }) => *handle,
+        _ => return Ok(()),
+    };
+
+    let data = this.get("data", activation)?.coerce_to_object(activation);
+    let mut properties = HashMap::new();
+    for key in data.get_keys(activation) {
+        let value = data.get(key, activation)?;
+        if let Some(value) = serialize_value(activation, value, AMFVersion::AMF0) {
+            properties.insert(key.to_utf8_lossy().into_owned(), value);
+        }
+    }
+
+    let Some(mut remote) = so.remote_mut(activation.context.gc_context) else {
+        return Ok(());
+    };
+    let backend = &mut activation.context.remote_shared_objects;
+    for (name, value) in &properties {
+        if remote.synced.get(name) != Some(value) {
+            backend.set_property(handle, name, value.clone());
+        }
+    }
+    for name in remote.synced.keys() {
+        if !properties.contains_key(name) {
+            backend.delete_property(handle, name);
+        }
+    }
+    remote.synced = properties;
+    Ok(())
+}
+
+/// Exchanges the changes of remote shared objects with the server, and runs
+/// their `onSync` handlers.
+pub fn sync_remote_shared_objects(context: &mut UpdateContext<'_, '_>) {
+    let events = context.remote_shared_objects.poll();
+    let remote_objects: Vec<_> = context
+        .avm1_shared_objects
+        .values()
+        .copied()
+        .filter(|so| {
+            so.as_shared_object()
+                .map_or(false, |so| so.remote().is_some())
+        })
+        .collect();
+    if remote_objects.is_empty() {
+        return;
+    }
+
+    let Some(mut activation) = Activation::try_from_stub(
+        context.reborrow(),
+        ActivationIdentifier::root("[Remote SharedObject]"),
+    ) else {
+        return;
+    };
+
+    let mut changes: Vec<(Object<'_>, Vec<Value<'_>>)> = Vec::new();
+    for event in events {
+        let handle = match &event {
+            RemoteSharedObjectEvent::Connected { handle, .. }
+            | RemoteSharedObjectEvent::Changed { handle, .. }
+            | RemoteSharedObjectEvent::Deleted { handle, .. }
+            | RemoteSharedObjectEvent::Accepted { handle, .. }
+            | RemoteSharedObjectEvent::Message { handle, .. } => *handle,
+        };
+        let Some(this) = remote_objects
+            .iter()
+            .copied()
+            .find(|object| object.as_shared_object().unwrap().remote_handle() == Some(handle))
+        else {
+            continue;
+        };
+
+        match apply_remote_event(&mut activation, this, event) {
+            Ok(Some(change)) => {
+                match changes.iter_mut().find(|(so, _)| Object::ptr_eq(*so, this)) {
+                    Some((_, list)) => list.push(change),
+                    None => changes.push((this, vec![change])),
+                }
+            }
+            Ok(None) => {}
+            Err(e) => tracing::error!("Error synchronizing remote shared object: {:?}", e),
+        }
+    }
+
+    for (this, list) in changes {
+        let list = ArrayObject::new(
+            activation.context.gc_context,
+            activation.context.avm1.prototypes().array,
+            list,
+        );
+        if let Err(e) = this.call_method(
+            "onSync".into(),
+            &[list.into()],
+            &mut activation,
+            ExecutionReason::Special,
+        ) {
+            tracing::error!("Error in SharedObject.onSync: {:?}", e);
+        }
+    }
+
+    for this in remote_objects {
+        if let Err(e) = send_local_changes(&mut activation, this) {
+            tracing::error!("Error synchronizing remote shared object: {:?}", e);
+        }
+    }
 }
 
 pub fn get_max_size<'gc>(
@@ -393,8 +789,9 @@ pub fn get_max_size<'gc>(
     _this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm1_stub!(activation, "SharedObject", "getMaxSize");
-    Ok(Value::Undefined)
+    let domain = storage_domain(activation);
+    let max_size = storage_quota(activation, &domain).map_or(f64::INFINITY, |quota| quota as f64);
+    Ok(max_size.into())
 }
 
 pub fn add_listener<'gc>(
@@ -444,6 +841,10 @@ pub fn clear<'gc>(
     }
 
     let so = this.as_shared_object().unwrap();
+    if so.remote().is_some() {
+        // The deleted properties are sent to the server with the next synchronization.
+        return Ok(Value::Undefined);
+    }
     let name = so.get_name();
 
     activation.context.storage.remove_key(&name);
@@ -453,63 +854,211 @@ pub fn clear<'gc>(
 
 pub fn close<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
+    this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm1_stub!(activation, "SharedObject", "close");
+    let Some(so) = this.as_shared_object() else {
+        return Ok(Value::Undefined);
+    };
+    if let Some(mut remote) = so.remote_mut(activation.context.gc_context) {
+        if let Some(handle) = remote.handle.take() {
+            activation.context.remote_shared_objects.close(handle);
+        }
+        remote.connected = false;
+    } else {
+        avm1_stub!(activation, "SharedObject", "close");
+    }
     Ok(Value::Undefined)
 }
 
 pub fn connect<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    this: Object<'gc>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm1_stub!(activation, "SharedObject", "connect");
-    Ok(Value::Undefined)
+    let Some(so) = this.as_shared_object() else {
+        return Ok(false.into());
+    };
+    let Some(persistent) = so.remote().map(|remote| remote.persistent) else {
+        return Ok(false.into());
+    };
+
+    // The connection is a `NetConnection` that was connected to the application,
+    // which we only need the URI of.
+    let uri = match args.get(0) {
+        Some(Value::Object(connection)) => connection.get("uri", activation)?,
+        Some(uri) => *uri,
+        None => return Ok(false.into()),
+    };
+    let uri = uri
+        .coerce_to_string(activation)?
+        .to_utf8_lossy()
+        .into_owned();
+
+    let name = so.get_name();
+    if let Some(mut remote) = so.remote_mut(activation.context.gc_context) {
+        if let Some(handle) = remote.handle.take() {
+            activation.context.remote_shared_objects.close(handle);
+        }
+        let handle = activation
+            .context
+            .remote_shared_objects
+            .connect(&uri, &name, persistent);
+        remote.handle = Some(handle);
+        remote.connected = false;
+    }
+    Ok(true.into())
 }
 
-pub fn flush<'gc>(
+/// The AMF version that a shared object is stored with, which movies choose
+/// with `objectEncoding`: 3 for AMF3, anything else for AMF0.
+fn object_encoding<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
-    _args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error<'gc>> {
-    let data = this.get("data", activation)?.coerce_to_object(activation);
+) -> Result<AMFVersion, Error<'gc>> {
+    let encoding = this
+        .get("objectEncoding", activation)?
+        .coerce_to_f64(activation)?;
+    Ok(if encoding == 3.0 {
+        AMFVersion::AMF3
+    } else {
+        AMFVersion::AMF0
+    })
+}
 
-    let this_obj = this.as_shared_object().unwrap();
-    let name = this_obj.get_name();
+/// Serializes the data of a local shared object, as it is stored.
+fn serialize_shared_object<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+) -> Result<Vec<u8>, Error<'gc>> {
+    let data = this.get("data", activation)?.coerce_to_object(activation);
+    let name = this.as_shared_object().unwrap().get_name();
+    let amf_version = object_encoding(activation, this)?;
 
     let mut elements = Vec::new();
-    recursive_serialize(activation, data, &mut elements);
+    recursive_serialize(activation, data, &mut elements, amf_version);
     let mut lso = Lso::new(
         elements,
         name.split('/')
             .last()
             .map(|e| e.to_string())
             .unwrap_or_else(|| "<unknown>".to_string()),
-        AMFVersion::AMF0,
+        amf_version,
     );
 
-    let bytes = flash_lso::write::write_to_bytes(&mut lso).unwrap_or_default();
+    Ok(flash_lso::write::write_to_bytes(&mut lso).unwrap_or_default())
+}
+
+pub fn flush<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this_obj = this.as_shared_object().unwrap();
+    if this_obj.remote().is_some() {
+        // Remote shared objects are kept by the server.
+        return Ok(true.into());
+    }
+    let name = this_obj.get_name();
+    let bytes = serialize_shared_object(activation, this)?;
+
+    // The shared objects of a domain share its quota, so the space needed is
+    // what the other ones use, and the space this one needs.
+    let min_disk_space = match args.get(0) {
+        Some(value) => value.coerce_to_f64(activation)?.max(0.0) as u64,
+        None => 0,
+    };
+    let domain = storage_domain(activation);
+    let storage = &activation.context.storage;
+    let others_usage = storage
+        .get_disk_usage(&format!("{domain}/"))
+        .saturating_sub(storage.get_size(&name).unwrap_or_default() as u64);
+    let needed = others_usage + (bytes.len() as u64).max(min_disk_space);
+
+    match storage_quota(activation, &domain) {
+        Some(0) => {
+            // The user chose to never let the domain store anything.
+            return Ok(false.into());
+        }
+        Some(quota) if needed > quota => {
+            // Flash Player asks the user for more space, and reports their answer to `onStatus`.
+            // There is nobody to ask, so the flush always fails.
+            tracing::warn!(
+                "SharedObject.flush: {} would need {} bytes, over the quota of {} bytes",
+                name,
+                needed,
+                quota
+            );
+            let info = ScriptObject::new(
+                activation.context.gc_context,
+                Some(activation.context.avm1.prototypes().object),
+            );
+            info.set("level", "error".into(), activation)?;
+            info.set("code", "SharedObject.Flush.Failed".into(), activation)?;
+            activation.context.action_queue.queue_action(
+                activation.base_clip(),
+                ActionType::Method {
+                    object: this,
+                    name: "onStatus",
+                    args: vec![info.into()],
+                },
+                false,
+            );
+            return Ok("pending".into());
+        }
+        _ => {}
+    }
 
     Ok(activation.context.storage.put(&name, &bytes).into())
 }
 
 pub fn get_size<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
+    this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm1_stub!(activation, "SharedObject", "getSize");
-    Ok(Value::Undefined)
+    let Some(so) = this.as_shared_object() else {
+        return Ok(Value::Undefined);
+    };
+    if so.remote().is_some() {
+        avm1_stub!(
+            activation,
+            "SharedObject",
+            "getSize",
+            "with remote shared objects"
+        );
+        return Ok(Value::Undefined);
+    }
+    let bytes = serialize_shared_object(activation, this)?;
+    Ok(bytes.len().into())
 }
 
 pub fn send<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    this: Object<'gc>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm1_stub!(activation, "SharedObject", "send");
+    let Some(handle) = this.as_shared_object().and_then(|so| so.remote_handle()) else {
+        return Ok(Value::Undefined);
+    };
+
+    let handler = args
+        .get(0)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_string(activation)?
+        .to_utf8_lossy()
+        .into_owned();
+    let arguments = args
+        .iter()
+        .skip(1)
+        .map(|argument| {
+            serialize_value(activation, *argument, AMFVersion::AMF0).unwrap_or(AmfValue::Undefined)
+        })
+        .collect();
+    activation
+        .context
+        .remote_shared_objects
+        .send(handle, &handler, arguments);
     Ok(Value::Undefined)
 }
 
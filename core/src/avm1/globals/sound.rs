@@ -257,6 +257,7 @@ fn load_sound<'gc>(
                 activation.context.player.clone(),
                 sound,
                 Request::get(url.to_utf8_lossy().into_owned()),
+                activation.base_clip().movie(),
                 is_streaming,
             );
             activation.context.navigator.spawn_future(future);
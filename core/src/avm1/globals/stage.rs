@@ -1,6 +1,8 @@
 //! Stage object
 //!
-//! TODO: This is a very rough stub with not much implementation.
+//! `Stage` is an `AsBroadcaster`; its listeners receive `onResize` when the
+//! stage size changes in `noScale` mode, and `onFullScreen` when the display
+//! state changes.
 
 use crate::avm1::activation::Activation;
 use crate::avm1::error::Error;
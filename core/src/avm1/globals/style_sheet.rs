@@ -0,0 +1,185 @@
+//! `TextField.StyleSheet` impl
+
+use crate::avm1::function::{Executable, FunctionObject};
+use crate::avm1::globals::text_field::new_text_format;
+use crate::avm1::object::NativeObject;
+use crate::avm1::property_decl::{define_properties_on, Declaration};
+use crate::avm1::{Activation, ArrayObject, Error, Object, ScriptObject, TObject, Value};
+use crate::avm1_stub;
+use crate::context::GcContext;
+use crate::html::{style_to_text_format, Style, StyleSheet};
+use crate::string::AvmString;
+
+macro_rules! style_sheet_method {
+    ($name:ident) => {
+        |activation, this, args| {
+            if let NativeObject::StyleSheet(style_sheet) = this.native() {
+                return $name(activation, style_sheet, args);
+            }
+            Ok(Value::Undefined)
+        }
+    };
+}
+
+const PROTO_DECLS: &[Declaration] = declare_properties! {
+    "getStyle" => method(style_sheet_method!(get_style); DONT_ENUM | DONT_DELETE);
+    "setStyle" => method(style_sheet_method!(set_style); DONT_ENUM | DONT_DELETE);
+    "getStyleNames" => method(style_sheet_method!(get_style_names); DONT_ENUM | DONT_DELETE);
+    "clear" => method(style_sheet_method!(clear); DONT_ENUM | DONT_DELETE);
+    "parseCSS" => method(style_sheet_method!(parse_css); DONT_ENUM | DONT_DELETE);
+    "transform" => method(transform; DONT_ENUM | DONT_DELETE);
+    "load" => method(load; DONT_ENUM | DONT_DELETE);
+};
+
+/// Reads the properties of a style object, as strings.
+fn style_properties<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    object: Object<'gc>,
+) -> Result<Vec<(String, String)>, Error<'gc>> {
+    let mut properties = Vec::new();
+    for name in object.get_keys(activation) {
+        let value = object.get(name, activation)?.coerce_to_string(activation)?;
+        properties.push((name.to_string(), value.to_string()));
+    }
+    Ok(properties)
+}
+
+fn get_style<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    style_sheet: StyleSheet<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let name = args
+        .get(0)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_string(activation)?;
+    let Some(style) = style_sheet.style(&name.to_string()) else {
+        return Ok(Value::Null);
+    };
+
+    // A copy of the style is returned, so that changing it doesn't change the style sheet.
+    let object = ScriptObject::new(
+        activation.context.gc_context,
+        Some(activation.context.avm1.prototypes().object),
+    );
+    for (name, value) in style.properties {
+        let name = AvmString::new_utf8(activation.context.gc_context, name);
+        let value = AvmString::new_utf8(activation.context.gc_context, value);
+        object.set(name, value.into(), activation)?;
+    }
+    Ok(object.into())
+}
+
+fn set_style<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    style_sheet: StyleSheet<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let name = args
+        .get(0)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_string(activation)?;
+    let style = match args.get(1) {
+        Some(Value::Object(object)) => Some(Style::new(style_properties(activation, *object)?)),
+        _ => None,
+    };
+    style_sheet.set_style(activation.context.gc_context, &name.to_string(), style);
+    Ok(Value::Undefined)
+}
+
+fn get_style_names<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    style_sheet: StyleSheet<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let gc_context = activation.context.gc_context;
+    Ok(ArrayObject::new(
+        gc_context,
+        activation.context.avm1.prototypes().array,
+        style_sheet
+            .style_names()
+            .into_iter()
+            .map(|name| AvmString::new_utf8(gc_context, name).into()),
+    )
+    .into())
+}
+
+fn clear<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    style_sheet: StyleSheet<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    style_sheet.clear(activation.context.gc_context);
+    Ok(Value::Undefined)
+}
+
+fn parse_css<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    style_sheet: StyleSheet<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let css = args
+        .get(0)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_string(activation)?;
+    let parsed = style_sheet.parse_css(activation.context.gc_context, &css.to_string());
+    Ok(parsed.into())
+}
+
+fn transform<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(Value::Object(style)) = args.get(0) else {
+        return Ok(Value::Null);
+    };
+    let properties = style_properties(activation, *style)?;
+    let text_format = style_to_text_format(&properties);
+    Ok(new_text_format(activation, text_format).into())
+}
+
+fn load<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    avm1_stub!(activation, "TextField.StyleSheet", "load");
+    Ok(false.into())
+}
+
+pub fn constructor<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    this.set_native(
+        activation.context.gc_context,
+        NativeObject::StyleSheet(StyleSheet::new(activation.context.gc_context)),
+    );
+    Ok(this.into())
+}
+
+pub fn create_proto<'gc>(
+    context: &mut GcContext<'_, 'gc>,
+    proto: Object<'gc>,
+    fn_proto: Object<'gc>,
+) -> Object<'gc> {
+    let object = ScriptObject::new(context.gc_context, Some(proto));
+    define_properties_on(PROTO_DECLS, context, object, fn_proto);
+    object.into()
+}
+
+pub fn create_class<'gc>(
+    context: &mut GcContext<'_, 'gc>,
+    style_sheet_proto: Object<'gc>,
+    fn_proto: Object<'gc>,
+) -> Object<'gc> {
+    FunctionObject::constructor(
+        context.gc_context,
+        Executable::Native(constructor),
+        constructor_to_fn!(constructor),
+        fn_proto,
+        style_sheet_proto,
+    )
+}
@@ -6,9 +6,12 @@ use crate::avm1::property_decl::{define_properties_on, Declaration};
 use crate::avm1::runtime::Avm1;
 use crate::avm1::{ScriptObject, TObject, Value};
 use crate::avm1_stub;
+use crate::backend::ui::SettingsPanel;
 use crate::context::GcContext;
+use crate::tag_utils::SwfMovie;
 use bitflags::bitflags;
 use core::fmt;
+use url::Url;
 
 const OBJECT_DECLS: &[Declaration] = declare_properties! {
     "exactSettings" => property(get_exact_settings, set_exact_settings);
@@ -40,7 +43,7 @@ impl fmt::Display for CpuArchitecture {
 }
 
 /// Available type of sandbox for a given SWF
-#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum SandboxType {
     Remote,
     LocalWithFile,
@@ -48,6 +51,35 @@ pub enum SandboxType {
     LocalTrusted,
 }
 
+impl SandboxType {
+    /// The sandbox that Flash Player puts a movie in, based on where it was loaded from.
+    ///
+    /// Local movies are restricted to either local files or the network,
+    /// depending on the `UseNetwork` flag of their `FileAttributes` tag.
+    pub fn for_movie(movie: &SwfMovie) -> Self {
+        match Url::parse(movie.url()) {
+            Ok(url) if url.scheme() == "file" => {
+                if movie.header().use_network_sandbox() {
+                    SandboxType::LocalWithNetwork
+                } else {
+                    SandboxType::LocalWithFile
+                }
+            }
+            _ => SandboxType::Remote,
+        }
+    }
+
+    /// Whether a movie in this sandbox may load the given URL.
+    pub fn can_access(self, url: &Url) -> bool {
+        let is_local = url.scheme() == "file";
+        match self {
+            SandboxType::LocalTrusted => true,
+            SandboxType::LocalWithFile => is_local,
+            SandboxType::LocalWithNetwork | SandboxType::Remote => !is_local,
+        }
+    }
+}
+
 impl fmt::Display for SandboxType {
     fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
         fmt.write_str(match self {
@@ -221,20 +253,6 @@ impl fmt::Display for PlayerType {
     }
 }
 
-#[derive(Debug, Copy, Clone, FromPrimitive)]
-enum SettingsPanel {
-    Privacy = 0,
-    LocalStorage = 1,
-    Microphone = 2,
-    Camera = 3,
-}
-
-impl SettingsPanel {
-    pub fn from_u8(n: u8) -> Option<Self> {
-        num_traits::FromPrimitive::from_u8(n)
-    }
-}
-
 bitflags! {
     pub struct SystemCapabilities: u32 {
         const AV_HARDWARE      = 1 << 0;
@@ -288,6 +306,9 @@ pub struct SystemProperties {
     pub os: OperatingSystem,
     /// The type of the player sandbox
     pub sandbox_type: SandboxType,
+    /// Whether movies may script movies from other domains without being
+    /// allowed to with `Security.allowDomain`
+    pub permissive_cross_scripting: bool,
     /// The cpu architecture of the platform
     pub cpu_architecture: CpuArchitecture,
     /// The highest supported h264 decoder level
@@ -312,6 +333,7 @@ impl SystemProperties {
             manufacturer: Manufacturer::Linux,
             os: OperatingSystem::Linux,
             sandbox_type,
+            permissive_cross_scripting: false,
             cpu_architecture: CpuArchitecture::X86,
             idc_level: "5.1".into(),
         }
@@ -324,6 +346,13 @@ impl SystemProperties {
         )
     }
 
+    /// Whether scripts are restricted to the movies that allow their domain.
+    ///
+    /// Trusted content may script any movie, like in Flash Player.
+    pub fn checks_cross_scripting(&self) -> bool {
+        self.sandbox_type != SandboxType::LocalTrusted && !self.permissive_cross_scripting
+    }
+
     pub fn has_capability(&self, cap: SystemCapabilities) -> bool {
         self.capabilities.contains(cap)
     }
@@ -446,9 +475,14 @@ pub fn show_settings<'gc>(
         .unwrap_or(&last_panel_pos.into())
         .coerce_to_i32(activation)?;
 
-    let _panel = SettingsPanel::from_u8(panel_pos as u8).unwrap_or(SettingsPanel::Privacy);
+    let panel = match panel_pos {
+        1 => SettingsPanel::LocalStorage,
+        2 => SettingsPanel::Microphone,
+        3 => SettingsPanel::Camera,
+        _ => SettingsPanel::Privacy,
+    };
 
-    avm1_stub!(activation, "System", "showSettings");
+    activation.context.ui.display_settings_panel(panel);
     Ok(Value::Undefined)
 }
 
@@ -2,12 +2,13 @@ use crate::avm1::activation::Activation;
 use crate::avm1::error::Error;
 use crate::avm1::object::NativeObject;
 use crate::avm1::property_decl::{define_properties_on, Declaration};
-use crate::avm1::{globals, Object, ScriptObject, TObject, Value};
+use crate::avm1::{self, globals, Object, ScriptObject, TObject, Value};
 use crate::context::GcContext;
 use crate::display_object::{AutoSizeMode, EditText, TDisplayObject, TextSelection};
 use crate::font::round_down_to_pixel;
 use crate::html::TextFormat;
 use crate::string::{AvmString, WStr};
+use crate::vminterface::AvmObject;
 use gc_arena::GcCell;
 use swf::Color;
 
@@ -70,6 +71,7 @@ const PROTO_DECLS: &[Declaration] = declare_properties! {
     "hscroll" => property(tf_getter!(hscroll), tf_setter!(set_hscroll));
     "html" => property(tf_getter!(html), tf_setter!(set_html));
     "htmlText" => property(tf_getter!(html_text), tf_setter!(set_html_text));
+    "styleSheet" => property(tf_getter!(style_sheet), tf_setter!(set_style_sheet));
     "length" => property(tf_getter!(length));
     "maxhscroll" => property(tf_getter!(maxhscroll));
     "maxscroll" => property(tf_getter!(maxscroll));
@@ -88,6 +90,7 @@ const PROTO_DECLS: &[Declaration] = declare_properties! {
     "gridFitType" => property(tf_getter!(grid_fit_type), tf_setter!(set_grid_fit_type));
     "sharpness" => property(tf_getter!(sharpness), tf_setter!(set_sharpness));
     "thickness" => property(tf_getter!(thickness), tf_setter!(set_thickness));
+    "filters" => property(tf_getter!(filters), tf_setter!(set_filters); DONT_DELETE | DONT_ENUM | VERSION_8);
 };
 
 /// Implements `TextField`
@@ -127,7 +130,7 @@ pub fn set_password<'gc>(
     Ok(())
 }
 
-fn new_text_format<'gc>(
+pub(crate) fn new_text_format<'gc>(
     activation: &mut Activation<'_, 'gc>,
     text_format: TextFormat,
 ) -> ScriptObject<'gc> {
@@ -381,6 +384,32 @@ pub fn set_html_text<'gc>(
     Ok(())
 }
 
+pub fn style_sheet<'gc>(
+    this: EditText<'gc>,
+    _activation: &mut Activation<'_, 'gc>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(match this.style_sheet_object() {
+        Some(AvmObject::Avm1(object)) => object.into(),
+        _ => Value::Undefined,
+    })
+}
+
+pub fn set_style_sheet<'gc>(
+    this: EditText<'gc>,
+    activation: &mut Activation<'_, 'gc>,
+    value: Value<'gc>,
+) -> Result<(), Error<'gc>> {
+    let style_sheet = match value {
+        Value::Object(object) => match object.native() {
+            NativeObject::StyleSheet(style_sheet) => Some((AvmObject::Avm1(object), style_sheet)),
+            _ => None,
+        },
+        _ => None,
+    };
+    this.set_style_sheet(&mut activation.context, style_sheet);
+    Ok(())
+}
+
 pub fn background<'gc>(
     this: EditText<'gc>,
     _activation: &mut Activation<'_, 'gc>,
@@ -778,6 +807,21 @@ pub fn set_thickness<'gc>(
     Ok(())
 }
 
+pub fn filters<'gc>(
+    this: EditText<'gc>,
+    activation: &mut Activation<'_, 'gc>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    avm1::filters::get_filters(activation, this.into())
+}
+
+pub fn set_filters<'gc>(
+    this: EditText<'gc>,
+    activation: &mut Activation<'_, 'gc>,
+    value: Value<'gc>,
+) -> Result<(), Error<'gc>> {
+    avm1::filters::set_filters(activation, this.into(), value)
+}
+
 pub fn sharpness<'gc>(
     this: EditText<'gc>,
     _activation: &mut Activation<'_, 'gc>,
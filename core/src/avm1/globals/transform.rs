@@ -3,6 +3,7 @@
 use crate::avm1::globals::color_transform::ColorTransformObject;
 use crate::avm1::globals::matrix::{matrix_to_object, object_to_matrix};
 use crate::avm1::object::transform_object::TransformObject;
+use crate::avm1::object_reference::MovieClipReference;
 use crate::avm1::property_decl::{define_properties_on, Declaration};
 use crate::avm1::{Activation, Error, Object, TObject, Value};
 use crate::context::GcContext;
@@ -12,7 +13,7 @@ macro_rules! tx_getter {
     ( $get:ident ) => {
         |activation, this, _args| {
             if let Some(transform) = this.as_transform_object() {
-                if let Some(clip) = transform.clip() {
+                if let Some(clip) = transform.clip(activation) {
                     return $get(activation, clip);
                 }
             }
@@ -25,7 +26,7 @@ macro_rules! tx_setter {
     ( $set:ident ) => {
         |activation, this, args| {
             if let Some(transform) = this.as_transform_object() {
-                if let Some(clip) = transform.clip() {
+                if let Some(clip) = transform.clip(activation) {
                     let value = args.get(0).unwrap_or(&Value::Undefined).clone();
                     $set(activation, clip, value)?;
                 }
@@ -49,23 +50,21 @@ pub fn constructor<'gc>(
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     // `Tranform` constructor accepts exactly 1 argument.
-    if let [Value::MovieClip(_)] = args {
-        let object = args.first().unwrap().coerce_to_object(activation);
-
-        if let (Some(transform), Some(clip)) = (
-            this.as_transform_object(),
-            object.as_display_object().and_then(|o| o.as_movie_clip()),
-        ) {
-            transform.set_clip(activation.context.gc_context, clip);
-            return Ok(this.into());
-        }
-    }
+    let clip = match args {
+        [Value::MovieClip(clip)] => Some(*clip),
+        [Value::Object(clip)] => clip
+            .as_stage_object()
+            .and_then(|clip| MovieClipReference::try_from_stage_object(activation, clip)),
+        _ => None,
+    };
 
-    if let [Value::Object(clip)] = args {
-        if let (Some(transform), Some(clip)) = (
-            this.as_transform_object(),
-            clip.as_display_object().and_then(|o| o.as_movie_clip()),
-        ) {
+    if let (Some(transform), Some(clip)) = (this.as_transform_object(), clip) {
+        // Only movie clips have a transform.
+        if clip
+            .resolve_reference(activation)
+            .and_then(|(_, _, clip)| clip.as_movie_clip())
+            .is_some()
+        {
             transform.set_clip(activation.context.gc_context, clip);
             return Ok(this.into());
         }
@@ -94,7 +93,7 @@ fn concatenated_color_transform<'gc>(
     let mut node = clip.avm1_parent();
     while let Some(display_object) = node {
         color_transform = *display_object.base().color_transform() * color_transform;
-        node = display_object.parent();
+        node = display_object.avm1_parent();
     }
     ColorTransformObject::construct(activation, color_transform)
 }
@@ -167,8 +166,7 @@ fn pixel_bounds<'gc>(
     activation: &mut Activation<'_, 'gc>,
     clip: MovieClip<'gc>,
 ) -> Result<Value<'gc>, Error<'gc>> {
-    // This is equivalent to `clip.getBounds()`.
-    let bounds = clip.world_bounds();
+    let bounds = clip.pixel_bounds();
 
     // Return Rectangle object.
     let constructor = activation.context.avm1.prototypes().rectangle_constructor;
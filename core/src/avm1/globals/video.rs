@@ -40,14 +40,18 @@ pub fn attach_video<'gc>(
     activation: &mut Activation<'_, 'gc>,
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    let source = args
-        .get(0)
-        .cloned()
-        .unwrap_or(Value::Undefined)
-        .coerce_to_object(activation);
+    let source = args.get(0).cloned().unwrap_or(Value::Undefined);
+    if matches!(source, Value::Null) {
+        // Attaching `null` detaches the camera.
+        video.attach_camera(&mut activation.context, None);
+        return Ok(Value::Undefined);
+    }
 
+    let source = source.coerce_to_object(activation);
     if let NativeObject::NetStream(ns) = source.native() {
         video.attach_netstream(&mut activation.context, ns);
+    } else if let NativeObject::Camera(camera) = source.native() {
+        video.attach_camera(&mut activation.context, Some(camera));
     } else {
         tracing::warn!("Cannot use object of type {:?} as video source", source);
     }
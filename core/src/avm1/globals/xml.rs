@@ -331,6 +331,7 @@ fn spawn_xml_fetch<'gc>(
         activation.context.player.clone(),
         loader_object,
         request,
+        activation.base_clip().movie(),
     );
     activation.context.navigator.spawn_future(future);
 
@@ -0,0 +1,236 @@
+//! XMLSocket class
+//!
+//! Messages are exchanged as strings, each one terminated by a zero byte.
+
+use crate::avm1::activation::{Activation, ActivationIdentifier};
+use crate::avm1::error::Error;
+use crate::avm1::function::ExecutionReason;
+use crate::avm1::globals::system::SandboxType;
+use crate::avm1::property_decl::{define_properties_on, Declaration};
+use crate::avm1::{Object, ScriptObject, TObject, Value};
+use crate::backend::socket::{SocketEvent, SocketPermission};
+use crate::context::{GcContext, UpdateContext};
+use crate::display_object::TDisplayObject;
+use crate::string::AvmString;
+
+const PROTO_DECLS: &[Declaration] = declare_properties! {
+    "connect" => method(connect; DONT_DELETE | DONT_ENUM);
+    "send" => method(send; DONT_DELETE | DONT_ENUM);
+    "close" => method(close; DONT_DELETE | DONT_ENUM);
+    "onData" => method(on_data; DONT_DELETE | DONT_ENUM);
+};
+
+/// Flash Player doesn't let movies connect to the ports reserved for system services.
+const MIN_PORT: i32 = 1024;
+
+/// The host that a `null` host connects to: the one the movie was loaded from.
+fn movie_host(activation: &Activation<'_, '_>) -> String {
+    let movie = activation.base_clip().movie();
+    match url::Url::parse(movie.url()) {
+        Ok(url) if url.scheme() != "file" => url.host_str().unwrap_or("localhost").to_owned(),
+        _ => "localhost".to_owned(),
+    }
+}
+
+/// Which servers the movie may connect to, or `None` if it may not use sockets at all.
+///
+/// Like in Flash Player, remote movies and local movies with network access need the
+/// server to allow them with a socket policy file. Local movies are trusted as long as
+/// the sandbox isn't enforced, and can't use the network at all otherwise.
+fn socket_permission(activation: &Activation<'_, '_>) -> Option<SocketPermission> {
+    let movie = activation.base_clip().movie();
+    match SandboxType::for_movie(&movie) {
+        SandboxType::Remote => Some(SocketPermission::PolicyFile {
+            domain: Some(movie_host(activation)),
+        }),
+        _ if activation.context.system.sandbox_type == SandboxType::LocalTrusted => {
+            Some(SocketPermission::Trusted)
+        }
+        SandboxType::LocalWithNetwork => Some(SocketPermission::PolicyFile { domain: None }),
+        SandboxType::LocalWithFile | SandboxType::LocalTrusted => None,
+    }
+}
+
+pub fn connect<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let host = match args.get(0) {
+        None | Some(Value::Undefined | Value::Null) => movie_host(activation),
+        Some(host) => host
+            .coerce_to_string(activation)?
+            .to_utf8_lossy()
+            .into_owned(),
+    };
+    let port = args
+        .get(1)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_i32(activation)?;
+    if !(MIN_PORT..=i32::from(u16::MAX)).contains(&port) {
+        return Ok(false.into());
+    }
+    let Some(permission) = socket_permission(activation) else {
+        tracing::warn!(
+            "XMLSocket: a movie in the {} sandbox can't connect to {}:{}",
+            SandboxType::for_movie(&activation.base_clip().movie()),
+            host,
+            port
+        );
+        return Ok(false.into());
+    };
+
+    // A socket only has one connection at a time.
+    close(activation, this, &[])?;
+
+    let handle = activation
+        .context
+        .sockets
+        .connect(&host, port as u16, permission);
+    activation.context.avm1.register_xml_socket(handle, this);
+    Ok(true.into())
+}
+
+pub fn send<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(handle) = activation.context.avm1.xml_socket_handle(this) else {
+        return Ok(Value::Undefined);
+    };
+    // XML objects are sent as their source.
+    let message = args
+        .get(0)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_string(activation)?;
+    let mut data = message.to_utf8_lossy().into_owned().into_bytes();
+    data.push(0);
+    activation.context.sockets.send(handle, data);
+    Ok(Value::Undefined)
+}
+
+pub fn close<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    // Closing the connection from the movie doesn't call `onClose`.
+    if let Some(handle) = activation.context.avm1.xml_socket_handle(this) {
+        activation.context.sockets.close(handle);
+        activation.context.avm1.unregister_xml_socket(handle);
+    }
+    Ok(Value::Undefined)
+}
+
+/// Parses a received message and passes it to `onXML`, unless a movie
+/// replaced `onData` to handle the raw message itself.
+fn on_data<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let src = args.get(0).copied().unwrap_or(Value::Undefined);
+    let xml_constructor = activation.context.avm1.prototypes().xml_constructor;
+    let xml = xml_constructor.construct(activation, &[src])?;
+    this.call_method(
+        "onXML".into(),
+        &[xml],
+        activation,
+        ExecutionReason::FunctionCall,
+    )?;
+    Ok(Value::Undefined)
+}
+
+/// Runs the `onConnect`, `onData` and `onClose` handlers of the sockets
+/// for what happened to their connections.
+pub fn poll_sockets(context: &mut UpdateContext<'_, '_>) {
+    let events = context.sockets.poll();
+    if events.is_empty() {
+        return;
+    }
+
+    let Some(mut activation) = Activation::try_from_stub(
+        context.reborrow(),
+        ActivationIdentifier::root("[XMLSocket]"),
+    ) else {
+        return;
+    };
+
+    for event in events {
+        if let Err(e) = handle_event(&mut activation, event) {
+            tracing::error!("Error in XMLSocket handler: {:?}", e);
+        }
+    }
+}
+
+fn handle_event<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    event: SocketEvent,
+) -> Result<(), Error<'gc>> {
+    match event {
+        SocketEvent::Connected { handle, success } => {
+            let Some((this, _)) = activation.context.avm1.xml_socket(handle) else {
+                return Ok(());
+            };
+            if !success {
+                activation.context.avm1.unregister_xml_socket(handle);
+            }
+            this.call_method(
+                "onConnect".into(),
+                &[success.into()],
+                activation,
+                ExecutionReason::Special,
+            )?;
+        }
+        SocketEvent::Data { handle, data } => {
+            let Some((this, buffer)) = activation.context.avm1.xml_socket(handle) else {
+                return Ok(());
+            };
+            buffer.extend_from_slice(&data);
+            let Some(end) = buffer.iter().rposition(|byte| *byte == 0) else {
+                return Ok(());
+            };
+            let received: Vec<u8> = buffer.drain(..=end).collect();
+
+            for message in received[..end].split(|byte| *byte == 0) {
+                let message = AvmString::new_utf8(
+                    activation.context.gc_context,
+                    String::from_utf8_lossy(message),
+                );
+                this.call_method(
+                    "onData".into(),
+                    &[message.into()],
+                    activation,
+                    ExecutionReason::Special,
+                )?;
+            }
+        }
+        SocketEvent::Closed { handle } => {
+            let Some((this, _)) = activation.context.avm1.xml_socket(handle) else {
+                return Ok(());
+            };
+            activation.context.avm1.unregister_xml_socket(handle);
+            this.call_method("onClose".into(), &[], activation, ExecutionReason::Special)?;
+        }
+    }
+    Ok(())
+}
+
+pub fn constructor<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(this.into())
+}
+
+pub fn create_proto<'gc>(
+    context: &mut GcContext<'_, 'gc>,
+    proto: Object<'gc>,
+    fn_proto: Object<'gc>,
+) -> Object<'gc> {
+    let object = ScriptObject::new(context.gc_context, Some(proto));
+    define_properties_on(PROTO_DECLS, context, object, fn_proto);
+    object.into()
+}
@@ -21,9 +21,10 @@ use crate::avm1::object::value_object::ValueObject;
 use crate::avm1::object::xml_node_object::XmlNodeObject;
 use crate::avm1::object::xml_object::XmlObject;
 use crate::avm1::{Activation, Attribute, Error, ScriptObject, SoundObject, StageObject, Value};
+use crate::camera::Camera;
 use crate::display_object::DisplayObject;
 use crate::display_object::TDisplayObject;
-use crate::html::TextFormat;
+use crate::html::{StyleSheet, TextFormat};
 use crate::streams::NetStream;
 use crate::string::AvmString;
 use crate::xml::XmlNode;
@@ -61,6 +62,8 @@ pub enum NativeObject<'gc> {
     ColorTransform(GcCell<'gc, ColorTransformObject>),
     TextFormat(GcCell<'gc, TextFormat>),
     NetStream(NetStream<'gc>),
+    StyleSheet(StyleSheet<'gc>),
+    Camera(Camera<'gc>),
 }
 
 /// Represents an object that can be directly interacted with by the AVM
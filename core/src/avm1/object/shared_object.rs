@@ -1,7 +1,11 @@
+use crate::backend::remote_shared_object::RemoteSharedObjectHandle;
 use crate::impl_custom_object;
+use flash_lso::types::Value as AmfValue;
 use gc_arena::{Collect, GcCell, MutationContext};
 
 use crate::avm1::{Object, ScriptObject, TObject};
+use std::cell::{Ref, RefMut};
+use std::collections::HashMap;
 use std::fmt;
 
 /// A SharedObject
@@ -17,7 +21,29 @@ pub struct SharedObjectData<'gc> {
 
     /// The local name of this shared object
     name: Option<String>,
-    // In future this will also handle remote SharedObjects
+
+    /// The state of this shared object if it was created with `getRemote`.
+    #[collect(require_static)]
+    remote: Option<RemoteState>,
+}
+
+/// The state of a remote shared object.
+#[derive(Clone, Debug, Default)]
+pub struct RemoteState {
+    /// The URI of the application the shared object belongs to.
+    pub remote_path: String,
+
+    pub persistent: bool,
+
+    /// The connection to the shared object, once `connect` was called.
+    pub handle: Option<RemoteSharedObjectHandle>,
+
+    /// Whether the connection was established, so that changes can be synchronized.
+    pub connected: bool,
+
+    /// The properties of the data object as last synchronized with the server,
+    /// to find the ones that were changed since.
+    pub synced: HashMap<String, AmfValue>,
 }
 
 impl fmt::Debug for SharedObject<'_> {
@@ -25,6 +51,7 @@ impl fmt::Debug for SharedObject<'_> {
         let this = self.0.read();
         f.debug_struct("SharedObject")
             .field("name", &this.name)
+            .field("remote", &this.remote)
             .finish()
     }
 }
@@ -36,6 +63,7 @@ impl<'gc> SharedObject<'gc> {
             SharedObjectData {
                 base: ScriptObject::new(gc_context, Some(proto)),
                 name: None,
+                remote: None,
             },
         ))
     }
@@ -47,6 +75,30 @@ impl<'gc> SharedObject<'gc> {
     pub fn get_name(&self) -> String {
         self.0.read().name.as_ref().cloned().unwrap_or_default()
     }
+
+    pub fn set_remote(&self, gc_context: MutationContext<'gc, '_>, remote: Option<RemoteState>) {
+        self.0.write(gc_context).remote = remote;
+    }
+
+    /// The state of this shared object, if it is a remote one.
+    pub fn remote(&self) -> Option<Ref<'_, RemoteState>> {
+        Ref::filter_map(self.0.read(), |data| data.remote.as_ref()).ok()
+    }
+
+    pub fn remote_handle(&self) -> Option<RemoteSharedObjectHandle> {
+        self.0
+            .read()
+            .remote
+            .as_ref()
+            .and_then(|remote| remote.handle)
+    }
+
+    pub fn remote_mut(
+        &self,
+        gc_context: MutationContext<'gc, '_>,
+    ) -> Option<RefMut<'_, RemoteState>> {
+        RefMut::filter_map(self.0.write(gc_context), |data| data.remote.as_mut()).ok()
+    }
 }
 
 impl<'gc> TObject<'gc> for SharedObject<'gc> {
@@ -1,5 +1,6 @@
-use crate::avm1::{Object, ScriptObject, TObject};
-use crate::display_object::MovieClip;
+use crate::avm1::object_reference::MovieClipReference;
+use crate::avm1::{Activation, Object, ScriptObject, TObject};
+use crate::display_object::{MovieClip, TDisplayObject};
 use crate::impl_custom_object;
 use gc_arena::{Collect, GcCell, MutationContext};
 use std::fmt;
@@ -14,7 +15,12 @@ pub struct TransformObject<'gc>(GcCell<'gc, TransformData<'gc>>);
 pub struct TransformData<'gc> {
     /// The underlying script object.
     base: ScriptObject<'gc>,
-    clip: Option<MovieClip<'gc>>,
+
+    /// The clip that this transform is a view of.
+    ///
+    /// Like other references to clips, this follows the path of the clip, so that a clip
+    /// that is removed and created again is still transformed.
+    clip: Option<MovieClipReference<'gc>>,
 }
 
 impl fmt::Debug for TransformObject<'_> {
@@ -38,11 +44,14 @@ impl<'gc> TransformObject<'gc> {
         ))
     }
 
-    pub fn clip(self) -> Option<MovieClip<'gc>> {
-        self.0.read().clip
+    /// The clip that this transform is a view of, if it currently exists.
+    pub fn clip(self, activation: &mut Activation<'_, 'gc>) -> Option<MovieClip<'gc>> {
+        let clip = self.0.read().clip?;
+        let (_, _, display_object) = clip.resolve_reference(activation)?;
+        display_object.as_movie_clip()
     }
 
-    pub fn set_clip(self, gc_context: MutationContext<'gc, '_>, clip: MovieClip<'gc>) {
+    pub fn set_clip(self, gc_context: MutationContext<'gc, '_>, clip: MovieClipReference<'gc>) {
         self.0.write(gc_context).clip = Some(clip)
     }
 }
@@ -6,6 +6,7 @@ use crate::avm1::object::TObject;
 use crate::avm1::property_map::PropertyMap;
 use crate::avm1::scope::Scope;
 use crate::avm1::{scope, Activation, ActivationIdentifier, Error, Object, Value};
+use crate::backend::socket::SocketHandle;
 use crate::context::{GcContext, UpdateContext};
 use crate::frame_lifecycle::FramePhase;
 use crate::prelude::*;
@@ -14,6 +15,7 @@ use crate::tag_utils::SwfSlice;
 use crate::{avm1, avm_debug};
 use gc_arena::{Collect, Gc, GcCell, MutationContext};
 use std::borrow::Cow;
+use std::collections::HashMap;
 use swf::avm1::read::Reader;
 use tracing::instrument;
 
@@ -68,6 +70,14 @@ pub struct Avm1<'gc> {
     constructor_registry_case_insensitive: PropertyMap<'gc, FunctionObject<'gc>>,
     constructor_registry_case_sensitive: PropertyMap<'gc, FunctionObject<'gc>>,
 
+    /// The `LocalConnection` objects listening to each connection, by full connection name,
+    /// along with the domain of the movie that connected them.
+    local_connections: HashMap<String, (Object<'gc>, String)>,
+
+    /// The `XMLSocket` objects with an open connection, by connection handle,
+    /// along with the bytes received after their last complete message.
+    xml_sockets: HashMap<SocketHandle, (Object<'gc>, Vec<u8>)>,
+
     #[cfg(feature = "avm_debug")]
     pub debug_output: bool,
 }
@@ -97,6 +107,8 @@ impl<'gc> Avm1<'gc> {
             clip_exec_list: None,
             constructor_registry_case_insensitive: PropertyMap::new(),
             constructor_registry_case_sensitive: PropertyMap::new(),
+            local_connections: HashMap::new(),
+            xml_sockets: HashMap::new(),
 
             #[cfg(feature = "avm_debug")]
             debug_output: false,
@@ -509,6 +521,59 @@ impl<'gc> Avm1<'gc> {
         registry.get(symbol, is_case_sensitive)
     }
 
+    /// The `LocalConnection` object listening to a connection, and the domain of its movie.
+    pub fn local_connection(&self, connection_name: &str) -> Option<(Object<'gc>, &str)> {
+        self.local_connections
+            .get(connection_name)
+            .map(|(connection, domain)| (*connection, domain.as_str()))
+    }
+
+    /// The connection that a `LocalConnection` object listens to.
+    pub fn local_connection_name(&self, connection: Object<'gc>) -> Option<&str> {
+        self.local_connections
+            .iter()
+            .find(|(_, (other, _))| Object::ptr_eq(*other, connection))
+            .map(|(name, _)| name.as_str())
+    }
+
+    pub fn register_local_connection(
+        &mut self,
+        connection_name: String,
+        connection: Object<'gc>,
+        domain: String,
+    ) {
+        self.local_connections
+            .insert(connection_name, (connection, domain));
+    }
+
+    pub fn unregister_local_connection(&mut self, connection_name: &str) {
+        self.local_connections.remove(connection_name);
+    }
+
+    /// The `XMLSocket` object using a connection, and the bytes it received
+    /// after its last complete message.
+    pub fn xml_socket(&mut self, handle: SocketHandle) -> Option<(Object<'gc>, &mut Vec<u8>)> {
+        self.xml_sockets
+            .get_mut(&handle)
+            .map(|(socket, buffer)| (*socket, buffer))
+    }
+
+    /// The connection that an `XMLSocket` object uses.
+    pub fn xml_socket_handle(&self, socket: Object<'gc>) -> Option<SocketHandle> {
+        self.xml_sockets
+            .iter()
+            .find(|(_, (other, _))| Object::ptr_eq(*other, socket))
+            .map(|(handle, _)| *handle)
+    }
+
+    pub fn register_xml_socket(&mut self, handle: SocketHandle, socket: Object<'gc>) {
+        self.xml_sockets.insert(handle, (socket, Vec::new()));
+    }
+
+    pub fn unregister_xml_socket(&mut self, handle: SocketHandle) {
+        self.xml_sockets.remove(&handle);
+    }
+
     pub fn register_constructor(
         &mut self,
         swf_version: u8,
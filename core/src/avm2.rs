@@ -8,9 +8,11 @@ use crate::avm2::script::{Script, TranslationUnit};
 use crate::context::{GcContext, UpdateContext};
 use crate::display_object::{DisplayObject, DisplayObjectWeak, TDisplayObject};
 use crate::string::AvmString;
+use crate::tag_utils::SwfMovie;
 
 use fnv::FnvHashMap;
 use gc_arena::{Collect, GcCell, MutationContext};
+use std::sync::Arc;
 use swf::avm2::read::Reader;
 use swf::DoAbc2Flag;
 
@@ -30,12 +32,14 @@ pub mod bytearray;
 mod call_stack;
 mod class;
 mod domain;
+mod dynamic_map;
 mod e4x;
 pub mod error;
 mod events;
 mod filters;
 mod function;
 pub mod globals;
+mod leak_detector;
 mod method;
 mod multiname;
 mod namespace;
@@ -52,6 +56,7 @@ mod stubs;
 mod traits;
 mod value;
 mod vector;
+mod verify;
 mod vtable;
 
 pub use crate::avm2::activation::Activation;
@@ -60,14 +65,18 @@ pub use crate::avm2::call_stack::{CallNode, CallStack};
 pub use crate::avm2::domain::Domain;
 pub use crate::avm2::error::Error;
 pub use crate::avm2::globals::flash::ui::context_menu::make_context_menu_state;
+pub use crate::avm2::leak_detector::{probable_leaks, LeakDetector, ProbableLeak};
 pub use crate::avm2::multiname::Multiname;
 pub use crate::avm2::namespace::Namespace;
 pub use crate::avm2::object::{
     ArrayObject, ClassObject, EventObject, Object, ScriptObject, SoundChannelObject, StageObject,
     TObject,
 };
+pub use crate::avm2::property::Property;
 pub use crate::avm2::qname::QName;
 pub use crate::avm2::value::Value;
+#[cfg(feature = "fuzzing")]
+pub use crate::avm2::verify::verify_abc_standalone;
 
 use self::scope::Scope;
 
@@ -125,6 +134,17 @@ pub struct Avm2<'gc> {
     /// collector does not support weak references.
     broadcast_list: FnvHashMap<AvmString<'gc>, Vec<Object<'gc>>>,
 
+    /// How many listeners are registered for each event type, on any object.
+    ///
+    /// Events of a type without listeners can't be observed by any object they
+    /// would be dispatched to, so they don't need to be dispatched at all.
+    ///
+    /// The counts are conservative: they only go down when a listener is removed,
+    /// or a dead weak listener is pruned from its dispatch list. Listeners of
+    /// objects that were garbage collected stay counted, which only costs a full
+    /// dispatch, but a listener that can still be called is never missed.
+    listener_counts: FnvHashMap<AvmString<'gc>, u32>,
+
     /// The list of 'orphan' objects - these objects have no parent,
     /// so we need to manually run their frames in `run_all_phases_avm2` to match
     /// Flash's behavior. Clips are added to this list with `add_orphan_movie`.
@@ -136,6 +156,10 @@ pub struct Avm2<'gc> {
     /// strong references around (this matches Flash's behavior).
     orphan_objects: Vec<DisplayObjectWeak<'gc>>,
 
+    /// Tracks display objects removed from the stage, if leak detection is enabled.
+    #[collect(require_static)]
+    pub leak_detector: Option<LeakDetector>,
+
     #[cfg(feature = "avm_debug")]
     pub debug_output: bool,
 }
@@ -171,9 +195,12 @@ impl<'gc> Avm2<'gc> {
             native_instance_init_table: Default::default(),
             native_call_handler_table: Default::default(),
             broadcast_list: Default::default(),
+            listener_counts: Default::default(),
 
             orphan_objects: Vec::new(),
 
+            leak_detector: None,
+
             #[cfg(feature = "avm_debug")]
             debug_output: false,
         }
@@ -325,6 +352,26 @@ impl<'gc> Avm2<'gc> {
         }
     }
 
+    /// Records that a listener for an event type was added to, or removed
+    /// from, the dispatch list of any object.
+    pub fn count_event_listener(&mut self, event_type: AvmString<'gc>, added: bool) {
+        if added {
+            *self.listener_counts.entry(event_type).or_default() += 1;
+        } else if let Some(count) = self.listener_counts.get_mut(&event_type) {
+            *count -= 1;
+            if *count == 0 {
+                self.listener_counts.remove(&event_type);
+            }
+        }
+    }
+
+    /// Whether any object may have a listener for an event type.
+    ///
+    /// This can return `true` when no listener is left, but never `false` when there is one.
+    pub fn has_event_listeners(&self, event_type: AvmString<'gc>) -> bool {
+        self.listener_counts.contains_key(&event_type)
+    }
+
     /// Add an object to the broadcast list.
     ///
     /// Each broadcastable event contains it's own broadcast list. You must
@@ -438,6 +485,7 @@ impl<'gc> Avm2<'gc> {
         name: Option<AvmString<'gc>>,
         flags: DoAbc2Flag,
         domain: Domain<'gc>,
+        movie: Option<Arc<SwfMovie>>,
     ) -> Result<(), Error<'gc>> {
         let mut reader = Reader::new(data);
         let abc = match reader.read() {
@@ -451,9 +499,10 @@ impl<'gc> Avm2<'gc> {
                 )?));
             }
         };
+        verify::verify_abc(context, &abc)?;
 
         let num_scripts = abc.scripts.len();
-        let tunit = TranslationUnit::from_abc(abc, domain, name, context.gc_context);
+        let tunit = TranslationUnit::from_abc(abc, domain, name, movie, context.gc_context);
         for i in 0..num_scripts {
             tunit.load_script(i as u32, context)?;
         }
@@ -528,7 +577,9 @@ impl<'gc> Avm2<'gc> {
     fn peek(&mut self, index: usize) -> Value<'gc> {
         let value = self
             .stack
-            .get(self.stack.len() - index - 1)
+            .len()
+            .checked_sub(index + 1)
+            .and_then(|index| self.stack.get(index))
             .copied()
             .unwrap_or_else(|| {
                 tracing::warn!("Avm1::pop: Stack underflow");
@@ -5,14 +5,15 @@ use crate::avm2::class::Class;
 use crate::avm2::domain::Domain;
 use crate::avm2::e4x::{escape_attribute_value, escape_element_value};
 use crate::avm2::error::{
-    argument_error, make_null_or_undefined_error, make_reference_error, type_error,
-    ReferenceErrorCode,
+    argument_error, make_null_or_undefined_error, make_reference_error, script_timeout_error,
+    type_error, verify_error, ReferenceErrorCode,
 };
 use crate::avm2::method::{BytecodeMethod, Method, ParamConfig};
 use crate::avm2::object::{
     ArrayObject, ByteArrayObject, ClassObject, FunctionObject, NamespaceObject, ScriptObject,
 };
 use crate::avm2::object::{Object, TObject};
+use crate::avm2::property::Property;
 use crate::avm2::scope::{search_scope_stack, Scope, ScopeChain};
 use crate::avm2::script::Script;
 use crate::avm2::value::Value;
@@ -21,12 +22,15 @@ use crate::avm2::Namespace;
 use crate::avm2::QName;
 use crate::avm2::{value, Avm2, Error};
 use crate::context::{GcContext, UpdateContext};
+use crate::limits::ScriptTimeout;
 use crate::string::{AvmAtom, AvmString};
 use crate::swf::extensions::ReadSwfExt;
+use crate::tag_utils::SwfMovie;
 use gc_arena::{Gc, GcCell};
 use smallvec::SmallVec;
 use std::borrow::Cow;
 use std::cmp::{min, Ordering};
+use std::sync::Arc;
 use swf::avm2::read::Reader;
 use swf::avm2::types::{
     Class as AbcClass, Exception, Index, Method as AbcMethod, MethodFlags as AbcMethodFlags,
@@ -681,6 +685,18 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         self.caller_domain.expect("No caller domain available - use Activation::from_domain when constructing your domain")
     }
 
+    /// Returns the movie whose code is currently running, ignoring any
+    /// playerglobals it called into.
+    pub fn caller_movie(&self) -> Option<Arc<SwfMovie>> {
+        self.context.avm2.call_stack().read().caller_movie()
+    }
+
+    /// Returns the movie of the running script, or the root movie when no script is running.
+    pub fn caller_movie_or_root(&self) -> Arc<SwfMovie> {
+        self.caller_movie()
+            .unwrap_or_else(|| self.context.swf.clone())
+    }
+
     /// Returns the global scope of this activation.
     ///
     /// The global scope refers to scope at the bottom of the
@@ -926,6 +942,7 @@ impl<'a, 'gc> Activation<'a, 'gc> {
             .body()
             .ok_or_else(|| "Cannot execute non-native method without body".into());
         let body = body?;
+        method.verify(self)?;
         let mut reader = Reader::new(&body.code);
 
         let val = loop {
@@ -1001,11 +1018,25 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         self.actions_since_timeout_check += 1;
         if self.actions_since_timeout_check >= 2000 {
             self.actions_since_timeout_check = 0;
-            if self.context.update_start.elapsed() >= self.context.max_execution_duration {
-                return Err(
-                    "A script in this movie has taken too long to execute and has been terminated."
-                        .into(),
-                );
+            let timeout = self.context.max_execution_duration.as_secs();
+            match self.context.check_script_timeout() {
+                ScriptTimeout::None | ScriptTimeout::Aborted => {}
+                ScriptTimeout::Abort => {
+                    let error = Error::AvmError(script_timeout_error(
+                        self,
+                        &format!("Error #1502: A script has executed for longer than the default timeout period of {timeout} seconds."),
+                        1502,
+                    )?);
+                    let instruction_start = reader.pos(full_data);
+                    return self.handle_err(method, reader, full_data, instruction_start, error);
+                }
+                ScriptTimeout::Terminate => {
+                    return Err(format!(
+                        "Error #1503: A script failed to exit after {} seconds and was terminated.",
+                        timeout * 2
+                    )
+                    .into());
+                }
             }
         }
 
@@ -1035,13 +1066,13 @@ impl<'a, 'gc> Activation<'a, 'gc> {
                 Op::Call { num_args } => self.op_call(num_args),
                 Op::CallMethod { index, num_args } => self.op_call_method(index, num_args),
                 Op::CallProperty { index, num_args } => {
-                    self.op_call_property(method, index, num_args)
+                    self.op_call_property(method, instruction_start, index, num_args)
                 }
                 Op::CallPropLex { index, num_args } => {
                     self.op_call_prop_lex(method, index, num_args)
                 }
                 Op::CallPropVoid { index, num_args } => {
-                    self.op_call_prop_void(method, index, num_args)
+                    self.op_call_prop_void(method, instruction_start, index, num_args)
                 }
                 Op::CallStatic { index, num_args } => self.op_call_static(method, index, num_args),
                 Op::CallSuper { index, num_args } => self.op_call_super(method, index, num_args),
@@ -1050,8 +1081,8 @@ impl<'a, 'gc> Activation<'a, 'gc> {
                 }
                 Op::ReturnValue => self.op_return_value(),
                 Op::ReturnVoid => self.op_return_void(),
-                Op::GetProperty { index } => self.op_get_property(method, index),
-                Op::SetProperty { index } => self.op_set_property(method, index),
+                Op::GetProperty { index } => self.op_get_property(method, instruction_start, index),
+                Op::SetProperty { index } => self.op_set_property(method, instruction_start, index),
                 Op::InitProperty { index } => self.op_init_property(method, index),
                 Op::DeleteProperty { index } => self.op_delete_property(method, index),
                 Op::GetSuper { index } => self.op_get_super(method, index),
@@ -1206,8 +1237,14 @@ impl<'a, 'gc> Activation<'a, 'gc> {
             }
             result
         } else if let Err(e) = op {
+            // Every instruction was decoded when the method was verified, so
+            // this can only happen when execution runs past the last one.
             tracing::error!("Parse error: {:?}", e);
-            Err(Error::RustError(Box::new(e)))
+            Err(Error::AvmError(verify_error(
+                self,
+                "Error #1020: Code cannot fall off the end of a method.",
+                1020,
+            )?))
         } else {
             unreachable!();
         }
@@ -1362,10 +1399,9 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         // The entire implementation of VTable assumes that
         // call_method is never encountered. (see the long comment there)
         // This was also the conlusion from analysing avmplus behavior - they
-        // unconditionally VerifyError upon noticing it.
-        // If we ever reach here, let's immediately panic instead of erroring,
-        // so we get an issue report ASAP.
-        panic!("Call_method is not supported");
+        // unconditionally VerifyError upon noticing it, and so does our
+        // verifier, so this can't be reached.
+        unreachable!("Call_method is rejected by the verifier");
 
         #[allow(unreachable_code)]
         {
@@ -1380,18 +1416,57 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         }
     }
 
+    /// Resolve the trait that a static property name refers to on an object,
+    /// using the inline cache of the instruction at `instruction_start`.
+    ///
+    /// Names with runtime components must not be resolved with this, as they
+    /// can refer to a different trait every time the instruction runs.
+    fn resolve_trait_cached(
+        &mut self,
+        method: Gc<'gc, BytecodeMethod<'gc>>,
+        instruction_start: usize,
+        object: Object<'gc>,
+        multiname: &Multiname<'gc>,
+    ) -> Option<Property> {
+        debug_assert!(!multiname.has_lazy_component());
+        let vtable = object.vtable()?;
+        let offset = instruction_start as u32;
+        if let Some(property) = method.cached_trait(offset, vtable) {
+            return Some(property);
+        }
+
+        let property = vtable.get_trait(multiname)?;
+        method.cache_trait(self.context.gc_context, offset, vtable, property);
+        Some(property)
+    }
+
     fn op_call_property(
         &mut self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
+        instruction_start: usize,
         index: Index<AbcMultiname>,
         arg_count: u32,
     ) -> Result<FrameControl<'gc>, Error<'gc>> {
         let args = self.pop_stack_args(arg_count);
-        let multiname = self.pool_multiname_and_initialize(method, index)?;
+        let multiname = self.pool_maybe_uninitialized_multiname(method, index)?;
+
+        // default path for static names
+        if !multiname.has_lazy_component() {
+            let receiver = self
+                .pop_stack()
+                .coerce_to_object_or_typeerror(self, Some(&multiname))?;
+            let property =
+                self.resolve_trait_cached(method, instruction_start, receiver, &multiname);
+            let value = receiver.call_resolved_property(&multiname, property, &args, self)?;
+            self.push_stack(value);
+            return Ok(FrameControl::Continue);
+        }
+
+        // main path for dynamic names
+        let multiname = multiname.fill_with_runtime_params(self)?;
         let receiver = self
             .pop_stack()
             .coerce_to_object_or_typeerror(self, Some(&multiname))?;
-
         let value = receiver.call_property(&multiname, &args, self)?;
 
         self.push_stack(value);
@@ -1425,15 +1500,29 @@ impl<'a, 'gc> Activation<'a, 'gc> {
     fn op_call_prop_void(
         &mut self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
+        instruction_start: usize,
         index: Index<AbcMultiname>,
         arg_count: u32,
     ) -> Result<FrameControl<'gc>, Error<'gc>> {
         let args = self.pop_stack_args(arg_count);
-        let multiname = self.pool_multiname_and_initialize(method, index)?;
+        let multiname = self.pool_maybe_uninitialized_multiname(method, index)?;
+
+        // default path for static names
+        if !multiname.has_lazy_component() {
+            let receiver = self
+                .pop_stack()
+                .coerce_to_object_or_typeerror(self, Some(&multiname))?;
+            let property =
+                self.resolve_trait_cached(method, instruction_start, receiver, &multiname);
+            receiver.call_resolved_property(&multiname, property, &args, self)?;
+            return Ok(FrameControl::Continue);
+        }
+
+        // main path for dynamic names
+        let multiname = multiname.fill_with_runtime_params(self)?;
         let receiver = self
             .pop_stack()
             .coerce_to_object_or_typeerror(self, Some(&multiname))?;
-
         receiver.call_property(&multiname, &args, self)?;
 
         Ok(FrameControl::Continue)
@@ -1511,6 +1600,7 @@ impl<'a, 'gc> Activation<'a, 'gc> {
     fn op_get_property(
         &mut self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
+        instruction_start: usize,
         index: Index<AbcMultiname>,
     ) -> Result<FrameControl<'gc>, Error<'gc>> {
         let multiname = self.pool_maybe_uninitialized_multiname(method, index)?;
@@ -1519,7 +1609,8 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         if !multiname.has_lazy_component() {
             let object = self.pop_stack();
             let object = object.coerce_to_object_or_typeerror(self, Some(&multiname))?;
-            let value = object.get_property(&multiname, self)?;
+            let property = self.resolve_trait_cached(method, instruction_start, object, &multiname);
+            let value = object.get_resolved_property(&multiname, property, self)?;
             self.push_stack(value);
             return Ok(FrameControl::Continue);
         }
@@ -1558,6 +1649,7 @@ impl<'a, 'gc> Activation<'a, 'gc> {
     fn op_set_property(
         &mut self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
+        instruction_start: usize,
         index: Index<AbcMultiname>,
     ) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value = self.pop_stack();
@@ -1567,7 +1659,8 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         if !multiname.has_lazy_component() {
             let object = self.pop_stack();
             let mut object = object.coerce_to_object_or_typeerror(self, Some(&multiname))?;
-            object.set_property(&multiname, value, self)?;
+            let property = self.resolve_trait_cached(method, instruction_start, object, &multiname);
+            object.set_resolved_property(&multiname, property, value, self)?;
             return Ok(FrameControl::Continue);
         }
 
@@ -1736,6 +1829,7 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         index: Index<Exception>,
     ) -> Result<FrameControl<'gc>, Error<'gc>> {
         if let Some(body) = method.body() {
+            // The verifier checked that the exception exists.
             let ex = &body.exceptions[index.0 as usize];
             let vname = ex.variable_name;
             let so = if vname.0 == 0 {
@@ -1,13 +1,54 @@
-use crate::avm2::bytearray::ByteArrayStorage;
+use crate::avm2::bytearray::{ByteArrayStorage, ObjectEncoding};
 use crate::avm2::object::{ByteArrayObject, TObject};
 use crate::avm2::ArrayObject;
 use crate::avm2::ArrayStorage;
 use crate::avm2::{Activation, Error, Object, Value};
 use crate::string::AvmString;
 use enumset::EnumSet;
+use flash_lso::amf0::read::AMF0Decoder;
+use flash_lso::amf3::read::AMF3Decoder;
 use flash_lso::types::{AMFVersion, Element, Lso};
 use flash_lso::types::{Attribute, ClassDefinition, Value as AmfValue};
 
+impl From<ObjectEncoding> for AMFVersion {
+    fn from(encoding: ObjectEncoding) -> Self {
+        match encoding {
+            ObjectEncoding::Amf0 => AMFVersion::AMF0,
+            ObjectEncoding::Amf3 => AMFVersion::AMF3,
+        }
+    }
+}
+
+/// Encodes a single AMF value, as written by `ByteArray.writeObject`.
+///
+/// Every call starts with empty reference tables, so the encoded value
+/// never refers to a previously encoded one.
+pub fn encode_value(value: AmfValue, amf_version: AMFVersion) -> Option<Vec<u8>> {
+    let element = Element::new("", value);
+    let mut lso = Lso::new(vec![element], "", amf_version);
+    let bytes = flash_lso::write::write_to_bytes(&mut lso).ok()?;
+    // This is kind of hacky: We need to strip out the header and any padding so that we only write
+    // the value. In the future, there should be a method to do this in the flash_lso crate.
+    let element_padding = match amf_version {
+        AMFVersion::AMF0 => 8,
+        AMFVersion::AMF3 => 7,
+    };
+    let start = flash_lso::write::header_length(&lso.header) + element_padding;
+    Some(bytes[start..bytes.len() - 1].to_vec())
+}
+
+/// Decodes a single AMF value, as read by `ByteArray.readObject`,
+/// returning it along with the number of bytes it took.
+///
+/// Like `encode_value`, every call starts with empty reference tables.
+pub fn decode_value(bytes: &[u8], amf_version: AMFVersion) -> Option<(AmfValue, usize)> {
+    let (rest, value) = match amf_version {
+        AMFVersion::AMF0 => AMF0Decoder::default().parse_single_element(bytes).ok()?,
+        AMFVersion::AMF3 => AMF3Decoder::default().parse_single_element(bytes).ok()?,
+    };
+    Some((value, bytes.len() - rest.len()))
+}
+
 /// Serialize a Value to an AmfValue
 pub fn serialize_value<'gc>(
     activation: &mut Activation<'_, 'gc>,
@@ -58,6 +99,13 @@ pub fn serialize_value<'gc>(
                     let len = sparse.len() as u32;
                     Some(AmfValue::ECMAArray(dense, sparse, len))
                 }
+            } else if let Some(bytearray) = o.as_bytearray() {
+                let bytes = AmfValue::ByteArray(bytearray.bytes().to_vec());
+                // AMF0 has no type for byte arrays, so Flash switches to AMF3 for them.
+                match amf_version {
+                    AMFVersion::AMF0 => Some(AmfValue::AMF3(Box::new(bytes))),
+                    AMFVersion::AMF3 => Some(bytes),
+                }
             } else if let Some(date) = o.as_date_object() {
                 date.date_time()
                     .map(|date_time| AmfValue::Date(date_time.timestamp_millis() as f64, None))
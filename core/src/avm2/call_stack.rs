@@ -2,7 +2,9 @@ use crate::avm2::function::{display_function, Executable};
 use crate::avm2::method::Method;
 use crate::avm2::object::ClassObject;
 use crate::string::WString;
+use crate::tag_utils::SwfMovie;
 use gc_arena::Collect;
+use std::sync::Arc;
 
 use super::script::Script;
 
@@ -72,6 +74,19 @@ impl<'gc> CallStack<'gc> {
     pub fn is_empty(&self) -> bool {
         self.stack.is_empty()
     }
+
+    /// The movie of the innermost call made by the code of a movie, skipping
+    /// native methods and Ruffle's own playerglobals.
+    pub fn caller_movie(&self) -> Option<Arc<SwfMovie>> {
+        self.stack.iter().rev().find_map(|call| match call {
+            CallNode::GlobalInit(script) => script.translation_unit()?.movie(),
+            CallNode::Method {
+                method: Method::Bytecode(method),
+                ..
+            } => method.txunit.movie(),
+            CallNode::Method { .. } => None,
+        })
+    }
 }
 
 impl<'gc> Default for CallStack<'gc> {
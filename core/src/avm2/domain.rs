@@ -244,6 +244,14 @@ impl<'gc> Domain<'gc> {
         self.0.write(mc).defs.insert(name, script);
     }
 
+    /// Remove a definition from the current application domain, so that it
+    /// can't be looked up anymore.
+    pub fn remove_definition(&self, name: QName<'gc>, mc: MutationContext<'gc, '_>) {
+        let mut write = self.0.write(mc);
+        write.defs.remove(name);
+        write.classes.remove(name);
+    }
+
     pub fn export_class(&self, class: GcCell<'gc, Class<'gc>>, mc: MutationContext<'gc, '_>) {
         self.0.write(mc).classes.insert(class.read().name(), class);
     }
@@ -0,0 +1,173 @@
+//! Dynamic property map
+
+use crate::avm2::AvmString;
+use crate::avm2::Value;
+use fnv::FnvHashMap;
+use gc_arena::{Collect, CollectionContext};
+use smallvec::SmallVec;
+
+/// How many dynamic properties an object stores inline, before the rest
+/// go in a hash map.
+const INLINE_CAPACITY: usize = 4;
+
+/// Type which represents the dynamic properties of an object.
+///
+/// Most objects only have a handful of dynamic properties, which are stored
+/// inline and found by comparing their names one after the other. This avoids
+/// hashing the name and following pointers into a separately allocated table,
+/// which is what objects with more properties fall back to.
+///
+/// Only `HashMap` methods that we need are implemented.
+#[derive(Clone, Debug, Default)]
+pub struct DynamicMap<'gc> {
+    /// The first properties of the object, which never spill onto the heap.
+    inline: SmallVec<[(AvmString<'gc>, Value<'gc>); INLINE_CAPACITY]>,
+
+    /// The properties that didn't fit inline.
+    overflow: FnvHashMap<AvmString<'gc>, Value<'gc>>,
+}
+
+// Implemented by hand, because SmallVec isn't garbage-collectable.
+unsafe impl<'gc> Collect for DynamicMap<'gc> {
+    #[inline]
+    fn trace(&self, cc: CollectionContext) {
+        for (key, value) in self.inline.iter() {
+            key.trace(cc);
+            value.trace(cc);
+        }
+        for (key, value) in self.overflow.iter() {
+            key.trace(cc);
+            value.trace(cc);
+        }
+    }
+}
+
+impl<'gc> DynamicMap<'gc> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get(&self, name: &AvmString<'gc>) -> Option<&Value<'gc>> {
+        self.inline
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value)
+            .or_else(|| self.overflow.get(name))
+    }
+
+    pub fn contains_key(&self, name: &AvmString<'gc>) -> bool {
+        self.get(name).is_some()
+    }
+
+    /// Sets the value of a property, returning its previous value if it already existed.
+    pub fn insert(&mut self, name: AvmString<'gc>, value: Value<'gc>) -> Option<Value<'gc>> {
+        if let Some((_, old)) = self.inline.iter_mut().find(|(key, _)| *key == name) {
+            return Some(std::mem::replace(old, value));
+        }
+
+        if self.inline.len() < INLINE_CAPACITY && !self.overflow.contains_key(&name) {
+            self.inline.push((name, value));
+            None
+        } else {
+            self.overflow.insert(name, value)
+        }
+    }
+
+    pub fn remove(&mut self, name: &AvmString<'gc>) -> Option<Value<'gc>> {
+        if let Some(index) = self.inline.iter().position(|(key, _)| key == name) {
+            Some(self.inline.swap_remove(index).1)
+        } else {
+            self.overflow.remove(name)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NAMES: [&str; 6] = ["a", "b", "c", "d", "e", "f"];
+
+    fn filled_map() -> DynamicMap<'static> {
+        let mut map = DynamicMap::new();
+        for (i, name) in NAMES.iter().enumerate() {
+            assert!(map
+                .insert((*name).into(), Value::Integer(i as i32))
+                .is_none());
+        }
+        map
+    }
+
+    fn get_integer(map: &DynamicMap<'static>, name: &'static str) -> Option<i32> {
+        match map.get(&name.into()) {
+            Some(Value::Integer(value)) => Some(*value),
+            Some(value) => panic!("Unexpected value {value:?}"),
+            None => None,
+        }
+    }
+
+    #[test]
+    fn test_overflow() {
+        let map = filled_map();
+        assert_eq!(map.inline.len(), INLINE_CAPACITY);
+        assert_eq!(map.overflow.len(), NAMES.len() - INLINE_CAPACITY);
+        for (i, name) in NAMES.iter().enumerate() {
+            assert_eq!(get_integer(&map, name), Some(i as i32));
+        }
+        assert!(!map.contains_key(&"g".into()));
+    }
+
+    #[test]
+    fn test_replace() {
+        let mut map = filled_map();
+        assert!(matches!(
+            map.insert("b".into(), Value::Integer(10)),
+            Some(Value::Integer(1))
+        ));
+        assert!(matches!(
+            map.insert("f".into(), Value::Integer(50)),
+            Some(Value::Integer(5))
+        ));
+        assert_eq!(get_integer(&map, "b"), Some(10));
+        assert_eq!(get_integer(&map, "f"), Some(50));
+        assert_eq!(map.inline.len() + map.overflow.len(), NAMES.len());
+    }
+
+    #[test]
+    fn test_remove() {
+        let mut map = filled_map();
+        assert!(matches!(map.remove(&"a".into()), Some(Value::Integer(0))));
+        assert!(matches!(map.remove(&"e".into()), Some(Value::Integer(4))));
+        assert!(map.remove(&"a".into()).is_none());
+        assert!(map.remove(&"g".into()).is_none());
+        assert!(!map.contains_key(&"a".into()));
+        assert!(!map.contains_key(&"e".into()));
+        for name in ["b", "c", "d", "f"] {
+            assert!(map.contains_key(&name.into()));
+        }
+    }
+
+    #[test]
+    fn test_reinsert() {
+        let mut map = filled_map();
+        map.remove(&"a".into());
+
+        // The free inline slot must not get a second copy of an overflowing property.
+        assert!(matches!(
+            map.insert("f".into(), Value::Integer(50)),
+            Some(Value::Integer(5))
+        ));
+        assert_eq!(map.inline.len(), INLINE_CAPACITY - 1);
+        assert!(map.remove(&"f".into()).is_some());
+        assert!(!map.contains_key(&"f".into()));
+
+        assert!(map.insert("a".into(), Value::Integer(100)).is_none());
+        assert_eq!(map.inline.len(), INLINE_CAPACITY);
+        assert_eq!(get_integer(&map, "a"), Some(100));
+
+        map.remove(&"e".into());
+        assert!(map.insert("e".into(), Value::Integer(40)).is_none());
+        assert_eq!(get_integer(&map, "e"), Some(40));
+        assert_eq!(map.inline.len() + map.overflow.len(), NAMES.len() - 1);
+    }
+}
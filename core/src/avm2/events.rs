@@ -1,16 +1,15 @@
 //! Core event structure
 
 use crate::avm2::activation::Activation;
-use crate::avm2::object::{Object, TObject};
+use crate::avm2::object::{FunctionObjectWeak, Object, TObject};
 use crate::avm2::value::Value;
 use crate::avm2::Error;
 use crate::avm2::Multiname;
 use crate::display_object::TDisplayObject;
 use crate::string::AvmString;
 use fnv::FnvHashMap;
-use gc_arena::Collect;
+use gc_arena::{Collect, MutationContext};
 use std::collections::BTreeMap;
-use std::hash::{Hash, Hasher};
 
 /// Which phase of event dispatch is currently occurring.
 #[derive(Copy, Clone, Collect, Debug, PartialEq, Eq)]
@@ -222,52 +221,78 @@ impl<'gc> DispatchList<'gc> {
     /// This enforces the invariant that an `EventHandler` must not appear at
     /// more than one priority (since we can't enforce that with clever-er data
     /// structure selection). If an event handler already exists, it will not
-    /// be added again, and this function will silently fail.
+    /// be added again, and this function will return `false`.
+    ///
+    /// If `use_weak_reference` is set, the dispatch list will not keep the
+    /// handler alive, and it will stop being called once it's collected.
     pub fn add_event_listener(
         &mut self,
         event: impl Into<AvmString<'gc>> + Clone,
         priority: i32,
         handler: Object<'gc>,
         use_capture: bool,
-    ) {
-        let new_handler = EventHandler::new(handler, use_capture);
-
+        use_weak_reference: bool,
+        mc: MutationContext<'gc, '_>,
+    ) -> bool {
         if let Some(event_sheaf) = self.get_event(event.clone()) {
             for (_other_prio, other_set) in event_sheaf.iter() {
-                if other_set.contains(&new_handler) {
-                    return;
+                if other_set.iter().any(|h| h.is_for(handler, use_capture, mc)) {
+                    return false;
                 }
             }
         }
 
         self.get_event_priority_mut(event, priority)
-            .push(new_handler);
+            .push(EventHandler::new(handler, use_capture, use_weak_reference));
+        true
     }
 
     /// Remove an event handler from this dispatch list.
     ///
     /// Any listener that has the same handler and capture-phase flag will be
-    /// removed from any priority in the list.
+    /// removed from any priority in the list. Returns whether it was found.
     pub fn remove_event_listener(
         &mut self,
         event: impl Into<AvmString<'gc>>,
         handler: Object<'gc>,
         use_capture: bool,
-    ) {
-        let old_handler = EventHandler::new(handler, use_capture);
-
+        mc: MutationContext<'gc, '_>,
+    ) -> bool {
+        let mut removed = false;
         for (_prio, set) in self.get_event_mut(event).iter_mut() {
-            if let Some(pos) = set.iter().position(|h| *h == old_handler) {
+            if let Some(pos) = set.iter().position(|h| h.is_for(handler, use_capture, mc)) {
                 set.remove(pos);
+                removed = true;
             }
         }
+        removed
+    }
+
+    /// Remove the weakly referenced handlers of an event that have been
+    /// collected, returning how many were removed.
+    pub fn remove_dead_handlers(
+        &mut self,
+        event: impl Into<AvmString<'gc>>,
+        mc: MutationContext<'gc, '_>,
+    ) -> usize {
+        let mut removed = 0;
+        for (_prio, set) in self.get_event_mut(event).iter_mut() {
+            let len = set.len();
+            set.retain(|h| h.handler.upgrade(mc).is_some());
+            removed += len - set.len();
+        }
+        removed
     }
 
-    /// Determine if there are any event listeners in this dispatch list.
-    pub fn has_event_listener(&self, event: impl Into<AvmString<'gc>>) -> bool {
+    /// Determine if there are any live event listeners in this dispatch list.
+    pub fn has_event_listener(
+        &self,
+        event: impl Into<AvmString<'gc>>,
+        mc: MutationContext<'gc, '_>,
+    ) -> bool {
         if let Some(event_sheaf) = self.get_event(event) {
             for (_prio, set) in event_sheaf.iter() {
-                if !set.is_empty() {
+                if set.iter().any(|h| h.handler.upgrade(mc).is_some()) {
                     return true;
                 }
             }
@@ -284,17 +309,20 @@ impl<'gc> DispatchList<'gc> {
     /// `use_capture` indicates if you want handlers that execute during the
     /// capture phase, or handlers that execute during the bubble and target
     /// phases.
+    ///
+    /// Weakly referenced handlers that have been collected are skipped.
     pub fn iter_event_handlers<'a>(
         &'a mut self,
         event: impl Into<AvmString<'gc>>,
         use_capture: bool,
+        mc: MutationContext<'gc, 'a>,
     ) -> impl 'a + Iterator<Item = Object<'gc>> {
         self.get_event_mut(event)
             .iter()
             .rev()
             .flat_map(|(_p, v)| v.iter())
             .filter(move |eh| eh.use_capture == use_capture)
-            .map(|eh| eh.handler)
+            .filter_map(move |eh| eh.handler.upgrade(mc))
     }
 }
 
@@ -309,7 +337,7 @@ impl<'gc> Default for DispatchList<'gc> {
 #[collect(no_drop)]
 struct EventHandler<'gc> {
     /// The event handler to call.
-    handler: Object<'gc>,
+    handler: HandlerRef<'gc>,
 
     /// Indicates if this handler should only be called for capturing events
     /// (when `true`), or if it should only be called for bubbling and
@@ -318,26 +346,52 @@ struct EventHandler<'gc> {
 }
 
 impl<'gc> EventHandler<'gc> {
-    fn new(handler: Object<'gc>, use_capture: bool) -> Self {
+    fn new(handler: Object<'gc>, use_capture: bool, use_weak_reference: bool) -> Self {
+        let handler = match handler.as_function_object() {
+            Some(function) if use_weak_reference => HandlerRef::Weak(function.downgrade()),
+            _ => HandlerRef::Strong(handler),
+        };
         Self {
             handler,
             use_capture,
         }
     }
-}
 
-impl<'gc> PartialEq for EventHandler<'gc> {
-    fn eq(&self, rhs: &Self) -> bool {
-        self.use_capture == rhs.use_capture && Object::ptr_eq(self.handler, rhs.handler)
+    /// Whether this handler calls `handler` in the given phase.
+    ///
+    /// A collected handler never matches, even if `handler` was allocated
+    /// where it used to be.
+    fn is_for(
+        &self,
+        handler: Object<'gc>,
+        use_capture: bool,
+        mc: MutationContext<'gc, '_>,
+    ) -> bool {
+        self.use_capture == use_capture
+            && self
+                .handler
+                .upgrade(mc)
+                .map_or(false, |live| Object::ptr_eq(live, handler))
     }
 }
 
-impl<'gc> Eq for EventHandler<'gc> {}
+/// A reference to the function called by an event handler.
+///
+/// Only functions can be weakly referenced; any other callable object is
+/// always held strongly.
+#[derive(Clone, Collect)]
+#[collect(no_drop)]
+enum HandlerRef<'gc> {
+    Strong(Object<'gc>),
+    Weak(FunctionObjectWeak<'gc>),
+}
 
-impl<'gc> Hash for EventHandler<'gc> {
-    fn hash<H: Hasher>(&self, state: &mut H) {
-        self.use_capture.hash(state);
-        self.handler.as_ptr().hash(state);
+impl<'gc> HandlerRef<'gc> {
+    fn upgrade(&self, mc: MutationContext<'gc, '_>) -> Option<Object<'gc>> {
+        match self {
+            HandlerRef::Strong(handler) => Some(*handler),
+            HandlerRef::Weak(handler) => handler.upgrade(mc).map(Object::from),
+        }
     }
 }
 
@@ -401,7 +455,7 @@ pub fn dispatch_event_to_target<'gc>(
     let handlers: Vec<Object<'gc>> = dispatch_list
         .as_dispatch_mut(activation.context.gc_context)
         .ok_or_else(|| Error::from("Internal dispatch list is missing during dispatch!"))?
-        .iter_event_handlers(name, use_capture)
+        .iter_event_handlers(name, use_capture, activation.context.gc_context)
         .collect();
 
     for handler in handlers.iter() {
@@ -441,6 +495,16 @@ pub fn dispatch_event<'gc>(
         .as_object()
         .unwrap_or(this);
 
+    let event_type = event.as_event().unwrap().event_type();
+    if !activation.avm2().has_event_listeners(event_type) {
+        // No object has a listener that could be called, so skip walking the
+        // ancestors, but leave the event as a full dispatch would.
+        let mut evtmut = event.as_event_mut(activation.context.gc_context).unwrap();
+        evtmut.set_target(target);
+        evtmut.set_phase(EventPhase::Bubbling);
+        return Ok(!evtmut.is_cancelled());
+    }
+
     let mut ancestor_list = Vec::new();
     let mut parent = parent_of(target);
     while let Some(par) = parent {
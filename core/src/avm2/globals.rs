@@ -106,12 +106,14 @@ pub struct SystemClasses<'gc> {
     pub point: ClassObject<'gc>,
     pub rangeerror: ClassObject<'gc>,
     pub referenceerror: ClassObject<'gc>,
+    pub securityerror: ClassObject<'gc>,
     pub argumenterror: ClassObject<'gc>,
     pub syntaxerror: ClassObject<'gc>,
     pub typeerror: ClassObject<'gc>,
     pub verifyerror: ClassObject<'gc>,
     pub ioerror: ClassObject<'gc>,
     pub eoferror: ClassObject<'gc>,
+    pub scripttimeouterror: ClassObject<'gc>,
     pub urierror: ClassObject<'gc>,
     pub error: ClassObject<'gc>,
     pub uncaughterrorevents: ClassObject<'gc>,
@@ -137,6 +139,7 @@ pub struct SystemClasses<'gc> {
     pub cubetexture: ClassObject<'gc>,
     pub rectangletexture: ClassObject<'gc>,
     pub morphshape: ClassObject<'gc>,
+    pub avm1movie: ClassObject<'gc>,
 }
 
 impl<'gc> SystemClasses<'gc> {
@@ -217,12 +220,14 @@ impl<'gc> SystemClasses<'gc> {
             point: object,
             rangeerror: object,
             referenceerror: object,
+            securityerror: object,
             argumenterror: object,
             syntaxerror: object,
             typeerror: object,
             verifyerror: object,
             ioerror: object,
             eoferror: object,
+            scripttimeouterror: object,
             urierror: object,
             error: object,
             uncaughterrorevents: object,
@@ -248,6 +253,7 @@ impl<'gc> SystemClasses<'gc> {
             cubetexture: object,
             rectangletexture: object,
             morphshape: object,
+            avm1movie: object,
         }
     }
 }
@@ -549,6 +555,29 @@ pub fn load_player_globals<'gc>(
     Ok(())
 }
 
+/// Classes of 'playerglobal' that only exist in AIR.
+///
+/// They are removed unless AIR compatibility is enabled, as movies commonly
+/// detect AIR by looking them up with `ApplicationDomain.hasDefinition` or
+/// `getDefinitionByName`.
+const AIR_CLASSES: &[(&str, &str)] = &[
+    ("flash.data", "EncryptedLocalStore"),
+    ("flash.data", "SQLConnection"),
+    ("flash.data", "SQLMode"),
+    ("flash.data", "SQLResult"),
+    ("flash.data", "SQLStatement"),
+    ("flash.desktop", "NativeApplication"),
+    ("flash.display", "NativeWindow"),
+    ("flash.errors", "SQLError"),
+    ("flash.events", "FileListEvent"),
+    ("flash.events", "OutputProgressEvent"),
+    ("flash.events", "SQLErrorEvent"),
+    ("flash.events", "SQLEvent"),
+    ("flash.filesystem", "File"),
+    ("flash.filesystem", "FileMode"),
+    ("flash.filesystem", "FileStream"),
+];
+
 /// This file is built by 'core/build_playerglobal/'
 /// See that tool, and 'core/src/avm2/globals/README.md', for more details
 const PLAYERGLOBAL: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/playerglobal.swf"));
@@ -586,6 +615,7 @@ fn load_playerglobal<'gc>(
                 None,
                 do_abc.flags,
                 domain,
+                None,
             )
             .expect("playerglobal.swf should be valid");
         } else if tag_code != TagCode::End {
@@ -621,6 +651,7 @@ fn load_playerglobal<'gc>(
             ("", "RangeError", rangeerror),
             ("", "RegExp", regexp),
             ("", "ReferenceError", referenceerror),
+            ("", "SecurityError", securityerror),
             ("", "SyntaxError", syntaxerror),
             ("", "TypeError", typeerror),
             ("", "URIError", urierror),
@@ -648,6 +679,7 @@ fn load_playerglobal<'gc>(
             ("flash.display", "GraphicsSolidFill", graphicssolidfill),
             ("flash.display", "GraphicsStroke", graphicsstroke),
             ("flash.display", "Graphics", graphics),
+            ("flash.display", "AVM1Movie", avm1movie),
             ("flash.display", "LoaderInfo", loaderinfo),
             ("flash.display", "MorphShape", morphshape),
             ("flash.display", "MovieClip", movieclip),
@@ -674,6 +706,7 @@ fn load_playerglobal<'gc>(
             ),
             ("flash.errors", "IOError", ioerror),
             ("flash.errors", "EOFError", eoferror),
+            ("flash.errors", "ScriptTimeoutError", scripttimeouterror),
             ("flash.events", "Event", event),
             ("flash.events", "EventDispatcher", eventdispatcher),
             ("flash.events", "TextEvent", textevent),
@@ -716,6 +749,14 @@ fn load_playerglobal<'gc>(
         ]
     );
 
+    if !activation.context.air_compatibility {
+        for (package, class_name) in AIR_CLASSES {
+            let ns = Namespace::package(*package, &mut activation.borrow_gc());
+            let name = QName::new(ns, *class_name);
+            domain.remove_definition(name, activation.context.gc_context);
+        }
+    }
+
     // Domain memory must be initialized after playerglobals is loaded because it relies on ByteArray.
     domain.init_default_domain_memory(activation)?;
     Ok(())
@@ -1,7 +1,7 @@
 package __ruffle__ {
     public native function log_warn(...arguments);
 
-    public native function stub_method(class_name, method);
+    public native function stub_method(class_name, method, specifics = null);
 
     public native function stub_getter(class_name, method);
 
@@ -1,14 +1,18 @@
 //! `flash` namespace
 
 pub mod crypto;
+pub mod data;
+pub mod desktop;
 pub mod display;
 #[allow(non_snake_case)]
 pub mod display3D;
 pub mod events;
 pub mod external;
+pub mod filesystem;
 pub mod geom;
 pub mod media;
 pub mod net;
+pub mod printing;
 pub mod system;
 pub mod text;
 pub mod ui;
@@ -0,0 +1,12 @@
+package flash.data {
+    import flash.utils.ByteArray;
+
+    public class EncryptedLocalStore {
+        public static native function get isSupported(): Boolean;
+
+        public static native function getItem(name: String): ByteArray;
+        public static native function setItem(name: String, data: ByteArray, stronglyBound: Boolean = false): void;
+        public static native function removeItem(name: String): void;
+        public static native function reset(): void;
+    }
+}
@@ -0,0 +1,161 @@
+package flash.data {
+    import flash.errors.IllegalOperationError;
+    import flash.errors.SQLError;
+    import flash.events.Event;
+    import flash.events.EventDispatcher;
+    import flash.events.SQLErrorEvent;
+    import flash.events.SQLEvent;
+    import flash.filesystem.File;
+    import flash.net.Responder;
+    import flash.utils.ByteArray;
+    import flash.utils.setTimeout;
+
+    namespace ruffle = "__ruffle__";
+
+    // Databases are SQLite databases of the file sandbox, opened by the storage backend.
+    // In asynchronous mode, operations still run immediately, but their outcome
+    // is only reported on the next frame.
+    public class SQLConnection extends EventDispatcher {
+        private var _handle: int = -1;
+        private var _async: Boolean = false;
+        private var _inTransaction: Boolean = false;
+        private var _lastInsertRowID: Number = 0;
+        private var _totalChanges: Number = 0;
+
+        public function SQLConnection() {
+        }
+
+        internal static native function openDatabase(path: String, openMode: String): Object;
+        internal static native function executeSql(handle: int, sql: String, names: Array, values: Array): Object;
+        internal static native function closeDatabase(handle: int): void;
+
+        public static function get isSupported(): Boolean {
+            return true;
+        }
+
+        public function get connected(): Boolean {
+            return this._handle != -1;
+        }
+
+        public function get inTransaction(): Boolean {
+            return this._inTransaction;
+        }
+
+        public function get lastInsertRowID(): Number {
+            return this._lastInsertRowID;
+        }
+
+        public function get totalChanges(): Number {
+            return this._totalChanges;
+        }
+
+        public function open(reference: Object = null, openMode: String = "create", autoCompact: Boolean = false, pageSize: int = 1024, encryptionKey: ByteArray = null): void {
+            this.openWithMode(reference, openMode, false, null);
+        }
+
+        public function openAsync(reference: Object = null, openMode: String = "create", responder: Responder = null, autoCompact: Boolean = false, pageSize: int = 1024, encryptionKey: ByteArray = null): void {
+            this.openWithMode(reference, openMode, true, responder);
+        }
+
+        private function openWithMode(reference: Object, openMode: String, async: Boolean, responder: Responder): void {
+            if (this.connected) {
+                throw new IllegalOperationError("Error #3105: Operation is only allowed if a connection is not open.", 3105);
+            }
+            this._async = async;
+
+            var path: String = null;
+            if (reference is File) {
+                path = File(reference).nativePath;
+            } else if (reference != null) {
+                throw new ArgumentError("Error #2004: One of the parameters is invalid.", 2004);
+            }
+
+            var result: Object = openDatabase(path, openMode);
+            if (result is String) {
+                this.ruffle::fail(this, "open", String(result), responder);
+                return;
+            }
+            this._handle = int(result);
+            this.ruffle::report(this, new SQLEvent(SQLEvent.OPEN), responder, this);
+        }
+
+        public function close(responder: Responder = null): void {
+            if (!this.connected) {
+                return;
+            }
+            closeDatabase(this._handle);
+            this._handle = -1;
+            this._inTransaction = false;
+            this.ruffle::report(this, new SQLEvent(SQLEvent.CLOSE), responder, null);
+        }
+
+        public function begin(option: String = null, responder: Responder = null): void {
+            this.runTransactionStatement("begin", "BEGIN " + (option || "DEFERRED"), SQLEvent.BEGIN, responder, true);
+        }
+
+        public function commit(responder: Responder = null): void {
+            this.runTransactionStatement("commit", "COMMIT", SQLEvent.COMMIT, responder, false);
+        }
+
+        public function rollback(responder: Responder = null): void {
+            this.runTransactionStatement("rollback", "ROLLBACK", SQLEvent.ROLLBACK, responder, false);
+        }
+
+        private function runTransactionStatement(operation: String, sql: String, eventType: String, responder: Responder, inTransaction: Boolean): void {
+            var result: Object = this.ruffle::execute(sql, [], []);
+            if (result is String) {
+                this.ruffle::fail(this, operation, String(result), responder);
+                return;
+            }
+            this._inTransaction = inTransaction;
+            this.ruffle::report(this, new SQLEvent(eventType), responder, null);
+        }
+
+        ruffle function execute(sql: String, names: Array, values: Array): Object {
+            if (!this.connected) {
+                throw new IllegalOperationError("Error #3104: A SQLConnection must be open to perform this operation.", 3104);
+            }
+            var result: Object = executeSql(this._handle, sql, names, values);
+            if (!(result is String)) {
+                this._lastInsertRowID = result.lastInsertRowID;
+                this._totalChanges += result.rowsAffected;
+            }
+            return result;
+        }
+
+        // Reports a successful operation, to its responder if there is one,
+        // or with an event in asynchronous mode.
+        ruffle function report(target: EventDispatcher, event: Event, responder: Responder, result: *): void {
+            var callback: Function = responder == null ? null : responder.ruffle::_result;
+            if (!this._async) {
+                if (callback != null) {
+                    callback(result);
+                }
+                return;
+            }
+            setTimeout(function(): void {
+                if (callback != null) {
+                    callback(result);
+                } else {
+                    target.dispatchEvent(event);
+                }
+            }, 0);
+        }
+
+        // Reports a failed operation, by throwing an `SQLError` in synchronous mode.
+        ruffle function fail(target: EventDispatcher, operation: String, details: String, responder: Responder): void {
+            var sqlError: SQLError = new SQLError(operation, details, "Error #3115: SQL Error.", 3115);
+            if (!this._async) {
+                throw sqlError;
+            }
+            var callback: Function = responder == null ? null : responder.ruffle::_status;
+            setTimeout(function(): void {
+                if (callback != null) {
+                    callback(sqlError);
+                } else {
+                    target.dispatchEvent(new SQLErrorEvent(SQLErrorEvent.ERROR, false, false, sqlError));
+                }
+            }, 0);
+        }
+    }
+}
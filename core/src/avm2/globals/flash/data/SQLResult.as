@@ -0,0 +1,31 @@
+package flash.data {
+    public class SQLResult {
+        private var _data: Array;
+        private var _rowsAffected: Number;
+        private var _complete: Boolean;
+        private var _lastInsertRowID: Number;
+
+        public function SQLResult(data: Array = null, rowsAffected: Number = 0, complete: Boolean = true, rowID: Number = 0) {
+            this._data = data;
+            this._rowsAffected = rowsAffected;
+            this._complete = complete;
+            this._lastInsertRowID = rowID;
+        }
+
+        public function get data(): Array {
+            return this._data;
+        }
+
+        public function get rowsAffected(): Number {
+            return this._rowsAffected;
+        }
+
+        public function get complete(): Boolean {
+            return this._complete;
+        }
+
+        public function get lastInsertRowID(): Number {
+            return this._lastInsertRowID;
+        }
+    }
+}
@@ -0,0 +1,78 @@
+package flash.data {
+    import flash.errors.IllegalOperationError;
+    import flash.events.EventDispatcher;
+    import flash.events.SQLEvent;
+    import flash.net.Responder;
+
+    // Statements always fetch all of their rows at once, so `prefetch` is ignored.
+    // The `ruffle` namespace is declared in SQLConnection.as.
+    public class SQLStatement extends EventDispatcher {
+        private var _parameters: Object = {};
+        private var _results: Array = [];
+
+        public var sqlConnection: SQLConnection;
+        public var text: String;
+        public var itemClass: Class;
+
+        public function SQLStatement() {
+        }
+
+        public function get parameters(): Object {
+            return this._parameters;
+        }
+
+        public function get executing(): Boolean {
+            return false;
+        }
+
+        public function clearParameters(): void {
+            this._parameters = {};
+        }
+
+        public function execute(prefetch: int = -1, responder: Responder = null): void {
+            var connection: SQLConnection = this.sqlConnection;
+            if (connection == null || !connection.connected || this.text == null) {
+                throw new IllegalOperationError("Error #3105: Operation is only allowed if a connection is open and a statement text is set.", 3105);
+            }
+
+            var names: Array = [];
+            var values: Array = [];
+            for (var name: String in this._parameters) {
+                names.push(name);
+                values.push(this._parameters[name]);
+            }
+
+            var result: Object = connection.ruffle::execute(this.text, names, values);
+            if (result is String) {
+                connection.ruffle::fail(this, "execute", String(result), responder);
+                return;
+            }
+
+            var data: Array = null;
+            if (result.rows.length > 0) {
+                data = [];
+                for each (var row: Array in result.rows) {
+                    var item: Object = this.itemClass == null ? {} : new this.itemClass();
+                    for (var i: int = 0; i < result.columns.length; i++) {
+                        item[result.columns[i]] = row[i];
+                    }
+                    data.push(item);
+                }
+            }
+
+            var sqlResult: SQLResult = new SQLResult(data, result.rowsAffected, true, result.lastInsertRowID);
+            this._results.push(sqlResult);
+            connection.ruffle::report(this, new SQLEvent(SQLEvent.RESULT), responder, sqlResult);
+        }
+
+        public function next(prefetch: int = -1, responder: Responder = null): void {
+        }
+
+        public function cancel(): void {
+        }
+
+        public function getResult(): SQLResult {
+            return this._results.length == 0 ? null : this._results.shift();
+        }
+    }
+}
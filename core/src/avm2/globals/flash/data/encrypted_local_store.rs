@@ -0,0 +1,153 @@
+//! `flash.data.EncryptedLocalStore` native methods
+//!
+//! The items of a movie are kept together in a single store of the storage backend.
+//! Unlike in AIR, the store isn't encrypted: a key that Ruffle could read back would be
+//! just as readable by anything else running as the user, so it wouldn't protect anything.
+
+use crate::avm2::activation::Activation;
+use crate::avm2::bytearray::ByteArrayStorage;
+use crate::avm2::error::{argument_error, Error};
+use crate::avm2::globals::flash::desktop::native_application::ensure_air_compatibility;
+use crate::avm2::object::{ByteArrayObject, Object, TObject};
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::value::Value;
+use indexmap::IndexMap;
+
+type Items = IndexMap<String, Vec<u8>>;
+
+/// Decodes the items of a store, as a sequence of length-prefixed names and values.
+fn decode_items(mut data: &[u8]) -> Option<Items> {
+    fn read_chunk<'a>(data: &mut &'a [u8]) -> Option<&'a [u8]> {
+        let len = u32::from_le_bytes(data.get(..4)?.try_into().ok()?) as usize;
+        let chunk = data.get(4..4 + len)?;
+        *data = &data[4 + len..];
+        Some(chunk)
+    }
+
+    let mut items = Items::new();
+    while !data.is_empty() {
+        let name = String::from_utf8(read_chunk(&mut data)?.to_vec()).ok()?;
+        let value = read_chunk(&mut data)?.to_vec();
+        items.insert(name, value);
+    }
+    Some(items)
+}
+
+fn encode_items(items: &Items) -> Vec<u8> {
+    let mut data = vec![];
+    for (name, value) in items {
+        for chunk in [name.as_bytes(), value] {
+            data.extend_from_slice(&(chunk.len() as u32).to_le_bytes());
+            data.extend_from_slice(chunk);
+        }
+    }
+    data
+}
+
+fn load_items(activation: &mut Activation<'_, '_>) -> Items {
+    let content_id = activation.context.swf.url().to_string();
+    let Some(data) = activation.context.storage.get_encrypted_store(&content_id) else {
+        return Items::new();
+    };
+    decode_items(&data).unwrap_or_else(|| {
+        tracing::warn!("EncryptedLocalStore: Ignoring corrupted store");
+        Items::new()
+    })
+}
+
+fn save_items(activation: &mut Activation<'_, '_>, items: &Items) {
+    let content_id = activation.context.swf.url().to_string();
+    if !activation
+        .context
+        .storage
+        .put_encrypted_store(&content_id, &encode_items(items))
+    {
+        tracing::warn!("EncryptedLocalStore: Unable to save store");
+    }
+}
+
+/// Gets the name of an item, which can't be empty.
+fn get_name<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    args: &[Value<'gc>],
+) -> Result<String, Error<'gc>> {
+    ensure_air_compatibility(activation, "EncryptedLocalStore")?;
+    let name = args.get_string(activation, 0)?;
+    if name.is_empty() {
+        return Err(Error::AvmError(argument_error(
+            activation,
+            "Error #2007: Parameter name must be non-null.",
+            2007,
+        )?));
+    }
+    Ok(name.to_utf8_lossy().into_owned())
+}
+
+/// Implements `EncryptedLocalStore.isSupported`
+pub fn get_is_supported<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(activation.context.air_compatibility.into())
+}
+
+/// Implements `EncryptedLocalStore.getItem`
+pub fn get_item<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let name = get_name(activation, args)?;
+    match load_items(activation).remove(&name) {
+        Some(value) => {
+            let storage = ByteArrayStorage::from_vec(value);
+            Ok(ByteArrayObject::from_storage(activation, storage)?.into())
+        }
+        None => Ok(Value::Null),
+    }
+}
+
+/// Implements `EncryptedLocalStore.setItem`
+pub fn set_item<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let name = get_name(activation, args)?;
+    let data = args.get_object(activation, 1, "data")?;
+    let value = match data.as_bytearray() {
+        Some(bytearray) => bytearray.bytes().to_vec(),
+        None => return Ok(Value::Undefined),
+    };
+
+    let mut items = load_items(activation);
+    items.insert(name, value);
+    save_items(activation, &items);
+    Ok(Value::Undefined)
+}
+
+/// Implements `EncryptedLocalStore.removeItem`
+pub fn remove_item<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let name = get_name(activation, args)?;
+    let mut items = load_items(activation);
+    if items.shift_remove(&name).is_some() {
+        save_items(activation, &items);
+    }
+    Ok(Value::Undefined)
+}
+
+/// Implements `EncryptedLocalStore.reset`
+pub fn reset<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    ensure_air_compatibility(activation, "EncryptedLocalStore")?;
+    save_items(activation, &Items::new());
+    Ok(Value::Undefined)
+}
@@ -0,0 +1,175 @@
+//! `flash.data.SQLConnection` native methods
+//!
+//! Databases are opened through the storage backend, which hands out a handle
+//! for each of them. Failures are returned to ActionScript as error messages,
+//! which turns them into `SQLError`s.
+
+use crate::avm2::activation::Activation;
+use crate::avm2::array::ArrayStorage;
+use crate::avm2::bytearray::ByteArrayStorage;
+use crate::avm2::error::Error;
+use crate::avm2::globals::flash::desktop::native_application::ensure_air_compatibility;
+use crate::avm2::object::{ArrayObject, ByteArrayObject, Object, TObject};
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::value::Value;
+use crate::backend::storage::{SqlOpenMode, SqlValue};
+use crate::string::AvmString;
+
+/// Implements `SQLConnection.openDatabase`
+pub fn open_database<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    ensure_air_compatibility(activation, "SQLConnection")?;
+    let path = args
+        .try_get_string(activation, 0)?
+        .map(|path| path.to_utf8_lossy().into_owned());
+    let mode = args.get_string(activation, 1)?;
+    let mode = if &mode == b"read" {
+        SqlOpenMode::Read
+    } else if &mode == b"update" {
+        SqlOpenMode::Update
+    } else {
+        SqlOpenMode::Create
+    };
+
+    match activation
+        .context
+        .storage
+        .open_database(path.as_deref(), mode)
+    {
+        Ok(handle) => Ok(handle.into()),
+        Err(message) => Ok(AvmString::new_utf8(activation.context.gc_context, message).into()),
+    }
+}
+
+/// Implements `SQLConnection.executeSql`, returning an object with the
+/// `columns`, `rows`, `rowsAffected` and `lastInsertRowID` of the result.
+pub fn execute_sql<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let handle = args.get_u32(activation, 0)?;
+    let sql = args.get_string(activation, 1)?.to_utf8_lossy().into_owned();
+    let names = args.get_object(activation, 2, "names")?;
+    let values = args.get_object(activation, 3, "values")?;
+
+    let names: Vec<Value<'gc>> = names
+        .as_array_storage()
+        .map(|names| {
+            names
+                .iter()
+                .map(|name| name.unwrap_or(Value::Undefined))
+                .collect()
+        })
+        .unwrap_or_default();
+    let values: Vec<Value<'gc>> = values
+        .as_array_storage()
+        .map(|values| {
+            values
+                .iter()
+                .map(|value| value.unwrap_or(Value::Undefined))
+                .collect()
+        })
+        .unwrap_or_default();
+    let mut parameters = Vec::with_capacity(names.len());
+    for (name, value) in names.into_iter().zip(values) {
+        let name = name
+            .coerce_to_string(activation)?
+            .to_utf8_lossy()
+            .into_owned();
+        parameters.push((name, to_sql_value(activation, value)?));
+    }
+
+    let result = match activation
+        .context
+        .storage
+        .execute_sql(handle, &sql, &parameters)
+    {
+        Ok(result) => result,
+        Err(message) => {
+            return Ok(AvmString::new_utf8(activation.context.gc_context, message).into())
+        }
+    };
+
+    let columns: Vec<Value<'gc>> = result
+        .columns
+        .iter()
+        .map(|column| AvmString::new_utf8(activation.context.gc_context, column).into())
+        .collect();
+    let mut rows = Vec::with_capacity(result.rows.len());
+    for row in result.rows {
+        let row = row
+            .into_iter()
+            .map(|value| from_sql_value(activation, value))
+            .collect::<Result<Vec<_>, _>>()?;
+        rows.push(ArrayObject::from_storage(activation, ArrayStorage::from_args(&row))?.into());
+    }
+
+    let object_class = activation.avm2().classes().object;
+    let mut object = object_class.construct(activation, &[])?;
+    let columns = ArrayObject::from_storage(activation, ArrayStorage::from_args(&columns))?;
+    object.set_public_property("columns", columns.into(), activation)?;
+    let rows = ArrayObject::from_storage(activation, ArrayStorage::from_args(&rows))?;
+    object.set_public_property("rows", rows.into(), activation)?;
+    object.set_public_property(
+        "rowsAffected",
+        (result.rows_affected as f64).into(),
+        activation,
+    )?;
+    object.set_public_property(
+        "lastInsertRowID",
+        (result.last_insert_row_id as f64).into(),
+        activation,
+    )?;
+    Ok(object.into())
+}
+
+/// Implements `SQLConnection.closeDatabase`
+pub fn close_database<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let handle = args.get_u32(activation, 0)?;
+    activation.context.storage.close_database(handle);
+    Ok(Value::Undefined)
+}
+
+fn to_sql_value<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    value: Value<'gc>,
+) -> Result<SqlValue, Error<'gc>> {
+    Ok(match value {
+        Value::Undefined | Value::Null => SqlValue::Null,
+        Value::Bool(value) => SqlValue::Integer(value.into()),
+        Value::Integer(value) => SqlValue::Integer(value.into()),
+        Value::Number(value) => SqlValue::Real(value),
+        Value::Object(object) if object.as_bytearray().is_some() => {
+            SqlValue::Blob(object.as_bytearray().unwrap().bytes().to_vec())
+        }
+        value => SqlValue::Text(
+            value
+                .coerce_to_string(activation)?
+                .to_utf8_lossy()
+                .into_owned(),
+        ),
+    })
+}
+
+fn from_sql_value<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    value: SqlValue,
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(match value {
+        SqlValue::Null => Value::Null,
+        SqlValue::Integer(value) => (value as f64).into(),
+        SqlValue::Real(value) => value.into(),
+        SqlValue::Text(value) => AvmString::new_utf8(activation.context.gc_context, value).into(),
+        SqlValue::Blob(value) => {
+            ByteArrayObject::from_storage(activation, ByteArrayStorage::from_vec(value))?.into()
+        }
+    })
+}
@@ -0,0 +1,69 @@
+package flash.desktop {
+    import flash.display.NativeWindow;
+    import flash.events.EventDispatcher;
+
+    public final class NativeApplication extends EventDispatcher {
+        private static var _nativeApplication: NativeApplication;
+
+        // Ruffle only ever has a single window, the one of the player.
+        private var _window: NativeWindow;
+
+        public var autoExit: Boolean = true;
+        public var idleThreshold: int = 300;
+
+        public function NativeApplication() {
+            checkSupported();
+            this._window = new NativeWindow();
+        }
+
+        private static native function checkSupported():void;
+
+        public static function get nativeApplication(): NativeApplication {
+            if (_nativeApplication == null) {
+                _nativeApplication = new NativeApplication();
+            }
+            return _nativeApplication;
+        }
+
+        public static function get supportsDefaultApplication(): Boolean {
+            return false;
+        }
+
+        public static function get supportsDockIcon(): Boolean {
+            return false;
+        }
+
+        public static function get supportsMenu(): Boolean {
+            return false;
+        }
+
+        public static function get supportsStartAtLogin(): Boolean {
+            return false;
+        }
+
+        public static function get supportsSystemTrayIcon(): Boolean {
+            return false;
+        }
+
+        public function get activeWindow(): NativeWindow {
+            return this._window.closed ? null : this._window;
+        }
+
+        public function get openedWindows(): Array {
+            return this._window.closed ? [] : [this._window];
+        }
+
+        public function get applicationID(): String {
+            return "";
+        }
+
+        public function get runtimeVersion(): String {
+            return "32.0";
+        }
+
+        public function activate(window: NativeWindow = null): void {
+        }
+
+        public native function exit(errorCode: int = 0): void;
+    }
+}
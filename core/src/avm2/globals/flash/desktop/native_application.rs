@@ -0,0 +1,45 @@
+//! `flash.desktop.NativeApplication` native methods
+
+use crate::avm2::activation::Activation;
+use crate::avm2::error::{reference_error, Error};
+use crate::avm2::object::Object;
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::value::Value;
+
+/// Throws the error Flash Player gives for an AIR-only class,
+/// unless the player was started with AIR compatibility enabled.
+pub fn ensure_air_compatibility<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    class_name: &str,
+) -> Result<(), Error<'gc>> {
+    if activation.context.air_compatibility {
+        return Ok(());
+    }
+
+    Err(Error::AvmError(reference_error(
+        activation,
+        &format!("Error #1065: Variable {class_name} is not defined."),
+        1065,
+    )?))
+}
+
+/// Implements `NativeApplication.checkSupported`
+pub fn check_supported<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    ensure_air_compatibility(activation, "NativeApplication")?;
+    Ok(Value::Undefined)
+}
+
+/// Implements `NativeApplication.exit`
+pub fn exit<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let code = args.get_i32(activation, 0)?;
+    activation.context.ui.exit(code);
+    Ok(Value::Undefined)
+}
@@ -0,0 +1,8 @@
+package flash.display {
+    // An AVM1 (ActionScript 1.0/2.0) movie loaded into an AVM2 movie.
+    public class AVM1Movie extends DisplayObject {
+        public function AVM1Movie() {
+            throw new ArgumentError("Error #2012: AVM1Movie$ class cannot be instantiated.", 2012)
+        }
+    }
+}
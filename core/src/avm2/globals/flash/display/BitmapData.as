@@ -5,7 +5,6 @@ package flash.display {
     import flash.geom.Matrix;
     import flash.filters.BitmapFilter;
     import flash.utils.ByteArray;
-    import __ruffle__.stub_method;
 
     [Ruffle(InstanceAllocator)]
     public class BitmapData implements IBitmapDrawable {
@@ -62,9 +61,6 @@ package flash.display {
         ):uint;
         public native function compare(otherBitmapData:BitmapData):Object;
 
-        public function generateFilterRect(sourceRect:Rectangle, filter:BitmapFilter):Rectangle {
-            stub_method("flash.display.BitmapData", "generateFilterRect");
-            return sourceRect.clone();
-        }
+        public native function generateFilterRect(sourceRect:Rectangle, filter:BitmapFilter):Rectangle;
     }
 }
@@ -10,8 +10,7 @@ package flash.display {
     import flash.geom.Point;
     import flash.events.EventDispatcher;
     
-    import __ruffle__.stub_getter;
-    import __ruffle__.stub_setter;
+    namespace ruffle = "__ruffle__";
 
     [Ruffle(InstanceAllocator)]
     [Ruffle(NativeInstanceInit)]
@@ -35,6 +34,8 @@ package flash.display {
         public native function get blendMode():String;
         public native function set blendMode(value:String):void;
 
+        public native function set blendShader(value:Shader):void;
+
         public native function get height():Number;
         public native function set height(value:Number):void;
 
@@ -71,13 +72,8 @@ package flash.display {
         public native function get scaleZ():Number;
         public native function set scaleZ(value:Number):void;
         
-        public function get scale9Grid():Rectangle {
-            stub_getter("flash.display.DisplayObject", "scale9Grid");
-            return null;
-        }
-        public function set scale9Grid(value:Rectangle):void {
-            stub_setter("flash.display.DisplayObject", "scale9Grid");
-        }
+        public native function get scale9Grid():Rectangle;
+        public native function set scale9Grid(value:Rectangle):void;
 
         public native function get name():String;
         public native function set name(value:String):void;
@@ -115,6 +111,10 @@ package flash.display {
         public native function get cacheAsBitmap():Boolean;
         public native function set cacheAsBitmap(value:Boolean):void;
 
+        // Ruffle extension: whether this object is interpolated between frames with smooth playback.
+        ruffle native function get smoothPlayback():Boolean;
+        ruffle native function set smoothPlayback(value:Boolean):void;
+
         public native function hitTestPoint(x:Number, y:Number, shapeFlag:Boolean = false):Boolean;
 
         public native function hitTestObject(obj:DisplayObject):Boolean;
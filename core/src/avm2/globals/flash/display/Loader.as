@@ -24,7 +24,8 @@ package flash.display {
 			if (this.numChildren == 0) {
 				return null;
 			}
-			return this.getChildAt(0)
+			// This goes through `LoaderInfo.content` for its cross-scripting checks.
+			return this._contentLoaderInfo.content;
 		}
 
 		public native function load(request: URLRequest, context: LoaderContext = null):void;
@@ -1,7 +1,14 @@
 package flash.display {
+    namespace ruffle = "__ruffle__";
+
     public final class MorphShape extends DisplayObject {
         public function MorphShape() {
             throw new ArgumentError("Error #2012: MorphShape$ class cannot be instantiated.", 2012)
         }
+
+        // Ruffle extension: the morph ratio, from 0 (start shape) to 1 (end shape).
+        // This isn't part of the Flash API, so it's hidden in the `__ruffle__` namespace.
+        ruffle native function get ratio():Number;
+        ruffle native function set ratio(value:Number):void;
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,78 @@
+package flash.display {
+    import flash.desktop.NativeApplication;
+    import flash.events.Event;
+    import flash.events.EventDispatcher;
+
+    // This class stands for the window of the player, which can't be controlled
+    // by the movie: everything but closing it is a no-op.
+    public class NativeWindow extends EventDispatcher {
+        private var _closed: Boolean = false;
+
+        public var title: String = "";
+        public var alwaysInFront: Boolean = false;
+        public var visible: Boolean = true;
+        public var x: Number = 0;
+        public var y: Number = 0;
+        public var width: Number = 0;
+        public var height: Number = 0;
+
+        public function NativeWindow(initOptions: * = null) {
+        }
+
+        public static function get isSupported(): Boolean {
+            return false;
+        }
+
+        public function get active(): Boolean {
+            return !this._closed;
+        }
+
+        public function get closed(): Boolean {
+            return this._closed;
+        }
+
+        public function get displayState(): String {
+            return "normal";
+        }
+
+        public function activate(): void {
+        }
+
+        public function close(): void {
+            if (this._closed || !this.dispatchEvent(new Event("closing", false, true))) {
+                return;
+            }
+            this._closed = true;
+            this.dispatchEvent(new Event(Event.CLOSE));
+
+            if (NativeApplication.nativeApplication.autoExit) {
+                NativeApplication.nativeApplication.exit();
+            }
+        }
+
+        public function maximize(): void {
+        }
+
+        public function minimize(): void {
+        }
+
+        public function restore(): void {
+        }
+
+        public function orderToFront(): Boolean {
+            return false;
+        }
+
+        public function orderToBack(): Boolean {
+            return false;
+        }
+
+        public function startMove(): Boolean {
+            return false;
+        }
+
+        public function startResize(edge: String = "BR"): Boolean {
+            return false;
+        }
+    }
+}
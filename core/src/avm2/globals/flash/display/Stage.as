@@ -119,6 +119,11 @@ package flash.display {
         public native function get color():uint;
         public native function set color(value:uint):void;
 
+        public native function get colorCorrection():String;
+        public native function set colorCorrection(value:String):void;
+
+        public native function get colorCorrectionSupport():String;
+
         public native function get contentsScaleFactor():Number;
 
         public native function get displayState():String;
@@ -161,6 +166,8 @@ package flash.display {
 
         public native function get stage3Ds():Vector.<Stage3D>;
 
+        public native function get wmodeGPU():Boolean;
+
         public native function invalidate():void;
     }
 }
@@ -2,27 +2,76 @@ package flash.display {
     import flash.events.ErrorEvent;
     import flash.events.EventDispatcher;
     import flash.display3D.Context3D;
+    import flash.display3D.Context3DProfile;
     import flash.utils.setTimeout;
-    
+
     [Ruffle(InstanceAllocator)]
     public class Stage3D extends EventDispatcher {
-        
+
+        // Every profile, from least to most capable.
+        private static const PROFILES:Array = [
+            Context3DProfile.BASELINE_CONSTRAINED,
+            Context3DProfile.BASELINE,
+            Context3DProfile.BASELINE_EXTENDED,
+            Context3DProfile.STANDARD_CONSTRAINED,
+            Context3DProfile.STANDARD,
+            Context3DProfile.STANDARD_EXTENDED
+        ];
+
         public native function get context3D():Context3D;
-        private native function requestContext3D_internal(context3DRenderMode:String, profile:String):void;
+        private native function requestContext3D_internal(context3DRenderMode:String, profile:String):Boolean;
 
         public function requestContext3D(context3DRenderMode:String = "auto", profile:String = "baseline"):void {
+            checkProfile(profile);
+            requestMatchingProfile(context3DRenderMode, [profile]);
+        }
+
+        public function requestContext3DMatchingProfiles(profiles:Vector.<String>):void {
+            if (profiles == null || profiles.length == 0) {
+                throw new ArgumentError("Error #2008: Parameter profiles must be one of the accepted values.", 2008);
+            }
+            var requested:Array = [];
+            for each (var profile:String in profiles) {
+                checkProfile(profile);
+                requested.push(profile);
+            }
+            requestMatchingProfile("auto", requested);
+        }
+
+        private static function checkProfile(profile:String):void {
+            if (PROFILES.indexOf(profile) == -1) {
+                throw new ArgumentError("Error #2008: Parameter profile must be one of the accepted values.", 2008);
+            }
+        }
+
+        // Creates a context with the most capable of `profiles` that the renderer supports,
+        // or dispatches an `ErrorEvent` if none of them are supported.
+        private function requestMatchingProfile(context3DRenderMode:String, profiles:Array):void {
             // Several SWFS (the examples from the Context3D documentation, and the Starling framework)
             // rely on the `context3DCreate` being fired asynchronously - they initialize variables
             // after the call to `requestContext3D`, and then use those variables in the event handler.
             // Currently, we create a `Context3D` synchronously, so we need to delay the event dispatch
             var stage3d = this;
             setTimeout(function() {
-                stage3d.requestContext3D_internal(context3DRenderMode, profile);
+                for (var i:int = PROFILES.length - 1; i >= 0; i--) {
+                    if (profiles.indexOf(PROFILES[i]) == -1) {
+                        continue;
+                    }
+                    if (stage3d.requestContext3D_internal(context3DRenderMode, PROFILES[i])) {
+                        return;
+                    }
+                }
+                stage3d.dispatchEvent(new ErrorEvent(ErrorEvent.ERROR, false, false, "Error #3702: Context3D not available.", 3702));
             }, 0);
         }
 
-        // FIXME - actually implement this
-        public var x:Number;
-        public var y:Number;
+        public native function get x():Number;
+        public native function set x(value:Number):void;
+
+        public native function get y():Number;
+        public native function set y(value:Number):void;
+
+        public native function get visible():Boolean;
+        public native function set visible(value:Boolean):void;
     }
-}
\ No newline at end of file
+}
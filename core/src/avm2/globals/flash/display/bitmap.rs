@@ -1,6 +1,7 @@
 //! `flash.display.Bitmap` builtin/prototype
 
 use crate::avm2::activation::Activation;
+use crate::avm2::error::make_error_2008;
 use crate::avm2::globals::flash::display::bitmap_data::fill_bitmap_data_from_symbol;
 use crate::avm2::object::{BitmapDataObject, Object, TObject};
 use crate::avm2::value::Value;
@@ -9,8 +10,8 @@ use crate::avm2::Error;
 use crate::avm2::parameters::ParametersExt;
 use crate::bitmap::bitmap_data::BitmapDataWrapper;
 use crate::character::Character;
-use crate::display_object::{Bitmap, TDisplayObject};
-use crate::{avm2_stub_getter, avm2_stub_setter};
+use crate::display_object::{Bitmap, PixelSnapping, TDisplayObject};
+use crate::string::AvmString;
 
 /// Implements `flash.display.Bitmap`'s `init` method, which is called from the constructor
 pub fn init<'gc>(
@@ -24,8 +25,10 @@ pub fn init<'gc>(
         let bitmap_data = args
             .try_get_object(activation, 0)
             .and_then(|o| o.as_bitmap_data());
-        //TODO: Pixel snapping is not supported
-        let _pixel_snapping = args.get_string(activation, 1);
+        let pixel_snapping: PixelSnapping = args
+            .get_string(activation, 1)?
+            .parse()
+            .map_err(|_| make_error_2008(activation, "pixelSnapping"))?;
         let smoothing = args.get_bool(2);
 
         if let Some(bitmap) = this.as_display_object().and_then(|dobj| dobj.as_bitmap()) {
@@ -80,6 +83,7 @@ pub fn init<'gc>(
             this.set_public_property("bitmapData", bd_object.into(), activation)?;
 
             bitmap.set_smoothing(activation.context.gc_context, smoothing);
+            bitmap.set_pixel_snapping(activation.context.gc_context, pixel_snapping);
         } else {
             //We are being initialized by AVM2 (and aren't associated with a
             //Bitmap subclass).
@@ -89,6 +93,7 @@ pub fn init<'gc>(
 
             let bitmap =
                 Bitmap::new_with_bitmap_data(&mut activation.context, 0, bitmap_data, smoothing);
+            bitmap.set_pixel_snapping(activation.context.gc_context, pixel_snapping);
 
             this.init_display_object(&mut activation.context, bitmap.into());
         }
@@ -144,23 +149,40 @@ pub fn set_bitmap_data<'gc>(
     Ok(Value::Undefined)
 }
 
-/// Stub `Bitmap.pixelSnapping`'s getter
+/// Implement `Bitmap.pixelSnapping`'s getter
 pub fn get_pixel_snapping<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Option<Object<'gc>>,
+    this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_getter!(activation, "flash.display.Bitmap", "pixelSnapping");
-    Ok("auto".into())
+    if let Some(bitmap) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|dobj| dobj.as_bitmap())
+    {
+        let pixel_snapping = bitmap.pixel_snapping().to_string();
+        return Ok(AvmString::new_utf8(activation.context.gc_context, pixel_snapping).into());
+    }
+
+    Ok(Value::Undefined)
 }
 
-/// Stub `Bitmap.pixelSnapping`'s setter
+/// Implement `Bitmap.pixelSnapping`'s setter
 pub fn set_pixel_snapping<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Option<Object<'gc>>,
-    _args: &[Value<'gc>],
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_setter!(activation, "flash.display.Bitmap", "pixelSnapping");
+    if let Some(bitmap) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|dobj| dobj.as_bitmap())
+    {
+        let pixel_snapping = args
+            .get_string(activation, 0)?
+            .parse()
+            .map_err(|_| make_error_2008(activation, "pixelSnapping"))?;
+        bitmap.set_pixel_snapping(activation.context.gc_context, pixel_snapping);
+    }
+
     Ok(Value::Undefined)
 }
 
@@ -606,6 +606,25 @@ pub fn color_transform<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Implements `BitmapData.generateFilterRect`.
+pub fn generate_filter_rect<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(bitmap_data) = this.and_then(|this| this.as_bitmap_data()) {
+        bitmap_data.check_valid(activation)?;
+        let source_rect = args.get_object(activation, 0, "sourceRect")?;
+        let source_rect = super::display_object::object_to_rectangle(activation, source_rect)?;
+        let filter = args.get_object(activation, 1, "filter")?;
+        let filter = Filter::from_avm2_object(activation, filter)?;
+        let dest_rect = filter.calculate_dest_rect(source_rect);
+        return Ok(super::display_object::new_rectangle(activation, dest_rect)?.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
 pub fn get_color_bounds_rect<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Option<Object<'gc>>,
@@ -3,6 +3,7 @@
 use crate::avm2::activation::Activation;
 use crate::avm2::error::make_error_2008;
 use crate::avm2::filters::FilterAvm2Ext;
+use crate::avm2::globals::flash::system::security::check_cross_scripting;
 pub use crate::avm2::object::stage_allocator as display_object_allocator;
 use crate::avm2::object::{Object, TObject};
 use crate::avm2::parameters::ParametersExt;
@@ -21,6 +22,7 @@ use crate::vminterface::Instantiator;
 use crate::{avm2_stub_getter, avm2_stub_setter};
 use ruffle_render::filters::Filter;
 use std::str::FromStr;
+use std::sync::Arc;
 use swf::BlendMode;
 
 /// Implements `flash.display.DisplayObject`'s native instance constructor.
@@ -515,15 +517,22 @@ pub fn set_name<'gc>(
 
 /// Implements `parent`.
 pub fn get_parent<'gc>(
-    _activation: &mut Activation<'_, 'gc>,
+    activation: &mut Activation<'_, 'gc>,
     this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
-        return Ok(dobj
-            .avm2_parent()
-            .map(|parent| parent.object2())
-            .unwrap_or(Value::Null));
+        let Some(parent) = dobj.avm2_parent() else {
+            return Ok(Value::Null);
+        };
+
+        // The root of a loaded movie is parented to a `Loader` of the movie that loaded it.
+        let parent_movie = parent.movie();
+        if parent.as_stage().is_none() && !Arc::ptr_eq(&parent_movie, &dobj.movie()) {
+            check_cross_scripting(activation, &parent_movie, "DisplayObject.parent")?;
+        }
+
+        return Ok(parent.object2());
     }
 
     Ok(Value::Undefined)
@@ -745,6 +754,9 @@ pub fn get_blend_mode<'gc>(
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        if dobj.uses_blend_shader() {
+            return Ok("shader".into());
+        }
         let mode =
             AvmString::new_utf8(activation.context.gc_context, dobj.blend_mode().to_string());
         return Ok(mode.into());
@@ -761,7 +773,14 @@ pub fn set_blend_mode<'gc>(
     if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
         let mode = args.get_string(activation, 0)?;
 
-        if let Ok(mode) = BlendMode::from_str(&mode.to_string()) {
+        if &*mode == b"shader" {
+            // Without a `blendShader`, this falls back to the normal blend mode.
+            // Shader blends themselves are rendered as normal, see `set_blend_shader`.
+            let has_shader = dobj.blend_shader().is_some();
+            dobj.set_uses_blend_shader(activation.context.gc_context, has_shader);
+            dobj.set_blend_mode(activation.context.gc_context, BlendMode::Normal);
+        } else if let Ok(mode) = BlendMode::from_str(&mode.to_string()) {
+            dobj.set_uses_blend_shader(activation.context.gc_context, false);
             dobj.set_blend_mode(activation.context.gc_context, mode);
         } else {
             tracing::error!("Unknown blend mode {}", mode);
@@ -771,7 +790,27 @@ pub fn set_blend_mode<'gc>(
     Ok(Value::Undefined)
 }
 
-fn new_rectangle<'gc>(
+/// Implements `DisplayObject.blendShader`'s setter.
+pub fn set_blend_shader<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let shader = args.try_get_object(activation, 0);
+
+        // FIXME - render this through the shader once Pixel Bender shaders are supported.
+        // Until then, we render with the normal blend mode.
+        avm2_stub_setter!(activation, "flash.display.DisplayObject", "blendShader");
+
+        dobj.set_blend_shader(activation.context.gc_context, shader);
+        dobj.set_uses_blend_shader(activation.context.gc_context, shader.is_some());
+        dobj.set_blend_mode(activation.context.gc_context, BlendMode::Normal);
+    }
+    Ok(Value::Undefined)
+}
+
+pub fn new_rectangle<'gc>(
     activation: &mut Activation<'_, 'gc>,
     rectangle: Rectangle<Twips>,
 ) -> Result<Object<'gc>, Error<'gc>> {
@@ -853,6 +892,37 @@ pub fn set_scroll_rect<'gc>(
     Ok(Value::Undefined)
 }
 
+pub fn get_scale9grid<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let rect = dobj.scaling_grid();
+        if rect.is_valid() {
+            return Ok(new_rectangle(activation, rect)?.into());
+        } else {
+            return Ok(Value::Null);
+        }
+    }
+    Ok(Value::Undefined)
+}
+
+pub fn set_scale9grid<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let rect = match args.try_get_object(activation, 0) {
+            Some(rect) => object_to_rectangle(activation, rect)?,
+            None => Default::default(),
+        };
+        dobj.set_scaling_grid(activation.context.gc_context, rect);
+    }
+    Ok(Value::Undefined)
+}
+
 pub fn local_to_global<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Option<Object<'gc>>,
@@ -1008,6 +1078,31 @@ pub fn set_cache_as_bitmap<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Implements the `__ruffle__` namespaced `smoothPlayback` getter.
+pub fn get_smooth_playback<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this.and_then(|this| this.as_display_object()) {
+        return Ok((!this.interpolation_disabled()).into());
+    }
+    Ok(Value::Undefined)
+}
+
+/// Implements the `__ruffle__` namespaced `smoothPlayback` setter.
+pub fn set_smooth_playback<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this.and_then(|this| this.as_display_object()) {
+        let smooth = args.get(0).unwrap_or(&Value::Undefined).coerce_to_boolean();
+        this.set_interpolation_disabled(activation.context.gc_context, !smooth);
+    }
+    Ok(Value::Undefined)
+}
+
 /// `opaqueBackground`'s getter.
 pub fn get_opaque_background<'gc>(
     _activation: &mut Activation<'_, 'gc>,
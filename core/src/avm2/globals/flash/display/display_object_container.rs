@@ -116,7 +116,6 @@ pub(super) fn add_child_to_displaylist<'gc>(
 ) {
     if let Some(mut ctr) = parent.as_container() {
         ctr.insert_at_index(context, child, index);
-        child.set_placed_by_script(context.gc_context, true);
     }
 }
 
@@ -454,12 +453,6 @@ pub fn swap_children_at<'gc>(
                 )?));
             }
 
-            let child0 = ctr.child_by_index(index0 as usize).unwrap();
-            let child1 = ctr.child_by_index(index1 as usize).unwrap();
-
-            child0.set_placed_by_script(activation.context.gc_context, true);
-            child1.set_placed_by_script(activation.context.gc_context, true);
-
             ctr.swap_at_index(&mut activation.context, index0 as usize, index1 as usize);
         }
     }
@@ -493,9 +486,6 @@ pub fn swap_children<'gc>(
                 .position(|a| DisplayObject::ptr_eq(a, child1))
                 .ok_or("ArgumentError: Child is not a child of this display object")?;
 
-            child0.set_placed_by_script(activation.context.gc_context, true);
-            child1.set_placed_by_script(activation.context.gc_context, true);
-
             ctr.swap_at_index(&mut activation.context, index0, index1);
         }
     }
@@ -77,12 +77,14 @@ pub fn load<'gc>(
 
         let request = request_from_url_request(activation, url_request)?;
 
-        let url = request.url().to_string();
+        // `loaderURL` is the URL of the movie doing the loading.
+        let loader_url = activation.context.swf.url().to_string();
         let future = activation.context.load_manager.load_movie_into_clip(
             activation.context.player.clone(),
             content.into(),
             request,
-            Some(url),
+            activation.caller_movie_or_root(),
+            Some(loader_url),
             Some(MovieLoaderEventHandler::Avm2LoaderInfo(loader_info)),
             Some(Avm2LoaderData {
                 context,
@@ -176,10 +178,12 @@ pub fn load_bytes<'gc>(
             )?
             .as_object()
             .unwrap();
+        let loader_url = activation.caller_movie_or_root().url().to_string();
         let future = activation.context.load_manager.load_movie_into_clip_bytes(
             activation.context.player.clone(),
             content.into(),
             bytearray.bytes().to_vec(),
+            loader_url,
             Some(MovieLoaderEventHandler::Avm2LoaderInfo(loader_info)),
             Some(Avm2LoaderData {
                 context,
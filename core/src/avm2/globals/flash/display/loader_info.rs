@@ -3,18 +3,40 @@
 use crate::avm2::activation::Activation;
 use crate::avm2::bytearray::Endian;
 use crate::avm2::error::error;
+use crate::avm2::globals::flash::system::security::check_cross_scripting;
 use crate::avm2::object::{DomainObject, LoaderStream, Object, TObject};
 use crate::avm2::value::Value;
 use crate::avm2::{AvmString, Error};
 use crate::avm2_stub_getter;
 use crate::display_object::TDisplayObject;
+use crate::tag_utils::SwfMovie;
 use swf::{write_swf, Compression};
+use url::Url;
 
 pub use crate::avm2::object::loader_info_allocator;
 
 const INSUFFICIENT: &str =
     "Error #2099: The loading object is not sufficiently loaded to provide this information.";
 
+const NOT_SWF: &str =
+    "Error #2098: The loading object is not a .swf file, you cannot request SWF properties from it.";
+
+/// Whether a movie was loaded from the same domain as the movie that loaded it.
+///
+/// The root movie wasn't loaded by another movie, so it's always in the same domain.
+fn is_same_domain(movie: &SwfMovie) -> bool {
+    let Some(loader_url) = movie.loader_url() else {
+        return true;
+    };
+
+    match (Url::parse(movie.url()), Url::parse(loader_url)) {
+        // Local files don't have an origin, but they share the local sandbox.
+        (Ok(url), Ok(loader_url)) if url.scheme() == "file" => loader_url.scheme() == "file",
+        (Ok(url), Ok(loader_url)) => url.origin() == loader_url.origin(),
+        _ => false,
+    }
+}
+
 /// Implements `flash.display.LoaderInfo`'s native instance constructor.
 pub fn native_instance_init<'gc>(
     activation: &mut Activation<'_, 'gc>,
@@ -43,6 +65,9 @@ pub fn get_action_script_version<'gc>(
                 LoaderStream::NotYetLoaded(_, _, _) => {
                     return Err(Error::AvmError(error(_activation, INSUFFICIENT, 2099)?));
                 }
+                LoaderStream::Swf(movie, _) if !movie.is_swf() => {
+                    return Err(Error::AvmError(error(_activation, NOT_SWF, 2098)?));
+                }
                 LoaderStream::Swf(movie, _) => {
                     let version = if movie.is_action_script_3() { 3 } else { 2 };
                     return Ok(version.into());
@@ -69,6 +94,10 @@ pub fn get_application_domain<'gc>(
                 LoaderStream::NotYetLoaded(_, _, _) => {
                     return Ok(DomainObject::from_domain(activation, activation.domain())?.into());
                 }
+                LoaderStream::Swf(movie, _) if movie.is_swf() && !movie.is_action_script_3() => {
+                    // AVM1 movies don't have an application domain.
+                    return Ok(Value::Null);
+                }
                 LoaderStream::Swf(movie, _) => {
                     let domain = activation
                         .context
@@ -136,7 +165,7 @@ pub fn get_bytes_loaded<'gc>(
 
 /// `content` getter
 pub fn get_content<'gc>(
-    _activation: &mut Activation<'_, 'gc>,
+    activation: &mut Activation<'_, 'gc>,
     this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
@@ -145,14 +174,18 @@ pub fn get_content<'gc>(
             .as_loader_info_object()
             .and_then(|o| o.as_loader_stream())
         {
-            match &*loader_stream {
-                LoaderStream::Swf(_, root) | LoaderStream::NotYetLoaded(_, Some(root), _) => {
-                    return Ok(root.object2());
-                }
-                _ => {
-                    return Ok(Value::Null);
-                }
-            }
+            let content = match &*loader_stream {
+                LoaderStream::Swf(movie, root)
+                | LoaderStream::NotYetLoaded(movie, Some(root), _) => Some((movie.clone(), *root)),
+                _ => None,
+            };
+            drop(loader_stream);
+
+            let Some((movie, root)) = content else {
+                return Ok(Value::Null);
+            };
+            check_cross_scripting(activation, &movie, "LoaderInfo.content")?;
+            return Ok(root.object2());
         }
     }
 
@@ -172,8 +205,8 @@ pub fn get_content_type<'gc>(
         {
             match &*loader_stream {
                 LoaderStream::NotYetLoaded(_, _, _) => return Ok(Value::Null),
-                LoaderStream::Swf(_, _) => {
-                    return Ok("application/x-shockwave-flash".into());
+                LoaderStream::Swf(movie, _) => {
+                    return Ok(movie.content_type().into());
                 }
             }
         }
@@ -257,9 +290,8 @@ pub fn get_same_domain<'gc>(
                 LoaderStream::NotYetLoaded(_, _, _) => {
                     return Err(Error::AvmError(error(activation, INSUFFICIENT, 2099)?));
                 }
-                LoaderStream::Swf(_root, _) => {
-                    avm2_stub_getter!(activation, "flash.display.LoaderInfo", "sameDomain");
-                    return Ok(false.into());
+                LoaderStream::Swf(root, _) => {
+                    return Ok(is_same_domain(root).into());
                 }
             }
         }
@@ -283,9 +315,9 @@ pub fn get_child_allows_parent<'gc>(
                 LoaderStream::NotYetLoaded(_, _, _) => {
                     return Err(Error::AvmError(error(activation, INSUFFICIENT, 2099)?));
                 }
-                LoaderStream::Swf(_root, _) => {
-                    avm2_stub_getter!(activation, "flash.display.LoaderInfo", "childAllowsParent");
-                    return Ok(false.into());
+                LoaderStream::Swf(root, _) => {
+                    // TODO: The child may also allow its parent with `Security.allowDomain`.
+                    return Ok(is_same_domain(root).into());
                 }
             }
         }
@@ -309,9 +341,9 @@ pub fn get_parent_allows_child<'gc>(
                 LoaderStream::NotYetLoaded(_, _, _) => {
                     return Err(Error::AvmError(error(activation, INSUFFICIENT, 2099)?));
                 }
-                LoaderStream::Swf(_root, _) => {
-                    avm2_stub_getter!(activation, "flash.display.LoaderInfo", "parentAllowsChild");
-                    return Ok(false.into());
+                LoaderStream::Swf(root, _) => {
+                    // TODO: The parent may also allow its child with `Security.allowDomain`.
+                    return Ok(is_same_domain(root).into());
                 }
             }
         }
@@ -334,6 +366,9 @@ pub fn get_swf_version<'gc>(
                 LoaderStream::NotYetLoaded(_, _, _) => {
                     return Err(Error::AvmError(error(activation, INSUFFICIENT, 2099)?));
                 }
+                LoaderStream::Swf(root, _) if !root.is_swf() => {
+                    return Err(Error::AvmError(error(activation, NOT_SWF, 2098)?));
+                }
                 LoaderStream::Swf(root, _) => {
                     return Ok(root.version().into());
                 }
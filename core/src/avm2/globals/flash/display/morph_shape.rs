@@ -0,0 +1,45 @@
+//! `flash.display.MorphShape` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+
+/// Implements `MorphShape.__ruffle__::ratio`'s getter.
+pub fn get_ratio<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(morph_shape) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|dobj| dobj.as_morph_shape())
+    {
+        return Ok((f64::from(morph_shape.ratio()) / 65535.0).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `MorphShape.__ruffle__::ratio`'s setter.
+pub fn set_ratio<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(mut morph_shape) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|dobj| dobj.as_morph_shape())
+    {
+        let ratio = args.get_f64(activation, 0)?;
+        let ratio = if ratio.is_nan() {
+            0
+        } else {
+            (ratio.clamp(0.0, 1.0) * 65535.0).round() as u16
+        };
+        morph_shape.set_ratio(activation.context.gc_context, ratio);
+    }
+
+    Ok(Value::Undefined)
+}
@@ -7,7 +7,7 @@ use crate::avm2::parameters::ParametersExt;
 use crate::avm2::value::Value;
 use crate::avm2::Error;
 use crate::avm2::{ArrayObject, ArrayStorage};
-use crate::display_object::{StageDisplayState, TDisplayObject};
+use crate::display_object::{StageDisplayState, TDisplayObject, WindowMode};
 use crate::string::{AvmString, WString};
 use crate::{avm2_stub_getter, avm2_stub_setter};
 use swf::Color;
@@ -124,6 +124,46 @@ pub fn set_color<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Implement `colorCorrection`'s getter
+pub fn get_color_correction<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let color_correction = AvmString::new_utf8(
+        activation.context.gc_context,
+        activation.context.stage.color_correction().to_string(),
+    );
+    Ok(color_correction.into())
+}
+
+/// Implement `colorCorrection`'s setter
+pub fn set_color_correction<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Ok(color_correction) = args.get_string(activation, 0)?.parse() {
+        activation
+            .context
+            .stage
+            .set_color_correction(activation.context.gc_context, color_correction);
+    } else {
+        return Err(make_error_2008(activation, "colorCorrection"));
+    }
+    Ok(Value::Undefined)
+}
+
+/// Implement `colorCorrectionSupport`'s getter
+pub fn get_color_correction_support<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    // Ruffle never corrects colors.
+    Ok("unsupported".into())
+}
+
 /// Implement `contentsScaleFactor`'s getter
 pub fn get_contents_scale_factor<'gc>(
     activation: &mut Activation<'_, 'gc>,
@@ -346,11 +386,20 @@ pub fn get_stage_width<'gc>(
 
 /// Implement `stageWidth`'s setter
 pub fn set_stage_width<'gc>(
-    _activation: &mut Activation<'_, 'gc>,
-    _this: Option<Object<'gc>>,
-    _args: &[Value<'gc>],
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    // For some reason this value is settable but it does nothing.
+    if let Some(dobj) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_stage())
+    {
+        // This only does something in the `noScale` scale mode.
+        let width = args.get_i32(activation, 0)?.max(0) as u32;
+        let height = dobj.stage_size().1;
+        dobj.set_stage_size(&mut activation.context, width, height);
+    }
+
     Ok(Value::Undefined)
 }
 
@@ -372,12 +421,30 @@ pub fn get_stage_height<'gc>(
 
 /// Implement `stageHeight`'s setter
 pub fn set_stage_height<'gc>(
-    _activation: &mut Activation<'_, 'gc>,
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(dobj) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_stage())
+    {
+        // This only does something in the `noScale` scale mode.
+        let width = dobj.stage_size().0;
+        let height = args.get_i32(activation, 0)?.max(0) as u32;
+        dobj.set_stage_size(&mut activation.context, width, height);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implement `wmodeGPU`'s getter
+pub fn get_wmode_gpu<'gc>(
+    activation: &mut Activation<'_, 'gc>,
     _this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    // For some reason this value is settable but it does nothing.
-    Ok(Value::Undefined)
+    Ok((activation.context.stage.window_mode() == WindowMode::Gpu).into())
 }
 
 /// Implement `allowsFullScreen`'s getter
@@ -463,7 +530,7 @@ pub fn invalidate<'gc>(
         .and_then(|this| this.as_display_object())
         .and_then(|this| this.as_stage())
     {
-        stage.set_invalidated(activation.context.gc_context, true);
+        stage.invalidate(&mut activation.context);
     }
     Ok(Value::Undefined)
 }
@@ -1,20 +1,35 @@
+use crate::avm2::error::make_error_2008;
 use crate::avm2::object::Context3DObject;
 use crate::avm2::object::TObject;
+use crate::avm2::parameters::ParametersExt;
 
 use crate::avm2::{Activation, Error, Object, Value};
+use ruffle_render::backend::Context3DProfile;
 
 pub use crate::avm2::object::stage_3d_allocator;
 
+/// Attempts to create a `Context3D` with the given profile, returning `false`
+/// if the render backend can't provide one.
 pub fn request_context3d_internal<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Option<Object<'gc>>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(this) = this {
         let this_stage3d = this.as_stage_3d().unwrap();
         if this_stage3d.context3d().is_none() {
-            let context = activation.context.renderer.create_context3d()?;
-            let context3d_obj = Context3DObject::from_context(activation, context)?;
+            let profile = args.get_string(activation, 1)?;
+            let profile = Context3DProfile::from_wstr(&profile)
+                .ok_or_else(|| make_error_2008(activation, "profile"))?;
+
+            let context = match activation.context.renderer.create_context3d(profile) {
+                Ok(context) => context,
+                Err(e) => {
+                    tracing::warn!("Failed to create Context3D with profile {profile:?}: {e}");
+                    return Ok(false.into());
+                }
+            };
+            let context3d_obj = Context3DObject::from_context(activation, context, profile)?;
             this_stage3d.set_context3d(context3d_obj, activation.context.gc_context);
 
             let event = activation
@@ -28,7 +43,7 @@ pub fn request_context3d_internal<'gc>(
             this.call_public_property("dispatchEvent", &[event.into()], activation)?;
         }
     }
-    Ok(Value::Undefined)
+    Ok(true.into())
 }
 
 pub fn get_context_3d<'gc>(
@@ -41,3 +56,72 @@ pub fn get_context_3d<'gc>(
     }
     Ok(Value::Undefined)
 }
+
+pub fn get_x<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this.and_then(|this| this.as_stage_3d()) {
+        return Ok(this.x().into());
+    }
+    Ok(Value::Undefined)
+}
+
+pub fn set_x<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this.and_then(|this| this.as_stage_3d()) {
+        let x = args.get_f64(activation, 0)?;
+        this.set_x(x, activation.context.gc_context);
+    }
+    Ok(Value::Undefined)
+}
+
+pub fn get_y<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this.and_then(|this| this.as_stage_3d()) {
+        return Ok(this.y().into());
+    }
+    Ok(Value::Undefined)
+}
+
+pub fn set_y<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this.and_then(|this| this.as_stage_3d()) {
+        let y = args.get_f64(activation, 0)?;
+        this.set_y(y, activation.context.gc_context);
+    }
+    Ok(Value::Undefined)
+}
+
+pub fn get_visible<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this.and_then(|this| this.as_stage_3d()) {
+        return Ok(this.visible().into());
+    }
+    Ok(Value::Undefined)
+}
+
+pub fn set_visible<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this.and_then(|this| this.as_stage_3d()) {
+        let visible = args.get_bool(0);
+        this.set_visible(visible, activation.context.gc_context);
+    }
+    Ok(Value::Undefined)
+}
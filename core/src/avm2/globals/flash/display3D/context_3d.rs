@@ -1,16 +1,17 @@
-use crate::avm2::error::make_error_2008;
+use crate::avm2::error::{argument_error, make_error_2008, range_error};
+use crate::avm2::object::Context3DObject;
 use crate::avm2::parameters::ParametersExt;
 use crate::avm2::Activation;
 use crate::avm2::TObject;
 use crate::avm2::Value;
 use crate::avm2::{Error, Object};
 use crate::avm2_stub_method;
-use ruffle_render::backend::Context3DTextureFilter;
 use ruffle_render::backend::Context3DWrapMode;
 use ruffle_render::backend::{
     BufferUsage, Context3DBlendFactor, Context3DCompareMode, Context3DTextureFormat,
     Context3DTriangleFace, Context3DVertexBufferFormat, ProgramType,
 };
+use ruffle_render::backend::{Context3DMipFilter, Context3DTextureFilter};
 
 pub fn create_index_buffer<'gc>(
     activation: &mut Activation<'_, 'gc>,
@@ -197,6 +198,21 @@ pub fn present<'gc>(
     Ok(Value::Undefined)
 }
 
+pub fn draw_to_bitmap_data<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(context) = this.and_then(|this| this.as_context_3d()) {
+        let destination = args.get_object(activation, 0, "destination")?;
+        if let Some(destination) = destination.as_bitmap_data() {
+            destination.check_valid(activation)?;
+            context.draw_to_bitmap_data(activation, destination)?;
+        }
+    }
+    Ok(Value::Undefined)
+}
+
 pub fn set_culling<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Option<Object<'gc>>,
@@ -240,6 +256,8 @@ pub fn set_program_constants_from_matrix<'gc>(
         };
 
         let first_register = args.get_u32(activation, 1)?;
+        // A matrix always fills 4 registers
+        check_constant_registers(activation, context, is_vertex, first_register, 4)?;
 
         let mut matrix = args
             .get(2)
@@ -327,6 +345,15 @@ pub fn set_program_constants_from_vector<'gc>(
             .take(to_take)
             .collect::<Result<Vec<f32>, _>>()?;
 
+        let num_registers = (raw_data.len() as u32 + 3) / 4;
+        check_constant_registers(
+            activation,
+            context,
+            program_type,
+            first_register,
+            num_registers,
+        )?;
+
         context.set_program_constants_from_matrix(
             activation,
             program_type,
@@ -374,6 +401,8 @@ pub fn create_texture<'gc>(
             )
         })?;
 
+        check_texture_size(activation, context, width, height)?;
+
         let class = activation.avm2().classes().texture;
 
         return context.create_texture(
@@ -410,6 +439,8 @@ pub fn create_rectangle_texture<'gc>(
             )
         })?;
 
+        check_texture_size(activation, context, width, height)?;
+
         let class = activation.avm2().classes().rectangletexture;
 
         return context.create_texture(
@@ -446,6 +477,8 @@ pub fn create_cube_texture<'gc>(
             )
         })?;
 
+        check_texture_size(activation, context, size, size)?;
+
         return context.create_cube_texture(
             size,
             format,
@@ -624,31 +657,80 @@ pub fn set_sampler_state_at<'gc>(
         let filter = Context3DTextureFilter::from_wstr(&filter)
             .ok_or_else(|| make_error_2008(activation, "filter"))?;
 
-        if matches!(
-            filter,
-            Context3DTextureFilter::Anisotropic2X
-                | Context3DTextureFilter::Anisotropic4X
-                | Context3DTextureFilter::Anisotropic8X
-                | Context3DTextureFilter::Anisotropic16X
-        ) {
-            avm2_stub_method!(
-                activation,
-                "flash.display3D.Context3D",
-                "setSamplerStateAt",
-                "filter == 'anisotropic'"
-            );
-        }
+        let mip_filter = Context3DMipFilter::from_wstr(&mip_filter)
+            .ok_or_else(|| make_error_2008(activation, "mipfilter"))?;
 
-        if &*mip_filter != b"mipnone" {
-            avm2_stub_method!(
-                activation,
-                "flash.display3D.Context3D",
-                "setSamplerStateAt",
-                "mipFilter != 'none'"
-            );
-        }
+        context.set_sampler_state_at(activation, sampler, wrap, filter, mip_filter);
+    }
+    Ok(Value::Undefined)
+}
 
-        context.set_sampler_state_at(activation, sampler, wrap, filter);
+pub fn get_profile<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(context) = this.and_then(|this| this.as_context_3d()) {
+        return Ok(context.profile().to_str().into());
+    }
+    Ok(Value::Undefined)
+}
+
+pub fn get_max_back_buffer_width<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(context) = this.and_then(|this| this.as_context_3d()) {
+        return Ok(context.profile().max_texture_size().into());
+    }
+    Ok(Value::Undefined)
+}
+
+pub fn get_max_back_buffer_height<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(context) = this.and_then(|this| this.as_context_3d()) {
+        return Ok(context.profile().max_texture_size().into());
     }
     Ok(Value::Undefined)
 }
+
+/// Checks that `num_registers` constant registers starting at `first_register`
+/// are available in the context's profile.
+fn check_constant_registers<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: Context3DObject<'gc>,
+    program_type: ProgramType,
+    first_register: u32,
+    num_registers: u32,
+) -> Result<(), Error<'gc>> {
+    let max_registers = context.profile().max_constant_registers(program_type);
+    if first_register.saturating_add(num_registers) > max_registers {
+        return Err(Error::AvmError(range_error(
+            activation,
+            "Error #2006: The supplied index is out of bounds.",
+            2006,
+        )?));
+    }
+    Ok(())
+}
+
+fn check_texture_size<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    context: Context3DObject<'gc>,
+    width: u32,
+    height: u32,
+) -> Result<(), Error<'gc>> {
+    let max_size = context.profile().max_texture_size();
+    if width > max_size || height > max_size {
+        return Err(Error::AvmError(argument_error(
+            activation,
+            &format!("Error #3683: Texture too big (max is {max_size}x{max_size})."),
+            3683,
+        )?));
+    }
+    Ok(())
+}
@@ -2,7 +2,6 @@ use crate::avm2::Activation;
 use crate::avm2::TObject;
 use crate::avm2::Value;
 use crate::avm2::{Error, Object};
-use crate::avm2_stub_method;
 
 pub fn upload_from_bitmap_data<'gc>(
     activation: &mut Activation<'_, 'gc>,
@@ -11,25 +10,17 @@ pub fn upload_from_bitmap_data<'gc>(
 ) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(texture) = this.and_then(|this| this.as_texture()) {
         if let Some(source) = args[0].coerce_to_object(activation)?.as_bitmap_data() {
+            // Flash orders the faces as +X, -X, +Y, -Y, +Z, -Z,
+            // which matches the array layer order that wgpu expects for cube maps.
             let side = args[1].coerce_to_u32(activation)?;
             let mip_level = args[2].coerce_to_u32(activation)?;
-            if mip_level == 0 {
-                texture.context3d().copy_bitmap_to_texture(
-                    source
-                        .bitmap_handle(activation.context.gc_context, activation.context.renderer),
-                    texture.handle(),
-                    // FIXME - is this right?
-                    side,
-                    activation,
-                );
-            } else {
-                avm2_stub_method!(
-                    activation,
-                    "flash.display3D.textures.CubeTexture",
-                    "uploadFromBitmapData",
-                    "with miplevel != 0"
-                );
-            }
+            texture.context3d().copy_bitmap_to_texture(
+                source.bitmap_handle(activation.context.gc_context, activation.context.renderer),
+                texture.handle(),
+                side,
+                mip_level,
+                activation,
+            );
         } else {
             panic!("Invalid source: {:?}", args[0]);
         }
@@ -43,12 +43,34 @@ pub fn add_event_listener<'gc>(
         let listener = args.get_value(1).as_callable(activation, None, None)?;
         let use_capture = args.get_bool(2);
         let priority = args.get_i32(activation, 3)?;
-
-        //TODO: If we ever get weak GC references, we should respect `useWeakReference`.
-        dispatch_list
-            .as_dispatch_mut(activation.context.gc_context)
-            .ok_or_else(|| Error::from("Internal properties should have what I put in them"))?
-            .add_event_listener(event_type, priority, listener, use_capture);
+        let use_weak_reference = args.get_bool(4);
+
+        let mc = activation.context.gc_context;
+        let mut dispatch_list = dispatch_list
+            .as_dispatch_mut(mc)
+            .ok_or_else(|| Error::from("Internal properties should have what I put in them"))?;
+        let dead = dispatch_list.remove_dead_handlers(event_type, mc);
+        let added = dispatch_list.add_event_listener(
+            event_type,
+            priority,
+            listener,
+            use_capture,
+            use_weak_reference,
+            mc,
+        );
+        drop(dispatch_list);
+        for _ in 0..dead {
+            activation
+                .context
+                .avm2
+                .count_event_listener(event_type, false);
+        }
+        if added {
+            activation
+                .context
+                .avm2
+                .count_event_listener(event_type, true);
+        }
 
         Avm2::register_broadcast_listener(&mut activation.context, this, event_type);
     }
@@ -68,10 +90,25 @@ pub fn remove_event_listener<'gc>(
         let listener = args.get_value(1).as_callable(activation, None, None)?;
         let use_capture = args.get_bool(2);
 
-        dispatch_list
-            .as_dispatch_mut(activation.context.gc_context)
-            .ok_or_else(|| Error::from("Internal properties should have what I put in them"))?
-            .remove_event_listener(event_type, listener, use_capture);
+        let mc = activation.context.gc_context;
+        let mut dispatch_list = dispatch_list
+            .as_dispatch_mut(mc)
+            .ok_or_else(|| Error::from("Internal properties should have what I put in them"))?;
+        let dead = dispatch_list.remove_dead_handlers(event_type, mc);
+        let removed = dispatch_list.remove_event_listener(event_type, listener, use_capture, mc);
+        drop(dispatch_list);
+        for _ in 0..dead {
+            activation
+                .context
+                .avm2
+                .count_event_listener(event_type, false);
+        }
+        if removed {
+            activation
+                .context
+                .avm2
+                .count_event_listener(event_type, false);
+        }
     }
 
     Ok(Value::Undefined)
@@ -90,7 +127,7 @@ pub fn has_event_listener<'gc>(
         return Ok(dispatch_list
             .as_dispatch_mut(activation.context.gc_context)
             .ok_or_else(|| Error::from("Internal properties should have what I put in them"))?
-            .has_event_listener(event_type)
+            .has_event_listener(event_type, activation.context.gc_context)
             .into());
     }
 
@@ -110,7 +147,7 @@ pub fn will_trigger<'gc>(
         if dispatch_list
             .as_dispatch_mut(activation.context.gc_context)
             .ok_or_else(|| Error::from("Internal properties should have what I put in them"))?
-            .has_event_listener(event_type)
+            .has_event_listener(event_type, activation.context.gc_context)
         {
             return Ok(true.into());
         }
@@ -137,10 +174,20 @@ pub fn dispatch_event<'gc>(
     this: Option<Object<'gc>>,
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    let event = args.get_object(activation, 0, "event")?;
+    let mut event = args.get_object(activation, 0, "event")?;
 
-    if event.as_event().is_none() {
+    let Some(already_dispatched) = event.as_event().map(|event| event.target().is_some()) else {
         return Err("Dispatched Events must be subclasses of Event.".into());
+    };
+
+    // An event that has already been dispatched is cloned before being
+    // dispatched again, so that its phase and target stay intact.
+    if already_dispatched {
+        event = event
+            .call_public_property("clone", &[], activation)?
+            .as_object()
+            .filter(|event| event.as_event().is_some())
+            .ok_or_else(|| Error::from("Event.clone must return an Event."))?;
     }
 
     if let Some(this) = this {
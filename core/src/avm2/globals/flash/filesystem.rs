@@ -0,0 +1,3 @@
+//! `flash.filesystem` namespace
+
+pub mod file;
@@ -0,0 +1,353 @@
+package flash.filesystem {
+    import flash.errors.IOError;
+    import flash.events.Event;
+    import flash.events.FileListEvent;
+    import flash.events.IOErrorEvent;
+    import flash.net.FileReference;
+    import flash.utils.ByteArray;
+    import flash.utils.setTimeout;
+
+    // Movies never see the real filesystem: every file lives in the sandbox
+    // given to the player, where each of the AIR directories is a directory
+    // of its own (e.g. `applicationStorageDirectory` is `/app-storage`).
+    public class File extends FileReference {
+        public static const separator: String = "/";
+        public static const lineEnding: String = "\n";
+        public static const systemCharset: String = "utf-8";
+
+        private static const APP_STORAGE_DIRECTORY: String = "/app-storage";
+        private static const APP_DIRECTORY: String = "/app";
+        private static const TEMP_DIRECTORY: String = "/tmp";
+
+        private static var _lastTempId: uint = 0;
+
+        // The normalized path of this file in the sandbox, or null.
+        private var _path: String;
+
+        public function File(path: String = null) {
+            checkSupported();
+            if (path != null) {
+                this.nativePath = path;
+            }
+        }
+
+        private static native function checkSupported():void;
+
+        internal static native function readFile(path: String): ByteArray;
+        internal static native function writeFile(path: String, bytes: ByteArray): Boolean;
+        internal static native function getFileInfo(path: String): Object;
+        internal static native function listDirectory(path: String): Array;
+        internal static native function createDirectoryAt(path: String): Boolean;
+        internal static native function deleteAt(path: String, recursive: Boolean): Boolean;
+
+        public static function get applicationStorageDirectory(): File {
+            return new File(APP_STORAGE_DIRECTORY);
+        }
+
+        public static function get applicationDirectory(): File {
+            return new File(APP_DIRECTORY);
+        }
+
+        public static function get cacheDirectory(): File {
+            return new File("/cache");
+        }
+
+        public static function get desktopDirectory(): File {
+            return new File("/desktop");
+        }
+
+        public static function get documentsDirectory(): File {
+            return new File("/documents");
+        }
+
+        public static function get userDirectory(): File {
+            return new File("/user");
+        }
+
+        public static function get permissionStatus(): String {
+            return "granted";
+        }
+
+        public static function createTempFile(): File {
+            var file: File = nextTempFile(".tmp");
+            if (!writeFile(file._path, new ByteArray())) {
+                throw accessDenied();
+            }
+            return file;
+        }
+
+        public static function createTempDirectory(): File {
+            var file: File = nextTempFile("");
+            file.createDirectory();
+            return file;
+        }
+
+        private static function nextTempFile(extension: String): File {
+            var file: File;
+            do {
+                _lastTempId++;
+                file = new File(TEMP_DIRECTORY + "/fla" + _lastTempId + extension);
+            } while (file.exists);
+            return file;
+        }
+
+        private static function doesNotExist(): IOError {
+            return new IOError("Error #3003: File or directory does not exist.", 3003);
+        }
+
+        private static function accessDenied(): IOError {
+            return new IOError("Error #3001: File or directory access denied.", 3001);
+        }
+
+        // Resolves `.` and `..`, without ever leaving the sandbox.
+        private static function normalize(path: String): String {
+            var segments: Array = [];
+            for each (var segment: String in path.split("\\").join("/").split("/")) {
+                if (segment == "..") {
+                    segments.pop();
+                } else if (segment != "" && segment != ".") {
+                    segments.push(segment);
+                }
+            }
+            return "/" + segments.join("/");
+        }
+
+        private static function isInDirectory(path: String, directory: String): Boolean {
+            return path == directory || path.indexOf(directory + "/") == 0;
+        }
+
+        public function get nativePath(): String {
+            return this._path;
+        }
+
+        public function set nativePath(value: String): void {
+            if (value.indexOf("app-storage:") == 0) {
+                value = APP_STORAGE_DIRECTORY + "/" + value.substr("app-storage:".length);
+            } else if (value.indexOf("app:") == 0) {
+                value = APP_DIRECTORY + "/" + value.substr("app:".length);
+            } else if (value.indexOf("file://") == 0) {
+                value = decodeURI(value.substr("file://".length));
+            }
+            this._path = normalize(value);
+        }
+
+        public function get url(): String {
+            if (this._path == null) {
+                return null;
+            }
+            if (isInDirectory(this._path, APP_STORAGE_DIRECTORY)) {
+                return "app-storage:" + encodeURI(this._path.substr(APP_STORAGE_DIRECTORY.length) || "/");
+            }
+            if (isInDirectory(this._path, APP_DIRECTORY)) {
+                return "app:" + encodeURI(this._path.substr(APP_DIRECTORY.length) || "/");
+            }
+            return "file://" + encodeURI(this._path);
+        }
+
+        public function set url(value: String): void {
+            this.nativePath = value;
+        }
+
+        override public function get name(): String {
+            if (this._path == null) {
+                return null;
+            }
+            return this._path.substr(this._path.lastIndexOf("/") + 1);
+        }
+
+        override public function get extension(): String {
+            var name: String = this.name;
+            if (name == null || name.lastIndexOf(".") == -1) {
+                return null;
+            }
+            return name.substr(name.lastIndexOf(".") + 1);
+        }
+
+        override public function get size(): Number {
+            var info: Object = getFileInfo(this._path);
+            if (info == null) {
+                throw new IOError("Error #3003: File or directory does not exist.", 3003);
+            }
+            return info.size;
+        }
+
+        public function get exists(): Boolean {
+            return this._path != null && getFileInfo(this._path) != null;
+        }
+
+        public function get isDirectory(): Boolean {
+            var info: Object = this._path == null ? null : getFileInfo(this._path);
+            return info != null && info.isDirectory;
+        }
+
+        public function get isHidden(): Boolean {
+            return false;
+        }
+
+        public function get isPackage(): Boolean {
+            return false;
+        }
+
+        public function get isSymbolicLink(): Boolean {
+            return false;
+        }
+
+        public function get parent(): File {
+            if (this._path == null || this._path == "/") {
+                return null;
+            }
+            return new File(this._path.substr(0, this._path.lastIndexOf("/")) || "/");
+        }
+
+        public function resolvePath(path: String): File {
+            if (path.charAt(0) == "/" || path.indexOf(":") != -1 || this._path == null) {
+                return new File(path);
+            }
+            return new File(this._path + "/" + path);
+        }
+
+        public function getDirectoryListing(): Array {
+            var names: Array = this._path == null ? null : listDirectory(this._path);
+            if (names == null) {
+                throw doesNotExist();
+            }
+            var files: Array = [];
+            for each (var name: String in names) {
+                files.push(this.resolvePath(name));
+            }
+            return files;
+        }
+
+        public function createDirectory(): void {
+            if (this._path == null || !createDirectoryAt(this._path)) {
+                throw accessDenied();
+            }
+        }
+
+        public function deleteFile(): void {
+            if (!this.exists) {
+                throw doesNotExist();
+            }
+            if (this.isDirectory || !deleteAt(this._path, false)) {
+                throw accessDenied();
+            }
+        }
+
+        public function deleteDirectory(deleteDirectoryContents: Boolean = false): void {
+            if (!this.exists) {
+                throw doesNotExist();
+            }
+            if (!this.isDirectory || !deleteAt(this._path, deleteDirectoryContents)) {
+                throw accessDenied();
+            }
+        }
+
+        public function copyTo(newLocation: FileReference, overwrite: Boolean = false): void {
+            var destination: File = prepareDestination(newLocation, overwrite);
+            copyEntry(this, destination);
+        }
+
+        public function moveTo(newLocation: FileReference, overwrite: Boolean = false): void {
+            var destination: File = prepareDestination(newLocation, overwrite);
+            copyEntry(this, destination);
+            if (!deleteAt(this._path, true)) {
+                throw accessDenied();
+            }
+            this._path = destination._path;
+        }
+
+        private function prepareDestination(newLocation: FileReference, overwrite: Boolean): File {
+            var destination: File = newLocation as File;
+            if (destination == null || destination._path == null) {
+                throw new ArgumentError("Error #2004: One of the parameters is invalid.", 2004);
+            }
+            if (!this.exists) {
+                throw doesNotExist();
+            }
+            if (isInDirectory(destination._path, this._path)) {
+                throw accessDenied();
+            }
+            if (destination.exists) {
+                if (!overwrite || !deleteAt(destination._path, true)) {
+                    throw accessDenied();
+                }
+            }
+            return destination;
+        }
+
+        private static function copyEntry(source: File, destination: File): void {
+            if (source.isDirectory) {
+                destination.createDirectory();
+                for each (var name: String in listDirectory(source._path)) {
+                    copyEntry(source.resolvePath(name), destination.resolvePath(name));
+                }
+            } else {
+                var bytes: ByteArray = readFile(source._path);
+                if (bytes == null || !writeFile(destination._path, bytes)) {
+                    throw accessDenied();
+                }
+            }
+        }
+
+        // The asynchronous operations run the synchronous ones on the next frame,
+        // and report their outcome with an event.
+        private function runAsync(operation: Function): void {
+            var file: File = this;
+            setTimeout(function(): void {
+                var event: Event;
+                try {
+                    event = operation();
+                } catch (ioError: IOError) {
+                    event = new IOErrorEvent(IOErrorEvent.IO_ERROR, false, false, ioError.message, ioError.errorID);
+                }
+                file.dispatchEvent(event);
+            }, 0);
+        }
+
+        public function getDirectoryListingAsync(): void {
+            var file: File = this;
+            this.runAsync(function(): Event {
+                return new FileListEvent(FileListEvent.DIRECTORY_LISTING, false, false, file.getDirectoryListing());
+            });
+        }
+
+        public function deleteFileAsync(): void {
+            var file: File = this;
+            this.runAsync(function(): Event {
+                file.deleteFile();
+                return new Event(Event.COMPLETE);
+            });
+        }
+
+        public function deleteDirectoryAsync(deleteDirectoryContents: Boolean = false): void {
+            var file: File = this;
+            this.runAsync(function(): Event {
+                file.deleteDirectory(deleteDirectoryContents);
+                return new Event(Event.COMPLETE);
+            });
+        }
+
+        public function copyToAsync(newLocation: FileReference, overwrite: Boolean = false): void {
+            var file: File = this;
+            this.runAsync(function(): Event {
+                file.copyTo(newLocation, overwrite);
+                return new Event(Event.COMPLETE);
+            });
+        }
+
+        public function moveToAsync(newLocation: FileReference, overwrite: Boolean = false): void {
+            var file: File = this;
+            this.runAsync(function(): Event {
+                file.moveTo(newLocation, overwrite);
+                return new Event(Event.COMPLETE);
+            });
+        }
+
+        public function clone(): File {
+            return new File(this._path);
+        }
+
+        public function canonicalize(): void {
+        }
+    }
+}
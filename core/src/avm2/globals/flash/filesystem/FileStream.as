@@ -0,0 +1,279 @@
+package flash.filesystem {
+    import flash.errors.IOError;
+    import flash.events.Event;
+    import flash.events.EventDispatcher;
+    import flash.events.IOErrorEvent;
+    import flash.events.OutputProgressEvent;
+    import flash.events.ProgressEvent;
+    import flash.utils.ByteArray;
+    import flash.utils.Endian;
+    import flash.utils.IDataInput;
+    import flash.utils.IDataOutput;
+    import flash.utils.setTimeout;
+
+    // The whole file is read when it's opened, and the stream works on that copy,
+    // which is written back to the file when the stream is closed.
+    // Streams opened with `openAsync` work the same, but also report
+    // their progress with events on the next frame.
+    public class FileStream extends EventDispatcher implements IDataInput, IDataOutput {
+        private var _file: File;
+        private var _fileMode: String;
+        private var _buffer: ByteArray;
+        private var _endian: String = Endian.BIG_ENDIAN;
+        private var _objectEncoding: uint = 3;
+        private var _async: Boolean = false;
+
+        public var readAhead: Number = Number.POSITIVE_INFINITY;
+
+        public function FileStream() {
+        }
+
+        public function open(file: File, fileMode: String): void {
+            this.openFile(file, fileMode);
+            this._async = false;
+        }
+
+        public function openAsync(file: File, fileMode: String): void {
+            try {
+                this.openFile(file, fileMode);
+            } catch (ioError: IOError) {
+                this.dispatchLater(new IOErrorEvent(IOErrorEvent.IO_ERROR, false, false, ioError.message, ioError.errorID));
+                return;
+            }
+            this._async = true;
+
+            if (fileMode != FileMode.WRITE) {
+                var length: Number = this._buffer.length;
+                this.dispatchLater(new ProgressEvent(ProgressEvent.PROGRESS, false, false, length, length));
+                this.dispatchLater(new Event(Event.COMPLETE));
+            }
+        }
+
+        private function dispatchLater(event: Event): void {
+            var stream: FileStream = this;
+            setTimeout(function(): void {
+                stream.dispatchEvent(event);
+            }, 0);
+        }
+
+        private function openFile(file: File, fileMode: String): void {
+            if (this._buffer != null) {
+                this.close();
+            }
+
+            var bytes: ByteArray = null;
+            if (fileMode != FileMode.WRITE) {
+                bytes = File.readFile(file.nativePath);
+                if (bytes == null && fileMode == FileMode.READ) {
+                    throw new IOError("Error #3003: File or directory does not exist.", 3003);
+                }
+            }
+            if (bytes == null) {
+                bytes = new ByteArray();
+            }
+            bytes.endian = this._endian;
+            bytes.objectEncoding = this._objectEncoding;
+            bytes.position = fileMode == FileMode.APPEND ? bytes.length : 0;
+
+            // Opening a file for writing creates it, or truncates it.
+            if (fileMode != FileMode.READ && !File.writeFile(file.nativePath, bytes)) {
+                throw new IOError("Error #3013: File or directory is in use.", 3013);
+            }
+
+            this._file = file;
+            this._fileMode = fileMode;
+            this._buffer = bytes;
+        }
+
+        public function close(): void {
+            if (this._buffer == null) {
+                return;
+            }
+            var file: File = this._file;
+            var bytes: ByteArray = this._buffer;
+            var fileMode: String = this._fileMode;
+            this._file = null;
+            this._fileMode = null;
+            this._buffer = null;
+
+            var written: Boolean = fileMode == FileMode.READ || File.writeFile(file.nativePath, bytes);
+            if (!this._async) {
+                if (!written) {
+                    throw new IOError("Error #3013: File or directory is in use.", 3013);
+                }
+                return;
+            }
+
+            if (!written) {
+                this.dispatchLater(new IOErrorEvent(IOErrorEvent.IO_ERROR, false, false, "Error #3013: File or directory is in use.", 3013));
+                return;
+            }
+            if (fileMode != FileMode.READ) {
+                this.dispatchLater(new OutputProgressEvent(OutputProgressEvent.OUTPUT_PROGRESS, false, false, 0, bytes.length));
+            }
+            this.dispatchLater(new Event(Event.CLOSE));
+        }
+
+        private function get readable(): ByteArray {
+            if (this._buffer == null || this._fileMode == FileMode.WRITE || this._fileMode == FileMode.APPEND) {
+                throw new IOError("Error #2029: This URLStream object does not have a stream opened.", 2029);
+            }
+            return this._buffer;
+        }
+
+        private function get writable(): ByteArray {
+            if (this._buffer == null || this._fileMode == FileMode.READ) {
+                throw new IOError("Error #2029: This URLStream object does not have a stream opened.", 2029);
+            }
+            if (this._fileMode == FileMode.APPEND) {
+                this._buffer.position = this._buffer.length;
+            }
+            return this._buffer;
+        }
+
+        public function get bytesAvailable(): uint {
+            return this._buffer == null ? 0 : this._buffer.bytesAvailable;
+        }
+
+        public function get position(): Number {
+            return this._buffer == null ? 0 : this._buffer.position;
+        }
+
+        public function set position(value: Number): void {
+            if (this._buffer != null) {
+                this._buffer.position = value;
+            }
+        }
+
+        public function get endian(): String {
+            return this._endian;
+        }
+
+        public function set endian(value: String): void {
+            this._endian = value;
+            if (this._buffer != null) {
+                this._buffer.endian = value;
+            }
+        }
+
+        public function get objectEncoding(): uint {
+            return this._objectEncoding;
+        }
+
+        public function set objectEncoding(value: uint): void {
+            this._objectEncoding = value;
+            if (this._buffer != null) {
+                this._buffer.objectEncoding = value;
+            }
+        }
+
+        public function truncate(): void {
+            var bytes: ByteArray = this.writable;
+            bytes.length = bytes.position;
+        }
+
+        public function readBoolean(): Boolean {
+            return this.readable.readBoolean();
+        }
+
+        public function readByte(): int {
+            return this.readable.readByte();
+        }
+
+        public function readBytes(bytes: ByteArray, offset: uint = 0, length: uint = 0): void {
+            this.readable.readBytes(bytes, offset, length);
+        }
+
+        public function readDouble(): Number {
+            return this.readable.readDouble();
+        }
+
+        public function readFloat(): Number {
+            return this.readable.readFloat();
+        }
+
+        public function readInt(): int {
+            return this.readable.readInt();
+        }
+
+        public function readMultiByte(length: uint, charSet: String): String {
+            return this.readable.readMultiByte(length, charSet);
+        }
+
+        public function readObject(): * {
+            return this.readable.readObject();
+        }
+
+        public function readShort(): int {
+            return this.readable.readShort();
+        }
+
+        public function readUnsignedByte(): uint {
+            return this.readable.readUnsignedByte();
+        }
+
+        public function readUnsignedInt(): uint {
+            return this.readable.readUnsignedInt();
+        }
+
+        public function readUnsignedShort(): uint {
+            return this.readable.readUnsignedShort();
+        }
+
+        public function readUTF(): String {
+            return this.readable.readUTF();
+        }
+
+        public function readUTFBytes(length: uint): String {
+            return this.readable.readUTFBytes(length);
+        }
+
+        public function writeBoolean(value: Boolean): void {
+            this.writable.writeBoolean(value);
+        }
+
+        public function writeByte(value: int): void {
+            this.writable.writeByte(value);
+        }
+
+        public function writeBytes(bytes: ByteArray, offset: uint = 0, length: uint = 0): void {
+            this.writable.writeBytes(bytes, offset, length);
+        }
+
+        public function writeDouble(value: Number): void {
+            this.writable.writeDouble(value);
+        }
+
+        public function writeFloat(value: Number): void {
+            this.writable.writeFloat(value);
+        }
+
+        public function writeInt(value: int): void {
+            this.writable.writeInt(value);
+        }
+
+        public function writeMultiByte(value: String, charSet: String): void {
+            this.writable.writeMultiByte(value, charSet);
+        }
+
+        public function writeObject(object: *): void { // NOPMD WronglyNamedVariable
+            this.writable.writeObject(object);
+        }
+
+        public function writeShort(value: int): void {
+            this.writable.writeShort(value);
+        }
+
+        public function writeUnsignedInt(value: uint): void {
+            this.writable.writeUnsignedInt(value);
+        }
+
+        public function writeUTF(value: String): void {
+            this.writable.writeUTF(value);
+        }
+
+        public function writeUTFBytes(value: String): void {
+            this.writable.writeUTFBytes(value);
+        }
+    }
+}
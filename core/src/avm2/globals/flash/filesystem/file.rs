@@ -0,0 +1,119 @@
+//! `flash.filesystem.File` native methods
+//!
+//! These give access to the file sandbox of the storage backend,
+//! with paths relative to its root.
+
+use crate::avm2::activation::Activation;
+use crate::avm2::array::ArrayStorage;
+use crate::avm2::bytearray::ByteArrayStorage;
+use crate::avm2::error::Error;
+use crate::avm2::globals::flash::desktop::native_application::ensure_air_compatibility;
+use crate::avm2::object::{ArrayObject, ByteArrayObject, Object, TObject};
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::value::Value;
+use crate::string::AvmString;
+
+/// Implements `File.checkSupported`
+pub fn check_supported<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    ensure_air_compatibility(activation, "File")?;
+    Ok(Value::Undefined)
+}
+
+/// Implements `File.readFile`, returning `null` if the file can't be read.
+pub fn read_file<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let path = args.get_string(activation, 0)?.to_utf8_lossy();
+    match activation.context.storage.read_file(&path) {
+        Some(data) => {
+            let storage = ByteArrayStorage::from_vec(data);
+            Ok(ByteArrayObject::from_storage(activation, storage)?.into())
+        }
+        None => Ok(Value::Null),
+    }
+}
+
+/// Implements `File.writeFile`
+pub fn write_file<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let path = args.get_string(activation, 0)?.to_utf8_lossy();
+    let data = args.get_object(activation, 1, "data")?;
+    let Some(bytearray) = data.as_bytearray() else {
+        return Ok(false.into());
+    };
+    let data = bytearray.bytes().to_vec();
+    drop(bytearray);
+    Ok(activation.context.storage.write_file(&path, &data).into())
+}
+
+/// Implements `File.getFileInfo`, returning an object with `isDirectory` and `size`
+/// properties, or `null` if nothing exists at the given path.
+pub fn get_file_info<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let path = args.get_string(activation, 0)?.to_utf8_lossy();
+    let Some(info) = activation.context.storage.file_info(&path) else {
+        return Ok(Value::Null);
+    };
+
+    let object_class = activation.avm2().classes().object;
+    let mut object = object_class.construct(activation, &[])?;
+    object.set_public_property("isDirectory", info.is_directory.into(), activation)?;
+    object.set_public_property("size", (info.size as f64).into(), activation)?;
+    Ok(object.into())
+}
+
+/// Implements `File.listDirectory`, returning the names of the entries of a directory,
+/// or `null` if it can't be listed.
+pub fn list_directory<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let path = args.get_string(activation, 0)?.to_utf8_lossy();
+    let Some(entries) = activation.context.storage.list_directory(&path) else {
+        return Ok(Value::Null);
+    };
+
+    let entries: Vec<Value<'gc>> = entries
+        .iter()
+        .map(|name| AvmString::new_utf8(activation.context.gc_context, name).into())
+        .collect();
+    Ok(ArrayObject::from_storage(activation, ArrayStorage::from_args(&entries))?.into())
+}
+
+/// Implements `File.createDirectoryAt`
+pub fn create_directory_at<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let path = args.get_string(activation, 0)?.to_utf8_lossy();
+    Ok(activation.context.storage.create_directory(&path).into())
+}
+
+/// Implements `File.deleteAt`
+pub fn delete_at<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let path = args.get_string(activation, 0)?.to_utf8_lossy();
+    let recursive = args.get_bool(1);
+    Ok(activation
+        .context
+        .storage
+        .delete_file(&path, recursive)
+        .into())
+}
@@ -1,5 +1,6 @@
 //! `flash.media` namespace
 
+pub mod camera;
 pub mod sound;
 pub mod sound_channel;
 pub mod sound_mixer;
@@ -17,10 +17,7 @@ package flash.media {
             __ruffle__.stub_method("flash.media.Camera", "drawToBitmapData");
         }
 
-        public static function getCamera(name: String = null):Camera {
-            __ruffle__.stub_method("flash.media.Camera", "getCamera");
-            return null;
-        }
+        public static native function getCamera(name: String = null):Camera;
 
         public function setKeyFrameInterval(keyFrameInterval:int) {
             __ruffle__.stub_method("flash.media.Camera", "setKeyFrameInterval");
@@ -30,9 +27,7 @@ package flash.media {
             __ruffle__.stub_method("flash.media.Camera", "setLoopback");
         }
 
-        public function setMode(width:int, height:int, fps:Number, favorArea:Boolean = true) {
-            __ruffle__.stub_method("flash.media.Camera", "setMode");
-        }
+        public native function setMode(width:int, height:int, fps:Number, favorArea:Boolean = true):void;
 
         public function setMotionLevel(motionLevel:int, timeout:int = 2000) {
             __ruffle__.stub_method("flash.media.Camera", "setMotionLevel");
@@ -52,30 +47,15 @@ package flash.media {
             return 0;
         }
 
-        public function get currentFPS(): Number {
-            __ruffle__.stub_getter("flash.media.Camera", "currentFPS");
-            return 0;
-        }
+        public native function get currentFPS(): Number;
 
-        public function get fps(): Number {
-            __ruffle__.stub_getter("flash.media.Camera", "fps");
-            return 0;
-        }
+        public native function get fps(): Number;
 
-        public function get height(): int {
-            __ruffle__.stub_getter("flash.media.Camera", "height");
-            return 0;
-        }
+        public native function get height(): int;
 
-        public function get index(): int {
-            __ruffle__.stub_getter("flash.media.Camera", "index");
-            return 0;
-        }
+        public native function get index(): int;
 
-        public static function get isSupported(): Boolean {
-            __ruffle__.stub_getter("flash.media.Camera", "isSupported");
-            return false;
-        }
+        public static native function get isSupported(): Boolean;
 
         public function get keyFrameInterval(): int {
             __ruffle__.stub_getter("flash.media.Camera", "keyFrameInterval");
@@ -98,29 +78,20 @@ package flash.media {
         }
 
         public function get muted(): Boolean {
-            __ruffle__.stub_getter("flash.media.Camera", "muted");
-            return true;
+            // Movies are always allowed to use the camera.
+            return false;
         }
 
-        public function get name(): String {
-            __ruffle__.stub_getter("flash.media.Camera", "name");
-            return "";
-        }
+        public native function get name(): String;
 
-        public static function get names(): Array {
-            __ruffle__.stub_getter("flash.media.Camera", "names");
-            return [];
-        }
+        public static native function get names(): Array;
         
         public function get quality(): int {
             __ruffle__.stub_getter("flash.media.Camera", "quality");
             return 0;
         }
         
-        public function get width(): int {
-            __ruffle__.stub_getter("flash.media.Camera", "width");
-            return 0;
-        }
+        public native function get width(): int;
     }
 
 }
\ No newline at end of file
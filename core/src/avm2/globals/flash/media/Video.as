@@ -40,5 +40,7 @@ package flash.media
         }
 
         public native function attachNetStream(netStream: NetStream);
+
+        public native function attachCamera(camera: Camera);
     }
 }
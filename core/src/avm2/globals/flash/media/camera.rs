@@ -0,0 +1,143 @@
+//! `flash.media.Camera` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::array::ArrayStorage;
+use crate::avm2::object::{ArrayObject, CameraObject, Object, TObject};
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::backend::video_capture::CaptureMode;
+use crate::streams::StreamManager;
+use crate::string::AvmString;
+
+pub fn get_camera<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(class) = this.and_then(|this| this.as_class_object()) else {
+        return Ok(Value::Null);
+    };
+
+    // Cameras are named by their index, as a string.
+    let index = match args.try_get_string(activation, 0)? {
+        Some(name) => match name.to_string().parse() {
+            Ok(index) => Some(index),
+            Err(_) => return Ok(Value::Null),
+        },
+        None => None,
+    };
+
+    match StreamManager::camera(&mut activation.context, index) {
+        Some(camera) => Ok(CameraObject::from_camera(activation, camera, class)?.into()),
+        None => Ok(Value::Null),
+    }
+}
+
+pub fn get_names<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let names = activation.context.video_capture.devices();
+    let gc_context = activation.context.gc_context;
+    let storage = ArrayStorage::from_iter(
+        names
+            .into_iter()
+            .map(|name| AvmString::new_utf8(gc_context, name)),
+    );
+    Ok(ArrayObject::from_storage(activation, storage)?.into())
+}
+
+pub fn get_is_supported<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok((!activation.context.video_capture.devices().is_empty()).into())
+}
+
+pub fn get_name<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(camera) = this.and_then(|this| this.as_camera()) {
+        return Ok(AvmString::new_utf8(activation.context.gc_context, camera.name()).into());
+    }
+    Ok(Value::Undefined)
+}
+
+pub fn get_index<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(camera) = this.and_then(|this| this.as_camera()) {
+        return Ok((camera.index() as i32).into());
+    }
+    Ok(Value::Undefined)
+}
+
+pub fn get_width<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(camera) = this.and_then(|this| this.as_camera()) {
+        return Ok(camera.mode().width.into());
+    }
+    Ok(Value::Undefined)
+}
+
+pub fn get_height<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(camera) = this.and_then(|this| this.as_camera()) {
+        return Ok(camera.mode().height.into());
+    }
+    Ok(Value::Undefined)
+}
+
+pub fn get_fps<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(camera) = this.and_then(|this| this.as_camera()) {
+        return Ok(camera.mode().fps.into());
+    }
+    Ok(Value::Undefined)
+}
+
+pub fn get_current_fps<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(camera) = this.and_then(|this| this.as_camera()) {
+        return Ok(camera.current_fps().into());
+    }
+    Ok(Value::Undefined)
+}
+
+pub fn set_mode<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(camera) = this.and_then(|this| this.as_camera()) {
+        let width = args.get_i32(activation, 0)?;
+        let height = args.get_i32(activation, 1)?;
+        let fps = args.get_f64(activation, 2)?;
+        let mode = CaptureMode {
+            width: width.max(1) as u32,
+            height: height.max(1) as u32,
+            fps: if fps > 0.0 { fps } else { camera.mode().fps },
+        };
+        camera.set_mode(&mut activation.context, mode);
+    }
+    Ok(Value::Undefined)
+}
@@ -257,6 +257,7 @@ pub fn load<'gc>(
             this,
             // FIXME: Set options from the `URLRequest`.
             Request::get(url.to_string()),
+            activation.caller_movie_or_root(),
         );
         activation.context.navigator.spawn_future(future);
     }
@@ -28,3 +28,24 @@ pub fn attach_net_stream<'gc>(
 
     Ok(Value::Undefined)
 }
+
+pub fn attach_camera<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(video) = this
+        .and_then(|o| o.as_display_object())
+        .and_then(|dobj| dobj.as_video())
+    {
+        // Attaching `null` detaches the camera.
+        let camera = args
+            .get(0)
+            .cloned()
+            .and_then(|v| v.as_object())
+            .and_then(|o| o.as_camera());
+        video.attach_camera(&mut activation.context, camera);
+    }
+
+    Ok(Value::Undefined)
+}
@@ -1,25 +1,286 @@
 package flash.net {
+    import flash.events.Event;
     import flash.events.EventDispatcher;
+    import flash.events.IOErrorEvent;
+    import flash.events.NetStatusEvent;
+    import flash.events.SecurityErrorEvent;
     import flash.errors.IOError;
+    import flash.utils.ByteArray;
+    import flash.utils.setTimeout;
     import __ruffle__.stub_method;
 
+    // The `ruffle` namespace is declared in SharedObject.as.
     public class NetConnection extends EventDispatcher {
 
         public static var defaultObjectEncoding:uint = 3;
 
         public var objectEncoding:uint = NetConnection.defaultObjectEncoding; // NOPMD WronglyNamedVariable
 
+        public var client:Object;
+
+        private var _uri:String = null;
+        private var _connected:Boolean = false;
+
+        // Headers sent with every remoting packet, as `[name, mustUnderstand, value]`.
+        private var _headers:Array = [];
+
+        // Calls made during the current frame, batched into a single packet.
+        private var _pendingCalls:Array = [];
+        private var _nextResponseId:uint = 1;
+
+        public function NetConnection() {
+            this.client = this;
+        }
+
+        public function get uri():String {
+            return this._uri;
+        }
+
+        public function get connected():Boolean {
+            return this._connected;
+        }
+
+        public function get usingTLS():Boolean {
+            return this._uri != null && this._uri.toLowerCase().indexOf("https:") == 0;
+        }
+
+        public function get protocol():String {
+            if (this._uri == null) {
+                return null;
+            }
+            var colon:int = this._uri.indexOf(":");
+            return colon < 0 ? null : this._uri.substr(0, colon).toLowerCase();
+        }
 
         public function connect(command:String, ... arguments):void {
-            stub_method("flash.net.NetConnection", "connect");
+            this.close();
+
+            if (command == null || command == "null") {
+                // Local connection, used for progressive video playback.
+                this._uri = "null";
+                this._connected = true;
+                this.dispatchStatus("NetConnection.Connect.Success", "status");
+                return;
+            }
+
+            this._uri = command;
+            var scheme:String = this.protocol;
+            if (scheme == "http" || scheme == "https") {
+                // Flash Remoting: the gateway is only contacted when `call` is used,
+                // and the connection never reports itself as connected.
+                return;
+            }
+
+            stub_method("flash.net.NetConnection", "connect", "with non-HTTP protocols");
+            var self:NetConnection = this;
+            setTimeout(function():void {
+                self.dispatchStatus("NetConnection.Connect.Failed", "error");
+            }, 0);
+        }
+
+        public function addHeader(operation:String, mustUnderstand:Boolean = false, param:Object = null):void {
+            for (var i:int = 0; i < this._headers.length; i++) {
+                if (this._headers[i][0] == operation) {
+                    this._headers.splice(i, 1);
+                    break;
+                }
+            }
+            if (param != null) {
+                this._headers.push([operation, mustUnderstand, param]);
+            }
         }
 
         public function call(command:String, responder:Responder, ... arguments):void {
-            stub_method("flash.net.NetConnection", "call");
+            if (this._uri == null) {
+                throw new IOError("Error #2126: NetConnection object must be connected.", 2126);
+            }
+
+            var scheme:String = this.protocol;
+            if (scheme != "http" && scheme != "https") {
+                stub_method("flash.net.NetConnection", "call", "with non-HTTP protocols");
+                return;
+            }
+
+            var responseId:uint = this._nextResponseId++;
+            this._pendingCalls.push({
+                command: command,
+                responder: responder,
+                args: arguments,
+                responseId: responseId
+            });
+
+            // Flash Player sends every call made during a frame as one packet.
+            if (this._pendingCalls.length == 1) {
+                var self:NetConnection = this;
+                setTimeout(function():void {
+                    self.flushCalls();
+                }, 0);
+            }
         }
-        
+
         public function close():void {
-            stub_method("flash.net.NetConnection", "close");
+            this._uri = null;
+            this._pendingCalls = [];
+            if (this._connected) {
+                this._connected = false;
+                this.dispatchStatus("NetConnection.Connect.Closed", "status");
+            }
+        }
+
+        private function flushCalls():void {
+            var calls:Array = this._pendingCalls;
+            this._pendingCalls = [];
+            if (calls.length == 0 || this._uri == null) {
+                return;
+            }
+
+            var request:URLRequest = new URLRequest(this._uri);
+            request.method = URLRequestMethod.POST;
+            request.contentType = "application/x-amf";
+            request.data = this.writePacket(calls);
+
+            var loader:URLLoader = new URLLoader();
+            loader.dataFormat = URLLoaderDataFormat.BINARY;
+
+            var self:NetConnection = this;
+            loader.addEventListener(Event.COMPLETE, function(e:Event):void {
+                self.readPacket(loader.data as ByteArray, calls);
+            });
+            loader.addEventListener(IOErrorEvent.IO_ERROR, function(e:IOErrorEvent):void {
+                self.dispatchStatus("NetConnection.Call.Failed", "error", e.text);
+            });
+            loader.addEventListener(SecurityErrorEvent.SECURITY_ERROR, function(e:SecurityErrorEvent):void {
+                self.dispatchStatus("NetConnection.Call.Prohibited", "error", e.text);
+            });
+            loader.load(request);
+        }
+
+        private function writePacket(calls:Array):ByteArray {
+            var useAmf3:Boolean = this.objectEncoding == ObjectEncoding.AMF3;
+            var packet:ByteArray = new ByteArray();
+            packet.objectEncoding = ObjectEncoding.AMF0;
+
+            packet.writeShort(useAmf3 ? 3 : 0);
+
+            packet.writeShort(this._headers.length);
+            for each (var header:Array in this._headers) {
+                packet.writeUTF(header[0]);
+                packet.writeBoolean(header[1]);
+                // Unknown length.
+                packet.writeInt(-1);
+                this.writeValue(packet, header[2], useAmf3);
+            }
+
+            packet.writeShort(calls.length);
+            for each (var pending:Object in calls) {
+                packet.writeUTF(pending.command);
+                packet.writeUTF("/" + pending.responseId);
+                packet.writeInt(-1);
+
+                // Arguments are always sent as an AMF0 strict array.
+                packet.writeByte(0x0A);
+                packet.writeUnsignedInt(pending.args.length);
+                for each (var arg:* in pending.args) {
+                    this.writeValue(packet, arg, useAmf3);
+                }
+            }
+
+            return packet;
+        }
+
+        private function writeValue(packet:ByteArray, value:*, useAmf3:Boolean):void {
+            if (useAmf3) {
+                // AMF0 "switch to AMF3" marker.
+                packet.writeByte(0x11);
+                packet.objectEncoding = ObjectEncoding.AMF3;
+                packet.writeObject(value);
+                packet.objectEncoding = ObjectEncoding.AMF0;
+            } else {
+                packet.writeObject(value);
+            }
+        }
+
+        private function readPacket(packet:ByteArray, calls:Array):void {
+            var messages:Array = [];
+            try {
+                packet.objectEncoding = ObjectEncoding.AMF0;
+                packet.position = 0;
+
+                var version:uint = packet.readUnsignedShort();
+                if (version != 0 && version != 3) {
+                    throw new Error("Unsupported AMF packet version " + version);
+                }
+
+                var headerCount:uint = packet.readUnsignedShort();
+                for (var i:uint = 0; i < headerCount; i++) {
+                    var name:String = packet.readUTF();
+                    packet.readBoolean();
+                    packet.readInt();
+                    var value:* = packet.readObject();
+                    this.handleResponseHeader(name, value);
+                }
+
+                var messageCount:uint = packet.readUnsignedShort();
+                for (var j:uint = 0; j < messageCount; j++) {
+                    var target:String = packet.readUTF();
+                    packet.readUTF();
+                    packet.readInt();
+                    messages.push([target, packet.readObject()]);
+                }
+            } catch (e:Error) {
+                this.dispatchStatus("NetConnection.Call.BadVersion", "error", e.message);
+                return;
+            }
+
+            for each (var message:Array in messages) {
+                // Targets look like "/<responseId>/onResult" or "/<responseId>/onStatus".
+                var messageTarget:String = message[0];
+                var slash:int = messageTarget.lastIndexOf("/");
+                if (slash < 0) {
+                    continue;
+                }
+                var messageId:String = messageTarget.substring(1, slash);
+                var method:String = messageTarget.substring(slash + 1);
+
+                for each (var pending:Object in calls) {
+                    if (String(pending.responseId) != messageId) {
+                        continue;
+                    }
+                    var responder:Responder = pending.responder;
+                    if (method == "onResult") {
+                        if (responder != null && responder.ruffle::_result != null) {
+                            responder.ruffle::_result(message[1]);
+                        }
+                    } else if (method == "onStatus") {
+                        if (responder != null && responder.ruffle::_status != null) {
+                            responder.ruffle::_status(message[1]);
+                        } else {
+                            this.dispatchEvent(new NetStatusEvent(NetStatusEvent.NET_STATUS, false, false, message[1]));
+                        }
+                    }
+                    break;
+                }
+            }
+        }
+
+        private function handleResponseHeader(name:String, value:*):void {
+            if (name == "AppendToGatewayUrl") {
+                this._uri += String(value);
+            } else if (name == "ReplaceGatewayUrl") {
+                this._uri = String(value);
+            } else if (name == "RequestPersistentHeader" && value != null) {
+                this.addHeader(value.name, value.mustUnderstand, value.data);
+            } else if (this.client != null && this.client.hasOwnProperty(name) && this.client[name] is Function) {
+                this.client[name](value);
+            }
+        }
+
+        private function dispatchStatus(code:String, level:String, description:String = null):void {
+            var info:Object = { code: code, level: level };
+            if (description != null) {
+                info.description = description;
+            }
+            this.dispatchEvent(new NetStatusEvent(NetStatusEvent.NET_STATUS, false, false, info));
         }
     }
 }
@@ -12,10 +12,7 @@ package flash.net
          this.data = {};
       }
 
-      // NOTE: We currently always use AMF3 serialization.
-      // If you implement the `defaultObjectEncoding` or `objectEncoding`,
-      // you will need to adjust the serialization and deserialization code
-      // to work with AMF0.
+      public static var defaultObjectEncoding:uint = 3;
 
       native public static function getLocal(name:String, localPath:String = null, secure:Boolean = false): SharedObject;
 
@@ -29,6 +26,8 @@ package flash.net
       // note: this is supposed to be a read-only property
       public var size: uint;
 
+      public var objectEncoding:uint = SharedObject.defaultObjectEncoding; // NOPMD WronglyNamedVariable
+
       ruffle var _ruffleName: String;
    }
 }
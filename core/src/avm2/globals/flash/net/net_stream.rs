@@ -39,7 +39,8 @@ pub fn play<'gc>(
             .map(|v| v.coerce_to_string(activation))
             .transpose()?;
 
-        ns.play(&mut activation.context, name);
+        let movie = activation.caller_movie_or_root();
+        ns.play(&mut activation.context, name, movie);
     }
 
     Ok(Value::Undefined)
@@ -194,15 +194,23 @@ pub fn flush<'gc>(
             .coerce_to_string(activation)?;
         let name = name.to_utf8_lossy();
 
+        let amf_version = match this
+            .get_public_property("objectEncoding", activation)?
+            .coerce_to_u32(activation)?
+        {
+            0 => AMFVersion::AMF0,
+            _ => AMFVersion::AMF3,
+        };
+
         let mut elements = Vec::new();
-        crate::avm2::amf::recursive_serialize(activation, data, &mut elements, AMFVersion::AMF3)?;
+        crate::avm2::amf::recursive_serialize(activation, data, &mut elements, amf_version)?;
         let mut lso = Lso::new(
             elements,
             name.split('/')
                 .last()
                 .map(|e| e.to_string())
                 .unwrap_or_else(|| "<unknown>".to_string()),
-            AMFVersion::AMF3,
+            amf_version,
         );
 
         let bytes = flash_lso::write::write_to_bytes(&mut lso).unwrap_or_default();
@@ -51,6 +51,7 @@ fn spawn_fetch<'gc>(
         activation.context.player.clone(),
         loader_object,
         request,
+        activation.caller_movie_or_root(),
         data_format,
     );
     activation.context.navigator.spawn_future(future);
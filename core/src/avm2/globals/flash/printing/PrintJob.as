@@ -0,0 +1,74 @@
+package flash.printing {
+    import flash.display.Sprite;
+    import flash.events.EventDispatcher;
+    import flash.geom.Rectangle;
+
+    public class PrintJob extends EventDispatcher {
+        private var _started:Boolean = false;
+        private var _paperWidth:int = 0;
+        private var _paperHeight:int = 0;
+        private var _pageWidth:int = 0;
+        private var _pageHeight:int = 0;
+        private var _orientation:String = PrintJobOrientation.PORTRAIT;
+
+        public function PrintJob() {
+        }
+
+        public static function get isSupported():Boolean {
+            return true;
+        }
+
+        public function get paperWidth():int {
+            return this._paperWidth;
+        }
+
+        public function get paperHeight():int {
+            return this._paperHeight;
+        }
+
+        public function get pageWidth():int {
+            return this._pageWidth;
+        }
+
+        public function get pageHeight():int {
+            return this._pageHeight;
+        }
+
+        public function get orientation():String {
+            return this._orientation;
+        }
+
+        public function start():Boolean {
+            var format:Array = this.startJob();
+            if (format == null) {
+                return false;
+            }
+            this._paperWidth = format[0];
+            this._paperHeight = format[1];
+            this._pageWidth = format[2];
+            this._pageHeight = format[3];
+            this._orientation = format[4];
+            this._started = true;
+            return true;
+        }
+
+        public function addPage(sprite:Sprite, printArea:Rectangle = null, options:PrintJobOptions = null, frameNum:int = 0):void {
+            if (!this._started || !this.addPageToJob(sprite, printArea, frameNum)) {
+                throw new Error("Error #2057: The page could not be added to the print job.", 2057);
+            }
+        }
+
+        public function send():void {
+            if (this._started) {
+                this._started = false;
+                this.sendJob();
+            }
+        }
+
+        // Returns the paper width, paper height, page width, page height and orientation,
+        // or null if the user cancelled.
+        private native function startJob():Array;
+        private native function addPageToJob(sprite:Sprite, printArea:Rectangle, frameNum:int):Boolean;
+        private native function sendJob():void;
+    }
+}
@@ -0,0 +1,10 @@
+package flash.printing {
+    public class PrintJobOptions {
+        // Pages are always printed as bitmaps by Ruffle.
+        public var printAsBitmap:Boolean;
+
+        public function PrintJobOptions(printAsBitmap:Boolean = false) {
+            this.printAsBitmap = printAsBitmap;
+        }
+    }
+}
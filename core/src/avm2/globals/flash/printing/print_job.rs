@@ -0,0 +1,87 @@
+//! `flash.printing.PrintJob` native methods
+
+use crate::avm2::activation::Activation;
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::avm2::{ArrayObject, ArrayStorage};
+use crate::avm2_stub_method;
+use crate::printing;
+use swf::{Rectangle, Twips};
+
+/// Implements `PrintJob.startJob`
+pub fn start_job<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(format) = printing::start_job(&mut activation.context) else {
+        return Ok(Value::Null);
+    };
+    let storage = ArrayStorage::from_args(&[
+        format.paper_width.into(),
+        format.paper_height.into(),
+        format.page_width.into(),
+        format.page_height.into(),
+        format.orientation.as_str().into(),
+    ]);
+    Ok(ArrayObject::from_storage(activation, storage)?.into())
+}
+
+/// Implements `PrintJob.addPageToJob`
+pub fn add_page_to_job<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(sprite) = args
+        .try_get_object(activation, 0)
+        .and_then(|sprite| sprite.as_display_object())
+    else {
+        return Ok(false.into());
+    };
+
+    // The area to print is in the coordinates of the sprite.
+    let area = match args.try_get_object(activation, 1) {
+        Some(rectangle) => {
+            let mut get = |name: &'static str| -> Result<f64, Error<'gc>> {
+                rectangle
+                    .get_public_property(name, activation)?
+                    .coerce_to_number(activation)
+            };
+            let (x, y) = (get("x")?, get("y")?);
+            let (width, height) = (get("width")?, get("height")?);
+            Some(Rectangle {
+                x_min: Twips::from_pixels(x),
+                x_max: Twips::from_pixels(x + width),
+                y_min: Twips::from_pixels(y),
+                y_max: Twips::from_pixels(y + height),
+            })
+        }
+        None => None,
+    };
+
+    // The current frame of the sprite is always printed.
+    if args.get_i32(activation, 2)? != 0 {
+        avm2_stub_method!(
+            activation,
+            "flash.printing.PrintJob",
+            "addPage",
+            "with frameNum"
+        );
+    }
+
+    let added = printing::add_page(&mut activation.context, sprite, area);
+    Ok(added.into())
+}
+
+/// Implements `PrintJob.sendJob`
+pub fn send_job<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    printing::send_job(&mut activation.context);
+    Ok(Value::Undefined)
+}
@@ -1,11 +1,17 @@
 //! `flash.system.Security` native methods
 
 use crate::avm2::activation::Activation;
+use crate::avm2::error::security_error;
 use crate::avm2::object::Object;
+use crate::avm2::parameters::ParametersExt;
 use crate::avm2::value::Value;
 use crate::avm2::Error;
 use crate::avm2_stub_method;
+use crate::backend::ui::SettingsPanel;
+use crate::security::can_script;
 use crate::string::AvmString;
+use crate::tag_utils::SwfMovie;
+use std::sync::Arc;
 
 pub fn get_sandbox_type<'gc>(
     activation: &mut Activation<'_, 'gc>,
@@ -16,21 +22,70 @@ pub fn get_sandbox_type<'gc>(
     return Ok(AvmString::new_utf8(activation.context.gc_context, sandbox_type).into());
 }
 
+/// Throws the `SecurityError` Flash Player throws when the running script uses
+/// `api` to access the content of a movie that didn't allow its domain.
+pub fn check_cross_scripting<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    target: &Arc<SwfMovie>,
+    api: &str,
+) -> Result<(), Error<'gc>> {
+    if !activation.context.system.checks_cross_scripting() {
+        return Ok(());
+    }
+    let Some(caller) = activation.caller_movie() else {
+        return Ok(());
+    };
+
+    let allowed = activation
+        .context
+        .library
+        .library_for_movie(target.clone())
+        .map(|library| library.allowed_domains());
+    if can_script(&caller, target, allowed) {
+        return Ok(());
+    }
+
+    let message = format!(
+        "Error #2121: Security sandbox violation: {api}: {} cannot access {}. This may be worked around by calling Security.allowDomain.",
+        caller.url(),
+        target.url()
+    );
+    Err(Error::AvmError(security_error(activation, &message, 2121)?))
+}
+
+fn allow_domains<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    args: &[Value<'gc>],
+    insecure: bool,
+) -> Result<(), Error<'gc>> {
+    let movie = activation.caller_movie_or_root();
+    for domain in args {
+        let domain = domain.coerce_to_string(activation)?.to_utf8_lossy();
+        activation
+            .context
+            .library
+            .library_for_movie_mut(movie.clone())
+            .allowed_domains_mut()
+            .allow(&domain, insecure);
+    }
+    Ok(())
+}
+
 pub fn allow_domain<'gc>(
     activation: &mut Activation<'_, 'gc>,
     _this: Option<Object<'gc>>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_method!(activation, "flash.system.Security", "allowDomain");
+    allow_domains(activation, args, false)?;
     Ok(Value::Undefined)
 }
 
 pub fn allow_insecure_domain<'gc>(
     activation: &mut Activation<'_, 'gc>,
     _this: Option<Object<'gc>>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_method!(activation, "flash.system.Security", "allowInsecureDomain");
+    allow_domains(activation, args, true)?;
     Ok(Value::Undefined)
 }
 
@@ -46,8 +101,10 @@ pub fn load_policy_file<'gc>(
 pub fn show_settings<'gc>(
     activation: &mut Activation<'_, 'gc>,
     _this: Option<Object<'gc>>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_method!(activation, "flash.system.Security", "showSettings");
+    let name = args.get_string(activation, 0)?.to_utf8_lossy();
+    let panel = SettingsPanel::from_name(&name).unwrap_or(SettingsPanel::Privacy);
+    activation.context.ui.display_settings_panel(panel);
     Ok(Value::Undefined)
 }
@@ -1,37 +1,19 @@
 package flash.text {
     
-    import __ruffle__.stub_constructor;
-    import __ruffle__.stub_method;
-    import __ruffle__.stub_getter;
-    
+    [Ruffle(InstanceAllocator)]
     public dynamic class StyleSheet {
         public function StyleSheet() {}
         
-        public function get styleNames():Array {
-            stub_getter("flash.text.StyleSheet", "styleNames");
-            return [];
-        }
+        public native function get styleNames():Array;
         
-        public function clear():void {
-            stub_method("flash.text.StyleSheet", "clear");
-        }
+        public native function clear():void;
         
-        public function getStyle(styleName:String):Object {
-            stub_method("flash.text.StyleSheet", "getStyle");
-            return null;
-        }
+        public native function getStyle(styleName:String):Object;
         
-        public function parseCSS(CSSText:String):void {
-            stub_method("flash.text.StyleSheet", "parseCSS");
-        }
+        public native function parseCSS(CSSText:String):void;
         
-        public function setStyle(styleName:String, styleObject:Object):void {
-            stub_method("flash.text.StyleSheet", "setStyle");
-        }
+        public native function setStyle(styleName:String, styleObject:Object):void;
         
-        public function transform(formatObject:Object):TextFormat {
-            stub_method("flash.text.StyleSheet", "transform");
-            return null;
-        }
+        public native function transform(formatObject:Object):TextFormat;
     }
 }
@@ -3,7 +3,6 @@ package flash.text {
     import __ruffle__.stub_setter;
     
     public class TextField extends InteractiveObject {
-        internal var _styleSheet:StyleSheet;
         internal var _useRichTextClipboard:Boolean;
     
         public function TextField() {
@@ -74,13 +73,8 @@ package flash.text {
         public native function get selectable():Boolean;
         public native function set selectable(value:Boolean):void;
         
-        public function get styleSheet():StyleSheet {
-            return this._styleSheet;
-        }
-        public function set styleSheet(value:StyleSheet):void {
-            this._styleSheet = value;
-            stub_setter("flash.text.TextField", "styleSheet");
-        }
+        public native function get styleSheet():StyleSheet;
+        public native function set styleSheet(value:StyleSheet):void;
         
         public native function get text():String;
         public native function set text(value:String):void;
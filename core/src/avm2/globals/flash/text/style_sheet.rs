@@ -0,0 +1,135 @@
+//! `flash.text.StyleSheet` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::array::ArrayStorage;
+use crate::avm2::object::{ArrayObject, Object, TObject, TextFormatObject};
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::html::{style_to_text_format, Style};
+use crate::string::AvmString;
+
+pub use crate::avm2::object::stylesheet_allocator as style_sheet_allocator;
+
+/// Reads the enumerable properties of a style object, as strings.
+fn style_properties<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    object: Object<'gc>,
+) -> Result<Vec<(String, String)>, Error<'gc>> {
+    let mut properties = Vec::new();
+    let mut last_index = 0;
+    while let Some(index) = object.get_next_enumerant(last_index, activation)? {
+        let name = object
+            .get_enumerant_name(index, activation)?
+            .coerce_to_string(activation)?;
+        let value = object
+            .get_enumerant_value(index, activation)?
+            .coerce_to_string(activation)?;
+        properties.push((name.to_string(), value.to_string()));
+        last_index = index;
+    }
+    Ok(properties)
+}
+
+pub fn get_style_names<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(style_sheet) = this.and_then(|this| this.as_style_sheet()) {
+        let gc_context = activation.context.gc_context;
+        let storage = ArrayStorage::from_iter(
+            style_sheet
+                .style_names()
+                .into_iter()
+                .map(|name| AvmString::new_utf8(gc_context, name)),
+        );
+        return Ok(ArrayObject::from_storage(activation, storage)?.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn clear<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(style_sheet) = this.and_then(|this| this.as_style_sheet()) {
+        style_sheet.clear(activation.context.gc_context);
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_style<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(style_sheet) = this.and_then(|this| this.as_style_sheet()) {
+        let name = args.get_string(activation, 0)?;
+        let Some(style) = style_sheet.style(&name.to_string()) else {
+            return Ok(Value::Null);
+        };
+
+        // A copy of the style is returned, so that changing it doesn't change the style sheet.
+        let mut object = activation
+            .avm2()
+            .classes()
+            .object
+            .construct(activation, &[])?;
+        for (name, value) in style.properties {
+            let name = AvmString::new_utf8(activation.context.gc_context, name);
+            let value = AvmString::new_utf8(activation.context.gc_context, value);
+            object.set_public_property(name, value.into(), activation)?;
+        }
+        return Ok(object.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn set_style<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(style_sheet) = this.and_then(|this| this.as_style_sheet()) {
+        let name = args.get_string(activation, 0)?;
+        let style = match args.try_get_object(activation, 1) {
+            Some(object) => Some(Style::new(style_properties(activation, object)?)),
+            None => None,
+        };
+        style_sheet.set_style(activation.context.gc_context, &name.to_string(), style);
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn parse_css<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(style_sheet) = this.and_then(|this| this.as_style_sheet()) {
+        let css = args.get_string(activation, 0)?;
+        // Invalid CSS is ignored, as `parseCSS` doesn't report errors in AVM2.
+        style_sheet.parse_css(activation.context.gc_context, &css.to_string());
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn transform<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(style) = args.try_get_object(activation, 0) else {
+        return Ok(Value::Null);
+    };
+    let properties = style_properties(activation, style)?;
+    let text_format = style_to_text_format(&properties);
+    Ok(TextFormatObject::from_text_format(activation, text_format)?.into())
+}
@@ -8,6 +8,7 @@ use crate::avm2::Error;
 use crate::display_object::{AutoSizeMode, EditText, TDisplayObject, TextSelection};
 use crate::html::TextFormat;
 use crate::string::AvmString;
+use crate::vminterface::AvmObject;
 use crate::{avm2_stub_getter, avm2_stub_setter};
 use swf::Color;
 
@@ -452,6 +453,44 @@ pub fn set_selectable<'gc>(
     Ok(Value::Undefined)
 }
 
+pub fn get_style_sheet<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        if let Some(AvmObject::Avm2(style_sheet)) = this.style_sheet_object() {
+            return Ok(style_sheet.into());
+        }
+        return Ok(Value::Null);
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn set_style_sheet<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        let style_sheet = args.try_get_object(activation, 0).and_then(|object| {
+            object
+                .as_style_sheet()
+                .map(|style_sheet| (AvmObject::Avm2(object), style_sheet))
+        });
+        this.set_style_sheet(&mut activation.context, style_sheet);
+    }
+
+    Ok(Value::Undefined)
+}
+
 pub fn get_text<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Option<Object<'gc>>,
@@ -7,6 +7,7 @@ use crate::avm2::{Activation, Error, Object, Value};
 use crate::avm2::{ClassObject, QName};
 use crate::string::AvmString;
 use crate::string::WString;
+use crate::timer::Timers;
 use instant::Instant;
 use std::fmt::Write;
 
@@ -51,7 +52,8 @@ pub fn set_interval<'gc>(
         .coerce_to_number(activation)?;
     Ok(Value::Integer(activation.context.timers.add_timer(
         callback,
-        interval as i32,
+        interval,
+        Timers::MIN_INTERVAL,
         false,
     )))
 }
@@ -94,7 +96,8 @@ pub fn set_timeout<'gc>(
         .coerce_to_number(activation)?;
     Ok(Value::Integer(activation.context.timers.add_timer(
         callback,
-        interval as i32,
+        interval,
+        Timers::MIN_INTERVAL,
         true,
     )))
 }
@@ -1,5 +1,6 @@
 use crate::avm2::activation::Activation;
-use crate::avm2::bytearray::{Endian, ObjectEncoding};
+use crate::avm2::amf;
+use crate::avm2::bytearray::{Endian, EofError, ObjectEncoding};
 pub use crate::avm2::object::byte_array_allocator;
 use crate::avm2::object::{Object, TObject};
 use crate::avm2::value::Value;
@@ -8,9 +9,6 @@ use crate::character::Character;
 use crate::string::AvmString;
 use encoding_rs::Encoding;
 use encoding_rs::UTF_8;
-use flash_lso::amf0::read::AMF0Decoder;
-use flash_lso::amf3::read::AMF3Decoder;
-use flash_lso::types::{AMFVersion, Element};
 
 /// Implements `flash.utils.ByteArray`'s instance constructor.
 pub fn init<'gc>(
@@ -796,35 +794,22 @@ pub fn read_object<'gc>(
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(this) = this {
-        if let Some(bytearray) = this.as_bytearray() {
-            let bytes = bytearray
-                .read_at(bytearray.bytes_available(), bytearray.position())
-                .map_err(|e| e.to_avm(activation))?;
-            let (bytes_left, value) = match bytearray.object_encoding() {
-                ObjectEncoding::Amf0 => {
-                    let mut decoder = AMF0Decoder::default();
-                    let (extra, amf) = decoder
-                        .parse_single_element(bytes)
-                        .map_err(|_| "Error: Invalid object")?;
-                    (
-                        extra.len(),
-                        crate::avm2::amf::deserialize_value(activation, &amf)?,
-                    )
-                }
-                ObjectEncoding::Amf3 => {
-                    let mut decoder = AMF3Decoder::default();
-                    let (extra, amf) = decoder
-                        .parse_single_element(bytes)
-                        .map_err(|_| "Error: Invalid object")?;
-                    (
-                        extra.len(),
-                        crate::avm2::amf::deserialize_value(activation, &amf)?,
-                    )
-                }
-            };
+        // Decode the value before deserializing it, as that can run arbitrary code.
+        let decoded = this.as_bytearray().map(|bytearray| {
+            let bytes = &bytearray.bytes()[bytearray.position().min(bytearray.len())..];
+            amf::decode_value(bytes, bytearray.object_encoding().into())
+                .map(|(value, length)| (value, bytearray.position() + length))
+        });
 
-            bytearray.set_position(bytearray.len() - bytes_left);
-            return Ok(value);
+        match decoded {
+            Some(Some((value, new_position))) => {
+                if let Some(bytearray) = this.as_bytearray() {
+                    bytearray.set_position(new_position);
+                }
+                return amf::deserialize_value(activation, &value);
+            }
+            Some(None) => return Err(EofError.to_avm(activation)),
+            None => {}
         }
     }
 
@@ -837,27 +822,20 @@ pub fn write_object<'gc>(
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(this) = this {
-        if let Some(mut bytearray) = this.as_bytearray_mut(activation.context.gc_context) {
-            let obj = args.get(0).cloned().unwrap_or(Value::Undefined);
-            let amf_version = match bytearray.object_encoding() {
-                ObjectEncoding::Amf0 => AMFVersion::AMF0,
-                ObjectEncoding::Amf3 => AMFVersion::AMF3,
-            };
-            if let Some(amf) = crate::avm2::amf::serialize_value(activation, obj, amf_version) {
-                let element = Element::new("", amf);
-                let mut lso = flash_lso::types::Lso::new(vec![element], "", amf_version);
-                let bytes = flash_lso::write::write_to_bytes(&mut lso)
-                    .map_err(|_| "Failed to serialize object")?;
-                // This is kind of hacky: We need to strip out the header and any padding so that we only write
-                // the value. In the future, there should be a method to do this in the flash_lso crate.
-                let element_padding = match amf_version {
-                    AMFVersion::AMF0 => 8,
-                    AMFVersion::AMF3 => 7,
-                };
-                bytearray.write_bytes(
-                    &bytes[flash_lso::write::header_length(&lso.header) + element_padding
-                        ..bytes.len() - 1],
-                )?;
+        let Some(amf_version) = this
+            .as_bytearray()
+            .map(|bytearray| bytearray.object_encoding().into())
+        else {
+            return Ok(Value::Undefined);
+        };
+
+        // Serialize the value before borrowing the ByteArray, as that can run arbitrary code
+        // (and the value may even contain this ByteArray).
+        let obj = args.get(0).cloned().unwrap_or(Value::Undefined);
+        if let Some(amf) = amf::serialize_value(activation, obj, amf_version) {
+            let bytes = amf::encode_value(amf, amf_version).ok_or("Failed to serialize object")?;
+            if let Some(mut bytearray) = this.as_bytearray_mut(activation.context.gc_context) {
+                bytearray.write_bytes(&bytes)?;
             }
         }
     }
@@ -5,7 +5,7 @@ use crate::avm2::object::TObject;
 use crate::avm2::value::Value;
 use crate::avm2::Multiname;
 use crate::avm2::{Error, Object};
-use crate::timer::TimerCallback;
+use crate::timer::{TimerCallback, Timers};
 
 /// Implements `Timer.stop`
 pub fn stop<'gc>(
@@ -72,7 +72,8 @@ pub fn start<'gc>(
                 closure: on_update,
                 params: vec![],
             },
-            delay as _,
+            delay,
+            Timers::MIN_INTERVAL,
             false,
         );
         this.set_property(
@@ -31,6 +31,7 @@ include "flash/utils/IDataOutput.as"
 include "flash/utils/IExternalizable.as"
 include "flash/utils/ByteArray.as"
 include "flash/utils/Dictionary.as"
+include "flash/data/EncryptedLocalStore.as"
 include "flash/desktop/ClipboardFormats.as"
 include "flash/desktop/ClipboardTransferMode.as"
 
@@ -51,6 +52,7 @@ include "flash/display/LoaderInfo.as"
 include "flash/display/Stage.as"
 
 include "flash/display/ActionScriptVersion.as"
+include "flash/display/AVM1Movie.as"
 include "flash/display/BitmapDataChannel.as"
 include "flash/display/BitmapEncodingColorSpace.as"
 include "flash/display/BlendMode.as"
@@ -83,6 +85,7 @@ include "flash/display/LineScaleMode.as"
 include "flash/display/MorphShape.as"
 include "flash/display/NativeMenu.as"
 include "flash/display/NativeMenuItem.as"
+include "flash/display/NativeWindow.as"
 include "flash/display/PixelSnapping.as"
 include "flash/display/PNGEncoderOptions.as"
 include "flash/display/Scene.as"
@@ -147,6 +150,7 @@ include "flash/events/DRMLicenseRequestEvent.as"
 include "flash/events/DRMReturnVoucherCompleteEvent.as"
 include "flash/events/DRMReturnVoucherErrorEvent.as"
 include "flash/events/EventPhase.as"
+include "flash/events/FileListEvent.as"
 include "flash/events/FocusEvent.as"
 include "flash/events/FullScreenEvent.as"
 include "flash/events/GameInputEvent.as"
@@ -156,8 +160,11 @@ include "flash/events/IOErrorEvent.as"
 include "flash/events/KeyboardEvent.as"
 include "flash/events/NetDataEvent.as"
 include "flash/events/NetStatusEvent.as"
+include "flash/events/OutputProgressEvent.as"
 include "flash/events/ProgressEvent.as"
 include "flash/events/SampleDataEvent.as"
+include "flash/events/SQLErrorEvent.as"
+include "flash/events/SQLEvent.as"
 include "flash/events/SecurityErrorEvent.as"
 include "flash/events/ShaderEvent.as"
 include "flash/events/SoftKeyboardEvent.as"
@@ -181,6 +188,7 @@ include "flash/errors/IllegalOperationError.as"
 include "flash/errors/InvalidSWFError.as"
 include "flash/errors/MemoryError.as"
 include "flash/errors/ScriptTimeoutError.as"
+include "flash/errors/SQLError.as"
 include "flash/errors/StackOverflowError.as"
 
 include "flash/filters/BitmapFilter.as"
@@ -269,7 +277,20 @@ include "flash/net/URLRequestMethod.as"
 include "flash/net/URLVariables.as"
 include "flash/net/XMLSocket.as"
 
+include "flash/desktop/NativeApplication.as"
+
+include "flash/filesystem/File.as"
+include "flash/filesystem/FileMode.as"
+include "flash/filesystem/FileStream.as"
+
+include "flash/data/SQLConnection.as"
+include "flash/data/SQLMode.as"
+include "flash/data/SQLResult.as"
+include "flash/data/SQLStatement.as"
+
 include "flash/profiler/Telemetry.as"
+include "flash/printing/PrintJob.as"
+include "flash/printing/PrintJobOptions.as"
 include "flash/printing/PrintJobOrientation.as"
 
 include "flash/profiler.as"
@@ -17,11 +17,12 @@ pub fn trace<'gc>(
     _this: Option<Object<'gc>>,
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
+    let movie = activation.caller_movie_or_root();
     match args {
-        [] => activation.context.avm_trace(""),
+        [] => activation.context.movie_trace(&movie, ""),
         [arg] => {
             let msg = arg.coerce_to_string(activation)?;
-            activation.context.avm_trace(&msg.to_utf8_lossy());
+            activation.context.movie_trace(&movie, &msg.to_utf8_lossy());
         }
         args => {
             let strings = args
@@ -29,7 +30,7 @@ pub fn trace<'gc>(
                 .map(|a| a.coerce_to_string(activation))
                 .collect::<Result<Vec<_>, _>>()?;
             let msg = crate::string::join(&strings, &WStr::from_units(b" "));
-            activation.context.avm_trace(&msg.to_utf8_lossy());
+            activation.context.movie_trace(&movie, &msg.to_utf8_lossy());
         }
     }
 
@@ -66,7 +67,7 @@ pub fn stub_method<'gc>(
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     match args {
-        [class, method] => {
+        [class, method] | [class, method, Value::Null] => {
             let class = class.coerce_to_string(activation)?;
             let method = method.coerce_to_string(activation)?;
             activation
@@ -0,0 +1,101 @@
+//! Diagnostics for display objects that stay alive after being removed from
+//! the stage.
+//!
+//! Objects listening to broadcast events such as `enterFrame` are kept in the
+//! AVM's broadcast lists, so they keep running and can never be collected,
+//! even once nothing else references them. This is also the case in Flash
+//! Player, and a common reason for memory to keep growing.
+
+use crate::avm2::object::{ObjectPtr, TObject};
+use crate::avm2::{Activation, Multiname, Object};
+use crate::context::UpdateContext;
+use crate::string::AvmString;
+use fnv::{FnvHashMap, FnvHashSet};
+
+/// Remembers which display objects were removed from the stage.
+///
+/// Objects are only remembered by address, so that the detector doesn't keep
+/// them alive itself.
+#[derive(Default)]
+pub struct LeakDetector {
+    removed_from_stage: FnvHashSet<*const ObjectPtr>,
+}
+
+impl LeakDetector {
+    pub fn removed_from_stage(&mut self, object: Object<'_>) {
+        self.removed_from_stage.insert(object.as_ptr());
+    }
+
+    pub fn added_to_stage(&mut self, object: Object<'_>) {
+        self.removed_from_stage.remove(&object.as_ptr());
+    }
+}
+
+/// Instances of a class that were removed from the stage, but are still kept
+/// alive by their listeners of a broadcast event.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProbableLeak {
+    /// The qualified name of the class of the objects.
+    pub class_name: String,
+
+    /// The broadcast event that the objects still listen to.
+    pub event: String,
+
+    /// How many objects are affected.
+    pub count: usize,
+}
+
+/// Finds the display objects that were removed from the stage but still listen
+/// to broadcast events, grouped by class and event, most common first.
+pub fn probable_leaks<'gc>(context: &mut UpdateContext<'_, 'gc>) -> Vec<ProbableLeak> {
+    let Some(detector) = &context.avm2.leak_detector else {
+        return Vec::new();
+    };
+    let candidates: Vec<(AvmString<'gc>, Object<'gc>)> = context
+        .avm2
+        .broadcast_list
+        .iter()
+        .flat_map(|(event, objects)| objects.iter().map(move |object| (*event, *object)))
+        .filter(|(_, object)| detector.removed_from_stage.contains(&object.as_ptr()))
+        .collect();
+
+    let mut activation = Activation::from_nothing(context.reborrow());
+    let mut counts: FnvHashMap<(String, String), usize> = FnvHashMap::default();
+    for (event, object) in candidates {
+        let dispatch_list = object
+            .get_property(
+                &Multiname::new(activation.avm2().flash_events_internal, "_dispatchList"),
+                &mut activation,
+            )
+            .ok()
+            .and_then(|list| list.as_object());
+        let Some(dispatch_list) = dispatch_list else {
+            continue;
+        };
+        let is_listening = dispatch_list.as_dispatch().map_or(false, |list| {
+            list.has_event_listener(event, activation.context.gc_context)
+        });
+        if is_listening {
+            let class_name = object
+                .instance_of_class_name(activation.context.gc_context)
+                .to_string();
+            *counts.entry((class_name, event.to_string())).or_default() += 1;
+        }
+    }
+
+    let mut leaks: Vec<ProbableLeak> = counts
+        .into_iter()
+        .map(|((class_name, event), count)| ProbableLeak {
+            class_name,
+            event,
+            count,
+        })
+        .collect();
+    leaks.sort_by(|a, b| {
+        b.count
+            .cmp(&a.count)
+            .then_with(|| a.class_name.cmp(&b.class_name))
+            .then_with(|| a.event.cmp(&b.event))
+    });
+    leaks
+}
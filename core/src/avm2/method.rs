@@ -2,12 +2,17 @@
 
 use crate::avm2::activation::Activation;
 use crate::avm2::object::{ClassObject, Object};
+use crate::avm2::property::Property;
 use crate::avm2::script::TranslationUnit;
 use crate::avm2::value::{abc_default_value, Value};
+use crate::avm2::verify::verify_method;
+use crate::avm2::vtable::VTable;
 use crate::avm2::Error;
 use crate::avm2::Multiname;
 use crate::string::AvmString;
+use fnv::FnvHashMap;
 use gc_arena::{Collect, Gc, GcCell, MutationContext};
+use std::cell::Cell;
 use std::fmt;
 use std::ops::Deref;
 use std::rc::Rc;
@@ -133,6 +138,27 @@ pub struct BytecodeMethod<'gc> {
     /// A free-standing function corresponds to the `Function` trait type, and
     /// is instantiated with the `newfunction` opcode.
     pub is_function: bool,
+
+    /// Whether the body of this method has been verified yet.
+    #[collect(require_static)]
+    verified: Cell<bool>,
+
+    /// The inline caches of the property accesses in the body of this method,
+    /// keyed by the offset of their instruction.
+    inline_caches: GcCell<'gc, FnvHashMap<u32, InlineCache<'gc>>>,
+}
+
+/// The trait that the name of a property access resolved to, in the vtable
+/// of the object it was last used on.
+///
+/// Objects of the same class share their vtable, so the entry can be reused
+/// for them without resolving the name again. A redefined class gets a new
+/// vtable, which never matches the entries made for the old one.
+#[derive(Collect, Clone, Copy)]
+#[collect(no_drop)]
+struct InlineCache<'gc> {
+    vtable: VTable<'gc>,
+    property: Property,
 }
 
 impl<'gc> BytecodeMethod<'gc> {
@@ -174,6 +200,11 @@ impl<'gc> BytecodeMethod<'gc> {
                         return_type,
                         is_function,
                         activation_class,
+                        verified: Cell::new(false),
+                        inline_caches: GcCell::allocate(
+                            activation.context.gc_context,
+                            Default::default(),
+                        ),
                     });
                 }
             }
@@ -188,6 +219,8 @@ impl<'gc> BytecodeMethod<'gc> {
             return_type: Multiname::any(activation.context.gc_context),
             is_function,
             activation_class: None,
+            verified: Cell::new(false),
+            inline_caches: GcCell::allocate(activation.context.gc_context, Default::default()),
         })
     }
 
@@ -217,6 +250,41 @@ impl<'gc> BytecodeMethod<'gc> {
         }
     }
 
+    /// Verify the body of this method, unless it has already been verified.
+    ///
+    /// This yields a `VerifyError` if the body is malformed.
+    pub fn verify(&self, activation: &mut Activation<'_, 'gc>) -> Result<(), Error<'gc>> {
+        if !self.verified.get() {
+            verify_method(activation, self)?;
+            self.verified.set(true);
+        }
+        Ok(())
+    }
+
+    /// Look up the trait that the property access at `offset` resolved to,
+    /// if it was last used on an object with the given vtable.
+    pub fn cached_trait(&self, offset: u32, vtable: VTable<'gc>) -> Option<Property> {
+        self.inline_caches
+            .read()
+            .get(&offset)
+            .filter(|cache| VTable::ptr_eq(cache.vtable, vtable))
+            .map(|cache| cache.property)
+    }
+
+    /// Remember the trait that the property access at `offset` resolved to
+    /// for objects with the given vtable.
+    pub fn cache_trait(
+        &self,
+        mc: MutationContext<'gc, '_>,
+        offset: u32,
+        vtable: VTable<'gc>,
+        property: Property,
+    ) {
+        self.inline_caches
+            .write(mc)
+            .insert(offset, InlineCache { vtable, property });
+    }
+
     /// Get the list of method params for this method.
     pub fn signature(&self) -> &[ParamConfig<'gc>] {
         &self.signature
@@ -18,9 +18,10 @@ use crate::avm2::Multiname;
 use crate::avm2::Namespace;
 use crate::avm2::QName;
 use crate::bitmap::bitmap_data::BitmapDataWrapper;
+use crate::camera::Camera;
 use crate::context::UpdateContext;
 use crate::display_object::DisplayObject;
-use crate::html::TextFormat;
+use crate::html::{StyleSheet, TextFormat};
 use crate::streams::NetStream;
 use crate::string::AvmString;
 use gc_arena::{Collect, GcCell, MutationContext};
@@ -32,6 +33,7 @@ use std::hash::{Hash, Hasher};
 mod array_object;
 mod bitmapdata_object;
 mod bytearray_object;
+mod camera_object;
 mod class_object;
 mod context3d_object;
 mod date_object;
@@ -55,6 +57,7 @@ mod sound_object;
 mod soundchannel_object;
 mod stage3d_object;
 mod stage_object;
+mod stylesheet_object;
 mod textformat_object;
 mod texture_object;
 mod vector_object;
@@ -65,6 +68,7 @@ mod xml_object;
 pub use crate::avm2::object::array_object::{array_allocator, ArrayObject};
 pub use crate::avm2::object::bitmapdata_object::{bitmap_data_allocator, BitmapDataObject};
 pub use crate::avm2::object::bytearray_object::{byte_array_allocator, ByteArrayObject};
+pub use crate::avm2::object::camera_object::CameraObject;
 pub use crate::avm2::object::class_object::ClassObject;
 pub use crate::avm2::object::context3d_object::Context3DObject;
 pub use crate::avm2::object::date_object::{date_allocator, DateObject};
@@ -73,7 +77,9 @@ pub use crate::avm2::object::dispatch_object::DispatchObject;
 pub use crate::avm2::object::domain_object::{application_domain_allocator, DomainObject};
 pub use crate::avm2::object::error_object::{error_allocator, ErrorObject};
 pub use crate::avm2::object::event_object::{event_allocator, EventObject};
-pub use crate::avm2::object::function_object::{function_allocator, FunctionObject};
+pub use crate::avm2::object::function_object::{
+    function_allocator, FunctionObject, FunctionObjectWeak,
+};
 pub use crate::avm2::object::index_buffer_3d_object::IndexBuffer3DObject;
 pub use crate::avm2::object::loaderinfo_object::{
     loader_info_allocator, LoaderInfoObject, LoaderStream,
@@ -90,6 +96,7 @@ pub use crate::avm2::object::sound_object::{sound_allocator, QueuedPlay, SoundDa
 pub use crate::avm2::object::soundchannel_object::{sound_channel_allocator, SoundChannelObject};
 pub use crate::avm2::object::stage3d_object::{stage_3d_allocator, Stage3DObject};
 pub use crate::avm2::object::stage_object::{stage_allocator, StageObject};
+pub use crate::avm2::object::stylesheet_object::{stylesheet_allocator, StyleSheetObject};
 pub use crate::avm2::object::textformat_object::{textformat_allocator, TextFormatObject};
 pub use crate::avm2::object::texture_object::TextureObject;
 pub use crate::avm2::object::vector_object::{vector_allocator, VectorObject};
@@ -136,6 +143,8 @@ pub use crate::avm2::object::xml_object::{xml_allocator, XmlObject};
         TextureObject(TextureObject<'gc>),
         Program3DObject(Program3DObject<'gc>),
         NetStreamObject(NetStreamObject<'gc>),
+        StyleSheetObject(StyleSheetObject<'gc>),
+        CameraObject(CameraObject<'gc>),
     }
 )]
 pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy {
@@ -165,13 +174,30 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
     /// This corresponds directly to the AVM2 operation `getproperty`, with the
     /// exception that it does not special-case object lookups on dictionary
     /// structured objects.
-    #[allow(unused_mut)] //Not unused.
     fn get_property(
+        self,
+        multiname: &Multiname<'gc>,
+        activation: &mut Activation<'_, 'gc>,
+    ) -> Result<Value<'gc>, Error<'gc>> {
+        let property = self.vtable().and_then(|vtable| vtable.get_trait(multiname));
+        self.get_resolved_property(multiname, property, activation)
+    }
+
+    /// Retrieve a property by Multiname lookup, given the trait that the
+    /// Multiname resolves to in the vtable of this object.
+    ///
+    /// This method should not be overridden.
+    ///
+    /// This is `get_property` for callers that already resolved the trait,
+    /// such as inline caches.
+    #[allow(unused_mut)] //Not unused.
+    fn get_resolved_property(
         mut self,
         multiname: &Multiname<'gc>,
+        property: Option<Property>,
         activation: &mut Activation<'_, 'gc>,
     ) -> Result<Value<'gc>, Error<'gc>> {
-        match self.vtable().and_then(|vtable| vtable.get_trait(multiname)) {
+        match property {
             Some(Property::Slot { slot_id }) | Some(Property::ConstSlot { slot_id }) => {
                 self.base().get_slot(slot_id)
             }
@@ -266,7 +292,25 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
         value: Value<'gc>,
         activation: &mut Activation<'_, 'gc>,
     ) -> Result<(), Error<'gc>> {
-        match self.vtable().and_then(|vtable| vtable.get_trait(multiname)) {
+        let property = self.vtable().and_then(|vtable| vtable.get_trait(multiname));
+        self.set_resolved_property(multiname, property, value, activation)
+    }
+
+    /// Set a property by Multiname lookup, given the trait that the Multiname
+    /// resolves to in the vtable of this object.
+    ///
+    /// This method should not be overridden.
+    ///
+    /// This is `set_property` for callers that already resolved the trait,
+    /// such as inline caches.
+    fn set_resolved_property(
+        &mut self,
+        multiname: &Multiname<'gc>,
+        property: Option<Property>,
+        value: Value<'gc>,
+        activation: &mut Activation<'_, 'gc>,
+    ) -> Result<(), Error<'gc>> {
+        match property {
             Some(Property::Slot { slot_id }) => {
                 let value = self
                     .vtable()
@@ -414,14 +458,32 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
     /// This method should not be overridden.
     ///
     /// This corresponds directly to the `callproperty` operation in AVM2.
-    #[allow(unused_mut)]
     fn call_property(
+        self,
+        multiname: &Multiname<'gc>,
+        arguments: &[Value<'gc>],
+        activation: &mut Activation<'_, 'gc>,
+    ) -> Result<Value<'gc>, Error<'gc>> {
+        let property = self.vtable().and_then(|vtable| vtable.get_trait(multiname));
+        self.call_resolved_property(multiname, property, arguments, activation)
+    }
+
+    /// Call a named property on the object, given the trait that the
+    /// Multiname resolves to in the vtable of this object.
+    ///
+    /// This method should not be overridden.
+    ///
+    /// This is `call_property` for callers that already resolved the trait,
+    /// such as inline caches.
+    #[allow(unused_mut)]
+    fn call_resolved_property(
         mut self,
         multiname: &Multiname<'gc>,
+        property: Option<Property>,
         arguments: &[Value<'gc>],
         activation: &mut Activation<'_, 'gc>,
     ) -> Result<Value<'gc>, Error<'gc>> {
-        match self.vtable().and_then(|vtable| vtable.get_trait(multiname)) {
+        match property {
             Some(Property::Slot { slot_id }) | Some(Property::ConstSlot { slot_id }) => {
                 let obj = self.base().get_slot(slot_id)?.as_callable(
                     activation,
@@ -1312,6 +1374,14 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
     fn as_netstream(self) -> Option<NetStream<'gc>> {
         None
     }
+
+    fn as_style_sheet(self) -> Option<StyleSheet<'gc>> {
+        None
+    }
+
+    fn as_camera(self) -> Option<Camera<'gc>> {
+        None
+    }
 }
 
 pub enum ObjectPtr {}
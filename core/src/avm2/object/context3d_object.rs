@@ -6,12 +6,14 @@ use crate::avm2::object::{Object, ObjectPtr, TObject};
 use crate::avm2::value::Value;
 use crate::avm2::Error;
 use crate::avm2_stub_method;
+use crate::bitmap::bitmap_data::{BitmapDataDrawError, BitmapDataWrapper};
+use crate::bitmap::operations;
 use crate::context::RenderContext;
 use gc_arena::{Collect, GcCell, MutationContext};
 use ruffle_render::backend::{
     BufferUsage, Context3D, Context3DBlendFactor, Context3DCommand, Context3DCompareMode,
-    Context3DTextureFormat, Context3DTriangleFace, Context3DVertexBufferFormat, ProgramType,
-    Texture,
+    Context3DProfile, Context3DTextureFormat, Context3DTriangleFace, Context3DVertexBufferFormat,
+    ProgramType, Texture,
 };
 use ruffle_render::bitmap::BitmapHandle;
 use ruffle_render::commands::CommandHandler;
@@ -30,6 +32,7 @@ impl<'gc> Context3DObject<'gc> {
     pub fn from_context(
         activation: &mut Activation<'_, 'gc>,
         context: Box<dyn Context3D>,
+        profile: Context3DProfile,
     ) -> Result<Object<'gc>, Error<'gc>> {
         let class = activation.avm2().classes().context3d;
         let base = ScriptObjectData::new(class);
@@ -39,6 +42,7 @@ impl<'gc> Context3DObject<'gc> {
             Context3DData {
                 base,
                 render_context: Some(context),
+                profile,
             },
         ))
         .into();
@@ -49,6 +53,10 @@ impl<'gc> Context3DObject<'gc> {
         Ok(this)
     }
 
+    pub fn profile(&self) -> Context3DProfile {
+        self.0.read().profile
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub fn configure_back_buffer(
         &mut self,
@@ -394,6 +402,27 @@ impl<'gc> Context3DObject<'gc> {
         Ok(())
     }
 
+    pub fn draw_to_bitmap_data(
+        &self,
+        activation: &mut Activation<'_, 'gc>,
+        destination: BitmapDataWrapper<'gc>,
+    ) -> Result<(), Error<'gc>> {
+        let back_buffer = self.0.read().render_context.as_ref().unwrap().back_buffer();
+
+        if let Some((handle, width, height)) = back_buffer {
+            if let Err(BitmapDataDrawError::Unimplemented) = operations::draw_stage3d_buffer(
+                &mut activation.context,
+                destination,
+                handle,
+                width,
+                height,
+            ) {
+                return Err("Render backend does not support Context3D.drawToBitmapData".into());
+            }
+        }
+        Ok(())
+    }
+
     // Renders our finalized frame to the screen, as part of the Ruffle rendering process.
     pub fn render(&self, context: &mut RenderContext<'_, 'gc>) {
         let context3d = self.0.read();
@@ -402,11 +431,9 @@ impl<'gc> Context3DObject<'gc> {
         if context3d.should_render() {
             let handle = context3d.bitmap_handle();
 
-            context.commands.render_stage3d(
-                handle,
-                // FIXME - apply x and y translation from Stage3D
-                context.transform_stack.transform(),
-            );
+            context
+                .commands
+                .render_stage3d(handle, context.transform_stack.transform());
         }
     }
 
@@ -446,6 +473,7 @@ impl<'gc> Context3DObject<'gc> {
         source: BitmapHandle,
         dest: Rc<dyn Texture>,
         layer: u32,
+        mip_level: u32,
         activation: &mut Activation<'_, 'gc>,
     ) {
         self.0
@@ -458,6 +486,7 @@ impl<'gc> Context3DObject<'gc> {
                     source,
                     dest,
                     layer,
+                    mip_level,
                 },
                 activation.context.gc_context,
             )
@@ -564,6 +593,7 @@ impl<'gc> Context3DObject<'gc> {
         sampler: u32,
         wrap: ruffle_render::backend::Context3DWrapMode,
         filter: ruffle_render::backend::Context3DTextureFilter,
+        mip_filter: ruffle_render::backend::Context3DMipFilter,
     ) {
         self.0
             .write(activation.context.gc_context)
@@ -575,6 +605,7 @@ impl<'gc> Context3DObject<'gc> {
                     sampler,
                     wrap,
                     filter,
+                    mip_filter,
                 },
                 activation.context.gc_context,
             )
@@ -589,6 +620,9 @@ pub struct Context3DData<'gc> {
 
     #[collect(require_static)]
     render_context: Option<Box<dyn Context3D>>,
+
+    /// The profile this context was created with, which determines its limits.
+    profile: Context3DProfile,
 }
 
 impl<'gc> TObject<'gc> for Context3DObject<'gc> {
@@ -9,7 +9,7 @@ use crate::avm2::scope::ScopeChain;
 use crate::avm2::value::Value;
 use crate::avm2::{Error, Multiname};
 use core::fmt;
-use gc_arena::{Collect, Gc, GcCell, MutationContext};
+use gc_arena::{Collect, Gc, GcCell, GcWeakCell, MutationContext};
 use std::cell::{Ref, RefMut};
 
 /// A class instance allocator that allocates Function objects.
@@ -57,6 +57,17 @@ pub fn function_allocator<'gc>(
 #[collect(no_drop)]
 pub struct FunctionObject<'gc>(GcCell<'gc, FunctionObjectData<'gc>>);
 
+/// A weak reference to a `FunctionObject`, which doesn't keep it alive.
+#[derive(Collect, Clone, Copy)]
+#[collect(no_drop)]
+pub struct FunctionObjectWeak<'gc>(GcWeakCell<'gc, FunctionObjectData<'gc>>);
+
+impl<'gc> FunctionObjectWeak<'gc> {
+    pub fn upgrade(self, mc: MutationContext<'gc, '_>) -> Option<FunctionObject<'gc>> {
+        self.0.upgrade(mc).map(FunctionObject)
+    }
+}
+
 impl fmt::Debug for FunctionObject<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("FunctionObject")
@@ -79,6 +90,10 @@ pub struct FunctionObjectData<'gc> {
 }
 
 impl<'gc> FunctionObject<'gc> {
+    pub fn downgrade(self) -> FunctionObjectWeak<'gc> {
+        FunctionObjectWeak(GcCell::downgrade(self.0))
+    }
+
     /// Construct a function from an ABC method and the current closure scope.
     ///
     /// This associated constructor will also create and initialize an empty
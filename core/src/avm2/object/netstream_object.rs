@@ -20,7 +20,7 @@ pub fn netstream_allocator<'gc>(
         activation.context.gc_context,
         NetStreamObjectData {
             base,
-            ns: NetStream::new(activation.context.gc_context),
+            ns: NetStream::new(activation.context.gc_context, None),
         },
     ))
     .into())
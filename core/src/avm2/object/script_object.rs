@@ -1,6 +1,7 @@
 //! Default AVM2 object impl
 
 use crate::avm2::activation::Activation;
+use crate::avm2::dynamic_map::DynamicMap;
 use crate::avm2::error;
 use crate::avm2::object::{ClassObject, FunctionObject, Object, ObjectPtr, TObject};
 use crate::avm2::value::Value;
@@ -8,10 +9,8 @@ use crate::avm2::vtable::VTable;
 use crate::avm2::Multiname;
 use crate::avm2::{Error, QName};
 use crate::string::AvmString;
-use fnv::FnvHashMap;
 use gc_arena::{Collect, GcCell, MutationContext};
 use std::cell::{Ref, RefMut};
-use std::collections::hash_map::Entry;
 use std::fmt::Debug;
 
 /// A class instance allocator that allocates `ScriptObject`s.
@@ -38,7 +37,7 @@ pub struct ScriptObject<'gc>(GcCell<'gc, ScriptObjectData<'gc>>);
 #[collect(no_drop)]
 pub struct ScriptObjectData<'gc> {
     /// Values stored on this object.
-    values: FnvHashMap<AvmString<'gc>, Value<'gc>>,
+    values: DynamicMap<'gc>,
 
     /// Slots stored on this object.
     slots: Vec<Value<'gc>>,
@@ -220,16 +219,10 @@ impl<'gc> ScriptObjectData<'gc> {
             ));
         };
 
-        match self.values.entry(local_name) {
-            Entry::Occupied(mut o) => {
-                o.insert(value);
-            }
-            Entry::Vacant(v) => {
-                //TODO: Not all classes are dynamic like this
-                self.enumerants.push(local_name);
-                v.insert(value);
-            }
-        };
+        if self.values.insert(local_name, value).is_none() {
+            //TODO: Not all classes are dynamic like this
+            self.enumerants.push(local_name);
+        }
         Ok(())
     }
 
@@ -21,6 +21,9 @@ pub fn stage_3d_allocator<'gc>(
         Stage3DObjectData {
             base,
             context3d: None,
+            x: 0.0,
+            y: 0.0,
+            visible: true,
         },
     ))
     .into())
@@ -46,6 +49,30 @@ impl<'gc> Stage3DObject<'gc> {
     pub fn set_context3d(self, context3d: Object<'gc>, mc: MutationContext<'gc, '_>) {
         self.0.write(mc).context3d = Some(context3d);
     }
+
+    pub fn x(self) -> f64 {
+        self.0.read().x
+    }
+
+    pub fn set_x(self, x: f64, mc: MutationContext<'gc, '_>) {
+        self.0.write(mc).x = x;
+    }
+
+    pub fn y(self) -> f64 {
+        self.0.read().y
+    }
+
+    pub fn set_y(self, y: f64, mc: MutationContext<'gc, '_>) {
+        self.0.write(mc).y = y;
+    }
+
+    pub fn visible(self) -> bool {
+        self.0.read().visible
+    }
+
+    pub fn set_visible(self, visible: bool, mc: MutationContext<'gc, '_>) {
+        self.0.write(mc).visible = visible;
+    }
 }
 
 #[derive(Clone, Collect)]
@@ -57,6 +84,13 @@ pub struct Stage3DObjectData<'gc> {
     /// The context3D object associated with this Stage3D object,
     /// if it's been created with `requestContext3D`
     context3d: Option<Object<'gc>>,
+
+    /// The position of the top-left corner of the back buffer, in stage coordinates.
+    x: f64,
+    y: f64,
+
+    /// Whether the back buffer is drawn underneath the stage.
+    visible: bool,
 }
 
 impl<'gc> TObject<'gc> for Stage3DObject<'gc> {
@@ -14,10 +14,12 @@ use crate::avm2::Namespace;
 use crate::avm2::{Avm2, Error};
 use crate::context::{GcContext, UpdateContext};
 use crate::string::{AvmAtom, AvmString};
+use crate::tag_utils::SwfMovie;
 use gc_arena::{Collect, Gc, GcCell, MutationContext};
 use std::cell::Ref;
 use std::mem::drop;
 use std::rc::Rc;
+use std::sync::Arc;
 use swf::avm2::types::{
     AbcFile, Index, Method as AbcMethod, Multiname as AbcMultiname, Namespace as AbcNamespace,
     Script as AbcScript,
@@ -48,6 +50,10 @@ struct TranslationUnitData<'gc> {
     /// The name from the original `DoAbc2` tag, or `None` if this came from a `DoAbc` tag
     name: Option<AvmString<'gc>>,
 
+    /// The movie the ABC file was loaded from, or `None` for Ruffle's own playerglobals.
+    #[collect(require_static)]
+    movie: Option<Arc<SwfMovie>>,
+
     /// The ABC file that all of the following loaded data comes from.
     #[collect(require_static)]
     abc: Rc<AbcFile>,
@@ -81,6 +87,7 @@ impl<'gc> TranslationUnit<'gc> {
         abc: AbcFile,
         domain: Domain<'gc>,
         name: Option<AvmString<'gc>>,
+        movie: Option<Arc<SwfMovie>>,
         mc: MutationContext<'gc, '_>,
     ) -> Self {
         let classes = vec![None; abc.classes.len()];
@@ -95,6 +102,7 @@ impl<'gc> TranslationUnit<'gc> {
             TranslationUnitData {
                 domain,
                 name,
+                movie,
                 abc: Rc::new(abc),
                 classes,
                 methods,
@@ -115,6 +123,11 @@ impl<'gc> TranslationUnit<'gc> {
         self.0.read().name
     }
 
+    /// Retrieve the movie this translation unit was loaded from.
+    pub fn movie(self) -> Option<Arc<SwfMovie>> {
+        self.0.read().movie.clone()
+    }
+
     /// Retrieve the underlying `AbcFile` for this translation unit.
     pub fn abc(self) -> Rc<AbcFile> {
         self.0.read().abc.clone()
@@ -0,0 +1,605 @@
+//! Verification of ABC files and method bodies.
+//!
+//! Flash Player refuses to run malformed bytecode, and throws a `VerifyError`
+//! into the content instead. We don't type check code like it does, but we do
+//! check every constant pool index, branch target and register that the rest
+//! of the VM assumes to be valid, so that malformed ABC can't panic the player.
+//! Only code that can run is checked, as the interpreter never decodes the rest.
+
+use crate::avm2::activation::Activation;
+use crate::avm2::error::verify_error;
+use crate::avm2::method::BytecodeMethod;
+use crate::avm2::Error;
+use crate::context::UpdateContext;
+use swf::avm2::read::Reader;
+use swf::avm2::types::{
+    AbcFile, DefaultValue, Index, Method, MethodBody, Multiname, Namespace, Op, Trait, TraitKind,
+};
+use swf::error::Error as SwfError;
+
+/// A reason for rejecting ABC, as the error code and message of the
+/// `VerifyError` to throw.
+struct Failure(u32, String);
+
+impl Failure {
+    fn corrupt() -> Self {
+        Self(
+            1107,
+            "Error #1107: The ABC data is corrupt, attempt to read out of bounds.".to_string(),
+        )
+    }
+
+    fn throw<'gc>(self, activation: &mut Activation<'_, 'gc>) -> Error<'gc> {
+        match verify_error(activation, &self.1, self.0) {
+            Ok(error) => Error::AvmError(error),
+            Err(error) => error,
+        }
+    }
+}
+
+/// Checks an index into a constant pool, where index 0 refers to the implicit
+/// default entry and `pool` holds the entries from index 1 onwards.
+fn check_cpool<T>(index: u32, pool: &[T]) -> Result<(), Failure> {
+    if index as usize > pool.len() {
+        return Err(Failure(
+            1032,
+            format!(
+                "Error #1032: Cpool index {} is out of range {}.",
+                index,
+                pool.len() + 1
+            ),
+        ));
+    }
+    Ok(())
+}
+
+/// Checks an index into a constant pool that may not refer to the default entry.
+fn check_cpool_entry<T>(index: u32, pool: &[T]) -> Result<(), Failure> {
+    if index == 0 {
+        return Err(Failure(
+            1032,
+            format!(
+                "Error #1032: Cpool index 0 is out of range {}.",
+                pool.len() + 1
+            ),
+        ));
+    }
+    check_cpool(index, pool)
+}
+
+fn check_method(abc: &AbcFile, index: Index<Method>) -> Result<(), Failure> {
+    if index.0 as usize >= abc.methods.len() {
+        return Err(Failure(
+            1027,
+            format!(
+                "Error #1027: Method_info {} exceeds method_count={}.",
+                index.0,
+                abc.methods.len()
+            ),
+        ));
+    }
+    Ok(())
+}
+
+fn check_class(abc: &AbcFile, index: u32) -> Result<(), Failure> {
+    if index as usize >= abc.classes.len() {
+        return Err(Failure(
+            1060,
+            format!(
+                "Error #1060: Class_info {} exceeds class_count={}.",
+                index,
+                abc.classes.len()
+            ),
+        ));
+    }
+    Ok(())
+}
+
+fn check_default_value(abc: &AbcFile, value: &DefaultValue) -> Result<(), Failure> {
+    let pool = &abc.constant_pool;
+    match value {
+        DefaultValue::Int(index) => check_cpool_entry(index.0, &pool.ints),
+        DefaultValue::Uint(index) => check_cpool_entry(index.0, &pool.uints),
+        DefaultValue::Double(index) => check_cpool_entry(index.0, &pool.doubles),
+        DefaultValue::String(index) => check_cpool_entry(index.0, &pool.strings),
+        DefaultValue::Namespace(index)
+        | DefaultValue::Package(index)
+        | DefaultValue::PackageInternal(index)
+        | DefaultValue::Protected(index)
+        | DefaultValue::Explicit(index)
+        | DefaultValue::StaticProtected(index)
+        | DefaultValue::Private(index) => check_cpool_entry(index.0, &pool.namespaces),
+        DefaultValue::True | DefaultValue::False | DefaultValue::Null | DefaultValue::Undefined => {
+            Ok(())
+        }
+    }
+}
+
+fn check_traits(abc: &AbcFile, traits: &[Trait]) -> Result<(), Failure> {
+    let multinames = &abc.constant_pool.multinames;
+    for abc_trait in traits {
+        check_cpool_entry(abc_trait.name.0, multinames)?;
+        match &abc_trait.kind {
+            TraitKind::Slot {
+                type_name, value, ..
+            }
+            | TraitKind::Const {
+                type_name, value, ..
+            } => {
+                check_cpool(type_name.0, multinames)?;
+                if let Some(value) = value {
+                    check_default_value(abc, value)?;
+                }
+            }
+            TraitKind::Method { method, .. }
+            | TraitKind::Getter { method, .. }
+            | TraitKind::Setter { method, .. }
+            | TraitKind::Function {
+                function: method, ..
+            } => check_method(abc, *method)?,
+            TraitKind::Class { class, .. } => check_class(abc, class.0)?,
+        }
+    }
+    Ok(())
+}
+
+/// Checks that everything an ABC file refers to exists in it.
+fn check_abc(abc: &AbcFile) -> Result<(), Failure> {
+    let pool = &abc.constant_pool;
+
+    for namespace in &pool.namespaces {
+        let (Namespace::Namespace(name)
+        | Namespace::Package(name)
+        | Namespace::PackageInternal(name)
+        | Namespace::Protected(name)
+        | Namespace::Explicit(name)
+        | Namespace::StaticProtected(name)
+        | Namespace::Private(name)) = namespace;
+        check_cpool(name.0, &pool.strings)?;
+    }
+
+    for namespace_set in &pool.namespace_sets {
+        for namespace in namespace_set {
+            check_cpool_entry(namespace.0, &pool.namespaces)?;
+        }
+    }
+
+    for (i, multiname) in pool.multinames.iter().enumerate() {
+        match multiname {
+            Multiname::QName { namespace, name } | Multiname::QNameA { namespace, name } => {
+                check_cpool(namespace.0, &pool.namespaces)?;
+                check_cpool(name.0, &pool.strings)?;
+            }
+            Multiname::RTQName { name } | Multiname::RTQNameA { name } => {
+                check_cpool(name.0, &pool.strings)?;
+            }
+            Multiname::RTQNameL | Multiname::RTQNameLA => {}
+            Multiname::Multiname {
+                namespace_set,
+                name,
+            }
+            | Multiname::MultinameA {
+                namespace_set,
+                name,
+            } => {
+                check_cpool_entry(namespace_set.0, &pool.namespace_sets)?;
+                check_cpool(name.0, &pool.strings)?;
+            }
+            Multiname::MultinameL { namespace_set } | Multiname::MultinameLA { namespace_set } => {
+                check_cpool_entry(namespace_set.0, &pool.namespace_sets)?;
+            }
+            Multiname::TypeName {
+                base_type,
+                parameters,
+            } => {
+                // Parameterized types may only refer to earlier multinames, which
+                // also rules out types that (indirectly) contain themselves.
+                for index in std::iter::once(base_type).chain(parameters) {
+                    check_cpool_entry(index.0, &pool.multinames[..i])?;
+                }
+            }
+        }
+    }
+
+    for method in &abc.methods {
+        check_cpool(method.name.0, &pool.strings)?;
+        check_cpool(method.return_type.0, &pool.multinames)?;
+        for param in &method.params {
+            check_cpool(param.kind.0, &pool.multinames)?;
+            if let Some(name) = param.name {
+                check_cpool(name.0, &pool.strings)?;
+            }
+            if let Some(value) = &param.default_value {
+                check_default_value(abc, value)?;
+            }
+        }
+    }
+
+    for instance in &abc.instances {
+        check_cpool_entry(instance.name.0, &pool.multinames)?;
+        check_cpool(instance.super_name.0, &pool.multinames)?;
+        if let Some(namespace) = instance.protected_namespace {
+            check_cpool(namespace.0, &pool.namespaces)?;
+        }
+        for interface in &instance.interfaces {
+            check_cpool_entry(interface.0, &pool.multinames)?;
+        }
+        check_method(abc, instance.init_method)?;
+        check_traits(abc, &instance.traits)?;
+    }
+
+    for class in &abc.classes {
+        check_method(abc, class.init_method)?;
+        check_traits(abc, &class.traits)?;
+    }
+
+    for script in &abc.scripts {
+        check_method(abc, script.init_method)?;
+        check_traits(abc, &script.traits)?;
+    }
+
+    for body in &abc.method_bodies {
+        check_method(abc, body.method)?;
+        if body.init_scope_depth > body.max_scope_depth {
+            return Err(Failure::corrupt());
+        }
+        for exception in &body.exceptions {
+            check_cpool(exception.variable_name.0, &pool.multinames)?;
+            check_cpool(exception.type_name.0, &pool.multinames)?;
+        }
+        check_traits(abc, &body.traits)?;
+    }
+
+    Ok(())
+}
+
+/// Verifies a freshly parsed ABC file, before any of it is loaded.
+pub fn verify_abc<'gc>(
+    context: &mut UpdateContext<'_, 'gc>,
+    abc: &AbcFile,
+) -> Result<(), Error<'gc>> {
+    check_abc(abc).map_err(|failure| {
+        let mut activation = Activation::from_nothing(context.reborrow());
+        failure.throw(&mut activation)
+    })
+}
+
+/// Checks the code of a method body.
+fn check_code(method: &BytecodeMethod<'_>) -> Result<(), Failure> {
+    let Some(body) = method.body() else {
+        return Ok(());
+    };
+
+    // Registers 0 and up hold `this`, the declared parameters and `arguments`
+    // or the rest parameter, followed by the other locals.
+    let num_registers =
+        body.num_locals as usize + method.signature().len() + usize::from(method.is_variadic()) + 1;
+    check_body(&method.abc(), body, num_registers, method.method_name())
+}
+
+/// Checks the instructions of a method body that can be reached.
+///
+/// Like Flash Player, this follows the control flow from the start of the code
+/// and from every exception handler, so that junk after jumps (as commonly
+/// inserted by obfuscators) is accepted, as it never runs.
+fn check_body(
+    abc: &AbcFile,
+    body: &MethodBody,
+    num_registers: usize,
+    method_name: &str,
+) -> Result<(), Failure> {
+    let pool = &abc.constant_pool;
+    let code = &body.code[..];
+
+    if code.is_empty() {
+        return Err(Failure(
+            1043,
+            "Error #1043: Invalid code_length=0.".to_string(),
+        ));
+    }
+
+    let check_register = |index: u32| {
+        if index as usize >= num_registers {
+            return Err(Failure(
+                1025,
+                format!("Error #1025: An invalid register {index} was accessed."),
+            ));
+        }
+        Ok(())
+    };
+
+    for exception in &body.exceptions {
+        if exception.from_offset > exception.to_offset
+            || exception.to_offset as usize > code.len()
+            || exception.target_offset as usize >= code.len()
+        {
+            return Err(Failure(
+                1054,
+                "Error #1054: Illegal range or target offsets in exception handler.".to_string(),
+            ));
+        }
+    }
+
+    let invalid_target = || {
+        Failure(
+            1021,
+            "Error #1021: At least one branch target was not on a valid instruction in the method."
+                .to_string(),
+        )
+    };
+
+    let mut reader = Reader::new(code);
+    let mut visited = vec![false; code.len()];
+    let mut pending: Vec<usize> = std::iter::once(0)
+        .chain(body.exceptions.iter().map(|e| e.target_offset as usize))
+        .collect();
+    while let Some(start) = pending.pop() {
+        if start >= code.len() {
+            return Err(Failure(
+                1020,
+                "Error #1020: Code cannot fall off the end of a method.".to_string(),
+            ));
+        }
+        if visited[start] {
+            continue;
+        }
+        visited[start] = true;
+
+        reader.seek_absolute(code, start);
+        let op = match reader.read_op() {
+            Ok(op) => op,
+            Err(SwfError::IoError(_)) => {
+                return Err(Failure(
+                    1020,
+                    "Error #1020: Code cannot fall off the end of a method.".to_string(),
+                ));
+            }
+            Err(_) => {
+                return Err(Failure(
+                    1011,
+                    format!(
+                        "Error #1011: Method {} contained illegal opcode {} at offset {}.",
+                        method_name, code[start], start
+                    ),
+                ));
+            }
+        };
+        let end = reader.pos(code);
+        let mut branch = |target: i64| {
+            let target = usize::try_from(target)
+                .ok()
+                .filter(|target| *target < code.len())
+                .ok_or_else(invalid_target)?;
+            pending.push(target);
+            Ok(())
+        };
+
+        match op {
+            Op::Jump { offset } => {
+                branch(end as i64 + offset as i64)?;
+                continue;
+            }
+            Op::IfTrue { offset }
+            | Op::IfFalse { offset }
+            | Op::IfStrictEq { offset }
+            | Op::IfStrictNe { offset }
+            | Op::IfEq { offset }
+            | Op::IfNe { offset }
+            | Op::IfGe { offset }
+            | Op::IfGt { offset }
+            | Op::IfLe { offset }
+            | Op::IfLt { offset }
+            | Op::IfNge { offset }
+            | Op::IfNgt { offset }
+            | Op::IfNle { offset }
+            | Op::IfNlt { offset } => branch(end as i64 + offset as i64)?,
+            Op::LookupSwitch {
+                default_offset,
+                case_offsets,
+            } => {
+                // Switch offsets are relative to the start of the instruction.
+                branch(start as i64 + default_offset as i64)?;
+                for offset in case_offsets.iter() {
+                    branch(start as i64 + *offset as i64)?;
+                }
+                continue;
+            }
+            Op::ReturnValue | Op::ReturnVoid | Op::Throw => continue,
+            Op::AsType { type_name: index }
+            | Op::CallProperty { index, .. }
+            | Op::CallPropLex { index, .. }
+            | Op::CallPropVoid { index, .. }
+            | Op::CallSuper { index, .. }
+            | Op::CallSuperVoid { index, .. }
+            | Op::Coerce { index }
+            | Op::ConstructProp { index, .. }
+            | Op::DeleteProperty { index }
+            | Op::FindDef { index }
+            | Op::FindProperty { index }
+            | Op::FindPropStrict { index }
+            | Op::GetDescendants { index }
+            | Op::GetLex { index }
+            | Op::GetProperty { index }
+            | Op::GetSuper { index }
+            | Op::InitProperty { index }
+            | Op::IsType { index }
+            | Op::SetProperty { index }
+            | Op::SetSuper { index } => check_cpool_entry(index.0, &pool.multinames)?,
+            Op::PushString { value: index }
+            | Op::Dxns { index }
+            | Op::DebugFile { file_name: index }
+            | Op::Debug {
+                register_name: index,
+                ..
+            } => check_cpool(index.0, &pool.strings)?,
+            Op::PushInt { value } => check_cpool(value.0, &pool.ints)?,
+            Op::PushUint { value } => check_cpool(value.0, &pool.uints)?,
+            Op::PushDouble { value } => check_cpool(value.0, &pool.doubles)?,
+            Op::PushNamespace { value } => check_cpool(value.0, &pool.namespaces)?,
+            Op::NewFunction { index } | Op::CallStatic { index, .. } => check_method(abc, index)?,
+            Op::CallMethod { index, .. } => {
+                // Flash Player rejects early bound calls in verified code.
+                return Err(Failure(
+                    1051,
+                    format!(
+                        "Error #1051: Illegal early binding access to method {}.",
+                        index.0
+                    ),
+                ));
+            }
+            Op::NewClass { index } => check_class(abc, index.0)?,
+            Op::NewCatch { index } => {
+                if index.0 as usize >= body.exceptions.len() {
+                    return Err(Failure::corrupt());
+                }
+            }
+            Op::GetLocal { index }
+            | Op::SetLocal { index }
+            | Op::Kill { index }
+            | Op::IncLocal { index }
+            | Op::IncLocalI { index }
+            | Op::DecLocal { index }
+            | Op::DecLocalI { index } => check_register(index)?,
+            Op::HasNext2 {
+                object_register,
+                index_register,
+            } => {
+                check_register(object_register)?;
+                check_register(index_register)?;
+            }
+            _ => {}
+        }
+
+        // Every other instruction continues with the next one.
+        pending.push(end);
+    }
+
+    Ok(())
+}
+
+/// Verifies the code of a method, before it is run for the first time.
+pub fn verify_method<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    method: &BytecodeMethod<'gc>,
+) -> Result<(), Error<'gc>> {
+    check_code(method).map_err(|failure| failure.throw(activation))
+}
+
+/// Verifies an ABC file and the code of all of its method bodies, without a
+/// player to throw the `VerifyError` into. Its error code is returned instead.
+///
+/// This is only meant for fuzzing the verifier.
+#[cfg(feature = "fuzzing")]
+pub fn verify_abc_standalone(abc: &AbcFile) -> Result<(), u32> {
+    use swf::avm2::types::MethodFlags;
+
+    check_abc(abc).map_err(|Failure(code, _)| code)?;
+    for (i, body) in abc.method_bodies.iter().enumerate() {
+        // `check_abc` made sure that the method exists.
+        let method = &abc.methods[body.method.0 as usize];
+        let is_variadic = method
+            .flags
+            .intersects(MethodFlags::NEED_ARGUMENTS | MethodFlags::NEED_REST);
+        let num_registers =
+            body.num_locals as usize + method.params.len() + usize::from(is_variadic) + 1;
+        check_body(abc, body, num_registers, &format!("method body {i}"))
+            .map_err(|Failure(code, _)| code)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use swf::avm2::types::{ConstantPool, Exception};
+
+    const JUMP: u8 = 0x10;
+    const IF_TRUE: u8 = 0x11;
+    const PUSH_TRUE: u8 = 0x26;
+    const RETURN_VOID: u8 = 0x47;
+    const THROW: u8 = 0x03;
+    const ILLEGAL: u8 = 0xff;
+
+    fn check(code: &[u8], exceptions: Vec<Exception>) -> Result<(), u32> {
+        let abc = AbcFile {
+            major_version: 46,
+            minor_version: 16,
+            constant_pool: ConstantPool {
+                ints: vec![],
+                uints: vec![],
+                doubles: vec![],
+                strings: vec![],
+                namespaces: vec![],
+                namespace_sets: vec![],
+                multinames: vec![],
+            },
+            methods: vec![],
+            metadata: vec![],
+            instances: vec![],
+            classes: vec![],
+            scripts: vec![],
+            method_bodies: vec![],
+        };
+        let body = MethodBody {
+            method: Index::new(0),
+            max_stack: 1,
+            num_locals: 1,
+            init_scope_depth: 0,
+            max_scope_depth: 0,
+            code: code.to_vec(),
+            exceptions,
+            traits: vec![],
+        };
+        check_body(&abc, &body, 1, "test").map_err(|failure| failure.0)
+    }
+
+    fn handler(from_offset: u32, to_offset: u32, target_offset: u32) -> Exception {
+        Exception {
+            from_offset,
+            to_offset,
+            target_offset,
+            variable_name: Index::new(0),
+            type_name: Index::new(0),
+        }
+    }
+
+    #[test]
+    fn test_unreachable_junk_is_accepted() {
+        // Illegal and truncated instructions after a jump never run.
+        assert_eq!(
+            check(&[JUMP, 2, 0, 0, ILLEGAL, JUMP, RETURN_VOID], vec![]),
+            Ok(())
+        );
+        assert_eq!(check(&[RETURN_VOID, ILLEGAL, JUMP, 0], vec![]), Ok(()));
+        assert_eq!(check(&[PUSH_TRUE, THROW, ILLEGAL], vec![]), Ok(()));
+    }
+
+    #[test]
+    fn test_reachable_errors_are_rejected() {
+        assert_eq!(check(&[ILLEGAL, RETURN_VOID], vec![]), Err(1011));
+        assert_eq!(
+            check(&[PUSH_TRUE, IF_TRUE, 1, 0, 0, RETURN_VOID, ILLEGAL], vec![]),
+            Err(1011)
+        );
+        assert_eq!(check(&[PUSH_TRUE], vec![]), Err(1020));
+        assert_eq!(
+            check(&[RETURN_VOID, JUMP, 0], vec![handler(0, 1, 1)]),
+            Err(1020)
+        );
+        assert_eq!(check(&[JUMP, 10, 0, 0, RETURN_VOID], vec![]), Err(1021));
+        assert_eq!(check(&[], vec![]), Err(1043));
+    }
+
+    #[test]
+    fn test_exception_handlers_are_followed() {
+        assert_eq!(
+            check(&[RETURN_VOID, ILLEGAL], vec![handler(0, 1, 1)]),
+            Err(1011)
+        );
+        assert_eq!(
+            check(&[RETURN_VOID, RETURN_VOID], vec![handler(0, 1, 1)]),
+            Ok(())
+        );
+        assert_eq!(check(&[RETURN_VOID], vec![handler(0, 1, 1)]), Err(1054));
+    }
+}
@@ -1,5 +1,10 @@
 pub mod audio;
+pub mod local_connection;
 pub mod log;
 pub mod navigator;
+pub mod print;
+pub mod remote_shared_object;
+pub mod socket;
 pub mod storage;
 pub mod ui;
+pub mod video_capture;
@@ -381,6 +381,7 @@ impl<'gc> AudioManager<'gc> {
             let instance = SoundInstance {
                 sound: Some(sound),
                 instance: handle,
+                settings: Some(settings.clone()),
                 display_object,
                 transform: display_object::SoundTransform::default(),
                 avm1_object,
@@ -454,6 +455,23 @@ impl<'gc> AudioManager<'gc> {
         audio.stop_all_sounds();
     }
 
+    /// The event sounds started by AVM1 that are playing, as recorded by save states.
+    pub fn avm1_event_sounds(&self, audio: &dyn AudioBackend) -> Vec<EventSoundState<'gc>> {
+        self.sounds
+            .iter()
+            .filter(|instance| instance.avm2_object.is_none())
+            .filter_map(|instance| {
+                Some(EventSoundState {
+                    sound: instance.sound?,
+                    settings: instance.settings.clone()?,
+                    position: audio.get_sound_position(instance.instance)?,
+                    display_object: instance.display_object,
+                    avm1_object: instance.avm1_object,
+                })
+            })
+            .collect()
+    }
+
     pub fn is_sound_playing(&mut self, sound: SoundInstanceHandle) -> bool {
         self.sounds.iter().any(|other| other.instance == sound)
     }
@@ -478,6 +496,7 @@ impl<'gc> AudioManager<'gc> {
             let instance = SoundInstance {
                 sound: None,
                 instance: handle,
+                settings: None,
                 display_object: Some(movie_clip.into()),
                 transform: display_object::SoundTransform::default(),
                 avm1_object: None,
@@ -647,6 +666,11 @@ pub struct SoundInstance<'gc> {
     #[collect(require_static)]
     sound: Option<SoundHandle>,
 
+    /// The settings that the sound was started with.
+    /// This will be `None` for stream sounds.
+    #[collect(require_static)]
+    settings: Option<swf::SoundInfo>,
+
     /// The display object that this sound is playing in, if any.
     /// Used for volume mixing and `Sound.stop()`.
     display_object: Option<DisplayObject<'gc>>,
@@ -667,6 +691,18 @@ pub struct SoundInstance<'gc> {
     stream_start_frame: Option<u16>,
 }
 
+/// A playing event sound, as recorded by save states.
+pub struct EventSoundState<'gc> {
+    pub sound: SoundHandle,
+    pub settings: swf::SoundInfo,
+
+    /// The position of the sound, in milliseconds.
+    pub position: f64,
+
+    pub display_object: Option<DisplayObject<'gc>>,
+    pub avm1_object: Option<SoundObject<'gc>>,
+}
+
 /// A sound transform for a playing sound, for use by audio backends.
 /// This differs from `display_object::SoundTransform` by being
 /// already converted to `f32` and having `volume` baked in.
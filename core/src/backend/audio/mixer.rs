@@ -44,6 +44,23 @@ impl CircBuf {
     }
 }
 
+/// The interpolation used when resampling sounds to the output sample rate.
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ResamplerQuality {
+    /// Linear interpolation between neighboring sample frames.
+    ///
+    /// This is cheap, but can cause audible aliasing on content with a lot of
+    /// high-frequency energy, such as chiptune-style square waves.
+    #[default]
+    Linear,
+
+    /// Band-limited sinc interpolation.
+    ///
+    /// This is noticeably more expensive than `Linear`, but greatly reduces aliasing.
+    Sinc,
+}
+
 /// An audio mixer for a Flash movie.
 ///
 /// `AudioMixer` manages the audio state for a Flash movie. This can be used by any backend that
@@ -67,6 +84,9 @@ pub struct AudioMixer {
     /// The sample rate of the output stream in Hz.
     output_sample_rate: u32,
 
+    /// The interpolation used when resampling new sounds to `output_sample_rate`.
+    resampler_quality: ResamplerQuality,
+
     /// The last two windows of output samples.
     output_memory: Arc<RwLock<CircBuf>>,
 }
@@ -86,6 +106,18 @@ trait Stream: dasp::signal::Signal<Frame = [i16; 2]> + Send + Sync {
     fn source_sample_rate(&self) -> u16;
 }
 
+impl<S: Stream + ?Sized> Stream for Box<S> {
+    #[inline]
+    fn source_position(&self) -> u32 {
+        (**self).source_position()
+    }
+
+    #[inline]
+    fn source_sample_rate(&self) -> u16 {
+        (**self).source_sample_rate()
+    }
+}
+
 /// A stream that wraps a `Decoder`.
 struct DecoderStream<D> {
     decoder: D,
@@ -241,10 +273,24 @@ impl AudioMixer {
             volume: Arc::new(RwLock::new(1.0)),
             num_output_channels,
             output_sample_rate,
+            resampler_quality: ResamplerQuality::default(),
             output_memory: Arc::new(RwLock::new(CircBuf::new())),
         }
     }
 
+    /// Returns the interpolation used when resampling sounds.
+    pub fn resampler_quality(&self) -> ResamplerQuality {
+        self.resampler_quality
+    }
+
+    /// Sets the interpolation used when resampling sounds.
+    ///
+    /// This only affects sounds started after this call; already playing sounds keep
+    /// the resampler they were created with.
+    pub fn set_resampler_quality(&mut self, quality: ResamplerQuality) {
+        self.resampler_quality = quality;
+    }
+
     /// Creates a proxy that may be sent to a different thread.
     pub fn proxy(&self) -> AudioMixerProxy {
         AudioMixerProxy {
@@ -329,18 +375,38 @@ impl AudioMixer {
     }
 
     /// Transforms a `Stream` into a new `Stream` that matches the output sample rate.
-    fn make_resampler(&self, mut stream: impl Stream) -> impl Stream {
-        // TODO: Allow interpolator to be user-configurable?
-        let left = stream.next();
-        let right = stream.next();
-        let interpolator = dasp::interpolate::linear::Linear::new(left, right);
+    fn make_resampler<'a>(&self, mut stream: impl 'a + Stream) -> Box<dyn 'a + Stream> {
         let sample_rate = stream.source_sample_rate().into();
-        ConverterStream(dasp::signal::interpolate::Converter::from_hz_to_hz(
-            stream,
-            interpolator,
-            sample_rate,
-            self.output_sample_rate.into(),
-        ))
+        match self.resampler_quality {
+            ResamplerQuality::Linear => {
+                let left = stream.next();
+                let right = stream.next();
+                let interpolator = dasp::interpolate::linear::Linear::new(left, right);
+                Box::new(ConverterStream(
+                    dasp::signal::interpolate::Converter::from_hz_to_hz(
+                        stream,
+                        interpolator,
+                        sample_rate,
+                        self.output_sample_rate.into(),
+                    ),
+                ))
+            }
+            ResamplerQuality::Sinc => {
+                // The interpolator looks at `SINC_DEPTH` frames on either side of the
+                // current position.
+                const SINC_DEPTH: usize = 16;
+                let ring_buffer = dasp::ring_buffer::Fixed::from([[0i16; 2]; SINC_DEPTH * 2]);
+                let interpolator = dasp::interpolate::sinc::Sinc::new(ring_buffer);
+                Box::new(ConverterStream(
+                    dasp::signal::interpolate::Converter::from_hz_to_hz(
+                        stream,
+                        interpolator,
+                        sample_rate,
+                        self.output_sample_rate.into(),
+                    ),
+                ))
+            }
+        }
     }
 
     /// Creates a `Stream` for an "event" that decodes and resamples the audio stream to the
@@ -369,7 +435,7 @@ impl AudioMixer {
             let envelope_signal = EnvelopeSignal::new(&envelope[..], self.output_sample_rate);
             Ok(Box::new(MulAmpStream::new(stream, envelope_signal)) as Box<dyn Stream>)
         } else {
-            Ok(Box::new(stream) as Box<dyn Stream>)
+            Ok(stream)
         }
     }
 
@@ -388,8 +454,7 @@ impl AudioMixer {
 
         // Convert the `Decoder` to a `Stream`, and resample it to output sample rate.
         let stream = DecoderStream::new(decoder);
-        let stream = self.make_resampler(stream);
-        Ok(Box::new(stream))
+        Ok(self.make_resampler(stream))
     }
 
     /// Creates a `Stream` that decodes and resamples a timeline "stream" sound.
@@ -403,8 +468,7 @@ impl AudioMixer {
 
         // Convert the `Decoder` to a `Stream`, and resample it to the output sample rate.
         let stream = DecoderStream::new(clip_stream_decoder);
-        let stream = Box::new(self.make_resampler(stream));
-        Ok(stream)
+        Ok(self.make_resampler(stream))
     }
 
     /// Callback to the audio thread.
@@ -780,7 +844,8 @@ impl EventSoundStream {
 
         let mut stream = Self {
             decoder,
-            num_loops: settings.num_loops,
+            // A loop count of 0 plays the sound once, like a loop count of 1.
+            num_loops: settings.num_loops.max(1),
             start_sample_frame,
             end_sample_frame: Some(end_sample_frame),
             cur_sample_frame: start_sample_frame,
@@ -1130,3 +1195,127 @@ macro_rules! impl_audio_mixer_backend {
         }
     };
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dasp::signal::Signal;
+
+    /// Plays an uncompressed mono sound at 44.1 kHz, and returns its samples.
+    fn play(data: &[u8], is_16_bit: bool, settings: &swf::SoundInfo) -> Vec<i16> {
+        let num_sample_frames = if is_16_bit {
+            data.len() / 2
+        } else {
+            data.len()
+        };
+        let data = Cursor::new(ArcAsRef(data.into()));
+        let decoder = Box::new(PcmDecoder::new(data, false, 44100, is_16_bit));
+        let mut stream =
+            EventSoundStream::new_with_settings(decoder, settings, num_sample_frames as u32, 0);
+        let mut samples = vec![];
+        while !stream.is_exhausted() {
+            let [left, _] = stream.next();
+            if !stream.is_exhausted() {
+                samples.push(left);
+            }
+        }
+        samples
+    }
+
+    fn sound_info(in_sample: Option<u32>, num_loops: u16) -> swf::SoundInfo {
+        swf::SoundInfo {
+            event: swf::SoundEvent::Event,
+            in_sample,
+            out_sample: None,
+            num_loops,
+            envelope: None,
+        }
+    }
+
+    const SAMPLES_16_BIT: [u8; 6] = [1, 0, 2, 0, 3, 0];
+
+    #[test]
+    fn zero_loops_play_once() {
+        assert_eq!(play(&SAMPLES_16_BIT, true, &sound_info(None, 0)), [1, 2, 3]);
+    }
+
+    #[test]
+    fn loops_repeat_the_sound() {
+        assert_eq!(
+            play(&SAMPLES_16_BIT, true, &sound_info(None, 3)),
+            [1, 2, 3, 1, 2, 3, 1, 2, 3]
+        );
+    }
+
+    #[test]
+    fn loops_start_at_the_in_point() {
+        assert_eq!(
+            play(&SAMPLES_16_BIT, true, &sound_info(Some(1), 2)),
+            [2, 3, 2, 3]
+        );
+        // 8-bit samples are unsigned, centered on 127.
+        assert_eq!(
+            play(&[127, 128, 129, 130], false, &sound_info(Some(2), 2)),
+            [256, 384, 256, 384]
+        );
+    }
+
+    /// The length of the sine wave used by the resampler tests, in sample frames.
+    /// This is a whole number of periods, so the wave ends without a jump to silence.
+    const SINE_FRAMES: usize = 2200;
+
+    /// Resamples a 441 Hz mono sine wave from 22.05 kHz to 44.1 kHz, and returns its samples.
+    fn resample_sine(quality: ResamplerQuality) -> Vec<i16> {
+        let data: Vec<u8> = (0..SINE_FRAMES)
+            .flat_map(|i| {
+                let phase = i as f64 * std::f64::consts::TAU / 50.0;
+                ((phase.sin() * 10000.0) as i16).to_le_bytes()
+            })
+            .collect();
+        let decoder = PcmDecoder::new(Cursor::new(data), false, 22050, true);
+        let mut mixer = AudioMixer::new(2, 44100);
+        mixer.set_resampler_quality(quality);
+        let mut stream = mixer.make_resampler(DecoderStream::new(decoder));
+        let mut samples = vec![];
+        while !stream.is_exhausted() && samples.len() < SINE_FRAMES * 4 {
+            let [left, _] = stream.next();
+            samples.push(left);
+        }
+        samples
+    }
+
+    fn assert_resampled_sine(quality: ResamplerQuality) {
+        let samples = resample_sine(quality);
+        // Doubling the sample rate doubles the number of sample frames, give or take
+        // the few frames buffered by the interpolator.
+        assert!(
+            samples.len().abs_diff(SINE_FRAMES * 2) <= 4,
+            "{quality:?} resampler output {} sample frames",
+            samples.len()
+        );
+
+        // Away from the edges, the wave stays smooth and keeps its amplitude.
+        let middle = &samples[64..samples.len() - 64];
+        let max_step = middle
+            .windows(2)
+            .map(|w| (i32::from(w[1]) - i32::from(w[0])).abs())
+            .max()
+            .unwrap();
+        assert!(
+            max_step < 1000,
+            "{quality:?} resampler output jumps by {max_step}"
+        );
+        let peak = middle.iter().map(|s| i32::from(*s).abs()).max().unwrap();
+        assert!(peak > 8000, "{quality:?} resampler output peaks at {peak}");
+    }
+
+    #[test]
+    fn linear_resampler_output() {
+        assert_resampled_sine(ResamplerQuality::Linear);
+    }
+
+    #[test]
+    fn sinc_resampler_output() {
+        assert_resampled_sine(ResamplerQuality::Sinc);
+    }
+}
@@ -0,0 +1,133 @@
+//! Local connections, which movies send messages to each other through,
+//! whether they are played by the same player or not.
+
+use flash_lso::types::{AMFVersion, Element, Lso, Value as AmfValue};
+use std::collections::HashSet;
+use std::rc::Rc;
+
+/// A message sent with `LocalConnection.send`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LocalConnectionMessage {
+    /// The full name of the connection that the message is sent to,
+    /// including the domain it is prefixed with.
+    pub connection_name: String,
+
+    /// The domain of the movie that sent the message.
+    pub sender_domain: String,
+
+    /// The method to call on the `LocalConnection` object listening to the connection.
+    pub method_name: String,
+
+    pub arguments: Vec<AmfValue>,
+}
+
+impl LocalConnectionMessage {
+    /// Encodes the message with AMF0, to send it to another process.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let arguments = self.arguments.iter().cloned().map(Rc::new).collect();
+        let mut lso = Lso::new(
+            vec![
+                Element::new(
+                    "connectionName",
+                    AmfValue::String(self.connection_name.clone()),
+                ),
+                Element::new("senderDomain", AmfValue::String(self.sender_domain.clone())),
+                Element::new("methodName", AmfValue::String(self.method_name.clone())),
+                Element::new("arguments", AmfValue::StrictArray(arguments)),
+            ],
+            "localConnection",
+            AMFVersion::AMF0,
+        );
+        flash_lso::write::write_to_bytes(&mut lso).unwrap_or_default()
+    }
+
+    /// Decodes a message encoded with `to_bytes`.
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let lso = flash_lso::read::Reader::default().parse(bytes).ok()?;
+        let find = |name: &str| {
+            lso.body
+                .iter()
+                .find(|element| element.name == name)
+                .map(|element| element.value())
+        };
+        let string = |name: &str| match find(name) {
+            Some(AmfValue::String(value)) => Some(value.clone()),
+            _ => None,
+        };
+        let arguments = match find("arguments") {
+            Some(AmfValue::StrictArray(values)) => {
+                values.iter().map(|value| (**value).clone()).collect()
+            }
+            _ => return None,
+        };
+
+        Some(Self {
+            connection_name: string("connectionName")?,
+            sender_domain: string("senderDomain")?,
+            method_name: string("methodName")?,
+            arguments,
+        })
+    }
+}
+
+/// Delivers the messages of local connections.
+///
+/// Messages are received asynchronously: the player calls `poll` once per frame.
+pub trait LocalConnectionBackend {
+    /// Starts listening for the messages sent to `connection_name`.
+    ///
+    /// Returns `false` if a movie already listens to it, in this player or another one.
+    fn connect(&mut self, connection_name: &str) -> bool;
+
+    /// Stops listening for the messages sent to `connection_name`.
+    fn close(&mut self, connection_name: &str);
+
+    /// Sends a message to the movie listening to its connection.
+    ///
+    /// Returns `false` if no movie listens to it.
+    fn send(&mut self, message: LocalConnectionMessage) -> bool;
+
+    /// Returns the messages sent to the connections that this player listens
+    /// to since the last call.
+    fn poll(&mut self) -> Vec<LocalConnectionMessage>;
+}
+
+/// Local connection backend that only delivers messages between the movies
+/// of the same player.
+#[derive(Default)]
+pub struct MemoryLocalConnectionBackend {
+    connections: HashSet<String>,
+    messages: Vec<LocalConnectionMessage>,
+}
+
+impl MemoryLocalConnectionBackend {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_connected(&self, connection_name: &str) -> bool {
+        self.connections.contains(connection_name)
+    }
+}
+
+impl LocalConnectionBackend for MemoryLocalConnectionBackend {
+    fn connect(&mut self, connection_name: &str) -> bool {
+        self.connections.insert(connection_name.to_owned())
+    }
+
+    fn close(&mut self, connection_name: &str) {
+        self.connections.remove(connection_name);
+    }
+
+    fn send(&mut self, message: LocalConnectionMessage) -> bool {
+        if !self.is_connected(&message.connection_name) {
+            return false;
+        }
+        self.messages.push(message);
+        true
+    }
+
+    fn poll(&mut self) -> Vec<LocalConnectionMessage> {
+        std::mem::take(&mut self.messages)
+    }
+}
@@ -1,5 +1,17 @@
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::rc::Rc;
+
 pub trait LogBackend {
     fn avm_trace(&self, message: &str);
+
+    /// Logs a trace of the movie at `movie_url`, which loaded movies have
+    /// their own of. Backends that don't tell movies apart log it like
+    /// any other trace.
+    fn movie_trace(&self, movie_url: &str, message: &str) {
+        let _ = movie_url;
+        self.avm_trace(message);
+    }
 }
 
 /// Logging backend that just reroutes traces to the log crate
@@ -22,3 +34,52 @@ impl Default for NullLogBackend {
         NullLogBackend::new()
     }
 }
+
+/// A trace kept by a `TraceBuffer`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TraceEntry {
+    pub movie_url: String,
+    pub message: String,
+}
+
+/// Keeps the latest traces, for debugging tools to show.
+///
+/// Clones share the same traces, so a log backend can fill the buffer
+/// while the frontend reads it.
+#[derive(Clone)]
+pub struct TraceBuffer {
+    entries: Rc<RefCell<VecDeque<TraceEntry>>>,
+    capacity: usize,
+}
+
+impl TraceBuffer {
+    /// Creates a buffer keeping the last `capacity` traces.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            entries: Rc::new(RefCell::new(VecDeque::with_capacity(capacity))),
+            capacity,
+        }
+    }
+
+    pub fn push(&self, movie_url: &str, message: &str) {
+        let mut entries = self.entries.borrow_mut();
+        if entries.len() >= self.capacity {
+            entries.pop_front();
+        }
+        if self.capacity > 0 {
+            entries.push_back(TraceEntry {
+                movie_url: movie_url.to_owned(),
+                message: message.to_owned(),
+            });
+        }
+    }
+
+    /// The kept traces, from the oldest to the latest.
+    pub fn entries(&self) -> Vec<TraceEntry> {
+        self.entries.borrow().iter().cloned().collect()
+    }
+
+    pub fn clear(&self) {
+        self.entries.borrow_mut().clear();
+    }
+}
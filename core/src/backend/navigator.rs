@@ -12,7 +12,7 @@ use swf::avm1::types::SendVarsMethod;
 use url::Url;
 
 /// Enumerates all possible navigation methods.
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, Debug)]
 pub enum NavigationMethod {
     /// Indicates that navigation should generate a GET request.
     Get,
@@ -144,6 +144,10 @@ pub struct Response {
 /// result of type `Result<T, E>`.
 pub type OwnedFuture<T, E> = Pin<Box<dyn Future<Output = Result<T, E>> + 'static>>;
 
+/// Called with how many bytes of a response body arrived so far, and how many there are
+/// in total, while the body is downloaded.
+pub type ProgressCallback = Box<dyn FnMut(usize, usize)>;
+
 /// A backend interacting with a browser environment.
 pub trait NavigatorBackend {
     /// Cause a browser navigation to a given URL.
@@ -178,6 +182,28 @@ pub trait NavigatorBackend {
     /// Fetch data and return it some time in the future.
     fn fetch(&self, request: Request) -> OwnedFuture<Response, Error>;
 
+    /// Fetch data like `fetch`, reporting the progress of the download to `on_progress`
+    /// as the body arrives.
+    ///
+    /// `on_progress` may only be called while the returned future is polled. The end of
+    /// the download doesn't need to be reported, as the response itself does that. By
+    /// default, no progress is reported.
+    fn fetch_with_progress(
+        &self,
+        request: Request,
+        _on_progress: ProgressCallback,
+    ) -> OwnedFuture<Response, Error> {
+        self.fetch(request)
+    }
+
+    /// The absolute URL that `fetch` would request for the given URL, if it's known.
+    ///
+    /// This is what the security sandbox is checked against, so relative URLs must be
+    /// resolved the same way `fetch` resolves them.
+    fn absolute_url(&self, url: &str) -> Option<Url> {
+        Url::parse(url).ok()
+    }
+
     /// Arrange for a future to be run at some point in the... well, future.
     ///
     /// This function must be called to ensure a future is actually computed.
@@ -211,6 +237,12 @@ impl NullExecutor {
     pub fn run(&mut self) {
         self.0.run();
     }
+
+    /// Runs all futures until none of them can make progress, without
+    /// waiting for the ones that are still pending.
+    pub fn run_until_stalled(&mut self) {
+        self.0.run_until_stalled();
+    }
 }
 
 impl Default for NullExecutor {
@@ -248,6 +280,8 @@ impl NullExecutor {
     }
 
     pub fn run(&mut self) {}
+
+    pub fn run_until_stalled(&mut self) {}
 }
 
 #[cfg(target_family = "wasm")]
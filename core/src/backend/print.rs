@@ -0,0 +1,230 @@
+//! Printing the pages that movies lay out with `PrintJob`.
+
+use flate2::write::ZlibEncoder;
+use flate2::Compression;
+use std::io::Write;
+
+/// How the paper is turned.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PrintOrientation {
+    #[default]
+    Portrait,
+    Landscape,
+}
+
+impl PrintOrientation {
+    /// The name of the orientation, as used by `PrintJob.orientation`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            PrintOrientation::Portrait => "portrait",
+            PrintOrientation::Landscape => "landscape",
+        }
+    }
+}
+
+/// The size of the paper and of the area that can be printed on, in points (1/72 inch).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PageFormat {
+    pub paper_width: u32,
+    pub paper_height: u32,
+    pub page_width: u32,
+    pub page_height: u32,
+    pub orientation: PrintOrientation,
+}
+
+impl Default for PageFormat {
+    /// US Letter paper in portrait, with quarter inch margins.
+    fn default() -> Self {
+        Self {
+            paper_width: 612,
+            paper_height: 792,
+            page_width: 576,
+            page_height: 756,
+            orientation: PrintOrientation::Portrait,
+        }
+    }
+}
+
+/// A page added by a movie, rendered as an image.
+#[derive(Clone, Debug)]
+pub struct PrintPage {
+    /// The size of the page, in points.
+    pub width: f64,
+    pub height: f64,
+
+    /// The opaque RGBA pixels of the page, which are stretched over its size.
+    pub image_width: u32,
+    pub image_height: u32,
+    pub rgba: Vec<u8>,
+}
+
+/// Prints the pages of a `PrintJob`.
+pub trait PrintBackend {
+    /// Asks the user to print, before the movie adds the pages.
+    ///
+    /// Returns the format of the pages, or `None` if the user cancelled.
+    fn start(&mut self) -> Option<PageFormat>;
+
+    /// Prints the pages of a job that `start` accepted.
+    fn send(&mut self, pages: Vec<PrintPage>);
+}
+
+/// Print backend that can't print, as if the user always cancelled.
+#[derive(Default)]
+pub struct NullPrintBackend;
+
+impl NullPrintBackend {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl PrintBackend for NullPrintBackend {
+    fn start(&mut self) -> Option<PageFormat> {
+        tracing::warn!("Printing is not supported");
+        None
+    }
+
+    fn send(&mut self, _pages: Vec<PrintPage>) {}
+}
+
+/// Print backend that turns every job into a PDF document without asking the user,
+/// for headless players.
+pub struct PdfPrintBackend {
+    format: PageFormat,
+    write: Box<dyn FnMut(Vec<u8>)>,
+}
+
+impl PdfPrintBackend {
+    /// Prints pages of `format`, passing each document to `write`.
+    pub fn new(format: PageFormat, write: impl FnMut(Vec<u8>) + 'static) -> Self {
+        Self {
+            format,
+            write: Box::new(write),
+        }
+    }
+}
+
+impl PrintBackend for PdfPrintBackend {
+    fn start(&mut self) -> Option<PageFormat> {
+        Some(self.format)
+    }
+
+    fn send(&mut self, pages: Vec<PrintPage>) {
+        (self.write)(write_pdf(&pages));
+    }
+}
+
+/// Writes pages as a PDF document, with one image per page.
+pub fn write_pdf(pages: &[PrintPage]) -> Vec<u8> {
+    // The catalog and the page tree are the first objects, followed by
+    // the page, its content and its image for each page.
+    let page_ids: Vec<_> = (0..pages.len()).map(|i| 3 + i * 3).collect();
+    let mut objects = vec![
+        b"<< /Type /Catalog /Pages 2 0 R >>".to_vec(),
+        format!(
+            "<< /Type /Pages /Kids [{}] /Count {} >>",
+            page_ids
+                .iter()
+                .map(|id| format!("{id} 0 R"))
+                .collect::<Vec<_>>()
+                .join(" "),
+            pages.len()
+        )
+        .into_bytes(),
+    ];
+    for (page, id) in pages.iter().zip(page_ids) {
+        objects.push(
+            format!(
+                "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] /Contents {} 0 R /Resources << /XObject << /Im {} 0 R >> >> >>",
+                page.width,
+                page.height,
+                id + 1,
+                id + 2
+            )
+            .into_bytes(),
+        );
+
+        // Images are drawn in a unit square, scaled to the page.
+        let content = format!("q {} 0 0 {} 0 0 cm /Im Do Q", page.width, page.height);
+        objects.push(pdf_stream("", content.as_bytes()));
+
+        let rgb: Vec<u8> = page
+            .rgba
+            .chunks_exact(4)
+            .flat_map(|pixel| [pixel[0], pixel[1], pixel[2]])
+            .collect();
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        let _ = encoder.write_all(&rgb);
+        let rgb = encoder.finish().unwrap_or_default();
+        objects.push(pdf_stream(
+            &format!(
+                "/Type /XObject /Subtype /Image /Width {} /Height {} /ColorSpace /DeviceRGB /BitsPerComponent 8 /Filter /FlateDecode",
+                page.image_width, page.image_height
+            ),
+            &rgb,
+        ));
+    }
+
+    let mut pdf = b"%PDF-1.4\n".to_vec();
+    let mut offsets = Vec::with_capacity(objects.len());
+    for (i, object) in objects.iter().enumerate() {
+        offsets.push(pdf.len());
+        pdf.extend_from_slice(format!("{} 0 obj\n", i + 1).as_bytes());
+        pdf.extend_from_slice(object);
+        pdf.extend_from_slice(b"\nendobj\n");
+    }
+    let xref_offset = pdf.len();
+    pdf.extend_from_slice(
+        format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1).as_bytes(),
+    );
+    for offset in offsets {
+        pdf.extend_from_slice(format!("{offset:010} 00000 n \n").as_bytes());
+    }
+    pdf.extend_from_slice(
+        format!(
+            "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{xref_offset}\n%%EOF\n",
+            objects.len() + 1
+        )
+        .as_bytes(),
+    );
+    pdf
+}
+
+fn pdf_stream(entries: &str, data: &[u8]) -> Vec<u8> {
+    let mut stream = format!("<< {entries} /Length {} >>\nstream\n", data.len()).into_bytes();
+    stream.extend_from_slice(data);
+    stream.extend_from_slice(b"\nendstream");
+    stream
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pdf_objects_are_indexed() {
+        let page = PrintPage {
+            width: 576.0,
+            height: 756.0,
+            image_width: 1,
+            image_height: 1,
+            rgba: vec![255, 0, 0, 255],
+        };
+        let pdf = write_pdf(&[page.clone(), page]);
+        assert!(pdf.starts_with(b"%PDF-1.4\n"));
+        assert!(pdf.ends_with(b"%%EOF\n"));
+
+        // The images are compressed, but the rest of the document is text.
+        let xref = pdf.windows(6).rposition(|w| w == b"\nxref\n").unwrap() + 1;
+        let trailer = std::str::from_utf8(&pdf[xref..]).unwrap();
+        let entries: Vec<_> = trailer.lines().skip(3).take(8).collect();
+        for (i, entry) in entries.iter().enumerate() {
+            let offset: usize = entry[..10].parse().unwrap();
+            assert!(pdf[offset..].starts_with(format!("{} 0 obj", i + 1).as_bytes()));
+        }
+        assert!(pdf[..xref]
+            .windows(26)
+            .any(|w| w == b"/Kids [3 0 R 6 0 R] /Count"));
+    }
+}
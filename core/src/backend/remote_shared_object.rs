@@ -0,0 +1,240 @@
+//! Remote shared objects, which movies share with each other through a server
+//! such as Flash Media Server.
+
+use flash_lso::types::Value as AmfValue;
+use std::collections::HashMap;
+
+/// Identifies a connection to a remote shared object, see `RemoteSharedObjectBackend::connect`.
+pub type RemoteSharedObjectHandle = u32;
+
+/// Something that happened to a remote shared object, as reported by
+/// `RemoteSharedObjectBackend::poll`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum RemoteSharedObjectEvent {
+    /// The connection requested with `connect` was established, or failed.
+    Connected {
+        handle: RemoteSharedObjectHandle,
+        success: bool,
+    },
+
+    /// Another client, or the server, changed a property.
+    Changed {
+        handle: RemoteSharedObjectHandle,
+        name: String,
+        value: AmfValue,
+    },
+
+    /// Another client, or the server, deleted a property.
+    Deleted {
+        handle: RemoteSharedObjectHandle,
+        name: String,
+    },
+
+    /// The server accepted a change of a property that was made through this connection.
+    Accepted {
+        handle: RemoteSharedObjectHandle,
+        name: String,
+    },
+
+    /// A client sent a message with `SharedObject.send`, which calls `handler`
+    /// on the shared object.
+    Message {
+        handle: RemoteSharedObjectHandle,
+        handler: String,
+        arguments: Vec<AmfValue>,
+    },
+}
+
+/// Talks to the server that remote shared objects are kept on.
+///
+/// Everything is asynchronous: the outcome of each call is reported by
+/// `poll`, which the player calls once per frame.
+pub trait RemoteSharedObjectBackend {
+    /// Starts connecting to the shared object `name` of the application at `uri`.
+    ///
+    /// Persistent shared objects are kept by the server after every client disconnected.
+    fn connect(&mut self, uri: &str, name: &str, persistent: bool) -> RemoteSharedObjectHandle;
+
+    /// Changes a property of a connected shared object.
+    fn set_property(&mut self, handle: RemoteSharedObjectHandle, name: &str, value: AmfValue);
+
+    /// Deletes a property of a connected shared object.
+    fn delete_property(&mut self, handle: RemoteSharedObjectHandle, name: &str);
+
+    /// Sends a message to every client connected to the shared object, including this one.
+    fn send(&mut self, handle: RemoteSharedObjectHandle, handler: &str, arguments: Vec<AmfValue>);
+
+    fn close(&mut self, handle: RemoteSharedObjectHandle);
+
+    /// Returns what happened since the last call.
+    fn poll(&mut self) -> Vec<RemoteSharedObjectEvent>;
+}
+
+/// Remote shared object backend that fails every connection.
+#[derive(Default)]
+pub struct NullRemoteSharedObjectBackend {
+    next_handle: RemoteSharedObjectHandle,
+    events: Vec<RemoteSharedObjectEvent>,
+}
+
+impl NullRemoteSharedObjectBackend {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl RemoteSharedObjectBackend for NullRemoteSharedObjectBackend {
+    fn connect(&mut self, uri: &str, _name: &str, _persistent: bool) -> RemoteSharedObjectHandle {
+        tracing::warn!("Can't connect to remote shared objects at {}", uri);
+        let handle = self.next_handle;
+        self.next_handle += 1;
+        self.events.push(RemoteSharedObjectEvent::Connected {
+            handle,
+            success: false,
+        });
+        handle
+    }
+
+    fn set_property(&mut self, _handle: RemoteSharedObjectHandle, _name: &str, _value: AmfValue) {}
+
+    fn delete_property(&mut self, _handle: RemoteSharedObjectHandle, _name: &str) {}
+
+    fn send(
+        &mut self,
+        _handle: RemoteSharedObjectHandle,
+        _handler: &str,
+        _arguments: Vec<AmfValue>,
+    ) {
+    }
+
+    fn close(&mut self, _handle: RemoteSharedObjectHandle) {}
+
+    fn poll(&mut self) -> Vec<RemoteSharedObjectEvent> {
+        std::mem::take(&mut self.events)
+    }
+}
+
+/// Remote shared object backend that acts as its own server, sharing the
+/// objects between the connections made through it.
+///
+/// This is useful for tests, and for running multiplayer content locally.
+#[derive(Default)]
+pub struct MemoryRemoteSharedObjectBackend {
+    /// The properties of each shared object, by URI and name.
+    objects: HashMap<(String, String), HashMap<String, AmfValue>>,
+
+    /// The shared object that each open connection is connected to.
+    connections: HashMap<RemoteSharedObjectHandle, (String, String)>,
+
+    next_handle: RemoteSharedObjectHandle,
+    events: Vec<RemoteSharedObjectEvent>,
+}
+
+impl MemoryRemoteSharedObjectBackend {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The connections to the same shared object as `handle`, including itself.
+    fn peers(&self, handle: RemoteSharedObjectHandle) -> Vec<RemoteSharedObjectHandle> {
+        let Some(key) = self.connections.get(&handle) else {
+            return vec![];
+        };
+        let mut peers: Vec<_> = self
+            .connections
+            .iter()
+            .filter(|(_, other)| *other == key)
+            .map(|(peer, _)| *peer)
+            .collect();
+        peers.sort_unstable();
+        peers
+    }
+}
+
+impl RemoteSharedObjectBackend for MemoryRemoteSharedObjectBackend {
+    fn connect(&mut self, uri: &str, name: &str, _persistent: bool) -> RemoteSharedObjectHandle {
+        let handle = self.next_handle;
+        self.next_handle += 1;
+
+        let key = (uri.to_owned(), name.to_owned());
+        let properties = self.objects.entry(key.clone()).or_default();
+        self.events.push(RemoteSharedObjectEvent::Connected {
+            handle,
+            success: true,
+        });
+        for (name, value) in properties.iter() {
+            self.events.push(RemoteSharedObjectEvent::Changed {
+                handle,
+                name: name.clone(),
+                value: value.clone(),
+            });
+        }
+        self.connections.insert(handle, key);
+        handle
+    }
+
+    fn set_property(&mut self, handle: RemoteSharedObjectHandle, name: &str, value: AmfValue) {
+        let Some(key) = self.connections.get(&handle) else {
+            return;
+        };
+        if let Some(properties) = self.objects.get_mut(key) {
+            properties.insert(name.to_owned(), value.clone());
+        }
+
+        for peer in self.peers(handle) {
+            self.events.push(if peer == handle {
+                RemoteSharedObjectEvent::Accepted {
+                    handle,
+                    name: name.to_owned(),
+                }
+            } else {
+                RemoteSharedObjectEvent::Changed {
+                    handle: peer,
+                    name: name.to_owned(),
+                    value: value.clone(),
+                }
+            });
+        }
+    }
+
+    fn delete_property(&mut self, handle: RemoteSharedObjectHandle, name: &str) {
+        let Some(key) = self.connections.get(&handle) else {
+            return;
+        };
+        if let Some(properties) = self.objects.get_mut(key) {
+            properties.remove(name);
+        }
+
+        for peer in self.peers(handle) {
+            self.events.push(if peer == handle {
+                RemoteSharedObjectEvent::Accepted {
+                    handle,
+                    name: name.to_owned(),
+                }
+            } else {
+                RemoteSharedObjectEvent::Deleted {
+                    handle: peer,
+                    name: name.to_owned(),
+                }
+            });
+        }
+    }
+
+    fn send(&mut self, handle: RemoteSharedObjectHandle, handler: &str, arguments: Vec<AmfValue>) {
+        for peer in self.peers(handle) {
+            self.events.push(RemoteSharedObjectEvent::Message {
+                handle: peer,
+                handler: handler.to_owned(),
+                arguments: arguments.clone(),
+            });
+        }
+    }
+
+    fn close(&mut self, handle: RemoteSharedObjectHandle) {
+        self.connections.remove(&handle);
+    }
+
+    fn poll(&mut self) -> Vec<RemoteSharedObjectEvent> {
+        std::mem::take(&mut self.events)
+    }
+}
@@ -0,0 +1,83 @@
+//! TCP connections that movies open to servers, with `XMLSocket`.
+
+/// Identifies a connection, see `SocketBackend::connect`.
+pub type SocketHandle = u32;
+
+/// Which servers a movie may connect to.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SocketPermission {
+    /// The movie may connect to any server.
+    Trusted,
+
+    /// The server must allow the connection with a socket policy file, which
+    /// grants access to the domain of the movie (`None` for local movies, which
+    /// only `domain="*"` grants access to).
+    PolicyFile { domain: Option<String> },
+}
+
+/// Something that happened to a connection, as reported by `SocketBackend::poll`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SocketEvent {
+    /// The connection requested with `connect` was established, or failed.
+    Connected { handle: SocketHandle, success: bool },
+
+    /// Bytes were received from the server.
+    Data { handle: SocketHandle, data: Vec<u8> },
+
+    /// The server closed the connection, or it was lost.
+    Closed { handle: SocketHandle },
+}
+
+/// Opens TCP connections to servers.
+///
+/// Everything is asynchronous: the outcome of each call is reported by
+/// `poll`, which the player calls once per frame.
+pub trait SocketBackend {
+    /// Starts connecting to `port` of `host`, if `permission` allows it.
+    ///
+    /// A connection that isn't allowed fails like one the server refused.
+    fn connect(&mut self, host: &str, port: u16, permission: SocketPermission) -> SocketHandle;
+
+    /// Sends bytes through an established connection.
+    fn send(&mut self, handle: SocketHandle, data: Vec<u8>);
+
+    /// Closes a connection. No event is reported for it afterwards.
+    fn close(&mut self, handle: SocketHandle);
+
+    /// Returns what happened since the last call.
+    fn poll(&mut self) -> Vec<SocketEvent>;
+}
+
+/// Socket backend that fails every connection.
+#[derive(Default)]
+pub struct NullSocketBackend {
+    next_handle: SocketHandle,
+    events: Vec<SocketEvent>,
+}
+
+impl NullSocketBackend {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl SocketBackend for NullSocketBackend {
+    fn connect(&mut self, host: &str, port: u16, _permission: SocketPermission) -> SocketHandle {
+        tracing::warn!("Can't connect to {}:{}", host, port);
+        let handle = self.next_handle;
+        self.next_handle += 1;
+        self.events.push(SocketEvent::Connected {
+            handle,
+            success: false,
+        });
+        handle
+    }
+
+    fn send(&mut self, _handle: SocketHandle, _data: Vec<u8>) {}
+
+    fn close(&mut self, _handle: SocketHandle) {}
+
+    fn poll(&mut self) -> Vec<SocketEvent> {
+        std::mem::take(&mut self.events)
+    }
+}
@@ -1,4 +1,50 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+
+/// The kind and size of an entry of the file sandbox, see `StorageBackend::file_info`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FileInfo {
+    pub is_directory: bool,
+
+    /// The size of a file in bytes, which is 0 for directories.
+    pub size: u64,
+}
+
+/// How a database is opened, see `StorageBackend::open_database`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SqlOpenMode {
+    /// Opens the database for reading and writing, creating it if needed.
+    Create,
+
+    /// Opens an existing database for reading and writing.
+    Update,
+
+    /// Opens an existing database for reading only.
+    Read,
+}
+
+/// A value bound to, or returned by, an SQL statement.
+#[derive(Clone, Debug, PartialEq)]
+pub enum SqlValue {
+    Null,
+    Integer(i64),
+    Real(f64),
+    Text(String),
+    Blob(Vec<u8>),
+}
+
+/// The outcome of an SQL statement.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SqlResult {
+    /// The names of the columns of the returned rows.
+    pub columns: Vec<String>,
+
+    pub rows: Vec<Vec<SqlValue>>,
+
+    /// The number of rows changed by an `INSERT`, `UPDATE` or `DELETE` statement.
+    pub rows_affected: u64,
+
+    pub last_insert_row_id: i64,
+}
 
 pub trait StorageBackend {
     fn get(&self, name: &str) -> Option<Vec<u8>>;
@@ -10,11 +56,94 @@ pub trait StorageBackend {
     }
 
     fn remove_key(&mut self, name: &str);
+
+    /// Returns the total size of the entries whose names start with `prefix`, in bytes.
+    ///
+    /// This is how much of its storage quota a domain uses, with a prefix of `domain/`.
+    /// Backends that can't list their entries report nothing as used.
+    fn get_disk_usage(&self, _prefix: &str) -> u64 {
+        0
+    }
+
+    /// Reads a file of the sandbox available to AIR content through `flash.filesystem`.
+    ///
+    /// Paths of the file sandbox are relative to its root, and use `/` as separator.
+    /// Backends without a file sandbox don't need to implement this.
+    fn read_file(&self, _path: &str) -> Option<Vec<u8>> {
+        None
+    }
+
+    /// Writes a file of the sandbox, creating its parent directories as needed.
+    fn write_file(&mut self, _path: &str, _data: &[u8]) -> bool {
+        false
+    }
+
+    /// Returns whether a path of the sandbox is a file or a directory,
+    /// or `None` if it doesn't exist.
+    fn file_info(&self, _path: &str) -> Option<FileInfo> {
+        None
+    }
+
+    /// Lists the names of the entries of a directory of the sandbox.
+    fn list_directory(&self, _path: &str) -> Option<Vec<String>> {
+        None
+    }
+
+    /// Creates a directory of the sandbox, along with its parent directories.
+    fn create_directory(&mut self, _path: &str) -> bool {
+        false
+    }
+
+    /// Deletes a file or a directory of the sandbox.
+    ///
+    /// Directories that aren't empty are only deleted if `recursive` is set.
+    fn delete_file(&mut self, _path: &str, _recursive: bool) -> bool {
+        false
+    }
+
+    /// Reads the `EncryptedLocalStore` of AIR content, identified by the URL of its movie.
+    ///
+    /// The store isn't encrypted, so the backend should at least keep it private to the user.
+    /// Backends without persistent storage don't need to implement this.
+    fn get_encrypted_store(&self, _content_id: &str) -> Option<Vec<u8>> {
+        None
+    }
+
+    /// Replaces the `EncryptedLocalStore` of AIR content.
+    fn put_encrypted_store(&mut self, _content_id: &str, _data: &[u8]) -> bool {
+        false
+    }
+
+    /// Opens an SQLite database of the file sandbox, or an in-memory one if `path` is `None`,
+    /// for the `flash.data` API of AIR content.
+    ///
+    /// Returns a handle to the database, which stays open until `close_database` is called.
+    /// Backends without SQLite support don't need to implement this.
+    fn open_database(&mut self, _path: Option<&str>, _mode: SqlOpenMode) -> Result<u32, String> {
+        Err("Databases are not supported".to_string())
+    }
+
+    /// Runs a statement against an open database.
+    ///
+    /// Parameters are either named (e.g. `:name`), or the 0-based index of a `?` parameter.
+    fn execute_sql(
+        &mut self,
+        _database: u32,
+        _sql: &str,
+        _parameters: &[(String, SqlValue)],
+    ) -> Result<SqlResult, String> {
+        Err("Databases are not supported".to_string())
+    }
+
+    fn close_database(&mut self, _database: u32) {}
 }
 
 #[derive(Default)]
 pub struct MemoryStorageBackend {
     map: HashMap<String, Vec<u8>>,
+    files: HashMap<String, Vec<u8>>,
+    directories: HashSet<String>,
+    encrypted_stores: HashMap<String, Vec<u8>>,
 }
 
 impl MemoryStorageBackend {
@@ -36,4 +165,117 @@ impl StorageBackend for MemoryStorageBackend {
     fn remove_key(&mut self, name: &str) {
         self.map.remove(name);
     }
+
+    fn get_disk_usage(&self, prefix: &str) -> u64 {
+        self.map
+            .iter()
+            .filter(|(name, _)| name.starts_with(prefix))
+            .map(|(_, value)| value.len() as u64)
+            .sum()
+    }
+
+    fn read_file(&self, path: &str) -> Option<Vec<u8>> {
+        self.files.get(path.trim_matches('/')).cloned()
+    }
+
+    fn write_file(&mut self, path: &str, data: &[u8]) -> bool {
+        let path = path.trim_matches('/');
+        if path.is_empty() || self.directories.contains(path) {
+            return false;
+        }
+        if let Some((parent, _)) = path.rsplit_once('/') {
+            self.create_directory(parent);
+        }
+        self.files.insert(path.to_string(), data.to_vec());
+        true
+    }
+
+    fn file_info(&self, path: &str) -> Option<FileInfo> {
+        let path = path.trim_matches('/');
+        if let Some(data) = self.files.get(path) {
+            Some(FileInfo {
+                is_directory: false,
+                size: data.len() as u64,
+            })
+        } else if path.is_empty() || self.directories.contains(path) {
+            Some(FileInfo {
+                is_directory: true,
+                size: 0,
+            })
+        } else {
+            None
+        }
+    }
+
+    fn list_directory(&self, path: &str) -> Option<Vec<String>> {
+        let path = path.trim_matches('/');
+        if !self.file_info(path)?.is_directory {
+            return None;
+        }
+
+        let prefix = if path.is_empty() {
+            String::new()
+        } else {
+            format!("{path}/")
+        };
+        let mut entries: Vec<String> = self
+            .files
+            .keys()
+            .chain(self.directories.iter())
+            .filter_map(|entry| entry.strip_prefix(&prefix))
+            .filter(|name| !name.contains('/'))
+            .map(str::to_string)
+            .collect();
+        entries.sort();
+        Some(entries)
+    }
+
+    fn create_directory(&mut self, path: &str) -> bool {
+        let path = path.trim_matches('/');
+        if path.is_empty() {
+            return true;
+        }
+        let ancestors = path.match_indices('/').map(|(end, _)| &path[..end]);
+        for directory in ancestors.chain(std::iter::once(path)) {
+            if self.files.contains_key(directory) {
+                return false;
+            }
+            self.directories.insert(directory.to_string());
+        }
+        true
+    }
+
+    fn delete_file(&mut self, path: &str, recursive: bool) -> bool {
+        let path = path.trim_matches('/');
+        if self.files.remove(path).is_some() {
+            return true;
+        }
+        if !self.directories.contains(path) {
+            return false;
+        }
+
+        let prefix = format!("{path}/");
+        let is_empty = !self
+            .files
+            .keys()
+            .chain(self.directories.iter())
+            .any(|entry| entry.starts_with(&prefix));
+        if !is_empty && !recursive {
+            return false;
+        }
+        self.files.retain(|file, _| !file.starts_with(&prefix));
+        self.directories
+            .retain(|directory| directory != path && !directory.starts_with(&prefix));
+        true
+    }
+
+    fn get_encrypted_store(&self, content_id: &str) -> Option<Vec<u8>> {
+        self.encrypted_stores.get(content_id).cloned()
+    }
+
+    fn put_encrypted_store(&mut self, content_id: &str, data: &[u8]) -> bool {
+        self.encrypted_stores
+            .insert(content_id.to_string(), data.to_vec());
+        true
+    }
 }
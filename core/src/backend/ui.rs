@@ -1,4 +1,5 @@
 use crate::events::{KeyCode, PlayerEvent};
+use crate::i18n::{LanguageIdentifier, FALLBACK_LANGUAGE};
 use std::borrow::Cow;
 use std::collections::HashSet;
 
@@ -31,6 +32,80 @@ pub trait UiBackend {
 
     // Only used on web.
     fn open_virtual_keyboard(&self);
+
+    /// Quits the application with the given exit code, as requested by AIR content
+    /// through `NativeApplication.exit`.
+    fn exit(&mut self, code: i32);
+
+    /// Opens Ruffle's equivalent of the Flash Player settings on the given panel,
+    /// as requested by content through `Security.showSettings`.
+    ///
+    /// The settings of the movie's domain can be read and changed through
+    /// `Player::domain_settings` and `Player::set_domain_settings`.
+    fn display_settings_panel(&mut self, panel: SettingsPanel);
+
+    /// Asks the user whether to abort a script that has been running for
+    /// longer than the script timeout, which would otherwise freeze the player.
+    ///
+    /// `message` is the question to ask, in the language of the user.
+    /// Returns `true` if the script should be aborted, or `false` to let it
+    /// continue for another timeout period.
+    fn display_script_timeout_prompt(&mut self, message: &str) -> bool;
+
+    /// The language of the user, which the text of the player is shown in.
+    ///
+    /// This is asked each time text is shown, so the language can be changed at any time.
+    fn language(&self) -> LanguageIdentifier;
+}
+
+/// A panel of the Flash Player settings.
+/// Equivalent to AS3 `SecurityPanel`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SettingsPanel {
+    /// Whether the movie may use the camera and microphone.
+    Privacy,
+
+    /// How much data the movie may store on the user's computer.
+    LocalStorage,
+
+    Microphone,
+
+    Camera,
+
+    /// Playback settings, such as muting the movie.
+    Display,
+
+    /// The settings of every domain.
+    SettingsManager,
+}
+
+impl SettingsPanel {
+    /// The panel for a name of AS3 `SecurityPanel`.
+    ///
+    /// `"default"` is supposed to be the panel that was open last, but Ruffle
+    /// always opens the privacy panel.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "default" | "privacy" => SettingsPanel::Privacy,
+            "localStorage" => SettingsPanel::LocalStorage,
+            "microphone" => SettingsPanel::Microphone,
+            "camera" => SettingsPanel::Camera,
+            "display" => SettingsPanel::Display,
+            "settingsManager" => SettingsPanel::SettingsManager,
+            _ => return None,
+        })
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            SettingsPanel::Privacy => "privacy",
+            SettingsPanel::LocalStorage => "localStorage",
+            SettingsPanel::Microphone => "microphone",
+            SettingsPanel::Camera => "camera",
+            SettingsPanel::Display => "display",
+            SettingsPanel::SettingsManager => "settingsManager",
+        }
+    }
 }
 
 /// A mouse cursor icon displayed by the Flash Player.
@@ -153,6 +228,18 @@ impl UiBackend for NullUiBackend {
     fn message(&self, _message: &str) {}
 
     fn open_virtual_keyboard(&self) {}
+
+    fn exit(&mut self, _code: i32) {}
+
+    fn display_settings_panel(&mut self, _panel: SettingsPanel) {}
+
+    fn display_script_timeout_prompt(&mut self, _message: &str) -> bool {
+        true
+    }
+
+    fn language(&self) -> LanguageIdentifier {
+        FALLBACK_LANGUAGE
+    }
 }
 
 impl Default for NullUiBackend {
@@ -237,6 +237,7 @@ mod wrapper {
     use ruffle_render::backend::RenderBackend;
     use ruffle_render::bitmap::{BitmapHandle, PixelRegion};
     use ruffle_render::commands::CommandHandler;
+    use ruffle_render::transform::Transform;
     use std::cell::Ref;
 
     use super::{copy_pixels_to_bitmapdata, BitmapData, DirtyState};
@@ -420,7 +421,12 @@ mod wrapper {
             self.0.write(mc).avm2_object = Some(object)
         }
 
-        pub fn render(&self, smoothing: bool, context: &mut RenderContext<'_, 'gc>) {
+        pub fn render(
+            &self,
+            smoothing: bool,
+            transform: Transform,
+            context: &mut RenderContext<'_, 'gc>,
+        ) {
             let mut inner_bitmap_data = self.0.write(context.gc_context);
             if inner_bitmap_data.disposed() {
                 return;
@@ -433,9 +439,7 @@ mod wrapper {
                 .bitmap_handle(context.renderer)
                 .expect("Missing bitmap handle");
 
-            context
-                .commands
-                .render_bitmap(handle, context.transform_stack.transform(), smoothing);
+            context.commands.render_bitmap(handle, transform, smoothing);
         }
 
         pub fn can_read(&self, read_area: PixelRegion) -> bool {
@@ -8,7 +8,7 @@ use crate::bitmap::turbulence::Turbulence;
 use crate::context::{RenderContext, UpdateContext};
 use crate::display_object::TDisplayObject;
 use gc_arena::MutationContext;
-use ruffle_render::bitmap::PixelRegion;
+use ruffle_render::bitmap::{BitmapHandle, PixelRegion};
 use ruffle_render::commands::{CommandHandler, CommandList};
 use ruffle_render::filters::Filter;
 use ruffle_render::matrix::Matrix;
@@ -1464,6 +1464,7 @@ pub fn draw<'gc>(
         library: context.library,
         transform_stack: &mut transform_stack,
         is_offscreen: true,
+        frame_interpolation: None,
         stage: context.stage,
     };
 
@@ -1493,7 +1494,8 @@ pub fn draw<'gc>(
 
     match &mut source {
         IBitmapDrawable::BitmapData(data) => {
-            data.render(smoothing, &mut render_context);
+            let transform = render_context.transform_stack.transform();
+            data.render(smoothing, transform, &mut render_context);
         }
         IBitmapDrawable::DisplayObject(object) => {
             // Note that we do *not* use `render_base`,
@@ -1542,6 +1544,50 @@ pub fn draw<'gc>(
     }
 }
 
+/// Copies the back buffer of a Stage3D context into `target`, as used by
+/// `Context3D.drawToBitmapData`.
+///
+/// The buffer is drawn at the top-left corner of `target` and cropped to fit.
+/// Unlike `draw`, the existing pixels are replaced rather than blended with.
+pub fn draw_stage3d_buffer<'gc>(
+    context: &mut UpdateContext<'_, 'gc>,
+    target: BitmapDataWrapper<'gc>,
+    buffer: BitmapHandle,
+    buffer_width: u32,
+    buffer_height: u32,
+) -> Result<(), BitmapDataDrawError> {
+    let mut dirty_region = PixelRegion::for_whole_size(buffer_width, buffer_height);
+    dirty_region.clamp(target.width(), target.height());
+    if dirty_region.width() == 0 || dirty_region.height() == 0 {
+        return Ok(());
+    }
+
+    // Stage3D buffers are always drawn opaquely, so this overwrites
+    // the destination pixels (alpha included).
+    let mut commands = CommandList::new();
+    commands.render_stage3d(buffer, Transform::default());
+
+    let handle = target.bitmap_handle(context.gc_context, context.renderer);
+    let (target, include_dirty_area) = target.overwrite_cpu_pixels_from_gpu(context.gc_context);
+    let mut write = target.write(context.gc_context);
+    if let Some(old) = include_dirty_area {
+        dirty_region.union(old);
+    }
+
+    let quality = context.stage.quality();
+    let image = context
+        .renderer
+        .render_offscreen(handle, commands, quality, dirty_region);
+
+    match image {
+        Some(sync_handle) => {
+            write.set_gpu_dirty(sync_handle, dirty_region);
+            Ok(())
+        }
+        None => Err(BitmapDataDrawError::Unimplemented),
+    }
+}
+
 pub fn get_vector(
     target: BitmapDataWrapper,
     x: i32,
@@ -0,0 +1,174 @@
+//! Decoding of the bitmaps of a movie ahead of preloading, on a thread pool.
+//!
+//! Bitmap tags are otherwise decompressed one at a time as they are preloaded,
+//! which dominates the load time of asset-heavy movies.
+//!
+//! A background thread decodes the bitmaps in batches, in the order of their tags,
+//! and only runs a few batches ahead of preloading so that a movie's bitmaps
+//! aren't all held decompressed at once.
+
+#![cfg_attr(target_family = "wasm", allow(dead_code))]
+
+use crate::tag_utils::{self, ControlFlow, SwfSlice};
+use ruffle_render::bitmap::Bitmap;
+use ruffle_render::utils::{
+    decode_define_bits_jpeg, decode_define_bits_lossless, glue_tables_to_jpeg,
+    remove_invalid_jpeg_data,
+};
+use std::borrow::Cow;
+#[cfg(not(target_family = "wasm"))]
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use swf::{CharacterId, TagCode};
+
+/// The compressed data of a bitmap definition tag.
+enum BitmapTag<'a> {
+    Jpeg {
+        data: Cow<'a, [u8]>,
+        alpha_data: Option<&'a [u8]>,
+    },
+    Lossless(swf::DefineBitsLossless<'a>),
+}
+
+impl BitmapTag<'_> {
+    fn decode(&self) -> Option<Bitmap> {
+        let result = match self {
+            BitmapTag::Jpeg { data, alpha_data } => decode_define_bits_jpeg(data, *alpha_data),
+            BitmapTag::Lossless(tag) => decode_define_bits_lossless(tag),
+        };
+        // The error is reported when the tag is decoded again during preloading.
+        result.ok()
+    }
+}
+
+/// The bitmaps of a movie, being decoded in the background.
+///
+/// Bitmaps that fail to decode, or that aren't decoded yet when their tag is
+/// preloaded, are decoded again by the preloader.
+pub struct BitmapPredecoder {
+    /// The decoded bitmaps, in the order of their tags.
+    #[cfg(not(target_family = "wasm"))]
+    receiver: Receiver<(CharacterId, Option<Bitmap>)>,
+}
+
+impl BitmapPredecoder {
+    /// Starts decoding all bitmaps defined by a movie.
+    #[cfg(not(target_family = "wasm"))]
+    pub fn start(movie: SwfSlice) -> Self {
+        use rayon::prelude::*;
+
+        // Each batch is decoded in parallel, and the next one is only started
+        // once the previous one fits in the channel.
+        let batch_size = rayon::current_num_threads().max(1);
+        let (sender, receiver) = mpsc::sync_channel(batch_size);
+        let spawned = std::thread::Builder::new()
+            .name("bitmap predecoder".to_string())
+            .spawn(move || {
+                for batch in read_bitmap_tags(&movie).chunks(batch_size) {
+                    let bitmaps: Vec<_> = batch
+                        .par_iter()
+                        .map(|(id, tag)| (*id, tag.decode()))
+                        .collect();
+                    for bitmap in bitmaps {
+                        if sender.send(bitmap).is_err() {
+                            // The movie was unloaded.
+                            return;
+                        }
+                    }
+                }
+            });
+        if let Err(e) = spawned {
+            tracing::warn!("Couldn't start decoding bitmaps in the background: {}", e);
+        }
+        Self { receiver }
+    }
+
+    /// Without threads, bitmaps are decoded as their tag is preloaded.
+    #[cfg(target_family = "wasm")]
+    pub fn start(_movie: SwfSlice) -> Self {
+        Self {}
+    }
+
+    /// Takes the bitmap of the tag being preloaded, if it was already decoded.
+    ///
+    /// Bitmaps are taken in the order of their tags, so any bitmap decoded before the
+    /// requested one belongs to a tag that was preloaded without it, and is dropped.
+    #[cfg(not(target_family = "wasm"))]
+    pub fn take(&mut self, id: CharacterId) -> Option<Bitmap> {
+        loop {
+            match self.receiver.try_recv() {
+                Ok((decoded_id, bitmap)) if decoded_id == id => return bitmap,
+                Ok(_) => continue,
+                Err(TryRecvError::Empty | TryRecvError::Disconnected) => return None,
+            }
+        }
+    }
+
+    #[cfg(target_family = "wasm")]
+    pub fn take(&mut self, _id: CharacterId) -> Option<Bitmap> {
+        None
+    }
+}
+
+/// Reads the bitmap definition tags of a movie, without decompressing them.
+fn read_bitmap_tags(movie: &SwfSlice) -> Vec<(CharacterId, BitmapTag<'_>)> {
+    let mut tags = Vec::new();
+    let mut jpeg_tables: Option<Cow<'_, [u8]>> = None;
+    let mut reader = movie.read_from(0);
+    let _ = tag_utils::decode_tags(&mut reader, |reader, tag_code, _tag_len| {
+        match tag_code {
+            TagCode::JpegTables if jpeg_tables.is_none() => {
+                let data = reader.read_slice_to_end();
+                if !data.is_empty() {
+                    jpeg_tables = Some(remove_invalid_jpeg_data(data));
+                }
+            }
+            TagCode::DefineBits => {
+                let id = reader.read_u16()?;
+                let jpeg_data = reader.read_slice_to_end();
+                let data = glue_tables_to_jpeg(jpeg_data, jpeg_tables.as_deref()).into_owned();
+                let data = Cow::Owned(data);
+                tags.push((
+                    id,
+                    BitmapTag::Jpeg {
+                        data,
+                        alpha_data: None,
+                    },
+                ));
+            }
+            TagCode::DefineBitsJpeg2 => {
+                let id = reader.read_u16()?;
+                let data = Cow::Borrowed(reader.read_slice_to_end());
+                tags.push((
+                    id,
+                    BitmapTag::Jpeg {
+                        data,
+                        alpha_data: None,
+                    },
+                ));
+            }
+            TagCode::DefineBitsJpeg3 | TagCode::DefineBitsJpeg4 => {
+                let id = reader.read_u16()?;
+                let jpeg_len = reader.read_u32()? as usize;
+                if tag_code == TagCode::DefineBitsJpeg4 {
+                    let _deblocking = reader.read_u16()?;
+                }
+                let data = Cow::Borrowed(reader.read_slice(jpeg_len)?);
+                let alpha_data = Some(reader.read_slice_to_end());
+                tags.push((id, BitmapTag::Jpeg { data, alpha_data }));
+            }
+            TagCode::DefineBitsLossless | TagCode::DefineBitsLossless2 => {
+                let version = if tag_code == TagCode::DefineBitsLossless {
+                    1
+                } else {
+                    2
+                };
+                let tag = reader.read_define_bits_lossless(version)?;
+                tags.push((tag.id, BitmapTag::Lossless(tag)));
+            }
+            TagCode::End => return Ok(ControlFlow::Exit),
+            _ => (),
+        }
+        Ok(ControlFlow::Continue)
+    });
+    tags
+}
@@ -0,0 +1,211 @@
+//! Cameras, as exposed by the `Camera` class of both AVMs.
+
+use crate::avm1::Object as Avm1Object;
+use crate::avm2::Object as Avm2Object;
+use crate::backend::video_capture::{CaptureMode, CapturedFrame};
+use crate::context::UpdateContext;
+use gc_arena::{Collect, GcCell, MutationContext};
+use instant::Instant;
+use ruffle_render::bitmap::{Bitmap, BitmapFormat, BitmapInfo, PixelRegion};
+use std::collections::VecDeque;
+use std::fmt;
+use std::time::Duration;
+
+/// A camera of the system, captured by the video capture backend.
+///
+/// Each camera has a single `Camera` object in each AVM, which `Camera.getCamera` returns.
+/// A camera starts capturing once it's attached to a `Video`, whose frames then display
+/// the last frame captured.
+#[derive(Clone, Collect, Copy)]
+#[collect(no_drop)]
+pub struct Camera<'gc>(GcCell<'gc, CameraData<'gc>>);
+
+impl fmt::Debug for Camera<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Camera")
+            .field("ptr", &self.0.as_ptr())
+            .finish()
+    }
+}
+
+#[derive(Clone, Collect)]
+#[collect(no_drop)]
+pub struct CameraData<'gc> {
+    /// The index of the camera in the devices of the backend.
+    index: usize,
+
+    name: String,
+
+    /// The mode requested by the movie.
+    #[collect(require_static)]
+    mode: CaptureMode,
+
+    /// Whether the camera is capturing.
+    capturing: bool,
+
+    /// The last frame captured.
+    #[collect(require_static)]
+    last_frame: Option<BitmapInfo>,
+
+    /// When the frames of the last second were captured, to measure the frame rate.
+    #[collect(require_static)]
+    frame_times: VecDeque<Instant>,
+
+    avm1_object: Option<Avm1Object<'gc>>,
+
+    avm2_object: Option<Avm2Object<'gc>>,
+}
+
+impl<'gc> Camera<'gc> {
+    pub fn new(gc_context: MutationContext<'gc, '_>, index: usize, name: String) -> Self {
+        Self(GcCell::allocate(
+            gc_context,
+            CameraData {
+                index,
+                name,
+                mode: CaptureMode::default(),
+                capturing: false,
+                last_frame: None,
+                frame_times: VecDeque::new(),
+                avm1_object: None,
+                avm2_object: None,
+            },
+        ))
+    }
+
+    pub fn index(self) -> usize {
+        self.0.read().index
+    }
+
+    pub fn name(self) -> String {
+        self.0.read().name.clone()
+    }
+
+    pub fn mode(self) -> CaptureMode {
+        self.0.read().mode
+    }
+
+    /// Changes the mode of the camera, restarting the capture if it's capturing.
+    pub fn set_mode(self, context: &mut UpdateContext<'_, 'gc>, mode: CaptureMode) {
+        let mut write = self.0.write(context.gc_context);
+        write.mode = mode;
+        if write.capturing {
+            context.video_capture.stop(write.index);
+            write.capturing = context.video_capture.start(write.index, mode);
+        }
+    }
+
+    /// Whether the camera is capturing frames.
+    pub fn is_capturing(self) -> bool {
+        self.0.read().capturing
+    }
+
+    /// Starts capturing, if the camera isn't already.
+    pub fn start(self, context: &mut UpdateContext<'_, 'gc>) {
+        let mut write = self.0.write(context.gc_context);
+        if !write.capturing {
+            write.capturing = context.video_capture.start(write.index, write.mode);
+            if !write.capturing {
+                tracing::warn!("Couldn't start capturing camera {}", write.name);
+            }
+        }
+    }
+
+    /// Stops capturing, keeping the last frame.
+    pub fn stop(self, context: &mut UpdateContext<'_, 'gc>) {
+        let mut write = self.0.write(context.gc_context);
+        if write.capturing {
+            context.video_capture.stop(write.index);
+            write.capturing = false;
+            write.frame_times.clear();
+        }
+    }
+
+    /// The rate the camera captured frames at during the last second.
+    pub fn current_fps(self) -> f64 {
+        self.0.read().frame_times.len() as f64
+    }
+
+    /// The last frame captured, which attached `Video` objects display.
+    pub fn last_frame(self) -> Option<BitmapInfo> {
+        self.0.read().last_frame.clone()
+    }
+
+    pub fn avm1_object(self) -> Option<Avm1Object<'gc>> {
+        self.0.read().avm1_object
+    }
+
+    pub fn set_avm1_object(self, gc_context: MutationContext<'gc, '_>, object: Avm1Object<'gc>) {
+        self.0.write(gc_context).avm1_object = Some(object);
+    }
+
+    pub fn avm2_object(self) -> Option<Avm2Object<'gc>> {
+        self.0.read().avm2_object
+    }
+
+    pub fn set_avm2_object(self, gc_context: MutationContext<'gc, '_>, object: Avm2Object<'gc>) {
+        self.0.write(gc_context).avm2_object = Some(object);
+    }
+
+    /// Takes the newest frame from the backend, if the camera captured one.
+    pub fn tick(self, context: &mut UpdateContext<'_, 'gc>) {
+        let mut write = self.0.write(context.gc_context);
+        if !write.capturing {
+            return;
+        }
+        let Some(frame) = context.video_capture.poll_frame(write.index) else {
+            return;
+        };
+
+        let now = Instant::now();
+        write.frame_times.push_back(now);
+        while let Some(time) = write.frame_times.front() {
+            if now.duration_since(*time) < Duration::from_secs(1) {
+                break;
+            }
+            write.frame_times.pop_front();
+        }
+
+        write.last_frame = upload_frame(context, write.last_frame.take(), frame);
+        *context.needs_render = true;
+    }
+}
+
+/// Uploads a captured frame, reusing the texture of the previous frame if it has the same size.
+fn upload_frame(
+    context: &mut UpdateContext<'_, '_>,
+    last_frame: Option<BitmapInfo>,
+    frame: CapturedFrame,
+) -> Option<BitmapInfo> {
+    let CapturedFrame {
+        width,
+        height,
+        rgba,
+    } = frame;
+    let bitmap = Bitmap::new(width, height, BitmapFormat::Rgba, rgba);
+    match last_frame {
+        Some(last_frame)
+            if u32::from(last_frame.width) == width && u32::from(last_frame.height) == height =>
+        {
+            let region = PixelRegion::for_whole_size(width, height);
+            if let Err(e) = context
+                .renderer
+                .update_texture(&last_frame.handle, bitmap, region)
+            {
+                tracing::error!("Couldn't update camera frame: {}", e);
+            }
+            Some(last_frame)
+        }
+        _ => match context.renderer.register_bitmap(bitmap) {
+            Ok(handle) => Some(BitmapInfo {
+                handle,
+                width: width as u16,
+                height: height as u16,
+            }),
+            Err(e) => {
+                tracing::error!("Couldn't register camera frame: {}", e);
+                None
+            }
+        },
+    }
+}
@@ -0,0 +1,224 @@
+//! Captions (subtitles) rendered above the stage, for accessibility of voiced animations.
+//!
+//! Captions are loaded from SubRip (`.srt`) or WebVTT (`.vtt`) files, and are synced to
+//! the root timeline: the time of the current frame is its index divided by the frame rate.
+
+use crate::context::RenderContext;
+use crate::font::EvalParameters;
+use crate::string::WString;
+use ruffle_render::commands::CommandHandler;
+use ruffle_render::matrix::Matrix;
+use ruffle_render::transform::Transform;
+use std::time::Duration;
+use swf::{Color, ColorTransform, Twips};
+use thiserror::Error;
+
+/// The color of the box drawn behind each line of a caption.
+const BACKGROUND_COLOR: Color = Color {
+    r: 0,
+    g: 0,
+    b: 0,
+    a: 160,
+};
+
+#[derive(Debug, Error)]
+pub enum CaptionsError {
+    #[error("Invalid caption timing on line {0}")]
+    InvalidTiming(usize),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Cue {
+    start: Duration,
+    end: Duration,
+    text: String,
+}
+
+/// A list of timed captions.
+#[derive(Clone, Debug, Default)]
+pub struct Captions {
+    cues: Vec<Cue>,
+}
+
+impl Captions {
+    /// Parses captions in the SubRip or WebVTT format.
+    pub fn parse(text: &str) -> Result<Self, CaptionsError> {
+        let mut cues = Vec::new();
+        let mut lines = text.lines().enumerate().peekable();
+
+        while let Some((line_number, line)) = lines.next() {
+            // Everything that isn't part of a cue (WebVTT headers, notes,
+            // styles and cue identifiers) is skipped.
+            let Some((start, end)) = line.split_once("-->") else {
+                continue;
+            };
+
+            // WebVTT cue settings may follow the end time.
+            let end = end.split_whitespace().next().unwrap_or_default();
+            let (Some(start), Some(end)) = (parse_timestamp(start), parse_timestamp(end)) else {
+                return Err(CaptionsError::InvalidTiming(line_number + 1));
+            };
+
+            let mut text = String::new();
+            while let Some((_, line)) = lines.next_if(|(_, line)| !line.trim().is_empty()) {
+                if !text.is_empty() {
+                    text.push('\n');
+                }
+                text.push_str(&strip_tags(line.trim()));
+            }
+
+            cues.push(Cue { start, end, text });
+        }
+
+        Ok(Self { cues })
+    }
+
+    /// The text of all captions shown at the given time, if any.
+    pub fn text_at(&self, time: Duration) -> Option<String> {
+        let texts: Vec<_> = self
+            .cues
+            .iter()
+            .filter(|cue| cue.start <= time && time < cue.end)
+            .map(|cue| cue.text.as_str())
+            .collect();
+
+        if texts.is_empty() {
+            None
+        } else {
+            Some(texts.join("\n"))
+        }
+    }
+}
+
+/// Parses a `[hh:]mm:ss.mmm` (WebVTT) or `hh:mm:ss,mmm` (SubRip) timestamp.
+fn parse_timestamp(timestamp: &str) -> Option<Duration> {
+    let timestamp = timestamp.trim().replace(',', ".");
+    let mut parts = timestamp.rsplit(':');
+    let seconds: f64 = parts.next()?.parse().ok()?;
+    let minutes: u64 = parts.next()?.parse().ok()?;
+    let hours: u64 = match parts.next() {
+        Some(hours) => hours.parse().ok()?,
+        None => 0,
+    };
+    if parts.next().is_some() || !seconds.is_finite() || seconds < 0.0 {
+        return None;
+    }
+
+    Some(Duration::from_secs(hours * 3600 + minutes * 60) + Duration::from_secs_f64(seconds))
+}
+
+/// Removes formatting tags such as `<i>` or `<c.yellow>` from a line of caption text.
+fn strip_tags(line: &str) -> String {
+    let mut text = String::with_capacity(line.len());
+    let mut in_tag = false;
+    for c in line.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text
+}
+
+/// Renders a caption centered at the bottom of the viewport, using the device font.
+pub(crate) fn render_caption(context: &mut RenderContext<'_, '_>, text: &str) {
+    let Some(font) = context.library.device_font() else {
+        return;
+    };
+
+    let viewport = context.renderer.viewport_dimensions();
+    let viewport_width = viewport.width as f64;
+    let viewport_height = viewport.height as f64;
+
+    let font_size = (viewport_height / 20.0).max(12.0);
+    let line_height = font_size * 1.25;
+    let padding = font_size * 0.25;
+    let params = EvalParameters::from_parts(Twips::from_pixels(font_size), Twips::ZERO, true);
+
+    let lines: Vec<_> = text.lines().map(WString::from_utf8).collect();
+    let mut y = viewport_height - font_size - line_height * lines.len() as f64;
+    for line in lines {
+        let (width, _) = font.measure(&line, params, false);
+        let width = width.to_pixels();
+        let x = (viewport_width - width) / 2.0;
+
+        context.commands.draw_rect(
+            BACKGROUND_COLOR,
+            Matrix::create_box(
+                (width + padding * 2.0) as f32,
+                line_height as f32,
+                0.0,
+                Twips::from_pixels(x - padding),
+                Twips::from_pixels(y),
+            ),
+        );
+
+        let transform = Transform {
+            matrix: Matrix::translate(
+                Twips::from_pixels(x),
+                Twips::from_pixels(y + (line_height - font_size) / 2.0),
+            ),
+            color_transform: ColorTransform::IDENTITY,
+        };
+        font.evaluate(
+            &line,
+            transform,
+            params,
+            |_pos, transform, glyph, _advance, _x| {
+                let shape = glyph.shape_handle(context.renderer);
+                context.commands.render_shape(shape, transform.clone());
+            },
+        );
+
+        y += line_height;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_srt() {
+        let captions = Captions::parse(
+            "1\r\n00:00:01,000 --> 00:00:02,500\r\n<i>Hello</i>\r\nthere\r\n\r\n\
+             2\r\n00:00:03,000 --> 00:00:04,000\r\nGoodbye\r\n",
+        )
+        .unwrap();
+
+        assert_eq!(captions.text_at(Duration::from_millis(500)), None);
+        assert_eq!(
+            captions.text_at(Duration::from_millis(1500)).as_deref(),
+            Some("Hello\nthere")
+        );
+        assert_eq!(
+            captions.text_at(Duration::from_millis(3000)).as_deref(),
+            Some("Goodbye")
+        );
+        assert_eq!(captions.text_at(Duration::from_millis(4000)), None);
+    }
+
+    #[test]
+    fn parse_webvtt() {
+        let captions = Captions::parse(
+            "WEBVTT\n\nNOTE a comment\n\nintro\n00:01.000 --> 00:02.000 align:middle\n\
+             <c.yellow>Hi</c>\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            captions.text_at(Duration::from_millis(1999)).as_deref(),
+            Some("Hi")
+        );
+    }
+
+    #[test]
+    fn invalid_timing() {
+        assert!(matches!(
+            Captions::parse("1\n00:00:01 --> soon\nHello\n"),
+            Err(CaptionsError::InvalidTiming(2))
+        ));
+    }
+}
@@ -0,0 +1,165 @@
+//! Compatibility hints, which warn about known issues of specific movies before they play.
+//!
+//! Hints are looked up by the hash of a movie in a JSON database:
+//!
+//! ```json
+//! {
+//!     "movies": {
+//!         "62f058628fc6cd7a5609191b98df9c5496478f046e170ac9e5612868c70774f5": {
+//!             "name": "Some Game",
+//!             "issues": ["Needs AVM2 workers, which aren't supported yet"],
+//!             "settings": { "quality": "low" }
+//!         }
+//!     }
+//! }
+//! ```
+//!
+//! The hash of a movie is the SHA-256 hash of its SWF file, as used by Flash preservation
+//! projects, and is logged as it's loaded. Settings are the names and values of player
+//! options, such as command line flags, recommended for the movie.
+
+use crate::tag_utils::SwfMovie;
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+
+/// The known issues of a movie.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct CompatibilityHint {
+    /// The name of the movie, as shown to the user.
+    pub name: Option<String>,
+
+    /// Descriptions of the issues of the movie.
+    pub issues: Vec<String>,
+
+    /// The settings recommended for the movie, by name.
+    pub settings: BTreeMap<String, String>,
+}
+
+#[derive(Deserialize)]
+struct Database {
+    #[serde(default)]
+    movies: HashMap<String, CompatibilityHint>,
+}
+
+/// A database of compatibility hints, by movie hash.
+#[derive(Clone, Debug)]
+pub struct CompatibilityHints {
+    movies: HashMap<String, CompatibilityHint>,
+}
+
+impl Default for CompatibilityHints {
+    #[cfg(feature = "default_compatibility_hints")]
+    fn default() -> Self {
+        Self::builtin_hints()
+    }
+
+    #[cfg(not(feature = "default_compatibility_hints"))]
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
+impl CompatibilityHints {
+    pub fn empty() -> Self {
+        Self {
+            movies: HashMap::new(),
+        }
+    }
+
+    /// The hints bundled with Ruffle.
+    pub fn builtin_hints() -> Self {
+        Self::from_json(include_str!("../assets/compatibility_hints.json"))
+            .expect("Builtin compatibility hints are valid")
+    }
+
+    /// Parses a JSON database of hints.
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        let database: Database = serde_json::from_str(json)?;
+        let movies = database
+            .movies
+            .into_iter()
+            .map(|(hash, hint)| (hash.to_ascii_lowercase(), hint))
+            .collect();
+        Ok(Self { movies })
+    }
+
+    /// Adds the hints of another database, which replace the hints of the same movies.
+    pub fn extend(&mut self, other: CompatibilityHints) {
+        self.movies.extend(other.movies);
+    }
+
+    /// The hint for a movie, if it has known issues.
+    pub fn hint_for(&self, movie: &SwfMovie) -> Option<&CompatibilityHint> {
+        self.movies.get(&movie_hash(movie)?)
+    }
+}
+
+/// The hash compatibility hints are keyed by, as 64 hexadecimal digits.
+///
+/// This is `None` for movies that weren't loaded from a SWF file.
+pub fn movie_hash(movie: &SwfMovie) -> Option<String> {
+    let hash = movie.sha256()?;
+    Some(hash.iter().map(|b| format!("{b:02x}")).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_json() {
+        let hints = CompatibilityHints::from_json(
+            r#"{
+                "movies": {
+                    "62F058628FC6CD7A5609191B98DF9C5496478F046E170AC9E5612868C70774F5": {
+                        "name": "Some Game",
+                        "issues": ["Needs AVM2 workers"],
+                        "settings": { "quality": "low" }
+                    },
+                    "0000000000000001": {}
+                }
+            }"#,
+        )
+        .unwrap();
+
+        let hint =
+            &hints.movies["62f058628fc6cd7a5609191b98df9c5496478f046e170ac9e5612868c70774f5"];
+        assert_eq!(hint.name.as_deref(), Some("Some Game"));
+        assert_eq!(hint.issues, vec!["Needs AVM2 workers".to_string()]);
+        assert_eq!(hint.settings["quality"], "low");
+        assert_eq!(
+            hints.movies["0000000000000001"],
+            CompatibilityHint::default()
+        );
+
+        assert!(CompatibilityHints::from_json(r#"{ "movies": [] }"#).is_err());
+    }
+
+    #[test]
+    fn test_builtin_hints_are_valid() {
+        CompatibilityHints::builtin_hints();
+    }
+
+    #[test]
+    fn test_hint_for_movie() {
+        let data =
+            include_bytes!("../../tests/tests/swfs/avm2/displayobject_opaque_background/test.swf");
+        let movie = SwfMovie::from_data(data, "file:///test.swf".into(), None).unwrap();
+        assert_eq!(
+            movie_hash(&movie).as_deref(),
+            Some("62f058628fc6cd7a5609191b98df9c5496478f046e170ac9e5612868c70774f5")
+        );
+
+        let hint = CompatibilityHints::builtin_hints()
+            .hint_for(&movie)
+            .cloned()
+            .unwrap();
+        assert_eq!(hint.issues.len(), 1);
+
+        assert_eq!(movie_hash(&SwfMovie::empty(10)), None);
+        assert!(CompatibilityHints::builtin_hints()
+            .hint_for(&SwfMovie::empty(10))
+            .is_none());
+    }
+}
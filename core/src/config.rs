@@ -53,3 +53,31 @@ pub enum NetworkingAccessMode {
     #[serde(rename = "none")]
     None,
 }
+
+/// How the player catches up when ticks come in slower than the movie's frame rate.
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FrameCatchUp {
+    /// Run as many frames per tick as recent frame timings suggest will fit, up to a few,
+    /// and slow the movie down if that still isn't enough.
+    #[default]
+    #[serde(rename = "adaptive")]
+    Adaptive,
+
+    /// Run at most one frame per tick, slowing the movie down instead of catching up.
+    #[serde(rename = "slow-down")]
+    SlowDown,
+
+    /// Keep the movie running at its frame rate by running several frames per tick and
+    /// only rendering the last one, like Flash Player does.
+    ///
+    /// A lag of more than `MAX_CATCH_UP_FRAMES` frames is dropped, so that the movie
+    /// doesn't run in a burst after the host stalled.
+    #[serde(rename = "skip-renders")]
+    SkipRenders,
+}
+
+impl FrameCatchUp {
+    /// The most frames that `SkipRenders` runs in one tick.
+    pub const MAX_CATCH_UP_FRAMES: u32 = 10;
+}
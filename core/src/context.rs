@@ -6,20 +6,28 @@ use crate::avm1::{Object as Avm1Object, Value as Avm1Value};
 use crate::avm2::{Avm2, Object as Avm2Object, SoundChannelObject};
 use crate::backend::{
     audio::{AudioBackend, AudioManager, SoundHandle, SoundInstanceHandle},
+    local_connection::LocalConnectionBackend,
     log::LogBackend,
     navigator::NavigatorBackend,
+    print::PrintBackend,
+    remote_shared_object::RemoteSharedObjectBackend,
+    socket::SocketBackend,
     storage::StorageBackend,
     ui::{InputManager, UiBackend},
+    video_capture::VideoCaptureBackend,
 };
 use crate::context_menu::ContextMenuState;
 use crate::display_object::{EditText, InteractiveObject, MovieClip, SoundTransform, Stage};
 use crate::external::ExternalInterface;
 use crate::focus_tracker::FocusTracker;
 use crate::frame_lifecycle::FramePhase;
+use crate::i18n::Localizations;
 use crate::library::Library;
+use crate::limits::{ScriptTimeout, ScriptWatchdog};
 use crate::loader::LoadManager;
 use crate::player::Player;
 use crate::prelude::*;
+use crate::printing::PrintJob;
 use crate::streams::StreamManager;
 use crate::string::AvmStringInterner;
 use crate::stub::StubCollection;
@@ -111,6 +119,24 @@ pub struct UpdateContext<'a, 'gc> {
     /// The storage backend, used for storing persistent state
     pub storage: &'a mut dyn StorageBackend,
 
+    /// The backend that remote shared objects are synchronized through.
+    pub remote_shared_objects: &'a mut dyn RemoteSharedObjectBackend,
+
+    /// The backend that the messages of local connections are delivered through.
+    pub local_connections: &'a mut dyn LocalConnectionBackend,
+
+    /// The backend that `XMLSocket` connections are opened through.
+    pub sockets: &'a mut dyn SocketBackend,
+
+    /// The backend that the pages of `PrintJob` are printed with.
+    pub printer: &'a mut dyn PrintBackend,
+
+    /// The pages added to the print job that is in progress, if any.
+    pub print_job: &'a mut Option<PrintJob>,
+
+    /// The translations of the text shown by the player.
+    pub localizations: &'a Localizations,
+
     /// The logging backend, used for trace output capturing.
     ///
     /// **DO NOT** use this field directly, use the `avm_trace` method instead.
@@ -119,6 +145,9 @@ pub struct UpdateContext<'a, 'gc> {
     /// The video backend, used for video decoding
     pub video: &'a mut dyn VideoBackend,
 
+    /// The backend that the frames of cameras are captured with.
+    pub video_capture: &'a mut dyn VideoCaptureBackend,
+
     /// The RNG, used by the AVM `RandomNumber` opcode,  `Math.random(),` and `random()`.
     pub rng: &'a mut SmallRng,
 
@@ -187,10 +216,13 @@ pub struct UpdateContext<'a, 'gc> {
     /// The instant at which the current update started.
     pub update_start: Instant,
 
-    /// The maximum amount of time that can be called before a `Error::ExecutionTimeout`
-    /// is raised. This defaults to 15 seconds but can be changed.
+    /// The maximum amount of time that scripts can run for before the user is
+    /// asked whether to abort them. This defaults to 15 seconds but can be changed.
     pub max_execution_duration: Duration,
 
+    /// Keeps track of how long scripts have been running in this update.
+    pub script_watchdog: &'a mut ScriptWatchdog,
+
     /// A tracker for the current keyboard focused element
     pub focus_tracker: FocusTracker<'gc>,
 
@@ -206,6 +238,9 @@ pub struct UpdateContext<'a, 'gc> {
     /// Whether movies are prevented from changing the stage frame rate.
     pub forced_frame_rate: bool,
 
+    /// Whether the APIs of Adobe AIR are available to movies.
+    pub air_compatibility: bool,
+
     /// Amount of actions performed since the last timeout check
     pub actions_since_timeout_check: &'a mut u16,
 
@@ -255,6 +290,33 @@ impl<'a, 'gc> UpdateContext<'a, 'gc> {
             .start_sound(self.audio, sound, settings, owner, avm1_object)
     }
 
+    /// Starts or stops a sound placed on the timeline or in a button state,
+    /// according to its sync setting in the Flash IDE.
+    pub fn sync_sound(
+        &mut self,
+        sound: SoundHandle,
+        settings: &swf::SoundInfo,
+        owner: Option<DisplayObject<'gc>>,
+    ) {
+        use swf::SoundEvent;
+        match settings.event {
+            // "Event" sounds always play, independent of the timeline.
+            SoundEvent::Event => {
+                let _ = self.start_sound(sound, settings, owner, None);
+            }
+
+            // "Start" sounds only play if an instance of the same sound is not already playing.
+            SoundEvent::Start => {
+                if !self.is_sound_playing_with_handle(sound) {
+                    let _ = self.start_sound(sound, settings, owner, None);
+                }
+            }
+
+            // "Stop" stops any active instances of a given sound.
+            SoundEvent::Stop => self.stop_sounds_with_handle(sound),
+        }
+    }
+
     pub fn attach_avm2_sound_channel(
         &mut self,
         instance: SoundInstanceHandle,
@@ -342,7 +404,14 @@ impl<'a, 'gc> UpdateContext<'a, 'gc> {
             log: self.log,
             ui: self.ui,
             video: self.video,
+            video_capture: self.video_capture,
             storage: self.storage,
+            remote_shared_objects: self.remote_shared_objects,
+            local_connections: self.local_connections,
+            sockets: self.sockets,
+            printer: self.printer,
+            print_job: self.print_job,
+            localizations: self.localizations,
             rng: self.rng,
             stage: self.stage,
             mouse_over_object: self.mouse_over_object,
@@ -365,11 +434,13 @@ impl<'a, 'gc> UpdateContext<'a, 'gc> {
             start_time: self.start_time,
             update_start: self.update_start,
             max_execution_duration: self.max_execution_duration,
+            script_watchdog: self.script_watchdog,
             focus_tracker: self.focus_tracker,
             times_get_time_called: self.times_get_time_called,
             time_offset: self.time_offset,
             frame_rate: self.frame_rate,
             forced_frame_rate: self.forced_frame_rate,
+            air_compatibility: self.air_compatibility,
             actions_since_timeout_check: self.actions_since_timeout_check,
             frame_phase: self.frame_phase,
             stream_manager: self.stream_manager,
@@ -391,8 +462,25 @@ impl<'a, 'gc> UpdateContext<'a, 'gc> {
         self.swf.is_action_script_3()
     }
 
+    /// The text of a message, in the language of the user.
+    pub fn text(&self, id: &str) -> String {
+        self.localizations.text(&self.ui.language(), id)
+    }
+
     pub fn avm_trace(&self, message: &str) {
-        self.log.avm_trace(&message.replace('\r', "\n"));
+        self.movie_trace(self.swf, message);
+    }
+
+    /// Logs a trace of `movie`, which may be a loaded movie rather than the root one.
+    pub fn movie_trace(&self, movie: &SwfMovie, message: &str) {
+        self.log
+            .movie_trace(movie.url(), &message.replace('\r', "\n"));
+    }
+
+    /// Checks whether the running script has exceeded the script timeout.
+    pub fn check_script_timeout(&mut self) -> ScriptTimeout {
+        self.script_watchdog
+            .check(self.ui, self.localizations, self.max_execution_duration)
     }
 }
 
@@ -485,6 +573,10 @@ pub struct RenderContext<'a, 'gc> {
     /// Whether we're rendering offscreen. This can disable some logic like Ruffle-side render culling
     pub is_offscreen: bool,
 
+    /// How far between the previous and the current frame display object transforms are
+    /// interpolated, from `0.0` to `1.0`. `None` if frame interpolation is disabled.
+    pub frame_interpolation: Option<f32>,
+
     /// The current player's stage (including all loaded levels)
     pub stage: Stage<'gc>,
 }
@@ -6,8 +6,9 @@
 
 use crate::avm1;
 use crate::avm2;
-use crate::display_object::Stage;
+use crate::context::UpdateContext;
 use crate::display_object::TDisplayObject;
+use crate::display_object::{DisplayObject, Stage};
 use gc_arena::Collect;
 use ruffle_render::quality::StageQuality;
 use serde::Serialize;
@@ -17,6 +18,10 @@ use serde::Serialize;
 pub struct ContextMenuState<'gc> {
     info: Vec<ContextMenuItem>,
     callbacks: Vec<ContextMenuCallback<'gc>>,
+
+    /// The display object whose `menu` was opened, which is passed to the
+    /// AVM1 menu callbacks.
+    target: Option<DisplayObject<'gc>>,
 }
 
 impl<'gc> ContextMenuState<'gc> {
@@ -33,7 +38,18 @@ impl<'gc> ContextMenuState<'gc> {
     pub fn callback(&self, index: usize) -> &ContextMenuCallback<'gc> {
         &self.callbacks[index]
     }
-    pub fn build_builtin_items(&mut self, item_flags: BuiltInItemFlags, stage: Stage<'gc>) {
+    pub fn target(&self) -> Option<DisplayObject<'gc>> {
+        self.target
+    }
+    pub fn set_target(&mut self, target: Option<DisplayObject<'gc>>) {
+        self.target = target;
+    }
+    pub fn build_builtin_items(
+        &mut self,
+        item_flags: BuiltInItemFlags,
+        context: &UpdateContext<'_, 'gc>,
+    ) {
+        let stage = context.stage;
         let Some(root_mc) = stage.root_clip().and_then(|c| c.as_movie_clip()) else {
             return;
         };
@@ -43,7 +59,7 @@ impl<'gc> ContextMenuState<'gc> {
                 ContextMenuItem {
                     enabled: true,
                     separator_before: true,
-                    caption: "Play".to_string(),
+                    caption: context.text("context-menu-play"),
                     checked: is_playing_root_movie,
                 },
                 ContextMenuCallback::Play,
@@ -55,7 +71,7 @@ impl<'gc> ContextMenuState<'gc> {
                 ContextMenuItem {
                     enabled: !is_first_frame,
                     separator_before: true,
-                    caption: "Rewind".to_string(),
+                    caption: context.text("context-menu-rewind"),
                     checked: false,
                 },
                 ContextMenuCallback::Rewind,
@@ -67,7 +83,7 @@ impl<'gc> ContextMenuState<'gc> {
                 ContextMenuItem {
                     enabled: true,
                     separator_before: false,
-                    caption: "Forward".to_string(),
+                    caption: context.text("context-menu-forward"),
                     checked: false,
                 },
                 ContextMenuCallback::Forward,
@@ -76,7 +92,7 @@ impl<'gc> ContextMenuState<'gc> {
                 ContextMenuItem {
                     enabled: !is_first_frame,
                     separator_before: false,
-                    caption: "Back".to_string(),
+                    caption: context.text("context-menu-back"),
                     checked: false,
                 },
                 ContextMenuCallback::Back,
@@ -89,7 +105,7 @@ impl<'gc> ContextMenuState<'gc> {
                     enabled: stage.quality() != StageQuality::Low,
                     separator_before: true,
                     checked: stage.quality() == StageQuality::Low,
-                    caption: "Quality: Low".to_string(),
+                    caption: context.text("context-menu-quality-low"),
                 },
                 ContextMenuCallback::QualityLow,
             );
@@ -98,7 +114,7 @@ impl<'gc> ContextMenuState<'gc> {
                     enabled: stage.quality() != StageQuality::Medium,
                     separator_before: false,
                     checked: stage.quality() == StageQuality::Medium,
-                    caption: "Quality: Medium".to_string(),
+                    caption: context.text("context-menu-quality-medium"),
                 },
                 ContextMenuCallback::QualityMedium,
             );
@@ -107,7 +123,7 @@ impl<'gc> ContextMenuState<'gc> {
                     enabled: stage.quality() != StageQuality::High,
                     separator_before: false,
                     checked: stage.quality() == StageQuality::High,
-                    caption: "Quality: High".to_string(),
+                    caption: context.text("context-menu-quality-high"),
                 },
                 ContextMenuCallback::QualityHigh,
             );
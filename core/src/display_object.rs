@@ -14,7 +14,8 @@ use crate::vminterface::Instantiator;
 use bitflags::bitflags;
 use gc_arena::{Collect, MutationContext};
 use ruffle_macros::enum_trait_object;
-use ruffle_render::transform::Transform;
+use ruffle_render::bitmap::{Bitmap as RenderBitmap, BitmapFormat, PixelRegion};
+use ruffle_render::transform::{Transform, TransformStack};
 use std::cell::{Ref, RefMut};
 use std::fmt::Debug;
 use std::sync::Arc;
@@ -41,7 +42,7 @@ pub use crate::display_object::container::{
 };
 pub use avm1_button::{Avm1Button, ButtonState, ButtonTracking};
 pub use avm2_button::Avm2Button;
-pub use bitmap::Bitmap;
+pub use bitmap::{Bitmap, PixelSnapping};
 pub use edit_text::{AutoSizeMode, EditText, TextSelection};
 pub use graphic::Graphic;
 pub use interactive::{Avm2MousePick, InteractiveObject, TInteractiveObject};
@@ -50,7 +51,9 @@ pub use morph_shape::{MorphShape, MorphShapeStatic};
 pub use movie_clip::{MovieClip, MovieClipWeak, Scene};
 use ruffle_render::commands::CommandHandler;
 use ruffle_render::filters::Filter;
-pub use stage::{Stage, StageAlign, StageDisplayState, StageScaleMode, WindowMode};
+pub use stage::{
+    ColorCorrection, Stage, StageAlign, StageDisplayState, StageScaleMode, WindowMode,
+};
 pub use text::Text;
 pub use video::Video;
 
@@ -100,6 +103,11 @@ pub struct DisplayObjectBase<'gc> {
     #[collect(require_static)]
     blend_mode: BlendMode,
 
+    /// The AVM2 `Shader` assigned with `DisplayObject.blendShader`.
+    /// It's kept even when the blend mode is changed, so that setting `blendMode`
+    /// back to `"shader"` uses it again.
+    blend_shader: Option<Avm2Object<'gc>>,
+
     /// The opaque background color of this display object.
     /// The bounding box of the display object will be filled with the given color. This also
     /// triggers cache-as-bitmap behavior. Only solid backgrounds are supported; the alpha channel
@@ -120,6 +128,19 @@ pub struct DisplayObjectBase<'gc> {
     /// changes immediately (without needing wait for a render)
     #[collect(require_static)]
     next_scroll_rect: Rectangle<Twips>,
+
+    /// The 9-slice scaling grid of this object, set by a `DefineScalingGrid` tag or by
+    /// `DisplayObject.scale9Grid`. This is an invalid rectangle when no grid is used.
+    #[collect(require_static)]
+    scaling_grid: Rectangle<Twips>,
+
+    /// The matrix of this object at the start of the most recently run frame.
+    /// This is used to interpolate the transform between frames when smooth playback is enabled.
+    ///
+    /// `None` if the object did not exist at the start of the frame, or if its transform
+    /// changed discontinuously (such as a timeline jump), in which case it is never interpolated.
+    #[collect(require_static)]
+    previous_matrix: Option<Matrix>,
 }
 
 impl<'gc> Default for DisplayObjectBase<'gc> {
@@ -141,10 +162,13 @@ impl<'gc> Default for DisplayObjectBase<'gc> {
             maskee: None,
             sound_transform: Default::default(),
             blend_mode: Default::default(),
+            blend_shader: None,
             opaque_background: Default::default(),
             flags: DisplayObjectFlags::VISIBLE,
             scroll_rect: None,
             next_scroll_rect: Default::default(),
+            scaling_grid: Default::default(),
+            previous_matrix: None,
         }
     }
 }
@@ -189,6 +213,30 @@ impl<'gc> DisplayObjectBase<'gc> {
         self.set_scale_rotation_cached(false);
     }
 
+    /// Records the current matrix as the starting point of transform interpolation.
+    pub fn record_previous_matrix(&mut self) {
+        self.previous_matrix = if self.interpolation_disabled() {
+            None
+        } else {
+            Some(self.transform.matrix)
+        };
+    }
+
+    /// Prevents the transform of this object from being interpolated until the next frame.
+    pub fn clear_previous_matrix(&mut self) {
+        self.previous_matrix = None;
+    }
+
+    /// Returns the transform of this object interpolated between the previous frame and the
+    /// current frame by `ratio`.
+    fn interpolated_transform(&self, ratio: f32) -> Transform {
+        let mut transform = self.transform.clone();
+        if let Some(previous_matrix) = &self.previous_matrix {
+            transform.matrix = previous_matrix.lerp(&self.transform.matrix, ratio);
+        }
+        transform
+    }
+
     pub fn color_transform(&self) -> &ColorTransform {
         &self.transform.color_transform
     }
@@ -443,6 +491,22 @@ impl<'gc> DisplayObjectBase<'gc> {
         self.blend_mode = value;
     }
 
+    fn blend_shader(&self) -> Option<Avm2Object<'gc>> {
+        self.blend_shader
+    }
+
+    fn set_blend_shader(&mut self, value: Option<Avm2Object<'gc>>) {
+        self.blend_shader = value;
+    }
+
+    fn uses_blend_shader(&self) -> bool {
+        self.flags.contains(DisplayObjectFlags::USES_BLEND_SHADER)
+    }
+
+    fn set_uses_blend_shader(&mut self, value: bool) {
+        self.flags.set(DisplayObjectFlags::USES_BLEND_SHADER, value);
+    }
+
     /// The opaque background color of this display object.
     /// The bounding box of the display object will be filled with this color.
     fn opaque_background(&self) -> Option<Color> {
@@ -502,6 +566,17 @@ impl<'gc> DisplayObjectBase<'gc> {
         self.flags.set(DisplayObjectFlags::CACHE_AS_BITMAP, value);
     }
 
+    fn interpolation_disabled(&self) -> bool {
+        self.flags.contains(DisplayObjectFlags::NO_INTERPOLATION)
+    }
+
+    fn set_interpolation_disabled(&mut self, value: bool) {
+        self.flags.set(DisplayObjectFlags::NO_INTERPOLATION, value);
+        if value {
+            self.clear_previous_matrix();
+        }
+    }
+
     fn instantiated_by_timeline(&self) -> bool {
         self.flags
             .contains(DisplayObjectFlags::INSTANTIATED_BY_TIMELINE)
@@ -549,7 +624,12 @@ pub fn render_base<'gc>(this: DisplayObject<'gc>, context: &mut RenderContext<'_
     if this.maskee().is_some() {
         return;
     }
-    context.transform_stack.push(this.base().transform());
+    if let Some(ratio) = context.frame_interpolation {
+        let transform = this.base().interpolated_transform(ratio);
+        context.transform_stack.push(&transform);
+    } else {
+        context.transform_stack.push(this.base().transform());
+    }
     let blend_mode = this.blend_mode();
     let original_commands = if blend_mode != BlendMode::Normal {
         Some(std::mem::take(&mut context.commands))
@@ -585,6 +665,19 @@ pub fn render_base<'gc>(this: DisplayObject<'gc>, context: &mut RenderContext<'_
     if let Some(m) = mask {
         mask_transform.matrix = this.global_to_local_matrix().unwrap_or_default();
         mask_transform.matrix *= m.local_to_global_matrix();
+    }
+
+    // When both the masker and the maskee are cached as bitmaps, Flash uses the
+    // alpha channel of the masker instead of a 1-bit stencil mask.
+    let alpha_mask = mask.filter(|m| this.is_bitmap_cached() && m.is_bitmap_cached());
+    let stencil_mask = if alpha_mask.is_some() { None } else { mask };
+    let commands_before_alpha_mask = if alpha_mask.is_some() {
+        Some(std::mem::take(&mut context.commands))
+    } else {
+        None
+    };
+
+    if let Some(m) = stencil_mask {
         context.commands.push_mask();
         context.transform_stack.push(&mask_transform);
         m.render_self(context);
@@ -610,7 +703,24 @@ pub fn render_base<'gc>(this: DisplayObject<'gc>, context: &mut RenderContext<'_
         context.commands.activate_mask();
     }
 
-    this.render_self(context);
+    // The opaque background is a solid rectangle covering the bounds of the object,
+    // drawn behind its contents.
+    if let Some(background) = this.opaque_background() {
+        let bounds = this.bounds();
+        if bounds.is_valid() {
+            let transform = context.transform_stack.transform();
+            let color = &transform.color_transform * background;
+            let matrix = transform.matrix
+                * Matrix::translate(bounds.x_min, bounds.y_min)
+                * Matrix::scale(
+                    bounds.width().to_pixels() as f32,
+                    bounds.height().to_pixels() as f32,
+                );
+            context.commands.draw_rect(color, matrix);
+        }
+    }
+
+    render_self_with_filters(this, context);
 
     if let Some(rect_mat) = scroll_rect_matrix {
         // Draw the rectangle again after deactivating the mask,
@@ -620,7 +730,7 @@ pub fn render_base<'gc>(this: DisplayObject<'gc>, context: &mut RenderContext<'_
         context.commands.pop_mask();
     }
 
-    if let Some(m) = mask {
+    if let Some(m) = stencil_mask {
         context.commands.deactivate_mask();
         context.transform_stack.push(&mask_transform);
         m.render_self(context);
@@ -628,6 +738,15 @@ pub fn render_base<'gc>(this: DisplayObject<'gc>, context: &mut RenderContext<'_
         context.commands.pop_mask();
     }
 
+    if let (Some(m), Some(commands_before_alpha_mask)) = (alpha_mask, commands_before_alpha_mask) {
+        let maskee_commands = std::mem::take(&mut context.commands);
+        context.transform_stack.push(&mask_transform);
+        m.render_self(context);
+        context.transform_stack.pop();
+        let mask_commands = std::mem::replace(&mut context.commands, commands_before_alpha_mask);
+        context.commands.alpha_mask(maskee_commands, mask_commands);
+    }
+
     if let Some(original_commands) = original_commands {
         let sub_commands = std::mem::replace(&mut context.commands, original_commands);
         context.commands.blend(sub_commands, blend_mode);
@@ -641,6 +760,224 @@ pub fn render_base<'gc>(this: DisplayObject<'gc>, context: &mut RenderContext<'_
     context.transform_stack.pop();
 }
 
+/// The largest size of the offscreen bitmap that filtered objects are rendered into.
+const MAX_FILTER_BITMAP_SIZE: u32 = 4096;
+
+/// Renders the contents of `this`, applying its filters.
+///
+/// Filtered objects are rendered into an offscreen bitmap covering their bounds
+/// on the stage, which the filters are then applied to. Filters that the renderer
+/// doesn't support are ignored.
+fn render_self_with_filters<'gc>(this: DisplayObject<'gc>, context: &mut RenderContext<'_, 'gc>) {
+    let filters: Vec<_> = this
+        .filters()
+        .into_iter()
+        .filter(|filter| context.renderer.is_filter_supported(filter))
+        .collect();
+    if filters.is_empty() {
+        render_self_with_scaling_grid(this, context);
+        return;
+    }
+
+    let transform = context.transform_stack.transform();
+    let bounds = filters.iter().fold(
+        this.bounds_with_transform(&transform.matrix),
+        |bounds, filter| filter.calculate_dest_rect(bounds),
+    );
+    if !bounds.is_valid() {
+        return;
+    }
+    let x = bounds.x_min.to_pixels().floor();
+    let y = bounds.y_min.to_pixels().floor();
+    let width = (bounds.x_max.to_pixels().ceil() - x).min(MAX_FILTER_BITMAP_SIZE.into()) as u32;
+    let height = (bounds.y_max.to_pixels().ceil() - y).min(MAX_FILTER_BITMAP_SIZE.into()) as u32;
+    if width == 0 || height == 0 {
+        return;
+    }
+
+    let bitmap = RenderBitmap::new(
+        width,
+        height,
+        BitmapFormat::Rgba,
+        vec![0; (width * height * 4) as usize],
+    );
+    let handle = match context.renderer.register_bitmap(bitmap) {
+        Ok(handle) => handle,
+        Err(e) => {
+            tracing::warn!("Couldn't create a bitmap to apply filters to: {}", e);
+            render_self_with_scaling_grid(this, context);
+            return;
+        }
+    };
+
+    // The object is rendered at the top left of the bitmap, keeping its transform otherwise.
+    let offset = Matrix::translate(Twips::from_pixels(-x), Twips::from_pixels(-y));
+    let mut transform_stack = TransformStack::new();
+    transform_stack.push(&Transform {
+        matrix: offset * transform.matrix,
+        color_transform: transform.color_transform,
+    });
+    let transform_stack = std::mem::replace(context.transform_stack, transform_stack);
+    let commands = std::mem::take(&mut context.commands);
+    let is_offscreen = std::mem::replace(&mut context.is_offscreen, true);
+    render_self_with_scaling_grid(this, context);
+    *context.transform_stack = transform_stack;
+    context.is_offscreen = is_offscreen;
+    let filtered_commands = std::mem::replace(&mut context.commands, commands);
+
+    let quality = context.stage.quality();
+    if context
+        .renderer
+        .render_offscreen(
+            handle.clone(),
+            filtered_commands,
+            quality,
+            PixelRegion::for_whole_size(width, height),
+        )
+        .is_none()
+    {
+        render_self_with_scaling_grid(this, context);
+        return;
+    }
+    for filter in filters {
+        context.renderer.apply_filter(
+            handle.clone(),
+            (0, 0),
+            (width, height),
+            handle.clone(),
+            (0, 0),
+            filter,
+        );
+    }
+
+    context.commands.render_bitmap(
+        handle,
+        Transform {
+            matrix: Matrix::translate(Twips::from_pixels(x), Twips::from_pixels(y)),
+            ..Default::default()
+        },
+        false,
+    );
+}
+
+/// Renders the contents of `this`, applying its 9-slice scaling grid.
+///
+/// Each of the nine regions of the grid is drawn separately, clipped to its own rectangle:
+/// the corners keep their original size, the edges are only stretched along one axis,
+/// and the center region takes up the remaining space.
+fn render_self_with_scaling_grid<'gc>(
+    this: DisplayObject<'gc>,
+    context: &mut RenderContext<'_, 'gc>,
+) {
+    let grid = this.scaling_grid();
+    let bounds = this.bounds();
+    let matrix = *this.base().matrix();
+    let scale_x = f32::hypot(matrix.a, matrix.b);
+    let scale_y = f32::hypot(matrix.c, matrix.d);
+
+    // The grid is ignored unless it lies inside of the bounds of the object.
+    let use_grid = grid.is_valid()
+        && bounds.is_valid()
+        && grid.x_min > bounds.x_min
+        && grid.x_max < bounds.x_max
+        && grid.y_min > bounds.y_min
+        && grid.y_max < bounds.y_max
+        && grid.x_min < grid.x_max
+        && grid.y_min < grid.y_max
+        && scale_x != 0.0
+        && scale_y != 0.0
+        && (scale_x != 1.0 || scale_y != 1.0);
+    if !use_grid {
+        this.render_self(context);
+        return;
+    }
+
+    let (source_x, dest_x) =
+        scaling_grid_edges(bounds.x_min, grid.x_min, grid.x_max, bounds.x_max, scale_x);
+    let (source_y, dest_y) =
+        scaling_grid_edges(bounds.y_min, grid.y_min, grid.y_max, bounds.y_max, scale_y);
+
+    for row in 0..3 {
+        for column in 0..3 {
+            let source_width = source_x[column + 1] - source_x[column];
+            let source_height = source_y[row + 1] - source_y[row];
+            let dest_width = dest_x[column + 1] - dest_x[column];
+            let dest_height = dest_y[row + 1] - dest_y[row];
+            if dest_width <= 0.0 || dest_height <= 0.0 {
+                continue;
+            }
+
+            // The region of the object that this part of the grid is drawn into.
+            let clip_matrix = context.transform_stack.transform().matrix
+                * Matrix {
+                    a: dest_width / Twips::TWIPS_PER_PIXEL as f32,
+                    b: 0.0,
+                    c: 0.0,
+                    d: dest_height / Twips::TWIPS_PER_PIXEL as f32,
+                    tx: Twips::new(dest_x[column] as i32),
+                    ty: Twips::new(dest_y[row] as i32),
+                };
+
+            // Maps this part of the grid from its original position to its scaled position.
+            let a = dest_width / source_width;
+            let d = dest_height / source_height;
+            let region_transform = Transform {
+                matrix: Matrix {
+                    a,
+                    b: 0.0,
+                    c: 0.0,
+                    d,
+                    tx: Twips::new((dest_x[column] - a * source_x[column]) as i32),
+                    ty: Twips::new((dest_y[row] - d * source_y[row]) as i32),
+                },
+                color_transform: Default::default(),
+            };
+
+            context.commands.push_mask();
+            // The color doesn't matter, as this is a mask.
+            context.commands.draw_rect(Color::WHITE, clip_matrix);
+            context.commands.activate_mask();
+
+            context.transform_stack.push(&region_transform);
+            this.render_self(context);
+            context.transform_stack.pop();
+
+            context.commands.deactivate_mask();
+            context.commands.draw_rect(Color::WHITE, clip_matrix);
+            context.commands.pop_mask();
+        }
+    }
+}
+
+/// Returns the edges of the three parts of a 9-slice scaling grid along one axis, in twips:
+/// first in the unscaled object, and then after the corners have been scaled back to their
+/// original size.
+fn scaling_grid_edges(
+    min: Twips,
+    grid_min: Twips,
+    grid_max: Twips,
+    max: Twips,
+    scale: f32,
+) -> ([f32; 4], [f32; 4]) {
+    let [min, grid_min, grid_max, max] =
+        [min, grid_min, grid_max, max].map(|twips| twips.get() as f32);
+    let mut start = (grid_min - min) / scale.abs();
+    let mut end = (max - grid_max) / scale.abs();
+
+    // If the object is shrunk so far that the corners would overlap, they're shrunk to fit.
+    let total = max - min;
+    if start + end > total {
+        let factor = total / (start + end);
+        start *= factor;
+        end *= factor;
+    }
+
+    (
+        [min, grid_min, grid_max, max],
+        [min, min + start, max - end, max],
+    )
+}
+
 #[enum_trait_object(
     #[derive(Clone, Collect, Debug, Copy)]
     #[collect(no_drop)]
@@ -705,6 +1042,15 @@ pub trait TDisplayObject<'gc>:
         self.bounds_with_transform(&self.local_to_global_matrix())
     }
 
+    /// The world bounding box of this object, expanded by the area affected by its filters.
+    fn pixel_bounds(&self) -> Rectangle<Twips> {
+        self.filters()
+            .iter()
+            .fold(self.world_bounds(), |bounds, filter| {
+                filter.calculate_dest_rect(bounds)
+            })
+    }
+
     /// Gets the bounds of this object and all children, transformed by a given matrix.
     /// This function recurses down and transforms the AABB each child before adding
     /// it to the bounding box. This gives a tighter AABB then if we simply transformed
@@ -1141,6 +1487,16 @@ pub trait TDisplayObject<'gc>:
         self.base_mut(gc_context).next_scroll_rect = rectangle;
     }
 
+    /// The 9-slice scaling grid of this object, in its local coordinate space.
+    /// This is an invalid rectangle if the object isn't 9-slice scaled.
+    fn scaling_grid(&self) -> Rectangle<Twips> {
+        self.base().scaling_grid.clone()
+    }
+
+    fn set_scaling_grid(&self, gc_context: MutationContext<'gc, '_>, rectangle: Rectangle<Twips>) {
+        self.base_mut(gc_context).scaling_grid = rectangle;
+    }
+
     /// Whether this object has been removed. Only applies to AVM1.
     fn avm1_removed(&self) -> bool {
         self.base().avm1_removed()
@@ -1182,6 +1538,30 @@ pub trait TDisplayObject<'gc>:
         self.base_mut(gc_context).set_blend_mode(value);
     }
 
+    /// The shader assigned with `DisplayObject.blendShader`, if any.
+    fn blend_shader(&self) -> Option<Avm2Object<'gc>> {
+        self.base().blend_shader()
+    }
+
+    fn set_blend_shader(
+        &self,
+        gc_context: MutationContext<'gc, '_>,
+        value: Option<Avm2Object<'gc>>,
+    ) {
+        self.base_mut(gc_context).set_blend_shader(value);
+    }
+
+    /// Whether the blend mode of this display object is `"shader"`.
+    /// Custom shader blends are not supported yet, so such objects are
+    /// rendered with `BlendMode::Normal`.
+    fn uses_blend_shader(&self) -> bool {
+        self.base().uses_blend_shader()
+    }
+
+    fn set_uses_blend_shader(&self, gc_context: MutationContext<'gc, '_>, value: bool) {
+        self.base_mut(gc_context).set_uses_blend_shader(value);
+    }
+
     /// The opaque background color of this display object.
     fn opaque_background(&self) -> Option<Color> {
         self.base().opaque_background()
@@ -1253,6 +1633,18 @@ pub trait TDisplayObject<'gc>:
         self.base_mut(gc_context).set_is_bitmap_cached(value)
     }
 
+    /// Whether the transform of this display object is never interpolated between frames,
+    /// even when smooth playback is enabled.
+    fn interpolation_disabled(&self) -> bool {
+        self.base().interpolation_disabled()
+    }
+
+    /// Opts this display object out of (or back into) transform interpolation.
+    /// Its children are still interpolated relative to it.
+    fn set_interpolation_disabled(&self, gc_context: MutationContext<'gc, '_>, value: bool) {
+        self.base_mut(gc_context).set_interpolation_disabled(value)
+    }
+
     /// Whether this display object has a scroll rectangle applied.
     fn has_scroll_rect(&self) -> bool {
         self.base().has_scroll_rect()
@@ -1855,6 +2247,12 @@ bitflags! {
         /// which are observed to lag behind objects placed by the timeline
         /// (even if they are both placed in the same frame)
         const SKIP_NEXT_ENTER_FRAME          = 1 << 11;
+
+        /// Whether this object's blend mode is `"shader"`, set by `DisplayObject.blendShader`.
+        const USES_BLEND_SHADER        = 1 << 12;
+
+        /// Whether this object opted out of transform interpolation when smooth playback is enabled.
+        const NO_INTERPOLATION         = 1 << 13;
     }
 }
 
@@ -1869,8 +2267,11 @@ bitflags! {
         /// Ignore objects with the ActionScript's visibility flag turned off.
         const SKIP_INVISIBLE = 1 << 1;
 
+        /// Test bitmaps against their alpha channel, instead of their bounding box.
+        const BITMAP_ALPHA = 1 << 2;
+
         /// The options used for `hitTest` calls in ActionScript.
-        const AVM_HIT_TEST = Self::SKIP_MASK.bits();
+        const AVM_HIT_TEST = Self::SKIP_MASK.bits() | Self::BITMAP_ALPHA.bits();
 
         /// The options used for mouse picking, such as clicking on buttons.
         const MOUSE_PICK = Self::SKIP_MASK.bits() | Self::SKIP_INVISIBLE.bits();
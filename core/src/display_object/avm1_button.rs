@@ -123,6 +123,11 @@ impl<'gc> Avm1Button<'gc> {
         }
     }
 
+    /// Get the rendered state of the button.
+    pub fn state(self) -> ButtonState {
+        self.0.read().state
+    }
+
     /// Set the state of a button, creating or destroying children as needed.
     ///
     /// This function instantiates children and thus must not be called whilst
@@ -212,6 +217,18 @@ impl<'gc> Avm1Button<'gc> {
     pub fn set_use_hand_cursor(self, context: &mut UpdateContext<'_, 'gc>, use_hand_cursor: bool) {
         self.0.write(context.gc_context).use_hand_cursor = use_hand_cursor;
     }
+
+    pub fn button_tracking(self) -> ButtonTracking {
+        self.0.read().tracking
+    }
+
+    pub fn set_button_tracking(
+        self,
+        context: &mut UpdateContext<'_, 'gc>,
+        tracking: ButtonTracking,
+    ) {
+        self.0.write(context.gc_context).tracking = tracking;
+    }
 }
 
 impl<'gc> TDisplayObject<'gc> for Avm1Button<'gc> {
@@ -421,6 +438,18 @@ impl<'gc> TInteractiveObject<'gc> for Avm1Button<'gc> {
         self.into()
     }
 
+    fn tracks_as_menu(self) -> bool {
+        self.0.read().tracking == ButtonTracking::Menu
+    }
+
+    fn set_tracks_as_menu(self, mc: MutationContext<'gc, '_>, value: bool) {
+        self.0.write(mc).tracking = if value {
+            ButtonTracking::Menu
+        } else {
+            ButtonTracking::Push
+        };
+    }
+
     fn filter_clip_event(self, _event: ClipEvent) -> ClipEventResult {
         // An invisible button can still run its `rollOut` or `releaseOutside` event.
         // A disabled button doesn't run its events (`KeyPress` being the exception) but
@@ -443,7 +472,20 @@ impl<'gc> TInteractiveObject<'gc> for Avm1Button<'gc> {
         // Translate the clip event to a button event, based on how the button state changes.
         let static_data = write.static_data;
         let static_data = static_data.read();
+        let is_menu = write.tracking == ButtonTracking::Menu;
         let (new_state, condition, sound) = match event {
+            // Menu buttons go back to idle when the mouse leaves them, and are
+            // pressed as soon as the mouse enters them with the button down.
+            ClipEvent::DragOut { .. } if is_menu => (
+                ButtonState::Up,
+                ButtonActionCondition::OVER_DOWN_TO_IDLE,
+                static_data.over_to_up_sound.as_ref(),
+            ),
+            ClipEvent::DragOver { .. } if is_menu => (
+                ButtonState::Down,
+                ButtonActionCondition::IDLE_TO_OVER_DOWN,
+                static_data.over_to_down_sound.as_ref(),
+            ),
             ClipEvent::DragOut { .. } => (
                 ButtonState::Over,
                 ButtonActionCondition::OVER_DOWN_TO_OUT_DOWN,
@@ -574,7 +616,7 @@ impl<'gc> Avm1ButtonData<'gc> {
                 .library_for_movie_mut(self.movie())
                 .get_sound(*id)
             {
-                let _ = context.start_sound(sound_handle, sound_info, None, None);
+                context.sync_sound(sound_handle, sound_info, None);
             }
         }
     }
@@ -266,24 +266,35 @@ impl<'gc> Avm2Button<'gc> {
     /// Change the rendered state of the button.
     pub fn set_state(self, context: &mut UpdateContext<'_, 'gc>, state: ButtonState) {
         self.0.write(context.gc_context).state = state;
-        let button = self.0.read();
-        if let Some(state) = button.up_state {
-            state.set_parent(context, None);
-        }
-        if let Some(state) = button.over_state {
-            state.set_parent(context, None);
-        }
-        if let Some(state) = button.down_state {
-            state.set_parent(context, None);
-        }
-        if let Some(state) = button.hit_area {
-            state.set_parent(context, None);
+        for child in self.state_children() {
+            child.set_parent(context, None);
         }
         if let Some(state) = self.get_state_child(state.into()) {
             state.set_parent(context, Some(self.into()));
         }
     }
 
+    /// The distinct display objects used for the states of this button.
+    ///
+    /// Buttons constructed in code often use the same object for several
+    /// states, which must still only run its frames once.
+    fn state_children(self) -> Vec<DisplayObject<'gc>> {
+        let button = self.0.read();
+        let mut children: Vec<DisplayObject<'gc>> = Vec::with_capacity(4);
+        let states = [
+            button.hit_area,
+            button.up_state,
+            button.down_state,
+            button.over_state,
+        ];
+        for child in states.into_iter().flatten() {
+            if !children.iter().any(|c| DisplayObject::ptr_eq(*c, child)) {
+                children.push(child);
+            }
+        }
+        children
+    }
+
     /// Get the display object that represents a particular button state.
     pub fn get_state_child(self, state: swf::ButtonState) -> Option<DisplayObject<'gc>> {
         match state {
@@ -324,8 +335,12 @@ impl<'gc> Avm2Button<'gc> {
             }
         }
 
+        // The old child may still be displayed for the current state.
         if let Some(old_state_child) = old_state_child {
-            old_state_child.set_parent(context, None);
+            let cur_state_child = self.get_state_child(self.0.read().state.into());
+            if !DisplayObject::option_ptr_eq(Some(old_state_child), cur_state_child) {
+                old_state_child.set_parent(context, None);
+            }
         }
 
         if is_cur_state {
@@ -334,7 +349,9 @@ impl<'gc> Avm2Button<'gc> {
             }
 
             if let Some(old_state_child) = old_state_child {
-                dispatch_removed_event(old_state_child, context);
+                if !DisplayObject::option_ptr_eq(Some(old_state_child), child) {
+                    dispatch_removed_event(old_state_child, context);
+                }
             }
 
             if let Some(child) = child {
@@ -422,46 +439,14 @@ impl<'gc> TDisplayObject<'gc> for Avm2Button<'gc> {
     }
 
     fn enter_frame(&self, context: &mut UpdateContext<'_, 'gc>) {
-        let hit_area = self.0.read().hit_area;
-        if let Some(hit_area) = hit_area {
-            hit_area.enter_frame(context);
-        }
-
-        let up_state = self.0.read().up_state;
-        if let Some(up_state) = up_state {
-            up_state.enter_frame(context);
-        }
-
-        let down_state = self.0.read().down_state;
-        if let Some(down_state) = down_state {
-            down_state.enter_frame(context);
-        }
-
-        let over_state = self.0.read().over_state;
-        if let Some(over_state) = over_state {
-            over_state.enter_frame(context);
+        for child in self.state_children() {
+            child.enter_frame(context);
         }
     }
 
     fn construct_frame(&self, context: &mut UpdateContext<'_, 'gc>) {
-        let hit_area = self.0.read().hit_area;
-        if let Some(hit_area) = hit_area {
-            hit_area.construct_frame(context);
-        }
-
-        let up_state = self.0.read().up_state;
-        if let Some(up_state) = up_state {
-            up_state.construct_frame(context);
-        }
-
-        let down_state = self.0.read().down_state;
-        if let Some(down_state) = down_state {
-            down_state.construct_frame(context);
-        }
-
-        let over_state = self.0.read().over_state;
-        if let Some(over_state) = over_state {
-            over_state.construct_frame(context);
+        for child in self.state_children() {
+            child.construct_frame(context);
         }
 
         let needs_avm2_construction = self.0.read().object.is_none();
@@ -568,24 +553,8 @@ impl<'gc> TDisplayObject<'gc> for Avm2Button<'gc> {
     }
 
     fn run_frame_scripts(self, context: &mut UpdateContext<'_, 'gc>) {
-        let hit_area = self.0.read().hit_area;
-        if let Some(hit_area) = hit_area {
-            hit_area.run_frame_scripts(context);
-        }
-
-        let up_state = self.0.read().up_state;
-        if let Some(up_state) = up_state {
-            up_state.run_frame_scripts(context);
-        }
-
-        let down_state = self.0.read().down_state;
-        if let Some(down_state) = down_state {
-            down_state.run_frame_scripts(context);
-        }
-
-        let over_state = self.0.read().over_state;
-        if let Some(over_state) = over_state {
-            over_state.run_frame_scripts(context);
+        for child in self.state_children() {
+            child.run_frame_scripts(context);
         }
     }
 
@@ -703,6 +672,18 @@ impl<'gc> TInteractiveObject<'gc> for Avm2Button<'gc> {
         self.into()
     }
 
+    fn tracks_as_menu(self) -> bool {
+        self.0.read().tracking == ButtonTracking::Menu
+    }
+
+    fn set_tracks_as_menu(self, mc: MutationContext<'gc, '_>, value: bool) {
+        self.0.write(mc).tracking = if value {
+            ButtonTracking::Menu
+        } else {
+            ButtonTracking::Push
+        };
+    }
+
     fn filter_clip_event(self, event: ClipEvent) -> ClipEventResult {
         if !self.visible() {
             return ClipEventResult::NotHandled;
@@ -744,7 +725,14 @@ impl<'gc> TInteractiveObject<'gc> for Avm2Button<'gc> {
         // Translate the clip event to a button event, based on how the button state changes.
         let static_data = write.static_data;
         let static_data = static_data.read();
+        let is_menu = write.tracking == ButtonTracking::Menu;
         let (new_state, sound) = match event {
+            ClipEvent::DragOut { .. } if is_menu => {
+                (ButtonState::Up, static_data.over_to_up_sound.as_ref())
+            }
+            ClipEvent::DragOver { .. } if is_menu => {
+                (ButtonState::Down, static_data.over_to_down_sound.as_ref())
+            }
             ClipEvent::DragOut { .. } => (ButtonState::Over, None),
             ClipEvent::DragOver { .. } => (ButtonState::Down, None),
             ClipEvent::Press => (ButtonState::Down, static_data.over_to_down_sound.as_ref()),
@@ -811,8 +799,7 @@ impl<'gc> TInteractiveObject<'gc> for Avm2Button<'gc> {
     }
 
     fn mouse_cursor(self, _context: &mut UpdateContext<'_, 'gc>) -> MouseCursor {
-        // TODO: Should we also need to check for the `enabled` property like AVM1 buttons?
-        if self.use_hand_cursor() {
+        if self.use_hand_cursor() && self.enabled() {
             MouseCursor::Hand
         } else {
             MouseCursor::Arrow
@@ -828,7 +815,7 @@ impl<'gc> Avm2ButtonData<'gc> {
                 .library_for_movie_mut(self.movie())
                 .get_sound(*id)
             {
-                let _ = context.start_sound(sound_handle, sound_info, None, None);
+                context.sync_sound(sound_handle, sound_info, None);
             }
         }
     }
@@ -7,16 +7,89 @@ use crate::avm2::{
 };
 use crate::bitmap::bitmap_data::BitmapDataWrapper;
 use crate::context::{RenderContext, UpdateContext};
+use crate::display_object::stage::ParseEnumError;
 use crate::display_object::{DisplayObjectBase, DisplayObjectPtr, TDisplayObject};
 use crate::prelude::*;
+use crate::string::{FromWStr, WStr};
 use crate::tag_utils::SwfMovie;
 use crate::vminterface::Instantiator;
 use core::fmt;
 use gc_arena::{Collect, GcCell, MutationContext};
-use ruffle_render::bitmap::BitmapFormat;
+use ruffle_render::bitmap::{BitmapFormat, PixelRegion};
 use std::cell::{Ref, RefMut};
+use std::fmt::{Display, Formatter};
 use std::sync::Arc;
 
+/// Controls whether a `Bitmap` is snapped to the nearest pixel when rendered.
+///
+/// Snapping avoids blurry or shimmering bitmaps when they're placed at sub-pixel positions.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Collect)]
+#[collect(require_static)]
+pub enum PixelSnapping {
+    /// The bitmap is always drawn where it's positioned.
+    #[default]
+    Never,
+
+    /// The bitmap is always snapped to the nearest pixel, regardless of its transform.
+    Always,
+
+    /// The bitmap is snapped to the nearest pixel, and drawn at its original size,
+    /// if it isn't rotated or skewed and is drawn at a scale between 99.9% and 100.1%.
+    Auto,
+}
+
+impl PixelSnapping {
+    /// Adjusts the world matrix of a bitmap according to this snapping mode.
+    pub fn apply(self, matrix: &mut Matrix) {
+        match self {
+            PixelSnapping::Never => return,
+            PixelSnapping::Always => (),
+            PixelSnapping::Auto => {
+                let is_unscaled = |scale: f32| (scale - 1.0).abs() <= 0.001;
+                if matrix.b != 0.0
+                    || matrix.c != 0.0
+                    || !is_unscaled(matrix.a)
+                    || !is_unscaled(matrix.d)
+                {
+                    return;
+                }
+                matrix.a = 1.0;
+                matrix.d = 1.0;
+            }
+        }
+        matrix.tx = Twips::from_pixels(matrix.tx.to_pixels().round());
+        matrix.ty = Twips::from_pixels(matrix.ty.to_pixels().round());
+    }
+}
+
+impl Display for PixelSnapping {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        // Match string values returned by AS.
+        let s = match *self {
+            PixelSnapping::Never => "never",
+            PixelSnapping::Always => "always",
+            PixelSnapping::Auto => "auto",
+        };
+        f.write_str(s)
+    }
+}
+
+impl FromWStr for PixelSnapping {
+    type Err = ParseEnumError;
+
+    fn from_wstr(s: &WStr) -> Result<Self, Self::Err> {
+        if s == WStr::from_units(b"never") {
+            Ok(PixelSnapping::Never)
+        } else if s == WStr::from_units(b"always") {
+            Ok(PixelSnapping::Always)
+        } else if s == WStr::from_units(b"auto") {
+            Ok(PixelSnapping::Auto)
+        } else {
+            Err(ParseEnumError)
+        }
+    }
+}
+
 /// The AVM2 class for the Bitmap associated with this object.
 ///
 /// Bitmaps may be associated with either a `Bitmap` or a `BitmapData`
@@ -81,6 +154,9 @@ pub struct BitmapGraphicData<'gc> {
     /// Whether or not bitmap smoothing is enabled.
     smoothing: bool,
 
+    /// How this bitmap is snapped to the pixel grid when rendered.
+    pixel_snapping: PixelSnapping,
+
     /// The AVM2 side of this object.
     ///
     /// AVM1 code cannot directly reference `Bitmap`s, so this does not support
@@ -120,6 +196,7 @@ impl<'gc> Bitmap<'gc> {
                 width,
                 height,
                 smoothing,
+                pixel_snapping: PixelSnapping::default(),
                 avm2_object: None,
                 avm2_bitmap_class: BitmapClass::NoSubclass,
                 movie: context.swf.clone(),
@@ -242,6 +319,14 @@ impl<'gc> Bitmap<'gc> {
     pub fn set_smoothing(self, mc: MutationContext<'gc, '_>, smoothing: bool) {
         self.0.write(mc).smoothing = smoothing;
     }
+
+    pub fn pixel_snapping(self) -> PixelSnapping {
+        self.0.read().pixel_snapping
+    }
+
+    pub fn set_pixel_snapping(self, mc: MutationContext<'gc, '_>, pixel_snapping: PixelSnapping) {
+        self.0.write(mc).pixel_snapping = pixel_snapping;
+    }
 }
 
 impl<'gc> TDisplayObject<'gc> for Bitmap<'gc> {
@@ -309,6 +394,39 @@ impl<'gc> TDisplayObject<'gc> for Bitmap<'gc> {
         }
     }
 
+    fn hit_test_shape(
+        &self,
+        _context: &mut UpdateContext<'_, 'gc>,
+        point: Point<Twips>,
+        options: HitTestOptions,
+    ) -> bool {
+        if !self.hit_test_bounds(point) {
+            return false;
+        }
+        if !options.contains(HitTestOptions::BITMAP_ALPHA) {
+            return true;
+        }
+
+        // Only the pixels that aren't fully transparent are hit.
+        let Some(local_matrix) = self.global_to_local_matrix() else {
+            return false;
+        };
+        let point = local_matrix * point;
+        let bitmap_data = self.bitmap_data_wrapper();
+        let x = point.x.to_pixels().floor();
+        let y = point.y.to_pixels().floor();
+        if x < 0.0
+            || y < 0.0
+            || x >= f64::from(bitmap_data.width())
+            || y >= f64::from(bitmap_data.height())
+        {
+            return false;
+        }
+        let (x, y) = (x as u32, y as u32);
+        let read = bitmap_data.read_area(PixelRegion::for_pixel(x, y));
+        read.get_pixel32_raw(x, y).alpha() > 0
+    }
+
     fn render_self(&self, context: &mut RenderContext<'_, 'gc>) {
         if !context.is_offscreen && !self.world_bounds().intersects(&context.stage.view_bounds()) {
             // Off-screen; culled
@@ -316,9 +434,11 @@ impl<'gc> TDisplayObject<'gc> for Bitmap<'gc> {
         }
 
         let bitmap_data = self.0.read();
+        let mut transform = context.transform_stack.transform();
+        bitmap_data.pixel_snapping.apply(&mut transform.matrix);
         bitmap_data
             .bitmap_data
-            .render(bitmap_data.smoothing, context);
+            .render(bitmap_data.smoothing, transform, context);
     }
 
     fn object2(&self) -> Avm2Value<'gc> {
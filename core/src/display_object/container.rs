@@ -25,6 +25,10 @@ pub fn dispatch_removed_from_stage_event<'gc>(
     context: &mut UpdateContext<'_, 'gc>,
 ) {
     if let Avm2Value::Object(object) = child.object2() {
+        if let Some(leak_detector) = &mut context.avm2.leak_detector {
+            leak_detector.removed_from_stage(object);
+        }
+
         let removed_evt = Avm2EventObject::bare_default_event(context, "removedFromStage");
         Avm2::dispatch_event(context, removed_evt, object);
     }
@@ -58,6 +62,10 @@ pub fn dispatch_added_to_stage_event_only<'gc>(
     context: &mut UpdateContext<'_, 'gc>,
 ) {
     if let Avm2Value::Object(object) = child.object2() {
+        if let Some(leak_detector) = &mut context.avm2.leak_detector {
+            leak_detector.added_to_stage(object);
+        }
+
         let added_evt = Avm2EventObject::bare_default_event(context, "addedToStage");
         Avm2::dispatch_event(context, added_evt, object);
     }
@@ -234,9 +242,9 @@ pub trait TDisplayObjectContainer<'gc>:
     /// Insert a child display object into the container at a specific position
     /// in the render list.
     ///
-    /// Callers of this method should be aware that reordering items onto or off of the
-    /// render list can make further depth list manipulations (e.g. from the
-    /// timeline) produce unusual results.
+    /// The child is not put onto the depth list, and is flagged as being
+    /// placed by script. Further timeline manipulations at the child's depth
+    /// will insert new children next to it instead of replacing it.
     fn insert_at_index(
         &mut self,
         context: &mut UpdateContext<'_, 'gc>,
@@ -268,6 +276,7 @@ pub trait TDisplayObjectContainer<'gc>:
 
         self.raw_container_mut(context.gc_context)
             .insert_at_id(child, index);
+        child.set_placed_by_script(context.gc_context, true);
 
         if parent_changed {
             dispatch_added_event(this, child, child_was_on_stage, context);
@@ -276,15 +285,23 @@ pub trait TDisplayObjectContainer<'gc>:
 
     /// Swap two children in the render list.
     ///
-    /// No changes to the depth or render lists are made by this function.
+    /// No changes to the depth list are made by this function. Both children
+    /// are flagged as being placed by script, as with `insert_at_index`.
     fn swap_at_index(
         &mut self,
         context: &mut UpdateContext<'_, 'gc>,
         index1: usize,
         index2: usize,
     ) {
-        self.raw_container_mut(context.gc_context)
-            .swap_at_id(index1, index2);
+        let mut write = self.raw_container_mut(context.gc_context);
+        let children = (write.get_id(index1), write.get_id(index2));
+        write.swap_at_id(index1, index2);
+        drop(write);
+
+        if let (Some(child1), Some(child2)) = children {
+            child1.set_placed_by_script(context.gc_context, true);
+            child2.set_placed_by_script(context.gc_context, true);
+        }
     }
 
     /// Remove (and unloads) a child display object from this container's render and depth lists.
@@ -585,11 +602,7 @@ impl<'gc> ChildContainer<'gc> {
     /// This returns `true` if the child was successfully removed, and `false`
     /// if no list alterations were made.
     fn remove_child_from_render_list(&mut self, child: DisplayObject<'gc>) -> bool {
-        let render_list_position = self
-            .render_list
-            .iter()
-            .position(|x| DisplayObject::ptr_eq(*x, child));
-        if let Some(position) = render_list_position {
+        if let Some(position) = self.render_list_position(child) {
             self.render_list.remove(position);
             true
         } else {
@@ -616,11 +629,7 @@ impl<'gc> ChildContainer<'gc> {
     ) -> Option<DisplayObject<'gc>> {
         let prev_child = self.insert_child_into_depth_list(depth, child);
         if let Some(prev_child) = prev_child {
-            if let Some(position) = self
-                .render_list
-                .iter()
-                .position(|x| DisplayObject::ptr_eq(*x, prev_child))
-            {
+            if let Some(position) = self.render_list_position(prev_child) {
                 if !prev_child.placed_by_script() {
                     self.replace_id(position, child);
                     Some(prev_child)
@@ -636,28 +645,20 @@ impl<'gc> ChildContainer<'gc> {
                 None
             }
         } else {
-            let above = self
+            // Children on the depth list may have been taken off the render
+            // list by script, so skip over them until we find one that isn't.
+            let above_position = self
                 .depth_list
                 .range((Bound::Excluded(depth), Bound::Unbounded))
-                .map(|(_, v)| *v)
-                .next();
+                .find_map(|(_, above_child)| self.render_list_position(*above_child));
 
-            if let Some(above_child) = above {
-                if let Some(position) = self
-                    .render_list
-                    .iter()
-                    .position(|x| DisplayObject::ptr_eq(*x, above_child))
-                {
-                    self.insert_id(position, child);
-                    None
-                } else {
-                    self.push_id(child);
-                    None
-                }
+            if let Some(position) = above_position {
+                self.insert_id(position, child);
             } else {
                 self.push_id(child);
-                None
             }
+
+            None
         }
     }
 
@@ -723,6 +724,14 @@ impl<'gc> ChildContainer<'gc> {
         }
     }
 
+    /// Get the render list position (ID) of a child, if it is on the render
+    /// list.
+    fn render_list_position(&self, child: DisplayObject<'gc>) -> Option<usize> {
+        self.render_list
+            .iter()
+            .position(|x| DisplayObject::ptr_eq(*x, child))
+    }
+
     /// Get a child by it's render list position (ID).
     fn get_id(&self, id: usize) -> Option<DisplayObject<'gc>> {
         self.render_list.get(id).copied()
@@ -771,11 +780,7 @@ impl<'gc> ChildContainer<'gc> {
     /// All children at or after the given ID will be shifted down in the
     /// render list. The child will *not* be put onto the depth list.
     fn insert_at_id(&mut self, child: DisplayObject<'gc>, id: usize) {
-        if let Some(old_id) = self
-            .render_list
-            .iter()
-            .position(|x| DisplayObject::ptr_eq(*x, child))
-        {
+        if let Some(old_id) = self.render_list_position(child) {
             match old_id.cmp(&id) {
                 Ordering::Less if id < self.render_list.len() => {
                     self.render_list[old_id..=id].rotate_left(1)
@@ -808,6 +813,10 @@ impl<'gc> ChildContainer<'gc> {
     /// within the render list at a position after the closest previous child
     /// in the depth list.
     ///
+    /// Children that were placed by script may not be on the depth list at
+    /// their current depth; in that case, whatever child is at that depth is
+    /// left alone.
+    ///
     /// `parent` should be the display object that owns this container.
     fn swap_at_depth(
         &mut self,
@@ -827,37 +836,32 @@ impl<'gc> ChildContainer<'gc> {
             prev_child.set_transformed_by_script(context.gc_context, true);
             self.depth_list.insert(prev_depth, prev_child);
 
-            let prev_position = self
-                .render_list
-                .iter()
-                .position(|x| DisplayObject::ptr_eq(*x, prev_child))
-                .unwrap();
-            let next_position = self
-                .render_list
-                .iter()
-                .position(|x| DisplayObject::ptr_eq(*x, child))
-                .unwrap();
-            self.render_list.swap(prev_position, next_position);
+            match (
+                self.render_list_position(prev_child),
+                self.render_list_position(child),
+            ) {
+                (Some(prev_position), Some(next_position)) => {
+                    self.render_list.swap(prev_position, next_position)
+                }
+                _ => tracing::error!(
+                    "ChildContainer::swap_at_depth: Swapped children are not in render list"
+                ),
+            }
         } else {
-            self.depth_list.remove(&prev_depth);
-
-            let old_position = self
-                .render_list
-                .iter()
-                .position(|x| DisplayObject::ptr_eq(*x, child))
-                .unwrap();
-            self.render_list.remove(old_position);
-
-            if let Some((_, below_child)) = self.depth_list.range(..depth).rev().next() {
-                let new_position = self
-                    .render_list
-                    .iter()
-                    .position(|x| DisplayObject::ptr_eq(*x, *below_child))
-                    .unwrap();
-                self.render_list.insert(new_position + 1, child);
-            } else {
-                self.render_list.insert(0, child);
+            if matches!(self.depth_list.get(&prev_depth), Some(c) if DisplayObject::ptr_eq(*c, child))
+            {
+                self.depth_list.remove(&prev_depth);
             }
+
+            self.remove_child_from_render_list(child);
+
+            let new_position = self
+                .depth_list
+                .range(..depth)
+                .rev()
+                .find_map(|(_, below_child)| self.render_list_position(*below_child))
+                .map_or(0, |position| position + 1);
+            self.render_list.insert(new_position, child);
         }
     }
 
@@ -983,3 +987,219 @@ impl<'gc> DoubleEndedIterator for RenderIter<'gc> {
 }
 
 impl<'gc> ExactSizeIterator for RenderIter<'gc> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tag_utils::SwfMovie;
+
+    fn with_container<F>(test: F)
+    where
+        F: for<'a, 'gc> FnOnce(&mut UpdateContext<'a, 'gc>, MovieClip<'gc>),
+    {
+        let movie = SwfMovie::empty(10);
+        let player = crate::player::PlayerBuilder::new()
+            .with_movie(movie)
+            .build();
+        let mut player = player.lock().unwrap();
+        player.mutate_with_update_context(|context| {
+            let container = MovieClip::new(context.swf.clone(), context.gc_context);
+            test(context, container);
+        })
+    }
+
+    fn new_child<'gc>(context: &mut UpdateContext<'_, 'gc>) -> DisplayObject<'gc> {
+        MovieClip::new(context.swf.clone(), context.gc_context).into()
+    }
+
+    fn assert_render_list<'gc>(container: MovieClip<'gc>, expected: &[DisplayObject<'gc>]) {
+        let actual: Vec<_> = container.iter_render_list().collect();
+        assert_eq!(actual.len(), expected.len(), "render list length");
+        for (i, (actual, expected)) in actual.iter().zip(expected).enumerate() {
+            assert!(
+                DisplayObject::ptr_eq(*actual, *expected),
+                "unexpected child at render list position {i}"
+            );
+        }
+    }
+
+    fn is_child_at_depth<'gc>(
+        container: MovieClip<'gc>,
+        depth: Depth,
+        child: DisplayObject<'gc>,
+    ) -> bool {
+        container
+            .child_by_depth(depth)
+            .map_or(false, |c| DisplayObject::ptr_eq(c, child))
+    }
+
+    #[test]
+    fn timeline_places_children_in_depth_order() {
+        with_container(|context, container| {
+            let a = new_child(context);
+            let b = new_child(context);
+            let c = new_child(context);
+            container.replace_at_depth(context, c, 3);
+            container.replace_at_depth(context, a, 1);
+            container.replace_at_depth(context, b, 2);
+
+            assert_render_list(container, &[a, b, c]);
+            assert!(is_child_at_depth(container, 2, b));
+            assert_eq!(b.depth(), 2);
+            assert_eq!(container.highest_depth(), 3);
+        });
+    }
+
+    #[test]
+    fn timeline_replaces_timeline_child() {
+        with_container(|context, container| {
+            let a = new_child(context);
+            let b = new_child(context);
+            container.replace_at_depth(context, a, 1);
+            let removed = container.replace_at_depth(context, b, 1);
+
+            assert!(removed.map_or(false, |r| DisplayObject::ptr_eq(r, a)));
+            assert!(a.parent().is_none());
+            assert_render_list(container, &[b]);
+            assert!(is_child_at_depth(container, 1, b));
+        });
+    }
+
+    #[test]
+    fn timeline_does_not_replace_scripted_child() {
+        with_container(|context, container| {
+            let a = new_child(context);
+            let b = new_child(context);
+            container.replace_at_depth(context, a, 1);
+            a.set_placed_by_script(context.gc_context, true);
+            let removed = container.replace_at_depth(context, b, 1);
+
+            assert!(removed.is_none());
+            assert_render_list(container, &[a, b]);
+            assert!(is_child_at_depth(container, 1, b));
+        });
+    }
+
+    #[test]
+    fn scripted_insert_is_not_on_depth_list() {
+        with_container(|context, mut container| {
+            let a = new_child(context);
+            let b = new_child(context);
+            let c = new_child(context);
+            let scripted = new_child(context);
+            container.replace_at_depth(context, a, 1);
+            container.replace_at_depth(context, c, 3);
+            container.insert_at_index(context, scripted, 1);
+
+            assert!(scripted.placed_by_script());
+            assert_render_list(container, &[a, scripted, c]);
+            assert!(is_child_at_depth(container, 1, a));
+            assert!(is_child_at_depth(container, 3, c));
+
+            // New timeline children are placed below the next child on the
+            // depth list, rather than relative to scripted children.
+            container.replace_at_depth(context, b, 2);
+            assert_render_list(container, &[a, scripted, b, c]);
+        });
+    }
+
+    #[test]
+    fn reordered_timeline_child_is_not_replaced() {
+        with_container(|context, mut container| {
+            let a = new_child(context);
+            let b = new_child(context);
+            let c = new_child(context);
+            container.replace_at_depth(context, a, 1);
+            container.replace_at_depth(context, b, 2);
+            container.insert_at_index(context, a, 1);
+
+            assert!(a.placed_by_script());
+            assert!(!b.placed_by_script());
+            assert_render_list(container, &[b, a]);
+
+            let removed = container.replace_at_depth(context, c, 1);
+            assert!(removed.is_none());
+            assert_render_list(container, &[b, a, c]);
+            assert!(is_child_at_depth(container, 1, c));
+        });
+    }
+
+    #[test]
+    fn swap_at_index_marks_children_as_scripted() {
+        with_container(|context, mut container| {
+            let a = new_child(context);
+            let b = new_child(context);
+            let c = new_child(context);
+            container.replace_at_depth(context, a, 1);
+            container.replace_at_depth(context, b, 2);
+            container.replace_at_depth(context, c, 3);
+            container.swap_at_index(context, 0, 2);
+
+            assert_render_list(container, &[c, b, a]);
+            assert!(a.placed_by_script());
+            assert!(!b.placed_by_script());
+            assert!(c.placed_by_script());
+            assert!(is_child_at_depth(container, 1, a));
+            assert!(is_child_at_depth(container, 3, c));
+        });
+    }
+
+    #[test]
+    fn swap_at_depth_with_occupied_depth() {
+        with_container(|context, mut container| {
+            let a = new_child(context);
+            let b = new_child(context);
+            let c = new_child(context);
+            container.replace_at_depth(context, a, 1);
+            container.replace_at_depth(context, b, 2);
+            container.replace_at_depth(context, c, 3);
+            container.swap_at_depth(context, a, 3);
+
+            assert_render_list(container, &[c, b, a]);
+            assert_eq!(a.depth(), 3);
+            assert_eq!(c.depth(), 1);
+            assert!(is_child_at_depth(container, 1, c));
+            assert!(is_child_at_depth(container, 3, a));
+        });
+    }
+
+    #[test]
+    fn swap_at_depth_to_empty_depth() {
+        with_container(|context, mut container| {
+            let a = new_child(context);
+            let b = new_child(context);
+            let c = new_child(context);
+            container.replace_at_depth(context, a, 1);
+            container.replace_at_depth(context, b, 2);
+            container.replace_at_depth(context, c, 10);
+            container.swap_at_depth(context, a, 5);
+
+            assert_render_list(container, &[b, a, c]);
+            assert_eq!(a.depth(), 5);
+            assert!(container.child_by_depth(1).is_none());
+            assert!(is_child_at_depth(container, 5, a));
+
+            container.swap_at_depth(context, c, -1);
+            assert_render_list(container, &[c, b, a]);
+        });
+    }
+
+    #[test]
+    fn swap_at_depth_keeps_timeline_child_sharing_depth() {
+        with_container(|context, mut container| {
+            let a = new_child(context);
+            let scripted = new_child(context);
+            container.replace_at_depth(context, a, 0);
+            container.insert_at_index(context, scripted, 0);
+            assert_eq!(scripted.depth(), 0);
+
+            // The scripted child is not on the depth list, so moving it must
+            // not remove the timeline child that shares its depth.
+            container.swap_at_depth(context, scripted, 4);
+
+            assert_render_list(container, &[a, scripted]);
+            assert!(is_child_at_depth(container, 0, a));
+            assert!(is_child_at_depth(container, 4, scripted));
+        });
+    }
+}
@@ -19,7 +19,9 @@ use crate::display_object::{DisplayObjectBase, DisplayObjectPtr, TDisplayObject}
 use crate::drawing::Drawing;
 use crate::events::{ButtonKeyCode, ClipEvent, ClipEventResult, KeyCode};
 use crate::font::{round_down_to_pixel, Glyph, TextRenderSettings};
-use crate::html::{BoxBounds, FormatSpans, LayoutBox, LayoutContent, LayoutMetrics, TextFormat};
+use crate::html::{
+    BoxBounds, FormatSpans, LayoutBox, LayoutContent, LayoutMetrics, StyleSheet, TextFormat,
+};
 use crate::prelude::*;
 use crate::string::{utils as string_utils, AvmString, SwfStrExt as _, WStr, WString};
 use crate::tag_utils::SwfMovie;
@@ -141,6 +143,16 @@ pub struct EditTextData<'gc> {
 
     /// Flags indicating the text field's settings.
     flags: EditTextFlag,
+
+    /// The style sheet applied to the HTML text, if any.
+    style_sheet: Option<StyleSheet<'gc>>,
+
+    /// The AVM object of the style sheet.
+    style_sheet_object: Option<AvmObject<'gc>>,
+
+    /// The HTML text last set, which is returned as is while a style sheet is applied.
+    #[collect(require_static)]
+    html_source: Option<WString>,
 }
 
 // TODO: would be nicer to compute (and return) this during layout, instead of afterwards
@@ -203,7 +215,7 @@ impl<'gc> EditText<'gc> {
         let text = swf_tag.initial_text().unwrap_or_default().decode(encoding);
 
         let mut text_spans = if swf_tag.is_html() {
-            FormatSpans::from_html(&text, default_format, swf_tag.is_multiline())
+            FormatSpans::from_html(&text, default_format, None, swf_tag.is_multiline())
         } else {
             FormatSpans::from_text(text.into_owned(), default_format)
         };
@@ -282,6 +294,9 @@ impl<'gc> EditText<'gc> {
                 line_data,
                 scroll: 1,
                 max_chars: swf_tag.max_length().unwrap_or_default() as i32,
+                style_sheet: None,
+                style_sheet_object: None,
+                html_source: None,
             },
         ));
 
@@ -336,6 +351,7 @@ impl<'gc> EditText<'gc> {
         let mut edit_text = self.0.write(context.gc_context);
         let default_format = edit_text.text_spans.default_format().clone();
         edit_text.text_spans = FormatSpans::from_text(text.into(), default_format);
+        edit_text.html_source = None;
         drop(edit_text);
 
         self.relayout(context);
@@ -343,7 +359,11 @@ impl<'gc> EditText<'gc> {
 
     pub fn html_text(self) -> WString {
         if self.is_html() {
-            self.0.read().text_spans.to_html()
+            let read = self.0.read();
+            if let (Some(_), Some(html_source)) = (read.style_sheet, &read.html_source) {
+                return html_source.clone();
+            }
+            read.text_spans.to_html()
         } else {
             // Non-HTML text fields always return plain text.
             self.text()
@@ -357,8 +377,10 @@ impl<'gc> EditText<'gc> {
             write.text_spans = FormatSpans::from_html(
                 text,
                 default_format,
+                write.style_sheet,
                 write.flags.contains(EditTextFlag::MULTILINE),
             );
+            write.html_source = Some(text.into());
             drop(write);
 
             self.relayout(context);
@@ -549,13 +571,37 @@ impl<'gc> EditText<'gc> {
         text: &WStr,
         context: &mut UpdateContext<'_, 'gc>,
     ) {
-        self.0
-            .write(context.gc_context)
-            .text_spans
-            .replace_text(from, to, text, None);
+        let mut write = self.0.write(context.gc_context);
+        write.text_spans.replace_text(from, to, text, None);
+        write.html_source = None;
+        drop(write);
         self.relayout(context);
     }
 
+    /// The AVM object of the style sheet applied to the HTML text, if any.
+    pub fn style_sheet_object(self) -> Option<AvmObject<'gc>> {
+        self.0.read().style_sheet_object
+    }
+
+    /// Applies a style sheet to the HTML text, or removes it.
+    ///
+    /// The HTML text last set is formatted again with the new style sheet.
+    pub fn set_style_sheet(
+        self,
+        context: &mut UpdateContext<'_, 'gc>,
+        style_sheet: Option<(AvmObject<'gc>, StyleSheet<'gc>)>,
+    ) {
+        let mut write = self.0.write(context.gc_context);
+        write.style_sheet_object = style_sheet.map(|(object, _)| object);
+        write.style_sheet = style_sheet.map(|(_, style_sheet)| style_sheet);
+        let html_source = write.html_source.clone();
+        drop(write);
+
+        if let Some(html_source) = html_source {
+            self.set_html_text(&html_source, context);
+        }
+    }
+
     /// Construct a base text transform for a particular `EditText` span.
     ///
     /// This `text_transform` is separate from and relative to the base
@@ -724,6 +770,12 @@ impl<'gc> EditText<'gc> {
         edit_text.hscroll = 0.0;
         edit_text.scroll = 1;
 
+        // Keep the selection within the new text, so that `Selection` reports valid indices.
+        let length = edit_text.text_spans.text().len();
+        if let Some(selection) = &mut edit_text.selection {
+            selection.clamp(length);
+        }
+
         if autosize != AutoSizeMode::None {
             if !is_word_wrap {
                 // The edit text's bounds needs to have the padding baked in.
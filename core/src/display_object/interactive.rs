@@ -73,6 +73,10 @@ bitflags! {
 
         /// Whether this `InteractiveObject` accepts double-clicks.
         const DOUBLE_CLICK_ENABLED = 1 << 1;
+
+        /// Whether this `InteractiveObject` receives mouse events while the
+        /// mouse button was pressed on another object.
+        const TRACK_AS_MENU = 1 << 2;
     }
 }
 
@@ -83,6 +87,10 @@ pub struct InteractiveObjectBase<'gc> {
     flags: InteractiveObjectFlags,
     context_menu: Avm2Value<'gc>,
 
+    /// Whether a yellow rectangle is drawn around this object when it has
+    /// keyboard focus, or `None` to use the setting of the stage.
+    focus_rect: Option<bool>,
+
     /// The time of the last click registered on this object.
     ///
     /// This should be cleared to `None` when the mouse leaves the current
@@ -97,6 +105,7 @@ impl<'gc> Default for InteractiveObjectBase<'gc> {
             base: Default::default(),
             flags: InteractiveObjectFlags::MOUSE_ENABLED,
             context_menu: Avm2Value::Null,
+            focus_rect: None,
             last_click: None,
         }
     }
@@ -162,6 +171,32 @@ pub trait TInteractiveObject<'gc>:
         self.raw_interactive_mut(mc).context_menu = value;
     }
 
+    /// Check if the interactive object receives mouse events while the mouse
+    /// button was pressed on another object, like the items of a menu.
+    fn tracks_as_menu(self) -> bool {
+        self.raw_interactive()
+            .flags
+            .contains(InteractiveObjectFlags::TRACK_AS_MENU)
+    }
+
+    /// Set if the interactive object receives mouse events while the mouse
+    /// button was pressed on another object.
+    fn set_tracks_as_menu(self, mc: MutationContext<'gc, '_>, value: bool) {
+        self.raw_interactive_mut(mc)
+            .flags
+            .set(InteractiveObjectFlags::TRACK_AS_MENU, value)
+    }
+
+    /// Whether a focus rectangle is drawn around this object, or `None` if
+    /// the setting of the stage is used.
+    fn focus_rect(self) -> Option<bool> {
+        self.raw_interactive().focus_rect
+    }
+
+    fn set_focus_rect(self, mc: MutationContext<'gc, '_>, value: Option<bool>) {
+        self.raw_interactive_mut(mc).focus_rect = value;
+    }
+
     /// Filter the incoming clip event.
     ///
     /// If this returns `Handled`, then the rest of the event handling
@@ -154,17 +154,16 @@ impl<'gc> TDisplayObject<'gc> for MorphShape<'gc> {
         _options: HitTestOptions,
     ) -> bool {
         if self.world_bounds().contains(point) {
-            if let Some(frame) = self.0.read().static_data.frames.borrow().get(&self.ratio()) {
-                let Some(local_matrix) = self.global_to_local_matrix() else { return false; };
-                let point = local_matrix * point;
-                return ruffle_render::shape_utils::shape_hit_test(
-                    &frame.shape,
-                    (point.x, point.y),
-                    &local_matrix,
-                );
-            } else {
-                tracing::warn!("Missing ratio for morph shape");
-            }
+            let Some(local_matrix) = self.global_to_local_matrix() else { return false; };
+            let point = local_matrix * point;
+            let this = self.0.read();
+            // The frame may not have been rendered yet, e.g. if the ratio was just changed.
+            let frame = this.static_data.get_frame(this.ratio);
+            return ruffle_render::shape_utils::shape_hit_test(
+                &frame.shape,
+                (point.x, point.y),
+                &local_matrix,
+            );
         }
 
         false
@@ -333,6 +333,15 @@ impl<'gc> MovieClip<'gc> {
         mc
     }
 
+    /// Whether this clip is the root of an AVM1 movie that was loaded into an
+    /// AVM2 movie.
+    ///
+    /// Such clips are exposed to AVM2 as `AVM1Movie` objects, which only
+    /// allow access to `DisplayObject` properties and events.
+    pub fn is_avm1_movie(self) -> bool {
+        self.is_root() && !self.movie().is_action_script_3()
+    }
+
     /// Replace the current MovieClipData with a completely new SwfMovie.
     ///
     /// Playback will start at position zero, any existing streamed audio will
@@ -376,6 +385,24 @@ impl<'gc> MovieClip<'gc> {
         drop(mc);
     }
 
+    /// Unloads the movie of this clip, as done by `unloadMovie` in AVM1.
+    ///
+    /// Clips keep their depth, instance name and `_lockroot` and are left empty,
+    /// except levels other than `_level0`, which are removed from the stage entirely.
+    pub fn avm1_unload_movie(mut self, context: &mut UpdateContext<'_, 'gc>) {
+        let is_level = self.parent().map_or(false, |p| p.as_stage().is_some());
+        if is_level && self.depth() != 0 {
+            // Pending removals are only processed below `_level0`, so a level can't
+            // wait for the next frame like a clip with an unload handler would.
+            // It's removed and unloaded right away, which runs its `onUnload`.
+            let stage = context.stage;
+            stage.remove_child_directly(context, self.into());
+        } else {
+            self.avm1_unload(context);
+            self.replace_with_movie(context, None, None);
+        }
+    }
+
     /// Preload a chunk of the movie.
     ///
     /// A "chunk" is an implementor-chosen number of tags that are parsed
@@ -419,6 +446,15 @@ impl<'gc> MovieClip<'gc> {
         };
         let mut reader = data.read_from(next_preload_chunk);
 
+        if next_preload_chunk == 0 {
+            // Decompress the bitmaps of the movie in the background, ahead of preloading.
+            let movie = self.movie();
+            context
+                .library
+                .library_for_movie_mut(movie.clone())
+                .predecode_bitmaps(SwfSlice::from(movie));
+        }
+
         if let Some(cur_preload_symbol) = preload_symbol {
             match context
                 .library
@@ -588,6 +624,10 @@ impl<'gc> MovieClip<'gc> {
                 TagCode::DefineSceneAndFrameLabelData => {
                     self.scene_and_frame_labels(reader, &mut static_data)
                 }
+                TagCode::DefineScalingGrid => self
+                    .0
+                    .write(context.gc_context)
+                    .define_scaling_grid(context, reader),
                 TagCode::ExportAssets => self
                     .0
                     .write(context.gc_context)
@@ -735,7 +775,10 @@ impl<'gc> MovieClip<'gc> {
         let data = reader.read_slice_to_end();
         if !data.is_empty() {
             let movie = self.movie();
-            let domain = context.library.library_for_movie_mut(movie).avm2_domain();
+            let domain = context
+                .library
+                .library_for_movie_mut(movie.clone())
+                .avm2_domain();
 
             // DoAbc tag seems to be equivalent to a DoAbc2 with Lazy flag set
             if let Err(e) = Avm2::do_abc(
@@ -744,6 +787,7 @@ impl<'gc> MovieClip<'gc> {
                 None,
                 swf::DoAbc2Flag::LAZY_INITIALIZE,
                 domain,
+                Some(movie),
             ) {
                 let mut activation = Avm2Activation::from_nothing(context.reborrow());
                 tracing::warn!(
@@ -770,10 +814,20 @@ impl<'gc> MovieClip<'gc> {
         let do_abc = reader.read_do_abc_2()?;
         if !do_abc.data.is_empty() {
             let movie = self.movie();
-            let domain = context.library.library_for_movie_mut(movie).avm2_domain();
+            let domain = context
+                .library
+                .library_for_movie_mut(movie.clone())
+                .avm2_domain();
             let name = AvmString::new(context.gc_context, do_abc.name.decode(reader.encoding()));
 
-            if let Err(e) = Avm2::do_abc(context, do_abc.data, Some(name), do_abc.flags, domain) {
+            if let Err(e) = Avm2::do_abc(
+                context,
+                do_abc.data,
+                Some(name),
+                do_abc.flags,
+                domain,
+                Some(movie),
+            ) {
                 let mut activation = Avm2Activation::from_nothing(context.reborrow());
                 tracing::warn!(
                     "Error loading ABC file: {}",
@@ -1695,6 +1749,12 @@ impl<'gc> MovieClip<'gc> {
         self.base_mut(context.gc_context)
             .set_skip_next_enter_frame(false);
 
+        // The transforms of our children jump discontinuously across a goto,
+        // so they shouldn't be interpolated between frames.
+        for child in self.iter_render_list() {
+            child.base_mut(context.gc_context).clear_previous_matrix();
+        }
+
         // Flash gotos are tricky:
         // 1) Conceptually, a goto should act like the playhead is advancing forward or
         //    backward to a frame.
@@ -1954,6 +2014,23 @@ impl<'gc> MovieClip<'gc> {
         self.assert_expected_tag_end(context, hit_target_frame);
     }
 
+    /// Copy the properties of an `attachMovie`/`duplicateMovieClip` init
+    /// object onto a newly constructed clip.
+    fn copy_init_object(
+        activation: &mut Avm1Activation<'_, 'gc>,
+        init_object: Avm1Object<'gc>,
+        object: Avm1Object<'gc>,
+    ) {
+        // AVM1 sets keys in reverse order (compared to enumeration order),
+        // regardless of whether the clip has a registered class.
+        // This behavior is visible to setters, and some SWFs depend on it.
+        for key in init_object.get_keys(activation).into_iter().rev() {
+            if let Ok(value) = init_object.get(key, activation) {
+                let _ = object.set(key, value, activation);
+            }
+        }
+    }
+
     fn construct_as_avm1_object(
         self,
         context: &mut UpdateContext<'_, 'gc>,
@@ -1991,13 +2068,7 @@ impl<'gc> MovieClip<'gc> {
                     }
 
                     if let Some(init_object) = init_object {
-                        // AVM1 sets keys in reverse order (compared to enumeration order).
-                        // This behavior is visible to setters, and some SWFs depend on it.
-                        for key in init_object.get_keys(&mut activation).into_iter().rev() {
-                            if let Ok(value) = init_object.get(key, &mut activation) {
-                                let _ = object.set(key, value, &mut activation);
-                            }
-                        }
+                        Self::copy_init_object(&mut activation, init_object, object);
                     }
                     let _ = constructor.construct_on_existing(&mut activation, object, &[]);
                 }
@@ -2024,11 +2095,7 @@ impl<'gc> MovieClip<'gc> {
                     self.into(),
                 );
 
-                for key in init_object.get_keys(&mut activation) {
-                    if let Ok(value) = init_object.get(key, &mut activation) {
-                        let _ = object.set(key, value, &mut activation);
-                    }
-                }
+                Self::copy_init_object(&mut activation, init_object, object);
             }
 
             let mut events = Vec::new();
@@ -2081,11 +2148,14 @@ impl<'gc> MovieClip<'gc> {
         context: &mut UpdateContext<'_, 'gc>,
         display_object: DisplayObject<'gc>,
     ) {
-        let class_object = self
-            .0
-            .read()
-            .avm2_class
-            .unwrap_or_else(|| context.avm2.classes().movieclip);
+        let class_object = if self.is_avm1_movie() {
+            context.avm2.classes().avm1movie
+        } else {
+            self.0
+                .read()
+                .avm2_class
+                .unwrap_or_else(|| context.avm2.classes().movieclip)
+        };
 
         let mut constr_thing = || {
             let mut activation = Avm2Activation::from_nothing(context.reborrow());
@@ -2420,7 +2490,15 @@ impl<'gc> TDisplayObject<'gc> for MovieClip<'gc> {
             self.0.write(context.gc_context).unset_loop_queued();
 
             if needs_construction {
-                self.construct_as_avm2_object(context);
+                if self.is_avm1_movie() {
+                    // `AVM1Movie` has no constructor that script can run, so
+                    // construct our children here instead of in the supercall.
+                    for child in self.iter_render_list() {
+                        child.construct_frame(context);
+                    }
+                } else {
+                    self.construct_as_avm2_object(context);
+                }
                 self.on_construction_complete(context);
             } else {
                 // The supercall constructor for display objects is responsible
@@ -3268,6 +3346,18 @@ impl<'gc> MovieClipData<'gc> {
 
 // Preloading of definition tags
 impl<'gc, 'a> MovieClipData<'gc> {
+    /// Takes a bitmap that was already decoded ahead of preloading.
+    fn take_predecoded_bitmap(
+        &self,
+        context: &mut UpdateContext<'_, 'gc>,
+        id: CharacterId,
+    ) -> Option<ruffle_render::bitmap::Bitmap> {
+        context
+            .library
+            .library_for_movie_mut(self.movie())
+            .take_predecoded_bitmap(id)
+    }
+
     #[inline]
     fn define_bits_lossless(
         &mut self,
@@ -3276,7 +3366,14 @@ impl<'gc, 'a> MovieClipData<'gc> {
         version: u8,
     ) -> Result<(), Error> {
         let define_bits_lossless = reader.read_define_bits_lossless(version)?;
-        let bitmap = ruffle_render::utils::decode_define_bits_lossless(&define_bits_lossless)?;
+        let bitmap = match self.take_predecoded_bitmap(context, define_bits_lossless.id) {
+            Some(bitmap) => bitmap,
+            None => ruffle_render::utils::decode_define_bits_lossless(&define_bits_lossless)?,
+        };
+        context
+            .library
+            .library_for_movie_mut(self.movie())
+            .substitute_texture(define_bits_lossless.id, &bitmap);
         let bitmap = Bitmap::new(context, define_bits_lossless.id, bitmap)?;
         context
             .library
@@ -3388,12 +3485,21 @@ impl<'gc, 'a> MovieClipData<'gc> {
     ) -> Result<(), Error> {
         let id = reader.read_u16()?;
         let jpeg_data = reader.read_slice_to_end();
-        let jpeg_tables = context
+        let bitmap = match self.take_predecoded_bitmap(context, id) {
+            Some(bitmap) => bitmap,
+            None => {
+                let jpeg_tables = context
+                    .library
+                    .library_for_movie_mut(self.movie())
+                    .jpeg_tables();
+                let jpeg_data = ruffle_render::utils::glue_tables_to_jpeg(jpeg_data, jpeg_tables);
+                ruffle_render::utils::decode_define_bits_jpeg(&jpeg_data, None)?
+            }
+        };
+        context
             .library
             .library_for_movie_mut(self.movie())
-            .jpeg_tables();
-        let jpeg_data = ruffle_render::utils::glue_tables_to_jpeg(jpeg_data, jpeg_tables);
-        let bitmap = ruffle_render::utils::decode_define_bits_jpeg(&jpeg_data, None)?;
+            .substitute_texture(id, &bitmap);
         let bitmap = Bitmap::new(context, id, bitmap)?;
         context
             .library
@@ -3410,7 +3516,14 @@ impl<'gc, 'a> MovieClipData<'gc> {
     ) -> Result<(), Error> {
         let id = reader.read_u16()?;
         let jpeg_data = reader.read_slice_to_end();
-        let bitmap = ruffle_render::utils::decode_define_bits_jpeg(jpeg_data, None)?;
+        let bitmap = match self.take_predecoded_bitmap(context, id) {
+            Some(bitmap) => bitmap,
+            None => ruffle_render::utils::decode_define_bits_jpeg(jpeg_data, None)?,
+        };
+        context
+            .library
+            .library_for_movie_mut(self.movie())
+            .substitute_texture(id, &bitmap);
         let bitmap = Bitmap::new(context, id, bitmap)?;
         context
             .library
@@ -3433,7 +3546,14 @@ impl<'gc, 'a> MovieClipData<'gc> {
         }
         let jpeg_data = reader.read_slice(jpeg_len)?;
         let alpha_data = reader.read_slice_to_end();
-        let bitmap = ruffle_render::utils::decode_define_bits_jpeg(jpeg_data, Some(alpha_data))?;
+        let bitmap = match self.take_predecoded_bitmap(context, id) {
+            Some(bitmap) => bitmap,
+            None => ruffle_render::utils::decode_define_bits_jpeg(jpeg_data, Some(alpha_data))?,
+        };
+        context
+            .library
+            .library_for_movie_mut(self.movie())
+            .substitute_texture(id, &bitmap);
         let bitmap = Bitmap::new(context, id, bitmap)?;
         context
             .library
@@ -3795,6 +3915,41 @@ impl<'gc, 'a> MovieClipData<'gc> {
         Ok(())
     }
 
+    #[inline]
+    fn define_scaling_grid(
+        &mut self,
+        context: &mut UpdateContext<'_, 'gc>,
+        reader: &mut SwfStream<'a>,
+    ) -> Result<(), Error> {
+        let id = reader.read_u16()?;
+        let rect = reader.read_rectangle()?;
+        let character = context
+            .library
+            .library_for_movie_mut(self.movie())
+            .character_by_id(id);
+        match character {
+            Some(Character::MovieClip(movie_clip)) => {
+                movie_clip.set_scaling_grid(context.gc_context, rect)
+            }
+            Some(Character::Avm1Button(button)) => {
+                button.set_scaling_grid(context.gc_context, rect)
+            }
+            Some(Character::Avm2Button(button)) => {
+                button.set_scaling_grid(context.gc_context, rect)
+            }
+            Some(_) => {
+                tracing::warn!(
+                    "DefineScalingGrid: Tried to apply on non-sprite/button ID {}",
+                    id
+                );
+            }
+            None => {
+                tracing::warn!("DefineScalingGrid: Character ID {} doesn't exist", id);
+            }
+        }
+        Ok(())
+    }
+
     #[inline]
     fn export_assets(
         &mut self,
@@ -4114,34 +4269,7 @@ impl<'gc, 'a> MovieClip<'gc> {
             .library_for_movie_mut(self.movie())
             .get_sound(start_sound.id)
         {
-            use swf::SoundEvent;
-            // The sound event type is controlled by the "Sync" setting in the Flash IDE.
-            match start_sound.sound_info.event {
-                // "Event" sounds always play, independent of the timeline.
-                SoundEvent::Event => {
-                    let _ = context.start_sound(
-                        handle,
-                        &start_sound.sound_info,
-                        Some(self.into()),
-                        None,
-                    );
-                }
-
-                // "Start" sounds only play if an instance of the same sound is not already playing.
-                SoundEvent::Start => {
-                    if !context.is_sound_playing_with_handle(handle) {
-                        let _ = context.start_sound(
-                            handle,
-                            &start_sound.sound_info,
-                            Some(self.into()),
-                            None,
-                        );
-                    }
-                }
-
-                // "Stop" stops any active instances of a given sound.
-                SoundEvent::Stop => context.stop_sounds_with_handle(handle),
-            }
+            context.sync_sound(handle, &start_sound.sound_info, Some(self.into()));
         }
         Ok(())
     }
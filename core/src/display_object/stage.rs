@@ -117,6 +117,11 @@ pub struct StageData<'gc> {
     /// Whether or not objects display a glowing border when they have focus.
     stage_focus_rect: bool,
 
+    /// The color correction mode requested by the movie.
+    ///
+    /// This setting is currently ignored in Ruffle.
+    color_correction: ColorCorrection,
+
     /// Whether to show default context menu items
     show_menu: bool,
 
@@ -173,6 +178,7 @@ impl<'gc> Stage<'gc> {
                 window_mode: Default::default(),
                 show_menu: true,
                 stage_focus_rect: true,
+                color_correction: Default::default(),
                 avm2_object: Avm2ScriptObject::custom_object(gc_context, None, None),
                 loader_info: Avm2ScriptObject::custom_object(gc_context, None, None),
                 stage3ds: vec![],
@@ -192,6 +198,11 @@ impl<'gc> Stage<'gc> {
         self.0.write(gc_context).background_color = color;
     }
 
+    /// The matrix from the stage to the viewport.
+    pub fn view_matrix(self) -> Matrix {
+        self.0.read().viewport_matrix
+    }
+
     pub fn inverse_view_matrix(self) -> Matrix {
         self.0
             .read()
@@ -240,6 +251,20 @@ impl<'gc> Stage<'gc> {
         self.0.write(gc_context).invalidated = value;
     }
 
+    /// Invalidate the stage, so that the 'render' event is broadcast before it's
+    /// next drawn.
+    ///
+    /// Repeated calls are coalesced into a single 'render' event. Calls made by its
+    /// listeners invalidate the stage again, for another 'render' event the next
+    /// time it's drawn. The first call requests a redraw, since it must happen
+    /// even if nothing else changed.
+    pub fn invalidate(self, context: &mut UpdateContext<'_, 'gc>) {
+        if !self.invalidated() {
+            self.set_invalidated(context.gc_context, true);
+            *context.needs_render = true;
+        }
+    }
+
     /// Returns the quality setting of the stage.
     ///
     /// In the Flash Player, the quality setting affects anti-aliasing and smoothing of bitmaps.
@@ -297,6 +322,39 @@ impl<'gc> Stage<'gc> {
         self.0.read().stage_size
     }
 
+    /// Set the size of the stage, as reported to ActionScript.
+    ///
+    /// This only has an effect in the `StageScaleMode::NO_SCALE` scale mode, and
+    /// lasts until the viewport is resized.
+    /// Used by AVM2 `Stage.stageWidth`/`stageHeight` setters.
+    pub fn set_stage_size(self, context: &mut UpdateContext<'_, 'gc>, width: u32, height: u32) {
+        if self.scale_mode() != StageScaleMode::NoScale || self.stage_size() == (width, height) {
+            return;
+        }
+        self.0.write(context.gc_context).stage_size = (width, height);
+        self.fire_resize_event(context);
+    }
+
+    /// Get the color correction mode of the stage.
+    ///
+    /// This setting is currently ignored in Ruffle.
+    /// Used by AVM2 `Stage.colorCorrection` property.
+    pub fn color_correction(self) -> ColorCorrection {
+        self.0.read().color_correction
+    }
+
+    /// Set the color correction mode of the stage.
+    ///
+    /// This setting is currently ignored in Ruffle.
+    /// Used by AVM2 `Stage.colorCorrection` property.
+    pub fn set_color_correction(
+        self,
+        gc_context: MutationContext<'gc, '_>,
+        color_correction: ColorCorrection,
+    ) {
+        self.0.write(gc_context).color_correction = color_correction;
+    }
+
     /// Get the stage mode.
     /// This controls how the content scales to fill the viewport.
     pub fn scale_mode(self) -> StageScaleMode {
@@ -304,10 +362,13 @@ impl<'gc> Stage<'gc> {
     }
 
     /// Set the stage scale mode.
+    ///
+    /// The movie is redrawn with the new viewport matrix, even if it's not playing.
     pub fn set_scale_mode(self, context: &mut UpdateContext<'_, 'gc>, scale_mode: StageScaleMode) {
         if !self.forced_scale_mode() {
             self.0.write(context.gc_context).scale_mode = scale_mode;
             self.build_matrices(context);
+            *context.needs_render = true;
         }
     }
 
@@ -380,9 +441,12 @@ impl<'gc> Stage<'gc> {
 
     /// Set the stage alignment.
     /// This only has an effect if the scale mode is not `StageScaleMode::ExactFit`.
+    ///
+    /// The movie is redrawn with the new viewport matrix, even if it's not playing.
     pub fn set_align(self, context: &mut UpdateContext<'_, 'gc>, align: StageAlign) {
         self.0.write(context.gc_context).align = align;
         self.build_matrices(context);
+        *context.needs_render = true;
     }
 
     /// Returns whether bitmaps will use high quality downsampling when scaled down.
@@ -661,11 +725,12 @@ impl<'gc> Stage<'gc> {
     /// TODO: Need additional check as Flash Player does not
     /// broadcast the 'render' event on the first render
     pub fn broadcast_render(&self, context: &mut UpdateContext<'_, 'gc>) {
+        // Cleared first, so that listeners can invalidate the stage again.
+        self.set_invalidated(context.gc_context, false);
+
         let render_evt = Avm2EventObject::bare_default_event(context, "render");
         let dobject_constr = context.avm2.classes().display_object;
         Avm2::broadcast_event(context, render_evt, dobject_constr);
-
-        self.set_invalidated(context.gc_context, false);
     }
 
     /// Fires `Stage.onFullScreen` in AVM1 or `Event.FULLSCREEN` in AVM2.
@@ -795,8 +860,20 @@ impl<'gc> TDisplayObject<'gc> for Stage<'gc> {
         // Note that the stage background color is actually the lowest possible layer,
         // and get applied when we start the frame (before `render` is called).
         for stage3d in self.stage3ds().iter() {
-            if let Some(context3d) = stage3d.as_stage_3d().unwrap().context3d() {
+            let stage3d = stage3d.as_stage_3d().unwrap();
+            if !stage3d.visible() {
+                continue;
+            }
+            if let Some(context3d) = stage3d.context3d() {
+                context.transform_stack.push(&Transform {
+                    matrix: Matrix::translate(
+                        Twips::from_pixels(stage3d.x()),
+                        Twips::from_pixels(stage3d.y()),
+                    ),
+                    color_transform: Default::default(),
+                });
                 context3d.as_context_3d().unwrap().render(context);
+                context.transform_stack.pop();
             }
         }
 
@@ -1015,6 +1092,64 @@ impl FromWStr for StageDisplayState {
     }
 }
 
+/// The color correction mode of a stage.
+/// This controls whether colors are adjusted to the color profile of the monitor.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Collect)]
+#[collect(require_static)]
+pub enum ColorCorrection {
+    /// Uses the host's default color correction.
+    #[default]
+    Default,
+
+    /// Forces color correction on.
+    On,
+
+    /// Forces color correction off.
+    Off,
+}
+
+impl Display for ColorCorrection {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        // Match string values returned by AS.
+        let s = match *self {
+            ColorCorrection::Default => "default",
+            ColorCorrection::On => "on",
+            ColorCorrection::Off => "off",
+        };
+        f.write_str(s)
+    }
+}
+
+impl FromStr for ColorCorrection {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let color_correction = match s {
+            "default" => ColorCorrection::Default,
+            "on" => ColorCorrection::On,
+            "off" => ColorCorrection::Off,
+            _ => return Err(ParseEnumError),
+        };
+        Ok(color_correction)
+    }
+}
+
+impl FromWStr for ColorCorrection {
+    type Err = ParseEnumError;
+
+    fn from_wstr(s: &WStr) -> Result<Self, Self::Err> {
+        if s == WStr::from_units(b"default") {
+            Ok(ColorCorrection::Default)
+        } else if s == WStr::from_units(b"on") {
+            Ok(ColorCorrection::On)
+        } else if s == WStr::from_units(b"off") {
+            Ok(ColorCorrection::Off)
+        } else {
+            Err(ParseEnumError)
+        }
+    }
+}
+
 bitflags! {
     /// The alignment of the stage.
     /// This controls the position of the movie after scaling to fill the viewport.
@@ -4,6 +4,7 @@ use crate::avm1::{Object as Avm1Object, StageObject as Avm1StageObject};
 use crate::avm2::{
     Activation as Avm2Activation, Object as Avm2Object, StageObject as Avm2StageObject,
 };
+use crate::camera::Camera;
 use crate::context::{RenderContext, UpdateContext};
 use crate::display_object::{DisplayObjectBase, DisplayObjectPtr, TDisplayObject};
 use crate::prelude::*;
@@ -116,6 +117,18 @@ pub enum VideoSource<'gc> {
         #[collect(require_static)]
         size: Option<Rectangle<Twips>>,
     },
+    /// An attached camera.
+    Camera {
+        /// The movie whose code created the Video object.
+        movie: Arc<SwfMovie>,
+
+        /// The camera the frames are captured from.
+        camera: Camera<'gc>,
+
+        /// The size the video was placed with, which frames are scaled to.
+        #[collect(require_static)]
+        size: Rectangle<Twips>,
+    },
 }
 
 impl<'gc> Video<'gc> {
@@ -151,20 +164,59 @@ impl<'gc> Video<'gc> {
     ///
     /// Existing video state related to the old video stream will be dropped.
     pub fn attach_netstream(self, context: &mut UpdateContext<'_, 'gc>, stream: NetStream<'gc>) {
+        if let Some(camera) = self.attached_camera() {
+            camera.stop(context);
+        }
+
         let movie = self.movie();
+        // The video keeps the size it was placed with, and the frames are scaled to it.
+        let size = Some(self.self_bounds());
         let mut video = self.0.write(context.gc_context);
 
         *video.source.write(context.gc_context) = VideoSource::NetStream {
             movie,
             stream,
             num_frames: None,
-            size: None,
+            size,
         };
 
         video.stream = VideoStream::Uninstantiated(0);
         video.keyframes = BTreeSet::new();
     }
 
+    /// Display the frames of a camera, which starts capturing.
+    ///
+    /// Without a camera, the video keeps showing the frame it last displayed,
+    /// and the camera that was attached stops capturing.
+    pub fn attach_camera(self, context: &mut UpdateContext<'_, 'gc>, camera: Option<Camera<'gc>>) {
+        let movie = self.movie();
+        let size = self.self_bounds();
+        if let Some(old_camera) = self.attached_camera() {
+            if camera.map_or(true, |camera| camera.index() != old_camera.index()) {
+                old_camera.stop(context);
+            }
+        }
+
+        if let Some(camera) = camera {
+            camera.start(context);
+            let mut video = self.0.write(context.gc_context);
+            *video.source.write(context.gc_context) = VideoSource::Camera {
+                movie,
+                camera,
+                size,
+            };
+            video.stream = VideoStream::Uninstantiated(0);
+            video.keyframes = BTreeSet::new();
+        }
+    }
+
+    fn attached_camera(self) -> Option<Camera<'gc>> {
+        match &*self.0.read().source.read() {
+            VideoSource::Camera { camera, .. } => Some(*camera),
+            _ => None,
+        }
+    }
+
     /// Preload frame data from an SWF.
     ///
     /// This function yields an error if this video player is not playing an
@@ -190,7 +242,7 @@ impl<'gc> Video<'gc> {
 
                 frames.insert(tag.frame_num.into(), (subslice.start, subslice.end));
             }
-            VideoSource::NetStream { .. } => {}
+            VideoSource::NetStream { .. } | VideoSource::Camera { .. } => {}
         }
     }
 
@@ -215,6 +267,7 @@ impl<'gc> Video<'gc> {
         let num_frames = match &*read.source.read() {
             VideoSource::Swf { streamdef, .. } => Some(streamdef.num_frames as usize),
             VideoSource::NetStream { num_frames, .. } => *num_frames,
+            VideoSource::Camera { .. } => None,
         };
 
         if let Some(num_frames) = num_frames {
@@ -308,7 +361,7 @@ impl<'gc> Video<'gc> {
                     }
                 }
             },
-            VideoSource::NetStream { .. } => return,
+            VideoSource::NetStream { .. } | VideoSource::Camera { .. } => return,
         };
 
         drop(read);
@@ -404,7 +457,7 @@ impl<'gc> TDisplayObject<'gc> for Video<'gc> {
 
                 (stream, movie.clone(), keyframes)
             }
-            VideoSource::NetStream { .. } => return,
+            VideoSource::NetStream { .. } | VideoSource::Camera { .. } => return,
         };
 
         let starting_seek = if let VideoStream::Uninstantiated(seek_to) = write.stream {
@@ -460,7 +513,7 @@ impl<'gc> TDisplayObject<'gc> for Video<'gc> {
     fn id(&self) -> CharacterId {
         match (*self.0.read().source.read()).borrow() {
             VideoSource::Swf { streamdef, .. } => streamdef.id,
-            VideoSource::NetStream { .. } => 0,
+            VideoSource::NetStream { .. } | VideoSource::Camera { .. } => 0,
         }
     }
 
@@ -473,6 +526,7 @@ impl<'gc> TDisplayObject<'gc> for Video<'gc> {
                 y_max: Twips::from_pixels_i32(streamdef.height.into()),
             },
             VideoSource::NetStream { size, .. } => size.clone().unwrap_or_default(),
+            VideoSource::Camera { size, .. } => size.clone(),
         }
     }
 
@@ -486,7 +540,17 @@ impl<'gc> TDisplayObject<'gc> for Video<'gc> {
 
         let read = self.0.read();
 
-        if let Some((_frame_id, ref bitmap)) = read.decoded_frame {
+        // Streams decode their frames as they play, rather than when the video is seeked.
+        let frame = match &*read.source.read() {
+            VideoSource::NetStream { stream, .. } => stream.last_frame(),
+            VideoSource::Camera { camera, .. } => camera.last_frame(),
+            VideoSource::Swf { .. } => read
+                .decoded_frame
+                .as_ref()
+                .map(|(_, bitmap)| bitmap.clone()),
+        };
+
+        if let Some(ref bitmap) = frame {
             let mut transform = context.transform_stack.transform();
             let bounds = self.self_bounds();
 
@@ -507,6 +571,7 @@ impl<'gc> TDisplayObject<'gc> for Video<'gc> {
                 VideoSource::NetStream { num_frames, .. } => {
                     (false, num_frames.unwrap_or(0), self.movie().version())
                 }
+                VideoSource::Camera { .. } => (false, 0, self.movie().version()),
             };
 
             let smoothing = match (context.stage.quality(), version) {
@@ -535,6 +600,7 @@ impl<'gc> TDisplayObject<'gc> for Video<'gc> {
         match &*self.0.read().source.read() {
             VideoSource::Swf { movie, .. } => movie.clone(),
             VideoSource::NetStream { movie, .. } => movie.clone(),
+            VideoSource::Camera { movie, .. } => movie.clone(),
         }
     }
 }
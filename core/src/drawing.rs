@@ -336,7 +336,7 @@ impl Drawing {
                 DrawingPath::Line(line) => {
                     if shape_utils::draw_command_stroke_hit_test(
                         &line.commands,
-                        line.style.width(),
+                        &line.style,
                         (point.x, point.y),
                         local_matrix,
                     ) {
@@ -356,7 +356,7 @@ impl Drawing {
         for line in &self.pending_lines {
             if shape_utils::draw_command_stroke_hit_test(
                 &line.commands,
-                line.style.width(),
+                &line.style,
                 (point.x, point.y),
                 local_matrix,
             ) {
@@ -367,7 +367,7 @@ impl Drawing {
         if let Some(line) = &self.current_line {
             if shape_utils::draw_command_stroke_hit_test(
                 &line.commands,
-                line.style.width(),
+                &line.style,
                 (point.x, point.y),
                 local_matrix,
             ) {
@@ -388,7 +388,7 @@ impl Drawing {
                             y: self.fill_start.y,
                         },
                     ],
-                    line.style.width(),
+                    &line.style,
                     (point.x, point.y),
                     local_matrix,
                 )
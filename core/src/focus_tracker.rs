@@ -42,16 +42,36 @@ impl<'gc> FocusTracker<'gc> {
 
             tracing::info!("Focus is now on {:?}", focused_element);
 
+            let old_object = old.map(|v| v.object()).unwrap_or(Value::Null);
+            let new_object = focused_element.map(|v| v.object()).unwrap_or(Value::Null);
+
+            // The objects are told about the change before the listeners of `Selection`.
+            if let (Some(old), Value::Object(object)) = (old, old_object) {
+                Avm1::run_stack_frame_for_method(
+                    old,
+                    object,
+                    context,
+                    "onKillFocus".into(),
+                    &[new_object],
+                );
+            }
+            if let (Some(new), Value::Object(object)) = (focused_element, new_object) {
+                Avm1::run_stack_frame_for_method(
+                    new,
+                    object,
+                    context,
+                    "onSetFocus".into(),
+                    &[old_object],
+                );
+            }
+
             if let Some(level0) = context.stage.root_clip() {
                 Avm1::notify_system_listeners(
                     level0,
                     context,
                     "Selection".into(),
                     "onSetFocus".into(),
-                    &[
-                        old.map(|v| v.object()).unwrap_or(Value::Null),
-                        focused_element.map(|v| v.object()).unwrap_or(Value::Null),
-                    ],
+                    &[old_object, new_object],
                 );
             }
         }
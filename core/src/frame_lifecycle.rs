@@ -18,9 +18,9 @@ use tracing::instrument;
 
 /// Which phase of the frame we're currently in.
 ///
-/// AVM2 frames exist in one of four phases: `Enter`, `Construct`,
-/// `FrameScripts`, or `Exit`. An additional `Idle` phase covers rendering and
-/// event processing.
+/// AVM2 frames exist in one of five phases: `Enter`, `Construct`,
+/// `FrameScripts`, `Exit`, or `Render`. An additional `Idle` phase covers
+/// rendering and event processing.
 #[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
 pub enum FramePhase {
     /// We're entering the next frame.
@@ -54,6 +54,12 @@ pub enum FramePhase {
     /// list.
     Exit,
 
+    /// We're about to render the frame.
+    ///
+    /// If `Stage.invalidate` was called, we fire `render` on the broadcast
+    /// list.
+    Render,
+
     /// We're not currently executing any frame code.
     ///
     /// At this point in time, event handlers are expected to run. No frame
@@ -71,26 +77,26 @@ pub enum FramePhase {
 pub fn run_all_phases_avm2(context: &mut UpdateContext<'_, '_>) {
     let stage = context.stage;
 
-    *context.frame_phase = FramePhase::Enter;
+    set_frame_phase(context, FramePhase::Enter);
     Avm2::each_orphan_obj(context, |orphan, context| {
         orphan.enter_frame(context);
     });
     stage.enter_frame(context);
 
-    *context.frame_phase = FramePhase::Construct;
+    set_frame_phase(context, FramePhase::Construct);
     Avm2::each_orphan_obj(context, |orphan, context| {
         orphan.construct_frame(context);
     });
     stage.construct_frame(context);
     stage.frame_constructed(context);
 
-    *context.frame_phase = FramePhase::FrameScripts;
+    set_frame_phase(context, FramePhase::FrameScripts);
     Avm2::each_orphan_obj(context, |orphan, context| {
         orphan.run_frame_scripts(context);
     });
     stage.run_frame_scripts(context);
 
-    *context.frame_phase = FramePhase::Exit;
+    set_frame_phase(context, FramePhase::Exit);
     Avm2::each_orphan_obj(context, |orphan, context| {
         orphan.on_exit_frame(context);
     });
@@ -103,7 +109,23 @@ pub fn run_all_phases_avm2(context: &mut UpdateContext<'_, '_>) {
     // a result of a RemoveObject tag - see `cleanup_dead_orphans` for details.
     Avm2::cleanup_dead_orphans(context);
 
-    *context.frame_phase = FramePhase::Idle;
+    // `render` is the last event of the frame, fired after `exitFrame` if the
+    // stage was invalidated at any point before it.
+    set_frame_phase(context, FramePhase::Render);
+    if stage.invalidated() {
+        stage.broadcast_render(context);
+    }
+
+    set_frame_phase(context, FramePhase::Idle);
+}
+
+/// Move on to the next frame phase.
+///
+/// Phase changes are logged at the `trace` level, to make it easier to compare
+/// the order of frame events and scripts with Flash Player.
+fn set_frame_phase(context: &mut UpdateContext<'_, '_>, phase: FramePhase) {
+    tracing::trace!("Entering frame phase {phase:?}");
+    *context.frame_phase = phase;
 }
 
 /// Run all previously-executed frame phases on a newly-constructed display
@@ -122,7 +144,11 @@ pub fn catchup_display_object_to_frame<'gc>(
         FramePhase::Enter => {
             dobj.enter_frame(context);
         }
-        FramePhase::Construct | FramePhase::FrameScripts | FramePhase::Exit | FramePhase::Idle => {
+        FramePhase::Construct
+        | FramePhase::FrameScripts
+        | FramePhase::Exit
+        | FramePhase::Render
+        | FramePhase::Idle => {
             dobj.enter_frame(context);
             dobj.construct_frame(context);
         }
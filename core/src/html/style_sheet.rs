@@ -0,0 +1,253 @@
+//! CSS style sheets, which format the HTML text of text fields.
+//!
+//! Flash only supports a handful of CSS properties, which are converted to a
+//! `TextFormat` when a style is set. Styles are looked up by tag name, such as
+//! `p`, and by class name, such as `.heading`, when HTML text is lowered into
+//! text spans. Changing a style sheet only affects HTML text set afterwards.
+
+use crate::html::TextFormat;
+use crate::string::WString;
+use gc_arena::{Collect, GcCell, MutationContext};
+use std::collections::BTreeMap;
+
+/// A style of a style sheet.
+#[derive(Clone, Debug, Default)]
+pub struct Style {
+    /// The properties of the style as set by the movie, named like `fontSize`.
+    pub properties: Vec<(String, String)>,
+
+    /// The text format the properties describe.
+    pub format: TextFormat,
+}
+
+impl Style {
+    pub fn new(properties: Vec<(String, String)>) -> Self {
+        let format = style_to_text_format(&properties);
+        Self { properties, format }
+    }
+}
+
+/// A style sheet shared by the AVM object it belongs to and the text fields using it.
+#[derive(Clone, Copy, Collect)]
+#[collect(no_drop)]
+pub struct StyleSheet<'gc>(GcCell<'gc, StyleSheetData>);
+
+#[derive(Clone, Collect, Debug, Default)]
+#[collect(require_static)]
+pub struct StyleSheetData {
+    /// The styles, by lowercase selector.
+    styles: BTreeMap<String, Style>,
+}
+
+impl<'gc> StyleSheet<'gc> {
+    pub fn new(gc_context: MutationContext<'gc, '_>) -> Self {
+        Self(GcCell::allocate(gc_context, Default::default()))
+    }
+
+    /// The names of the styles, in lowercase.
+    pub fn style_names(self) -> Vec<String> {
+        self.0.read().styles.keys().cloned().collect()
+    }
+
+    pub fn style(self, name: &str) -> Option<Style> {
+        self.0
+            .read()
+            .styles
+            .get(&name.to_ascii_lowercase())
+            .cloned()
+    }
+
+    /// Sets the style of a selector, or removes it.
+    pub fn set_style(self, gc_context: MutationContext<'gc, '_>, name: &str, style: Option<Style>) {
+        let name = name.to_ascii_lowercase();
+        let mut write = self.0.write(gc_context);
+        match style {
+            Some(style) => {
+                write.styles.insert(name, style);
+            }
+            None => {
+                write.styles.remove(&name);
+            }
+        }
+    }
+
+    pub fn clear(self, gc_context: MutationContext<'gc, '_>) {
+        self.0.write(gc_context).styles.clear();
+    }
+
+    /// Parses CSS text, adding its rules to the style sheet.
+    ///
+    /// Returns `false` without changing anything if the text isn't valid CSS.
+    pub fn parse_css(self, gc_context: MutationContext<'gc, '_>, css: &str) -> bool {
+        let Some(rules) = parse_css(css) else {
+            return false;
+        };
+        for (selectors, properties) in rules {
+            for selector in selectors {
+                // Properties set by later rules are added to the existing style.
+                let mut all_properties = self
+                    .style(&selector)
+                    .map_or_else(Vec::new, |style| style.properties);
+                for (name, value) in &properties {
+                    all_properties.retain(|(existing, _)| existing != name);
+                    all_properties.push((name.clone(), value.clone()));
+                }
+                self.set_style(gc_context, &selector, Some(Style::new(all_properties)));
+            }
+        }
+        true
+    }
+
+    /// The format of an element with the tag `tag` and the class attribute `class`,
+    /// the style of the class taking precedence over the style of the tag.
+    pub fn format_of(self, tag: &str, class: Option<&WString>) -> Option<TextFormat> {
+        let read = self.0.read();
+        let tag_format = read
+            .styles
+            .get(&tag.to_ascii_lowercase())
+            .map(|style| style.format.clone());
+        let class_format = class.and_then(|class| {
+            let selector = format!(".{}", class.to_utf8_lossy().to_ascii_lowercase());
+            read.styles.get(&selector).map(|style| style.format.clone())
+        });
+        match (class_format, tag_format) {
+            (Some(class_format), Some(tag_format)) => Some(class_format.mix_with(tag_format)),
+            (class_format, tag_format) => class_format.or(tag_format),
+        }
+    }
+}
+
+/// The rules of a style sheet, each with its selectors and its properties.
+type CssRules = Vec<(Vec<String>, Vec<(String, String)>)>;
+
+/// Parses the rules of CSS text, naming properties like style objects do.
+///
+/// Returns `None` if a rule isn't closed, or has no selector.
+pub fn parse_css(css: &str) -> Option<CssRules> {
+    let mut css = css.to_owned();
+    while let Some(start) = css.find("/*") {
+        let end = css[start..]
+            .find("*/")
+            .map_or(css.len(), |end| start + end + 2);
+        css.replace_range(start..end, "");
+    }
+
+    let mut rules = Vec::new();
+    let mut rest = css.trim();
+    while !rest.is_empty() {
+        let open = rest.find('{')?;
+        let close = open + rest[open..].find('}')?;
+        let selectors: Vec<String> = rest[..open]
+            .split(',')
+            .map(|selector| selector.trim().to_owned())
+            .filter(|selector| !selector.is_empty())
+            .collect();
+        if selectors.is_empty() {
+            return None;
+        }
+        let properties = rest[open + 1..close]
+            .split(';')
+            .filter_map(|declaration| {
+                let (name, value) = declaration.split_once(':')?;
+                let name = camel_case(name.trim());
+                let value = value.trim().to_owned();
+                (!name.is_empty()).then_some((name, value))
+            })
+            .collect();
+        rules.push((selectors, properties));
+        rest = rest[close + 1..].trim_start();
+    }
+    Some(rules)
+}
+
+/// Converts a CSS property name like `font-size` to the name style objects use, like `fontSize`.
+fn camel_case(name: &str) -> String {
+    let mut result = String::with_capacity(name.len());
+    let mut upper = false;
+    for c in name.chars() {
+        if c == '-' {
+            upper = true;
+        } else if upper {
+            result.push(c.to_ascii_uppercase());
+            upper = false;
+        } else {
+            result.push(c);
+        }
+    }
+    result
+}
+
+/// Parses the number at the start of a CSS value, ignoring its unit.
+fn parse_length(value: &str) -> Option<f64> {
+    let end = value
+        .find(|c: char| !(c.is_ascii_digit() || matches!(c, '.' | '-' | '+')))
+        .unwrap_or(value.len());
+    value[..end].parse().ok()
+}
+
+fn parse_color(value: &str) -> Option<swf::Color> {
+    let hex = value.strip_prefix('#')?;
+    let rgb = u32::from_str_radix(hex, 16).ok()?;
+    Some(swf::Color::from_rgb(rgb, 0))
+}
+
+/// Converts the properties of a style to the text format they describe.
+///
+/// Unknown properties and invalid values are ignored, as are the values of
+/// `display`, which doesn't change the format.
+pub fn style_to_text_format(properties: &[(String, String)]) -> TextFormat {
+    let mut format = TextFormat::default();
+    for (name, value) in properties {
+        let value = value.trim();
+        match name.as_str() {
+            "color" => format.color = parse_color(value),
+            "fontFamily" => {
+                // The first family is used, with generic families mapped to device fonts.
+                let family = value.split(',').next().unwrap_or_default().trim();
+                let family = family.trim_matches(|c| c == '"' || c == '\'');
+                let family = match family {
+                    "sans-serif" => "_sans",
+                    "serif" => "_serif",
+                    "mono" | "monospace" => "_typewriter",
+                    family => family,
+                };
+                format.font = Some(WString::from_utf8(family));
+            }
+            "fontSize" => format.size = parse_length(value),
+            "fontStyle" => match value {
+                "italic" => format.italic = Some(true),
+                "normal" => format.italic = Some(false),
+                _ => {}
+            },
+            "fontWeight" => match value {
+                "bold" => format.bold = Some(true),
+                "normal" => format.bold = Some(false),
+                _ => {}
+            },
+            "kerning" => match value {
+                "true" => format.kerning = Some(true),
+                "false" => format.kerning = Some(false),
+                _ => {}
+            },
+            "leading" => format.leading = parse_length(value),
+            "letterSpacing" => format.letter_spacing = parse_length(value),
+            "marginLeft" => format.left_margin = parse_length(value),
+            "marginRight" => format.right_margin = parse_length(value),
+            "textAlign" => match value {
+                "left" => format.align = Some(swf::TextAlign::Left),
+                "center" => format.align = Some(swf::TextAlign::Center),
+                "right" => format.align = Some(swf::TextAlign::Right),
+                "justify" => format.align = Some(swf::TextAlign::Justify),
+                _ => {}
+            },
+            "textDecoration" => match value {
+                "underline" => format.underline = Some(true),
+                "none" => format.underline = Some(false),
+                _ => {}
+            },
+            "textIndent" => format.indent = parse_length(value),
+            _ => {}
+        }
+    }
+    format
+}
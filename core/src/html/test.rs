@@ -1,6 +1,7 @@
 //! Tests for HTML module
 
 use crate::html::dimensions::{BoxBounds, Position, Size};
+use crate::html::style_sheet::{parse_css, style_to_text_format};
 use crate::html::text_format::{FormatSpans, TextFormat, TextSpan};
 use crate::string::{WStr, WString};
 use swf::{Rectangle, Twips};
@@ -864,3 +865,50 @@ fn formatspans_replace_text_degenerate() {
     assert_eq!((0, 1), fs.get_span_boundaries(0, 5));
     assert_eq!((1, 2), fs.get_span_boundaries(5, 9));
 }
+
+#[test]
+fn parse_css_rules() {
+    let rules = parse_css(
+        "/* headings */ h1, .title { font-size: 24px; font-weight: bold }\np { color: #FF0000; }",
+    )
+    .unwrap();
+
+    assert_eq!(rules.len(), 2);
+    assert_eq!(rules[0].0, vec!["h1".to_owned(), ".title".to_owned()]);
+    assert_eq!(
+        rules[0].1,
+        vec![
+            ("fontSize".to_owned(), "24px".to_owned()),
+            ("fontWeight".to_owned(), "bold".to_owned()),
+        ]
+    );
+    assert_eq!(rules[1].0, vec!["p".to_owned()]);
+    assert_eq!(rules[1].1, vec![("color".to_owned(), "#FF0000".to_owned())]);
+}
+
+#[test]
+fn parse_css_invalid() {
+    assert!(parse_css("p { color: #FF0000;").is_none());
+    assert!(parse_css("{ color: #FF0000; }").is_none());
+}
+
+#[test]
+fn style_to_text_format_properties() {
+    let format = style_to_text_format(&[
+        ("color".to_owned(), "#00FF00".to_owned()),
+        ("fontFamily".to_owned(), "sans-serif".to_owned()),
+        ("fontSize".to_owned(), "12px".to_owned()),
+        ("fontStyle".to_owned(), "italic".to_owned()),
+        ("textAlign".to_owned(), "center".to_owned()),
+        ("textDecoration".to_owned(), "underline".to_owned()),
+        ("display".to_owned(), "block".to_owned()),
+    ]);
+
+    assert_eq!(format.color, Some(swf::Color::from_rgb(0x00FF00, 0)));
+    assert_eq!(format.font, Some(WString::from_utf8("_sans")));
+    assert_eq!(format.size, Some(12.0));
+    assert_eq!(format.italic, Some(true));
+    assert_eq!(format.bold, None);
+    assert_eq!(format.align, Some(swf::TextAlign::Center));
+    assert_eq!(format.underline, Some(true));
+}
@@ -2,6 +2,7 @@
 
 use crate::context::UpdateContext;
 use crate::html::iterators::TextSpanIter;
+use crate::html::StyleSheet;
 use crate::string::{Integer, SwfStrExt as _, Units, WStr, WString};
 use crate::tag_utils::SwfMovie;
 use gc_arena::Collect;
@@ -564,11 +565,14 @@ impl FormatSpans {
 
     /// Lower an HTML tree into text-span representation.
     ///
-    /// This is the "legacy" implementation of this process: it only looks for
-    /// a handful of presentational attributes in the HTML tree to generate
-    /// styling. There's also a `lower_from_css` that respects both
-    /// presentational markup and CSS stylesheets.
-    pub fn from_html(html: &WStr, default_format: TextFormat, is_multiline: bool) -> Self {
+    /// Presentational tags and attributes are applied first, and then the styles
+    /// of `style_sheet` matching the tag and class of each element, if any.
+    pub fn from_html(
+        html: &WStr,
+        default_format: TextFormat,
+        style_sheet: Option<StyleSheet<'_>>,
+        is_multiline: bool,
+    ) -> Self {
         let mut format_stack = vec![default_format.clone()];
         let mut text = WString::new();
         let mut spans: Vec<TextSpan> = Vec::new();
@@ -759,6 +763,14 @@ impl FormatSpans {
                         }
                         _ => {}
                     }
+                    if let Some(style_sheet) = style_sheet {
+                        let tag = String::from_utf8_lossy(e.name().into_inner());
+                        if let Some(style) =
+                            style_sheet.format_of(&tag, attribute(b"class").as_ref())
+                        {
+                            format = style.mix_with(format);
+                        }
+                    }
                     format_stack.push(format);
                 }
                 Ok(Event::Text(e)) if !e.is_empty() => {
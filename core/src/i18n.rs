@@ -0,0 +1,145 @@
+//! Translations of the text that the player shows to users, such as the
+//! items of its context menu, written with Fluent.
+//!
+//! Frontends add the translations of their own text to the same
+//! `Localizations`, and embedders may add languages at runtime.
+
+use fluent::{FluentArgs, FluentBundle, FluentResource};
+use std::sync::Arc;
+
+use unic_langid::langid;
+
+pub use unic_langid::LanguageIdentifier;
+
+/// The language used for text missing from the language of the user.
+pub const FALLBACK_LANGUAGE: LanguageIdentifier = langid!("en-US");
+
+/// The translations that come with the core.
+const BUILT_IN_TEXTS: &[(LanguageIdentifier, &str)] = &[
+    (
+        langid!("en-US"),
+        include_str!("../assets/texts/en-US/context_menu.ftl"),
+    ),
+    (
+        langid!("en-US"),
+        include_str!("../assets/texts/en-US/messages.ftl"),
+    ),
+    (
+        langid!("fr-FR"),
+        include_str!("../assets/texts/fr-FR/context_menu.ftl"),
+    ),
+    (
+        langid!("fr-FR"),
+        include_str!("../assets/texts/fr-FR/messages.ftl"),
+    ),
+];
+
+/// The translations of every language known to the player.
+#[derive(Clone)]
+pub struct Localizations {
+    /// The parsed Fluent files of each language, in the order they were added.
+    resources: Vec<(LanguageIdentifier, Arc<FluentResource>)>,
+}
+
+impl Localizations {
+    /// Creates the localizations with the built-in translations of the core.
+    pub fn new() -> Self {
+        let mut localizations = Self { resources: vec![] };
+        for (language, source) in BUILT_IN_TEXTS {
+            localizations
+                .add_resource(language.clone(), source)
+                .expect("Built-in texts should be valid");
+        }
+        localizations
+    }
+
+    /// Adds the messages of a Fluent file written for `language`.
+    ///
+    /// Messages added later take precedence over the ones with the same ID,
+    /// so embedders can also change the built-in text.
+    pub fn add_resource(
+        &mut self,
+        language: LanguageIdentifier,
+        source: &str,
+    ) -> Result<(), String> {
+        let resource = FluentResource::try_new(source.to_owned()).map_err(|(_, errors)| {
+            errors
+                .iter()
+                .map(|e| e.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        })?;
+        self.resources.push((language, Arc::new(resource)));
+        Ok(())
+    }
+
+    /// The languages that have translations.
+    pub fn languages(&self) -> Vec<LanguageIdentifier> {
+        let mut languages: Vec<_> = vec![];
+        for (language, _) in &self.resources {
+            if !languages.contains(language) {
+                languages.push(language.clone());
+            }
+        }
+        languages
+    }
+
+    /// Builds a bundle with every message that can be shown in `language`:
+    /// its own translations first, then those of other regions of the same
+    /// language, then the fallback language.
+    fn bundle(&self, language: &LanguageIdentifier) -> FluentBundle<Arc<FluentResource>> {
+        let mut bundle = FluentBundle::new(vec![language.clone()]);
+        bundle.set_use_isolating(false);
+
+        let ranks: [&dyn Fn(&LanguageIdentifier) -> bool; 3] = [
+            &|other| other == language,
+            &|other| other.language == language.language,
+            &|other| *other == FALLBACK_LANGUAGE,
+        ];
+        for is_match in ranks {
+            for (_, resource) in self
+                .resources
+                .iter()
+                .rev()
+                .filter(|(other, _)| is_match(other))
+            {
+                // Messages already in the bundle are kept, which is what gives precedence.
+                let _ = bundle.add_resource(resource.clone());
+            }
+        }
+        bundle
+    }
+
+    /// The text of a message in `language`.
+    pub fn text(&self, language: &LanguageIdentifier, id: &str) -> String {
+        self.text_with_args(language, id, None)
+    }
+
+    /// The text of a message in `language`, with variables replaced by `args`.
+    ///
+    /// Missing messages are shown as their ID.
+    pub fn text_with_args(
+        &self,
+        language: &LanguageIdentifier,
+        id: &str,
+        args: Option<&FluentArgs>,
+    ) -> String {
+        let bundle = self.bundle(language);
+        let Some(pattern) = bundle.get_message(id).and_then(|message| message.value()) else {
+            tracing::warn!("Missing text {} for {}", id, language);
+            return id.to_owned();
+        };
+        let mut errors = vec![];
+        let text = bundle.format_pattern(pattern, args, &mut errors);
+        for error in errors {
+            tracing::warn!("Error formatting text {}: {}", id, error);
+        }
+        text.into_owned()
+    }
+}
+
+impl Default for Localizations {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,134 @@
+//! Scripts supplied by the embedder, which are run against loaded content.
+//!
+//! This is a sanctioned hook for mods such as trainers or accessibility aids.
+//! An injected script is an ActionScript 3.0 ABC block, which is loaded into the
+//! application domain of the root movie once it has fully loaded and run its first frame.
+//! Living in the same domain gives the script access to the content's own classes,
+//! and its entry point receives the root display object, so it can install
+//! frame scripts with `MovieClip.addFrameScript`.
+
+use crate::avm2::{Activation, Avm2, QName, TObject, Value};
+use crate::context::UpdateContext;
+use crate::display_object::TDisplayObject;
+use crate::string::AvmString;
+use swf::DoAbc2Flag;
+use url::Url;
+
+/// An ActionScript 3.0 script supplied by the embedder.
+#[derive(Clone, Debug)]
+pub struct InjectedScript {
+    abc: Vec<u8>,
+    domain: Option<String>,
+    entry_point: Option<String>,
+}
+
+impl InjectedScript {
+    /// Creates a script from ABC bytecode, as found in a `DoAbc` tag.
+    ///
+    /// By default, the script is injected into every ActionScript 3.0 movie,
+    /// and only its script initializers are run.
+    pub fn new(abc: Vec<u8>) -> Self {
+        Self {
+            abc,
+            domain: None,
+            entry_point: None,
+        }
+    }
+
+    /// Only inject this script into movies loaded from the given host, or any of its subdomains.
+    pub fn with_domain(mut self, domain: impl Into<String>) -> Self {
+        self.domain = Some(domain.into());
+        self
+    }
+
+    /// Sets the name of a public global function defined by this script,
+    /// which is called with the root display object after the script is loaded.
+    pub fn with_entry_point(mut self, entry_point: impl Into<String>) -> Self {
+        self.entry_point = Some(entry_point.into());
+        self
+    }
+
+    /// Whether this script should be injected into a movie loaded from `url`.
+    fn applies_to(&self, url: &str) -> bool {
+        let Some(domain) = &self.domain else {
+            return true;
+        };
+
+        let Ok(url) = Url::parse(url) else {
+            return false;
+        };
+
+        url.host_str().map_or(false, |host| {
+            host.eq_ignore_ascii_case(domain)
+                || host
+                    .to_ascii_lowercase()
+                    .ends_with(&format!(".{}", domain.to_ascii_lowercase()))
+        })
+    }
+}
+
+/// Loads all applicable injected scripts into the domain of the root movie,
+/// and calls their entry points.
+pub(crate) fn run_injected_scripts(
+    context: &mut UpdateContext<'_, '_>,
+    scripts: &[InjectedScript],
+) {
+    if !context.is_action_script_3() {
+        if !scripts.is_empty() {
+            tracing::warn!("Injected scripts are only supported for ActionScript 3.0 movies");
+        }
+        return;
+    }
+
+    let Some(root) = context.stage.root_clip() else {
+        return;
+    };
+    let movie = context.swf.clone();
+    let domain = context
+        .library
+        .library_for_movie_mut(movie.clone())
+        .avm2_domain();
+
+    for script in scripts
+        .iter()
+        .filter(|script| script.applies_to(movie.url()))
+    {
+        if let Err(e) = Avm2::do_abc(
+            context,
+            &script.abc,
+            None,
+            DoAbc2Flag::empty(),
+            domain,
+            Some(movie.clone()),
+        ) {
+            tracing::error!("Error loading injected script: {}", e);
+            continue;
+        }
+
+        let Some(entry_point) = &script.entry_point else {
+            continue;
+        };
+
+        let mut activation = Activation::from_domain(context.reborrow(), domain);
+        let name = QName::new(
+            activation.avm2().public_namespace,
+            AvmString::new_utf8(activation.context.gc_context, entry_point),
+        );
+        match domain.get_defined_value(&mut activation, name) {
+            Ok(Value::Object(function)) => {
+                if let Err(e) = function.call(None, &[root.object2()], &mut activation) {
+                    tracing::error!("Error in injected script `{}`: {}", entry_point, e);
+                }
+            }
+            Ok(_) => tracing::error!(
+                "Injected script entry point `{}` is not a function",
+                entry_point
+            ),
+            Err(e) => tracing::error!(
+                "Couldn't find injected script entry point `{}`: {}",
+                entry_point,
+                e
+            ),
+        }
+    }
+}
@@ -16,9 +16,12 @@ extern crate num_derive;
 
 #[macro_use]
 mod avm1;
+pub mod asset_export;
 mod avm2;
 mod binary_data;
 pub mod bitmap;
+mod camera;
+pub mod captions;
 mod character;
 pub mod context;
 pub mod context_menu;
@@ -30,31 +33,46 @@ pub mod focus_tracker;
 mod font;
 mod frame_lifecycle;
 mod html;
+pub mod i18n;
+pub mod injected_script;
 mod library;
 pub mod limits;
 pub mod loader;
 mod locale;
+pub mod network_log;
+mod object_inspector;
+pub mod patch;
+mod perf_hud;
 mod player;
 mod prelude;
+mod printing;
+pub mod save_state;
+mod security;
+pub mod settings;
 mod streams;
 pub mod string;
 pub mod tag_utils;
+mod timeline_inspector;
 pub mod timer;
 mod types;
 mod vminterface;
 mod xml;
 
 pub mod backend;
+pub mod compatibility_hints;
 pub mod compatibility_rules;
 pub mod config;
 pub mod external;
 pub mod stub;
 
 pub use avm1::globals::system::SandboxType;
+#[cfg(feature = "fuzzing")]
+pub use avm2::verify_abc_standalone;
 pub use context_menu::ContextMenuItem;
 pub use events::PlayerEvent;
 pub use indexmap;
 pub use loader::LoadBehavior;
+pub use object_inspector::{InspectedProperty, ValueKind};
 pub use player::{Player, PlayerBuilder, StaticCallstack};
 pub use ruffle_render::backend::ViewportDimensions;
 pub use swf;
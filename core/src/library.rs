@@ -1,18 +1,22 @@
 use crate::avm1::PropertyMap as Avm1PropertyMap;
 use crate::avm2::{ClassObject as Avm2ClassObject, Domain as Avm2Domain};
 use crate::backend::audio::SoundHandle;
+use crate::bitmap::predecode::BitmapPredecoder;
 use crate::character::Character;
 
 use crate::display_object::{Bitmap, Graphic, MorphShape, TDisplayObject, Text};
 use crate::font::{Font, FontDescriptor};
+use crate::patch::texture_hash;
 use crate::prelude::*;
+use crate::security::AllowedDomains;
 use crate::string::AvmString;
-use crate::tag_utils::SwfMovie;
+use crate::tag_utils::{SwfMovie, SwfSlice};
 use gc_arena::{Collect, MutationContext};
 use ruffle_render::backend::RenderBackend;
-use ruffle_render::bitmap::BitmapHandle;
+use ruffle_render::bitmap::{Bitmap as RenderBitmap, BitmapHandle};
 use ruffle_render::utils::remove_invalid_jpeg_data;
 
+use std::cell::RefCell;
 use std::collections::HashMap;
 use std::sync::{Arc, Weak};
 use swf::CharacterId;
@@ -125,6 +129,33 @@ pub struct MovieLibrary<'gc> {
     jpeg_tables: Option<Vec<u8>>,
     fonts: HashMap<FontDescriptor, Font<'gc>>,
     avm2_domain: Option<Avm2Domain<'gc>>,
+
+    /// Replacement characters from a movie patch, used instead of the characters
+    /// defined by the SWF with the same ID.
+    patched_characters: HashMap<CharacterId, Character<'gc>>,
+
+    /// High-resolution replacements for bitmaps from a movie patch, keyed by texture hash.
+    #[collect(require_static)]
+    texture_substitutions: HashMap<u64, RenderBitmap>,
+
+    /// The replacement textures of the bitmap characters matching a texture substitution.
+    /// These are registered with the renderer on first use.
+    #[collect(require_static)]
+    substituted_textures: RefCell<HashMap<CharacterId, SubstitutedTexture>>,
+
+    /// The domains this movie allows to script it.
+    #[collect(require_static)]
+    allowed_domains: AllowedDomains,
+
+    /// The bitmaps of this movie being decoded ahead of preloading, once started.
+    #[collect(require_static)]
+    bitmap_predecoder: Option<BitmapPredecoder>,
+}
+
+enum SubstitutedTexture {
+    Pending(RenderBitmap),
+    Registered(BitmapHandle),
+    Failed,
 }
 
 impl<'gc> MovieLibrary<'gc> {
@@ -135,12 +166,91 @@ impl<'gc> MovieLibrary<'gc> {
             jpeg_tables: None,
             fonts: HashMap::new(),
             avm2_domain: None,
+            patched_characters: HashMap::new(),
+            texture_substitutions: HashMap::new(),
+            substituted_textures: RefCell::new(HashMap::new()),
+            allowed_domains: AllowedDomains::default(),
+            bitmap_predecoder: None,
+        }
+    }
+
+    /// Starts decoding the bitmaps of the movie in the background, if this wasn't done yet.
+    /// The results are picked up with `take_predecoded_bitmap` during preloading.
+    pub fn predecode_bitmaps(&mut self, movie: SwfSlice) {
+        if self.bitmap_predecoder.is_none() {
+            self.bitmap_predecoder = Some(BitmapPredecoder::start(movie));
+        }
+    }
+
+    pub fn take_predecoded_bitmap(&mut self, id: CharacterId) -> Option<RenderBitmap> {
+        self.bitmap_predecoder.as_mut()?.take(id)
+    }
+
+    /// Sets the replacement characters of a movie patch, keyed by character ID.
+    /// These take effect as the original characters are registered.
+    pub fn set_patched_characters(&mut self, characters: Vec<(CharacterId, Character<'gc>)>) {
+        self.patched_characters.extend(characters);
+    }
+
+    /// Sets the high-resolution bitmap replacements of a movie patch, keyed by texture hash.
+    pub fn set_texture_substitutions(&mut self, textures: HashMap<u64, RenderBitmap>) {
+        self.texture_substitutions = textures;
+    }
+
+    /// Checks whether a newly defined bitmap has a high-resolution replacement,
+    /// which is then used when rendering shapes filled with this bitmap.
+    pub fn substitute_texture(&mut self, id: CharacterId, bitmap: &RenderBitmap) {
+        if self.texture_substitutions.is_empty() && !tracing::enabled!(tracing::Level::DEBUG) {
+            return;
+        }
+
+        let hash = texture_hash(bitmap);
+        tracing::debug!("Bitmap {} has texture hash {:016x}", id, hash);
+        if let Some(texture) = self.texture_substitutions.get(&hash) {
+            self.substituted_textures
+                .get_mut()
+                .insert(id, SubstitutedTexture::Pending(texture.clone()));
+        }
+    }
+
+    /// Returns the replacement texture of a bitmap character, registering it if necessary.
+    fn substituted_texture(
+        &self,
+        id: CharacterId,
+        backend: &mut dyn RenderBackend,
+    ) -> Option<BitmapHandle> {
+        let mut textures = self.substituted_textures.borrow_mut();
+        let texture = textures.get_mut(&id)?;
+        if let SubstitutedTexture::Pending(bitmap) =
+            std::mem::replace(texture, SubstitutedTexture::Failed)
+        {
+            match backend.register_bitmap(bitmap) {
+                Ok(handle) => *texture = SubstitutedTexture::Registered(handle),
+                Err(e) => tracing::error!("Couldn't register texture of bitmap {}: {}", id, e),
+            }
+        }
+
+        match texture {
+            SubstitutedTexture::Registered(handle) => Some(handle.clone()),
+            _ => None,
         }
     }
 
     pub fn register_character(&mut self, id: CharacterId, character: Character<'gc>) {
         // TODO(Herschel): What is the behavior if id already exists?
         if !self.contains_character(id) {
+            let character = match (self.patched_characters.remove(&id), character) {
+                (Some(patched @ Character::Bitmap(_)), Character::Bitmap(_))
+                | (Some(patched @ Character::Sound(_)), Character::Sound(_)) => patched,
+                (Some(_), character) => {
+                    tracing::warn!(
+                        "Ignoring patched character {}, which doesn't have the type of the original",
+                        id
+                    );
+                    character
+                }
+                (None, character) => character,
+            };
             if let Character::Font(font) = character {
                 // The first font with a given descriptor wins
                 if !self.fonts.contains_key(font.descriptor()) {
@@ -306,6 +416,16 @@ impl<'gc> MovieLibrary<'gc> {
         }
     }
 
+    /// Returns the character ID of a sound of this movie.
+    pub fn sound_id(&self, sound: SoundHandle) -> Option<CharacterId> {
+        self.characters
+            .iter()
+            .find_map(|(id, character)| match character {
+                Character::Sound(other) if *other == sound => Some(*id),
+                _ => None,
+            })
+    }
+
     /// Returns the `Text` with the given character ID.
     /// Returns `None` if the ID does not exist or is not a `Text`.
     pub fn get_text(&self, id: CharacterId) -> Option<Text<'gc>> {
@@ -349,6 +469,14 @@ impl<'gc> MovieLibrary<'gc> {
     pub fn avm2_domain(&self) -> Avm2Domain<'gc> {
         self.avm2_domain.unwrap()
     }
+
+    pub fn allowed_domains(&self) -> &AllowedDomains {
+        &self.allowed_domains
+    }
+
+    pub fn allowed_domains_mut(&mut self) -> &mut AllowedDomains {
+        &mut self.allowed_domains
+    }
 }
 
 pub struct MovieLibrarySource<'a, 'gc> {
@@ -367,6 +495,10 @@ impl<'a, 'gc> ruffle_render::bitmap::BitmapSource for MovieLibrarySource<'a, 'gc
     }
 
     fn bitmap_handle(&self, id: u16, backend: &mut dyn RenderBackend) -> Option<BitmapHandle> {
+        if let Some(handle) = self.library.substituted_texture(id, backend) {
+            return Some(handle);
+        }
+
         self.library.get_bitmap(id).map(|bitmap| {
             bitmap
                 .bitmap_data_wrapper()
@@ -1,4 +1,7 @@
+use crate::backend::ui::UiBackend;
 use crate::context::UpdateContext;
+use crate::i18n::Localizations;
+use instant::Instant;
 use std::time::Duration;
 
 /// Indication of how long execution is allowed to take.
@@ -91,3 +94,129 @@ impl ExecutionLimit {
         false
     }
 }
+
+/// The verdict of the script watchdog on the currently running script.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ScriptTimeout {
+    /// The script may keep running.
+    None,
+
+    /// The script has run for too long, and the user chose to abort it.
+    ///
+    /// AVM2 scripts get a chance to clean up, as this is thrown into them as a
+    /// catchable `ScriptTimeoutError`.
+    Abort,
+
+    /// The user chose to abort the scripts of this update earlier.
+    ///
+    /// AVM1 scripts can't catch the timeout, so they are stopped, while AVM2
+    /// scripts keep their chance to clean up until `Terminate`.
+    Aborted,
+
+    /// The script kept running after it was aborted, and must be terminated.
+    Terminate,
+}
+
+/// Keeps track of how long scripts have been running for in the current turn
+/// of the event loop, to stop runaway scripts from freezing the player.
+///
+/// Like Flash Player, the user is asked whether to abort a script once it has
+/// run for longer than the script timeout (15 seconds by default), and an
+/// aborted script that is still running after another timeout is terminated.
+pub struct ScriptWatchdog {
+    /// When scripts started running, or when the user last chose to let them
+    /// continue.
+    started: Instant,
+
+    /// When the user chose to abort the running scripts, if they did.
+    /// This lasts until the end of the update.
+    aborted: Option<Instant>,
+}
+
+impl ScriptWatchdog {
+    pub fn new() -> Self {
+        Self {
+            started: Instant::now(),
+            aborted: None,
+        }
+    }
+
+    /// Starts watching a new turn of the event loop.
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    /// Checks whether scripts have run for longer than `timeout`.
+    ///
+    /// The first time they have, the user is asked through the UI backend
+    /// whether the scripts should be aborted.
+    pub fn check(
+        &mut self,
+        ui: &mut dyn UiBackend,
+        localizations: &Localizations,
+        timeout: Duration,
+    ) -> ScriptTimeout {
+        match self.aborted {
+            Some(aborted) if aborted.elapsed() >= timeout => ScriptTimeout::Terminate,
+            Some(_) => ScriptTimeout::Aborted,
+            None if self.started.elapsed() < timeout => ScriptTimeout::None,
+            None => {
+                let message = localizations.text(&ui.language(), "script-timeout-message");
+                if ui.display_script_timeout_prompt(&message) {
+                    self.aborted = Some(Instant::now());
+                    ScriptTimeout::Abort
+                } else {
+                    self.started = Instant::now();
+                    ScriptTimeout::None
+                }
+            }
+        }
+    }
+}
+
+impl Default for ScriptWatchdog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::ui::NullUiBackend;
+
+    #[test]
+    fn test_aborted_until_reset() {
+        let timeout = Duration::from_secs(60);
+        let mut ui = NullUiBackend::new();
+        let localizations = Localizations::new();
+        let mut watchdog = ScriptWatchdog::new();
+        assert_eq!(
+            watchdog.check(&mut ui, &localizations, timeout),
+            ScriptTimeout::None
+        );
+
+        // The null UI backend always chooses to abort.
+        watchdog.started = Instant::now() - timeout;
+        assert_eq!(
+            watchdog.check(&mut ui, &localizations, timeout),
+            ScriptTimeout::Abort
+        );
+        assert_eq!(
+            watchdog.check(&mut ui, &localizations, timeout),
+            ScriptTimeout::Aborted
+        );
+
+        watchdog.aborted = Some(Instant::now() - timeout);
+        assert_eq!(
+            watchdog.check(&mut ui, &localizations, timeout),
+            ScriptTimeout::Terminate
+        );
+
+        watchdog.reset();
+        assert_eq!(
+            watchdog.check(&mut ui, &localizations, timeout),
+            ScriptTimeout::None
+        );
+    }
+}
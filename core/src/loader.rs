@@ -1,5 +1,6 @@
 //! Management of async loaders
 
+use crate::avm1::globals::system::SandboxType;
 use crate::avm1::Avm1;
 use crate::avm1::ExecutionReason;
 use crate::avm1::{Activation, ActivationIdentifier};
@@ -13,7 +14,7 @@ use crate::avm2::{
     Activation as Avm2Activation, Avm2, Domain as Avm2Domain, Object as Avm2Object,
     Value as Avm2Value,
 };
-use crate::backend::navigator::{OwnedFuture, Request};
+use crate::backend::navigator::{OwnedFuture, ProgressCallback, Request};
 use crate::context::{ActionQueue, ActionType, UpdateContext};
 use crate::display_object::{
     Bitmap, DisplayObject, TDisplayObject, TDisplayObjectContainer, TInteractiveObject,
@@ -24,20 +25,30 @@ use crate::limits::ExecutionLimit;
 use crate::player::Player;
 use crate::streams::NetStream;
 use crate::string::AvmString;
-use crate::tag_utils::SwfMovie;
+use crate::tag_utils::{SwfMovie, SWF_CONTENT_TYPE};
 use crate::vminterface::Instantiator;
 use encoding_rs::UTF_8;
 use gc_arena::{Collect, CollectionContext};
 use generational_arena::{Arena, Index};
 use ruffle_render::utils::{determine_jpeg_tag_format, JpegTagFormat};
 use std::fmt;
+use std::sync::atomic::{AtomicU32, Ordering};
 use std::sync::{Arc, Mutex, Weak};
 use std::time::Duration;
 use swf::read::{extract_swz, read_compression_type};
 use thiserror::Error;
-use url::form_urlencoded;
+use url::{form_urlencoded, Url};
 
-pub type Handle = Index;
+/// A handle to a loader of a `LoadManager`.
+///
+/// Handles only refer to loaders of the `LoadManager` that created them. When the player is
+/// reset, its `LoadManager` is replaced, and the loads still in flight are cancelled instead
+/// of resolving into loaders of the new one.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Handle {
+    index: Index,
+    manager: u32,
+}
 
 /// How Ruffle should load movies.
 #[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
@@ -108,6 +119,17 @@ impl ContentType {
         }
     }
 
+    /// The MIME type of this content, as reported by `LoaderInfo.contentType`.
+    pub fn mime_type(self) -> &'static str {
+        match self {
+            Self::Swf => SWF_CONTENT_TYPE,
+            Self::Jpeg => "image/jpeg",
+            Self::Png => "image/png",
+            Self::Gif => "image/gif",
+            Self::Unknown => "application/octet-stream",
+        }
+    }
+
     /// Assert that content is of a given type, and error otherwise.
     fn expect(self, expected: Self) -> Result<Self, Error> {
         if self == expected {
@@ -155,6 +177,11 @@ pub enum Error {
     #[error("Could not fetch: {0}")]
     FetchError(String),
 
+    /// A request that the security sandbox of the movie doesn't allow, along
+    /// with the Flash error code and message describing it.
+    #[error("Security sandbox violation: {1}")]
+    SandboxViolation(u32, String),
+
     #[error("Invalid SWF: {0}")]
     InvalidSwf(#[from] crate::tag_utils::Error),
 
@@ -184,12 +211,39 @@ impl From<crate::avm1::Error<'_>> for Error {
     }
 }
 
+impl Error {
+    /// The error for a movie in the given sandbox attempting to load a URL
+    /// that its sandbox doesn't allow.
+    pub fn sandbox_violation(sandbox_type: SandboxType, swf_url: &str, url: &Url) -> Self {
+        if sandbox_type == SandboxType::LocalWithFile {
+            Error::SandboxViolation(
+                2028,
+                format!(
+                    "Error #2028: Local-with-filesystem SWF file {swf_url} cannot access Internet URL {url}."
+                ),
+            )
+        } else {
+            Error::SandboxViolation(
+                2148,
+                format!(
+                    "Error #2148: SWF file {swf_url} cannot access local resource {url}. Only local-with-filesystem and trusted local SWF files may access local resources."
+                ),
+            )
+        }
+    }
+}
+
 /// Holds all in-progress loads for the player.
-pub struct LoadManager<'gc>(Arena<Loader<'gc>>);
+pub struct LoadManager<'gc> {
+    loaders: Arena<Loader<'gc>>,
+
+    /// The ID of this `LoadManager`, unique to the process, which its handles carry.
+    id: u32,
+}
 
 unsafe impl<'gc> Collect for LoadManager<'gc> {
     fn trace(&self, cc: CollectionContext) {
-        for (_, loader) in self.0.iter() {
+        for (_, loader) in self.loaders.iter() {
             loader.trace(cc)
         }
     }
@@ -198,7 +252,12 @@ unsafe impl<'gc> Collect for LoadManager<'gc> {
 impl<'gc> LoadManager<'gc> {
     /// Construct a new `LoadManager`.
     pub fn new() -> Self {
-        Self(Arena::new())
+        static NEXT_ID: AtomicU32 = AtomicU32::new(0);
+
+        Self {
+            loaders: Arena::new(),
+            id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+        }
     }
 
     /// Add a new loader to the `LoadManager`.
@@ -207,7 +266,10 @@ impl<'gc> LoadManager<'gc> {
     /// valid for as long as the load operation. Once the load finishes,
     /// the handle will be invalidated (and the underlying loader deleted).
     pub fn add_loader(&mut self, loader: Loader<'gc>) -> Handle {
-        let handle = self.0.insert(loader);
+        let handle = Handle {
+            index: self.loaders.insert(loader),
+            manager: self.id,
+        };
         match self.get_loader_mut(handle).unwrap() {
             Loader::RootMovie { self_handle, .. }
             | Loader::Movie { self_handle, .. }
@@ -222,13 +284,21 @@ impl<'gc> LoadManager<'gc> {
     }
 
     /// Retrieve a loader by handle.
+    ///
+    /// Returns `None` if the load finished, or if the handle belongs to another `LoadManager`.
     pub fn get_loader(&self, handle: Handle) -> Option<&Loader<'gc>> {
-        self.0.get(handle)
+        if handle.manager != self.id {
+            return None;
+        }
+        self.loaders.get(handle.index)
     }
 
     /// Retrieve a loader by handle for mutation.
     pub fn get_loader_mut(&mut self, handle: Handle) -> Option<&mut Loader<'gc>> {
-        self.0.get_mut(handle)
+        if handle.manager != self.id {
+            return None;
+        }
+        self.loaders.get_mut(handle.index)
     }
 
     /// Kick off the root movie load.
@@ -258,6 +328,7 @@ impl<'gc> LoadManager<'gc> {
         player: Weak<Mutex<Player>>,
         target_clip: DisplayObject<'gc>,
         request: Request,
+        requesting_movie: Arc<SwfMovie>,
         loader_url: Option<String>,
         event_handler: Option<MovieLoaderEventHandler<'gc>>,
         avm2_data: Option<Avm2LoaderData<'gc>>,
@@ -269,20 +340,25 @@ impl<'gc> LoadManager<'gc> {
             loader_status: LoaderStatus::Pending,
             movie: None,
             avm2_data,
+            download_progress_reported: false,
         };
         let handle = self.add_loader(loader);
         let loader = self.get_loader_mut(handle).unwrap();
-        loader.movie_loader(player, request, loader_url)
+        loader.movie_loader(player, request, requesting_movie, loader_url)
     }
 
     /// Kick off a movie clip load.
     ///
     /// Returns the loader's async process, which you will need to spawn.
+    ///
+    /// The loaded movie is given the URL of the movie that loaded it, so that it is in the
+    /// same security domain.
     pub fn load_movie_into_clip_bytes(
         &mut self,
         player: Weak<Mutex<Player>>,
         target_clip: DisplayObject<'gc>,
         bytes: Vec<u8>,
+        loader_url: String,
         event_handler: Option<MovieLoaderEventHandler<'gc>>,
         avm2_data: Option<Avm2LoaderData<'gc>>,
     ) -> OwnedFuture<(), Error> {
@@ -293,10 +369,11 @@ impl<'gc> LoadManager<'gc> {
             loader_status: LoaderStatus::Pending,
             movie: None,
             avm2_data,
+            download_progress_reported: false,
         };
         let handle = self.add_loader(loader);
         let loader = self.get_loader_mut(handle).unwrap();
-        loader.movie_loader_bytes(player, bytes)
+        loader.movie_loader_bytes(player, bytes, loader_url)
     }
 
     /// Indicates that a movie clip has initialized (ran its first frame).
@@ -305,14 +382,14 @@ impl<'gc> LoadManager<'gc> {
     pub fn movie_clip_on_load(&mut self, queue: &mut ActionQueue<'gc>) {
         let mut invalidated_loaders = vec![];
 
-        for (index, loader) in self.0.iter_mut().rev() {
+        for (index, loader) in self.loaders.iter_mut().rev() {
             if loader.movie_clip_loaded(queue) {
                 invalidated_loaders.push(index);
             }
         }
 
         for index in invalidated_loaders {
-            self.0.remove(index);
+            self.loaders.remove(index);
         }
     }
 
@@ -324,6 +401,7 @@ impl<'gc> LoadManager<'gc> {
         player: Weak<Mutex<Player>>,
         target_object: Object<'gc>,
         request: Request,
+        requesting_movie: Arc<SwfMovie>,
     ) -> OwnedFuture<(), Error> {
         let loader = Loader::Form {
             self_handle: None,
@@ -331,7 +409,7 @@ impl<'gc> LoadManager<'gc> {
         };
         let handle = self.add_loader(loader);
         let loader = self.get_loader_mut(handle).unwrap();
-        loader.form_loader(player, request)
+        loader.form_loader(player, request, requesting_movie)
     }
 
     /// Kick off a form data load into an AVM1 object.
@@ -342,6 +420,7 @@ impl<'gc> LoadManager<'gc> {
         player: Weak<Mutex<Player>>,
         target_object: Object<'gc>,
         request: Request,
+        requesting_movie: Arc<SwfMovie>,
     ) -> OwnedFuture<(), Error> {
         let loader = Loader::LoadVars {
             self_handle: None,
@@ -349,7 +428,7 @@ impl<'gc> LoadManager<'gc> {
         };
         let handle = self.add_loader(loader);
         let loader = self.get_loader_mut(handle).unwrap();
-        loader.load_vars_loader(player, request)
+        loader.load_vars_loader(player, request, requesting_movie)
     }
 
     /// Kick off a data load into a `URLLoader`, updating
@@ -361,6 +440,7 @@ impl<'gc> LoadManager<'gc> {
         player: Weak<Mutex<Player>>,
         target_object: Avm2Object<'gc>,
         request: Request,
+        requesting_movie: Arc<SwfMovie>,
         data_format: DataFormat,
     ) -> OwnedFuture<(), Error> {
         let loader = Loader::LoadURLLoader {
@@ -369,7 +449,7 @@ impl<'gc> LoadManager<'gc> {
         };
         let handle = self.add_loader(loader);
         let loader = self.get_loader_mut(handle).unwrap();
-        loader.load_url_loader(player, request, data_format)
+        loader.load_url_loader(player, request, requesting_movie, data_format)
     }
 
     /// Kick off an AVM1 audio load.
@@ -380,6 +460,7 @@ impl<'gc> LoadManager<'gc> {
         player: Weak<Mutex<Player>>,
         target_object: SoundObject<'gc>,
         request: Request,
+        requesting_movie: Arc<SwfMovie>,
         is_streaming: bool,
     ) -> OwnedFuture<(), Error> {
         let loader = Loader::SoundAvm1 {
@@ -388,7 +469,7 @@ impl<'gc> LoadManager<'gc> {
         };
         let handle = self.add_loader(loader);
         let loader = self.get_loader_mut(handle).unwrap();
-        loader.sound_loader_avm1(player, request, is_streaming)
+        loader.sound_loader_avm1(player, request, requesting_movie, is_streaming)
     }
 
     /// Kick off an AVM2 audio load.
@@ -399,6 +480,7 @@ impl<'gc> LoadManager<'gc> {
         player: Weak<Mutex<Player>>,
         target_object: Avm2Object<'gc>,
         request: Request,
+        requesting_movie: Arc<SwfMovie>,
     ) -> OwnedFuture<(), Error> {
         let loader = Loader::SoundAvm2 {
             self_handle: None,
@@ -406,7 +488,7 @@ impl<'gc> LoadManager<'gc> {
         };
         let handle = self.add_loader(loader);
         let loader = self.get_loader_mut(handle).unwrap();
-        loader.sound_loader_avm2(player, request)
+        loader.sound_loader_avm2(player, request, requesting_movie)
     }
 
     pub fn load_netstream(
@@ -414,6 +496,7 @@ impl<'gc> LoadManager<'gc> {
         player: Weak<Mutex<Player>>,
         target_stream: NetStream<'gc>,
         request: Request,
+        requesting_movie: Arc<SwfMovie>,
     ) -> OwnedFuture<(), Error> {
         let loader = Loader::NetStream {
             self_handle: None,
@@ -421,7 +504,7 @@ impl<'gc> LoadManager<'gc> {
         };
         let handle = self.add_loader(loader);
         let loader = self.get_loader_mut(handle).unwrap();
-        loader.stream_loader(player, request)
+        loader.stream_loader(player, request, requesting_movie)
     }
 
     /// Process tags on all loaders in the Parsing phase.
@@ -429,7 +512,13 @@ impl<'gc> LoadManager<'gc> {
     /// Returns true if *all* loaders finished preloading.
     pub fn preload_tick(context: &mut UpdateContext<'_, 'gc>, limit: &mut ExecutionLimit) -> bool {
         let mut did_finish = true;
-        let handles: Vec<_> = context.load_manager.0.iter().map(|(h, _)| h).collect();
+        let manager = context.load_manager.id;
+        let handles: Vec<_> = context
+            .load_manager
+            .loaders
+            .iter()
+            .map(|(index, _)| Handle { index, manager })
+            .collect();
 
         for handle in handles {
             let status = match context.load_manager.get_loader(handle) {
@@ -529,6 +618,9 @@ pub enum Loader<'gc> {
 
         /// AVM2 specific data for this SWF.
         avm2_data: Option<Avm2LoaderData<'gc>>,
+
+        /// Whether progress events were already fired while the movie was downloading.
+        download_progress_reported: bool,
     },
 
     /// Loader that is loading form data into an AVM1 object scope.
@@ -730,7 +822,7 @@ impl<'gc> Loader<'gc> {
             .expect("Could not upgrade weak reference to player");
 
         Box::pin(async move {
-            let fetch = player.lock().unwrap().navigator().fetch(request);
+            let fetch = player.lock().unwrap().fetch_root_movie_data(request);
 
             let response = fetch.await.map_err(|error| {
                 player
@@ -773,6 +865,7 @@ impl<'gc> Loader<'gc> {
         &mut self,
         player: Weak<Mutex<Player>>,
         request: Request,
+        requesting_movie: Arc<SwfMovie>,
         loader_url: Option<String>,
     ) -> OwnedFuture<(), Error> {
         let handle = match self {
@@ -785,7 +878,29 @@ impl<'gc> Loader<'gc> {
             .expect("Could not upgrade weak reference to player");
 
         Box::pin(async move {
-            let fetch = player.lock().unwrap().navigator().fetch(request);
+            let on_progress: ProgressCallback = {
+                let player = Arc::downgrade(&player);
+                Box::new(move |loaded, total| {
+                    let Some(player) = player.upgrade() else {
+                        return;
+                    };
+                    let _ = player.lock().unwrap().update(|uc| {
+                        if let Some(Loader::Movie {
+                            download_progress_reported,
+                            ..
+                        }) = uc.load_manager.get_loader_mut(handle)
+                        {
+                            *download_progress_reported = true;
+                        }
+                        Loader::movie_loader_progress(handle, uc, loaded, total)
+                    });
+                })
+            };
+            let fetch =
+                player
+                    .lock()
+                    .unwrap()
+                    .fetch_with_progress(request, &requesting_movie, on_progress);
 
             let mut replacing_root_movie = false;
             player.lock().unwrap().update(|uc| -> Result<(), Error> {
@@ -809,6 +924,20 @@ impl<'gc> Loader<'gc> {
                     mc.replace_with_movie(uc, None, None);
                 }
 
+                // Loading a movie into `_level0` unloads every other level as well.
+                if replacing_root_movie && !uc.is_action_script_3() {
+                    let levels: Vec<_> = uc
+                        .stage
+                        .iter_render_list()
+                        .filter(|level| level.depth() != 0)
+                        .collect();
+                    for level in levels {
+                        if let Some(mc) = level.as_movie_clip() {
+                            mc.avm1_unload_movie(uc);
+                        }
+                    }
+                }
+
                 Loader::movie_loader_start(handle, uc)
             })?;
 
@@ -833,7 +962,7 @@ impl<'gc> Loader<'gc> {
                 Err(e) => {
                     tracing::error!("Error during movie loading: {:?}", e);
                     player.lock().unwrap().update(|uc| -> Result<(), Error> {
-                        Loader::movie_loader_error(handle, uc)
+                        Loader::movie_loader_error(handle, uc, &e)
                     })?;
                 }
             }
@@ -846,6 +975,7 @@ impl<'gc> Loader<'gc> {
         &mut self,
         player: Weak<Mutex<Player>>,
         bytes: Vec<u8>,
+        loader_url: String,
     ) -> OwnedFuture<(), Error> {
         let handle = match self {
             Loader::Movie { self_handle, .. } => self_handle.expect("Loader not self-introduced"),
@@ -886,12 +1016,19 @@ impl<'gc> Loader<'gc> {
             if replacing_root_movie {
                 ContentType::sniff(&bytes).expect(ContentType::Swf)?;
 
-                let movie = SwfMovie::from_data(&bytes, "file:///".into(), None)?;
+                let movie = SwfMovie::from_data(&bytes, loader_url.clone(), Some(loader_url))?;
                 player.lock().unwrap().set_root_movie(movie);
                 return Ok(());
             }
 
-            Loader::movie_loader_data(handle, player, &bytes, "file:///".into(), None, true)
+            Loader::movie_loader_data(
+                handle,
+                player,
+                &bytes,
+                loader_url.clone(),
+                Some(loader_url),
+                true,
+            )
         })
     }
 
@@ -899,6 +1036,7 @@ impl<'gc> Loader<'gc> {
         &mut self,
         player: Weak<Mutex<Player>>,
         request: Request,
+        requesting_movie: Arc<SwfMovie>,
     ) -> OwnedFuture<(), Error> {
         let handle = match self {
             Loader::Form { self_handle, .. } => self_handle.expect("Loader not self-introduced"),
@@ -910,7 +1048,7 @@ impl<'gc> Loader<'gc> {
             .expect("Could not upgrade weak reference to player");
 
         Box::pin(async move {
-            let fetch = player.lock().unwrap().navigator().fetch(request);
+            let fetch = player.lock().unwrap().fetch(request, &requesting_movie);
 
             let response = fetch.await?;
 
@@ -960,6 +1098,7 @@ impl<'gc> Loader<'gc> {
         &mut self,
         player: Weak<Mutex<Player>>,
         request: Request,
+        requesting_movie: Arc<SwfMovie>,
     ) -> OwnedFuture<(), Error> {
         let handle = match self {
             Loader::LoadVars { self_handle, .. } => {
@@ -973,7 +1112,7 @@ impl<'gc> Loader<'gc> {
             .expect("Could not upgrade weak reference to player");
 
         Box::pin(async move {
-            let fetch = player.lock().unwrap().navigator().fetch(request);
+            let fetch = player.lock().unwrap().fetch(request, &requesting_movie);
 
             let data = fetch.await;
 
@@ -1055,6 +1194,7 @@ impl<'gc> Loader<'gc> {
         &mut self,
         player: Weak<Mutex<Player>>,
         request: Request,
+        requesting_movie: Arc<SwfMovie>,
         data_format: DataFormat,
     ) -> OwnedFuture<(), Error> {
         let handle = match self {
@@ -1069,7 +1209,7 @@ impl<'gc> Loader<'gc> {
             .expect("Could not upgrade weak reference to player");
 
         Box::pin(async move {
-            let fetch = player.lock().unwrap().navigator().fetch(request);
+            let fetch = player.lock().unwrap().fetch(request, &requesting_movie);
             let response = fetch.await;
 
             player.lock().unwrap().update(|uc| {
@@ -1134,6 +1274,13 @@ impl<'gc> Loader<'gc> {
                         );
                         Avm2::dispatch_event(uc, complete_evt, target);
                     }
+                    Err(Error::SandboxViolation(code, message)) => {
+                        set_data(Vec::new(), &mut activation, target, data_format);
+
+                        let security_error_evt =
+                            Loader::security_error_event(&mut activation, code, &message)?;
+                        Avm2::dispatch_event(uc, security_error_evt, target);
+                    }
                     Err(_err) => {
                         // Testing with Flash shoes that the 'data' property is cleared
                         // when an error occurs
@@ -1170,6 +1317,7 @@ impl<'gc> Loader<'gc> {
         &mut self,
         player: Weak<Mutex<Player>>,
         request: Request,
+        requesting_movie: Arc<SwfMovie>,
         is_streaming: bool,
     ) -> OwnedFuture<(), Error> {
         let handle = match self {
@@ -1184,7 +1332,7 @@ impl<'gc> Loader<'gc> {
             .expect("Could not upgrade weak reference to player");
 
         Box::pin(async move {
-            let fetch = player.lock().unwrap().navigator().fetch(request);
+            let fetch = player.lock().unwrap().fetch(request, &requesting_movie);
             let data = fetch.await;
 
             // Fire the load handler.
@@ -1233,6 +1381,7 @@ impl<'gc> Loader<'gc> {
         &mut self,
         player: Weak<Mutex<Player>>,
         request: Request,
+        requesting_movie: Arc<SwfMovie>,
     ) -> OwnedFuture<(), Error> {
         let handle = match self {
             Loader::SoundAvm2 { self_handle, .. } => {
@@ -1246,7 +1395,7 @@ impl<'gc> Loader<'gc> {
             .expect("Could not upgrade weak reference to player");
 
         Box::pin(async move {
-            let fetch = player.lock().unwrap().navigator().fetch(request);
+            let fetch = player.lock().unwrap().fetch(request, &requesting_movie);
             let response = fetch.await;
 
             player.lock().unwrap().update(|uc| {
@@ -1280,6 +1429,12 @@ impl<'gc> Loader<'gc> {
                         );
                         Avm2::dispatch_event(uc, complete_evt, sound_object);
                     }
+                    Err(Error::SandboxViolation(code, message)) => {
+                        let mut activation = Avm2Activation::from_nothing(uc.reborrow());
+                        let security_error_evt =
+                            Loader::security_error_event(&mut activation, code, &message)?;
+                        Avm2::dispatch_event(uc, security_error_evt, sound_object);
+                    }
                     Err(_err) => {
                         // FIXME: Match the exact error message generated by Flash.
                         let mut activation = Avm2Activation::from_nothing(uc.reborrow());
@@ -1310,6 +1465,7 @@ impl<'gc> Loader<'gc> {
         &mut self,
         player: Weak<Mutex<Player>>,
         request: Request,
+        requesting_movie: Arc<SwfMovie>,
     ) -> OwnedFuture<(), Error> {
         let handle = match self {
             Loader::SoundAvm2 { self_handle, .. } => {
@@ -1323,7 +1479,7 @@ impl<'gc> Loader<'gc> {
             .expect("Could not upgrade weak reference to player");
 
         Box::pin(async move {
-            let fetch = player.lock().unwrap().navigator().fetch(request);
+            let fetch = player.lock().unwrap().fetch(request, &requesting_movie);
             let response = fetch.await;
 
             player.lock().unwrap().update(|uc| {
@@ -1336,10 +1492,10 @@ impl<'gc> Loader<'gc> {
 
                 match response {
                     Ok(mut response) => {
-                        stream.load_buffer(uc.gc_context, &mut response.body);
+                        stream.load_buffer(uc.gc_context, &mut response.body, true);
                     }
                     Err(err) => {
-                        stream.report_error(err);
+                        stream.report_error(uc, err);
                     }
                 }
 
@@ -1349,7 +1505,7 @@ impl<'gc> Loader<'gc> {
     }
 
     /// Report a movie loader start event to script code.
-    fn movie_loader_start(handle: Index, uc: &mut UpdateContext<'_, 'gc>) -> Result<(), Error> {
+    fn movie_loader_start(handle: Handle, uc: &mut UpdateContext<'_, 'gc>) -> Result<(), Error> {
         let me = uc.load_manager.get_loader_mut(handle);
         if me.is_none() {
             return Err(Error::Cancelled);
@@ -1405,16 +1561,23 @@ impl<'gc> Loader<'gc> {
             }
         }
         player.lock().unwrap().update(|uc| {
-            let (clip, event_handler, avm2_data) = match uc.load_manager.get_loader(handle) {
-                Some(Loader::Movie {
-                    target_clip,
-                    event_handler,
-                    avm2_data,
-                    ..
-                }) => (*target_clip, *event_handler, *avm2_data),
-                None => return Err(Error::Cancelled),
-                _ => unreachable!(),
-            };
+            let (clip, event_handler, avm2_data, download_progress_reported) =
+                match uc.load_manager.get_loader(handle) {
+                    Some(Loader::Movie {
+                        target_clip,
+                        event_handler,
+                        avm2_data,
+                        download_progress_reported,
+                        ..
+                    }) => (
+                        *target_clip,
+                        *event_handler,
+                        *avm2_data,
+                        *download_progress_reported,
+                    ),
+                    None => return Err(Error::Cancelled),
+                    _ => unreachable!(),
+                };
 
             if let ContentType::Unknown = sniffed_type {
                 length = 0;
@@ -1422,7 +1585,13 @@ impl<'gc> Loader<'gc> {
 
             match sniffed_type {
                 ContentType::Swf => {
-                    let movie = Arc::new(SwfMovie::from_data(data, url, loader_url)?);
+                    let mut movie = SwfMovie::from_data(data, url, loader_url)?;
+                    // The parameters of a loaded movie come from the query string of its URL.
+                    if let Ok(url) = url::Url::parse(movie.url()) {
+                        let parameters: Vec<_> = url.query_pairs().into_owned().collect();
+                        movie.append_parameters(parameters);
+                    }
+                    let movie = Arc::new(movie);
 
                     match uc.load_manager.get_loader_mut(handle) {
                         Some(Loader::Movie {
@@ -1452,7 +1621,9 @@ impl<'gc> Loader<'gc> {
                         // Flash always fires an initial 'progress' event with
                         // bytesLoaded=0 and bytesTotal set to the proper value.
                         // This only seems to happen for an AVM2 event handler
-                        Loader::movie_loader_progress(handle, uc, 0, length)?;
+                        if !download_progress_reported {
+                            Loader::movie_loader_progress(handle, uc, 0, length)?;
+                        }
                     }
 
                     let mut activation = Avm2Activation::from_nothing(uc.reborrow());
@@ -1512,10 +1683,16 @@ impl<'gc> Loader<'gc> {
                         // Flash always fires an initial 'progress' event with
                         // bytesLoaded=0 and bytesTotal set to the proper value.
                         // This only seems to happen for an AVM2 event handler
-                        Loader::movie_loader_progress(handle, uc, 0, length)?;
+                        if !download_progress_reported {
+                            Loader::movie_loader_progress(handle, uc, 0, length)?;
+                        }
                     }
 
-                    let movie = Arc::new(SwfMovie::from_loaded_image(url, length));
+                    let movie = Arc::new(SwfMovie::from_loaded_image(
+                        url,
+                        length,
+                        sniffed_type.mime_type(),
+                    ));
 
                     let bitmap = ruffle_render::utils::decode_define_bits_jpeg(data, None)?;
                     let bitmap_obj = Bitmap::new(uc, 0, bitmap)?;
@@ -1540,7 +1717,7 @@ impl<'gc> Loader<'gc> {
     ///
     /// The current and total length are always reported as compressed lengths.
     fn movie_loader_progress(
-        handle: Index,
+        handle: Handle,
         uc: &mut UpdateContext<'_, 'gc>,
         cur_len: usize,
         total_len: usize,
@@ -1604,7 +1781,7 @@ impl<'gc> Loader<'gc> {
     }
 
     /// Report a movie loader completion to script code.
-    fn movie_loader_complete(handle: Index, uc: &mut UpdateContext<'_, 'gc>) -> Result<(), Error> {
+    fn movie_loader_complete(handle: Handle, uc: &mut UpdateContext<'_, 'gc>) -> Result<(), Error> {
         let (clip, event_handler) = match uc.load_manager.get_loader_mut(handle) {
             Some(Loader::Movie {
                 target_clip,
@@ -1648,12 +1825,39 @@ impl<'gc> Loader<'gc> {
         Ok(())
     }
 
+    /// Construct the `securityError` event for a load that was blocked by the
+    /// security sandbox.
+    fn security_error_event(
+        activation: &mut Avm2Activation<'_, 'gc>,
+        code: u32,
+        message: &str,
+    ) -> Result<Avm2Object<'gc>, Error> {
+        let message = AvmString::new_utf8(activation.context.gc_context, message);
+        let security_error_evt_cls = activation.avm2().classes().securityerrorevent;
+        security_error_evt_cls
+            .construct(
+                activation,
+                &[
+                    "securityError".into(),
+                    false.into(),
+                    false.into(),
+                    message.into(),
+                    code.into(),
+                ],
+            )
+            .map_err(|e| Error::Avm2Error(e.to_string()))
+    }
+
     /// Report a movie loader error to script code.
     ///
     /// This is an associated function because we cannot borrow both the update
     /// context and one of it's loaders.
-    fn movie_loader_error(handle: Index, uc: &mut UpdateContext<'_, 'gc>) -> Result<(), Error> {
-        //TODO: Inspect the fetch error.
+    fn movie_loader_error(
+        handle: Handle,
+        uc: &mut UpdateContext<'_, 'gc>,
+        error: &Error,
+    ) -> Result<(), Error> {
+        //TODO: Inspect the fetch error further.
         //This requires cooperation from the backend to send abstract
         //error types we can actually inspect.
         //This also can get errors from decoding an invalid SWF file,
@@ -1684,6 +1888,13 @@ impl<'gc> Loader<'gc> {
             }
             Some(MovieLoaderEventHandler::Avm2LoaderInfo(loader_info)) => {
                 let mut activation = Avm2Activation::from_nothing(uc.reborrow());
+                if let Error::SandboxViolation(code, message) = error {
+                    let security_error_evt =
+                        Loader::security_error_event(&mut activation, *code, message)?;
+                    Avm2::dispatch_event(uc, security_error_evt, loader_info);
+                    return Loader::movie_loader_failed(handle, uc);
+                }
+
                 // FIXME - Match the exact error message generated by Flash
 
                 let io_error_evt_cls = activation.avm2().classes().ioerrorevent;
@@ -1705,6 +1916,10 @@ impl<'gc> Loader<'gc> {
             None => {}
         }
 
+        Loader::movie_loader_failed(handle, uc)
+    }
+
+    fn movie_loader_failed(handle: Handle, uc: &mut UpdateContext<'_, 'gc>) -> Result<(), Error> {
         if let Loader::Movie { loader_status, .. } = uc.load_manager.get_loader_mut(handle).unwrap()
         {
             *loader_status = LoaderStatus::Failed;
@@ -1751,3 +1966,23 @@ impl<'gc> Loader<'gc> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn handles_are_only_valid_in_their_load_manager() {
+        let mut old_manager = LoadManager::new();
+        let old_handle = old_manager.add_loader(Loader::RootMovie { self_handle: None });
+
+        // Resetting the player replaces its `LoadManager`, whose first loader takes the same slot.
+        let mut new_manager = LoadManager::new();
+        let new_handle = new_manager.add_loader(Loader::RootMovie { self_handle: None });
+
+        assert!(old_manager.get_loader(old_handle).is_some());
+        assert!(new_manager.get_loader(new_handle).is_some());
+        assert!(new_manager.get_loader(old_handle).is_none());
+        assert!(new_manager.get_loader_mut(old_handle).is_none());
+    }
+}
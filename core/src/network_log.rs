@@ -0,0 +1,355 @@
+//! A log of the requests made by the movie, to diagnose site locks and missing assets.
+//!
+//! Every request sent through the navigator is recorded when it starts, and completed
+//! when its response arrives or it fails, including requests blocked by the sandbox.
+//! The log can be shown as a panel, and exported as an HTTP Archive (HAR) file to be
+//! opened in the developer tools of browsers.
+//!
+//! Navigators only report whether a request succeeded, so successful requests are
+//! recorded with status 200, and failed ones with status 0 and their error.
+
+use crate::backend::navigator::{NavigationMethod, OwnedFuture, Request, Response};
+use crate::context::RenderContext;
+use crate::loader::Error;
+use crate::perf_hud::{panel_height, render_panel};
+use crate::string::WString;
+use chrono::{DateTime, Utc};
+use instant::Instant;
+use serde_json::{json, Value};
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::rc::Rc;
+use std::time::Duration;
+
+/// How many requests are kept, the oldest being dropped first.
+const MAX_ENTRIES: usize = 1000;
+
+/// How many requests are listed at once by the panel, around the selected one.
+const MAX_LINES: usize = 12;
+
+/// How a request ended.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum NetworkOutcome {
+    /// The request hasn't completed yet.
+    Pending,
+
+    /// The response was received, from `url` after any redirects.
+    Loaded { url: String, size: usize },
+
+    /// The request failed, or was blocked.
+    Failed(String),
+}
+
+/// A request recorded in the network log.
+#[derive(Clone, Debug)]
+pub struct NetworkEntry {
+    pub method: NavigationMethod,
+
+    /// The URL of the request, resolved against the URL of the movie.
+    pub url: String,
+
+    /// The URL as requested by the movie, which it's replayed with.
+    pub request_url: String,
+
+    /// The body of the request and its MIME type.
+    pub body: Option<(Vec<u8>, String)>,
+
+    pub started: DateTime<Utc>,
+
+    /// How long the request took, once it completed.
+    pub duration: Option<Duration>,
+
+    pub outcome: NetworkOutcome,
+}
+
+impl NetworkEntry {
+    /// Creates the entry of a request that's starting, to `url` once resolved.
+    pub(crate) fn new(request: &Request, url: String) -> Self {
+        Self {
+            method: request.method(),
+            url,
+            request_url: request.url().to_owned(),
+            body: request.body().clone(),
+            started: Utc::now(),
+            duration: None,
+            outcome: NetworkOutcome::Pending,
+        }
+    }
+
+    /// The status of the response, as exported.
+    pub fn status(&self) -> u16 {
+        match self.outcome {
+            NetworkOutcome::Loaded { .. } => 200,
+            NetworkOutcome::Pending | NetworkOutcome::Failed(_) => 0,
+        }
+    }
+
+    /// Creates a request that sends this one again.
+    pub fn to_request(&self) -> Request {
+        Request::request(self.method, self.request_url.clone(), self.body.clone())
+    }
+
+    fn to_har(&self) -> Value {
+        let body_size = self.body.as_ref().map_or(0, |(data, _)| data.len());
+        let mut request = json!({
+            "method": self.method.to_string(),
+            "url": self.url,
+            "httpVersion": "HTTP/1.1",
+            "cookies": [],
+            "headers": [],
+            "queryString": [],
+            "headersSize": -1,
+            "bodySize": body_size,
+        });
+        if let Some((data, mime_type)) = &self.body {
+            request["postData"] = json!({
+                "mimeType": mime_type,
+                "text": String::from_utf8_lossy(data),
+            });
+        }
+
+        let (status_text, size, redirect_url) = match &self.outcome {
+            NetworkOutcome::Pending => ("Pending".to_owned(), 0, ""),
+            NetworkOutcome::Loaded { url, size } => {
+                let redirect_url = if *url != self.url { url.as_str() } else { "" };
+                ("OK".to_owned(), *size, redirect_url)
+            }
+            NetworkOutcome::Failed(error) => (error.clone(), 0, ""),
+        };
+        let mut response = json!({
+            "status": self.status(),
+            "statusText": status_text,
+            "httpVersion": "HTTP/1.1",
+            "cookies": [],
+            "headers": [],
+            "content": {
+                "size": size,
+                "mimeType": "",
+            },
+            "redirectURL": redirect_url,
+            "headersSize": -1,
+            "bodySize": size,
+        });
+        if let NetworkOutcome::Failed(error) = &self.outcome {
+            response["_error"] = json!(error);
+        }
+
+        let time = self
+            .duration
+            .map_or(-1.0, |duration| duration.as_secs_f64() * 1000.0);
+        json!({
+            "startedDateTime": self.started.to_rfc3339(),
+            "time": time.max(0.0),
+            "request": request,
+            "response": response,
+            "cache": {},
+            "timings": {
+                "send": 0,
+                "wait": time,
+                "receive": 0,
+            },
+        })
+    }
+}
+
+/// The requests made by the movie since the player started.
+#[derive(Default)]
+pub struct NetworkLog {
+    entries: VecDeque<NetworkEntry>,
+
+    /// The ID of the first entry, IDs being given in order.
+    first_id: usize,
+
+    /// The index of the entry selected in the panel.
+    selected: usize,
+}
+
+impl NetworkLog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn entries(&self) -> impl Iterator<Item = &NetworkEntry> {
+        self.entries.iter()
+    }
+
+    /// The entry selected in the panel.
+    pub fn selected(&self) -> Option<&NetworkEntry> {
+        self.entries.get(self.selected)
+    }
+
+    /// Selects the entry `delta` lines below the current one.
+    pub fn move_selection(&mut self, delta: isize) {
+        let last = self.entries.len().saturating_sub(1);
+        let selected = self.selected.min(last) as isize + delta;
+        self.selected = selected.clamp(0, last as isize) as usize;
+    }
+
+    /// Records a request that's starting, returning the ID of its entry.
+    fn start(&mut self, entry: NetworkEntry) -> usize {
+        if self.entries.len() == MAX_ENTRIES {
+            self.entries.pop_front();
+            self.first_id += 1;
+            self.selected = self.selected.saturating_sub(1);
+        }
+        self.entries.push_back(entry);
+        self.first_id + self.entries.len() - 1
+    }
+
+    /// Records how the request of an entry ended, unless it was dropped.
+    fn complete(&mut self, id: usize, outcome: NetworkOutcome, duration: Duration) {
+        if let Some(entry) = id
+            .checked_sub(self.first_id)
+            .and_then(|index| self.entries.get_mut(index))
+        {
+            entry.outcome = outcome;
+            entry.duration = Some(duration);
+        }
+    }
+
+    /// Exports the log as an HTTP Archive.
+    pub fn to_har(&self) -> String {
+        let har = json!({
+            "log": {
+                "version": "1.2",
+                "creator": {
+                    "name": "Ruffle",
+                    "version": env!("CARGO_PKG_VERSION"),
+                },
+                "pages": [],
+                "entries": self.entries.iter().map(NetworkEntry::to_har).collect::<Vec<_>>(),
+            },
+        });
+        serde_json::to_string_pretty(&har).expect("HAR is serializable")
+    }
+
+    /// Renders the panel at the bottom left of the viewport.
+    pub(crate) fn render(&self, context: &mut RenderContext<'_, '_>) {
+        let mut lines = vec![WString::from_utf8(&format!(
+            "Network: {} requests (Ctrl+Up/Down to select, Ctrl+Y to replay, Ctrl+H to export)",
+            self.entries.len()
+        ))];
+        let first = self
+            .selected
+            .saturating_sub(MAX_LINES / 2)
+            .min(self.entries.len().saturating_sub(MAX_LINES));
+        for (index, entry) in self.entries.iter().enumerate().skip(first).take(MAX_LINES) {
+            let marker = if index == self.selected { '>' } else { ' ' };
+            let outcome = match &entry.outcome {
+                NetworkOutcome::Pending => "pending".to_owned(),
+                NetworkOutcome::Loaded { size, .. } => format!("200, {size} bytes"),
+                NetworkOutcome::Failed(error) => format!("failed: {error}"),
+            };
+            let duration = entry.duration.map_or_else(String::new, |duration| {
+                format!(", {} ms", duration.as_millis())
+            });
+            lines.push(WString::from_utf8(&format!(
+                "{marker} {} {} ({outcome}{duration})",
+                entry.method, entry.url
+            )));
+        }
+
+        let height = f64::from(context.renderer.viewport_dimensions().height);
+        let y = (height - panel_height(lines.len())).max(0.0);
+        render_panel(context, &lines, 0.0, y);
+    }
+}
+
+/// Records the request of `entry` in `log`, completing the entry when `fetch` does.
+pub(crate) fn record(
+    log: &Rc<RefCell<NetworkLog>>,
+    entry: NetworkEntry,
+    fetch: OwnedFuture<Response, Error>,
+) -> OwnedFuture<Response, Error> {
+    let id = log.borrow_mut().start(entry);
+    let log = Rc::downgrade(log);
+    let start = Instant::now();
+    Box::pin(async move {
+        let result = fetch.await;
+        if let Some(log) = log.upgrade() {
+            let outcome = match &result {
+                Ok(response) => NetworkOutcome::Loaded {
+                    url: response.url.clone(),
+                    size: response.body.len(),
+                },
+                Err(error) => NetworkOutcome::Failed(error.to_string()),
+            };
+            log.borrow_mut().complete(id, outcome, start.elapsed());
+        }
+        result
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(url: &str, outcome: NetworkOutcome) -> NetworkEntry {
+        NetworkEntry {
+            method: NavigationMethod::Get,
+            url: url.to_owned(),
+            request_url: url.to_owned(),
+            body: None,
+            started: Utc::now(),
+            duration: Some(Duration::from_millis(5)),
+            outcome,
+        }
+    }
+
+    #[test]
+    fn completes_entries_by_id() {
+        let mut log = NetworkLog::new();
+        for _ in 0..MAX_ENTRIES + 1 {
+            log.start(entry("http://example.com/a.swf", NetworkOutcome::Pending));
+        }
+        // The first entry was dropped, so completing it does nothing.
+        log.complete(
+            0,
+            NetworkOutcome::Failed("dropped".to_owned()),
+            Duration::ZERO,
+        );
+        log.complete(
+            MAX_ENTRIES,
+            NetworkOutcome::Failed("last".to_owned()),
+            Duration::ZERO,
+        );
+        assert_eq!(log.entries.len(), MAX_ENTRIES);
+        assert_eq!(
+            log.entries.back().unwrap().outcome,
+            NetworkOutcome::Failed("last".to_owned())
+        );
+        assert!(log
+            .entries()
+            .take(MAX_ENTRIES - 1)
+            .all(|entry| entry.outcome == NetworkOutcome::Pending));
+    }
+
+    #[test]
+    fn exports_har() {
+        let mut log = NetworkLog::new();
+        log.start(entry(
+            "http://example.com/a.swf",
+            NetworkOutcome::Loaded {
+                url: "https://example.com/a.swf".to_owned(),
+                size: 42,
+            },
+        ));
+        log.start(entry(
+            "http://example.com/b.xml",
+            NetworkOutcome::Failed("Couldn't fetch".to_owned()),
+        ));
+
+        let har: Value = serde_json::from_str(&log.to_har()).unwrap();
+        let entries = har["log"]["entries"].as_array().unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0]["request"]["method"], "GET");
+        assert_eq!(entries[0]["response"]["status"], 200);
+        assert_eq!(entries[0]["response"]["content"]["size"], 42);
+        assert_eq!(
+            entries[0]["response"]["redirectURL"],
+            "https://example.com/a.swf"
+        );
+        assert_eq!(entries[1]["response"]["status"], 0);
+        assert_eq!(entries[1]["response"]["_error"], "Couldn't fetch");
+    }
+}
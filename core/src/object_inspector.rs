@@ -0,0 +1,585 @@
+//! A panel browsing the objects of the movie from its root, to debug scripts
+//! between frames.
+//!
+//! Properties are listed without running any script: getters aren't called,
+//! and only their names are shown. Watched properties are read again after
+//! every frame, and booleans, numbers and strings can be edited.
+
+use crate::avm1::{
+    Activation as Avm1Activation, ActivationIdentifier, Object as Avm1Object,
+    TObject as Avm1TObject, Value as Avm1Value,
+};
+use crate::avm2::{
+    Activation as Avm2Activation, Multiname, Object as Avm2Object, Property as Avm2Property,
+    TObject as Avm2TObject, Value as Avm2Value,
+};
+use crate::context::{RenderContext, UpdateContext};
+use crate::display_object::{TDisplayObject, TDisplayObjectContainer};
+use crate::perf_hud::render_panel;
+use crate::string::{AvmString, WString};
+
+/// How many properties are listed at once, around the selected one.
+const MAX_LINES: usize = 30;
+
+/// The display properties of AVM1 display objects, which aren't enumerable.
+const AVM1_DISPLAY_PROPERTIES: &[&str] = &[
+    "_name",
+    "_x",
+    "_y",
+    "_width",
+    "_height",
+    "_xscale",
+    "_yscale",
+    "_rotation",
+    "_alpha",
+    "_visible",
+    "_currentframe",
+    "_totalframes",
+];
+
+/// The type of the value of a property, which decides how it can be edited.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ValueKind {
+    /// An object, whose properties can be inspected in turn.
+    Object,
+    Boolean,
+    Number,
+    String,
+
+    /// `undefined`, `null`, or a getter that isn't called.
+    Other,
+}
+
+/// A property of an inspected object.
+#[derive(Clone, Debug)]
+pub struct InspectedProperty {
+    pub name: String,
+
+    /// The value of the property, formatted for display.
+    pub value: String,
+    pub kind: ValueKind,
+
+    /// Whether the property can be edited with `Player::set_object_property`.
+    pub editable: bool,
+}
+
+#[derive(Clone, Copy)]
+enum AnyObject<'gc> {
+    Avm1(Avm1Object<'gc>),
+    Avm2(Avm2Object<'gc>),
+}
+
+#[derive(Clone, Copy)]
+enum PropertyValue<'gc> {
+    Avm1(Avm1Value<'gc>),
+    Avm2(Avm2Value<'gc>),
+    Getter,
+}
+
+/// Where a property is stored, to write it back.
+#[derive(Clone, Copy)]
+enum Place<'gc> {
+    Avm1(Avm1Object<'gc>, AvmString<'gc>),
+    Avm2Slot(Avm2Object<'gc>, u32),
+    Avm2Dynamic(Avm2Object<'gc>, AvmString<'gc>),
+    Avm2Element(Avm2Object<'gc>, usize),
+
+    /// Constants, getters and children of the display list.
+    ReadOnly,
+}
+
+struct Property<'gc> {
+    name: String,
+    value: PropertyValue<'gc>,
+    place: Place<'gc>,
+}
+
+/// A property whose value is shown after every frame.
+struct Watch {
+    path: Vec<String>,
+    value: String,
+}
+
+#[derive(Default)]
+pub struct ObjectInspector {
+    /// The names of the properties leading from the root to the inspected object.
+    path: Vec<String>,
+
+    /// The index of the selected property of the inspected object.
+    selected: usize,
+
+    /// The properties of the inspected object, as of the last refresh.
+    properties: Vec<InspectedProperty>,
+
+    watches: Vec<Watch>,
+}
+
+impl ObjectInspector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reads the properties of the inspected object and the watched properties again.
+    ///
+    /// If the inspected object can't be reached anymore, its closest parent is inspected.
+    pub(crate) fn refresh(&mut self, context: &mut UpdateContext<'_, '_>) {
+        loop {
+            if let Some(properties) = inspect(context, &self.path) {
+                self.properties = properties;
+                break;
+            }
+            if self.path.pop().is_none() {
+                self.properties.clear();
+                break;
+            }
+        }
+        self.selected = self.selected.min(self.properties.len().saturating_sub(1));
+
+        for watch in &mut self.watches {
+            let Some((name, path)) = watch.path.split_last() else {
+                continue;
+            };
+            watch.value = match resolve(context, path) {
+                Some(object) => properties(context, object)
+                    .into_iter()
+                    .find(|property| property.name == *name)
+                    .map_or_else(
+                        || "(missing)".to_owned(),
+                        |property| property.value.describe(context),
+                    ),
+                None => "(missing)".to_owned(),
+            };
+        }
+    }
+
+    /// Moves the selection by `delta` properties.
+    pub(crate) fn move_selection(&mut self, delta: isize) {
+        let last = self.properties.len().saturating_sub(1);
+        let selected = self.selected.min(last) as isize + delta;
+        self.selected = selected.clamp(0, last as isize) as usize;
+    }
+
+    /// Inspects the selected property, if it's an object.
+    pub(crate) fn enter(&mut self, context: &mut UpdateContext<'_, '_>) {
+        let Some(property) = self.properties.get(self.selected) else {
+            return;
+        };
+        if property.kind == ValueKind::Object {
+            self.path.push(property.name.clone());
+            self.selected = 0;
+            self.refresh(context);
+        }
+    }
+
+    /// Inspects the object owning the inspected one, selecting the property it came from.
+    pub(crate) fn leave(&mut self, context: &mut UpdateContext<'_, '_>) {
+        let Some(name) = self.path.pop() else {
+            return;
+        };
+        self.refresh(context);
+        self.selected = self
+            .properties
+            .iter()
+            .position(|property| property.name == name)
+            .unwrap_or(0);
+    }
+
+    /// Starts or stops watching the selected property.
+    pub(crate) fn toggle_watch(&mut self, context: &mut UpdateContext<'_, '_>) {
+        let Some(property) = self.properties.get(self.selected) else {
+            return;
+        };
+        let mut path = self.path.clone();
+        path.push(property.name.clone());
+        if let Some(index) = self.watches.iter().position(|watch| watch.path == path) {
+            self.watches.remove(index);
+        } else {
+            self.watches.push(Watch {
+                path,
+                value: String::new(),
+            });
+            self.refresh(context);
+        }
+    }
+
+    /// Toggles the selected property if it's a boolean, or adds `delta` to it if it's a number.
+    pub(crate) fn adjust_selected(&mut self, context: &mut UpdateContext<'_, '_>, delta: f64) {
+        let Some(property) = self.properties.get(self.selected) else {
+            return;
+        };
+        let value = match property.kind {
+            ValueKind::Boolean => (property.value != "true").to_string(),
+            ValueKind::Number => match property.value.parse::<f64>() {
+                Ok(number) => (number + delta).to_string(),
+                Err(_) => return,
+            },
+            _ => return,
+        };
+        let name = property.name.clone();
+        set_property(context, &self.path, &name, &value);
+        self.refresh(context);
+    }
+
+    /// Renders the panel on the right half of the viewport.
+    pub(crate) fn render(&self, context: &mut RenderContext<'_, '_>) {
+        let x = f64::from(context.renderer.viewport_dimensions().width) / 2.0;
+
+        let mut lines = vec![WString::from_utf8(&format!(
+            "{} (Ctrl+Up/Down to select, Ctrl+Right/Left to enter/leave, Ctrl+W to watch, Ctrl+=/- to edit)",
+            path_name(&self.path)
+        ))];
+        let first = self
+            .selected
+            .saturating_sub(MAX_LINES / 2)
+            .min(self.properties.len().saturating_sub(MAX_LINES));
+        for (index, property) in self
+            .properties
+            .iter()
+            .enumerate()
+            .skip(first)
+            .take(MAX_LINES)
+        {
+            let marker = if index == self.selected { '>' } else { ' ' };
+            lines.push(WString::from_utf8(&format!(
+                "{marker} {} = {}",
+                property.name, property.value
+            )));
+        }
+        if !self.watches.is_empty() {
+            lines.push(WString::from_utf8("Watches:"));
+            for watch in &self.watches {
+                lines.push(WString::from_utf8(&format!(
+                    "  {} = {}",
+                    path_name(&watch.path),
+                    watch.value
+                )));
+            }
+        }
+        render_panel(context, &lines, x, 0.0);
+    }
+}
+
+/// The names of the properties on `path`, joined with dots.
+fn path_name(path: &[String]) -> String {
+    std::iter::once("root")
+        .chain(path.iter().map(String::as_str))
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+/// Lists the properties of the object at the end of `path`.
+///
+/// The path starts from `_root` in AVM1 movies, and from the stage in AVM2 movies.
+pub(crate) fn inspect(
+    context: &mut UpdateContext<'_, '_>,
+    path: &[impl AsRef<str>],
+) -> Option<Vec<InspectedProperty>> {
+    let object = resolve(context, path)?;
+    let properties = properties(context, object)
+        .into_iter()
+        .map(|property| InspectedProperty {
+            value: property.value.describe(context),
+            kind: property.value.kind(),
+            editable: !matches!(property.place, Place::ReadOnly)
+                && matches!(
+                    property.value.kind(),
+                    ValueKind::Boolean | ValueKind::Number | ValueKind::String
+                ),
+            name: property.name,
+        })
+        .collect();
+    Some(properties)
+}
+
+/// Sets the property `name` of the object at the end of `path`, keeping its type.
+///
+/// `value` is parsed as a boolean or a number if the property is one. Returns whether
+/// the property could be set.
+pub(crate) fn set_property(
+    context: &mut UpdateContext<'_, '_>,
+    path: &[impl AsRef<str>],
+    name: &str,
+    value: &str,
+) -> bool {
+    let Some(object) = resolve(context, path) else {
+        return false;
+    };
+    let Some(property) = properties(context, object)
+        .into_iter()
+        .find(|property| property.name == name)
+    else {
+        return false;
+    };
+
+    let Some(value) = parse_value(property.value.kind(), value) else {
+        return false;
+    };
+    match property.place {
+        Place::ReadOnly => false,
+        Place::Avm1(object, name) => {
+            let value = value.to_value(context);
+            let mut activation = Avm1Activation::from_stub(
+                context.reborrow(),
+                ActivationIdentifier::root("[Object Inspector]"),
+            );
+            object.set(name, value, &mut activation).is_ok()
+        }
+        Place::Avm2Slot(object, slot_id) => object
+            .set_slot(slot_id, value.to_value(context), context.gc_context)
+            .is_ok(),
+        Place::Avm2Dynamic(mut object, name) => {
+            let value = value.to_value(context);
+            let mut activation = Avm2Activation::from_nothing(context.reborrow());
+            object
+                .set_public_property(name, value, &mut activation)
+                .is_ok()
+        }
+        Place::Avm2Element(object, index) => {
+            let value = value.to_value(context);
+            let Some(mut array) = object.as_array_storage_mut(context.gc_context) else {
+                return false;
+            };
+            array.set(index, value);
+            true
+        }
+    }
+}
+
+enum ParsedValue<'a> {
+    Boolean(bool),
+    Number(f64),
+    String(&'a str),
+}
+
+fn parse_value(kind: ValueKind, value: &str) -> Option<ParsedValue<'_>> {
+    match kind {
+        ValueKind::Boolean => value.trim().parse().ok().map(ParsedValue::Boolean),
+        ValueKind::Number => value.trim().parse().ok().map(ParsedValue::Number),
+        ValueKind::String => Some(ParsedValue::String(value)),
+        ValueKind::Object | ValueKind::Other => None,
+    }
+}
+
+/// Finds the object at the end of `path`, following properties by name.
+fn resolve<'gc>(
+    context: &mut UpdateContext<'_, 'gc>,
+    path: &[impl AsRef<str>],
+) -> Option<AnyObject<'gc>> {
+    let mut object = if context.is_action_script_3() {
+        match context.stage.object2() {
+            Avm2Value::Object(object) => AnyObject::Avm2(object),
+            _ => return None,
+        }
+    } else {
+        match context.stage.root_clip()?.object() {
+            Avm1Value::Object(object) => AnyObject::Avm1(object),
+            _ => return None,
+        }
+    };
+    for name in path {
+        object = properties(context, object)
+            .into_iter()
+            .find(|property| property.name == name.as_ref())
+            .and_then(|property| property.value.object())?;
+    }
+    Some(object)
+}
+
+fn properties<'gc>(
+    context: &mut UpdateContext<'_, 'gc>,
+    object: AnyObject<'gc>,
+) -> Vec<Property<'gc>> {
+    match object {
+        AnyObject::Avm1(object) => avm1_properties(context, object),
+        AnyObject::Avm2(object) => avm2_properties(context, object),
+    }
+}
+
+/// Lists the enumerable properties, the display properties and the named children of
+/// an AVM1 object. Only stored values are read, so getters added with `addProperty`
+/// aren't called.
+fn avm1_properties<'gc>(
+    context: &mut UpdateContext<'_, 'gc>,
+    object: Avm1Object<'gc>,
+) -> Vec<Property<'gc>> {
+    let mut activation = Avm1Activation::from_stub(
+        context.reborrow(),
+        ActivationIdentifier::root("[Object Inspector]"),
+    );
+    let mut names = object.get_keys(&mut activation);
+    if object.as_display_object().is_some() {
+        names.extend(
+            AVM1_DISPLAY_PROPERTIES
+                .iter()
+                .map(|&name| AvmString::from(name)),
+        );
+    }
+    names
+        .into_iter()
+        .filter_map(|name| {
+            // Inherited properties aren't listed.
+            let value = match object.get_local_stored(name, &mut activation)? {
+                value @ Avm1Value::MovieClip(_) => {
+                    Avm1Value::Object(value.coerce_to_object(&mut activation))
+                }
+                value => value,
+            };
+            Some(Property {
+                name: name.to_string(),
+                value: PropertyValue::Avm1(value),
+                place: Place::Avm1(object, name),
+            })
+        })
+        .collect()
+}
+
+/// Lists the children, the public variables and getters, the dynamic properties and
+/// the elements of an AVM2 object.
+fn avm2_properties<'gc>(
+    context: &mut UpdateContext<'_, 'gc>,
+    object: Avm2Object<'gc>,
+) -> Vec<Property<'gc>> {
+    let mut properties = Vec::new();
+
+    if let Some(container) = object.as_display_object().and_then(|o| o.as_container()) {
+        for child in container.iter_render_list() {
+            if let value @ Avm2Value::Object(_) = child.object2() {
+                properties.push(Property {
+                    name: child.name().to_string(),
+                    value: PropertyValue::Avm2(value),
+                    place: Place::ReadOnly,
+                });
+            }
+        }
+    }
+
+    if let Some(vtable) = object.vtable() {
+        let mut traits = vtable.public_properties();
+        traits.sort_by(|(a, _), (b, _)| a.cmp(b));
+        for (name, property) in traits {
+            let (value, place) = match property {
+                Avm2Property::Slot { slot_id } => (
+                    PropertyValue::Avm2(object.get_slot(slot_id).unwrap_or(Avm2Value::Undefined)),
+                    Place::Avm2Slot(object, slot_id),
+                ),
+                Avm2Property::ConstSlot { slot_id } => (
+                    PropertyValue::Avm2(object.get_slot(slot_id).unwrap_or(Avm2Value::Undefined)),
+                    Place::ReadOnly,
+                ),
+                Avm2Property::Virtual { get: Some(_), .. } => {
+                    (PropertyValue::Getter, Place::ReadOnly)
+                }
+                Avm2Property::Virtual { get: None, .. } | Avm2Property::Method { .. } => continue,
+            };
+            properties.push(Property {
+                name: name.to_string(),
+                value,
+                place,
+            });
+        }
+    }
+
+    let mut activation = Avm2Activation::from_nothing(context.reborrow());
+    let mut names = Vec::new();
+    {
+        let base = object.base();
+        let mut index = 0;
+        while let Some(next) = base.get_next_enumerant(index) {
+            index = next;
+            match base.get_enumerant_name(index) {
+                Some(Avm2Value::String(name)) => names.push(name),
+                Some(Avm2Value::Integer(name)) => names.push(AvmString::new_utf8(
+                    activation.context.gc_context,
+                    name.to_string(),
+                )),
+                _ => {}
+            }
+        }
+    }
+    for name in names {
+        let multiname = Multiname::new(activation.avm2().public_namespace, name);
+        let value = object
+            .base()
+            .get_property_local(&multiname, &mut activation)
+            .unwrap_or(Avm2Value::Undefined);
+        properties.push(Property {
+            name: name.to_string(),
+            value: PropertyValue::Avm2(value),
+            place: Place::Avm2Dynamic(object, name),
+        });
+    }
+
+    if let Some(array) = object.as_array_storage() {
+        for (index, value) in array.iter().enumerate() {
+            properties.push(Property {
+                name: index.to_string(),
+                value: PropertyValue::Avm2(value.unwrap_or(Avm2Value::Undefined)),
+                place: Place::Avm2Element(object, index),
+            });
+        }
+    }
+
+    properties
+}
+
+impl<'gc> PropertyValue<'gc> {
+    fn object(self) -> Option<AnyObject<'gc>> {
+        match self {
+            PropertyValue::Avm1(Avm1Value::Object(object)) => Some(AnyObject::Avm1(object)),
+            PropertyValue::Avm2(Avm2Value::Object(object)) => Some(AnyObject::Avm2(object)),
+            _ => None,
+        }
+    }
+
+    fn kind(self) -> ValueKind {
+        match self {
+            PropertyValue::Avm1(Avm1Value::Object(_) | Avm1Value::MovieClip(_))
+            | PropertyValue::Avm2(Avm2Value::Object(_)) => ValueKind::Object,
+            PropertyValue::Avm1(Avm1Value::Bool(_)) | PropertyValue::Avm2(Avm2Value::Bool(_)) => {
+                ValueKind::Boolean
+            }
+            PropertyValue::Avm1(Avm1Value::Number(_))
+            | PropertyValue::Avm2(Avm2Value::Number(_) | Avm2Value::Integer(_)) => {
+                ValueKind::Number
+            }
+            PropertyValue::Avm1(Avm1Value::String(_))
+            | PropertyValue::Avm2(Avm2Value::String(_)) => ValueKind::String,
+            _ => ValueKind::Other,
+        }
+    }
+
+    fn describe(self, context: &UpdateContext<'_, 'gc>) -> String {
+        match self {
+            PropertyValue::Avm1(value) => match value {
+                Avm1Value::Undefined => "undefined".to_owned(),
+                Avm1Value::Null => "null".to_owned(),
+                Avm1Value::Bool(value) => value.to_string(),
+                Avm1Value::Number(value) => value.to_string(),
+                Avm1Value::String(value) => format!("{:?}", value.to_string()),
+                Avm1Value::Object(object) => {
+                    if let Some(object) = object.as_display_object() {
+                        format!("[{}]", object.path())
+                    } else if object.as_executable().is_some() {
+                        "[function]".to_owned()
+                    } else {
+                        "[object]".to_owned()
+                    }
+                }
+                Avm1Value::MovieClip(reference) => format!("[{}]", reference.path()),
+            },
+            PropertyValue::Avm2(value) => match value {
+                Avm2Value::Undefined => "undefined".to_owned(),
+                Avm2Value::Null => "null".to_owned(),
+                Avm2Value::Bool(value) => value.to_string(),
+                Avm2Value::Number(value) => value.to_string(),
+                Avm2Value::Integer(value) => value.to_string(),
+                Avm2Value::String(value) => format!("{:?}", value.to_string()),
+                Avm2Value::Object(object) => format!(
+                    "[object {}]",
+                    object.instance_of_class_name(context.gc_context)
+                ),
+            },
+            PropertyValue::Getter => "(getter)".to_owned(),
+        }
+    }
+}
@@ -0,0 +1,379 @@
+//! Movie patches, which replace characters and FlashVars without editing the SWF.
+//!
+//! A patch is usually described by a JSON manifest stored next to the movie:
+//!
+//! ```json
+//! {
+//!     "characters": {
+//!         "12": "title_en.png",
+//!         "VoiceIntro": "intro_en.mp3"
+//!     },
+//!     "flashvars": {
+//!         "lang": "en"
+//!     },
+//!     "textures": {
+//!         "8f3a61c02d9e4b17": "hd/background.png"
+//!     }
+//! }
+//! ```
+//!
+//! Characters are keyed by their character ID, or by their export name, which is the
+//! name given by `ExportAssets` or the class linked by `SymbolClass`.
+//! Bitmaps may be PNG, JPEG or GIF images, and sounds must be MP3 files. A replacement
+//! is only used for a character of the same type.
+//!
+//! Textures are high-resolution replacements for bitmaps, similar to the texture packs of
+//! emulators. They are keyed by the `texture_hash` of the original bitmap, which is logged
+//! at the debug level as bitmaps are loaded. The original bitmap keeps its size and pixels
+//! as seen by ActionScript, but shapes filled with it are rendered with the replacement.
+
+use crate::character::Character;
+use crate::context::UpdateContext;
+use crate::display_object::Bitmap;
+use crate::tag_utils::SwfMovie;
+use fnv::FnvHasher;
+use ruffle_render::bitmap::Bitmap as RenderBitmap;
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hasher;
+use swf::{CharacterId, Tag};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum PatchError {
+    #[error("Invalid patch manifest: {0}")]
+    InvalidManifest(#[from] serde_json::Error),
+
+    #[error("Unsupported patch asset {0}")]
+    UnsupportedAsset(String),
+
+    #[error("Couldn't load patch asset {0}: {1}")]
+    LoadAsset(String, #[source] std::io::Error),
+
+    #[error("Invalid texture hash {0}")]
+    InvalidTextureHash(String),
+}
+
+/// Identifies the character replaced by a patch asset.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum CharacterKey {
+    Id(CharacterId),
+    ExportName(String),
+}
+
+impl From<&str> for CharacterKey {
+    fn from(key: &str) -> Self {
+        match key.parse() {
+            Ok(id) => Self::Id(id),
+            Err(_) => Self::ExportName(key.to_string()),
+        }
+    }
+}
+
+/// A replacement for a character of the patched movie.
+#[derive(Clone, Debug)]
+pub enum PatchAsset {
+    /// A PNG, JPEG or GIF image, replacing a bitmap character.
+    Bitmap(Vec<u8>),
+
+    /// An MP3 file, replacing a sound character.
+    Sound(Vec<u8>),
+}
+
+#[derive(Deserialize)]
+struct Manifest {
+    #[serde(default)]
+    characters: BTreeMap<String, String>,
+
+    #[serde(default)]
+    flashvars: BTreeMap<String, String>,
+
+    #[serde(default)]
+    textures: BTreeMap<String, String>,
+}
+
+/// A set of replacement characters and FlashVars for a movie.
+#[derive(Clone, Debug, Default)]
+pub struct MoviePatch {
+    assets: HashMap<CharacterKey, PatchAsset>,
+    flashvars: Vec<(String, String)>,
+    textures: HashMap<u64, Vec<u8>>,
+}
+
+impl MoviePatch {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parses a JSON patch manifest.
+    ///
+    /// `load_asset` is called with the path of every asset listed in the manifest,
+    /// exactly as written in the manifest.
+    pub fn from_manifest(
+        manifest: &str,
+        mut load_asset: impl FnMut(&str) -> Result<Vec<u8>, std::io::Error>,
+    ) -> Result<Self, PatchError> {
+        let manifest: Manifest = serde_json::from_str(manifest)?;
+        let mut patch = Self::new();
+
+        for (key, path) in manifest.characters {
+            let extension = path
+                .rsplit_once('.')
+                .map(|(_, extension)| extension.to_ascii_lowercase());
+            let asset: fn(Vec<u8>) -> PatchAsset = match extension.as_deref() {
+                Some("png" | "jpg" | "jpeg" | "gif") => PatchAsset::Bitmap,
+                Some("mp3") => PatchAsset::Sound,
+                _ => return Err(PatchError::UnsupportedAsset(path)),
+            };
+            let data = load_asset(&path).map_err(|e| PatchError::LoadAsset(path.clone(), e))?;
+            patch.add_asset(key.as_str().into(), asset(data));
+        }
+
+        for (name, value) in manifest.flashvars {
+            patch.add_flashvar(name, value);
+        }
+
+        for (hash, path) in manifest.textures {
+            let hash = u64::from_str_radix(&hash, 16)
+                .map_err(|_| PatchError::InvalidTextureHash(hash.clone()))?;
+            let data = load_asset(&path).map_err(|e| PatchError::LoadAsset(path.clone(), e))?;
+            patch.add_texture(hash, data);
+        }
+
+        Ok(patch)
+    }
+
+    pub fn add_asset(&mut self, key: CharacterKey, asset: PatchAsset) {
+        self.assets.insert(key, asset);
+    }
+
+    pub fn add_flashvar(&mut self, name: String, value: String) {
+        self.flashvars.push((name, value));
+    }
+
+    /// Adds a high-resolution replacement (a PNG, JPEG or GIF image) for
+    /// the bitmap with the given `texture_hash`.
+    pub fn add_texture(&mut self, hash: u64, data: Vec<u8>) {
+        self.textures.insert(hash, data);
+    }
+
+    pub fn flashvars(&self) -> &[(String, String)] {
+        &self.flashvars
+    }
+
+    /// Decodes the texture substitutions of this patch, keyed by texture hash.
+    pub(crate) fn decode_textures(&self) -> HashMap<u64, RenderBitmap> {
+        self.textures
+            .iter()
+            .filter_map(|(hash, data)| {
+                match ruffle_render::utils::decode_define_bits_jpeg(data, None) {
+                    Ok(bitmap) => Some((*hash, bitmap)),
+                    Err(e) => {
+                        tracing::error!("Couldn't decode texture {:016x}: {}", hash, e);
+                        None
+                    }
+                }
+            })
+            .collect()
+    }
+
+    /// Builds the replacement characters of this patch for the root movie, keyed by the ID
+    /// of the character they replace.
+    pub(crate) fn build_characters<'gc>(
+        &self,
+        context: &mut UpdateContext<'_, 'gc>,
+    ) -> Vec<(CharacterId, Character<'gc>)> {
+        let mut characters = Vec::with_capacity(self.assets.len());
+        let export_ids = if self
+            .assets
+            .keys()
+            .any(|key| matches!(key, CharacterKey::ExportName(_)))
+        {
+            exported_ids(context.swf)
+        } else {
+            HashMap::new()
+        };
+
+        for (key, asset) in &self.assets {
+            let id = match key {
+                CharacterKey::Id(id) => *id,
+                CharacterKey::ExportName(name) => match export_ids.get(name) {
+                    Some(id) => *id,
+                    None => {
+                        tracing::warn!("Patched character {:?} isn't exported by the movie", key);
+                        continue;
+                    }
+                },
+            };
+
+            let character = match asset {
+                PatchAsset::Bitmap(data) => {
+                    match ruffle_render::utils::decode_define_bits_jpeg(data, None)
+                        .and_then(|bitmap| Bitmap::new(context, id, bitmap))
+                    {
+                        Ok(bitmap) => Character::Bitmap(bitmap),
+                        Err(e) => {
+                            tracing::error!("Couldn't decode patched bitmap {:?}: {}", key, e);
+                            continue;
+                        }
+                    }
+                }
+                PatchAsset::Sound(data) => match context.audio.register_mp3(data) {
+                    Ok(handle) => Character::Sound(handle),
+                    Err(e) => {
+                        tracing::error!("Couldn't decode patched sound {:?}: {}", key, e);
+                        continue;
+                    }
+                },
+            };
+
+            characters.push((id, character));
+        }
+
+        characters
+    }
+}
+
+/// Reads the IDs of the characters exported by a movie, by their export name.
+fn exported_ids(movie: &SwfMovie) -> HashMap<String, CharacterId> {
+    let mut reader = swf::read::Reader::new(movie.data(), movie.version());
+    let mut ids = HashMap::new();
+
+    loop {
+        match reader.read_tag() {
+            Ok(Tag::End) => break,
+            Ok(Tag::ExportAssets(exports)) => {
+                for export in exports {
+                    let name = export.name.to_str_lossy(movie.encoding()).into_owned();
+                    ids.insert(name, export.id);
+                }
+            }
+            Ok(Tag::SymbolClass(links)) => {
+                for link in links {
+                    let name = link.class_name.to_str_lossy(movie.encoding()).into_owned();
+                    ids.insert(name, link.id);
+                }
+            }
+            Ok(_) => (),
+            Err(e) => {
+                tracing::warn!("Stopped looking for patched exports: {}", e);
+                break;
+            }
+        }
+    }
+
+    ids
+}
+
+/// Computes the content hash that identifies a bitmap in texture substitutions.
+pub fn texture_hash(bitmap: &RenderBitmap) -> u64 {
+    // 64-bit FNV-1a, which is stable across platforms and versions.
+    let mut hasher = FnvHasher::default();
+    hasher.write(&bitmap.width().to_le_bytes());
+    hasher.write(&bitmap.height().to_le_bytes());
+    hasher.write(bitmap.data());
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ruffle_render::bitmap::BitmapFormat;
+
+    #[test]
+    fn test_texture_hash() {
+        // Texture packs rely on hashes staying the same across versions.
+        let bitmap = RenderBitmap::new(1, 1, BitmapFormat::Rgba, vec![0x11, 0x22, 0x33, 0xff]);
+        assert_eq!(texture_hash(&bitmap), 0xdb3d_a0b4_80f9_226c);
+    }
+
+    #[test]
+    fn test_from_manifest() {
+        let manifest = r#"{
+            "characters": {
+                "12": "title_en.png",
+                "VoiceIntro": "intro_en.MP3"
+            },
+            "flashvars": {
+                "lang": "en"
+            },
+            "textures": {
+                "8f3a61c02d9e4b17": "hd/background.png"
+            }
+        }"#;
+        let mut paths = vec![];
+        let patch = MoviePatch::from_manifest(manifest, |path| {
+            paths.push(path.to_string());
+            Ok(path.as_bytes().to_vec())
+        })
+        .unwrap();
+
+        paths.sort();
+        assert_eq!(paths, ["hd/background.png", "intro_en.MP3", "title_en.png"]);
+        assert!(matches!(
+            patch.assets.get(&CharacterKey::Id(12)),
+            Some(PatchAsset::Bitmap(data)) if data == b"title_en.png"
+        ));
+        assert!(matches!(
+            patch.assets.get(&CharacterKey::ExportName("VoiceIntro".to_string())),
+            Some(PatchAsset::Sound(data)) if data == b"intro_en.MP3"
+        ));
+        assert_eq!(patch.flashvars(), [("lang".to_string(), "en".to_string())]);
+        assert_eq!(
+            patch
+                .textures
+                .get(&0x8f3a_61c0_2d9e_4b17)
+                .map(Vec::as_slice),
+            Some(&b"hd/background.png"[..])
+        );
+    }
+
+    #[test]
+    fn test_from_manifest_errors() {
+        let load = |_: &str| Ok(vec![]);
+        assert!(matches!(
+            MoviePatch::from_manifest(r#"{"characters": {"1": "movie.swf"}}"#, load),
+            Err(PatchError::UnsupportedAsset(path)) if path == "movie.swf"
+        ));
+        assert!(matches!(
+            MoviePatch::from_manifest(r#"{"textures": {"background": "hd.png"}}"#, load),
+            Err(PatchError::InvalidTextureHash(hash)) if hash == "background"
+        ));
+        assert!(matches!(
+            MoviePatch::from_manifest(r#"{"characters": []}"#, load),
+            Err(PatchError::InvalidManifest(_))
+        ));
+        assert!(matches!(
+            MoviePatch::from_manifest(r#"{"characters": {"1": "missing.png"}}"#, |_| {
+                Err(std::io::ErrorKind::NotFound.into())
+            }),
+            Err(PatchError::LoadAsset(path, _)) if path == "missing.png"
+        ));
+    }
+
+    #[test]
+    fn test_exported_ids() {
+        fn tag(code: u16, data: &[u8]) -> Vec<u8> {
+            let mut tag = ((code << 6) | data.len() as u16).to_le_bytes().to_vec();
+            tag.extend_from_slice(data);
+            tag
+        }
+
+        let exports = [&[1, 0, 3, 0][..], b"Title\0"].concat();
+        let links = [&[2, 0, 7, 0][..], b"Voice\0", &[9, 0], b"Music\0"].concat();
+        let mut body = vec![0x00, 0x00, 0x18, 0x01, 0x00];
+        body.extend(tag(56, &exports));
+        body.extend(tag(76, &links));
+        body.extend(tag(0, &[]));
+        let mut data = b"FWS\x0a".to_vec();
+        data.extend_from_slice(&(8 + body.len() as u32).to_le_bytes());
+        data.extend(body);
+
+        let movie = SwfMovie::from_data(&data, "file:///patched.swf".to_string(), None).unwrap();
+        let ids = exported_ids(&movie);
+        assert_eq!(ids.len(), 3);
+        assert_eq!(ids["Title"], 3);
+        assert_eq!(ids["Voice"], 7);
+        assert_eq!(ids["Music"], 9);
+    }
+}
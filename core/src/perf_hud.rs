@@ -0,0 +1,147 @@
+//! An overlay showing how the time of each frame is spent, to help users
+//! report performance issues.
+//!
+//! The overlay is drawn with the renderer like the rest of the stage, so it
+//! works with every backend. Nothing is sent anywhere.
+
+use crate::context::RenderContext;
+use crate::font::EvalParameters;
+use crate::string::WString;
+use instant::Instant;
+use ruffle_render::commands::CommandHandler;
+use ruffle_render::matrix::Matrix;
+use ruffle_render::transform::Transform;
+use std::collections::VecDeque;
+use std::time::Duration;
+use swf::{Color, ColorTransform, Twips};
+
+/// The color of the box drawn behind the overlay.
+const BACKGROUND_COLOR: Color = Color {
+    r: 0,
+    g: 0,
+    b: 0,
+    a: 192,
+};
+
+const FONT_SIZE: f64 = 12.0;
+const LINE_HEIGHT: f64 = 15.0;
+const PADDING: f64 = 4.0;
+
+/// The measurements shown by the overlay.
+#[derive(Default)]
+pub struct PerfHud {
+    /// When the recent renders happened, to count the renders of the last second.
+    render_times: VecDeque<Instant>,
+
+    /// How long the scripts and timelines of the last frame took, in milliseconds.
+    pub script_ms: f64,
+
+    /// How long garbage collection took after the last frame, in milliseconds.
+    pub gc_ms: f64,
+
+    /// How long the last render took, in milliseconds.
+    pub render_ms: f64,
+
+    /// How many commands the last render submitted to the renderer.
+    pub render_commands: usize,
+}
+
+impl PerfHud {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records a render that happened at `time`.
+    pub fn record_render(&mut self, time: Instant, duration: Duration, render_commands: usize) {
+        self.render_times.push_back(time);
+        while let Some(first) = self.render_times.front() {
+            if time.duration_since(*first) <= Duration::from_secs(1) {
+                break;
+            }
+            self.render_times.pop_front();
+        }
+        self.render_ms = duration.as_secs_f64() * 1000.0;
+        self.render_commands = render_commands;
+    }
+
+    /// The number of renders in the last second.
+    pub fn fps(&self) -> usize {
+        self.render_times.len()
+    }
+
+    fn lines(&self) -> [String; 3] {
+        [
+            format!("{} FPS", self.fps()),
+            format!(
+                "script {:.1} ms, gc {:.1} ms, render {:.1} ms",
+                self.script_ms, self.gc_ms, self.render_ms
+            ),
+            format!("{} render commands", self.render_commands),
+        ]
+    }
+
+    /// The height of the overlay, in pixels.
+    pub(crate) fn height(&self) -> f64 {
+        panel_height(self.lines().len())
+    }
+
+    /// Renders the overlay at the top left of the viewport.
+    pub(crate) fn render(&self, context: &mut RenderContext<'_, '_>) {
+        let lines: Vec<_> = self
+            .lines()
+            .iter()
+            .map(|line| WString::from_utf8(line))
+            .collect();
+        render_panel(context, &lines, 0.0, 0.0);
+    }
+}
+
+/// The height of a panel of `num_lines` lines, in pixels.
+pub(crate) fn panel_height(num_lines: usize) -> f64 {
+    LINE_HEIGHT * num_lines as f64 + PADDING * 2.0
+}
+
+/// Renders lines of text over a dark box at `x`, `y` of the viewport, using the device font.
+pub(crate) fn render_panel(context: &mut RenderContext<'_, '_>, lines: &[WString], x: f64, y: f64) {
+    let Some(font) = context.library.device_font() else {
+        return;
+    };
+
+    let params = EvalParameters::from_parts(Twips::from_pixels(FONT_SIZE), Twips::ZERO, true);
+    let width = lines
+        .iter()
+        .map(|line| font.measure(line, params, false).0.to_pixels())
+        .fold(0.0, f64::max);
+
+    context.commands.draw_rect(
+        BACKGROUND_COLOR,
+        Matrix::create_box(
+            (width + PADDING * 2.0) as f32,
+            panel_height(lines.len()) as f32,
+            0.0,
+            Twips::from_pixels(x),
+            Twips::from_pixels(y),
+        ),
+    );
+
+    let mut line_y = y + PADDING;
+    for line in lines {
+        let transform = Transform {
+            matrix: Matrix::translate(
+                Twips::from_pixels(x + PADDING),
+                Twips::from_pixels(line_y + (LINE_HEIGHT - FONT_SIZE) / 2.0),
+            ),
+            color_transform: ColorTransform::IDENTITY,
+        };
+        font.evaluate(
+            line,
+            transform,
+            params,
+            |_pos, transform, glyph, _advance, _x| {
+                let shape = glyph.shape_handle(context.renderer);
+                context.commands.render_shape(shape, transform.clone());
+            },
+        );
+        line_y += LINE_HEIGHT;
+    }
+}
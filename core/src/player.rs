@@ -8,17 +8,24 @@ use crate::avm1::{Activation, ActivationIdentifier};
 use crate::avm1::{ScriptObject, TObject, Value};
 use crate::avm2::{
     object::LoaderInfoObject, object::TObject as _, Activation as Avm2Activation, Avm2, CallStack,
-    Domain as Avm2Domain, Object as Avm2Object,
+    Domain as Avm2Domain, LeakDetector, Object as Avm2Object, ProbableLeak,
 };
 use crate::backend::{
     audio::{AudioBackend, AudioManager},
+    local_connection::LocalConnectionBackend,
     log::LogBackend,
-    navigator::{NavigatorBackend, Request},
+    navigator::{NavigatorBackend, OwnedFuture, ProgressCallback, Request, Response},
+    print::PrintBackend,
+    remote_shared_object::RemoteSharedObjectBackend,
+    socket::SocketBackend,
     storage::StorageBackend,
     ui::{InputManager, MouseCursor, UiBackend},
+    video_capture::VideoCaptureBackend,
 };
+use crate::captions::{render_caption, Captions};
+use crate::compatibility_hints::{movie_hash, CompatibilityHint, CompatibilityHints};
 use crate::compatibility_rules::CompatibilityRules;
-use crate::config::Letterbox;
+use crate::config::{FrameCatchUp, Letterbox};
 use crate::context::GcContext;
 use crate::context::{ActionQueue, ActionType, RenderContext, UpdateContext};
 use crate::context_menu::{
@@ -26,8 +33,8 @@ use crate::context_menu::{
 };
 use crate::display_object::Avm2MousePick;
 use crate::display_object::{
-    EditText, InteractiveObject, MovieClip, Stage, StageAlign, StageDisplayState, StageScaleMode,
-    TInteractiveObject, WindowMode,
+    ButtonState, EditText, HitTestOptions, InteractiveObject, MovieClip, Stage, StageAlign,
+    StageDisplayState, StageScaleMode, TInteractiveObject, WindowMode,
 };
 use crate::events::{ButtonKeyCode, ClipEvent, ClipEventResult, KeyCode, MouseButton, PlayerEvent};
 use crate::external::Value as ExternalValue;
@@ -35,15 +42,25 @@ use crate::external::{ExternalInterface, ExternalInterfaceProvider};
 use crate::focus_tracker::FocusTracker;
 use crate::font::Font;
 use crate::frame_lifecycle::{run_all_phases_avm2, FramePhase};
+use crate::i18n::Localizations;
+use crate::injected_script::{run_injected_scripts, InjectedScript};
 use crate::library::Library;
-use crate::limits::ExecutionLimit;
-use crate::loader::{LoadBehavior, LoadManager};
+use crate::limits::{ExecutionLimit, ScriptWatchdog};
+use crate::loader::{Error as LoaderError, LoadBehavior, LoadManager};
 use crate::locale::get_current_date_time;
+use crate::network_log::{self, NetworkEntry, NetworkLog};
+use crate::object_inspector::{self, InspectedProperty, ObjectInspector};
+use crate::patch::MoviePatch;
+use crate::perf_hud::PerfHud;
 use crate::prelude::*;
+use crate::printing::PrintJob;
+use crate::save_state::{self, SaveStateError};
+use crate::settings::DomainSettings;
 use crate::streams::StreamManager;
 use crate::string::{AvmString, AvmStringInterner};
 use crate::stub::StubCollection;
-use crate::tag_utils::SwfMovie;
+use crate::tag_utils::{MovieInfo, SwfMovie};
+use crate::timeline_inspector::TimelineInspector;
 use crate::timer::Timers;
 use crate::vminterface::Instantiator;
 use gc_arena::MutationContext;
@@ -63,6 +80,7 @@ use std::str::FromStr;
 use std::sync::{Arc, Mutex, Weak};
 use std::time::Duration;
 use tracing::{info, instrument};
+use url::Url;
 
 /// The newest known Flash Player version, serves as a default to
 /// `player_version`.
@@ -213,9 +231,14 @@ type Audio = Box<dyn AudioBackend>;
 type Navigator = Box<dyn NavigatorBackend>;
 type Renderer = Box<dyn RenderBackend>;
 type Storage = Box<dyn StorageBackend>;
+type RemoteSharedObjects = Box<dyn RemoteSharedObjectBackend>;
+type LocalConnections = Box<dyn LocalConnectionBackend>;
+type Sockets = Box<dyn SocketBackend>;
+type Printer = Box<dyn PrintBackend>;
 type Log = Box<dyn LogBackend>;
 type Ui = Box<dyn UiBackend>;
 type Video = Box<dyn VideoBackend>;
+type VideoCapture = Box<dyn VideoCaptureBackend>;
 
 pub struct Player {
     /// The version of the player we're emulating.
@@ -241,9 +264,17 @@ pub struct Player {
     audio: Audio,
     navigator: Navigator,
     storage: Storage,
+    remote_shared_objects: RemoteSharedObjects,
+    local_connections: LocalConnections,
+    sockets: Sockets,
+    printer: Printer,
     log: Log,
+
+    /// The translations of the text shown by the player.
+    localizations: Localizations,
     ui: Ui,
     video: Video,
+    video_capture: VideoCapture,
 
     transform_stack: TransformStack,
 
@@ -255,6 +286,9 @@ pub struct Player {
     forced_frame_rate: bool,
     actions_since_timeout_check: u16,
 
+    /// Whether the APIs of Adobe AIR, such as `flash.filesystem.File`, are available to movies.
+    air_compatibility: bool,
+
     frame_phase: FramePhase,
 
     stub_tracker: StubCollection,
@@ -266,6 +300,24 @@ pub struct Player {
     frame_accumulator: f64,
     recent_run_frame_timings: VecDeque<f64>,
 
+    /// How frames are caught up when ticks fall behind the frame rate.
+    frame_catch_up: FrameCatchUp,
+
+    /// The multiplier applied to the passage of time, used for fast-forward and slow-motion.
+    /// `1.0` is normal speed.
+    playback_speed: f64,
+
+    /// The master volume to restore once playback returns to normal speed.
+    /// Audio is muted while this is `Some`, as sounds can't follow a changed playback speed.
+    muted_volume: Option<f32>,
+
+    /// Whether display object transforms are interpolated between frames when rendering
+    /// more often than the SWF frame rate ("smooth playback").
+    frame_interpolation: bool,
+
+    /// Whether display objects removed from the stage are tracked to find probable leaks.
+    leak_detection: bool,
+
     /// Faked time passage for fooling hand-written busy-loop FPS limiters.
     time_offset: u32,
 
@@ -278,6 +330,15 @@ pub struct Player {
     mouse_cursor: MouseCursor,
     mouse_cursor_needs_check: bool,
 
+    /// What is known about the object the mouse was last found to hover over.
+    ///
+    /// As long as the mouse moves within its bounds and nothing else happened,
+    /// mouse moves reuse the hovered object instead of hit testing the whole display list.
+    /// The object is still hit tested, together with the masks and scroll rects of its
+    /// ancestors, and dropped when anything drawn above it may be under the mouse.
+    /// This is reset whenever other scripts may have run.
+    hover_cache: Option<HoverCache>,
+
     system: SystemProperties,
 
     /// The current instance ID. Used to generate default `instanceN` names.
@@ -289,10 +350,12 @@ pub struct Player {
     /// The instant at which the SWF was launched.
     start_time: Instant,
 
-    /// The maximum amount of time that can be called before a `Error::ExecutionTimeout`
-    /// is raised. This defaults to 15 seconds but can be changed.
+    /// The maximum amount of time that scripts can run for before the user is
+    /// asked whether to abort them. This defaults to 15 seconds but can be changed.
     max_execution_duration: Duration,
 
+    script_watchdog: ScriptWatchdog,
+
     /// Self-reference to ourselves.
     ///
     /// This is a weak reference that is upgraded and handed out in various
@@ -311,8 +374,45 @@ pub struct Player {
     /// the actual loaded url will be used
     spoofed_url: Option<String>,
 
+    /// Whether each movie is put in the security sandbox of its URL, which is
+    /// enforced on the requests it makes.
+    enforce_sandbox: bool,
+
     /// Any compatibility rules to apply for this movie.
     compatibility_rules: CompatibilityRules,
+
+    /// The known issues of movies, which are shown before they play.
+    compatibility_hints: CompatibilityHints,
+
+    /// Embedder-supplied scripts to run against the root movie.
+    injected_scripts: Vec<InjectedScript>,
+
+    /// Whether the injected scripts still need to be run for the current root movie.
+    injected_scripts_pending: bool,
+
+    /// Replacement characters and FlashVars applied to the root movie.
+    patch: Option<Arc<MoviePatch>>,
+
+    /// Captions displayed above the stage, synced to the root timeline.
+    captions: Option<Captions>,
+
+    /// The performance overlay, if it is shown.
+    perf_hud: Option<PerfHud>,
+
+    /// The timeline inspector, if it is shown.
+    timeline_inspector: Option<TimelineInspector>,
+
+    /// The object inspector, if it is shown.
+    object_inspector: Option<ObjectInspector>,
+
+    /// The requests made by the movie since the player started.
+    network_log: Rc<RefCell<NetworkLog>>,
+
+    /// Whether the network log is shown.
+    show_network_log: bool,
+
+    /// The pages added to the print job that is in progress, if any.
+    print_job: Option<PrintJob>,
 }
 
 impl Player {
@@ -337,12 +437,119 @@ impl Player {
         });
     }
 
+    /// Restarts the root movie from scratch, reusing the already loaded SWF data.
+    ///
+    /// All ActionScript state, child movies, timers and sounds are discarded, exactly as if the
+    /// movie had been loaded into a new player. Loads still in flight are cancelled. Stage
+    /// settings such as the scale mode and quality are kept.
+    pub fn reset(&mut self) {
+        if self.swf.data().is_empty() {
+            // The root movie hasn't loaded yet, so there's nothing to restart.
+            return;
+        }
+
+        self.flush_shared_objects();
+        let movie = SwfMovie::clone(&self.swf);
+        self.restart(movie);
+    }
+
+    /// Replaces the root movie with a new version of it, restarting it as `reset` does.
+    ///
+    /// This is used to reload a movie when it's rebuilt during development. Unless
+    /// `preserve_shared_objects` is set, the shared objects opened by the previous version
+    /// are deleted, so that the new version starts with fresh data.
+    pub fn reload(&mut self, movie: SwfMovie, preserve_shared_objects: bool) {
+        if preserve_shared_objects {
+            self.flush_shared_objects();
+        } else {
+            self.delete_shared_objects();
+        }
+        self.restart(movie);
+    }
+
+    fn restart(&mut self, movie: SwfMovie) {
+        self.audio.stop_all_sounds();
+
+        let (fullscreen, scale_mode, forced_scale_mode, align, letterbox, quality, window_mode) =
+            self.mutate_with_update_context(|context| {
+                let stage = context.stage;
+                (
+                    stage.is_fullscreen(),
+                    stage.scale_mode(),
+                    stage.forced_scale_mode(),
+                    stage.align(),
+                    stage.letterbox(),
+                    stage.quality(),
+                    stage.window_mode(),
+                )
+            });
+
+        let player_version = self.player_version;
+        let fake_movie = Arc::new(SwfMovie::empty(player_version));
+        // The new `LoadManager` doesn't know the handles of pending loads, which are cancelled.
+        *self.gc_arena.borrow_mut() = GcArena::new(ArenaParameters::default(), |gc_context| {
+            Self::create_gc_root(gc_context, player_version, fullscreen, fake_movie.clone())
+        });
+        self.initialize_stage(fake_movie, scale_mode, forced_scale_mode);
+
+        self.frame_phase = Default::default();
+        self.frame_accumulator = 0.0;
+        self.recent_run_frame_timings.clear();
+        self.current_frame = None;
+        self.start_time = Instant::now();
+        self.time_offset = 0;
+        self.time_til_next_timer = None;
+        self.mouse_cursor_needs_check = true;
+        self.needs_render = true;
+
+        self.set_letterbox(letterbox);
+        self.set_quality(quality);
+        self.mutate_with_update_context(|context| {
+            let stage = context.stage;
+            stage.set_align(context, align);
+            stage.set_window_mode(context, window_mode);
+        });
+        self.set_leak_detection(self.leak_detection);
+        self.set_viewport_dimensions(self.renderer.viewport_dimensions());
+        self.set_root_movie(movie);
+    }
+
+    /// Sets up a freshly created GC root, placing an empty movie on the stage
+    /// until the root movie is loaded.
+    fn initialize_stage(
+        &mut self,
+        fake_movie: Arc<SwfMovie>,
+        scale_mode: StageScaleMode,
+        forced_scale_mode: bool,
+    ) {
+        self.mutate_with_update_context(|context| {
+            // Instantiate an empty root before the main movie loads.
+            let fake_root = MovieClip::new(fake_movie, context.gc_context);
+            fake_root.post_instantiation(context, None, Instantiator::Movie, false);
+            context.stage.replace_at_depth(context, fake_root.into(), 0);
+            Avm2::load_player_globals(context).expect("Unable to load AVM2 globals");
+            let stage = context.stage;
+            stage.set_scale_mode(context, scale_mode);
+            stage.set_forced_scale_mode(context, forced_scale_mode);
+            stage.post_instantiation(context, None, Instantiator::Movie, false);
+            stage.build_matrices(context);
+        });
+        self.gc_arena.borrow().mutate(|context, root| {
+            let call_stack = root.data.read().avm2.call_stack();
+            root.callstack.write(context).avm2 = Some(call_stack);
+        });
+    }
+
     /// Change the root movie.
     ///
     /// This should only be called once, as it makes no attempt at removing
     /// previous stage contents. If you need to load a new root movie, you
     /// should destroy and recreate the player instance.
-    pub fn set_root_movie(&mut self, movie: SwfMovie) {
+    pub fn set_root_movie(&mut self, mut movie: SwfMovie) {
+        if let Some(patch) = &self.patch {
+            movie.append_parameters(patch.flashvars().iter().cloned());
+        }
+
         if !self.forced_frame_rate {
             self.frame_rate = movie.frame_rate().into();
         }
@@ -355,8 +562,19 @@ impl Player {
             self.frame_rate(),
         );
 
+        if let Some(hash) = movie_hash(&movie) {
+            info!("Movie hash for compatibility hints: {}", hash);
+        }
+
+        if self.enforce_sandbox {
+            self.system.sandbox_type = SandboxType::for_movie(&movie);
+            info!("Playing in the {} sandbox", self.system.sandbox_type);
+        }
+
         self.swf = Arc::new(movie);
         self.instance_counter = 0;
+        self.injected_scripts_pending = !self.injected_scripts.is_empty();
+        let patch = self.patch.clone();
 
         self.mutate_with_update_context(|context| {
             context.stage.set_movie_size(
@@ -381,6 +599,15 @@ impl Player {
                 .library
                 .library_for_movie_mut(activation.context.swf.clone())
                 .set_avm2_domain(domain);
+            if let Some(patch) = patch {
+                let characters = patch.build_characters(&mut activation.context);
+                let library = activation
+                    .context
+                    .library
+                    .library_for_movie_mut(activation.context.swf.clone());
+                library.set_patched_characters(characters);
+                library.set_texture_substitutions(patch.decode_textures());
+            }
             activation.context.ui.set_mouse_visible(true);
 
             let swf = activation.context.swf.clone();
@@ -495,12 +722,18 @@ impl Player {
             return;
         }
 
+        let dt = dt * self.playback_speed;
+
         if self.is_playing() {
             self.frame_accumulator += dt;
             let frame_rate = self.frame_rate;
             let frame_time = 1000.0 / frame_rate;
 
-            let max_frames_per_tick = self.max_frames_per_tick();
+            let max_frames_per_tick = match self.frame_catch_up {
+                FrameCatchUp::Adaptive => self.max_frames_per_tick(),
+                FrameCatchUp::SlowDown => 1,
+                FrameCatchUp::SkipRenders => FrameCatchUp::MAX_CATCH_UP_FRAMES,
+            };
             let mut frame = 0;
 
             while frame < max_frames_per_tick && self.frame_accumulator >= frame_time {
@@ -530,26 +763,51 @@ impl Player {
             // so timer callbacks won't get cancelled/delayed.
             self.time_offset = 0;
 
-            // Sanity: If we had too many frames to tick, just reset the accumulator
-            // to prevent running at turbo speed.
-            if self.frame_accumulator >= frame_time {
-                self.frame_accumulator = 0.0;
+            match self.frame_catch_up {
+                FrameCatchUp::SkipRenders => {
+                    // Keep the remaining lag to run it on the next ticks, but not more than
+                    // we can catch up in one tick.
+                    let max_lag = frame_time * FrameCatchUp::MAX_CATCH_UP_FRAMES as f64;
+                    self.frame_accumulator = self.frame_accumulator.min(max_lag);
+                }
+                FrameCatchUp::Adaptive | FrameCatchUp::SlowDown => {
+                    // Sanity: If we had too many frames to tick, just reset the accumulator
+                    // to prevent running at turbo speed.
+                    if self.frame_accumulator >= frame_time {
+                        self.frame_accumulator = 0.0;
+                    }
+                }
             }
 
             // Adjust playback speed for next frame to stay in sync with timeline audio tracks ("stream" sounds).
-            let cur_frame_offset = self.frame_accumulator;
-            self.frame_accumulator += self.mutate_with_update_context(|context| {
-                context
-                    .audio_manager
-                    .audio_skew_time(context.audio, cur_frame_offset)
-                    * 1000.0
-            });
+            // Audio is muted when playing at a different speed, so there is nothing to sync to.
+            if self.playback_speed == 1.0 {
+                let cur_frame_offset = self.frame_accumulator;
+                self.frame_accumulator += self.mutate_with_update_context(|context| {
+                    context
+                        .audio_manager
+                        .audio_skew_time(context.audio, cur_frame_offset)
+                        * 1000.0
+                });
+            }
 
             self.update_timers(dt);
             self.update(|context| {
                 StreamManager::tick(context, dt);
             });
+            self.update(crate::avm1::globals::shared_object::sync_remote_shared_objects);
+            self.update(crate::avm1::globals::local_connection::receive_messages);
+            self.poll_sockets();
             self.audio.tick();
+
+            // Timers and streams may have run scripts.
+            self.hover_cache = None;
+
+            if self.frame_interpolation {
+                // Every tick moves the interpolated transforms along, so we always need to redraw.
+                // Hosts pace these redraws by the display, see `Player::wants_continuous_render`.
+                self.needs_render = true;
+            }
         }
     }
     pub fn time_til_next_timer(&self) -> Option<f64> {
@@ -572,6 +830,9 @@ impl Player {
             dt = dt.min(time_til_next_timer)
         }
 
+        // Convert from movie time to host time.
+        dt /= self.playback_speed;
+
         dt = dt.max(0.0);
 
         std::time::Duration::from_micros(dt as u64 * 1000)
@@ -591,12 +852,42 @@ impl Player {
 
     /// Returns the master volume of the player. 1.0 is 100% volume.
     pub fn volume(&self) -> f32 {
-        self.audio.volume()
+        self.muted_volume.unwrap_or_else(|| self.audio.volume())
     }
 
     /// Sets the master volume of the player. 1.0 is 100% volume.
     pub fn set_volume(&mut self, volume: f32) {
-        self.audio.set_volume(volume)
+        if self.muted_volume.is_some() {
+            self.muted_volume = Some(volume);
+        } else {
+            self.audio.set_volume(volume)
+        }
+    }
+
+    /// Returns the playback speed multiplier of the player. 1.0 is normal speed.
+    pub fn playback_speed(&self) -> f64 {
+        self.playback_speed
+    }
+
+    /// Sets the playback speed multiplier of the player. 1.0 is normal speed.
+    ///
+    /// This scales the passage of time for frames, timers and streams alike.
+    /// Audio is muted while playing at any speed other than 1.0.
+    pub fn set_playback_speed(&mut self, speed: f64) {
+        if !speed.is_finite() || speed <= 0.0 {
+            tracing::warn!("Ignoring invalid playback speed {}", speed);
+            return;
+        }
+
+        self.playback_speed = speed;
+        if speed == 1.0 {
+            if let Some(volume) = self.muted_volume.take() {
+                self.audio.set_volume(volume);
+            }
+        } else if self.muted_volume.is_none() {
+            self.muted_volume = Some(self.audio.volume());
+            self.audio.set_volume(0.0);
+        }
     }
 
     pub fn prepare_context_menu(&mut self) -> Vec<ContextMenuItem> {
@@ -605,28 +896,44 @@ impl Player {
                 return vec![];
             }
 
-            // TODO: This should use a pointed display object with `.menu`
             let root_dobj = context.stage.root_clip();
 
-            let menu = if let Some(Value::Object(obj)) = root_dobj.map(|root| root.object()) {
+            let menu = if let Some(Value::Object(_)) = root_dobj.map(|root| root.object()) {
+                let pointed = run_mouse_pick(context, false).map(|obj| obj.as_displayobject());
                 let mut activation = Activation::from_stub(
                     context.reborrow(),
                     ActivationIdentifier::root("[ContextMenu]"),
                 );
-                let menu_object = if let Ok(Value::Object(menu)) = obj.get("menu", &mut activation)
-                {
+
+                // The menu of the pointed object is used, or the one of its closest ancestor
+                // which has a menu, and finally the one of the root movie.
+                let mut target = root_dobj;
+                let mut menu_object = None;
+                let mut node = pointed.or(root_dobj);
+                while let Some(dobj) = node {
+                    if let Value::Object(obj) = dobj.object() {
+                        if let Ok(Value::Object(menu)) = obj.get("menu", &mut activation) {
+                            target = Some(dobj);
+                            menu_object = Some(menu);
+                            break;
+                        }
+                    }
+                    node = dobj.avm1_parent();
+                }
+
+                if let (Some(menu), Some(target)) = (menu_object, target) {
                     if let Ok(Value::Object(on_select)) = menu.get("onSelect", &mut activation) {
                         Self::run_context_menu_custom_callback(
                             menu,
                             on_select,
+                            target,
                             &mut activation.context,
                         );
                     }
-                    Some(menu)
-                } else {
-                    None
-                };
-                crate::avm1::make_context_menu_state(menu_object, &mut activation)
+                }
+                let mut menu = crate::avm1::make_context_menu_state(menu_object, &mut activation);
+                menu.set_target(target);
+                menu
             } else if let Some(Avm2Value::Object(_obj)) = root_dobj.map(|root| root.object2()) {
                 // TODO: send "menuSelect" event
                 tracing::warn!("AVM2 Context menu callbacks are not implemented");
@@ -644,7 +951,7 @@ impl Player {
                 // no AVM1 or AVM2 object - so just prepare the builtin items
                 let mut menu = ContextMenuState::new();
                 let builtin_items = BuiltInItemFlags::for_stage(context.stage);
-                menu.build_builtin_items(builtin_items, context.stage);
+                menu.build_builtin_items(builtin_items, context);
                 menu
             };
 
@@ -667,7 +974,11 @@ impl Player {
             if let Some(ref menu) = menu {
                 match menu.callback(index) {
                     ContextMenuCallback::Avm1 { item, callback } => {
-                        Self::run_context_menu_custom_callback(*item, *callback, context)
+                        if let Some(target) = menu.target().or_else(|| context.stage.root_clip()) {
+                            Self::run_context_menu_custom_callback(
+                                *item, *callback, target, context,
+                            )
+                        }
                     }
                     ContextMenuCallback::Play => Self::toggle_play_root_movie(context),
                     ContextMenuCallback::Forward => Self::forward_root_movie(context),
@@ -695,18 +1006,22 @@ impl Player {
     fn run_context_menu_custom_callback<'gc>(
         item: Object<'gc>,
         callback: Object<'gc>,
+        target: DisplayObject<'gc>,
         context: &mut UpdateContext<'_, 'gc>,
     ) {
-        if let Some(root_clip) = context.stage.root_clip() {
+        let base_clip = if target.avm1_removed() {
+            context.stage.root_clip()
+        } else {
+            Some(target)
+        };
+        if let Some(base_clip) = base_clip {
             let mut activation = Activation::from_nothing(
                 context.reborrow(),
                 ActivationIdentifier::root("[Context Menu Callback]"),
-                root_clip,
+                base_clip,
             );
 
-            // TODO: Remember to also change the first arg
-            // when we support contextmenu on non-root-movie
-            let params = vec![root_clip.object(), Value::Object(item)];
+            let params = vec![target.object(), Value::Object(item)];
 
             let _ = callback.call(
                 "[Context Menu Callback]".into(),
@@ -805,6 +1120,41 @@ impl Player {
         })
     }
 
+    /// Sets whether display objects removed from the stage are tracked, so that
+    /// `probable_leaks` can report those that are still listening to broadcast events.
+    pub fn set_leak_detection(&mut self, enabled: bool) {
+        self.leak_detection = enabled;
+        self.mutate_with_update_context(|context| {
+            if !enabled {
+                context.avm2.leak_detector = None;
+            } else if context.avm2.leak_detector.is_none() {
+                context.avm2.leak_detector = Some(LeakDetector::default());
+            }
+        })
+    }
+
+    /// Returns the classes of display objects that were removed from the stage but are
+    /// still kept alive by listeners of broadcast events such as `enterFrame`.
+    ///
+    /// This is always empty unless leak detection was enabled.
+    pub fn probable_leaks(&mut self) -> Vec<ProbableLeak> {
+        self.mutate_with_update_context(crate::avm2::probable_leaks)
+    }
+
+    /// Returns the properties of the root movie, such as its stage size or metadata.
+    pub fn movie_info(&self) -> MovieInfo {
+        self.swf.info()
+    }
+
+    /// Exports the bitmaps, sounds, shapes and sprites of the root movie,
+    /// calling `write` with the name and contents of each file.
+    pub fn export_assets(
+        &self,
+        write: impl FnMut(&str, &[u8]) -> Result<(), std::io::Error>,
+    ) -> Result<Vec<crate::asset_export::Asset>, crate::asset_export::ExportError> {
+        crate::asset_export::export_assets(&self.swf, write)
+    }
+
     pub fn movie_width(&mut self) -> u32 {
         self.mutate_with_update_context(|context| context.stage.movie_size().0)
     }
@@ -884,6 +1234,11 @@ impl Player {
         self.input.handle_event(&event);
         let is_mouse_button_changed = self.input.is_mouse_down() != prev_is_mouse_down;
 
+        if !matches!(event, PlayerEvent::MouseMove { .. }) {
+            // Any other event may run scripts.
+            self.hover_cache = None;
+        }
+
         if cfg!(feature = "avm_debug") {
             match event {
                 PlayerEvent::KeyDown {
@@ -1059,6 +1414,54 @@ impl Player {
                 }
             }
 
+            // A focused button is pressed and released with the Enter or Space key,
+            // unless a `keyPress` handler took the key. In AVM2, this also applies to
+            // sprites in button mode, and releasing the key dispatches a `click` event.
+            if !key_press_handled {
+                let focused_button = context.focus_tracker.get().filter(|o| {
+                    o.as_avm1_button().is_some()
+                        || o.as_avm2_button().is_some()
+                        || (context.is_action_script_3()
+                            && o.as_movie_clip()
+                                .map_or(false, |mc| mc.forced_button_mode()))
+                });
+                let is_pressed = |o: DisplayObject<'_>| {
+                    if let Some(button) = o.as_avm1_button() {
+                        button.state() == ButtonState::Down
+                    } else if let Some(button) = o.as_avm2_button() {
+                        button.state() == ButtonState::Down
+                    } else {
+                        true
+                    }
+                };
+                let button_event = match (focused_button, event) {
+                    (
+                        Some(_),
+                        PlayerEvent::KeyDown {
+                            key_code: KeyCode::Return | KeyCode::Space,
+                            ..
+                        },
+                    ) => Some(ClipEvent::Press),
+                    (
+                        Some(button),
+                        PlayerEvent::KeyUp {
+                            key_code: KeyCode::Return | KeyCode::Space,
+                            ..
+                        },
+                    ) if is_pressed(button) => Some(ClipEvent::Release),
+                    _ => None,
+                };
+                if let (Some(button), Some(button_event)) = (
+                    focused_button.and_then(|o| o.as_interactive()),
+                    button_event,
+                ) {
+                    button.handle_clip_event(context, button_event);
+                    if context.is_action_script_3() && button_event == ClipEvent::Release {
+                        button.event_dispatch_to_avm2(context, button_event);
+                    }
+                }
+            }
+
             // keyPress events take precedence over text input.
             if !key_press_handled {
                 if let PlayerEvent::TextInput { codepoint } = event {
@@ -1238,14 +1641,39 @@ impl Player {
         let mut new_cursor = self.mouse_cursor;
         let mut mouse_cursor_needs_check = self.mouse_cursor_needs_check;
         let mouse_in_stage = self.mouse_in_stage();
+        let mut hover_cache = self.hover_cache.take();
 
         // Determine the display object the mouse is hovering over.
         // Search through levels from top-to-bottom, returning the first display object that is under the mouse.
         let needs_render = self.mutate_with_update_context(|context| {
-            let new_over_object = if mouse_in_stage {
-                run_mouse_pick(context, true)
-            } else {
+            let cached_over_object = match (&mut hover_cache, context.mouse_over_object) {
+                (Some(cache), Some(over_object))
+                    if !is_mouse_button_changed
+                        && cache.bounds.contains(*context.mouse_position)
+                        && if context.is_action_script_3() {
+                            over_object.as_displayobject().is_on_stage(context)
+                        } else {
+                            !over_object.as_displayobject().avm1_removed()
+                        } =>
+                {
+                    is_still_picked(context, over_object, &mut cache.covering)
+                        .then_some(over_object)
+                }
+                _ => None,
+            };
+            let new_over_object = if !mouse_in_stage {
                 None
+            } else if cached_over_object.is_some() {
+                cached_over_object
+            } else {
+                run_mouse_pick(context, true)
+            };
+            hover_cache = match (hover_cache.take(), new_over_object) {
+                (Some(cache), Some(_)) if cached_over_object.is_some() => Some(cache),
+                (_, new_over_object) => new_over_object.map(|o| HoverCache {
+                    bounds: o.as_displayobject().world_bounds(),
+                    covering: None,
+                }),
             };
             let mut events: smallvec::SmallVec<[(InteractiveObject<'_>, ClipEvent); 2]> =
                 Default::default();
@@ -1325,6 +1753,39 @@ impl Player {
                             ));
                         }
                     }
+
+                    // Objects tracking as menus also receive "drag" events when the mouse
+                    // was pressed on another object.
+                    if let Some(cur_over_object) = cur_over_object {
+                        if cur_over_object.tracks_as_menu()
+                            && !InteractiveObject::option_ptr_eq(
+                                context.mouse_down_object,
+                                Some(cur_over_object),
+                            )
+                        {
+                            events.push((
+                                cur_over_object,
+                                ClipEvent::DragOut {
+                                    to: new_over_object,
+                                },
+                            ));
+                        }
+                    }
+                    if let Some(new_over_object) = new_over_object {
+                        if new_over_object.tracks_as_menu()
+                            && !InteractiveObject::option_ptr_eq(
+                                context.mouse_down_object,
+                                Some(new_over_object),
+                            )
+                        {
+                            events.push((
+                                new_over_object,
+                                ClipEvent::DragOver {
+                                    from: cur_over_object,
+                                },
+                            ));
+                        }
+                    }
                 } else {
                     // The mouse button is up, so fire rollover states for the object we are hovering over.
                     // Rolled out of the previous object.
@@ -1388,15 +1849,20 @@ impl Player {
                         } else {
                             events.push((context.stage.into(), ClipEvent::ReleaseOutside));
                         }
-                        // The new object is rolled over immediately.
                         if let Some(over_object) = context.mouse_over_object {
                             new_cursor = over_object.mouse_cursor(context);
-                            events.push((
-                                over_object,
-                                ClipEvent::RollOver {
-                                    from: cur_over_object,
-                                },
-                            ));
+                            if over_object.tracks_as_menu() {
+                                // Objects tracking as menus are released as if they were pressed.
+                                events.push((over_object, ClipEvent::Release));
+                            } else {
+                                // The new object is rolled over immediately.
+                                events.push((
+                                    over_object,
+                                    ClipEvent::RollOver {
+                                        from: cur_over_object,
+                                    },
+                                ));
+                            }
                         } else {
                             new_cursor = MouseCursor::Arrow;
                         }
@@ -1405,6 +1871,19 @@ impl Player {
                 }
             }
 
+            // Whatever is drawn above the hovered object must be looked at again
+            // once mouse handlers may have changed the display list.
+            let may_run_handlers = !context.is_action_script_3()
+                || context.avm2.has_event_listeners("mouseMove".into())
+                || events
+                    .iter()
+                    .any(|(_, event)| *event != ClipEvent::MouseMoveInside);
+            if may_run_handlers {
+                if let Some(cache) = &mut hover_cache {
+                    cache.covering = None;
+                }
+            }
+
             // Fire any pending mouse events.
             let needs_render = if events.is_empty() {
                 false
@@ -1442,6 +1921,7 @@ impl Player {
             self.ui.set_mouse_cursor(new_cursor)
         }
         self.mouse_cursor_needs_check = mouse_cursor_needs_check;
+        self.hover_cache = hover_cache;
 
         needs_render
     }
@@ -1505,6 +1985,8 @@ impl Player {
 
     #[instrument(level = "debug", skip_all)]
     pub fn run_frame(&mut self) {
+        let frame_start = Instant::now();
+        self.hover_cache = None;
         let frame_time = Duration::from_nanos((750_000_000.0 / self.frame_rate) as u64);
         let (mut execution_limit, may_execute_while_streaming) = match self.load_behavior {
             LoadBehavior::Streaming => (
@@ -1523,6 +2005,13 @@ impl Player {
             return;
         }
 
+        if self.frame_interpolation {
+            self.gc_arena.borrow().mutate(|gc_context, gc_root| {
+                let stage = gc_root.data.read().stage;
+                record_previous_matrices(gc_context, stage.into());
+            });
+        }
+
         self.update(|context| {
             if context.is_action_script_3() {
                 run_all_phases_avm2(context);
@@ -1532,11 +2021,25 @@ impl Player {
             AudioManager::update_sounds(context);
         });
 
+        if self.injected_scripts_pending && preload_finished {
+            self.injected_scripts_pending = false;
+            let scripts = std::mem::take(&mut self.injected_scripts);
+            self.update(|context| run_injected_scripts(context, &scripts));
+            self.injected_scripts = scripts;
+        }
+
+        // Watched properties are shown as of the end of each frame.
+        if let Some(mut inspector) = self.object_inspector.take() {
+            self.update(|context| inspector.refresh(context));
+            self.object_inspector = Some(inspector);
+        }
+
         self.needs_render = true;
     }
 
     #[instrument(level = "debug", skip_all)]
     pub fn render(&mut self) {
+        let render_start = Instant::now();
         let invalidated = self
             .gc_arena
             .borrow()
@@ -1550,6 +2053,18 @@ impl Player {
 
         let mut background_color = Color::WHITE;
 
+        let frame_interpolation = if self.frame_interpolation && self.is_playing {
+            let frame_time = 1000.0 / self.frame_rate;
+            Some((self.frame_accumulator / frame_time).clamp(0.0, 1.0) as f32)
+        } else {
+            None
+        };
+
+        let caption = self.captions.as_ref().and_then(|captions| {
+            let frame = self.current_frame?.saturating_sub(1);
+            captions.text_at(Duration::from_secs_f64(f64::from(frame) / self.frame_rate))
+        });
+
         let commands = self.gc_arena.borrow().mutate(|gc_context, gc_root| {
             let root_data = gc_root.data.read();
             let stage = root_data.stage;
@@ -1561,11 +2076,36 @@ impl Player {
                 library: &root_data.library,
                 transform_stack: &mut self.transform_stack,
                 is_offscreen: false,
+                frame_interpolation,
                 stage,
             };
 
             stage.render(&mut render_context);
 
+            if let Some(caption) = &caption {
+                render_caption(&mut render_context, caption);
+            }
+
+            if let Some(inspector) = &self.timeline_inspector {
+                let hovered = root_data
+                    .mouse_hovered_object
+                    .map(|object| object.as_displayobject());
+                let y = self.perf_hud.as_ref().map_or(0.0, PerfHud::height);
+                inspector.render(&mut render_context, hovered, y);
+            }
+
+            if let Some(inspector) = &self.object_inspector {
+                inspector.render(&mut render_context);
+            }
+
+            if self.show_network_log {
+                self.network_log.borrow().render(&mut render_context);
+            }
+
+            if let Some(perf_hud) = &self.perf_hud {
+                perf_hud.render(&mut render_context);
+            }
+
             background_color =
                 if stage.window_mode() != WindowMode::Transparent || stage.is_fullscreen() {
                     stage.background_color().unwrap_or(Color::WHITE)
@@ -1576,9 +2116,188 @@ impl Player {
             render_context.commands
         });
 
+        let render_commands = commands.commands.len();
         self.renderer.submit_frame(background_color, commands);
 
-        self.needs_render = false;
+        if let Some(perf_hud) = &mut self.perf_hud {
+            perf_hud.record_render(render_start, render_start.elapsed(), render_commands);
+            // Keep the overlay up to date.
+            self.needs_render = true;
+        } else {
+            // The outline of the hovered object follows the mouse, and requests
+            // complete between frames.
+            self.needs_render = self.timeline_inspector.is_some() || self.show_network_log;
+        }
+    }
+
+    /// Whether the performance overlay is shown.
+    pub fn show_perf_hud(&self) -> bool {
+        self.perf_hud.is_some()
+    }
+
+    /// Shows or hides an overlay with the frame rate, the time spent running scripts,
+    /// collecting garbage and rendering, and the number of render commands.
+    pub fn set_show_perf_hud(&mut self, show: bool) {
+        if show != self.perf_hud.is_some() {
+            self.perf_hud = show.then(PerfHud::new);
+            self.needs_render = true;
+        }
+    }
+
+    /// Whether the timeline inspector is shown.
+    pub fn show_timeline_inspector(&self) -> bool {
+        self.timeline_inspector.is_some()
+    }
+
+    /// Shows or hides a panel listing the display list with the position, frame, visibility,
+    /// blend mode and filters of each object, and outlining the selected and hovered objects.
+    pub fn set_show_timeline_inspector(&mut self, show: bool) {
+        if show != self.timeline_inspector.is_some() {
+            self.timeline_inspector = show.then(TimelineInspector::new);
+            self.needs_render = true;
+        }
+    }
+
+    /// Selects the object `delta` lines below the current one in the timeline inspector.
+    pub fn timeline_inspector_select(&mut self, delta: isize) {
+        if let Some(inspector) = &mut self.timeline_inspector {
+            self.gc_arena.borrow().mutate(|_, gc_root| {
+                inspector.move_selection(gc_root.data.read().stage, delta);
+            });
+            self.needs_render = true;
+        }
+    }
+
+    /// Moves the movie clip selected in the timeline inspector `delta` frames
+    /// forward or back, and stops it there.
+    pub fn timeline_inspector_step_frame(&mut self, delta: i32) {
+        if let Some(inspector) = self.timeline_inspector.take() {
+            self.update(|context| inspector.step_frame(context, delta));
+            self.timeline_inspector = Some(inspector);
+            self.needs_render = true;
+        }
+    }
+
+    /// Whether the object inspector is shown.
+    pub fn show_object_inspector(&self) -> bool {
+        self.object_inspector.is_some()
+    }
+
+    /// Shows or hides a panel browsing the properties of the objects of the movie from
+    /// its root, with a list of watched properties updated after every frame.
+    pub fn set_show_object_inspector(&mut self, show: bool) {
+        if show != self.object_inspector.is_some() {
+            self.object_inspector = show.then(ObjectInspector::new);
+            self.with_object_inspector(|_, _| {});
+        }
+    }
+
+    /// Selects the property `delta` lines below the current one in the object inspector.
+    pub fn object_inspector_select(&mut self, delta: isize) {
+        self.with_object_inspector(|inspector, _| inspector.move_selection(delta));
+    }
+
+    /// Inspects the object selected in the object inspector.
+    pub fn object_inspector_enter(&mut self) {
+        self.with_object_inspector(ObjectInspector::enter);
+    }
+
+    /// Goes back to the object owning the one in the object inspector.
+    pub fn object_inspector_leave(&mut self) {
+        self.with_object_inspector(ObjectInspector::leave);
+    }
+
+    /// Starts or stops watching the property selected in the object inspector.
+    pub fn object_inspector_toggle_watch(&mut self) {
+        self.with_object_inspector(ObjectInspector::toggle_watch);
+    }
+
+    /// Toggles the boolean selected in the object inspector, or adds `delta` to the number.
+    pub fn object_inspector_adjust(&mut self, delta: f64) {
+        self.with_object_inspector(|inspector, context| inspector.adjust_selected(context, delta));
+    }
+
+    /// Runs `f` on the object inspector, if it's shown, and refreshes it.
+    fn with_object_inspector(
+        &mut self,
+        f: impl FnOnce(&mut ObjectInspector, &mut UpdateContext<'_, '_>),
+    ) {
+        if let Some(mut inspector) = self.object_inspector.take() {
+            self.update(|context| {
+                f(&mut inspector, context);
+                inspector.refresh(context);
+            });
+            self.object_inspector = Some(inspector);
+            self.needs_render = true;
+        }
+    }
+
+    /// Whether the network log is shown.
+    pub fn show_network_log(&self) -> bool {
+        self.show_network_log
+    }
+
+    /// Shows or hides a panel listing the requests made by the movie, with their outcome,
+    /// size and duration.
+    pub fn set_show_network_log(&mut self, show: bool) {
+        self.show_network_log = show;
+        self.needs_render = true;
+    }
+
+    /// Selects the request `delta` lines below the current one in the network log.
+    pub fn network_log_select(&mut self, delta: isize) {
+        self.network_log.borrow_mut().move_selection(delta);
+        self.needs_render = true;
+    }
+
+    /// Sends the request selected in the network log again, recording it as a new request.
+    ///
+    /// The response is only recorded, and isn't given to the movie.
+    pub fn replay_network_request(&mut self) {
+        let Some(request) = self
+            .network_log
+            .borrow()
+            .selected()
+            .map(NetworkEntry::to_request)
+        else {
+            return;
+        };
+        let fetch = self.fetch(request, &self.swf);
+        self.navigator.spawn_future(Box::pin(async move {
+            let _ = fetch.await;
+            Ok(())
+        }));
+    }
+
+    /// The requests made by the movie since the player started, oldest first.
+    pub fn network_requests(&self) -> Vec<NetworkEntry> {
+        self.network_log.borrow().entries().cloned().collect()
+    }
+
+    /// Exports the requests made by the movie as an HTTP Archive (HAR) document.
+    pub fn export_network_log(&self) -> String {
+        self.network_log.borrow().to_har()
+    }
+
+    /// Lists the properties of an object of the movie, without running any script.
+    ///
+    /// The object is found by following the properties named in `path`, from `_root` in
+    /// AVM1 movies and from the stage in AVM2 movies. Getters aren't called, so their
+    /// values aren't listed.
+    pub fn inspect_object(&mut self, path: &[&str]) -> Option<Vec<InspectedProperty>> {
+        self.update(|context| object_inspector::inspect(context, path))
+    }
+
+    /// Sets a boolean, number or string property of the object at the end of `path`,
+    /// parsing `value` as the current type of the property.
+    ///
+    /// Returns whether the property could be set.
+    pub fn set_object_property(&mut self, path: &[&str], name: &str, value: &str) -> bool {
+        let set = self.update(|context| object_inspector::set_property(context, path, name, value));
+        if set {
+            self.with_object_inspector(|_, _| {});
+        }
+        set
     }
 
     /// The current frame of the main timeline, if available.
@@ -1587,6 +2306,12 @@ impl Player {
         self.current_frame
     }
 
+    /// Sets the captions to display above the stage, or removes them.
+    pub fn set_captions(&mut self, captions: Option<Captions>) {
+        self.captions = captions;
+        self.needs_render = true;
+    }
+
     pub fn audio(&self) -> &Audio {
         &self.audio
     }
@@ -1599,6 +2324,81 @@ impl Player {
         &self.navigator
     }
 
+    /// Fetches a resource requested by a movie.
+    ///
+    /// When the sandbox is enforced, requests that the security sandbox of the requesting
+    /// movie doesn't allow fail with `LoaderError::SandboxViolation` without reaching the
+    /// navigator.
+    pub fn fetch(
+        &self,
+        request: Request,
+        requesting_movie: &SwfMovie,
+    ) -> OwnedFuture<Response, LoaderError> {
+        self.fetch_with_progress(request, requesting_movie, Box::new(|_, _| {}))
+    }
+
+    /// Fetches data for a movie like `fetch`, reporting the progress of the download to
+    /// `on_progress` as the body arrives, if the navigator can.
+    pub fn fetch_with_progress(
+        &self,
+        request: Request,
+        requesting_movie: &SwfMovie,
+        on_progress: ProgressCallback,
+    ) -> OwnedFuture<Response, LoaderError> {
+        // Navigators that don't know how they resolve a URL leave it relative to the movie.
+        let url = self.navigator.absolute_url(request.url()).or_else(|| {
+            let movie_url = Url::parse(requesting_movie.url()).ok()?;
+            movie_url.join(request.url()).ok()
+        });
+        let entry = NetworkEntry::new(
+            &request,
+            url.as_ref()
+                .map_or_else(|| request.url().to_owned(), Url::to_string),
+        );
+        if self.enforce_sandbox {
+            let sandbox_type = SandboxType::for_movie(requesting_movie);
+            let error = match &url {
+                Some(url) if sandbox_type.can_access(url) => None,
+                Some(url) => Some(LoaderError::sandbox_violation(
+                    sandbox_type,
+                    requesting_movie.url(),
+                    url,
+                )),
+                // A URL that can't be resolved can't be checked, so it isn't loaded.
+                None => Some(LoaderError::SandboxViolation(
+                    2048,
+                    format!(
+                        "Error #2048: Security sandbox violation: {} cannot load data from {}.",
+                        requesting_movie.url(),
+                        request.url()
+                    ),
+                )),
+            };
+            if let Some(error) = error {
+                tracing::warn!("{}", error);
+                return network_log::record(
+                    &self.network_log,
+                    entry,
+                    Box::pin(async move { Err(error) }),
+                );
+            }
+        }
+        network_log::record(
+            &self.network_log,
+            entry,
+            self.navigator.fetch_with_progress(request, on_progress),
+        )
+    }
+
+    /// Fetches the root movie, which the sandbox doesn't apply to.
+    pub(crate) fn fetch_root_movie_data(
+        &self,
+        request: Request,
+    ) -> OwnedFuture<Response, LoaderError> {
+        let entry = NetworkEntry::new(&request, request.url().to_owned());
+        network_log::record(&self.network_log, entry, self.navigator.fetch(request))
+    }
+
     // The frame rate of the current movie in FPS.
     pub fn frame_rate(&self) -> f64 {
         self.frame_rate
@@ -1620,6 +2420,21 @@ impl Player {
         &mut self.storage
     }
 
+    /// The domain whose settings apply to the root movie.
+    pub fn settings_domain(&self) -> String {
+        DomainSettings::domain_of(self.swf.url())
+    }
+
+    /// The settings the user chose for the domain of the root movie.
+    pub fn domain_settings(&self) -> DomainSettings {
+        DomainSettings::load(self.storage.as_ref(), &self.settings_domain())
+    }
+
+    pub fn set_domain_settings(&mut self, settings: &DomainSettings) {
+        let domain = self.settings_domain();
+        settings.save(self.storage.as_mut(), &domain);
+    }
+
     pub fn destroy(self) -> Renderer {
         self.renderer
     }
@@ -1757,6 +2572,8 @@ impl Player {
                 stream_manager,
             ) = root_data.update_context_params();
 
+            self.script_watchdog.reset();
+
             let mut update_context = UpdateContext {
                 player_version: self.player_version,
                 swf: &self.swf,
@@ -1780,8 +2597,15 @@ impl Player {
                 system: &mut self.system,
                 instance_counter: &mut self.instance_counter,
                 storage: self.storage.deref_mut(),
+                remote_shared_objects: self.remote_shared_objects.deref_mut(),
+                local_connections: self.local_connections.deref_mut(),
+                sockets: self.sockets.deref_mut(),
+                printer: self.printer.deref_mut(),
+                print_job: &mut self.print_job,
+                localizations: &self.localizations,
                 log: self.log.deref_mut(),
                 video: self.video.deref_mut(),
+                video_capture: self.video_capture.deref_mut(),
                 avm1_shared_objects,
                 avm2_shared_objects,
                 unbound_text_fields,
@@ -1794,12 +2618,14 @@ impl Player {
                 start_time: self.start_time,
                 update_start: Instant::now(),
                 max_execution_duration: self.max_execution_duration,
+                script_watchdog: &mut self.script_watchdog,
                 focus_tracker,
                 times_get_time_called: 0,
                 time_offset: &mut self.time_offset,
                 audio_manager,
                 frame_rate: &mut self.frame_rate,
                 forced_frame_rate: self.forced_frame_rate,
+                air_compatibility: self.air_compatibility,
                 actions_since_timeout_check: &mut self.actions_since_timeout_check,
                 frame_phase: &mut self.frame_phase,
                 stub_tracker: &mut self.stub_tracker,
@@ -1877,8 +2703,14 @@ impl Player {
         self.update_mouse_state(false, false);
 
         // GC
+        let gc_start = Instant::now();
         self.gc_arena.borrow_mut().collect_debt();
 
+        if let Some(perf_hud) = &mut self.perf_hud {
+            perf_hud.script_ms = gc_start.duration_since(frame_start).as_secs_f64() * 1000.0;
+            perf_hud.gc_ms = gc_start.elapsed().as_secs_f64() * 1000.0;
+        }
+
         rval
     }
 
@@ -1907,6 +2739,35 @@ impl Player {
         });
     }
 
+    /// Deletes the stored data of the shared objects opened by the movie, without flushing them.
+    fn delete_shared_objects(&mut self) {
+        self.update(|context| {
+            let names: Vec<String> = context
+                .avm1_shared_objects
+                .keys()
+                .chain(context.avm2_shared_objects.keys())
+                .cloned()
+                .collect();
+            for name in names {
+                context.storage.remove_key(&name);
+            }
+        });
+    }
+
+    /// Snapshot the state of the running movie into a versioned binary blob.
+    ///
+    /// See `crate::save_state` for what is (and isn't) captured.
+    pub fn save_state(&mut self) -> Result<Vec<u8>, SaveStateError> {
+        self.update(save_state::save_state)
+    }
+
+    /// Restore a snapshot previously created by `save_state`.
+    pub fn load_state(&mut self, data: &[u8]) -> Result<(), SaveStateError> {
+        let result = self.update(|context| save_state::load_state(context, data));
+        self.needs_render = true;
+        result
+    }
+
     /// Update all AVM-based timers (such as created via setInterval).
     /// Returns the approximate amount of time until the next timer tick.
     pub fn update_timers(&mut self, dt: f64) {
@@ -1914,6 +2775,13 @@ impl Player {
             self.mutate_with_update_context(|context| Timers::update_timers(context, dt));
     }
 
+    /// Runs the `XMLSocket` handlers for what happened to their connections.
+    ///
+    /// This is done on every tick, and can be called by frontends that run frames themselves.
+    pub fn poll_sockets(&mut self) {
+        self.update(crate::avm1::globals::xml_socket::poll_sockets);
+    }
+
     /// Returns whether this player consumes mouse wheel events.
     /// Used by web to prevent scrolling.
     pub fn should_prevent_scrolling(&mut self) -> bool {
@@ -1948,10 +2816,30 @@ impl Player {
         &self.compatibility_rules
     }
 
+    /// The known issues of the root movie, if it has any.
+    pub fn compatibility_hint(&self) -> Option<&CompatibilityHint> {
+        self.compatibility_hints.hint_for(&self.swf)
+    }
+
     pub fn log_backend(&self) -> &Log {
         &self.log
     }
 
+    pub fn localizations(&self) -> &Localizations {
+        &self.localizations
+    }
+
+    /// The translations of the text shown by the player, which embedders
+    /// may add languages to.
+    pub fn localizations_mut(&mut self) -> &mut Localizations {
+        &mut self.localizations
+    }
+
+    /// The text of a message, in the language of the user.
+    pub fn text(&self, id: &str) -> String {
+        self.localizations.text(&self.ui.language(), id)
+    }
+
     pub fn max_execution_duration(&self) -> Duration {
         self.max_execution_duration
     }
@@ -1965,6 +2853,42 @@ impl Player {
             arena: Rc::downgrade(&self.gc_arena),
         }
     }
+
+    pub fn frame_catch_up(&self) -> FrameCatchUp {
+        self.frame_catch_up
+    }
+
+    /// Sets how frames are caught up when ticks fall behind the frame rate.
+    pub fn set_frame_catch_up(&mut self, frame_catch_up: FrameCatchUp) {
+        self.frame_catch_up = frame_catch_up;
+    }
+
+    pub fn frame_interpolation(&self) -> bool {
+        self.frame_interpolation
+    }
+
+    /// Enables or disables interpolating display object transforms between frames.
+    pub fn set_frame_interpolation(&mut self, frame_interpolation: bool) {
+        self.frame_interpolation = frame_interpolation;
+        if !frame_interpolation {
+            // Forget the recorded transforms, so that re-enabling this later doesn't
+            // interpolate from stale positions.
+            self.gc_arena.borrow().mutate(|gc_context, gc_root| {
+                let stage = gc_root.data.read().stage;
+                clear_previous_matrices(gc_context, stage.into());
+            });
+        }
+        self.needs_render = true;
+    }
+
+    /// Whether every frame of the display should be rendered, rather than only the frames of
+    /// the movie. This is the case while smooth playback interpolates between movie frames.
+    ///
+    /// Hosts should then redraw once per display refresh (for example, when presenting the
+    /// previous frame completes), instead of waiting for `time_til_next_frame`.
+    pub fn wants_continuous_render(&self) -> bool {
+        self.frame_interpolation && self.is_playing
+    }
 }
 
 /// Player factory, which can be used to configure the aspects of a Ruffle player.
@@ -1977,8 +2901,14 @@ pub struct PlayerBuilder {
     navigator: Option<Navigator>,
     renderer: Option<Renderer>,
     storage: Option<Storage>,
+    remote_shared_objects: Option<RemoteSharedObjects>,
+    local_connections: Option<LocalConnections>,
+    sockets: Option<Sockets>,
+    printer: Option<Printer>,
     ui: Option<Ui>,
+    localizations: Option<Localizations>,
     video: Option<Video>,
+    video_capture: Option<VideoCapture>,
 
     // Misc. player configuration
     autoplay: bool,
@@ -1993,11 +2923,22 @@ pub struct PlayerBuilder {
     warn_on_unsupported_content: bool,
     load_behavior: LoadBehavior,
     spoofed_url: Option<String>,
+    enforce_sandbox: bool,
+    permissive_cross_scripting: bool,
     compatibility_rules: CompatibilityRules,
+    compatibility_hints: CompatibilityHints,
     player_version: Option<u8>,
     quality: StageQuality,
     sandbox_type: SandboxType,
     frame_rate: Option<f64>,
+    frame_catch_up: FrameCatchUp,
+    frame_interpolation: bool,
+    leak_detection: bool,
+    playback_speed: f64,
+    air_compatibility: bool,
+    injected_scripts: Vec<InjectedScript>,
+    patch: Option<MoviePatch>,
+    captions: Option<Captions>,
 }
 
 impl PlayerBuilder {
@@ -2015,8 +2956,14 @@ impl PlayerBuilder {
             navigator: None,
             renderer: None,
             storage: None,
+            remote_shared_objects: None,
+            local_connections: None,
+            sockets: None,
+            printer: None,
             ui: None,
+            localizations: None,
             video: None,
+            video_capture: None,
 
             autoplay: false,
             scale_mode: StageScaleMode::ShowAll,
@@ -2035,11 +2982,22 @@ impl PlayerBuilder {
             warn_on_unsupported_content: true,
             load_behavior: LoadBehavior::Streaming,
             spoofed_url: None,
+            enforce_sandbox: false,
+            permissive_cross_scripting: false,
             compatibility_rules: CompatibilityRules::default(),
+            compatibility_hints: CompatibilityHints::default(),
             player_version: None,
             quality: StageQuality::High,
             sandbox_type: SandboxType::LocalTrusted,
             frame_rate: None,
+            frame_catch_up: FrameCatchUp::Adaptive,
+            frame_interpolation: false,
+            leak_detection: false,
+            playback_speed: 1.0,
+            air_compatibility: false,
+            injected_scripts: vec![],
+            patch: None,
+            captions: None,
         }
     }
 
@@ -2085,6 +3043,59 @@ impl PlayerBuilder {
         self
     }
 
+    /// Sets the backend that remote shared objects are synchronized through.
+    #[inline]
+    pub fn with_remote_shared_objects(
+        mut self,
+        remote_shared_objects: impl 'static + RemoteSharedObjectBackend,
+    ) -> Self {
+        self.remote_shared_objects = Some(Box::new(remote_shared_objects));
+        self
+    }
+
+    /// Sets the backend that the messages of local connections are delivered through.
+    /// By default, messages are only delivered between the movies of the player.
+    #[inline]
+    pub fn with_local_connections(
+        mut self,
+        local_connections: impl 'static + LocalConnectionBackend,
+    ) -> Self {
+        self.local_connections = Some(Box::new(local_connections));
+        self
+    }
+
+    /// Sets the backend that `XMLSocket` connections are opened through.
+    /// By default, every connection fails.
+    #[inline]
+    pub fn with_sockets(mut self, sockets: impl 'static + SocketBackend) -> Self {
+        self.sockets = Some(Box::new(sockets));
+        self
+    }
+
+    /// Sets the backend that the pages of `PrintJob` are printed with.
+    /// By default, printing is not supported.
+    #[inline]
+    pub fn with_printer(mut self, printer: impl 'static + PrintBackend) -> Self {
+        self.printer = Some(Box::new(printer));
+        self
+    }
+
+    /// Sets the backend that the frames of cameras are captured with.
+    /// By default, no camera is available.
+    #[inline]
+    pub fn with_video_capture(mut self, video_capture: impl 'static + VideoCaptureBackend) -> Self {
+        self.video_capture = Some(Box::new(video_capture));
+        self
+    }
+
+    /// Sets the translations of the text shown by the player, which frontends
+    /// add the translations of their own text to.
+    #[inline]
+    pub fn with_localizations(mut self, localizations: Localizations) -> Self {
+        self.localizations = Some(localizations);
+        self
+    }
+
     /// Sets the UI backend of the player.
     #[inline]
     pub fn with_ui(mut self, ui: impl 'static + UiBackend) -> Self {
@@ -2179,6 +3190,12 @@ impl PlayerBuilder {
         self
     }
 
+    /// Sets the database of known issues that movies are checked against.
+    pub fn with_compatibility_hints(mut self, compatibility_hints: CompatibilityHints) -> Self {
+        self.compatibility_hints = compatibility_hints;
+        self
+    }
+
     /// Configures the target player version.
     pub fn with_player_version(mut self, version: Option<u8>) -> Self {
         self.player_version = version;
@@ -2191,12 +3208,82 @@ impl PlayerBuilder {
         self
     }
 
+    /// Determines the security sandbox of each movie from its URL, the way Flash Player
+    /// does, and restricts what the movie may load accordingly. Off by default.
+    /// The sandbox of the root movie overrides `with_sandbox_type` once it is loaded.
+    pub fn with_sandbox_enforcement(mut self, enforce_sandbox: bool) -> Self {
+        self.enforce_sandbox = enforce_sandbox;
+        self
+    }
+
+    /// Lets movies script movies from other domains even if these didn't call
+    /// `Security.allowDomain`, for content that relied on a lenient host.
+    pub fn with_permissive_cross_scripting(mut self, permissive: bool) -> Self {
+        self.permissive_cross_scripting = permissive;
+        self
+    }
+
     /// Sets and locks the player's frame rate. If None is provided, this has no effect.
     pub fn with_frame_rate(mut self, frame_rate: Option<f64>) -> Self {
         self.frame_rate = frame_rate;
         self
     }
 
+    /// Sets how frames are caught up when ticks fall behind the frame rate.
+    /// This defaults to `FrameCatchUp::Adaptive`.
+    pub fn with_frame_catch_up(mut self, frame_catch_up: FrameCatchUp) -> Self {
+        self.frame_catch_up = frame_catch_up;
+        self
+    }
+
+    /// Sets whether display object transforms are interpolated between frames when the host
+    /// renders faster than the movie's frame rate. This is disabled by default.
+    pub fn with_frame_interpolation(mut self, frame_interpolation: bool) -> Self {
+        self.frame_interpolation = frame_interpolation;
+        self
+    }
+
+    /// Sets whether display objects removed from the stage are tracked to report probable
+    /// leaks through `Player::probable_leaks`. This is disabled by default.
+    pub fn with_leak_detection(mut self, leak_detection: bool) -> Self {
+        self.leak_detection = leak_detection;
+        self
+    }
+
+    /// Sets the initial playback speed multiplier of the player (default is 1.0).
+    pub fn with_playback_speed(mut self, speed: f64) -> Self {
+        self.playback_speed = speed;
+        self
+    }
+
+    /// Sets whether the APIs of Adobe AIR are available to movies (default is false).
+    ///
+    /// This enables shims for AIR-packaged games: `flash.filesystem.File` and `FileStream`
+    /// access the file sandbox of the storage backend, `NativeApplication.exit` quits through
+    /// the UI backend, and `NativeWindow` does nothing.
+    pub fn with_air_compatibility(mut self, air_compatibility: bool) -> Self {
+        self.air_compatibility = air_compatibility;
+        self
+    }
+
+    /// Adds a script to be run against the root movie once it has loaded.
+    pub fn with_injected_script(mut self, script: InjectedScript) -> Self {
+        self.injected_scripts.push(script);
+        self
+    }
+
+    /// Sets a patch of replacement characters and FlashVars to apply to the root movie.
+    pub fn with_patch(mut self, patch: MoviePatch) -> Self {
+        self.patch = Some(patch);
+        self
+    }
+
+    /// Sets the captions to display above the stage.
+    pub fn with_captions(mut self, captions: Captions) -> Self {
+        self.captions = Some(captions);
+        self
+    }
+
     fn create_gc_root<'gc>(
         gc_context: MutationContext<'gc, '_>,
         player_version: u8,
@@ -2261,12 +3348,27 @@ impl PlayerBuilder {
         let storage = self
             .storage
             .unwrap_or_else(|| Box::new(storage::MemoryStorageBackend::new()));
+        let remote_shared_objects = self.remote_shared_objects.unwrap_or_else(|| {
+            Box::new(remote_shared_object::NullRemoteSharedObjectBackend::new())
+        });
+        let local_connections = self
+            .local_connections
+            .unwrap_or_else(|| Box::new(local_connection::MemoryLocalConnectionBackend::new()));
+        let sockets = self
+            .sockets
+            .unwrap_or_else(|| Box::new(socket::NullSocketBackend::new()));
+        let printer = self
+            .printer
+            .unwrap_or_else(|| Box::new(print::NullPrintBackend::new()));
         let ui = self
             .ui
             .unwrap_or_else(|| Box::new(ui::NullUiBackend::new()));
         let video = self
             .video
             .unwrap_or_else(|| Box::new(null::NullVideoBackend::new()));
+        let video_capture = self
+            .video_capture
+            .unwrap_or_else(|| Box::new(video_capture::NullVideoCaptureBackend::new()));
 
         let player_version = self.player_version.unwrap_or(NEWEST_PLAYER_VERSION);
 
@@ -2282,8 +3384,14 @@ impl PlayerBuilder {
                 navigator,
                 renderer,
                 storage,
+                remote_shared_objects,
+                local_connections,
+                sockets,
+                printer,
                 ui,
                 video,
+                video_capture,
+                localizations: self.localizations.unwrap_or_default(),
 
                 // SWF info
                 swf: fake_movie.clone(),
@@ -2292,13 +3400,20 @@ impl PlayerBuilder {
                 // Timing
                 frame_rate,
                 forced_frame_rate,
+                air_compatibility: self.air_compatibility,
                 frame_phase: Default::default(),
                 frame_accumulator: 0.0,
                 recent_run_frame_timings: VecDeque::with_capacity(10),
+                frame_catch_up: self.frame_catch_up,
+                frame_interpolation: self.frame_interpolation,
+                leak_detection: self.leak_detection,
+                playback_speed: 1.0,
+                muted_volume: None,
                 start_time: Instant::now(),
                 time_offset: 0,
                 time_til_next_timer: None,
                 max_execution_duration: self.max_execution_duration,
+                script_watchdog: ScriptWatchdog::new(),
                 actions_since_timeout_check: 0,
 
                 // Input
@@ -2307,10 +3422,14 @@ impl PlayerBuilder {
                 mouse_position: Point::ZERO,
                 mouse_cursor: MouseCursor::Arrow,
                 mouse_cursor_needs_check: false,
+                hover_cache: None,
 
                 // Misc. state
                 rng: SmallRng::seed_from_u64(get_current_date_time().timestamp_millis() as u64),
-                system: SystemProperties::new(self.sandbox_type),
+                system: SystemProperties {
+                    permissive_cross_scripting: self.permissive_cross_scripting,
+                    ..SystemProperties::new(self.sandbox_type)
+                },
                 transform_stack: TransformStack::new(),
                 instance_counter: 0,
                 player_version,
@@ -2320,8 +3439,20 @@ impl PlayerBuilder {
                 self_reference: self_ref.clone(),
                 load_behavior: self.load_behavior,
                 spoofed_url: self.spoofed_url.clone(),
+                enforce_sandbox: self.enforce_sandbox,
                 compatibility_rules: self.compatibility_rules.clone(),
+                compatibility_hints: self.compatibility_hints.clone(),
                 stub_tracker: StubCollection::new(),
+                injected_scripts: self.injected_scripts.clone(),
+                injected_scripts_pending: false,
+                patch: self.patch.clone().map(Arc::new),
+                captions: self.captions.clone(),
+                perf_hud: None,
+                timeline_inspector: None,
+                object_inspector: None,
+                network_log: Rc::new(RefCell::new(NetworkLog::new())),
+                show_network_log: false,
+                print_job: None,
 
                 // GC data
                 gc_arena: Rc::new(RefCell::new(GcArena::new(
@@ -2340,25 +3471,12 @@ impl PlayerBuilder {
 
         // Finalize configuration and load the movie.
         let mut player_lock = player.lock().unwrap();
-        player_lock.mutate_with_update_context(|context| {
-            // Instantiate an empty root before the main movie loads.
-            let fake_root = MovieClip::new(fake_movie, context.gc_context);
-            fake_root.post_instantiation(context, None, Instantiator::Movie, false);
-            context.stage.replace_at_depth(context, fake_root.into(), 0);
-            Avm2::load_player_globals(context).expect("Unable to load AVM2 globals");
-            let stage = context.stage;
-            stage.set_scale_mode(context, self.scale_mode);
-            stage.set_forced_scale_mode(context, self.forced_scale_mode);
-            stage.post_instantiation(context, None, Instantiator::Movie, false);
-            stage.build_matrices(context);
-        });
-        player_lock.gc_arena.borrow().mutate(|context, root| {
-            let call_stack = root.data.read().avm2.call_stack();
-            root.callstack.write(context).avm2 = Some(call_stack);
-        });
+        player_lock.initialize_stage(fake_movie, self.scale_mode, self.forced_scale_mode);
         player_lock.audio.set_frame_rate(frame_rate);
         player_lock.set_letterbox(self.letterbox);
         player_lock.set_quality(self.quality);
+        player_lock.set_playback_speed(self.playback_speed);
+        player_lock.set_leak_detection(self.leak_detection);
         player_lock.set_viewport_dimensions(ViewportDimensions {
             width: self.viewport_width,
             height: self.viewport_height,
@@ -2401,6 +3519,118 @@ pub struct DragObject<'gc> {
     pub constraint: Rectangle<Twips>,
 }
 
+/// Records the current matrix of `object` and all of its descendants for frame interpolation.
+fn record_previous_matrices<'gc>(gc_context: MutationContext<'gc, '_>, object: DisplayObject<'gc>) {
+    object.base_mut(gc_context).record_previous_matrix();
+    if let Some(container) = object.as_container() {
+        for child in container.iter_render_list() {
+            record_previous_matrices(gc_context, child);
+        }
+    }
+}
+
+/// Clears the recorded matrix of `object` and all of its descendants.
+fn clear_previous_matrices<'gc>(gc_context: MutationContext<'gc, '_>, object: DisplayObject<'gc>) {
+    object.base_mut(gc_context).clear_previous_matrix();
+    if let Some(container) = object.as_container() {
+        for child in container.iter_render_list() {
+            clear_previous_matrices(gc_context, child);
+        }
+    }
+}
+
+/// The object the mouse was last found to hover over, see `Player::hover_cache`.
+struct HoverCache {
+    /// The world bounds of the hovered object.
+    bounds: Rectangle<Twips>,
+
+    /// The union of the world bounds of everything drawn above the hovered object,
+    /// computed when first needed.
+    covering: Option<Rectangle<Twips>>,
+}
+
+/// Whether `run_mouse_pick` would still find the given object under the mouse.
+///
+/// The object itself is hit tested again, and the mouse must be within the masks and
+/// scroll rects of its ancestors. It must also not be within the bounds of anything drawn
+/// above it, which could then be picked instead; those are cached in `covering`.
+fn is_still_picked<'gc>(
+    context: &mut UpdateContext<'_, 'gc>,
+    object: InteractiveObject<'gc>,
+    covering: &mut Option<Rectangle<Twips>>,
+) -> bool {
+    let point = *context.mouse_position;
+    let picked = if context.is_action_script_3() {
+        match object.mouse_pick_avm2(context, point, true) {
+            Avm2MousePick::Hit(target) => Some(target),
+            _ => None,
+        }
+    } else {
+        object.mouse_pick_avm1(context, point, true)
+    };
+    if !InteractiveObject::option_ptr_eq(picked, Some(object)) {
+        return false;
+    }
+
+    let mut child = object.as_displayobject();
+    while let Some(parent) = child.parent() {
+        if !parent.visible() || parent.maskee().is_some() {
+            return false;
+        }
+        if let Some(masker) = parent.masker() {
+            if !masker.hit_test_shape(context, point, HitTestOptions::SKIP_INVISIBLE) {
+                return false;
+            }
+        }
+        if let Some(scroll_rect) = parent.scroll_rect() {
+            let visible_area = parent.local_to_global_matrix_without_own_scroll_rect()
+                * Rectangle {
+                    x_min: Twips::ZERO,
+                    y_min: Twips::ZERO,
+                    x_max: scroll_rect.width(),
+                    y_max: scroll_rect.height(),
+                };
+            if !visible_area.contains(point) {
+                return false;
+            }
+        }
+        if let Some(container) = parent.as_container() {
+            let depth = child.depth();
+            let is_clipped = container.iter_render_list().any(|clip| {
+                clip.clip_depth() > 0
+                    && (clip.depth() + 1..=clip.clip_depth()).contains(&depth)
+                    && !clip.hit_test_shape(context, point, HitTestOptions::MOUSE_PICK)
+            });
+            if is_clipped {
+                return false;
+            }
+        }
+        child = parent;
+    }
+
+    let covering = covering.get_or_insert_with(|| covering_bounds(object.as_displayobject()));
+    !covering.contains(point)
+}
+
+/// The union of the world bounds of everything drawn above the given object.
+fn covering_bounds(object: DisplayObject<'_>) -> Rectangle<Twips> {
+    let mut covering = Rectangle::default();
+    let mut child = object;
+    while let Some(parent) = child.parent() {
+        if let Some(container) = parent.as_container() {
+            for above in container
+                .iter_render_list()
+                .skip_while(|above| !DisplayObject::ptr_eq(*above, child))
+                .skip(1)
+            {
+                covering = covering.union(&above.world_bounds());
+            }
+        }
+        child = parent;
+    }
+    covering
+}
+
 fn run_mouse_pick<'gc>(
     context: &mut UpdateContext<'_, 'gc>,
     require_button_mode: bool,
@@ -2424,3 +3654,56 @@ fn run_mouse_pick<'gc>(
         })
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Builds a playing player for an empty movie, running at 10 frames per second.
+    fn playing_player() -> Arc<Mutex<Player>> {
+        PlayerBuilder::new()
+            .with_movie(SwfMovie::empty(10))
+            .with_frame_rate(Some(10.0))
+            .with_autoplay(true)
+            .build()
+    }
+
+    #[test]
+    fn tick_speeds_up_time() {
+        let player = playing_player();
+        let mut player = player.lock().unwrap();
+        player.set_playback_speed(2.0);
+
+        player.tick(25.0);
+        assert_eq!(player.frame_accumulator, 50.0);
+        assert!(player.recent_run_frame_timings.is_empty());
+        // The time until the next frame is in host time.
+        assert_eq!(player.time_til_next_frame(), Duration::from_millis(25));
+
+        player.tick(25.0);
+        assert_eq!(player.frame_accumulator, 0.0);
+        assert_eq!(player.recent_run_frame_timings.len(), 1);
+    }
+
+    #[test]
+    fn tick_slows_down_time() {
+        let player = playing_player();
+        let mut player = player.lock().unwrap();
+        player.set_playback_speed(0.5);
+
+        player.tick(100.0);
+        assert_eq!(player.frame_accumulator, 50.0);
+        assert!(player.recent_run_frame_timings.is_empty());
+        assert_eq!(player.time_til_next_frame(), Duration::from_millis(100));
+    }
+
+    #[test]
+    fn invalid_playback_speeds_are_ignored() {
+        let player = playing_player();
+        let mut player = player.lock().unwrap();
+        player.set_playback_speed(2.0);
+        player.set_playback_speed(0.0);
+        player.set_playback_speed(f64::NAN);
+        assert_eq!(player.playback_speed(), 2.0);
+    }
+}
@@ -0,0 +1,108 @@
+//! The print job of `PrintJob`, shared by both AVMs.
+//!
+//! Only one job can be in progress at a time. Its pages are rendered as
+//! soon as they are added, since the movie may change right after.
+
+use crate::backend::print::{PageFormat, PrintPage};
+use crate::bitmap::bitmap_data::{BitmapData, BitmapDataWrapper, IBitmapDrawable};
+use crate::bitmap::operations;
+use crate::context::UpdateContext;
+use crate::display_object::{DisplayObject, TDisplayObject};
+use gc_arena::GcCell;
+use ruffle_render::matrix::Matrix;
+use ruffle_render::quality::StageQuality;
+use ruffle_render::transform::Transform;
+use swf::{BlendMode, Rectangle, Twips};
+
+/// How many pixels pages are rendered with per point, to print sharper than the screen.
+const PIXELS_PER_POINT: f32 = 2.0;
+
+/// The pages added to a job that the user accepted to print.
+pub struct PrintJob {
+    format: PageFormat,
+    pages: Vec<PrintPage>,
+}
+
+/// Asks the user to print, returning the format of the pages if they accepted.
+///
+/// Fails if another job is in progress.
+pub fn start_job(context: &mut UpdateContext<'_, '_>) -> Option<PageFormat> {
+    if context.print_job.is_some() {
+        return None;
+    }
+    let format = context.printer.start()?;
+    *context.print_job = Some(PrintJob {
+        format,
+        pages: Vec::new(),
+    });
+    Some(format)
+}
+
+/// Renders `area` of `object` as a new page of the job in progress.
+///
+/// The area is in the coordinates of the object, and defaults to its bounds.
+/// It's printed at the top left of the page, and cropped to the page.
+pub fn add_page<'gc>(
+    context: &mut UpdateContext<'_, 'gc>,
+    object: DisplayObject<'gc>,
+    area: Option<Rectangle<Twips>>,
+) -> bool {
+    let Some(format) = context.print_job.as_ref().map(|job| job.format) else {
+        return false;
+    };
+    let area = area.unwrap_or_else(|| object.bounds());
+    if !area.is_valid() {
+        return false;
+    }
+    let width = area.width().to_pixels().min(format.page_width.into());
+    let height = area.height().to_pixels().min(format.page_height.into());
+    let image_width = (width * f64::from(PIXELS_PER_POINT)).ceil() as u32;
+    let image_height = (height * f64::from(PIXELS_PER_POINT)).ceil() as u32;
+    if image_width == 0 || image_height == 0 {
+        return false;
+    }
+
+    // Pages are printed on white paper.
+    let mut page = BitmapData::default();
+    page.init_pixels(image_width, image_height, false, -1);
+    let target = BitmapDataWrapper::new(GcCell::allocate(context.gc_context, page));
+    let transform = Transform {
+        matrix: Matrix::scale(PIXELS_PER_POINT, PIXELS_PER_POINT)
+            * Matrix::translate(-area.x_min, -area.y_min),
+        ..Default::default()
+    };
+    if let Err(e) = operations::draw(
+        context,
+        target,
+        IBitmapDrawable::DisplayObject(object),
+        transform,
+        true,
+        BlendMode::Normal,
+        None,
+        StageQuality::High,
+    ) {
+        tracing::warn!("Couldn't render a printed page: {:?}", e);
+        return false;
+    }
+    let rgba = target.sync().read().pixels_rgba();
+
+    if let Some(job) = context.print_job.as_mut() {
+        job.pages.push(PrintPage {
+            width,
+            height,
+            image_width,
+            image_height,
+            rgba,
+        });
+    }
+    true
+}
+
+/// Prints the pages of the job in progress, ending it.
+pub fn send_job(context: &mut UpdateContext<'_, '_>) {
+    if let Some(job) = context.print_job.take() {
+        if !job.pages.is_empty() {
+            context.printer.send(job.pages);
+        }
+    }
+}
@@ -0,0 +1,1215 @@
+//! Save states, which snapshot and restore the state of a running movie.
+//!
+//! A save state is stored as an AMF0 LSO containing:
+//! - the display list: the current frame, transform, visibility and text of every display
+//!   object, along with the character of the movie clips created by ActionScript, which are
+//!   recreated when restoring,
+//! - the ActionScript heap reachable from `_global` and the timelines. Every object is
+//!   written once and referred to by its ID afterwards, so shared and cyclic references are
+//!   kept,
+//! - the active intervals and timeouts,
+//! - the playing event sounds and their positions.
+//!
+//! Only AVM1 content is currently supported. Functions and objects with native state
+//! (other than arrays and dates) can't be recreated. These are recorded by where they were
+//! first found, and restored to the object found at the same place in the running movie,
+//! along with their properties. This is exact when restoring into the session that saved
+//! the state; otherwise, the movie must have created these objects by then. For the same
+//! reason, timers whose callback was only reachable from the timer are dropped, and so are
+//! movie clips created by ActionScript that aren't library symbols or empty clips.
+//!
+//! Restoring doesn't run any script: the frame scripts and events of the clips that the
+//! restored frames place are skipped, and properties are defined without calling setters.
+//! Properties defined with `addProperty` are left alone.
+
+use crate::avm1::{
+    Activation, ActivationIdentifier, ArrayObject, Attribute, NativeObject, Object, ObjectPtr,
+    ScriptObject, TObject, Value,
+};
+use crate::backend::audio::EventSoundState;
+use crate::context::{ActionType, UpdateContext};
+use crate::display_object::{DisplayObject, MovieClip, TDisplayObject, TDisplayObjectContainer};
+use crate::prelude::Depth;
+use crate::string::{AvmString, WString};
+use crate::timer::{TimerCallback, TimerState};
+use crate::vminterface::Instantiator;
+use flash_lso::types::{AMFVersion, Element, Lso, Value as AmfValue};
+use ruffle_render::matrix::Matrix;
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+use swf::{CharacterId, ColorTransform, Fixed8, SoundEvent, SoundInfo, Twips};
+use thiserror::Error;
+
+/// The name of the LSO that holds a save state.
+const SAVE_STATE_NAME: &str = "RuffleSaveState";
+
+/// The version of the save state format.
+///
+/// This must be increased whenever the format changes in an incompatible way.
+const SAVE_STATE_VERSION: f64 = 2.0;
+
+/// The attributes of the hidden properties that are saved, `prototype` and `__proto__`.
+const HIDDEN_PROPERTY: Attribute = Attribute::DONT_ENUM.union(Attribute::DONT_DELETE);
+
+#[derive(Debug, Error)]
+pub enum SaveStateError {
+    #[error("Save states are only supported for ActionScript 1.0/2.0 movies")]
+    UnsupportedMovie,
+
+    #[error("Invalid save state data")]
+    InvalidData,
+
+    #[error("Unsupported save state version {0}")]
+    UnsupportedVersion(f64),
+
+    #[error("Save state belongs to a different movie: {0}")]
+    MovieMismatch(String),
+}
+
+/// Snapshot the state of the running movie into a binary blob.
+pub fn save_state(context: &mut UpdateContext<'_, '_>) -> Result<Vec<u8>, SaveStateError> {
+    if context.is_action_script_3() {
+        return Err(SaveStateError::UnsupportedMovie);
+    }
+
+    let mut activation = Activation::from_stub(
+        context.reborrow(),
+        ActivationIdentifier::root("[Save State]"),
+    );
+    let mut saver = Saver::default();
+
+    let global = activation.context.avm1.global_object();
+    let global = saver.save_properties(&mut activation, global, &Location::global());
+
+    let levels: Vec<_> = activation.context.stage.iter_render_list().collect();
+    let levels = levels
+        .into_iter()
+        .map(|level| Rc::new(saver.save_display_object(&mut activation, level)))
+        .collect();
+
+    let timers = activation.context.timers.states();
+    let timers = timers
+        .into_iter()
+        .filter_map(|timer| saver.save_timer(&mut activation, timer))
+        .map(Rc::new)
+        .collect();
+
+    let sounds = activation
+        .context
+        .audio_manager
+        .avm1_event_sounds(activation.context.audio);
+    let sounds = sounds
+        .into_iter()
+        .filter_map(|sound| saver.save_sound(&mut activation, sound))
+        .map(Rc::new)
+        .collect();
+
+    let elements = vec![
+        Element::new("version", AmfValue::Number(SAVE_STATE_VERSION)),
+        Element::new(
+            "url",
+            AmfValue::String(activation.context.swf.url().to_string()),
+        ),
+        Element::new("global", global),
+        Element::new("levels", AmfValue::StrictArray(levels)),
+        Element::new("timers", AmfValue::StrictArray(timers)),
+        Element::new("sounds", AmfValue::StrictArray(sounds)),
+    ];
+
+    let mut lso = Lso::new(elements, SAVE_STATE_NAME, AMFVersion::AMF0);
+    flash_lso::write::write_to_bytes(&mut lso).map_err(|_| SaveStateError::InvalidData)
+}
+
+/// Restore the running movie to a state previously created by `save_state`.
+///
+/// Timelines are first sent to their recorded frames, without running their scripts.
+/// Afterwards, the heap is restored, followed by the timers and sounds.
+pub fn load_state(context: &mut UpdateContext<'_, '_>, data: &[u8]) -> Result<(), SaveStateError> {
+    if context.is_action_script_3() {
+        return Err(SaveStateError::UnsupportedMovie);
+    }
+
+    let lso = flash_lso::read::Reader::default()
+        .parse(data)
+        .map_err(|_| SaveStateError::InvalidData)?;
+
+    match find(&lso.body, "version") {
+        Some(AmfValue::Number(version)) if *version == SAVE_STATE_VERSION => {}
+        Some(AmfValue::Number(version)) => {
+            return Err(SaveStateError::UnsupportedVersion(*version));
+        }
+        _ => return Err(SaveStateError::InvalidData),
+    }
+
+    match find(&lso.body, "url") {
+        Some(AmfValue::String(url)) if url == context.swf.url() => {}
+        Some(AmfValue::String(url)) => return Err(SaveStateError::MovieMismatch(url.clone())),
+        _ => return Err(SaveStateError::InvalidData),
+    }
+
+    let levels = match find(&lso.body, "levels") {
+        Some(AmfValue::StrictArray(levels)) => levels,
+        _ => return Err(SaveStateError::InvalidData),
+    };
+
+    context.stop_all_sounds();
+
+    for (level, state) in matching_children(context.stage.into(), levels) {
+        restore_display_object(context, level, state);
+    }
+
+    let mut activation = Activation::from_stub(
+        context.reborrow(),
+        ActivationIdentifier::root("[Load State]"),
+    );
+
+    skip_queued_actions(&mut activation);
+
+    // Functions and other objects that can't be recreated are looked up before
+    // anything is restored, while the heap still has its current shape.
+    let mut loader = Loader::default();
+    for element in &lso.body {
+        loader.resolve_live_objects(&mut activation, element.value());
+    }
+
+    if let Some(AmfValue::Object(global, _)) = find(&lso.body, "global") {
+        let global_object = activation.context.avm1.global_object();
+        loader.restore_properties(&mut activation, global_object, global, true);
+    }
+
+    for (level, state) in matching_children(activation.context.stage.into(), levels) {
+        loader.restore_display_object_variables(&mut activation, level, state);
+    }
+
+    if let Some(AmfValue::StrictArray(timers)) = find(&lso.body, "timers") {
+        let timers = timers
+            .iter()
+            .filter_map(|timer| loader.restore_timer(&mut activation, timer))
+            .collect();
+        activation.context.timers.restore(timers);
+    }
+
+    if let Some(AmfValue::StrictArray(sounds)) = find(&lso.body, "sounds") {
+        for sound in sounds {
+            loader.restore_sound(&mut activation, sound);
+        }
+    }
+
+    Ok(())
+}
+
+/// Where an object was first found while saving, starting from `_global` or a display object.
+#[derive(Clone)]
+struct Location<'gc> {
+    /// The display object whose properties lead to the object, or `None` for `_global`.
+    root: Option<DisplayObject<'gc>>,
+
+    /// The names of the properties leading to the object.
+    names: Vec<AvmString<'gc>>,
+}
+
+impl<'gc> Location<'gc> {
+    fn global() -> Self {
+        Self {
+            root: None,
+            names: vec![],
+        }
+    }
+
+    fn display_object(object: DisplayObject<'gc>) -> Self {
+        Self {
+            root: Some(object),
+            names: vec![],
+        }
+    }
+
+    fn child(&self, name: AvmString<'gc>) -> Self {
+        let mut names = self.names.clone();
+        names.push(name);
+        Self {
+            root: self.root,
+            names,
+        }
+    }
+
+    fn to_amf(&self) -> Option<AmfValue> {
+        let root = match self.root {
+            Some(root) => depth_path_to_amf(root)?,
+            None => AmfValue::String("_global".to_string()),
+        };
+        let names = self
+            .names
+            .iter()
+            .map(|name| Rc::new(AmfValue::String(name.to_utf8_lossy().into_owned())))
+            .collect();
+        Some(AmfValue::Object(
+            vec![
+                Element::new("root", root),
+                Element::new("names", AmfValue::StrictArray(names)),
+            ],
+            None,
+        ))
+    }
+}
+
+/// Walks the state of the movie, keeping track of the objects already written.
+#[derive(Default)]
+struct Saver {
+    /// The IDs of the objects written so far.
+    ids: HashMap<*const ObjectPtr, u32>,
+}
+
+impl Saver {
+    fn save_display_object<'gc>(
+        &mut self,
+        activation: &mut Activation<'_, 'gc>,
+        object: DisplayObject<'gc>,
+    ) -> AmfValue {
+        let matrix = *object.base().matrix();
+        let color_transform = *object.base().color_transform();
+
+        let mut elements = vec![
+            Element::new("depth", AmfValue::Number(object.depth().into())),
+            Element::new(
+                "name",
+                AmfValue::String(object.name().to_utf8_lossy().into_owned()),
+            ),
+            Element::new("character", AmfValue::Number(object.id().into())),
+            Element::new(
+                "dynamic",
+                AmfValue::Bool(!object.instantiated_by_timeline()),
+            ),
+            Element::new("matrix", matrix_to_amf(&matrix)),
+            Element::new("colorTransform", color_transform_to_amf(&color_transform)),
+            Element::new("visible", AmfValue::Bool(object.visible())),
+        ];
+
+        if let Some(clip) = object.as_movie_clip() {
+            elements.push(Element::new("movieClip", AmfValue::Bool(true)));
+            elements.push(Element::new(
+                "frame",
+                AmfValue::Number(clip.current_frame().into()),
+            ));
+            elements.push(Element::new("playing", AmfValue::Bool(clip.playing())));
+        }
+
+        if let Some(edit_text) = object.as_edit_text() {
+            elements.push(Element::new(
+                "text",
+                AmfValue::String(edit_text.text().to_utf8_lossy().into_owned()),
+            ));
+        }
+
+        if let Value::Object(avm1_object) = object.object() {
+            let variables =
+                self.save_properties(activation, avm1_object, &Location::display_object(object));
+            elements.push(Element::new("variables", variables));
+        }
+
+        if let Some(container) = object.as_container() {
+            let children: Vec<_> = container.iter_render_list().collect();
+            let children = children
+                .into_iter()
+                .map(|child| Rc::new(self.save_display_object(activation, child)))
+                .collect();
+            elements.push(Element::new("children", AmfValue::StrictArray(children)));
+        }
+
+        AmfValue::Object(elements, None)
+    }
+
+    /// Serialize the own enumerable properties of an object, without calling getters.
+    fn save_properties<'gc>(
+        &mut self,
+        activation: &mut Activation<'_, 'gc>,
+        object: Object<'gc>,
+        location: &Location<'gc>,
+    ) -> AmfValue {
+        let mut elements = Vec::new();
+        for (name, value) in own_properties(activation, object) {
+            let value = self.save_value(activation, value, Some(&location.child(name)));
+            elements.push(Element::new(name.to_utf8_lossy(), value));
+        }
+        AmfValue::Object(elements, None)
+    }
+
+    /// Serialize a value found at `location`, if it was found in a property.
+    fn save_value<'gc>(
+        &mut self,
+        activation: &mut Activation<'_, 'gc>,
+        value: Value<'gc>,
+        location: Option<&Location<'gc>>,
+    ) -> AmfValue {
+        let object = match value {
+            Value::Undefined => return AmfValue::Undefined,
+            Value::Null => return AmfValue::Null,
+            Value::Bool(b) => return AmfValue::Bool(b),
+            Value::Number(n) => return AmfValue::Number(n),
+            Value::String(s) => return AmfValue::String(s.to_utf8_lossy().into_owned()),
+            Value::MovieClip(_) => value.coerce_to_object(activation),
+            Value::Object(object) => object,
+        };
+
+        if let Some(display_object) = object.as_display_object() {
+            return match depth_path_to_amf(display_object) {
+                Some(path) => node("displayObject", vec![Element::new("path", path)]),
+                None => AmfValue::Undefined,
+            };
+        }
+
+        if let Some(id) = self.ids.get(&object.as_ptr()) {
+            return node("reference", vec![id_element(*id)]);
+        }
+        let id = self.ids.len() as u32;
+        self.ids.insert(object.as_ptr(), id);
+        let mut elements = vec![id_element(id)];
+
+        let prototypes = activation.context.avm1.prototypes();
+        let (kind, default_proto) = if object.as_array_object().is_some() {
+            ("array", Some(prototypes.array))
+        } else if let NativeObject::Date(date) = object.native() {
+            elements.push(Element::new("time", AmfValue::Number(date.read().time())));
+            ("date", None)
+        } else if matches!(object, Object::ScriptObject(_))
+            && matches!(object.native(), NativeObject::None)
+        {
+            ("object", Some(prototypes.object))
+        } else {
+            // This can't be recreated, so the object found at the same place is used instead.
+            if let Some(location) = location.and_then(Location::to_amf) {
+                elements.push(Element::new("location", location));
+            }
+            ("live", None)
+        };
+
+        if let Some(default_proto) = default_proto {
+            if let Value::Object(proto) = object.proto(activation) {
+                if !Object::ptr_eq(proto, default_proto) {
+                    let location = location.map(|location| location.child("__proto__".into()));
+                    let proto = self.save_value(activation, proto.into(), location.as_ref());
+                    elements.push(Element::new("proto", proto));
+                }
+            }
+        }
+
+        let mut properties = Vec::new();
+        if kind == "live" {
+            // The prototype of functions holds the methods of their class.
+            if let Some(Value::Object(prototype)) = object.get_local_stored("prototype", activation)
+            {
+                let location = location.map(|location| location.child("prototype".into()));
+                let prototype = self.save_value(activation, prototype.into(), location.as_ref());
+                properties.push(Element::new("prototype", prototype));
+            }
+        }
+        for (name, value) in own_properties(activation, object) {
+            let location = location.map(|location| location.child(name));
+            let value = self.save_value(activation, value, location.as_ref());
+            properties.push(Element::new(name.to_utf8_lossy(), value));
+        }
+        elements.push(Element::new(
+            "properties",
+            AmfValue::Object(properties, None),
+        ));
+
+        if kind == "array" {
+            let length = object.length(activation).unwrap_or_default();
+            elements.push(Element::new("length", AmfValue::Number(length.into())));
+        }
+
+        node(kind, elements)
+    }
+
+    fn save_timer<'gc>(
+        &mut self,
+        activation: &mut Activation<'_, 'gc>,
+        timer: TimerState<'gc>,
+    ) -> Option<AmfValue> {
+        let mut elements = vec![
+            Element::new("id", AmfValue::Number(timer.id.into())),
+            Element::new("interval", AmfValue::Number(timer.interval)),
+            Element::new("timeLeft", AmfValue::Number(timer.time_left)),
+            Element::new("timeout", AmfValue::Bool(timer.is_timeout)),
+        ];
+
+        let (params, base_clip) = match timer.callback {
+            TimerCallback::Avm1Function {
+                func,
+                params,
+                base_clip,
+            } => {
+                let func = self.save_value(activation, func.into(), None);
+                elements.push(Element::new("function", func));
+                (params, base_clip)
+            }
+            TimerCallback::Avm1Method {
+                this,
+                method_name,
+                params,
+                base_clip,
+            } => {
+                let this = self.save_value(activation, this.into(), None);
+                elements.push(Element::new("this", this));
+                elements.push(Element::new(
+                    "method",
+                    AmfValue::String(method_name.to_utf8_lossy().into_owned()),
+                ));
+                (params, base_clip)
+            }
+            TimerCallback::Avm2Callback { .. } => return None,
+        };
+
+        let params = params
+            .into_iter()
+            .map(|param| Rc::new(self.save_value(activation, param, None)))
+            .collect();
+        elements.push(Element::new("params", AmfValue::StrictArray(params)));
+        if let Some(base_clip) = depth_path_to_amf(base_clip) {
+            elements.push(Element::new("baseClip", base_clip));
+        }
+
+        Some(AmfValue::Object(elements, None))
+    }
+
+    fn save_sound<'gc>(
+        &mut self,
+        activation: &mut Activation<'_, 'gc>,
+        sound: EventSoundState<'gc>,
+    ) -> Option<AmfValue> {
+        let owner = sound.display_object;
+        let movie = owner.map_or_else(|| activation.context.swf.clone(), |owner| owner.movie());
+        let id = activation
+            .context
+            .library
+            .library_for_movie(movie)?
+            .sound_id(sound.sound)?;
+
+        let mut elements = vec![
+            Element::new("character", AmfValue::Number(id.into())),
+            Element::new("position", AmfValue::Number(sound.position)),
+            Element::new(
+                "inSample",
+                AmfValue::Number(sound.settings.in_sample.unwrap_or_default().into()),
+            ),
+            Element::new("loops", AmfValue::Number(sound.settings.num_loops.into())),
+        ];
+        if let Some(out_sample) = sound.settings.out_sample {
+            elements.push(Element::new(
+                "outSample",
+                AmfValue::Number(out_sample.into()),
+            ));
+        }
+        if let Some(owner) = owner.and_then(depth_path_to_amf) {
+            elements.push(Element::new("owner", owner));
+        }
+        if let Some(sound_object) = sound.avm1_object {
+            let sound_object = self.save_value(activation, Object::from(sound_object).into(), None);
+            elements.push(Element::new("soundObject", sound_object));
+        }
+
+        Some(AmfValue::Object(elements, None))
+    }
+}
+
+/// Restores the heap of a save state, keeping track of the objects already restored.
+#[derive(Default)]
+struct Loader<'gc> {
+    /// The objects restored so far, by ID.
+    objects: HashMap<u32, Object<'gc>>,
+
+    /// The objects of the running movie found where the objects that can't be recreated were.
+    live_objects: HashMap<u32, Object<'gc>>,
+
+    /// The objects of the running movie that were reused for restored objects.
+    reused: HashSet<*const ObjectPtr>,
+}
+
+impl<'gc> Loader<'gc> {
+    /// Look up the objects that can't be recreated, anywhere in `value`.
+    fn resolve_live_objects(&mut self, activation: &mut Activation<'_, 'gc>, value: &AmfValue) {
+        match value {
+            AmfValue::Object(elements, _) => {
+                if let (Some(AmfValue::String(kind)), Some(id), Some(location)) = (
+                    find(elements, "type"),
+                    find_id(elements),
+                    find(elements, "location"),
+                ) {
+                    if kind == "live" {
+                        if let Some(object) = resolve_location(activation, location) {
+                            self.live_objects.insert(id, object);
+                        }
+                    }
+                }
+                for element in elements {
+                    self.resolve_live_objects(activation, element.value());
+                }
+            }
+            AmfValue::StrictArray(values) => {
+                for value in values {
+                    self.resolve_live_objects(activation, value);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn restore_display_object_variables(
+        &mut self,
+        activation: &mut Activation<'_, 'gc>,
+        object: DisplayObject<'gc>,
+        state: &[Element],
+    ) {
+        if let (Value::Object(object), Some(AmfValue::Object(variables, _))) =
+            (object.object(), find(state, "variables"))
+        {
+            self.restore_properties(activation, object, variables, true);
+        }
+
+        if let Some(AmfValue::StrictArray(children)) = find(state, "children") {
+            for (child, state) in matching_children(object, children) {
+                self.restore_display_object_variables(activation, child, state);
+            }
+        }
+    }
+
+    /// Restore the properties of `object`.
+    ///
+    /// The current value of each property is reused for its restored value where possible.
+    /// If `exact` is set, the properties that the state doesn't have are deleted.
+    fn restore_properties(
+        &mut self,
+        activation: &mut Activation<'_, 'gc>,
+        object: Object<'gc>,
+        properties: &[Element],
+        exact: bool,
+    ) {
+        if exact {
+            for (name, _) in own_properties(activation, object) {
+                let name_utf8 = name.to_utf8_lossy();
+                if !properties.iter().any(|property| property.name == name_utf8) {
+                    object.delete(activation, name);
+                }
+            }
+        }
+
+        for property in properties {
+            let name = AvmString::new_utf8(activation.context.gc_context, &property.name);
+            if object.has_own_virtual(activation, name) {
+                continue;
+            }
+            let current = object.get_local_stored(name, activation);
+            let value = self.restore_value(activation, property.value(), current);
+            if let (Some(Value::Object(current)), Value::Object(value)) = (current, value) {
+                if Object::ptr_eq(current, value) {
+                    continue;
+                }
+            }
+            let attributes = if property.name == "prototype" {
+                HIDDEN_PROPERTY
+            } else {
+                Attribute::empty()
+            };
+            object.define_value(activation.context.gc_context, name, value, attributes);
+        }
+    }
+
+    /// Deserialize a value, reusing `current` for it if possible.
+    fn restore_value(
+        &mut self,
+        activation: &mut Activation<'_, 'gc>,
+        value: &AmfValue,
+        current: Option<Value<'gc>>,
+    ) -> Value<'gc> {
+        let elements = match value {
+            AmfValue::Undefined => return Value::Undefined,
+            AmfValue::Null => return Value::Null,
+            AmfValue::Bool(b) => return (*b).into(),
+            AmfValue::Number(n) => return (*n).into(),
+            AmfValue::String(s) => {
+                return AvmString::new_utf8(activation.context.gc_context, s).into();
+            }
+            AmfValue::Object(elements, _) => elements,
+            _ => return Value::Undefined,
+        };
+
+        let Some(AmfValue::String(kind)) = find(elements, "type") else {
+            return Value::Undefined;
+        };
+        if kind == "displayObject" {
+            return find(elements, "path")
+                .and_then(|path| resolve_depth_path(activation.context.stage.into(), path))
+                .map_or(Value::Undefined, |object| object.object());
+        }
+        let Some(id) = find_id(elements) else {
+            return Value::Undefined;
+        };
+        if kind == "reference" {
+            return self
+                .objects
+                .get(&id)
+                .map_or(Value::Undefined, |o| (*o).into());
+        }
+
+        let current = match current {
+            Some(Value::Object(current)) if !self.reused.contains(&current.as_ptr()) => {
+                Some(current)
+            }
+            _ => None,
+        };
+        let prototypes = activation.context.avm1.prototypes();
+        let (object, exact) = match kind.as_str() {
+            "object" => match current {
+                Some(current)
+                    if matches!(current, Object::ScriptObject(_))
+                        && matches!(current.native(), NativeObject::None) =>
+                {
+                    (current, true)
+                }
+                _ => (
+                    ScriptObject::new(activation.context.gc_context, Some(prototypes.object))
+                        .into(),
+                    false,
+                ),
+            },
+            "array" => match current {
+                Some(current) if current.as_array_object().is_some() => (current, true),
+                _ => (ArrayObject::empty(activation).into(), false),
+            },
+            "date" => {
+                let time = match find(elements, "time") {
+                    Some(AmfValue::Number(time)) => *time,
+                    _ => f64::NAN,
+                };
+                let date_constructor = prototypes.date_constructor;
+                match date_constructor.construct(activation, &[time.into()]) {
+                    Ok(Value::Object(date)) => (date, false),
+                    _ => return Value::Undefined,
+                }
+            }
+            "live" => match self.live_objects.get(&id) {
+                Some(live) => (*live, false),
+                None => {
+                    tracing::warn!("Save state refers to an object that the movie doesn't have");
+                    return Value::Undefined;
+                }
+            },
+            _ => return Value::Undefined,
+        };
+        self.objects.insert(id, object);
+        self.reused.insert(object.as_ptr());
+
+        if let Some(proto) = find(elements, "proto") {
+            let current = object.get_local_stored("__proto__", activation);
+            let proto = self.restore_value(activation, proto, current);
+            object.define_value(
+                activation.context.gc_context,
+                "__proto__",
+                proto,
+                HIDDEN_PROPERTY,
+            );
+        }
+
+        if let Some(AmfValue::Object(properties, _)) = find(elements, "properties") {
+            self.restore_properties(activation, object, properties, exact);
+        }
+
+        if let Some(AmfValue::Number(length)) = find(elements, "length") {
+            let _ = object.set_length(activation, *length as i32);
+        }
+
+        object.into()
+    }
+
+    fn restore_timer(
+        &mut self,
+        activation: &mut Activation<'_, 'gc>,
+        state: &AmfValue,
+    ) -> Option<TimerState<'gc>> {
+        let AmfValue::Object(state, _) = state else {
+            return None;
+        };
+        let number = |name| match find(state, name) {
+            Some(AmfValue::Number(n)) => Some(*n),
+            _ => None,
+        };
+
+        let params = match find(state, "params") {
+            Some(AmfValue::StrictArray(params)) => params
+                .iter()
+                .map(|param| self.restore_value(activation, param, None))
+                .collect(),
+            _ => vec![],
+        };
+        let base_clip = find(state, "baseClip")
+            .and_then(|path| resolve_depth_path(activation.context.stage.into(), path))
+            .or_else(|| activation.context.stage.root_clip())?;
+
+        let callback = if let Some(func) = find(state, "function") {
+            match self.restore_value(activation, func, None) {
+                Value::Object(func) => TimerCallback::Avm1Function {
+                    func,
+                    params,
+                    base_clip,
+                },
+                _ => {
+                    tracing::warn!("Skipped restoring a timer whose function isn't available");
+                    return None;
+                }
+            }
+        } else {
+            let this = self.restore_value(activation, find(state, "this")?, None);
+            let Some(AmfValue::String(method_name)) = find(state, "method") else {
+                return None;
+            };
+            match this {
+                Value::Object(this) => TimerCallback::Avm1Method {
+                    this,
+                    method_name: AvmString::new_utf8(activation.context.gc_context, method_name),
+                    params,
+                    base_clip,
+                },
+                _ => {
+                    tracing::warn!("Skipped restoring a timer whose object isn't available");
+                    return None;
+                }
+            }
+        };
+
+        Some(TimerState {
+            id: number("id")? as i32,
+            callback,
+            interval: number("interval")?,
+            time_left: number("timeLeft")?,
+            is_timeout: matches!(find(state, "timeout"), Some(AmfValue::Bool(true))),
+        })
+    }
+
+    fn restore_sound(&mut self, activation: &mut Activation<'_, 'gc>, state: &AmfValue) {
+        let AmfValue::Object(state, _) = state else {
+            return;
+        };
+        let number = |name| match find(state, name) {
+            Some(AmfValue::Number(n)) => Some(*n),
+            _ => None,
+        };
+        let (Some(id), Some(position)) = (number("character"), number("position")) else {
+            return;
+        };
+
+        let owner = find(state, "owner")
+            .and_then(|path| resolve_depth_path(activation.context.stage.into(), path));
+        let movie = owner.map_or_else(|| activation.context.swf.clone(), |owner| owner.movie());
+        let Some(sound) = activation
+            .context
+            .library
+            .library_for_movie(movie)
+            .and_then(|library| library.get_sound(id as CharacterId))
+        else {
+            tracing::warn!("Save state refers to missing sound {id}");
+            return;
+        };
+        let sound_object = find(state, "soundObject")
+            .map(|sound_object| self.restore_value(activation, sound_object, None))
+            .and_then(|sound_object| match sound_object {
+                Value::Object(sound_object) => sound_object.as_sound_object(),
+                _ => None,
+            });
+
+        // Sample positions are always in 44.1 kHz.
+        let in_sample = number("inSample").unwrap_or_default() + position * 44.1;
+        let settings = SoundInfo {
+            event: SoundEvent::Event,
+            in_sample: Some(in_sample as u32),
+            out_sample: number("outSample").map(|out_sample| out_sample as u32),
+            num_loops: number("loops").unwrap_or(1.0) as u16,
+            envelope: None,
+        };
+        let instance = activation.context.audio_manager.start_sound(
+            activation.context.audio,
+            sound,
+            &settings,
+            owner,
+            sound_object,
+        );
+        if let (Some(instance), Some(sound_object)) = (instance, sound_object) {
+            sound_object.set_sound_instance(activation.context.gc_context, Some(instance));
+        }
+    }
+}
+
+/// The own enumerable properties of an object that aren't defined by `addProperty`.
+fn own_properties<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    object: Object<'gc>,
+) -> Vec<(AvmString<'gc>, Value<'gc>)> {
+    // Display objects also list their children, which aren't properties.
+    let object = match object {
+        Object::StageObject(_) => object.raw_script_object().into(),
+        _ => object,
+    };
+    let mut properties = Vec::new();
+    for name in object.get_keys(activation).into_iter().rev() {
+        if object.has_own_virtual(activation, name) {
+            continue;
+        }
+        if let Some(value) = object.get_local_stored(name, activation) {
+            properties.push((name, value));
+        }
+    }
+    properties
+}
+
+/// Drop the actions that restoring the display list queued, so that no script runs.
+///
+/// Clips of classes registered with `Object.registerClass` still get their prototype.
+fn skip_queued_actions(activation: &mut Activation<'_, '_>) {
+    while let Some(action) = activation.context.action_queue.pop_action() {
+        if let ActionType::Construct {
+            constructor: Some(constructor),
+            ..
+        } = action.action_type
+        {
+            if let (Ok(prototype), Value::Object(object)) = (
+                constructor.get("prototype", activation),
+                action.clip.object(),
+            ) {
+                object.define_value(
+                    activation.context.gc_context,
+                    "__proto__",
+                    prototype,
+                    HIDDEN_PROPERTY,
+                );
+            }
+        }
+    }
+}
+
+fn restore_display_object<'gc>(
+    context: &mut UpdateContext<'_, 'gc>,
+    object: DisplayObject<'gc>,
+    state: &[Element],
+) {
+    if let Some(clip) = object.as_movie_clip() {
+        if let Some(AmfValue::Number(frame)) = find(state, "frame") {
+            let playing = matches!(find(state, "playing"), Some(AmfValue::Bool(true)));
+            clip.goto_frame(context, *frame as u16, !playing);
+        }
+    }
+
+    if let Some(matrix) = find(state, "matrix").and_then(matrix_from_amf) {
+        object.set_matrix(context.gc_context, matrix);
+    }
+
+    if let Some(color_transform) = find(state, "colorTransform").and_then(color_transform_from_amf)
+    {
+        object.set_color_transform(context.gc_context, color_transform);
+    }
+
+    if let Some(AmfValue::Bool(visible)) = find(state, "visible") {
+        object.set_visible(context.gc_context, *visible);
+    }
+
+    if let (Some(edit_text), Some(AmfValue::String(text))) =
+        (object.as_edit_text(), find(state, "text"))
+    {
+        edit_text.set_text(&WString::from_utf8(text), context);
+    }
+
+    if let Some(AmfValue::StrictArray(children)) = find(state, "children") {
+        restore_dynamic_children(context, object, children);
+        for (child, state) in matching_children(object, children) {
+            restore_display_object(context, child, state);
+        }
+    }
+}
+
+/// Remove the children that ActionScript created since the state was saved,
+/// and recreate the ones it removed.
+fn restore_dynamic_children<'gc>(
+    context: &mut UpdateContext<'_, 'gc>,
+    object: DisplayObject<'gc>,
+    states: &[Rc<AmfValue>],
+) {
+    let Some(mut container) = object.as_container() else {
+        return;
+    };
+    let states: Vec<_> = states
+        .iter()
+        .filter_map(|state| match state.as_ref() {
+            AmfValue::Object(state, _) => Some(state.as_slice()),
+            _ => None,
+        })
+        .collect();
+
+    let children: Vec<_> = container.iter_render_list().collect();
+    for child in children {
+        let is_saved = states.iter().any(|state| is_same_child(child, state));
+        if !child.instantiated_by_timeline() && !is_saved {
+            container.remove_child(context, child);
+        }
+    }
+
+    for state in states {
+        if !matches!(find(state, "dynamic"), Some(AmfValue::Bool(true))) {
+            continue;
+        }
+        let (Some(AmfValue::Number(depth)), Some(AmfValue::String(name))) =
+            (find(state, "depth"), find(state, "name"))
+        else {
+            continue;
+        };
+        let depth = *depth as Depth;
+        if container
+            .child_by_depth(depth)
+            .map_or(false, |child| is_same_child(child, state))
+        {
+            continue;
+        }
+        if !matches!(find(state, "movieClip"), Some(AmfValue::Bool(true))) {
+            tracing::warn!("Can't recreate the dynamic display object at depth {depth}");
+            continue;
+        }
+
+        let id = match find(state, "character") {
+            Some(AmfValue::Number(id)) => *id as CharacterId,
+            _ => 0,
+        };
+        let movie = object.movie();
+        let child = if id != 0 {
+            let Some(child) = context
+                .library
+                .library_for_movie(movie)
+                .and_then(|library| library.instantiate_by_id(id, context.gc_context).ok())
+            else {
+                tracing::warn!("Save state refers to missing character {id}");
+                continue;
+            };
+            child
+        } else {
+            MovieClip::new(movie, context.gc_context).into()
+        };
+        child.set_name(
+            context.gc_context,
+            AvmString::new_utf8(context.gc_context, name),
+        );
+        container.replace_at_depth(context, child, depth);
+        // Registered classes are constructed by the queued action, which is skipped.
+        child.post_instantiation(context, None, Instantiator::Movie, true);
+    }
+}
+
+/// Whether `object` is the child that `state` was saved from.
+fn is_same_child(object: DisplayObject<'_>, state: &[Element]) -> bool {
+    let number = |name| match find(state, name) {
+        Some(AmfValue::Number(n)) => Some(*n),
+        _ => None,
+    };
+    let dynamic = matches!(find(state, "dynamic"), Some(AmfValue::Bool(true)));
+    let name = match find(state, "name") {
+        Some(AmfValue::String(name)) => name.as_str(),
+        _ => return false,
+    };
+
+    number("depth") == Some(object.depth().into())
+        && number("character") == Some(object.id().into())
+        && dynamic == !object.instantiated_by_timeline()
+        && object.name().to_utf8_lossy() == name
+}
+
+/// Pair up the recorded states of children with the children currently at the same depth.
+fn matching_children<'a, 'gc>(
+    object: DisplayObject<'gc>,
+    states: &'a [Rc<AmfValue>],
+) -> Vec<(DisplayObject<'gc>, &'a [Element])> {
+    let Some(container) = object.as_container() else {
+        return vec![];
+    };
+
+    states
+        .iter()
+        .filter_map(|state| match state.as_ref() {
+            AmfValue::Object(state, _) => Some(state.as_slice()),
+            _ => None,
+        })
+        .filter_map(|state| {
+            let depth = match find(state, "depth") {
+                Some(AmfValue::Number(depth)) => *depth as Depth,
+                _ => return None,
+            };
+            let child = container.child_by_depth(depth);
+            if child.is_none() {
+                tracing::warn!("Save state refers to missing display object at depth {depth}");
+            }
+            child.map(|child| (child, state))
+        })
+        .collect()
+}
+
+/// The depths of a display object and its ancestors, from the level down.
+///
+/// Returns `None` if the object isn't on the stage.
+fn depth_path_to_amf(object: DisplayObject<'_>) -> Option<AmfValue> {
+    let mut depths = Vec::new();
+    let mut object = object;
+    loop {
+        let parent = object.parent()?;
+        depths.push(Rc::new(AmfValue::Number(object.depth().into())));
+        if parent.as_stage().is_some() {
+            break;
+        }
+        object = parent;
+    }
+    depths.reverse();
+    Some(AmfValue::StrictArray(depths))
+}
+
+fn resolve_depth_path<'gc>(
+    stage: DisplayObject<'gc>,
+    path: &AmfValue,
+) -> Option<DisplayObject<'gc>> {
+    let AmfValue::StrictArray(depths) = path else {
+        return None;
+    };
+    depths
+        .iter()
+        .try_fold(stage, |object, depth| match depth.as_ref() {
+            AmfValue::Number(depth) => object.as_container()?.child_by_depth(*depth as Depth),
+            _ => None,
+        })
+}
+
+/// Find the object at a location written by `Location::to_amf`, without calling getters.
+fn resolve_location<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    location: &AmfValue,
+) -> Option<Object<'gc>> {
+    let AmfValue::Object(location, _) = location else {
+        return None;
+    };
+    let mut object = match find(location, "root")? {
+        AmfValue::String(root) if root == "_global" => activation.context.avm1.global_object(),
+        root => match resolve_depth_path(activation.context.stage.into(), root)?.object() {
+            Value::Object(object) => object,
+            _ => return None,
+        },
+    };
+    let AmfValue::StrictArray(names) = find(location, "names")? else {
+        return None;
+    };
+    for name in names {
+        let AmfValue::String(name) = name.as_ref() else {
+            return None;
+        };
+        let name = AvmString::new_utf8(activation.context.gc_context, name);
+        let owner = match object {
+            Object::StageObject(_) => object.raw_script_object().into(),
+            _ => object,
+        };
+        object = match owner.get_local_stored(name, activation)? {
+            Value::Object(child) => child,
+            _ => return None,
+        };
+    }
+    Some(object)
+}
+
+fn node(kind: &str, mut elements: Vec<Element>) -> AmfValue {
+    elements.insert(0, Element::new("type", AmfValue::String(kind.to_string())));
+    AmfValue::Object(elements, None)
+}
+
+fn id_element(id: u32) -> Element {
+    Element::new("id", AmfValue::Number(id.into()))
+}
+
+fn find_id(elements: &[Element]) -> Option<u32> {
+    match find(elements, "id") {
+        Some(AmfValue::Number(id)) => Some(*id as u32),
+        _ => None,
+    }
+}
+
+fn find<'a>(elements: &'a [Element], name: &str) -> Option<&'a AmfValue> {
+    elements
+        .iter()
+        .find(|element| element.name == name)
+        .map(|element| element.value())
+}
+
+fn numbers_from_amf<const N: usize>(value: &AmfValue) -> Option<[f64; N]> {
+    let AmfValue::StrictArray(values) = value else {
+        return None;
+    };
+    if values.len() != N {
+        return None;
+    }
+
+    let mut numbers = [0.0; N];
+    for (number, value) in numbers.iter_mut().zip(values) {
+        match value.as_ref() {
+            AmfValue::Number(n) => *number = *n,
+            _ => return None,
+        }
+    }
+    Some(numbers)
+}
+
+fn numbers_to_amf(numbers: &[f64]) -> AmfValue {
+    AmfValue::StrictArray(
+        numbers
+            .iter()
+            .map(|n| Rc::new(AmfValue::Number(*n)))
+            .collect(),
+    )
+}
+
+fn matrix_to_amf(matrix: &Matrix) -> AmfValue {
+    numbers_to_amf(&[
+        matrix.a.into(),
+        matrix.b.into(),
+        matrix.c.into(),
+        matrix.d.into(),
+        matrix.tx.get().into(),
+        matrix.ty.get().into(),
+    ])
+}
+
+fn matrix_from_amf(value: &AmfValue) -> Option<Matrix> {
+    let [a, b, c, d, tx, ty] = numbers_from_amf(value)?;
+    Some(Matrix {
+        a: a as f32,
+        b: b as f32,
+        c: c as f32,
+        d: d as f32,
+        tx: Twips::new(tx as i32),
+        ty: Twips::new(ty as i32),
+    })
+}
+
+fn color_transform_to_amf(color_transform: &ColorTransform) -> AmfValue {
+    numbers_to_amf(&[
+        color_transform.r_multiply.to_f64(),
+        color_transform.g_multiply.to_f64(),
+        color_transform.b_multiply.to_f64(),
+        color_transform.a_multiply.to_f64(),
+        color_transform.r_add.into(),
+        color_transform.g_add.into(),
+        color_transform.b_add.into(),
+        color_transform.a_add.into(),
+    ])
+}
+
+fn color_transform_from_amf(value: &AmfValue) -> Option<ColorTransform> {
+    let [r_multiply, g_multiply, b_multiply, a_multiply, r_add, g_add, b_add, a_add] =
+        numbers_from_amf(value)?;
+    Some(ColorTransform {
+        r_multiply: Fixed8::from_f64(r_multiply),
+        g_multiply: Fixed8::from_f64(g_multiply),
+        b_multiply: Fixed8::from_f64(b_multiply),
+        a_multiply: Fixed8::from_f64(a_multiply),
+        r_add: r_add as i16,
+        g_add: g_add as i16,
+        b_add: b_add as i16,
+        a_add: a_add as i16,
+    })
+}
@@ -0,0 +1,68 @@
+//! Cross-movie scripting permissions, as granted by `Security.allowDomain`.
+
+use crate::tag_utils::SwfMovie;
+use url::Url;
+
+/// The domains that a movie allows to script it, through `Security.allowDomain`
+/// and `Security.allowInsecureDomain`.
+#[derive(Default)]
+pub struct AllowedDomains {
+    domains: Vec<String>,
+    insecure_domains: Vec<String>,
+}
+
+impl AllowedDomains {
+    /// Grants scripting access to a domain, `*` for all domains.
+    ///
+    /// Flash Player also accepts a full URL, in which case only its host is used.
+    /// Movies loaded over HTTPS need `insecure` to be set to allow movies that
+    /// weren't loaded over HTTPS.
+    pub fn allow(&mut self, domain: &str, insecure: bool) {
+        let domain = match Url::parse(domain) {
+            Ok(url) if url.has_host() => url.host_str().unwrap_or_default().to_owned(),
+            _ => domain.trim().to_ascii_lowercase(),
+        };
+        let domains = if insecure {
+            &mut self.insecure_domains
+        } else {
+            &mut self.domains
+        };
+        if !domains.contains(&domain) {
+            domains.push(domain);
+        }
+    }
+
+    fn allows(&self, host: Option<&str>, insecure: bool) -> bool {
+        let matches = |domain: &String| domain == "*" || Some(domain.as_str()) == host;
+        self.insecure_domains.iter().any(matches) || (!insecure && self.domains.iter().any(matches))
+    }
+}
+
+/// Whether two movies were loaded from the same domain.
+///
+/// Local files don't have an origin, but they share the local sandbox.
+pub fn is_same_domain(a: &SwfMovie, b: &SwfMovie) -> bool {
+    match (Url::parse(a.url()), Url::parse(b.url())) {
+        (Ok(a), Ok(b)) if a.scheme() == "file" || b.scheme() == "file" => a.scheme() == b.scheme(),
+        (Ok(a), Ok(b)) => a.origin() == b.origin(),
+        _ => false,
+    }
+}
+
+/// Whether scripts of `caller` may access the objects of `target`, given the
+/// domains that `target` allowed.
+pub fn can_script(caller: &SwfMovie, target: &SwfMovie, allowed: Option<&AllowedDomains>) -> bool {
+    if is_same_domain(caller, target) {
+        return true;
+    }
+
+    let Some(allowed) = allowed else {
+        return false;
+    };
+    let caller_url = Url::parse(caller.url()).ok();
+    let caller_host = caller_url.as_ref().and_then(Url::host_str);
+    let is_secure =
+        |movie: &SwfMovie| Url::parse(movie.url()).map_or(false, |url| url.scheme() == "https");
+    let insecure = is_secure(target) && !is_secure(caller);
+    allowed.allows(caller_host, insecure)
+}
@@ -0,0 +1,118 @@
+//! The choices a user made in the settings panel, which Flash Player
+//! remembered for each domain.
+
+use crate::backend::storage::StorageBackend;
+use url::Url;
+
+/// How much data a domain may store by default, in bytes.
+pub const DEFAULT_STORAGE_QUOTA: u64 = 100 * 1024;
+
+/// Whether content may use a device such as the camera.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DevicePermission {
+    /// The user is asked when content wants to use the device.
+    #[default]
+    Ask,
+    Allow,
+    Deny,
+}
+
+impl DevicePermission {
+    pub fn name(self) -> &'static str {
+        match self {
+            DevicePermission::Ask => "ask",
+            DevicePermission::Allow => "allow",
+            DevicePermission::Deny => "deny",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "ask" => DevicePermission::Ask,
+            "allow" => DevicePermission::Allow,
+            "deny" => DevicePermission::Deny,
+            _ => return None,
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DomainSettings {
+    /// How much data the domain may store, in bytes, or `None` for no limit.
+    pub storage_quota: Option<u64>,
+
+    pub camera: DevicePermission,
+
+    pub microphone: DevicePermission,
+}
+
+impl Default for DomainSettings {
+    fn default() -> Self {
+        Self {
+            storage_quota: Some(DEFAULT_STORAGE_QUOTA),
+            camera: DevicePermission::Ask,
+            microphone: DevicePermission::Ask,
+        }
+    }
+}
+
+impl DomainSettings {
+    /// The domain that the settings of a movie are kept for.
+    /// Local movies all share the settings of `localhost`.
+    pub fn domain_of(movie_url: &str) -> String {
+        match Url::parse(movie_url) {
+            Ok(url) if url.scheme() != "file" => url.host_str().unwrap_or_default().to_owned(),
+            _ => "localhost".to_owned(),
+        }
+    }
+
+    /// The storage key of the settings of a domain, which is where Flash
+    /// Player kept them too.
+    fn key(domain: &str) -> String {
+        format!("macromedia.com/support/flashplayer/sys/#{domain}/settings")
+    }
+
+    pub fn load(storage: &dyn StorageBackend, domain: &str) -> Self {
+        let mut settings = Self::default();
+        let Some(data) = storage.get(&Self::key(domain)) else {
+            return settings;
+        };
+
+        for line in String::from_utf8_lossy(&data).lines() {
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+            match key {
+                "storage_quota" => {
+                    settings.storage_quota = match value {
+                        "unlimited" => None,
+                        value => value.parse().ok().or(settings.storage_quota),
+                    }
+                }
+                "camera" => {
+                    settings.camera = DevicePermission::from_name(value).unwrap_or_default()
+                }
+                "microphone" => {
+                    settings.microphone = DevicePermission::from_name(value).unwrap_or_default()
+                }
+                _ => tracing::warn!("Unknown setting {} for {}", key, domain),
+            }
+        }
+        settings
+    }
+
+    pub fn save(&self, storage: &mut dyn StorageBackend, domain: &str) {
+        let storage_quota = self
+            .storage_quota
+            .map_or_else(|| "unlimited".to_owned(), |quota| quota.to_string());
+        let data = format!(
+            "storage_quota={}\ncamera={}\nmicrophone={}\n",
+            storage_quota,
+            self.camera.name(),
+            self.microphone.name()
+        );
+        if !storage.put(&Self::key(domain), data.as_bytes()) {
+            tracing::warn!("Couldn't save the settings of {}", domain);
+        }
+    }
+}
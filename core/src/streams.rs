@@ -1,10 +1,32 @@
 //! NetStream implementation
 
+mod flv;
+
+use crate::avm1::{
+    Activation as Avm1Activation, ActivationIdentifier as Avm1ActivationIdentifier,
+    Error as Avm1Error, ExecutionReason, Object as Avm1Object, ScriptObject as Avm1ScriptObject,
+    TObject as _, Value as Avm1Value,
+};
 use crate::backend::navigator::Request;
+use crate::camera::Camera;
 use crate::context::UpdateContext;
 use crate::loader::Error;
 use crate::string::AvmString;
+use crate::tag_utils::SwfMovie;
+use crate::vminterface::AvmObject;
+use flash_lso::types::Value as AmfValue;
+use flv::{FlvHeader, FlvTag, FlvTagData, FrameType};
 use gc_arena::{Collect, GcCell, MutationContext};
+use ruffle_render::bitmap::BitmapInfo;
+use ruffle_video::frame::EncodedFrame;
+use ruffle_video::VideoStreamHandle;
+use std::fmt;
+use std::sync::Arc;
+use swf::{VideoCodec, VideoDeblocking};
+
+/// How many seconds of media are buffered before playback starts, unless
+/// `setBufferTime` is called.
+const DEFAULT_BUFFER_TIME: f64 = 0.1;
 
 /// Manager for all media streams.
 ///
@@ -19,6 +41,9 @@ pub struct StreamManager<'gc> {
     /// This is not the total list of all created NetStreams; only the ones
     /// that have been configured to play media.
     playing_streams: Vec<NetStream<'gc>>,
+
+    /// The cameras requested by movies, by index, created when first requested.
+    cameras: Vec<Option<Camera<'gc>>>,
 }
 
 impl<'gc> Default for StreamManager<'gc> {
@@ -31,9 +56,28 @@ impl<'gc> StreamManager<'gc> {
     pub fn new() -> Self {
         StreamManager {
             playing_streams: Vec::new(),
+            cameras: Vec::new(),
         }
     }
 
+    /// The camera at `index`, or the default camera, unless the system has no such camera.
+    pub fn camera(
+        context: &mut UpdateContext<'_, 'gc>,
+        index: Option<usize>,
+    ) -> Option<Camera<'gc>> {
+        let devices = context.video_capture.devices();
+        let index = index.unwrap_or(0);
+        let name = devices.into_iter().nth(index)?;
+
+        let cameras = &mut context.stream_manager.cameras;
+        if cameras.len() <= index {
+            cameras.resize(index + 1, None);
+        }
+        let camera =
+            *cameras[index].get_or_insert_with(|| Camera::new(context.gc_context, index, name));
+        Some(camera)
+    }
+
     pub fn ensure_playing(context: &mut UpdateContext<'_, 'gc>, stream: NetStream<'gc>) {
         if !context.stream_manager.playing_streams.contains(&stream) {
             context.stream_manager.playing_streams.push(stream);
@@ -71,7 +115,18 @@ impl<'gc> StreamManager<'gc> {
     /// support video framerates separate from the Stage frame rate.
     ///
     /// This does not borrow `&mut self` as we need the `UpdateContext`, too.
-    pub fn tick(_context: &mut UpdateContext<'_, 'gc>, _dt: f64) {}
+    pub fn tick(context: &mut UpdateContext<'_, 'gc>, dt: f64) {
+        // Handlers may start or stop streams while they are ticked.
+        let streams = context.stream_manager.playing_streams.clone();
+        for stream in streams {
+            stream.tick(context, dt);
+        }
+
+        let cameras = context.stream_manager.cameras.clone();
+        for camera in cameras.into_iter().flatten() {
+            camera.tick(context);
+        }
+    }
 }
 
 /// A stream representing download of some (audiovisual) data.
@@ -88,9 +143,17 @@ impl<'gc> StreamManager<'gc> {
 ///
 /// It corresponds directly to the AVM1 and AVM2 `NetStream` classes; it's API
 /// is intended to be a VM-agnostic version of those.
-#[derive(Clone, Debug, Collect, Copy)]
+#[derive(Clone, Collect, Copy)]
 #[collect(no_drop)]
-pub struct NetStream<'gc>(GcCell<'gc, NetStreamData>);
+pub struct NetStream<'gc>(GcCell<'gc, NetStreamData<'gc>>);
+
+impl fmt::Debug for NetStream<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NetStream")
+            .field("ptr", &self.0.as_ptr())
+            .finish()
+    }
+}
 
 impl<'gc> PartialEq for NetStream<'gc> {
     fn eq(&self, other: &Self) -> bool {
@@ -100,27 +163,102 @@ impl<'gc> PartialEq for NetStream<'gc> {
 
 impl<'gc> Eq for NetStream<'gc> {}
 
-#[derive(Clone, Debug, Collect)]
-#[collect(require_static)]
-pub struct NetStreamData {
+#[derive(Collect)]
+#[collect(no_drop)]
+pub struct NetStreamData<'gc> {
     /// All data currently loaded in the stream.
+    #[collect(require_static)]
     buffer: Vec<u8>,
+
+    /// The size of the media, once it was fully downloaded.
+    bytes_total: Option<usize>,
+
+    /// The offset of the next tag to play, once the FLV header was read.
+    offset: Option<usize>,
+
+    /// The position of the playhead, in milliseconds.
+    time: f64,
+
+    /// How many seconds of media to download before playing it.
+    buffer_time: f64,
+
+    /// Whether playback waits for enough media to be downloaded.
+    buffering: bool,
+
+    /// The decoder of the video, and the codec it was created for.
+    #[collect(require_static)]
+    video_stream: Option<(VideoStreamHandle, VideoCodec)>,
+
+    /// The size of the video, as given by its metadata.
+    #[collect(require_static)]
+    video_size: Option<(u16, u16)>,
+
+    /// The ID of the next video frame to decode.
+    next_frame_id: u32,
+
+    /// The last decoded video frame.
+    #[collect(require_static)]
+    last_frame: Option<BitmapInfo>,
+
+    /// The object that the handlers of the stream are called on.
+    avm_object: Option<AvmObject<'gc>>,
+}
+
+/// A tag read from the stream, copied out of the buffer so that handlers
+/// can use the stream while it's played.
+enum StreamTag {
+    Video {
+        frame_type: FrameType,
+        codec: Option<VideoCodec>,
+        data: Vec<u8>,
+    },
+    Script {
+        name: String,
+        value: AmfValue,
+    },
+    Other,
 }
 
 impl<'gc> NetStream<'gc> {
-    pub fn new(gc_context: MutationContext<'gc, '_>) -> Self {
+    pub fn new(gc_context: MutationContext<'gc, '_>, avm_object: Option<AvmObject<'gc>>) -> Self {
         Self(GcCell::allocate(
             gc_context,
-            NetStreamData { buffer: Vec::new() },
+            NetStreamData {
+                buffer: Vec::new(),
+                bytes_total: None,
+                offset: None,
+                time: 0.0,
+                buffer_time: DEFAULT_BUFFER_TIME,
+                buffering: true,
+                video_stream: None,
+                video_size: None,
+                next_frame_id: 0,
+                last_frame: None,
+                avm_object,
+            },
         ))
     }
 
-    pub fn load_buffer(self, gc_context: MutationContext<'gc, '_>, data: &mut Vec<u8>) {
-        self.0.write(gc_context).buffer.append(data);
+    /// Adds downloaded data to the stream.
+    ///
+    /// `is_complete` tells whether this is the end of the download.
+    pub fn load_buffer(
+        self,
+        gc_context: MutationContext<'gc, '_>,
+        data: &mut Vec<u8>,
+        is_complete: bool,
+    ) {
+        let mut write = self.0.write(gc_context);
+        write.buffer.append(data);
+        if is_complete {
+            write.bytes_total = Some(write.buffer.len());
+        }
     }
 
-    pub fn report_error(self, _error: Error) {
-        //TODO: Report an `asyncError` to AVM1 or 2.
+    pub fn report_error(self, context: &mut UpdateContext<'_, 'gc>, error: Error) {
+        tracing::warn!("Couldn't load the media of a NetStream: {}", error);
+        StreamManager::ensure_paused(context, self);
+        self.trigger_status_event(context, "NetStream.Play.StreamNotFound", "error");
     }
 
     pub fn bytes_loaded(self) -> usize {
@@ -128,23 +266,67 @@ impl<'gc> NetStream<'gc> {
     }
 
     pub fn bytes_total(self) -> usize {
-        self.0.read().buffer.len()
+        let read = self.0.read();
+        read.bytes_total.unwrap_or(read.buffer.len())
+    }
+
+    /// The position of the playhead, in seconds.
+    pub fn time(self) -> f64 {
+        self.0.read().time / 1000.0
+    }
+
+    pub fn buffer_time(self) -> f64 {
+        self.0.read().buffer_time
+    }
+
+    pub fn set_buffer_time(self, gc_context: MutationContext<'gc, '_>, buffer_time: f64) {
+        self.0.write(gc_context).buffer_time = buffer_time.max(0.0);
+    }
+
+    /// How many seconds of media were downloaded ahead of the playhead.
+    pub fn buffer_length(self) -> f64 {
+        let read = self.0.read();
+        let Some(mut offset) = read.offset else {
+            return 0.0;
+        };
+        let mut last_timestamp = None;
+        while let Some((tag, next_offset)) = FlvTag::read(&read.buffer, offset) {
+            last_timestamp = Some(tag.timestamp);
+            offset = next_offset;
+        }
+        last_timestamp.map_or(0.0, |timestamp| {
+            (f64::from(timestamp) - read.time).max(0.0) / 1000.0
+        })
+    }
+
+    /// The last decoded video frame, which attached `Video` objects display.
+    pub fn last_frame(self) -> Option<BitmapInfo> {
+        self.0.read().last_frame.clone()
     }
 
     /// Start playing media from this NetStream.
     ///
     /// If `name` is specified, this will also trigger streaming download of
     /// the given resource. Otherwise, the stream will play whatever data is
-    /// available in the buffer.
-    pub fn play(self, context: &mut UpdateContext<'_, 'gc>, name: Option<AvmString<'gc>>) {
+    /// available in the buffer. The resource is requested on behalf of `movie`.
+    pub fn play(
+        self,
+        context: &mut UpdateContext<'_, 'gc>,
+        name: Option<AvmString<'gc>>,
+        movie: Arc<SwfMovie>,
+    ) {
         if let Some(name) = name {
+            self.reset(context.gc_context);
             let request = Request::get(name.to_string());
-            context
-                .load_manager
-                .load_netstream(context.player.clone(), self, request);
+            let future =
+                context
+                    .load_manager
+                    .load_netstream(context.player.clone(), self, request, movie);
+            context.navigator.spawn_future(future);
         }
 
         StreamManager::ensure_playing(context, self);
+        self.trigger_status_event(context, "NetStream.Play.Start", "status");
     }
 
     /// Pause stream playback.
@@ -161,4 +343,275 @@ impl<'gc> NetStream<'gc> {
     pub fn toggle_paused(self, context: &mut UpdateContext<'_, 'gc>) {
         StreamManager::toggle_paused(context, self);
     }
+
+    /// Stops playback and forgets the downloaded media.
+    pub fn close(self, context: &mut UpdateContext<'_, 'gc>) {
+        StreamManager::ensure_paused(context, self);
+        self.reset(context.gc_context);
+    }
+
+    /// Moves the playhead to `time`, in seconds.
+    ///
+    /// The video is decoded from the start of the stream, so that the frame
+    /// shown is correct whatever keyframes the video has. Cue points that are
+    /// skipped over aren't triggered.
+    pub fn seek(self, context: &mut UpdateContext<'_, 'gc>, time: f64) {
+        let target = (time * 1000.0).max(0.0);
+        {
+            let mut write = self.0.write(context.gc_context);
+            let Some(header_offset) = FlvHeader::read(&write.buffer).ok().flatten() else {
+                return;
+            };
+            write.offset = Some(header_offset.data_offset);
+            write.time = target;
+            write.next_frame_id = 0;
+            write.last_frame = None;
+        }
+        self.play_tags(context, false);
+        self.trigger_status_event(context, "NetStream.Seek.Notify", "status");
+    }
+
+    fn reset(self, gc_context: MutationContext<'gc, '_>) {
+        let mut write = self.0.write(gc_context);
+        write.buffer.clear();
+        write.bytes_total = None;
+        write.offset = None;
+        write.time = 0.0;
+        write.buffering = true;
+        write.next_frame_id = 0;
+        write.last_frame = None;
+    }
+
+    /// Advances the playhead by `dt` milliseconds, playing the tags it passes.
+    fn tick(self, context: &mut UpdateContext<'_, 'gc>, dt: f64) {
+        let mut write = self.0.write(context.gc_context);
+        if write.offset.is_none() {
+            match FlvHeader::read(&write.buffer) {
+                Ok(Some(header)) => write.offset = Some(header.data_offset),
+                Ok(None) if write.bytes_total.is_none() => return,
+                Ok(None) | Err(_) => {
+                    drop(write);
+                    tracing::warn!("NetStream media isn't an FLV file");
+                    StreamManager::ensure_paused(context, self);
+                    self.trigger_status_event(context, "NetStream.Play.StreamNotFound", "error");
+                    return;
+                }
+            }
+        }
+
+        if write.buffering {
+            let buffer_time = write.buffer_time;
+            let is_loaded = write.bytes_total.is_some();
+            drop(write);
+            if !is_loaded && self.buffer_length() < buffer_time {
+                return;
+            }
+            self.0.write(context.gc_context).buffering = false;
+            self.trigger_status_event(context, "NetStream.Buffer.Full", "status");
+        } else {
+            drop(write);
+        }
+
+        self.0.write(context.gc_context).time += dt;
+        let reached_end = self.play_tags(context, true);
+
+        if reached_end {
+            let is_loaded = self.0.read().bytes_total.is_some();
+            if is_loaded {
+                StreamManager::ensure_paused(context, self);
+                self.trigger_status_event(context, "NetStream.Play.Stop", "status");
+            } else {
+                self.0.write(context.gc_context).buffering = true;
+            }
+            self.trigger_status_event(context, "NetStream.Buffer.Empty", "status");
+        }
+    }
+
+    /// Plays the tags up to the playhead. Returns whether all downloaded tags were played.
+    fn play_tags(self, context: &mut UpdateContext<'_, 'gc>, run_scripts: bool) -> bool {
+        loop {
+            let tag = {
+                let mut write = self.0.write(context.gc_context);
+                let Some(offset) = write.offset else {
+                    return true;
+                };
+                let Some((tag, next_offset)) = FlvTag::read(&write.buffer, offset) else {
+                    return true;
+                };
+                if f64::from(tag.timestamp) > write.time {
+                    return false;
+                }
+                let tag = match tag.data {
+                    FlvTagData::Video {
+                        frame_type,
+                        codec,
+                        data,
+                    } => StreamTag::Video {
+                        frame_type,
+                        codec,
+                        data: data.to_vec(),
+                    },
+                    FlvTagData::Script { name, value } => StreamTag::Script { name, value },
+                    FlvTagData::Audio(_) | FlvTagData::Unknown => StreamTag::Other,
+                };
+                write.offset = Some(next_offset);
+                tag
+            };
+
+            match tag {
+                StreamTag::Video {
+                    frame_type,
+                    codec: Some(codec),
+                    data,
+                } if frame_type != FrameType::Other => self.decode_frame(context, codec, &data),
+                StreamTag::Script { name, value } if run_scripts => {
+                    self.run_script_tag(context, &name, &value)
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn decode_frame(self, context: &mut UpdateContext<'_, 'gc>, codec: VideoCodec, data: &[u8]) {
+        let mut write = self.0.write(context.gc_context);
+        let handle = match write.video_stream {
+            Some((handle, stream_codec)) if stream_codec == codec => handle,
+            _ => {
+                let size = write.video_size.unwrap_or_default();
+                match context.video.register_video_stream(
+                    0,
+                    size,
+                    codec,
+                    VideoDeblocking::UseVideoPacketValue,
+                ) {
+                    Ok(handle) => {
+                        write.video_stream = Some((handle, codec));
+                        handle
+                    }
+                    Err(e) => {
+                        tracing::warn!("Couldn't play NetStream video: {}", e);
+                        return;
+                    }
+                }
+            }
+        };
+
+        let frame_id = write.next_frame_id;
+        write.next_frame_id += 1;
+        let frame = || EncodedFrame {
+            codec,
+            data,
+            frame_id,
+        };
+        let result = match context.video.preload_video_stream_frame(handle, frame()) {
+            Ok(_) => context
+                .video
+                .decode_video_stream_frame(handle, frame(), context.renderer),
+            Err(e) => Err(e),
+        };
+        match result {
+            Ok(bitmap) => write.last_frame = Some(bitmap),
+            Err(e) => tracing::warn!("Couldn't decode NetStream video frame: {}", e),
+        }
+    }
+
+    /// Calls the handler that a script tag is for, such as `onMetaData` or `onCuePoint`.
+    fn run_script_tag(self, context: &mut UpdateContext<'_, 'gc>, name: &str, value: &AmfValue) {
+        if name == "onMetaData" {
+            let width = metadata_number(value, "width");
+            let height = metadata_number(value, "height");
+            if let (Some(width), Some(height)) = (width, height) {
+                self.0.write(context.gc_context).video_size = Some((width as u16, height as u16));
+            }
+        }
+
+        let Some(AvmObject::Avm1(object)) = self.0.read().avm_object else {
+            return;
+        };
+        let name = AvmString::new_utf8(context.gc_context, name);
+        self.call_avm1_handler(context, object, name, |activation| {
+            Ok(vec![amf_to_avm1_object(activation, value)])
+        });
+    }
+
+    /// Calls `onStatus` with an info object describing a change of the stream.
+    fn trigger_status_event(
+        self,
+        context: &mut UpdateContext<'_, 'gc>,
+        code: &'static str,
+        level: &'static str,
+    ) {
+        let Some(AvmObject::Avm1(object)) = self.0.read().avm_object else {
+            return;
+        };
+        self.call_avm1_handler(context, object, "onStatus".into(), |activation| {
+            let info = Avm1ScriptObject::new(
+                activation.context.gc_context,
+                Some(activation.context.avm1.prototypes().object),
+            );
+            info.set("code", code.into(), activation)?;
+            info.set("level", level.into(), activation)?;
+            Ok(vec![info.into()])
+        });
+    }
+
+    fn call_avm1_handler(
+        self,
+        context: &mut UpdateContext<'_, 'gc>,
+        object: Avm1Object<'gc>,
+        name: AvmString<'gc>,
+        args: impl FnOnce(&mut Avm1Activation<'_, 'gc>) -> Result<Vec<Avm1Value<'gc>>, Avm1Error<'gc>>,
+    ) {
+        let Some(mut activation) = Avm1Activation::try_from_stub(
+            context.reborrow(),
+            Avm1ActivationIdentifier::root("[NetStream]"),
+        ) else {
+            return;
+        };
+        let result = args(&mut activation).and_then(|args| {
+            object.call_method(name, &args, &mut activation, ExecutionReason::Special)
+        });
+        if let Err(e) = result {
+            tracing::error!("Error in NetStream.{}: {:?}", name, e);
+        }
+    }
+}
+
+/// Reads a number from the properties of an AMF object.
+fn metadata_number(value: &AmfValue, name: &str) -> Option<f64> {
+    let (AmfValue::ECMAArray(_, elements, _) | AmfValue::Object(elements, _)) = value else {
+        return None;
+    };
+    elements
+        .iter()
+        .find(|element| element.name() == name)
+        .and_then(|element| match element.value() {
+            AmfValue::Number(number) => Some(*number),
+            _ => None,
+        })
+}
+
+/// Converts the argument of a script tag, which FLV files store as an ECMA
+/// array, to the object that handlers are called with.
+fn amf_to_avm1_object<'gc>(
+    activation: &mut Avm1Activation<'_, 'gc>,
+    value: &AmfValue,
+) -> Avm1Value<'gc> {
+    use crate::avm1::globals::shared_object::deserialize_value;
+
+    let AmfValue::ECMAArray(_, elements, _) = value else {
+        return deserialize_value(activation, value);
+    };
+    let object = Avm1ScriptObject::new(
+        activation.context.gc_context,
+        Some(activation.context.avm1.prototypes().object),
+    );
+    for element in elements {
+        let property = deserialize_value(activation, element.value());
+        let name = AvmString::new_utf8(activation.context.gc_context, element.name());
+        if let Err(e) = object.set(name, property, activation) {
+            tracing::warn!("Couldn't set NetStream handler argument {}: {:?}", name, e);
+        }
+    }
+    object.into()
 }
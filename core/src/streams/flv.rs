@@ -0,0 +1,178 @@
+//! Reading FLV files, the container of the media played by `NetStream`.
+//!
+//! Tags are read one at a time from the downloaded part of the file, so that
+//! playback can start before the download finished.
+
+use flash_lso::amf0::read::AMF0Decoder;
+use flash_lso::types::Value as AmfValue;
+use swf::VideoCodec;
+
+/// The size of the tag header, which is followed by the tag data and the size of the tag.
+const TAG_HEADER_LEN: usize = 11;
+
+/// The header of an FLV file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FlvHeader {
+    pub has_audio: bool,
+    pub has_video: bool,
+
+    /// Where the first tag starts.
+    pub data_offset: usize,
+}
+
+impl FlvHeader {
+    /// Reads the header at the start of an FLV file.
+    ///
+    /// Returns `Ok(None)` if the header wasn't fully downloaded yet.
+    pub fn read(data: &[u8]) -> Result<Option<Self>, &'static str> {
+        if data.len() < 9 {
+            return Ok(None);
+        }
+        if &data[0..3] != b"FLV" {
+            return Err("Not an FLV file");
+        }
+        let flags = data[4];
+        let header_len = u32::from_be_bytes([data[5], data[6], data[7], data[8]]) as usize;
+        Ok(Some(Self {
+            has_audio: flags & 0x04 != 0,
+            has_video: flags & 0x01 != 0,
+            // The header is followed by the size of the previous tag, which is always 0.
+            data_offset: header_len + 4,
+        }))
+    }
+}
+
+/// The kind of frame that a video tag contains.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FrameType {
+    Keyframe,
+    Interframe,
+    DisposableInterframe,
+    Other,
+}
+
+/// The data of an FLV tag.
+#[derive(Debug)]
+pub enum FlvTagData<'a> {
+    Audio(&'a [u8]),
+
+    /// A video frame. The data is laid out the same as in the `VideoFrame` tags of SWF files.
+    Video {
+        frame_type: FrameType,
+        codec: Option<VideoCodec>,
+        data: &'a [u8],
+    },
+
+    /// A call to a handler of the `NetStream`, such as `onMetaData` or `onCuePoint`.
+    Script {
+        name: String,
+        value: AmfValue,
+    },
+
+    /// A tag that can't be played, such as an encrypted tag or a malformed script tag.
+    Unknown,
+}
+
+#[derive(Debug)]
+pub struct FlvTag<'a> {
+    /// When the tag is played, in milliseconds since the start of the stream.
+    pub timestamp: u32,
+    pub data: FlvTagData<'a>,
+}
+
+impl<'a> FlvTag<'a> {
+    /// Reads the tag at `offset`, returning it with the offset of the next tag.
+    ///
+    /// Returns `None` if the tag wasn't fully downloaded yet.
+    pub fn read(data: &'a [u8], offset: usize) -> Option<(Self, usize)> {
+        let header = data.get(offset..offset + TAG_HEADER_LEN)?;
+        let tag_type = header[0];
+        let data_len = u32::from_be_bytes([0, header[1], header[2], header[3]]) as usize;
+        let timestamp = u32::from_be_bytes([header[7], header[4], header[5], header[6]]);
+        let start = offset + TAG_HEADER_LEN;
+        let tag_data = data.get(start..start + data_len)?;
+        let next_offset = start + data_len + 4;
+
+        let data = match tag_type {
+            8 => FlvTagData::Audio(tag_data),
+            9 => read_video_data(tag_data),
+            18 => read_script_data(tag_data),
+            _ => FlvTagData::Unknown,
+        };
+        Some((Self { timestamp, data }, next_offset))
+    }
+}
+
+fn read_video_data(data: &[u8]) -> FlvTagData<'_> {
+    let Some((&flags, data)) = data.split_first() else {
+        return FlvTagData::Unknown;
+    };
+    let frame_type = match flags >> 4 {
+        1 => FrameType::Keyframe,
+        2 => FrameType::Interframe,
+        3 => FrameType::DisposableInterframe,
+        _ => FrameType::Other,
+    };
+    let codec = VideoCodec::from_u8(flags & 0x0f);
+    // FLV files start VP6 frames with the cropping of the frame, which SWF files don't have.
+    let data = match codec {
+        Some(VideoCodec::Vp6 | VideoCodec::Vp6WithAlpha) => data.get(1..).unwrap_or_default(),
+        _ => data,
+    };
+    FlvTagData::Video {
+        frame_type,
+        codec,
+        data,
+    }
+}
+
+fn read_script_data(data: &[u8]) -> FlvTagData<'_> {
+    let mut decoder = AMF0Decoder::default();
+    let Ok((rest, AmfValue::String(name))) = decoder.parse_single_element(data) else {
+        return FlvTagData::Unknown;
+    };
+    let value = match decoder.parse_single_element(rest) {
+        Ok((_, value)) => value,
+        Err(_) => AmfValue::Undefined,
+    };
+    FlvTagData::Script { name, value }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read_header_and_tags() {
+        let mut flv = b"FLV\x01\x05\x00\x00\x00\x09\x00\x00\x00\x00".to_vec();
+        // A keyframe of 2 bytes at 0x01000010 ms.
+        flv.extend_from_slice(&[9, 0, 0, 3, 0, 0, 0x10, 0x01, 0, 0, 0, 0x12, 0xab, 0xcd]);
+        flv.extend_from_slice(&[0, 0, 0, 14]);
+
+        let header = FlvHeader::read(&flv).unwrap().unwrap();
+        assert!(header.has_audio);
+        assert!(header.has_video);
+        assert_eq!(header.data_offset, 13);
+
+        let (tag, next_offset) = FlvTag::read(&flv, header.data_offset).unwrap();
+        assert_eq!(tag.timestamp, 0x01000010);
+        assert_eq!(next_offset, flv.len());
+        assert!(matches!(
+            tag.data,
+            FlvTagData::Video {
+                frame_type: FrameType::Keyframe,
+                codec: Some(VideoCodec::H263),
+                data: [0xab, 0xcd],
+            }
+        ));
+
+        // The tag isn't complete until its data is downloaded.
+        assert!(FlvTag::read(&flv[..flv.len() - 5], header.data_offset).is_none());
+    }
+
+    #[test]
+    fn reject_other_files() {
+        assert_eq!(FlvHeader::read(b"FL"), Ok(None));
+        assert!(FlvHeader::read(b"CWS\x0a\x00\x00\x00\x00\x00").is_err());
+    }
+}
@@ -9,7 +9,7 @@ use crate::string::{AvmAtom, AvmStringRepr};
 #[derive(Clone, Copy, Collect)]
 #[collect(no_drop)]
 enum Source<'gc> {
-    Owned(Gc<'gc, AvmStringRepr>),
+    Owned(Gc<'gc, AvmStringRepr<'gc>>),
     Static(&'static WStr),
 }
 
@@ -20,7 +20,10 @@ pub struct AvmString<'gc> {
 }
 
 impl<'gc> AvmString<'gc> {
-    pub(super) fn to_owned(self, gc_context: MutationContext<'gc, '_>) -> Gc<'gc, AvmStringRepr> {
+    pub(super) fn to_owned(
+        self,
+        gc_context: MutationContext<'gc, '_>,
+    ) -> Gc<'gc, AvmStringRepr<'gc>> {
         match self.source {
             Source::Owned(s) => s,
             Source::Static(s) => {
@@ -80,12 +83,37 @@ impl<'gc> AvmString<'gc> {
         } else if right.is_empty() {
             left
         } else {
-            let mut out = WString::from(left.as_wstr());
+            if let Source::Owned(left) = left.source {
+                if let Some(repr) = AvmStringRepr::try_append_inline(left, &right) {
+                    return Self {
+                        source: Source::Owned(Gc::allocate(gc_context, repr)),
+                    };
+                }
+            }
+
+            // Appending to a string that is itself a concatenation usually means
+            // that it's built in a loop, so leave room for appending to the result
+            // in place. Other strings are rarely appended to, and are kept exact.
+            let len = left.len() + right.len();
+            let capacity = if left.is_concatenated() {
+                len.saturating_mul(2).min(WStr::MAX_LEN)
+            } else {
+                len
+            };
+            let mut out = WString::with_capacity(capacity, left.is_wide() || right.is_wide());
+            out.push_str(&left);
             out.push_str(&right);
-            Self::new(gc_context, out)
+            let repr = AvmStringRepr::from_concatenation(out);
+            Self {
+                source: Source::Owned(Gc::allocate(gc_context, repr)),
+            }
         }
     }
 
+    fn is_concatenated(&self) -> bool {
+        matches!(self.source, Source::Owned(s) if s.is_concatenated())
+    }
+
     #[inline]
     pub fn ptr_eq(this: &Self, other: &Self) -> bool {
         std::ptr::eq(this.as_wstr(), other.as_wstr())
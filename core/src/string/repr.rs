@@ -1,23 +1,40 @@
 use std::cell::Cell;
 use std::ops::Deref;
 
-use gc_arena::Collect;
-use ruffle_wstr::{ptr as wptr, wstr_impl_traits, WStr, WString};
+use gc_arena::{Collect, Gc};
+use ruffle_wstr::{ptr as wptr, wstr_impl_traits, Units, WStr, WString};
 
 /// Internal representation of `AvmAtom`s and (owned) `AvmString`.
 ///
 /// Using this type directly is dangerous, as it can be used to violate
 /// the interning invariants.
 #[derive(Collect)]
-#[collect(require_static)]
-pub struct AvmStringRepr {
+#[collect(unsafe_drop)]
+pub struct AvmStringRepr<'gc> {
+    #[collect(require_static)]
     ptr: *mut (),
+    #[collect(require_static)]
     meta: wptr::WStrMetadata,
     // We abuse the 'is_wide' bit for interning.
+    #[collect(require_static)]
     capacity: Cell<wptr::WStrMetadata>,
+
+    /// The string owning the buffer of this string, if this string was built
+    /// by appending to that buffer in place.
+    owner: Option<Gc<'gc, Self>>,
+
+    /// How many units of the buffer are used, by this string and by the strings
+    /// appended to it in place. Only meaningful for strings owning their buffer.
+    #[collect(require_static)]
+    units_used: Cell<u32>,
+
+    /// Whether this string is the result of a concatenation, which hints that
+    /// it's being built by appending to it repeatedly.
+    #[collect(require_static)]
+    concatenated: bool,
 }
 
-impl AvmStringRepr {
+impl<'gc> AvmStringRepr<'gc> {
     pub fn from_raw(s: WString, interned: bool) -> Self {
         let (ptr, meta, cap) = s.into_raw_parts();
         let capacity = Cell::new(wptr::WStrMetadata::new32(cap, interned));
@@ -25,15 +42,80 @@ impl AvmStringRepr {
             ptr,
             meta,
             capacity,
+            owner: None,
+            units_used: Cell::new(meta.len32()),
+            concatenated: false,
         }
     }
 
+    /// Creates the result of a concatenation, whose buffer may have room for appending to it.
+    pub fn from_concatenation(s: WString) -> Self {
+        let mut repr = Self::from_raw(s, false);
+        repr.concatenated = true;
+        repr
+    }
+
+    /// Appends `right` to `left` without copying `left`, by writing `right`
+    /// into the spare capacity of the buffer that `left` is in.
+    ///
+    /// This only works once for each string: afterwards, the units following
+    /// `left` in the buffer are used. This is still enough to make repeatedly
+    /// appending to the same string amortized linear.
+    pub fn try_append_inline(left: Gc<'gc, Self>, right: &WStr) -> Option<Self> {
+        let owner = left.owner.unwrap_or(left);
+        let used = owner.units_used.get() as usize;
+        let new_len = left.len() + right.len();
+        let is_wide = owner.meta.is_wide();
+
+        if left.len() != used
+            || new_len > owner.capacity.get().len()
+            || (right.is_wide() && !is_wide)
+        {
+            return None;
+        }
+
+        // SAFETY: the units from `used` to `new_len` are in the capacity of the buffer,
+        // and no string refers to them yet, as `left` ends at the used units.
+        unsafe {
+            match right.units() {
+                Units::Bytes(units) if !is_wide => {
+                    let dest = (owner.ptr as *mut u8).add(used);
+                    std::ptr::copy_nonoverlapping(units.as_ptr(), dest, units.len());
+                }
+                Units::Bytes(units) => {
+                    let dest = (owner.ptr as *mut u16).add(used);
+                    for (i, unit) in units.iter().enumerate() {
+                        dest.add(i).write(u16::from(*unit));
+                    }
+                }
+                Units::Wide(units) => {
+                    let dest = (owner.ptr as *mut u16).add(used);
+                    std::ptr::copy_nonoverlapping(units.as_ptr(), dest, units.len());
+                }
+            }
+        }
+        owner.units_used.set(new_len as u32);
+
+        Some(Self {
+            ptr: owner.ptr,
+            meta: wptr::WStrMetadata::new32(new_len as u32, is_wide),
+            capacity: Cell::new(wptr::WStrMetadata::new32(0, false)),
+            owner: Some(owner),
+            units_used: Cell::new(0),
+            concatenated: true,
+        })
+    }
+
     #[inline]
     pub fn as_wstr(&self) -> &WStr {
-        // SAFETY: we own a `WString`.
+        // SAFETY: we own a `WString`, or the string we share a buffer with does.
         unsafe { &*wptr::from_raw_parts(self.ptr, self.meta) }
     }
 
+    pub fn is_concatenated(&self) -> bool {
+        self.concatenated
+    }
+
     pub fn is_interned(&self) -> bool {
         self.capacity.get().is_wide()
     }
@@ -45,8 +127,13 @@ impl AvmStringRepr {
     }
 }
 
-impl Drop for AvmStringRepr {
+impl<'gc> Drop for AvmStringRepr<'gc> {
     fn drop(&mut self) {
+        if self.owner.is_some() {
+            // The buffer is dropped by its owner.
+            return;
+        }
+
         // SAFETY: we drop the `WString` we logically own.
         unsafe {
             let cap = self.capacity.get().len32();
@@ -55,7 +142,7 @@ impl Drop for AvmStringRepr {
     }
 }
 
-impl Deref for AvmStringRepr {
+impl<'gc> Deref for AvmStringRepr<'gc> {
     type Target = WStr;
     #[inline]
     fn deref(&self) -> &WStr {
@@ -63,11 +150,117 @@ impl Deref for AvmStringRepr {
     }
 }
 
-impl Default for AvmStringRepr {
+impl<'gc> Default for AvmStringRepr<'gc> {
     #[inline]
     fn default() -> Self {
         Self::from_raw(WString::new(), false)
     }
 }
 
-wstr_impl_traits!(impl for AvmStringRepr);
+wstr_impl_traits!(impl['gc] for AvmStringRepr<'gc>);
+
+#[cfg(test)]
+mod tests {
+    use super::AvmStringRepr;
+    use crate::string::{AvmString, AvmStringInterner, WStr, WString};
+    use gc_arena::{rootless_arena, Gc};
+
+    #[test]
+    fn append_in_place() {
+        rootless_arena(|mc| {
+            let mut strings = vec![AvmString::from("a")];
+            for _ in 0..20 {
+                let last = *strings.last().unwrap();
+                strings.push(AvmString::concat(mc, last, "b".into()));
+            }
+
+            // Appending something else to a string that was already appended to
+            // can't reuse its buffer, and mustn't overwrite the other strings.
+            let x = AvmString::concat(mc, strings[10], "x".into());
+            let y = AvmString::concat(mc, strings[10], "y".into());
+            assert_eq!(x.to_utf8_lossy(), format!("a{}x", "b".repeat(10)));
+            assert_eq!(y.to_utf8_lossy(), format!("a{}y", "b".repeat(10)));
+
+            for (i, s) in strings.iter().enumerate() {
+                assert_eq!(s.to_utf8_lossy(), format!("a{}", "b".repeat(i)));
+            }
+        });
+    }
+
+    #[test]
+    fn append_wide_and_narrow() {
+        rootless_arena(|mc| {
+            let narrow = AvmString::concat(mc, "ab".into(), "cd".into());
+            let narrow = AvmString::concat(mc, narrow, "ef".into());
+
+            // A narrow buffer can't hold wide units.
+            let wide = AvmString::concat(mc, narrow, AvmString::new_utf8(mc, "λ"));
+            assert!(!narrow.is_wide());
+            assert!(wide.is_wide());
+
+            // A wide buffer can hold narrow units.
+            let wider = AvmString::concat(mc, wide, "gh".into());
+            assert!(wider.is_wide());
+
+            // The narrow buffer is still free after `narrow`.
+            let narrower = AvmString::concat(mc, narrow, "ij".into());
+            assert!(!narrower.is_wide());
+
+            assert_eq!(narrow.to_utf8_lossy(), "abcdef");
+            assert_eq!(wide.to_utf8_lossy(), "abcdefλ");
+            assert_eq!(wider.to_utf8_lossy(), "abcdefλgh");
+            assert_eq!(narrower.to_utf8_lossy(), "abcdefij");
+        });
+    }
+
+    #[test]
+    fn intern_appended_strings() {
+        rootless_arena(|mc| {
+            let mut interner = AvmStringInterner::new();
+            let base = AvmString::concat(mc, "ab".into(), "cd".into());
+            let base = AvmString::concat(mc, base, "ef".into());
+            let appended = AvmString::concat(mc, base, "gh".into());
+
+            let base_atom = interner.intern(mc, base);
+            let atom = interner.intern(mc, appended);
+            assert_eq!(
+                atom,
+                interner.intern_wstr(mc, WStr::from_units(b"abcdefgh"))
+            );
+
+            // Interned strings can still be appended to, without changing them.
+            let more = AvmString::concat(mc, appended, "ij".into());
+            assert_eq!(more.to_utf8_lossy(), "abcdefghij");
+            assert_eq!(base_atom.to_string(), "abcdef");
+            assert_eq!(atom.to_string(), "abcdefgh");
+            assert_eq!(interner.get(mc, WStr::from_units(b"abcdefgh")), Some(atom));
+            assert_eq!(interner.get(mc, WStr::from_units(b"abcdefghij")), None);
+        });
+    }
+
+    #[test]
+    fn drop_shared_buffer() {
+        rootless_arena(|mc| {
+            let mut buffer = WString::with_capacity(8, false);
+            buffer.push_str(WStr::from_units(b"ab"));
+            let owner = Gc::allocate(mc, AvmStringRepr::from_concatenation(buffer));
+
+            let appended = Gc::allocate(
+                mc,
+                AvmStringRepr::try_append_inline(owner, WStr::from_units(b"cd")).unwrap(),
+            );
+
+            // Strings sharing the buffer of their owner don't free it.
+            let dropped =
+                AvmStringRepr::try_append_inline(appended, WStr::from_units(b"ef")).unwrap();
+            assert_eq!(dropped.to_utf8_lossy(), "abcdef");
+            drop(dropped);
+            assert_eq!(owner.to_utf8_lossy(), "ab");
+            assert_eq!(appended.to_utf8_lossy(), "abcd");
+
+            // The units of the dropped string stay used.
+            assert!(AvmStringRepr::try_append_inline(appended, WStr::from_units(b"gh")).is_none());
+            // `owner` and `appended` are freed along with the arena, in any order.
+        });
+    }
+}
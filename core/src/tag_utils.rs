@@ -1,5 +1,8 @@
 use gc_arena::Collect;
+use sha2::{Digest, Sha256};
 use std::sync::Arc;
+use std::time::Duration;
+use swf::extensions::ReadSwfExt;
 use swf::{CharacterId, Fixed8, HeaderExt, Rectangle, TagCode, Twips};
 use thiserror::Error;
 
@@ -24,6 +27,9 @@ pub enum Error {
     InvalidSwfUrl,
 }
 
+/// The MIME type of SWF files.
+pub const SWF_CONTENT_TYPE: &str = "application/x-shockwave-flash";
+
 /// Whether or not to end tag decoding.
 pub enum ControlFlow {
     /// Stop decoding after this tag.
@@ -62,6 +68,12 @@ pub struct SwfMovie {
 
     /// The compressed length of the entire datastream
     compressed_len: usize,
+
+    /// The SHA-256 hash of the SWF file, as it was loaded.
+    sha256: Option<[u8; 32]>,
+
+    /// The MIME type of the loaded content, which is an image type for loaded images.
+    content_type: &'static str,
 }
 
 impl SwfMovie {
@@ -75,6 +87,8 @@ impl SwfMovie {
             parameters: Vec::new(),
             encoding: swf::UTF_8,
             compressed_len: 0,
+            sha256: None,
+            content_type: SWF_CONTENT_TYPE,
         }
     }
 
@@ -109,11 +123,13 @@ impl SwfMovie {
             parameters: Vec::new(),
             encoding,
             compressed_len,
+            sha256: Some(Sha256::digest(swf_data).into()),
+            content_type: SWF_CONTENT_TYPE,
         })
     }
 
     /// Construct a movie based on a loaded image (JPEG, GIF or PNG).
-    pub fn from_loaded_image(url: String, length: usize) -> Self {
+    pub fn from_loaded_image(url: String, length: usize, content_type: &'static str) -> Self {
         Self {
             header: HeaderExt::default_with_uncompressed_len(length as u32),
             data: vec![],
@@ -122,6 +138,8 @@ impl SwfMovie {
             parameters: Vec::new(),
             encoding: swf::UTF_8,
             compressed_len: length,
+            sha256: None,
+            content_type,
         }
     }
 
@@ -178,10 +196,27 @@ impl SwfMovie {
         self.parameters.extend(params);
     }
 
+    /// The MIME type of the loaded content, such as `application/x-shockwave-flash`.
+    pub fn content_type(&self) -> &'static str {
+        self.content_type
+    }
+
+    /// Whether this is an actual SWF, rather than a loaded image.
+    pub fn is_swf(&self) -> bool {
+        self.content_type == SWF_CONTENT_TYPE
+    }
+
     pub fn compressed_len(&self) -> usize {
         self.compressed_len
     }
 
+    /// The SHA-256 hash of the SWF file, before it was decompressed.
+    ///
+    /// This is `None` for movies that weren't loaded from a SWF file.
+    pub fn sha256(&self) -> Option<&[u8; 32]> {
+        self.sha256.as_ref()
+    }
+
     pub fn uncompressed_len(&self) -> u32 {
         self.header.uncompressed_len()
     }
@@ -201,6 +236,72 @@ impl SwfMovie {
     pub fn frame_rate(&self) -> Fixed8 {
         self.header.frame_rate()
     }
+
+    /// The XMP metadata stored in the `Metadata` tag of this movie, if any.
+    pub fn metadata(&self) -> Option<String> {
+        let mut reader = SwfStream::new(&self.data, self.version());
+        let mut metadata = None;
+        let _ = decode_tags(&mut reader, |reader, tag_code, _tag_len| match tag_code {
+            TagCode::Metadata => {
+                metadata = Some(reader.read_str()?.to_string_lossy(self.encoding));
+                Ok(ControlFlow::Exit)
+            }
+            // The metadata comes before the content of the first frame.
+            TagCode::ShowFrame | TagCode::End => Ok(ControlFlow::Exit),
+            _ => Ok(ControlFlow::Continue),
+        });
+        metadata
+    }
+
+    /// Summarizes the properties of this movie.
+    pub fn info(&self) -> MovieInfo {
+        MovieInfo {
+            width: self.width().to_pixels(),
+            height: self.height().to_pixels(),
+            frame_rate: self.frame_rate().to_f32(),
+            num_frames: self.num_frames(),
+            swf_version: self.version(),
+            is_action_script_3: self.is_action_script_3(),
+            metadata: self.metadata(),
+        }
+    }
+}
+
+/// The properties of a movie, as shown to users and catalogs.
+#[derive(Clone, Debug)]
+pub struct MovieInfo {
+    /// The width of the stage in pixels.
+    pub width: f64,
+
+    /// The height of the stage in pixels.
+    pub height: f64,
+
+    pub frame_rate: f32,
+
+    /// The number of frames on the root timeline.
+    pub num_frames: u16,
+
+    pub swf_version: u8,
+
+    pub is_action_script_3: bool,
+
+    /// The XMP metadata of the movie, from its `Metadata` tag.
+    pub metadata: Option<String>,
+}
+
+impl MovieInfo {
+    /// Estimates the duration of the movie, assuming its root timeline plays each frame once.
+    ///
+    /// This is only accurate for linear animations: scripts may loop or stop the timeline.
+    pub fn duration(&self) -> Option<Duration> {
+        if self.frame_rate > 0.0 {
+            Some(Duration::from_secs_f64(
+                f64::from(self.num_frames) / f64::from(self.frame_rate),
+            ))
+        } else {
+            None
+        }
+    }
 }
 
 /// A shared-ownership reference to some portion of an SWF datastream.
@@ -0,0 +1,219 @@
+//! A panel listing the display list with the properties of each object, to
+//! help understand and report rendering and timeline bugs.
+//!
+//! The selected movie clip can be stepped through frame by frame, and the
+//! selected and hovered objects are outlined on the stage. Like the
+//! performance overlay, the panel is drawn with the renderer.
+
+use crate::context::{RenderContext, UpdateContext};
+use crate::display_object::{DisplayObject, Stage, TDisplayObject, TDisplayObjectContainer};
+use crate::perf_hud::render_panel;
+use crate::string::WString;
+use ruffle_render::commands::CommandHandler;
+use ruffle_render::matrix::Matrix;
+use swf::{BlendMode, Color, Rectangle, Twips};
+
+/// How many objects are listed at once, around the selected one.
+const MAX_LINES: usize = 30;
+
+/// The width of the outlines drawn around objects, in pixels.
+const OUTLINE_WIDTH: f32 = 2.0;
+
+const SELECTED_COLOR: Color = Color::from_rgb(0x00c0ff, 255);
+const HOVERED_COLOR: Color = Color::from_rgb(0xff40c0, 255);
+
+/// An object of the display list, with how deep it is nested.
+struct Entry<'gc> {
+    object: DisplayObject<'gc>,
+    level: usize,
+}
+
+#[derive(Default)]
+pub struct TimelineInspector {
+    /// The index of the selected object in the display list, in render order.
+    selected: usize,
+}
+
+impl TimelineInspector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Lists the objects on the stage in render order, children after their parent.
+    fn entries(stage: Stage<'_>) -> Vec<Entry<'_>> {
+        fn add_children<'gc>(
+            object: DisplayObject<'gc>,
+            level: usize,
+            entries: &mut Vec<Entry<'gc>>,
+        ) {
+            let Some(container) = object.as_container() else {
+                return;
+            };
+            for child in container.iter_render_list() {
+                entries.push(Entry {
+                    object: child,
+                    level,
+                });
+                add_children(child, level + 1, entries);
+            }
+        }
+
+        let mut entries = Vec::new();
+        add_children(stage.into(), 0, &mut entries);
+        entries
+    }
+
+    /// The selected object, if the stage isn't empty.
+    pub(crate) fn selected_object<'gc>(&self, stage: Stage<'gc>) -> Option<DisplayObject<'gc>> {
+        let entries = Self::entries(stage);
+        let index = self.selected.min(entries.len().checked_sub(1)?);
+        Some(entries[index].object)
+    }
+
+    /// Moves the selection by `delta` objects.
+    pub(crate) fn move_selection(&mut self, stage: Stage<'_>, delta: isize) {
+        let last = Self::entries(stage).len().saturating_sub(1);
+        let selected = self.selected.min(last) as isize + delta;
+        self.selected = selected.clamp(0, last as isize) as usize;
+    }
+
+    /// Moves the selected movie clip `delta` frames forward or back, and stops it there.
+    pub(crate) fn step_frame(&self, context: &mut UpdateContext<'_, '_>, delta: i32) {
+        let Some(clip) = self
+            .selected_object(context.stage)
+            .and_then(|object| object.as_movie_clip())
+        else {
+            return;
+        };
+        let frame = (i32::from(clip.current_frame()) + delta)
+            .clamp(1, i32::from(clip.total_frames().max(1)));
+        clip.goto_frame(context, frame as u16, true);
+    }
+
+    /// Renders the panel at `y` of the viewport, and outlines the selected and hovered objects.
+    pub(crate) fn render<'gc>(
+        &self,
+        context: &mut RenderContext<'_, 'gc>,
+        hovered: Option<DisplayObject<'gc>>,
+        y: f64,
+    ) {
+        let stage = context.stage;
+        let entries = Self::entries(stage);
+        let selected = self.selected.min(entries.len().saturating_sub(1));
+        let selected_object = entries.get(selected).map(|entry| entry.object);
+
+        if let Some(object) = hovered {
+            outline(context, stage, object, HOVERED_COLOR);
+        }
+        if let Some(object) = selected_object {
+            outline(context, stage, object, SELECTED_COLOR);
+        }
+
+        let first = selected
+            .saturating_sub(MAX_LINES / 2)
+            .min(entries.len().saturating_sub(MAX_LINES));
+        let mut lines = vec![WString::from_utf8(&format!(
+            "Display list: {} objects (Ctrl+Up/Down to select, Ctrl+Left/Right to step frames)",
+            entries.len()
+        ))];
+        for (index, entry) in entries.iter().enumerate().skip(first).take(MAX_LINES) {
+            let marker = if index == selected {
+                '>'
+            } else if hovered.map_or(false, |hovered| {
+                DisplayObject::ptr_eq(hovered, entry.object)
+            }) {
+                '*'
+            } else {
+                ' '
+            };
+            let line = format!(
+                "{marker} {}{}",
+                "  ".repeat(entry.level),
+                describe(entry.object)
+            );
+            lines.push(WString::from_utf8(&line));
+        }
+        render_panel(context, &lines, 0.0, y);
+    }
+}
+
+/// Summarizes the properties of an object on one line.
+fn describe(object: DisplayObject<'_>) -> String {
+    let name = object.name();
+    let mut line = format!(
+        "{} {} #{} at {}, {}",
+        type_name(object),
+        if name.is_empty() {
+            "(unnamed)".to_owned()
+        } else {
+            name.to_string()
+        },
+        object.id(),
+        object.x().to_pixels(),
+        object.y().to_pixels(),
+    );
+    if let Some(clip) = object.as_movie_clip() {
+        line.push_str(&format!(
+            ", frame {}/{}",
+            clip.current_frame(),
+            clip.total_frames()
+        ));
+        if !clip.playing() {
+            line.push_str(" (stopped)");
+        }
+    }
+    if !object.visible() {
+        line.push_str(", hidden");
+    }
+    if object.blend_mode() != BlendMode::Normal {
+        line.push_str(&format!(", blend {}", object.blend_mode()));
+    }
+    let num_filters = object.filters().len();
+    if num_filters > 0 {
+        line.push_str(&format!(", {num_filters} filters"));
+    }
+    line
+}
+
+fn type_name(object: DisplayObject<'_>) -> &'static str {
+    match object {
+        DisplayObject::Stage(_) => "Stage",
+        DisplayObject::Bitmap(_) => "Bitmap",
+        DisplayObject::Avm1Button(_) | DisplayObject::Avm2Button(_) => "Button",
+        DisplayObject::EditText(_) => "EditText",
+        DisplayObject::Graphic(_) => "Shape",
+        DisplayObject::MorphShape(_) => "MorphShape",
+        DisplayObject::MovieClip(_) => "MovieClip",
+        DisplayObject::Text(_) => "Text",
+        DisplayObject::Video(_) => "Video",
+        DisplayObject::LoaderDisplay(_) => "Loader",
+    }
+}
+
+/// Draws the outline of the bounds of `object` on the stage.
+fn outline<'gc>(
+    context: &mut RenderContext<'_, 'gc>,
+    stage: Stage<'gc>,
+    object: DisplayObject<'gc>,
+    color: Color,
+) {
+    let bounds: Rectangle<Twips> = stage.view_matrix() * object.world_bounds();
+    if !bounds.is_valid() {
+        return;
+    }
+    let (x, y) = (bounds.x_min, bounds.y_min);
+    let (width, height) = (
+        bounds.width().to_pixels() as f32,
+        bounds.height().to_pixels() as f32,
+    );
+    let right = bounds.x_max - Twips::from_pixels(OUTLINE_WIDTH.into());
+    let bottom = bounds.y_max - Twips::from_pixels(OUTLINE_WIDTH.into());
+    for matrix in [
+        Matrix::create_box(width, OUTLINE_WIDTH, 0.0, x, y),
+        Matrix::create_box(width, OUTLINE_WIDTH, 0.0, x, bottom),
+        Matrix::create_box(OUTLINE_WIDTH, height, 0.0, x, y),
+        Matrix::create_box(OUTLINE_WIDTH, height, 0.0, right, y),
+    ] {
+        context.commands.draw_rect(color.clone(), matrix);
+    }
+}
@@ -78,12 +78,16 @@ impl<'gc> Timers<'gc> {
             let expected_id = timer.id;
 
             let cancel_timer = match callback {
-                TimerCallback::Avm1Function { func, params } => {
-                    if let Some(level0) = level0 {
+                TimerCallback::Avm1Function {
+                    func,
+                    params,
+                    base_clip,
+                } => {
+                    if let Some(base_clip) = Self::avm1_base_clip(base_clip, level0) {
                         let mut avm1_activation = Activation::from_nothing(
                             context.reborrow(),
                             ActivationIdentifier::root("[Timer Callback]"),
-                            level0,
+                            base_clip,
                         );
                         let result = func.call(
                             "[Timer Callback]".into(),
@@ -105,6 +109,7 @@ impl<'gc> Timers<'gc> {
                     this,
                     method_name,
                     params,
+                    base_clip,
                 } => {
                     // If you add a timer onto a MovieClip and then remove the clip
                     // The timer should stop firing and be canceled
@@ -124,11 +129,11 @@ impl<'gc> Timers<'gc> {
                     }
 
                     if !removed {
-                        if let Some(level0) = level0 {
+                        if let Some(base_clip) = Self::avm1_base_clip(base_clip, level0) {
                             let mut avm1_activation = Activation::from_nothing(
                                 context.reborrow(),
                                 ActivationIdentifier::root("[Timer Callback]"),
-                                level0,
+                                base_clip,
                             );
                             let result = this.call_method(
                                 method_name,
@@ -196,8 +201,37 @@ impl<'gc> Timers<'gc> {
             .map(|timer| (timer.tick_time.wrapping_sub(cur_time)) as f64 / Self::TIMER_SCALE)
     }
 
-    /// The minimum interval we allow for timers.
-    const MIN_INTERVAL: i32 = 10;
+    /// The clip that an AVM1 timer callback runs in.
+    ///
+    /// This is the clip that created the timer, so that callbacks of loaded movies
+    /// run with the SWF version and globals of their own movie, unless it has since
+    /// been removed.
+    fn avm1_base_clip(
+        base_clip: DisplayObject<'gc>,
+        level0: Option<DisplayObject<'gc>>,
+    ) -> Option<DisplayObject<'gc>> {
+        if base_clip.avm1_removed() {
+            level0
+        } else {
+            Some(base_clip)
+        }
+    }
+
+    /// The minimum interval we allow for timers, in milliseconds.
+    pub const MIN_INTERVAL: f64 = 10.0;
+
+    /// The minimum interval of AVM1 timers created by a movie of the given SWF version.
+    ///
+    /// Flash Player 10 started clamping intervals to 10 milliseconds. Older movies can
+    /// use intervals as short as a millisecond, although timers still only tick a
+    /// limited number of times per update.
+    pub fn avm1_min_interval(swf_version: u8) -> f64 {
+        if swf_version >= 10 {
+            Self::MIN_INTERVAL
+        } else {
+            1.0
+        }
+    }
 
     /// The maximum timer ticks per call to `update_ticks`, for sanity.
     const MAX_TICKS: i32 = 10;
@@ -220,14 +254,23 @@ impl<'gc> Timers<'gc> {
     }
 
     /// Registers a new timer and returns the timer ID.
+    ///
+    /// The interval is in milliseconds, and may be fractional. It is clamped to
+    /// at least `min_interval` milliseconds.
+    ///
+    /// Timer IDs are shared by all movies, so that a movie can clear a timer created
+    /// by another one, as in Flash Player.
     pub fn add_timer(
         &mut self,
         callback: TimerCallback<'gc>,
-        interval: i32,
+        interval: f64,
+        min_interval: f64,
         is_timeout: bool,
     ) -> i32 {
         // SANITY: Set a minimum interval so we don't spam too much.
-        let interval = interval.max(Self::MIN_INTERVAL) as u64 * (Self::TIMER_SCALE as u64);
+        // This also replaces `NaN` with the minimum interval.
+        let interval = interval.max(min_interval).min(i32::MAX.into());
+        let interval = (interval * Self::TIMER_SCALE) as u64;
 
         self.timer_counter = self.timer_counter.wrapping_add(1);
         let id = self.timer_counter;
@@ -254,6 +297,39 @@ impl<'gc> Timers<'gc> {
         }
     }
 
+    /// The state of every active timer, as recorded by save states.
+    pub fn states(&self) -> Vec<TimerState<'gc>> {
+        self.timers
+            .iter()
+            .filter(|timer| timer.is_alive.get())
+            .map(|timer| TimerState {
+                id: timer.id,
+                callback: timer.callback.clone(),
+                interval: timer.interval as f64 / Self::TIMER_SCALE,
+                time_left: timer.tick_time.wrapping_sub(self.cur_time) as f64 / Self::TIMER_SCALE,
+                is_timeout: timer.is_timeout,
+            })
+            .collect()
+    }
+
+    /// Replaces every timer by the given ones, which keep their IDs.
+    pub fn restore(&mut self, states: Vec<TimerState<'gc>>) {
+        self.timers.clear();
+        for state in states {
+            self.timer_counter = self.timer_counter.max(state.id);
+            self.timers.push(Timer {
+                id: state.id,
+                callback: state.callback,
+                tick_time: self
+                    .cur_time
+                    .wrapping_add((state.time_left.max(0.0) * Self::TIMER_SCALE) as u64),
+                interval: (state.interval * Self::TIMER_SCALE) as u64,
+                is_timeout: state.is_timeout,
+                is_alive: std::cell::Cell::new(true),
+            });
+        }
+    }
+
     fn peek(&self) -> Option<&Timer<'gc>> {
         self.timers.peek()
     }
@@ -328,6 +404,20 @@ impl Ord for Timer<'_> {
     }
 }
 
+/// The state of an active timer, as recorded by save states.
+pub struct TimerState<'gc> {
+    pub id: i32,
+    pub callback: TimerCallback<'gc>,
+
+    /// The interval between timer ticks, in milliseconds.
+    pub interval: f64,
+
+    /// The time until the timer next ticks, in milliseconds.
+    pub time_left: f64,
+
+    pub is_timeout: bool,
+}
+
 /// A callback fired by a `setInterval`/`setTimeout` timer.
 #[derive(Clone, Collect)]
 #[collect(no_drop)]
@@ -336,12 +426,16 @@ pub enum TimerCallback<'gc> {
         func: Avm1Object<'gc>,
         /// The parameters to pass to the callback function.
         params: Vec<Avm1Value<'gc>>,
+        /// The clip whose code created the timer.
+        base_clip: DisplayObject<'gc>,
     },
 
     Avm1Method {
         this: Avm1Object<'gc>,
         method_name: AvmString<'gc>,
         params: Vec<Avm1Value<'gc>>,
+        /// The clip whose code created the timer.
+        base_clip: DisplayObject<'gc>,
     },
 
     Avm2Callback {
@@ -0,0 +1,14 @@
+dialog-title = Ruffle
+
+unsupported-content-title = Ruffle - Unsupported content
+unsupported-content-message =
+    The Ruffle emulator may not yet fully support all of ActionScript 3 used by this content.
+    Some parts of the content may not work as expected.
+
+    See the following link for more info:
+    https://github.com/ruffle-rs/ruffle/wiki/Frequently-Asked-Questions-For-Users
+
+download-failed-title = Ruffle - Load failed
+download-failed-message = Ruffle failed to open or download this file.
+
+script-timeout-title = Ruffle - Slow script
@@ -0,0 +1,14 @@
+dialog-title = Ruffle
+
+unsupported-content-title = Ruffle - Contenu non pris en charge
+unsupported-content-message =
+    L'émulateur Ruffle ne prend peut-être pas encore en charge tout l'ActionScript 3 utilisé par ce contenu.
+    Certaines parties du contenu pourraient ne pas fonctionner comme prévu.
+
+    Consultez le lien suivant pour plus d'informations :
+    https://github.com/ruffle-rs/ruffle/wiki/Frequently-Asked-Questions-For-Users
+
+download-failed-title = Ruffle - Échec du chargement
+download-failed-message = Ruffle n'a pas pu ouvrir ou télécharger ce fichier.
+
+script-timeout-title = Ruffle - Script lent
@@ -1,10 +1,11 @@
 use anyhow::{anyhow, Context, Error};
 use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
 use ruffle_core::backend::audio::{
-    swf, AudioBackend, AudioMixer, DecodeError, RegisterError, SoundHandle, SoundInstanceHandle,
-    SoundTransform,
+    swf, AudioBackend, AudioMixer, DecodeError, RegisterError, ResamplerQuality, SoundHandle,
+    SoundInstanceHandle, SoundTransform,
 };
 use ruffle_core::impl_audio_mixer_backend;
+use std::path::PathBuf;
 
 pub struct CpalAudioBackend {
     #[allow(dead_code)]
@@ -15,13 +16,53 @@ pub struct CpalAudioBackend {
     mixer: AudioMixer,
 }
 
+/// Returns the names of all audio output devices of the default host.
+pub fn output_device_names() -> Result<Vec<String>, Error> {
+    let host = cpal::default_host();
+    let devices = host
+        .output_devices()
+        .context("Failed to enumerate audio output devices")?;
+    Ok(devices.filter_map(|device| device.name().ok()).collect())
+}
+
+fn preference_path() -> Option<PathBuf> {
+    Some(dirs::data_local_dir()?.join("ruffle").join("audio_device"))
+}
+
+/// The name of the output device chosen in the settings, or `None` to use the default one.
+pub fn preferred_device() -> Option<String> {
+    let name = std::fs::read_to_string(preference_path()?).ok()?;
+    Some(name.trim().to_owned()).filter(|name| !name.is_empty())
+}
+
+/// Remembers the output device to play sound on from now on, or `None` for the default one.
+pub fn set_preferred_device(name: Option<&str>) -> Result<(), Error> {
+    let path = preference_path().context("Couldn't find a valid data_local dir")?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(&path, name.unwrap_or_default())
+        .with_context(|| format!("Couldn't write {}", path.display()))
+}
+
 impl CpalAudioBackend {
-    pub fn new() -> Result<Self, Error> {
+    /// Creates an audio backend playing to the output device with the given name,
+    /// or to the default output device if `device_name` is `None`.
+    pub fn new(
+        device_name: Option<&str>,
+        resampler_quality: ResamplerQuality,
+    ) -> Result<Self, Error> {
         // Create CPAL audio device.
         let host = cpal::default_host();
-        let device = host
-            .default_output_device()
-            .ok_or_else(|| anyhow!("No audio devices available"))?;
+        let device = if let Some(device_name) = device_name {
+            host.output_devices()
+                .context("Failed to enumerate audio output devices")?
+                .find(|device| device.name().map_or(false, |name| name == device_name))
+                .ok_or_else(|| anyhow!("No audio output device named \"{device_name}\""))?
+        } else {
+            host.default_output_device()
+                .ok_or_else(|| anyhow!("No audio devices available"))?
+        };
 
         // Create audio stream for device.
         let config = device
@@ -29,7 +70,8 @@ impl CpalAudioBackend {
             .context("Failed to get default output config")?;
         let sample_format = config.sample_format();
         let config = cpal::StreamConfig::from(config);
-        let mixer = AudioMixer::new(config.channels as u8, config.sample_rate.0);
+        let mut mixer = AudioMixer::new(config.channels as u8, config.sample_rate.0);
+        mixer.set_resampler_quality(resampler_quality);
 
         // Start the audio stream.
         let stream = {
@@ -0,0 +1,170 @@
+//! Capturing the cameras of the system, backing `flash.media.Camera`.
+//!
+//! Each camera captures on its own thread, as reading a frame blocks until the device
+//! has one. The thread keeps the newest frame, which the player takes every tick.
+
+use nokhwa::pixel_format::RgbFormat;
+use nokhwa::utils::{
+    ApiBackend, CameraFormat, CameraIndex, FrameFormat, RequestedFormat, RequestedFormatType,
+    Resolution,
+};
+use ruffle_core::backend::video_capture::{CaptureMode, CapturedFrame, VideoCaptureBackend};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread::JoinHandle;
+
+/// A camera being captured.
+struct Capture {
+    /// Tells the thread to close the camera.
+    stop: Arc<AtomicBool>,
+
+    /// The newest frame captured, if it wasn't taken yet.
+    frame: Arc<Mutex<Option<CapturedFrame>>>,
+
+    thread: JoinHandle<()>,
+}
+
+pub struct DesktopVideoCaptureBackend {
+    /// The names of the cameras, queried once as it can be slow.
+    devices: Vec<String>,
+
+    captures: HashMap<usize, Capture>,
+}
+
+impl DesktopVideoCaptureBackend {
+    pub fn new() -> Self {
+        let devices = match nokhwa::query(ApiBackend::Auto) {
+            Ok(devices) => devices.iter().map(|info| info.human_name()).collect(),
+            Err(e) => {
+                tracing::warn!("Couldn't list the cameras: {}", e);
+                Vec::new()
+            }
+        };
+        Self {
+            devices,
+            captures: HashMap::new(),
+        }
+    }
+}
+
+impl Drop for DesktopVideoCaptureBackend {
+    fn drop(&mut self) {
+        for index in self.captures.keys().copied().collect::<Vec<_>>() {
+            self.stop(index);
+        }
+    }
+}
+
+/// Opens a camera, and captures it until `stop` is set.
+///
+/// Whether the camera could be opened is sent through `opened`.
+fn capture(
+    index: usize,
+    mode: CaptureMode,
+    stop: Arc<AtomicBool>,
+    frame: Arc<Mutex<Option<CapturedFrame>>>,
+    opened: mpsc::Sender<bool>,
+) {
+    // The closest mode the camera supports is used.
+    let format =
+        RequestedFormat::new::<RgbFormat>(RequestedFormatType::Closest(CameraFormat::new(
+            Resolution::new(mode.width, mode.height),
+            FrameFormat::MJPEG,
+            mode.fps.round().max(1.0) as u32,
+        )));
+    let camera = nokhwa::Camera::new(CameraIndex::Index(index as u32), format)
+        .and_then(|mut camera| camera.open_stream().map(|()| camera));
+    let mut camera = match camera {
+        Ok(camera) => {
+            let _ = opened.send(true);
+            camera
+        }
+        Err(e) => {
+            tracing::error!("Couldn't open camera {}: {}", index, e);
+            let _ = opened.send(false);
+            return;
+        }
+    };
+
+    while !stop.load(Ordering::Relaxed) {
+        let image = match camera
+            .frame()
+            .and_then(|buffer| buffer.decode_image::<RgbFormat>())
+        {
+            Ok(image) => image,
+            Err(e) => {
+                tracing::error!("Couldn't capture camera {}: {}", index, e);
+                break;
+            }
+        };
+
+        let (width, height) = image.dimensions();
+        let mut rgba = Vec::with_capacity((width * height * 4) as usize);
+        for pixel in image.into_raw().chunks_exact(3) {
+            rgba.extend_from_slice(pixel);
+            rgba.push(255);
+        }
+        *frame.lock().expect("Frame lock is not poisoned") = Some(CapturedFrame {
+            width,
+            height,
+            rgba,
+        });
+    }
+
+    if let Err(e) = camera.stop_stream() {
+        tracing::warn!("Couldn't close camera {}: {}", index, e);
+    }
+}
+
+impl VideoCaptureBackend for DesktopVideoCaptureBackend {
+    fn devices(&self) -> Vec<String> {
+        self.devices.clone()
+    }
+
+    fn start(&mut self, index: usize, mode: CaptureMode) -> bool {
+        if index >= self.devices.len() {
+            return false;
+        }
+        self.stop(index);
+
+        let stop = Arc::new(AtomicBool::new(false));
+        let frame = Arc::new(Mutex::new(None));
+        let (opened_sender, opened) = mpsc::channel();
+        let thread = {
+            let stop = stop.clone();
+            let frame = frame.clone();
+            std::thread::spawn(move || capture(index, mode, stop, frame, opened_sender))
+        };
+
+        if !opened.recv().unwrap_or(false) {
+            let _ = thread.join();
+            return false;
+        }
+        self.captures.insert(
+            index,
+            Capture {
+                stop,
+                frame,
+                thread,
+            },
+        );
+        true
+    }
+
+    fn stop(&mut self, index: usize) {
+        if let Some(capture) = self.captures.remove(&index) {
+            capture.stop.store(true, Ordering::Relaxed);
+            let _ = capture.thread.join();
+        }
+    }
+
+    fn poll_frame(&mut self, index: usize) -> Option<CapturedFrame> {
+        self.captures
+            .get(&index)?
+            .frame
+            .lock()
+            .expect("Frame lock is not poisoned")
+            .take()
+    }
+}
@@ -7,4 +7,20 @@ pub enum RuffleEvent {
 
     /// Indicates that an asynchronous SWF metadata load has been completed.
     OnMetadata(ruffle_core::swf::HeaderExt),
+
+    /// Indicates that the movie asked to quit with the given exit code.
+    Exit(i32),
+
+    /// Indicates that the movie asked to open a panel of the settings.
+    ShowSettings(ruffle_core::backend::ui::SettingsPanel),
+
+    /// Indicates that a newer version of Ruffle is available.
+    UpdateAvailable(crate::update::Update),
+
+    /// Indicates that downloading an update finished, with the path it was
+    /// saved to or the reason it failed.
+    UpdateDownloaded(Result<std::path::PathBuf, String>),
+
+    /// Indicates that the file of the movie changed, and it should be reloaded.
+    MovieChanged,
 }
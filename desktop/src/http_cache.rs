@@ -0,0 +1,328 @@
+//! On-disk cache for HTTP responses.
+//!
+//! Responses are only cached if the server gave them an `ETag` or
+//! `Last-Modified` validator and didn't forbid storing them, and cached
+//! entries are always revalidated with a conditional request before they're used.
+//!
+//! The entries are indexed in memory, so that looking up validators and evicting
+//! entries never touches the disk. Reading and writing the cached files is done
+//! by a thread of the cache, so that it doesn't block the player.
+
+use futures::channel::oneshot;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fs;
+use std::future::Future;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{channel, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::SystemTime;
+
+const ETAG_PREFIX: &str = "etag: ";
+const LAST_MODIFIED_PREFIX: &str = "last-modified: ";
+
+/// The validators of a cached response, to send when revalidating it.
+#[derive(Clone)]
+pub struct Validators {
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+}
+
+/// A cached response, as known to the index.
+struct IndexEntry {
+    url: String,
+    validators: Validators,
+    len: u64,
+    last_used: SystemTime,
+}
+
+/// The entries of the cache by key, see `HttpCache::key`.
+#[derive(Default)]
+struct Index {
+    entries: HashMap<String, IndexEntry>,
+    total_size: u64,
+}
+
+impl Index {
+    fn insert(&mut self, key: String, entry: IndexEntry) {
+        self.total_size += entry.len;
+        if let Some(old) = self.entries.insert(key, entry) {
+            self.total_size -= old.len;
+        }
+    }
+
+    fn remove(&mut self, key: &str) -> bool {
+        match self.entries.remove(key) {
+            Some(old) => {
+                self.total_size -= old.len;
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// A disk operation, run by the thread of the cache.
+enum Task {
+    Read {
+        key: String,
+        sender: oneshot::Sender<Option<Vec<u8>>>,
+    },
+    Write {
+        key: String,
+        meta: String,
+        body: Vec<u8>,
+    },
+    Touch {
+        key: String,
+    },
+    Remove {
+        key: String,
+    },
+}
+
+pub struct HttpCache {
+    index: Arc<Mutex<Index>>,
+
+    /// The total size of cached bodies, in bytes, above which the least
+    /// recently used entries are removed.
+    max_size: u64,
+
+    tasks: Sender<Task>,
+}
+
+impl HttpCache {
+    pub fn new(dir: PathBuf, max_size: u64) -> io::Result<Self> {
+        fs::create_dir_all(&dir)?;
+        let index = Arc::new(Mutex::new(Index::default()));
+        let (tasks, receiver) = channel();
+        {
+            let index = index.clone();
+            thread::Builder::new()
+                .name("http cache".to_string())
+                .spawn(move || {
+                    // Entries are missed until the existing ones are indexed.
+                    load_index(&dir, &index);
+                    for task in receiver {
+                        run_task(&dir, task);
+                    }
+                })?;
+        }
+        Ok(Self {
+            index,
+            max_size,
+            tasks,
+        })
+    }
+
+    /// The cache directory used when none is given on the command line.
+    pub fn default_dir() -> Option<PathBuf> {
+        dirs::cache_dir().map(|dir| dir.join("ruffle").join("http"))
+    }
+
+    /// The name of the files of a URL, without extension.
+    fn key(url: &str) -> String {
+        Sha256::digest(url.as_bytes())
+            .iter()
+            .map(|b| format!("{b:02x}"))
+            .collect()
+    }
+
+    /// The validators of the cached response for a URL, if any.
+    pub fn validators(&self, url: &str) -> Option<Validators> {
+        let index = self.index.lock().expect("HTTP cache index is not poisoned");
+        let entry = index.entries.get(&Self::key(url))?;
+        // The URL is compared in case of hash collisions.
+        (entry.url == url).then(|| entry.validators.clone())
+    }
+
+    /// Reads the cached body for a URL, once it was revalidated, and marks it as
+    /// recently used.
+    pub fn read(&self, url: &str) -> impl Future<Output = Option<Vec<u8>>> {
+        let key = Self::key(url);
+        let (sender, receiver) = oneshot::channel();
+        if let Some(entry) = self
+            .index
+            .lock()
+            .expect("HTTP cache index is not poisoned")
+            .entries
+            .get_mut(&key)
+        {
+            entry.last_used = SystemTime::now();
+        }
+        let _ = self.tasks.send(Task::Touch { key: key.clone() });
+        let _ = self.tasks.send(Task::Read { key, sender });
+        async move { receiver.await.ok().flatten() }
+    }
+
+    pub fn put(&self, url: &str, body: Vec<u8>, validators: Validators) {
+        if validators.etag.is_none() && validators.last_modified.is_none() {
+            return;
+        }
+        let len = body.len() as u64;
+        if len > self.max_size {
+            return;
+        }
+
+        let mut meta = format!("{url}\n");
+        if let Some(etag) = &validators.etag {
+            meta.push_str(&format!("{ETAG_PREFIX}{etag}\n"));
+        }
+        if let Some(last_modified) = &validators.last_modified {
+            meta.push_str(&format!("{LAST_MODIFIED_PREFIX}{last_modified}\n"));
+        }
+
+        let key = Self::key(url);
+        let mut index = self.index.lock().expect("HTTP cache index is not poisoned");
+        index.insert(
+            key.clone(),
+            IndexEntry {
+                url: url.to_owned(),
+                validators,
+                len,
+                last_used: SystemTime::now(),
+            },
+        );
+        let _ = self.tasks.send(Task::Write { key, meta, body });
+
+        // Remove the least recently used entries until the cache fits in its size cap.
+        while index.total_size > self.max_size {
+            let Some(oldest) = index
+                .entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(key, _)| key.clone())
+            else {
+                break;
+            };
+            index.remove(&oldest);
+            let _ = self.tasks.send(Task::Remove { key: oldest });
+        }
+    }
+
+    /// Removes the cached response for a URL, if any.
+    pub fn remove(&self, url: &str) {
+        let key = Self::key(url);
+        let removed = self
+            .index
+            .lock()
+            .expect("HTTP cache index is not poisoned")
+            .remove(&key);
+        if removed {
+            let _ = self.tasks.send(Task::Remove { key });
+        }
+    }
+}
+
+/// Whether a response may be stored, according to its `Cache-Control` header.
+///
+/// This is a private cache, but it persists across sessions on disk, so responses
+/// marked `private` aren't stored either.
+pub fn may_store(cache_control: Option<&str>) -> bool {
+    cache_control.map_or(true, |cache_control| {
+        !cache_control.split(',').any(|directive| {
+            let name = directive.split('=').next().unwrap_or_default().trim();
+            name.eq_ignore_ascii_case("no-store") || name.eq_ignore_ascii_case("private")
+        })
+    })
+}
+
+/// Indexes the entries already in the cache directory.
+fn load_index(dir: &Path, index: &Mutex<Index>) {
+    let Ok(files) = fs::read_dir(dir) else {
+        return;
+    };
+    for file in files.filter_map(Result::ok) {
+        let path = file.path();
+        if path.extension().map_or(true, |ext| ext != "meta") {
+            continue;
+        }
+        let (Some(key), Ok(meta), Ok(body_metadata)) = (
+            path.file_stem().and_then(|stem| stem.to_str()),
+            fs::read_to_string(&path),
+            fs::metadata(path.with_extension("body")),
+        ) else {
+            continue;
+        };
+
+        let mut lines = meta.lines();
+        let Some(url) = lines.next() else {
+            continue;
+        };
+        let mut validators = Validators {
+            etag: None,
+            last_modified: None,
+        };
+        for line in lines {
+            if let Some(etag) = line.strip_prefix(ETAG_PREFIX) {
+                validators.etag = Some(etag.to_owned());
+            } else if let Some(last_modified) = line.strip_prefix(LAST_MODIFIED_PREFIX) {
+                validators.last_modified = Some(last_modified.to_owned());
+            }
+        }
+
+        // The modification time of an entry's metadata is used as its last use.
+        let last_used = file
+            .metadata()
+            .and_then(|meta| meta.modified())
+            .unwrap_or(SystemTime::UNIX_EPOCH);
+        let mut index = index.lock().expect("HTTP cache index is not poisoned");
+        // Entries put while indexing are newer.
+        if !index.entries.contains_key(key) {
+            index.insert(
+                key.to_owned(),
+                IndexEntry {
+                    url: url.to_owned(),
+                    validators,
+                    len: body_metadata.len(),
+                    last_used,
+                },
+            );
+        }
+    }
+}
+
+fn run_task(dir: &Path, task: Task) {
+    let body_path = |key: &str| dir.join(format!("{key}.body"));
+    let meta_path = |key: &str| dir.join(format!("{key}.meta"));
+    match task {
+        Task::Read { key, sender } => {
+            let _ = sender.send(fs::read(body_path(&key)).ok());
+        }
+        Task::Write { key, meta, body } => {
+            if let Err(e) =
+                fs::write(body_path(&key), body).and_then(|_| fs::write(meta_path(&key), meta))
+            {
+                tracing::warn!("Couldn't write to the HTTP cache: {}", e);
+            }
+        }
+        Task::Touch { key } => {
+            let path = meta_path(&key);
+            if let Ok(meta) = fs::read(&path) {
+                let _ = fs::write(path, meta);
+            }
+        }
+        Task::Remove { key } => {
+            let _ = fs::remove_file(meta_path(&key));
+            let _ = fs::remove_file(body_path(&key));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_may_store() {
+        assert!(may_store(None));
+        assert!(may_store(Some("max-age=3600, must-revalidate")));
+        assert!(may_store(Some("no-cache")));
+        assert!(!may_store(Some("no-store")));
+        assert!(!may_store(Some("max-age=0, No-Store")));
+        assert!(!may_store(Some("private")));
+        assert!(!may_store(Some("private=\"Set-Cookie\", max-age=60")));
+    }
+}
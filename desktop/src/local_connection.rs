@@ -0,0 +1,263 @@
+//! Delivers the messages of local connections between Ruffle processes.
+//!
+//! Each connection that a player listens to has a TCP listener on the loopback
+//! interface. Its port is written, along with a random token, to a file named after
+//! the connection in a directory that only the user may access. Senders connect to
+//! that port, write the token and the message and close the stream, so that other
+//! users of the machine can neither read nor forge messages.
+//!
+//! Messages are sent and read by short-lived threads, so that a process that doesn't
+//! answer never blocks the player.
+
+use ruffle_core::backend::local_connection::{LocalConnectionBackend, LocalConnectionMessage};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fs;
+use std::io::{ErrorKind, Read, Write};
+use std::net::{Ipv4Addr, SocketAddr, TcpListener, TcpStream};
+use std::path::PathBuf;
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::thread;
+use std::time::Duration;
+
+/// How long to wait for another process to accept or send a message.
+const TIMEOUT: Duration = Duration::from_millis(100);
+
+/// The largest message that is received, in bytes. Flash Player limits messages to 40 KiB.
+const MAX_MESSAGE_SIZE: u64 = 40 * 1024;
+
+/// The length of the tokens, in hexadecimal digits.
+const TOKEN_LENGTH: usize = 32;
+
+/// A connection that this player listens to.
+struct Listener {
+    listener: TcpListener,
+
+    /// The token that senders must write before their message.
+    token: String,
+}
+
+/// Where a connection listened to by another process can be reached.
+struct Endpoint {
+    port: u16,
+    token: String,
+}
+
+pub struct SocketLocalConnectionBackend {
+    directory: PathBuf,
+    listeners: HashMap<String, Listener>,
+    messages: Vec<LocalConnectionMessage>,
+
+    /// The messages read by the receiving threads.
+    received_sender: Sender<LocalConnectionMessage>,
+    received: Receiver<LocalConnectionMessage>,
+}
+
+impl SocketLocalConnectionBackend {
+    pub fn new() -> std::io::Result<Self> {
+        let directory = dirs::runtime_dir()
+            .map(|dir| dir.join("ruffle"))
+            .or_else(|| dirs::data_local_dir().map(|dir| dir.join("ruffle")))
+            .ok_or_else(|| std::io::Error::new(ErrorKind::NotFound, "No user directory"))?
+            .join("local-connections");
+        let mut builder = fs::DirBuilder::new();
+        builder.recursive(true);
+        #[cfg(unix)]
+        std::os::unix::fs::DirBuilderExt::mode(&mut builder, 0o700);
+        builder.create(&directory)?;
+
+        let (received_sender, received) = channel();
+        Ok(Self {
+            directory,
+            listeners: HashMap::new(),
+            messages: Vec::new(),
+            received_sender,
+            received,
+        })
+    }
+
+    /// The file that the port of a connection is written to. Connection names
+    /// may contain any character, so the file is named after their hash.
+    fn port_path(&self, connection_name: &str) -> PathBuf {
+        let name: String = Sha256::digest(connection_name.as_bytes())
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect();
+        self.directory.join(name)
+    }
+
+    /// Where the process listening to a connection can be reached, if any.
+    fn endpoint(&self, connection_name: &str) -> Option<Endpoint> {
+        let contents = fs::read_to_string(self.port_path(connection_name)).ok()?;
+        let (port, token) = contents.trim().split_once(' ')?;
+        Some(Endpoint {
+            port: port.parse().ok()?,
+            token: token.to_owned(),
+        })
+    }
+
+    /// Writes the port and token of a connection to its file, which only the user may read.
+    fn register(&self, connection_name: &str, port: u16, token: &str) -> std::io::Result<()> {
+        let mut options = fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+        options
+            .open(self.port_path(connection_name))?
+            .write_all(format!("{port} {token}").as_bytes())
+    }
+
+    fn connect_to(endpoint: &Endpoint) -> std::io::Result<TcpStream> {
+        let address = SocketAddr::from((Ipv4Addr::LOCALHOST, endpoint.port));
+        TcpStream::connect_timeout(&address, TIMEOUT)
+    }
+
+    /// Reads a message, which is only accepted if it starts with the token of its connection.
+    fn receive(
+        mut stream: TcpStream,
+        token: &str,
+    ) -> std::io::Result<Option<LocalConnectionMessage>> {
+        stream.set_nonblocking(false)?;
+        stream.set_read_timeout(Some(TIMEOUT))?;
+        let mut sent_token = [0; TOKEN_LENGTH];
+        match stream.read_exact(&mut sent_token) {
+            Ok(()) => {}
+            // Empty streams are other processes checking whether the connection is taken.
+            Err(e) if e.kind() == ErrorKind::UnexpectedEof => return Ok(None),
+            Err(e) => return Err(e),
+        }
+        if sent_token != token.as_bytes() {
+            tracing::warn!("Ignored a local connection message with a wrong token");
+            return Ok(None);
+        }
+        let mut bytes = Vec::new();
+        stream.take(MAX_MESSAGE_SIZE).read_to_end(&mut bytes)?;
+        Ok(LocalConnectionMessage::from_bytes(&bytes))
+    }
+}
+
+impl LocalConnectionBackend for SocketLocalConnectionBackend {
+    fn connect(&mut self, connection_name: &str) -> bool {
+        if self.listeners.contains_key(connection_name) {
+            return false;
+        }
+        // The file of a process that exited without closing its connections is left
+        // behind, so the connection is only taken if nobody answers. Connecting to a
+        // loopback port that nobody listens to is refused right away.
+        if let Some(endpoint) = self.endpoint(connection_name) {
+            if Self::connect_to(&endpoint).is_ok() {
+                return false;
+            }
+        }
+
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).and_then(|listener| {
+            let port = listener.local_addr()?.port();
+            listener.set_nonblocking(true)?;
+            Ok((listener, port))
+        });
+        let (listener, port) = match listener {
+            Ok(listener) => listener,
+            Err(e) => {
+                tracing::warn!(
+                    "Couldn't listen to local connection {}: {}",
+                    connection_name,
+                    e
+                );
+                return false;
+            }
+        };
+        let token = format!("{:032x}", rand::random::<u128>());
+        if let Err(e) = self.register(connection_name, port, &token) {
+            tracing::warn!(
+                "Couldn't register local connection {}: {}",
+                connection_name,
+                e
+            );
+            return false;
+        }
+        self.listeners
+            .insert(connection_name.to_owned(), Listener { listener, token });
+        true
+    }
+
+    fn close(&mut self, connection_name: &str) {
+        if self.listeners.remove(connection_name).is_some() {
+            let _ = fs::remove_file(self.port_path(connection_name));
+        }
+    }
+
+    /// Messages to other processes are delivered asynchronously, so this only
+    /// tells whether another process registered the connection.
+    fn send(&mut self, message: LocalConnectionMessage) -> bool {
+        if self.listeners.contains_key(&message.connection_name) {
+            self.messages.push(message);
+            return true;
+        }
+
+        let Some(endpoint) = self.endpoint(&message.connection_name) else {
+            return false;
+        };
+        thread::spawn(move || {
+            let result = Self::connect_to(&endpoint).and_then(|mut stream| {
+                stream.set_write_timeout(Some(TIMEOUT))?;
+                stream.write_all(endpoint.token.as_bytes())?;
+                stream.write_all(&message.to_bytes())
+            });
+            if let Err(e) = result {
+                tracing::warn!(
+                    "Couldn't send a message to local connection {}: {}",
+                    message.connection_name,
+                    e
+                );
+            }
+        });
+        true
+    }
+
+    fn poll(&mut self) -> Vec<LocalConnectionMessage> {
+        for (connection_name, listener) in &self.listeners {
+            loop {
+                match listener.listener.accept() {
+                    Ok((stream, _)) => {
+                        let connection_name = connection_name.clone();
+                        let token = listener.token.clone();
+                        let received = self.received_sender.clone();
+                        thread::spawn(move || match Self::receive(stream, &token) {
+                            Ok(Some(message)) if message.connection_name == connection_name => {
+                                let _ = received.send(message);
+                            }
+                            Ok(_) => {}
+                            Err(e) => tracing::warn!(
+                                "Couldn't receive a message of local connection {}: {}",
+                                connection_name,
+                                e
+                            ),
+                        });
+                    }
+                    Err(e) if e.kind() == ErrorKind::WouldBlock => break,
+                    Err(e) => {
+                        tracing::warn!("Couldn't accept a local connection message: {}", e);
+                        break;
+                    }
+                }
+            }
+        }
+
+        // Messages read after their connection was closed are dropped.
+        let listeners = &self.listeners;
+        self.messages.extend(
+            self.received
+                .try_iter()
+                .filter(|message| listeners.contains_key(&message.connection_name)),
+        );
+        std::mem::take(&mut self.messages)
+    }
+}
+
+impl Drop for SocketLocalConnectionBackend {
+    fn drop(&mut self) {
+        for connection_name in self.listeners.keys() {
+            let _ = fs::remove_file(self.port_path(connection_name));
+        }
+    }
+}
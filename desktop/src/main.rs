@@ -7,23 +7,51 @@
 #![windows_subsystem = "windows"]
 
 mod audio;
+#[cfg(feature = "camera")]
+mod camera;
 mod custom_event;
 mod executor;
+mod http_cache;
+mod local_connection;
 mod navigator;
+mod print;
+mod settings_panel;
+mod socket;
+#[cfg(feature = "sqlite")]
+mod sql;
 mod storage;
 mod task;
+mod throttle;
+mod trace_log;
 mod ui;
+mod update;
+mod watch;
 
 use crate::custom_event::RuffleEvent;
 use crate::executor::GlutinAsyncExecutor;
+use crate::throttle::{NetworkConditions, NetworkThrottle, ThrottledNavigatorBackend};
+use crate::update::UpdateChannel;
 use anyhow::{anyhow, Context, Error};
 use clap::Parser;
 use isahc::{config::RedirectPolicy, prelude::*, HttpClient};
 use rfd::FileDialog;
+use ruffle_core::asset_export;
+use ruffle_core::backend::audio::ResamplerQuality;
 use ruffle_core::backend::navigator::OpenURLMode;
+use ruffle_core::backend::print::{PageFormat, PdfPrintBackend};
+use ruffle_core::backend::ui::SettingsPanel;
+use ruffle_core::backend::video_capture::TestVideoCaptureBackend;
+use ruffle_core::i18n::{LanguageIdentifier, FALLBACK_LANGUAGE};
 use ruffle_core::{
-    config::Letterbox, events::KeyCode, tag_utils::SwfMovie, LoadBehavior, Player, PlayerBuilder,
-    PlayerEvent, StageDisplayState, StageScaleMode, StaticCallstack, ViewportDimensions,
+    captions::Captions,
+    compatibility_hints::{CompatibilityHint, CompatibilityHints},
+    config::{FrameCatchUp, Letterbox},
+    events::KeyCode,
+    injected_script::InjectedScript,
+    patch::MoviePatch,
+    tag_utils::{MovieInfo, SwfMovie},
+    LoadBehavior, Player, PlayerBuilder, PlayerEvent, StageDisplayState, StageScaleMode,
+    StaticCallstack, ViewportDimensions,
 };
 use ruffle_render::backend::RenderBackend;
 use ruffle_render::quality::StageQuality;
@@ -34,7 +62,7 @@ use std::io::Read;
 use std::panic::PanicInfo;
 use std::path::{Path, PathBuf};
 use std::rc::Rc;
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, Mutex, MutexGuard};
 use std::time::{Duration, Instant};
 use url::Url;
 use winit::dpi::{LogicalSize, PhysicalPosition, PhysicalSize, Size};
@@ -43,7 +71,7 @@ use winit::event::{
     WindowEvent,
 };
 use winit::event_loop::{ControlFlow, EventLoop, EventLoopBuilder};
-use winit::window::{Fullscreen, Icon, Window, WindowBuilder};
+use winit::window::{Fullscreen, Icon, Window, WindowBuilder, WindowLevel};
 
 thread_local! {
     static CALLSTACK: RefCell<Option<StaticCallstack>> = RefCell::default();
@@ -58,6 +86,9 @@ static GLOBAL: tracing_tracy::client::ProfiledAllocator<std::alloc::System> =
 
 static RUFFLE_VERSION: &str = include_str!(concat!(env!("OUT_DIR"), "/version-info.txt"));
 
+/// Set in the environment of a player process launched by the kiosk supervisor.
+const KIOSK_CHILD_ENV: &str = "RUFFLE_KIOSK_CHILD";
+
 #[derive(Parser, Debug)]
 #[clap(
     name = "Ruffle",
@@ -84,6 +115,11 @@ struct Opt {
     #[clap(long, short, default_value = "high")]
     power: PowerPreference,
 
+    /// Draw fully opaque bitmaps and rectangles front to back with a depth test, skipping what they cover.
+    /// This can speed up movies with layered full-screen backgrounds on integrated GPUs.
+    #[clap(long, action)]
+    opaque_sorting: bool,
+
     /// Width of window in pixels.
     #[clap(long, display_order = 1)]
     width: Option<f64>,
@@ -92,7 +128,7 @@ struct Opt {
     #[clap(long, display_order = 2)]
     height: Option<f64>,
 
-    /// Maximum number of seconds a script can run before scripting is disabled.
+    /// Maximum number of seconds a script can run before asking whether to abort it.
     #[clap(long, short, default_value = "15.0")]
     max_execution_duration: f64,
 
@@ -126,6 +162,30 @@ struct Opt {
     #[clap(long, action)]
     upgrade_to_https: bool,
 
+    /// Cache network responses on disk, and revalidate them instead of downloading
+    /// them again. Responses that servers mark as private or not to be stored are never cached.
+    #[clap(long, action)]
+    http_cache: bool,
+
+    /// Directory to cache network responses in.
+    /// Defaults to a directory in the user's cache directory.
+    #[clap(long, requires = "http_cache")]
+    http_cache_dir: Option<PathBuf>,
+
+    /// Maximum size of the network response cache, in megabytes.
+    #[clap(long, default_value = "256")]
+    http_cache_size: u64,
+
+    /// Simulate a slow network by delaying every request by this many milliseconds.
+    #[clap(long)]
+    simulate_latency: Option<u64>,
+
+    /// Simulate a slow network by limiting downloads to this many kilobits per second.
+    /// With the debugging shortcuts, Ctrl+Shift+B switches between typical connections
+    /// while the movie runs.
+    #[clap(long)]
+    simulate_bandwidth: Option<f64>,
+
     /// Start application in fullscreen.
     #[clap(long, action)]
     fullscreen: bool,
@@ -148,6 +208,22 @@ struct Opt {
     #[clap(long, value_parser)]
     spoof_url: Option<Url>,
 
+    /// Put movies in the security sandbox of their location, like Flash Player does.
+    /// Local movies can then load either local files or network URLs, depending on
+    /// their `UseNetwork` flag, instead of both.
+    #[clap(long, action)]
+    enforce_sandbox: bool,
+
+    /// Let every movie connect to any server with `XMLSocket`, even if the server
+    /// doesn't allow it with a socket policy file.
+    #[clap(long, action)]
+    trust_sockets: bool,
+
+    /// Let movies script movies from other domains that didn't allow them to
+    /// with `Security.allowDomain`. Some content only worked in lenient hosts.
+    #[clap(long, action)]
+    permissive_cross_scripting: bool,
+
     /// The version of the player to emulate
     #[clap(long)]
     player_version: Option<u8>,
@@ -156,9 +232,232 @@ struct Opt {
     #[clap(long)]
     frame_rate: Option<f64>,
 
+    /// Initial playback speed multiplier. With `--debug-shortcuts`, it can be changed
+    /// while playing with Ctrl+Shift+] (faster), Ctrl+Shift+[ (slower) and
+    /// Ctrl+Shift+\ (normal speed).
+    #[clap(long, default_value = "1.0")]
+    speed: f64,
+
+    /// How to catch up when the movie runs slower than its frame rate: run a few frames
+    /// per render depending on recent frame timings, slow the movie down, or keep the
+    /// movie's speed by skipping renders.
+    #[clap(long, default_value = "adaptive")]
+    frame_catch_up: FrameCatchUp,
+
+    /// Smoothly interpolate the positions of objects between frames, for content with a
+    /// frame rate lower than the refresh rate of the display.
+    #[clap(long, action)]
+    smooth_playback: bool,
+
+    /// Enable the debugging shortcuts, such as restarting the movie, changing its speed
+    /// or toggling the inspector panels. They're all pressed with Ctrl+Shift, and are
+    /// disabled by default so that every key reaches the movie.
+    /// When several panels are shown, the arrow keys go to the one shown last, and
+    /// Ctrl+Shift+Tab gives them to the next one.
+    #[clap(long, action)]
+    debug_shortcuts: bool,
+
+    /// Show an overlay with the frame rate and how long scripts, garbage collection and
+    /// rendering take, to include in performance reports. Toggle it with Ctrl+Shift+P.
+    #[clap(long, action)]
+    perf_hud: bool,
+
+    /// Show a panel listing the objects on the stage with their properties, to inspect and
+    /// step through timelines. Toggle it with Ctrl+Shift+T, select objects with
+    /// Ctrl+Shift+Up and Ctrl+Shift+Down, and step the selected movie clip with
+    /// Ctrl+Shift+Left and Ctrl+Shift+Right.
+    #[clap(long, action)]
+    timeline_inspector: bool,
+
+    /// Show a panel browsing the properties of the objects of the movie. Toggle it with
+    /// Ctrl+Shift+O, select properties with Ctrl+Shift+Up and Ctrl+Shift+Down, enter and
+    /// leave objects with Ctrl+Shift+Right and Ctrl+Shift+Left, watch a property with
+    /// Ctrl+Shift+W, and toggle booleans or step numbers with Ctrl+Shift+= and Ctrl+Shift+-.
+    #[clap(long, action)]
+    object_inspector: bool,
+
+    /// Show a panel listing the requests made by the movie, to diagnose site locks and
+    /// missing assets. Toggle it with Ctrl+Shift+N, select requests with Ctrl+Shift+Up and
+    /// Ctrl+Shift+Down, send the selected one again with Ctrl+Shift+Y, and export the log as
+    /// a HAR file with Ctrl+Shift+H.
+    #[clap(long, action)]
+    network_log: bool,
+
+    /// Track display objects removed from the stage, to find the ones kept alive by
+    /// listeners of events such as `enterFrame`. Press Ctrl+Shift+K to log the probable leaks.
+    #[clap(long, action)]
+    detect_leaks: bool,
+
+    /// Load replacement characters and FlashVars from this patch manifest.
+    /// By default, a `<movie>.patch.json` file next to a local movie is used if it exists.
+    #[clap(long, value_parser)]
+    patch: Option<PathBuf>,
+
+    /// Write the traces of each movie to its own log file in this directory.
+    /// Log files are rotated when they reach 1 MiB, keeping the 3 previous ones.
+    #[clap(long, value_parser)]
+    trace_log_dir: Option<PathBuf>,
+
+    /// Display captions from this SubRip (.srt) or WebVTT (.vtt) file, synced to the main timeline.
+    #[clap(long, value_parser)]
+    captions: Option<PathBuf>,
+
     /// The handling mode of links opening a new website.
     #[clap(long, default_value = "allow")]
     open_url_mode: OpenURLMode,
+
+    /// The language of the text shown by Ruffle, such as `fr-FR`.
+    /// By default, the language of the system is used.
+    #[clap(long)]
+    language: Option<LanguageIdentifier>,
+
+    /// Name of the audio output device to play sound on.
+    /// If this is not specified, the device chosen in the settings (opened with Ctrl+Comma)
+    /// is used, or else the default output device of the system.
+    #[clap(long)]
+    audio_device: Option<String>,
+
+    /// Export the bitmaps, sounds and shapes of the movie to this directory and exit,
+    /// along with a `manifest.json` file listing them.
+    /// While playing, Ctrl+Shift+E exports the assets of the current movie.
+    #[clap(long, value_parser)]
+    export_assets: Option<PathBuf>,
+
+    /// Print the pages of `PrintJob` to this PDF file without asking.
+    /// By default, the pages are saved to a PDF file chosen when printing, then opened.
+    #[clap(long, value_parser)]
+    print_to: Option<PathBuf>,
+
+    /// List the names of all available audio output devices and exit.
+    #[clap(long, action)]
+    list_audio_devices: bool,
+
+    /// Interpolation used when resampling sounds to the output sample rate.
+    /// Sinc is more expensive, but reduces aliasing in high-pitched content.
+    #[clap(long, default_value = "linear")]
+    resampler: ResamplerQuality,
+
+    /// Run as a kiosk: fullscreen and borderless, with the player's keyboard shortcuts disabled,
+    /// and relaunched if it crashes. Press Ctrl+Alt+Shift+Q to quit.
+    #[clap(long, action)]
+    kiosk: bool,
+
+    /// In kiosk mode, restart the movie after this many seconds without any input.
+    #[clap(long, requires = "kiosk")]
+    kiosk_idle_timeout: Option<f64>,
+
+    /// In kiosk mode, only give the movie the keys and mouse buttons with these Flash key
+    /// codes, such as `1,37,38,39,40` for the left mouse button and the arrow keys.
+    /// Other input is ignored. By default, all input is given to the movie.
+    #[clap(long, requires = "kiosk", value_delimiter = ',', value_parser = parse_key_code)]
+    kiosk_allowed_keys: Vec<KeyCode>,
+
+    /// Enable the AIR compatibility shims, for AIR games that only rely on simple
+    /// `flash.filesystem` access and `NativeApplication`.
+    #[clap(long, action)]
+    air: bool,
+
+    /// The directory that AIR content is allowed to read and write files in.
+    /// Defaults to a directory named after the movie in Ruffle's data directory.
+    #[clap(long, requires = "air")]
+    air_sandbox: Option<PathBuf>,
+
+    /// Inject an ActionScript 3.0 script, compiled to an ABC file, into the movie once it has
+    /// loaded. Can be given several times.
+    #[clap(long)]
+    inject_script: Vec<PathBuf>,
+
+    /// A public function defined by the injected scripts, which is called with the root
+    /// display object after they're loaded.
+    #[clap(long, requires = "inject_script")]
+    inject_script_entry_point: Option<String>,
+
+    /// Reload the movie whenever its file changes, to test it while authoring it.
+    /// Only local files can be watched.
+    #[clap(long, action)]
+    watch: bool,
+
+    /// When reloading a watched movie, keep the data of its shared objects.
+    /// By default, the shared objects opened by the movie are deleted, so it starts afresh.
+    #[clap(long, action, requires = "watch")]
+    watch_preserve_shared_objects: bool,
+
+    /// Give movies a single test camera, which captures scrolling color bars,
+    /// instead of the cameras of the system.
+    #[clap(long, action)]
+    test_camera: bool,
+
+    /// A JSON database of the known issues of movies, added to the one bundled with Ruffle.
+    /// Defaults to `compatibility_hints.json` in Ruffle's data directory, if it exists.
+    #[clap(long)]
+    compatibility_hints: Option<PathBuf>,
+
+    /// Don't warn about the known issues of the movie before playing it.
+    #[clap(long, action, conflicts_with = "compatibility_hints")]
+    no_compatibility_hints: bool,
+
+    /// Check for a newer version of Ruffle on startup, and offer to download it.
+    ///
+    /// Without this flag, the user is asked once whether to check for updates on every startup.
+    #[clap(long, action)]
+    check_for_updates: bool,
+
+    /// Don't check for updates, nor ask whether to.
+    #[clap(long, action, conflicts_with = "check_for_updates")]
+    no_check_for_updates: bool,
+
+    /// The kind of builds to look for when checking for updates.
+    /// Defaults to the channel this build was released on.
+    #[clap(long, conflicts_with = "no_check_for_updates")]
+    update_channel: Option<UpdateChannel>,
+}
+
+impl Opt {
+    /// Whether input from the given key or mouse button is given to the movie.
+    fn is_input_allowed(&self, key_code: KeyCode) -> bool {
+        self.kiosk_allowed_keys.is_empty() || self.kiosk_allowed_keys.contains(&key_code)
+    }
+}
+
+/// Parses a Flash key code, for `--kiosk-allowed-keys`.
+fn parse_key_code(value: &str) -> Result<KeyCode, String> {
+    value
+        .trim()
+        .parse()
+        .ok()
+        .and_then(KeyCode::from_u8)
+        .ok_or_else(|| format!("{value} is not a Flash key code"))
+}
+
+/// Opens the on-disk cache for network responses, if it was enabled.
+fn open_http_cache(opt: &Opt) -> Option<http_cache::HttpCache> {
+    if !opt.http_cache {
+        return None;
+    }
+
+    let dir = opt
+        .http_cache_dir
+        .clone()
+        .or_else(http_cache::HttpCache::default_dir)?;
+    match http_cache::HttpCache::new(dir, opt.http_cache_size * 1024 * 1024) {
+        Ok(cache) => Some(cache),
+        Err(e) => {
+            tracing::warn!("Couldn't open the HTTP cache: {}", e);
+            None
+        }
+    }
+}
+
+/// The network connection to simulate, if any was given on the command line.
+fn network_conditions(opt: &Opt) -> Option<NetworkConditions> {
+    if opt.simulate_latency.is_none() && opt.simulate_bandwidth.is_none() {
+        return None;
+    }
+
+    Some(NetworkConditions {
+        latency: Duration::from_millis(opt.simulate_latency.unwrap_or(0)),
+        bandwidth: opt.simulate_bandwidth.map(|kbps| kbps * 1000.0 / 8.0),
+    })
 }
 
 #[cfg(feature = "render_trace")]
@@ -176,6 +475,47 @@ fn trace_path(_opt: &Opt) -> Option<&Path> {
     None
 }
 
+/// Loads the patch manifest given on the command line, or the one next to a local movie.
+fn load_patch(opt: &Opt, movie_url: &Url) -> Result<Option<MoviePatch>, Error> {
+    let path = match &opt.patch {
+        Some(path) => path.to_owned(),
+        None => match movie_url.to_file_path() {
+            Ok(movie_path) if movie_path.with_extension("patch.json").is_file() => {
+                movie_path.with_extension("patch.json")
+            }
+            _ => return Ok(None),
+        },
+    };
+
+    let manifest = std::fs::read_to_string(&path)
+        .with_context(|| format!("Couldn't read patch {}", path.display()))?;
+    let base_path = path.parent().unwrap_or_else(|| Path::new("."));
+    let patch = MoviePatch::from_manifest(&manifest, |asset| std::fs::read(base_path.join(asset)))
+        .with_context(|| format!("Couldn't load patch {}", path.display()))?;
+    tracing::info!("Loaded patch {}", path.display());
+    Ok(Some(patch))
+}
+
+/// Loads the bundled compatibility hints, and the local database of hints.
+fn load_compatibility_hints(opt: &Opt) -> Result<CompatibilityHints, Error> {
+    let mut hints = CompatibilityHints::default();
+    let path = opt.compatibility_hints.clone().or_else(|| {
+        dirs::data_local_dir()
+            .map(|dir| dir.join("ruffle").join("compatibility_hints.json"))
+            .filter(|path| path.is_file())
+    });
+    let Some(path) = path else {
+        return Ok(hints);
+    };
+
+    let json = std::fs::read_to_string(&path)
+        .with_context(|| format!("Couldn't read compatibility hints {}", path.display()))?;
+    let local_hints = CompatibilityHints::from_json(&json)
+        .with_context(|| format!("Couldn't load compatibility hints {}", path.display()))?;
+    hints.extend(local_hints);
+    Ok(hints)
+}
+
 fn parse_url(path: &Path) -> Result<Url, Error> {
     if path.exists() {
         let absolute_path = path.canonicalize().unwrap_or_else(|_| path.to_owned());
@@ -269,10 +609,13 @@ fn get_screen_size(event_loop: &EventLoop<RuffleEvent>) -> PhysicalSize<u32> {
 
 struct App {
     opt: Opt,
+    movie_url: Url,
     window: Rc<Window>,
     event_loop: EventLoop<RuffleEvent>,
     executor: Arc<Mutex<GlutinAsyncExecutor>>,
     player: Arc<Mutex<Player>>,
+    save_state_path: Option<PathBuf>,
+    network_throttle: NetworkThrottle,
     min_window_size: LogicalSize<u32>,
     max_window_size: PhysicalSize<u32>,
 }
@@ -302,21 +645,42 @@ impl App {
             .unwrap_or_else(|| movie_url.as_str());
         let title = format!("Ruffle - {filename}");
         SWF_INFO.with(|i| *i.borrow_mut() = Some(filename.to_string()));
+        let save_state_path = dirs::data_local_dir().map(|dir| {
+            dir.join("ruffle")
+                .join("SaveStates")
+                .join(format!("{filename}.state"))
+        });
 
         let min_window_size = (16, 16).into();
         let max_window_size = get_screen_size(&event_loop);
 
-        let window = WindowBuilder::new()
+        let mut window_builder = WindowBuilder::new()
             .with_visible(false)
             .with_title(title)
             .with_window_icon(Some(icon))
             .with_min_inner_size(min_window_size)
-            .with_max_inner_size(max_window_size)
-            .build(&event_loop)?;
+            .with_max_inner_size(max_window_size);
+        if opt.kiosk {
+            window_builder = window_builder
+                .with_decorations(false)
+                .with_window_level(WindowLevel::AlwaysOnTop);
+        }
+        let window = window_builder.build(&event_loop)?;
 
         let mut builder = PlayerBuilder::new();
 
-        match audio::CpalAudioBackend::new() {
+        let audio = match (opt.audio_device.as_deref(), audio::preferred_device()) {
+            (Some(device), _) => audio::CpalAudioBackend::new(Some(device), opt.resampler),
+            (None, Some(device)) => {
+                audio::CpalAudioBackend::new(Some(device.as_str()), opt.resampler).or_else(|e| {
+                    // The device chosen in the settings may have been unplugged since.
+                    tracing::warn!("Using the default audio device instead: {}", e);
+                    audio::CpalAudioBackend::new(None, opt.resampler)
+                })
+            }
+            (None, None) => audio::CpalAudioBackend::new(None, opt.resampler),
+        };
+        match audio {
             Ok(audio) => builder = builder.with_audio(audio),
             Err(e) => {
                 tracing::error!("Unable to create audio device: {}", e);
@@ -331,10 +695,11 @@ impl App {
             opt.proxy.clone(),
             opt.upgrade_to_https,
             opt.open_url_mode,
+            open_http_cache(&opt),
         );
 
         let viewport_size = window.inner_size();
-        let renderer = WgpuRenderBackend::for_window(
+        let mut renderer = WgpuRenderBackend::for_window(
             &window,
             (viewport_size.width, viewport_size.height),
             opt.graphics.into(),
@@ -343,6 +708,7 @@ impl App {
         )
         .map_err(|e| anyhow!(e.to_string()))
         .context("Couldn't create wgpu rendering backend")?;
+        renderer.set_opaque_sorting(opt.opaque_sorting);
         RENDER_INFO.with(|i| *i.borrow_mut() = Some(renderer.debug_info().to_string()));
 
         let window = Rc::new(window);
@@ -352,30 +718,165 @@ impl App {
                 builder.with_video(ruffle_video_software::backend::SoftwareVideoBackend::new());
         }
 
+        let mut storage = storage::DiskStorageBackend::new()?;
+        if opt.air {
+            let sandbox = opt
+                .air_sandbox
+                .clone()
+                .or_else(|| storage::DiskStorageBackend::default_file_sandbox(filename));
+            if let Some(sandbox) = sandbox {
+                storage = storage.with_file_sandbox(sandbox);
+            }
+        }
+
+        let localizations = ui::localizations();
+        let language = opt
+            .language
+            .clone()
+            .or_else(ui::system_language)
+            .unwrap_or(FALLBACK_LANGUAGE);
+
+        let network_throttle = NetworkThrottle::new(network_conditions(&opt));
         builder = builder
-            .with_navigator(navigator)
+            .with_navigator(ThrottledNavigatorBackend::new(
+                navigator,
+                network_throttle.clone(),
+            ))
             .with_renderer(renderer)
-            .with_storage(storage::DiskStorageBackend::new()?)
-            .with_ui(ui::DesktopUiBackend::new(window.clone())?)
+            .with_storage(storage)
+            .with_ui(ui::DesktopUiBackend::new(
+                window.clone(),
+                event_loop.create_proxy(),
+                localizations.clone(),
+                language,
+            )?)
+            .with_localizations(localizations)
+            .with_viewport_dimensions(
+                viewport_size.width,
+                viewport_size.height,
+                window.scale_factor(),
+            )
             .with_autoplay(true)
             .with_letterbox(opt.letterbox)
             .with_max_execution_duration(Duration::from_secs_f64(opt.max_execution_duration))
             .with_quality(opt.quality)
             .with_warn_on_unsupported_content(!opt.dont_warn_on_unsupported_content)
             .with_scale_mode(opt.scale, opt.force_scale)
-            .with_fullscreen(opt.fullscreen)
+            .with_fullscreen(opt.fullscreen || opt.kiosk)
             .with_load_behavior(opt.load_behavior)
             .with_spoofed_url(opt.spoof_url.clone().map(|url| url.to_string()))
+            .with_sandbox_enforcement(opt.enforce_sandbox)
+            .with_permissive_cross_scripting(opt.permissive_cross_scripting)
             .with_player_version(opt.player_version)
-            .with_frame_rate(opt.frame_rate);
+            .with_frame_rate(opt.frame_rate)
+            .with_frame_catch_up(opt.frame_catch_up)
+            .with_frame_interpolation(opt.smooth_playback)
+            .with_leak_detection(opt.detect_leaks)
+            .with_playback_speed(opt.speed)
+            .with_air_compatibility(opt.air);
+
+        if let Some(patch) = load_patch(&opt, &movie_url)? {
+            builder = builder.with_patch(patch);
+        }
+
+        for path in &opt.inject_script {
+            let abc = std::fs::read(path)
+                .with_context(|| format!("Couldn't read injected script {}", path.display()))?;
+            let mut script = InjectedScript::new(abc);
+            if let Some(entry_point) = &opt.inject_script_entry_point {
+                script = script.with_entry_point(entry_point);
+            }
+            builder = builder.with_injected_script(script);
+        }
+
+        match local_connection::SocketLocalConnectionBackend::new() {
+            Ok(local_connections) => builder = builder.with_local_connections(local_connections),
+            Err(e) => tracing::warn!("Local connections between players are unavailable: {}", e),
+        }
+        builder = builder.with_sockets(socket::TcpSocketBackend::new(opt.trust_sockets));
+        if opt.test_camera {
+            builder = builder.with_video_capture(TestVideoCaptureBackend::new());
+        } else {
+            #[cfg(feature = "camera")]
+            {
+                builder = builder.with_video_capture(camera::DesktopVideoCaptureBackend::new());
+            }
+        }
+        builder = match &opt.print_to {
+            Some(path) => builder.with_printer(PdfPrintBackend::new(
+                PageFormat::default(),
+                print::pdf_file_writer(path.clone()),
+            )),
+            None => builder.with_printer(print::DesktopPrintBackend::new()),
+        };
+
+        if let Some(directory) = &opt.trace_log_dir {
+            let log = trace_log::FileLogBackend::new(directory.clone()).with_context(|| {
+                format!(
+                    "Couldn't create the trace log directory {}",
+                    directory.display()
+                )
+            })?;
+            builder = builder.with_log(log);
+        }
+
+        if !opt.no_compatibility_hints {
+            builder = builder.with_compatibility_hints(load_compatibility_hints(&opt)?);
+        }
+
+        if let Some(path) = &opt.captions {
+            let captions = std::fs::read_to_string(path)
+                .map_err(Error::from)
+                .and_then(|text| Ok(Captions::parse(&text)?))
+                .with_context(|| format!("Couldn't load captions {}", path.display()))?;
+            builder = builder.with_captions(captions);
+        }
 
         let player = builder.build();
+        if opt.perf_hud {
+            player
+                .lock()
+                .expect("Cannot reenter")
+                .set_show_perf_hud(true);
+        }
+        if opt.timeline_inspector {
+            player
+                .lock()
+                .expect("Cannot reenter")
+                .set_show_timeline_inspector(true);
+        }
+        if opt.object_inspector {
+            player
+                .lock()
+                .expect("Cannot reenter")
+                .set_show_object_inspector(true);
+        }
+        if opt.network_log {
+            player
+                .lock()
+                .expect("Cannot reenter")
+                .set_show_network_log(true);
+        }
 
         let event_loop_proxy = event_loop.create_proxy();
         let on_metadata = move |swf_header: &ruffle_core::swf::HeaderExt| {
             let _ = event_loop_proxy.send_event(RuffleEvent::OnMetadata(swf_header.clone()));
         };
 
+        if should_check_for_updates(&opt) {
+            let event_loop_proxy = event_loop.create_proxy();
+            let channel = opt.update_channel.unwrap_or_default();
+            std::thread::spawn(
+                move || match update::check_for_update(channel, RUFFLE_VERSION) {
+                    Ok(Some(update)) => {
+                        let _ = event_loop_proxy.send_event(RuffleEvent::UpdateAvailable(update));
+                    }
+                    Ok(None) => tracing::info!("Ruffle is up to date"),
+                    Err(e) => tracing::warn!("Couldn't check for updates: {:#}", e),
+                },
+            );
+        }
+
         let mut parameters: Vec<(String, String)> = movie_url.query_pairs().into_owned().collect();
         parameters.extend(parse_parameters(&opt));
         player.lock().expect("Cannot reenter").fetch_root_movie(
@@ -388,12 +889,22 @@ impl App {
             *callstack.borrow_mut() = Some(player.lock().expect("Cannot reenter").callstack());
         });
 
+        if opt.watch {
+            match movie_url.to_file_path() {
+                Ok(path) => watch::watch_movie(path, event_loop.create_proxy()),
+                Err(()) => tracing::warn!("Only local movies can be watched, not {}", movie_url),
+            }
+        }
+
         Ok(Self {
             opt,
+            movie_url,
             window,
             event_loop,
             executor,
             player,
+            save_state_path,
+            network_throttle,
             min_window_size,
             max_window_size,
         })
@@ -412,10 +923,28 @@ impl App {
         let mut minimized = false;
         let mut modifiers = ModifiersState::empty();
         let mut fullscreen_down = false;
+        // Whether the characters typed by the last key press are given to the movie.
+        let mut text_input_allowed = true;
+        let mut focused_panel = None;
+        let mut last_input = Instant::now();
+        let event_loop_proxy = self.event_loop.create_proxy();
 
         // Poll UI events.
         self.event_loop
             .run(move |event, _window_target, control_flow| {
+                if let winit::event::Event::WindowEvent {
+                    event:
+                        WindowEvent::CursorMoved { .. }
+                        | WindowEvent::MouseInput { .. }
+                        | WindowEvent::MouseWheel { .. }
+                        | WindowEvent::KeyboardInput { .. }
+                        | WindowEvent::Touch(_),
+                    ..
+                } = &event
+                {
+                    last_input = Instant::now();
+                }
+
                 match event {
                     winit::event::Event::LoopDestroyed => {
                         self.player
@@ -435,6 +964,15 @@ impl App {
                         if dt > 0 {
                             time = new_time;
                             let mut player_lock = self.player.lock().expect("Cannot reenter");
+                            if let Some(timeout) = self.opt.kiosk_idle_timeout {
+                                if new_time.duration_since(last_input).as_secs_f64() >= timeout {
+                                    last_input = new_time;
+                                    player_lock.reset();
+                                    tracing::info!(
+                                        "Restarted movie after {timeout}s without input"
+                                    );
+                                }
+                            }
                             player_lock.tick(dt as f64 / 1000.0);
                             next_frame_time = new_time + player_lock.time_til_next_frame();
                             if player_lock.needs_render() {
@@ -447,7 +985,13 @@ impl App {
                     winit::event::Event::RedrawRequested(_) => {
                         // Don't render when minimized to avoid potential swap chain errors in `wgpu`.
                         if !minimized {
-                            self.player.lock().expect("Cannot reenter").render();
+                            let mut player_lock = self.player.lock().expect("Cannot reenter");
+                            player_lock.render();
+                            // Presenting waits for vsync, so this redraws once per refresh
+                            // of the display without spinning.
+                            if player_lock.wants_continuous_render() {
+                                self.window.request_redraw();
+                            }
                             #[cfg(feature = "tracy")]
                             tracing_tracy::client::Client::running()
                                 .expect("tracy client must be running")
@@ -456,10 +1000,14 @@ impl App {
                     }
 
                     winit::event::Event::WindowEvent { event, .. } => match event {
+                        // Kiosks can only be quit with their dedicated shortcut.
+                        WindowEvent::CloseRequested if self.opt.kiosk => {}
                         WindowEvent::CloseRequested => {
                             *control_flow = ControlFlow::Exit;
                             return;
                         }
+                        // Take the focus back if the user switches to another window.
+                        WindowEvent::Focused(false) if self.opt.kiosk => self.window.focus_window(),
                         WindowEvent::Resized(size) => {
                             // TODO: Change this when winit adds a `Window::minimzed` or `WindowEvent::Minimize`.
                             minimized = size.width == 0 && size.height == 0;
@@ -476,6 +1024,22 @@ impl App {
                                 loaded = LoadingState::Loaded;
                             }
                         }
+                        WindowEvent::ScaleFactorChanged {
+                            scale_factor,
+                            new_inner_size,
+                        } => {
+                            // The window was moved to a monitor with a different DPI, or the DPI
+                            // setting changed. We keep the size suggested by winit, which preserves
+                            // the logical size of the window, and re-render the vectors at the new
+                            // resolution instead of stretching the previous frame.
+                            let mut player_lock = self.player.lock().expect("Cannot reenter");
+                            player_lock.set_viewport_dimensions(ViewportDimensions {
+                                width: new_inner_size.width,
+                                height: new_inner_size.height,
+                                scale_factor,
+                            });
+                            self.window.request_redraw();
+                        }
                         WindowEvent::CursorMoved { position, .. } => {
                             let mut player_lock = self.player.lock().expect("Cannot reenter");
                             mouse_pos = position;
@@ -499,6 +1063,9 @@ impl App {
                                 MouseButton::Middle => RuffleMouseButton::Middle,
                                 MouseButton::Other(_) => RuffleMouseButton::Unknown,
                             };
+                            if !self.opt.is_input_allowed(button.into()) {
+                                return;
+                            }
                             let event = match state {
                                 ElementState::Pressed => PlayerEvent::MouseDown { x, y, button },
                                 ElementState::Released => PlayerEvent::MouseUp { x, y, button },
@@ -544,6 +1111,21 @@ impl App {
                         WindowEvent::KeyboardInput { input, .. } => {
                             // Handle fullscreen keyboard shortcuts: Alt+Return, Escape.
                             match input {
+                                KeyboardInput {
+                                    state: ElementState::Pressed,
+                                    virtual_keycode: Some(VirtualKeyCode::Q),
+                                    ..
+                                } if self.opt.kiosk
+                                    && modifiers.ctrl()
+                                    && modifiers.alt()
+                                    && modifiers.shift() =>
+                                {
+                                    *control_flow = ControlFlow::Exit;
+                                    return;
+                                }
+                                // Other shortcuts are disabled in kiosk mode, and all keys
+                                // are given to the movie.
+                                _ if self.opt.kiosk => (),
                                 KeyboardInput {
                                     state: ElementState::Pressed,
                                     virtual_keycode: Some(VirtualKeyCode::Return),
@@ -571,12 +1153,51 @@ impl App {
                                 } => self.player.lock().expect("Cannot reenter").update(|uc| {
                                     uc.stage.set_display_state(uc, StageDisplayState::Normal);
                                 }),
+                                // Ctrl+Comma opens the settings, like in most applications.
+                                KeyboardInput {
+                                    state: ElementState::Pressed,
+                                    virtual_keycode: Some(VirtualKeyCode::Comma),
+                                    ..
+                                } if modifiers.ctrl() && !modifiers.shift() => {
+                                    let mut player = self.player.lock().expect("Cannot reenter");
+                                    settings_panel::show_settings_panel(
+                                        &mut player,
+                                        SettingsPanel::SettingsManager,
+                                    );
+                                    return;
+                                }
+                                KeyboardInput {
+                                    state: ElementState::Pressed,
+                                    virtual_keycode: Some(key),
+                                    ..
+                                } if self.opt.debug_shortcuts
+                                    && modifiers.ctrl()
+                                    && modifiers.shift() =>
+                                {
+                                    let player_lock = self.player.lock().expect("Cannot reenter");
+                                    if handle_debug_shortcut(
+                                        player_lock,
+                                        key,
+                                        &mut focused_panel,
+                                        self.save_state_path.as_deref(),
+                                        &self.network_throttle,
+                                    ) {
+                                        return;
+                                    }
+                                }
                                 _ => (),
                             }
 
                             let mut player_lock = self.player.lock().expect("Cannot reenter");
                             if let Some(key) = input.virtual_keycode {
                                 let key_code = winit_to_ruffle_key_code(key);
+                                let is_allowed = self.opt.is_input_allowed(key_code);
+                                if input.state == ElementState::Pressed {
+                                    text_input_allowed = is_allowed;
+                                }
+                                if !is_allowed {
+                                    return;
+                                }
                                 let key_char = winit_key_to_char(key, modifiers.shift());
                                 let event = match input.state {
                                     ElementState::Pressed => {
@@ -592,6 +1213,7 @@ impl App {
                                 }
                             }
                         }
+                        WindowEvent::ReceivedCharacter(_) if !text_input_allowed => {}
                         WindowEvent::ReceivedCharacter(codepoint) => {
                             let mut player_lock = self.player.lock().expect("Cannot reenter");
                             let event = PlayerEvent::TextInput { codepoint };
@@ -607,7 +1229,53 @@ impl App {
                         .lock()
                         .expect("active executor reference")
                         .poll_all(),
+                    winit::event::Event::UserEvent(RuffleEvent::UpdateAvailable(update)) => {
+                        if confirm_update(&update) {
+                            let event_loop_proxy = event_loop_proxy.clone();
+                            std::thread::spawn(move || {
+                                let result =
+                                    update::download_update(&update).map_err(|e| format!("{e:#}"));
+                                let _ = event_loop_proxy
+                                    .send_event(RuffleEvent::UpdateDownloaded(result));
+                            });
+                        }
+                    }
+                    winit::event::Event::UserEvent(RuffleEvent::UpdateDownloaded(result)) => {
+                        show_update_downloaded(result);
+                    }
+                    winit::event::Event::UserEvent(RuffleEvent::MovieChanged) => {
+                        match load_movie(&self.movie_url, &self.opt) {
+                            Ok(movie) => {
+                                let mut player_lock = self.player.lock().expect("Cannot reenter");
+                                player_lock.reload(movie, self.opt.watch_preserve_shared_objects);
+                                self.window.request_redraw();
+                            }
+                            // The movie keeps playing, to be reloaded when it's fixed.
+                            Err(e) => tracing::error!("Couldn't reload the movie: {:#}", e),
+                        }
+                    }
+                    winit::event::Event::UserEvent(RuffleEvent::ShowSettings(panel)) => {
+                        let mut player = self.player.lock().expect("Cannot reenter");
+                        settings_panel::show_settings_panel(&mut player, panel);
+                    }
+                    winit::event::Event::UserEvent(RuffleEvent::Exit(code)) => {
+                        *control_flow = ControlFlow::ExitWithCode(code);
+                        return;
+                    }
                     winit::event::Event::UserEvent(RuffleEvent::OnMetadata(swf_header)) => {
+                        let hint = self
+                            .player
+                            .lock()
+                            .expect("Cannot reenter")
+                            .compatibility_hint()
+                            .cloned();
+                        if let Some(hint) = hint {
+                            if !confirm_compatibility_hint(&hint) {
+                                *control_flow = ControlFlow::Exit;
+                                return;
+                            }
+                        }
+
                         let movie_width = swf_header.stage_size().width().to_pixels();
                         let movie_height = swf_header.stage_size().height().to_pixels();
 
@@ -636,11 +1304,12 @@ impl App {
                         );
 
                         self.window.set_inner_size(window_size);
-                        self.window.set_fullscreen(if self.opt.fullscreen {
-                            Some(Fullscreen::Borderless(None))
-                        } else {
-                            None
-                        });
+                        self.window
+                            .set_fullscreen(if self.opt.fullscreen || self.opt.kiosk {
+                                Some(Fullscreen::Borderless(None))
+                            } else {
+                                None
+                            });
                         self.window.set_visible(true);
 
                         let viewport_size = self.window.inner_size();
@@ -675,6 +1344,445 @@ impl App {
     }
 }
 
+/// Logs the display objects that were removed from the stage but are still kept alive.
+fn log_probable_leaks(player: &mut Player) {
+    let leaks = player.probable_leaks();
+    if leaks.is_empty() {
+        tracing::info!("No probable leaks found");
+        return;
+    }
+    tracing::info!("Probable leaks of objects removed from the stage:");
+    for leak in leaks {
+        tracing::info!(
+            "{} x {}, still listening to {}",
+            leak.count,
+            leak.class_name,
+            leak.event
+        );
+    }
+}
+
+/// A debugging panel that takes the arrow keys while it has the keyboard focus.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum DebugPanel {
+    ObjectInspector,
+    TimelineInspector,
+    NetworkLog,
+}
+
+impl DebugPanel {
+    const ALL: [DebugPanel; 3] = [
+        DebugPanel::ObjectInspector,
+        DebugPanel::TimelineInspector,
+        DebugPanel::NetworkLog,
+    ];
+
+    fn is_shown(self, player: &Player) -> bool {
+        match self {
+            DebugPanel::ObjectInspector => player.show_object_inspector(),
+            DebugPanel::TimelineInspector => player.show_timeline_inspector(),
+            DebugPanel::NetworkLog => player.show_network_log(),
+        }
+    }
+
+    fn set_shown(self, player: &mut Player, show: bool) {
+        match self {
+            DebugPanel::ObjectInspector => player.set_show_object_inspector(show),
+            DebugPanel::TimelineInspector => player.set_show_timeline_inspector(show),
+            DebugPanel::NetworkLog => player.set_show_network_log(show),
+        }
+    }
+
+    fn toggle_key(self) -> VirtualKeyCode {
+        match self {
+            DebugPanel::ObjectInspector => VirtualKeyCode::O,
+            DebugPanel::TimelineInspector => VirtualKeyCode::T,
+            DebugPanel::NetworkLog => VirtualKeyCode::N,
+        }
+    }
+}
+
+/// Runs the debugging shortcut for Ctrl+Shift+`key`, if there is one.
+///
+/// These are only enabled with `--debug-shortcuts`, and keys without a shortcut are
+/// still given to the movie. Only the focused panel takes the arrow keys: a panel gets
+/// the focus when it's shown, and Ctrl+Shift+Tab moves it to the next shown panel.
+/// Returns whether `key` was a shortcut.
+fn handle_debug_shortcut(
+    mut player: MutexGuard<Player>,
+    key: VirtualKeyCode,
+    focused_panel: &mut Option<DebugPanel>,
+    save_state_path: Option<&Path>,
+    network_throttle: &NetworkThrottle,
+) -> bool {
+    let speed = player.playback_speed();
+    if let Some(speed) = shortcut_playback_speed(key, speed) {
+        player.set_playback_speed(speed);
+        tracing::info!("Playback speed set to {}x", speed);
+        return true;
+    }
+    match key {
+        VirtualKeyCode::R => {
+            player.reset();
+            tracing::info!("Restarted movie");
+            return true;
+        }
+        VirtualKeyCode::K => {
+            log_probable_leaks(&mut player);
+            return true;
+        }
+        VirtualKeyCode::P => {
+            let show = !player.show_perf_hud();
+            player.set_show_perf_hud(show);
+            return true;
+        }
+        VirtualKeyCode::B => {
+            tracing::info!("Network simulation: {}", network_throttle.next_preset());
+            return true;
+        }
+        _ => (),
+    }
+
+    let toggled_panel = DebugPanel::ALL
+        .into_iter()
+        .find(|panel| panel.toggle_key() == key);
+    if let Some(panel) = toggled_panel {
+        let show = !panel.is_shown(&player);
+        panel.set_shown(&mut player, show);
+        if show {
+            *focused_panel = Some(panel);
+        }
+    }
+    // Panels may also be shown from the command line, or closed.
+    let shown_panels: Vec<_> = DebugPanel::ALL
+        .into_iter()
+        .filter(|panel| panel.is_shown(&player))
+        .collect();
+    if !focused_panel.map_or(false, |panel| shown_panels.contains(&panel)) {
+        *focused_panel = shown_panels.first().copied();
+    }
+    if toggled_panel.is_some() {
+        return true;
+    }
+    if key == VirtualKeyCode::Tab {
+        if let Some(focused) = *focused_panel {
+            let index = shown_panels
+                .iter()
+                .position(|panel| *panel == focused)
+                .unwrap_or_default();
+            let next = shown_panels[(index + 1) % shown_panels.len()];
+            *focused_panel = Some(next);
+            tracing::info!("Keyboard focus moved to the {:?} panel", next);
+            return true;
+        }
+    }
+
+    match (*focused_panel, key) {
+        (Some(DebugPanel::ObjectInspector), VirtualKeyCode::Up) => {
+            player.object_inspector_select(-1);
+            return true;
+        }
+        (Some(DebugPanel::ObjectInspector), VirtualKeyCode::Down) => {
+            player.object_inspector_select(1);
+            return true;
+        }
+        (Some(DebugPanel::ObjectInspector), VirtualKeyCode::Left) => {
+            player.object_inspector_leave();
+            return true;
+        }
+        (Some(DebugPanel::ObjectInspector), VirtualKeyCode::Right) => {
+            player.object_inspector_enter();
+            return true;
+        }
+        (Some(DebugPanel::ObjectInspector), VirtualKeyCode::W) => {
+            player.object_inspector_toggle_watch();
+            return true;
+        }
+        (Some(DebugPanel::ObjectInspector), VirtualKeyCode::Equals) => {
+            player.object_inspector_adjust(1.0);
+            return true;
+        }
+        (Some(DebugPanel::ObjectInspector), VirtualKeyCode::Minus) => {
+            player.object_inspector_adjust(-1.0);
+            return true;
+        }
+        (Some(DebugPanel::TimelineInspector), VirtualKeyCode::Up) => {
+            player.timeline_inspector_select(-1);
+            return true;
+        }
+        (Some(DebugPanel::TimelineInspector), VirtualKeyCode::Down) => {
+            player.timeline_inspector_select(1);
+            return true;
+        }
+        (Some(DebugPanel::TimelineInspector), VirtualKeyCode::Left) => {
+            player.timeline_inspector_step_frame(-1);
+            return true;
+        }
+        (Some(DebugPanel::TimelineInspector), VirtualKeyCode::Right) => {
+            player.timeline_inspector_step_frame(1);
+            return true;
+        }
+        (Some(DebugPanel::NetworkLog), VirtualKeyCode::Up) => {
+            player.network_log_select(-1);
+            return true;
+        }
+        (Some(DebugPanel::NetworkLog), VirtualKeyCode::Down) => {
+            player.network_log_select(1);
+            return true;
+        }
+        // These don't conflict with other panels, and only need the log to be shown.
+        (_, VirtualKeyCode::Y) if player.show_network_log() => {
+            player.replay_network_request();
+            return true;
+        }
+        (_, VirtualKeyCode::H) if player.show_network_log() => {
+            let har = player.export_network_log();
+            drop(player);
+            save_network_log(&har);
+            return true;
+        }
+        _ => (),
+    }
+
+    match (key, save_state_path) {
+        (VirtualKeyCode::E, _) => {
+            if let Some(directory) = FileDialog::new().set_title("Export Assets").pick_folder() {
+                export_player_assets(&player, &directory);
+            }
+            true
+        }
+        (VirtualKeyCode::I, _) => {
+            let info = player.movie_info();
+            drop(player);
+            show_movie_properties(&info);
+            true
+        }
+        (VirtualKeyCode::S, Some(path)) => {
+            save_state(&mut player, path);
+            true
+        }
+        (VirtualKeyCode::L, Some(path)) => {
+            load_state(&mut player, path);
+            true
+        }
+        _ => false,
+    }
+}
+
+/// Returns the new playback speed for a Ctrl+Shift+`key` shortcut,
+/// or `None` if `key` isn't a playback speed shortcut.
+fn shortcut_playback_speed(key: VirtualKeyCode, speed: f64) -> Option<f64> {
+    const MIN_SPEED: f64 = 0.125;
+    const MAX_SPEED: f64 = 8.0;
+    match key {
+        VirtualKeyCode::RBracket => Some((speed * 2.0).min(MAX_SPEED)),
+        VirtualKeyCode::LBracket => Some((speed / 2.0).max(MIN_SPEED)),
+        VirtualKeyCode::Backslash => Some(1.0),
+        _ => None,
+    }
+}
+
+/// Shows the "Movie properties" dialog, opened with Ctrl+Shift+I.
+fn show_movie_properties(info: &MovieInfo) {
+    let mut description = format!(
+        "Stage size: {}x{}\n\
+        Frame rate: {} fps\n\
+        Frames: {}\n\
+        SWF version: {}\n\
+        ActionScript: {}",
+        info.width,
+        info.height,
+        info.frame_rate,
+        info.num_frames,
+        info.swf_version,
+        if info.is_action_script_3 {
+            "3.0"
+        } else {
+            "1.0/2.0"
+        },
+    );
+    if let Some(duration) = info.duration() {
+        description.push_str(&format!(
+            "\nEstimated duration: {:.1}s",
+            duration.as_secs_f64()
+        ));
+    }
+    if let Some(metadata) = &info.metadata {
+        description.push_str(&format!("\n\nMetadata:\n{metadata}"));
+    }
+
+    rfd::MessageDialog::new()
+        .set_level(rfd::MessageLevel::Info)
+        .set_title("Movie properties")
+        .set_description(&description)
+        .set_buttons(rfd::MessageButtons::Ok)
+        .show();
+}
+
+/// Writes a file exported from a movie to `directory`.
+fn write_asset(directory: &Path, name: &str, data: &[u8]) -> Result<(), std::io::Error> {
+    std::fs::create_dir_all(directory)?;
+    std::fs::write(directory.join(name), data)
+}
+
+/// Asks where to save an exported network log, and saves it there.
+fn save_network_log(har: &str) {
+    let Some(path) = FileDialog::new()
+        .set_title("Export Network Log")
+        .add_filter("HTTP Archive", &["har"])
+        .set_file_name("network.har")
+        .save_file()
+    else {
+        return;
+    };
+    match std::fs::write(&path, har) {
+        Ok(()) => tracing::info!("Exported the network log to {}", path.display()),
+        Err(e) => tracing::error!("Couldn't export the network log: {}", e),
+    }
+}
+
+/// Exports the assets of the current movie (Ctrl+Shift+E).
+fn export_player_assets(player: &Player, directory: &Path) {
+    match player.export_assets(|name, data| write_asset(directory, name, data)) {
+        Ok(assets) => tracing::info!(
+            "Exported {} assets to {}",
+            assets.len(),
+            directory.display()
+        ),
+        Err(e) => tracing::error!("Couldn't export assets: {}", e),
+    }
+}
+
+/// Shows the known issues of the movie before it plays, asking whether to play it anyway.
+fn confirm_compatibility_hint(hint: &CompatibilityHint) -> bool {
+    let mut description = match &hint.name {
+        Some(name) => format!("{name} has known issues in Ruffle:\n"),
+        None => "This movie has known issues in Ruffle:\n".to_string(),
+    };
+    for issue in &hint.issues {
+        description.push_str(&format!("\n- {issue}"));
+    }
+    if !hint.settings.is_empty() {
+        description.push_str("\n\nRecommended settings:\n");
+        for (name, value) in &hint.settings {
+            description.push_str(&format!("\n- {name}: {value}"));
+        }
+    }
+    description.push_str("\n\nDo you want to play it anyway?");
+
+    rfd::MessageDialog::new()
+        .set_level(rfd::MessageLevel::Warning)
+        .set_title("Known issues")
+        .set_description(&description)
+        .set_buttons(rfd::MessageButtons::YesNo)
+        .show()
+}
+
+/// Whether to check for updates on startup, asking the user the first time.
+fn should_check_for_updates(opt: &Opt) -> bool {
+    if opt.check_for_updates {
+        return true;
+    }
+    if opt.no_check_for_updates {
+        return false;
+    }
+    if let Some(enabled) = update::automatic_checks() {
+        return enabled;
+    }
+
+    let enabled = rfd::MessageDialog::new()
+        .set_level(rfd::MessageLevel::Info)
+        .set_title("Ruffle updates")
+        .set_description(
+            "Do you want Ruffle to check for a newer version every time it starts?\n\n\
+            You will be asked before anything is downloaded.",
+        )
+        .set_buttons(rfd::MessageButtons::YesNo)
+        .show();
+    if let Err(e) = update::set_automatic_checks(enabled) {
+        tracing::warn!("Couldn't save the update preference: {:#}", e);
+    }
+    enabled
+}
+
+/// Asks the user whether to download an available update.
+/// Updates that this build can't verify only offer to open their release page.
+fn confirm_update(update: &update::Update) -> bool {
+    let dialog = rfd::MessageDialog::new()
+        .set_level(rfd::MessageLevel::Info)
+        .set_title("Update available")
+        .set_buttons(rfd::MessageButtons::YesNo);
+
+    if update.can_download() {
+        dialog
+            .set_description(&format!(
+                "{} is available. Do you want to download it?",
+                update.name
+            ))
+            .show()
+    } else {
+        let open_page = dialog
+            .set_description(&format!(
+                "{} is available. Do you want to open its download page?",
+                update.name
+            ))
+            .show();
+        if open_page {
+            let _ = webbrowser::open(&update.page_url);
+        }
+        false
+    }
+}
+
+fn show_update_downloaded(result: Result<PathBuf, String>) {
+    let (level, description) = match result {
+        Ok(path) => (
+            rfd::MessageLevel::Info,
+            format!(
+                "The update was verified and saved to {}.\n\
+                Extract it over this copy of Ruffle to finish updating.",
+                path.display()
+            ),
+        ),
+        Err(e) => (
+            rfd::MessageLevel::Error,
+            format!("Couldn't download the update: {e}"),
+        ),
+    };
+    rfd::MessageDialog::new()
+        .set_level(level)
+        .set_title("Ruffle update")
+        .set_description(&description)
+        .set_buttons(rfd::MessageButtons::Ok)
+        .show();
+}
+
+/// Saves a snapshot of the player state to disk (Ctrl+Shift+S).
+fn save_state(player: &mut Player, path: &Path) {
+    let result = player.save_state().map_err(Error::from).and_then(|data| {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, data)?;
+        Ok(())
+    });
+    match result {
+        Ok(()) => tracing::info!("Saved state to {}", path.display()),
+        Err(e) => tracing::error!("Couldn't save state: {}", e),
+    }
+}
+
+/// Restores the snapshot previously saved with `save_state` (Ctrl+Shift+L).
+fn load_state(player: &mut Player, path: &Path) {
+    let result = std::fs::read(path)
+        .map_err(Error::from)
+        .and_then(|data| Ok(player.load_state(&data)?));
+    match result {
+        Ok(()) => tracing::info!("Loaded state from {}", path.display()),
+        Err(e) => tracing::error!("Couldn't load state: {}", e),
+    }
+}
+
 /// Convert a winit `VirtualKeyCode` into a Ruffle `KeyCode`.
 /// Return `KeyCode::Unknown` if there is no matching Flash key code.
 fn winit_to_ruffle_key_code(key_code: VirtualKeyCode) -> KeyCode {
@@ -953,6 +2061,32 @@ fn run_timedemo(opt: Opt) -> Result<(), Error> {
     Ok(())
 }
 
+fn run_asset_export(opt: &Opt, directory: &Path) -> Result<(), Error> {
+    let path = opt
+        .input_path
+        .as_ref()
+        .ok_or_else(|| anyhow!("Input file necessary to export assets"))?;
+    let movie_url = parse_url(path)?;
+    let movie = load_movie(&movie_url, opt).context("Couldn't load movie")?;
+    let assets =
+        asset_export::export_assets(&movie, |name, data| write_asset(directory, name, data))?;
+
+    let exported = assets.iter().filter(|asset| asset.file.is_some()).count();
+    let failed = assets.iter().filter(|asset| asset.error.is_some()).count();
+    println!(
+        "Exported {exported} assets to {} ({failed} couldn't be exported).",
+        directory.display()
+    );
+    Ok(())
+}
+
+fn list_audio_devices() -> Result<(), Error> {
+    for name in audio::output_device_names()? {
+        println!("{name}");
+    }
+    Ok(())
+}
+
 fn init() {
     // When linked with the windows subsystem windows won't automatically attach
     // to the console of the parent process, so we do it explicitly. This fails
@@ -988,6 +2122,11 @@ fn panic_hook(info: &PanicInfo) {
         }
     });
 
+    // The kiosk supervisor relaunches the player, so nobody should have to dismiss a dialog.
+    if std::env::var_os(KIOSK_CHILD_ENV).is_some() {
+        return;
+    }
+
     // [NA] Let me just point out that PanicInfo::message() exists but isn't stable and that sucks.
     let panic_text = info.to_string();
     let message = if let Some(text) = panic_text.strip_prefix("panicked at '") {
@@ -1046,6 +2185,41 @@ fn panic_hook(info: &PanicInfo) {
     }
 }
 
+/// Runs the player in a child process, which is relaunched whenever it crashes.
+fn run_kiosk_supervisor() -> Result<(), Error> {
+    /// A player that crashes sooner than this after being launched counts as a failed launch.
+    const MIN_RUN_TIME: Duration = Duration::from_secs(10);
+    const MAX_FAILED_LAUNCHES: u32 = 5;
+
+    let exe = std::env::current_exe().context("Couldn't find the Ruffle executable")?;
+    let mut failed_launches = 0;
+    loop {
+        let launch_time = Instant::now();
+        let status = std::process::Command::new(&exe)
+            .args(std::env::args_os().skip(1))
+            .env(KIOSK_CHILD_ENV, "1")
+            .status()
+            .context("Couldn't launch the player")?;
+        if status.success() {
+            return Ok(());
+        }
+
+        if launch_time.elapsed() < MIN_RUN_TIME {
+            failed_launches += 1;
+            if failed_launches >= MAX_FAILED_LAUNCHES {
+                return Err(anyhow!(
+                    "The player crashed {failed_launches} times in a row, giving up"
+                ));
+            }
+        } else {
+            failed_launches = 0;
+        }
+
+        tracing::error!("The player exited with {}, relaunching it", status);
+        std::thread::sleep(Duration::from_secs(1));
+    }
+}
+
 fn shutdown() {
     // Without explicitly detaching the console cmd won't redraw it's prompt.
     #[cfg(windows)]
@@ -1057,8 +2231,14 @@ fn shutdown() {
 fn main() -> Result<(), Error> {
     init();
     let opt = Opt::parse();
-    let result = if opt.timedemo {
+    let result = if opt.list_audio_devices {
+        list_audio_devices()
+    } else if opt.timedemo {
         run_timedemo(opt)
+    } else if let Some(directory) = &opt.export_assets {
+        run_asset_export(&opt, directory)
+    } else if opt.kiosk && std::env::var_os(KIOSK_CHILD_ENV).is_none() {
+        run_kiosk_supervisor()
     } else {
         App::new(opt).map(|app| app.run())
     };
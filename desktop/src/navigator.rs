@@ -1,8 +1,10 @@
 //! Navigator backend for web
 
 use crate::custom_event::RuffleEvent;
+use crate::http_cache::{may_store, HttpCache, Validators};
 use isahc::{
-    config::RedirectPolicy, prelude::*, AsyncReadResponseExt, HttpClient, Request as IsahcRequest,
+    config::RedirectPolicy, http::StatusCode, prelude::*, AsyncReadResponseExt, HttpClient,
+    Request as IsahcRequest,
 };
 use rfd::{MessageButtons, MessageDialog, MessageLevel};
 use ruffle_core::backend::navigator::{
@@ -30,6 +32,9 @@ pub struct ExternalNavigatorBackend {
     // Client to use for network requests
     client: Option<Rc<HttpClient>>,
 
+    /// Cache for responses to network requests, if enabled.
+    http_cache: Option<Rc<HttpCache>>,
+
     upgrade_to_https: bool,
 
     open_url_mode: OpenURLMode,
@@ -44,6 +49,7 @@ impl ExternalNavigatorBackend {
         proxy: Option<Url>,
         upgrade_to_https: bool,
         open_url_mode: OpenURLMode,
+        http_cache: Option<HttpCache>,
     ) -> Self {
         let proxy = proxy.and_then(|url| url.as_str().parse().ok());
         let builder = HttpClient::builder()
@@ -62,6 +68,7 @@ impl ExternalNavigatorBackend {
             channel,
             event_loop,
             client,
+            http_cache: http_cache.map(Rc::new),
             base_url,
             upgrade_to_https,
             open_url_mode,
@@ -142,6 +149,11 @@ impl NavigatorBackend for ExternalNavigatorBackend {
         };
     }
 
+    fn absolute_url(&self, url: &str) -> Option<Url> {
+        let url = self.base_url.join(url).ok()?;
+        Some(self.pre_process_url(url))
+    }
+
     fn fetch(&self, request: Request) -> OwnedFuture<Response, Error> {
         // TODO: honor sandbox type (local-with-filesystem, local-with-network, remote, ...)
         let full_url = match self.base_url.join(request.url()) {
@@ -155,6 +167,7 @@ impl NavigatorBackend for ExternalNavigatorBackend {
         let processed_url = self.pre_process_url(full_url);
 
         let client = self.client.clone();
+        let http_cache = self.http_cache.clone();
 
         match processed_url.scheme() {
             "file" => Box::pin(async move {
@@ -191,20 +204,61 @@ impl NavigatorBackend for ExternalNavigatorBackend {
                 let client =
                     client.ok_or_else(|| Error::FetchError("Network unavailable".to_string()))?;
 
-                let isahc_request = match request.method() {
-                    NavigationMethod::Get => IsahcRequest::get(processed_url.to_string()),
-                    NavigationMethod::Post => IsahcRequest::post(processed_url.to_string()),
-                };
+                // Only plain GET requests are cached.
+                let http_cache = http_cache.filter(|_| {
+                    matches!(request.method(), NavigationMethod::Get) && request.body().is_none()
+                });
+                let cache_url = processed_url.to_string();
+                let mut validators = http_cache
+                    .as_ref()
+                    .and_then(|cache| cache.validators(&cache_url));
+
+                let mut response = loop {
+                    let mut isahc_request = match request.method() {
+                        NavigationMethod::Get => IsahcRequest::get(processed_url.to_string()),
+                        NavigationMethod::Post => IsahcRequest::post(processed_url.to_string()),
+                    };
+                    if let Some(validators) = &validators {
+                        if let Some(etag) = &validators.etag {
+                            isahc_request = isahc_request.header("If-None-Match", etag);
+                        }
+                        if let Some(last_modified) = &validators.last_modified {
+                            isahc_request =
+                                isahc_request.header("If-Modified-Since", last_modified);
+                        }
+                    }
 
-                let (body_data, _) = request.body().clone().unwrap_or_default();
-                let body = isahc_request
-                    .body(body_data)
-                    .map_err(|e| Error::FetchError(e.to_string()))?;
+                    let (body_data, _) = request.body().clone().unwrap_or_default();
+                    let body = isahc_request
+                        .body(body_data)
+                        .map_err(|e| Error::FetchError(e.to_string()))?;
+
+                    let response = client
+                        .send_async(body)
+                        .await
+                        .map_err(|e| Error::FetchError(e.to_string()))?;
+                    if response.status() != StatusCode::NOT_MODIFIED {
+                        break response;
+                    }
+                    let Some(cache) = &http_cache else {
+                        break response;
+                    };
+                    if validators.is_none() {
+                        break response;
+                    }
 
-                let mut response = client
-                    .send_async(body)
-                    .await
-                    .map_err(|e| Error::FetchError(e.to_string()))?;
+                    let url = if let Some(uri) = response.effective_uri() {
+                        uri.to_string()
+                    } else {
+                        processed_url.to_string()
+                    };
+                    if let Some(body) = cache.read(&cache_url).await {
+                        tracing::debug!("Using cached response for {}", processed_url);
+                        return Ok(Response { url, body });
+                    }
+                    // The entry was evicted in the meantime, so it's requested again without validators.
+                    validators = None;
+                };
 
                 if !response.status().is_success() {
                     return Err(Error::FetchError(format!(
@@ -225,6 +279,25 @@ impl NavigatorBackend for ExternalNavigatorBackend {
                     .await
                     .map_err(|e| Error::FetchError(e.to_string()))?;
 
+                if let Some(cache) = &http_cache {
+                    let header = |name| {
+                        response
+                            .headers()
+                            .get(name)
+                            .and_then(|value| value.to_str().ok())
+                            .map(str::to_owned)
+                    };
+                    if may_store(header("Cache-Control").as_deref()) {
+                        let validators = Validators {
+                            etag: header("ETag"),
+                            last_modified: header("Last-Modified"),
+                        };
+                        cache.put(&cache_url, body.clone(), validators);
+                    } else {
+                        cache.remove(&cache_url);
+                    }
+                }
+
                 Ok(Response { url, body })
             }),
         }
@@ -0,0 +1,69 @@
+//! Prints the pages of `PrintJob`.
+//!
+//! Pages are written to a PDF document, which is opened in the default viewer
+//! of the system to be printed from there.
+
+use rfd::{FileDialog, MessageButtons, MessageDialog, MessageLevel};
+use ruffle_core::backend::print::{write_pdf, PageFormat, PrintBackend, PrintPage};
+use std::path::PathBuf;
+
+pub struct DesktopPrintBackend;
+
+impl DesktopPrintBackend {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl PrintBackend for DesktopPrintBackend {
+    fn start(&mut self) -> Option<PageFormat> {
+        MessageDialog::new()
+            .set_level(MessageLevel::Info)
+            .set_title("Ruffle - Print")
+            .set_description("This content wants to print. Continue?")
+            .set_buttons(MessageButtons::OkCancel)
+            .show()
+            .then(PageFormat::default)
+    }
+
+    fn send(&mut self, pages: Vec<PrintPage>) {
+        let Some(path) = FileDialog::new()
+            .set_title("Print to PDF")
+            .add_filter("PDF", &["pdf"])
+            .set_file_name("print.pdf")
+            .save_file()
+        else {
+            return;
+        };
+        if let Err(e) = std::fs::write(&path, write_pdf(&pages)) {
+            tracing::error!(
+                "Couldn't write the printed pages to {}: {}",
+                path.display(),
+                e
+            );
+            return;
+        }
+        if let Err(e) = webbrowser::open(&path.to_string_lossy()) {
+            tracing::warn!("Couldn't open the printed pages: {}", e);
+        }
+    }
+}
+
+/// Writes the pages of each print job to a PDF file, without asking the user.
+/// Later jobs are numbered after the first one.
+pub fn pdf_file_writer(path: PathBuf) -> impl FnMut(Vec<u8>) {
+    let mut num_jobs = 0;
+    move |pdf| {
+        num_jobs += 1;
+        let path = if num_jobs == 1 {
+            path.clone()
+        } else {
+            let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+            path.with_file_name(format!("{stem}-{num_jobs}.pdf"))
+        };
+        match std::fs::write(&path, pdf) {
+            Ok(()) => tracing::info!("Printed to {}", path.display()),
+            Err(e) => tracing::error!("Couldn't print to {}: {}", path.display(), e),
+        }
+    }
+}
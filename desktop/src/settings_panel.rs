@@ -0,0 +1,155 @@
+//! Ruffle's equivalent of the Flash Player settings, opened when content
+//! calls `Security.showSettings`.
+//!
+//! Each setting is asked about with a native dialog.
+
+use crate::audio;
+use rfd::{MessageButtons, MessageDialog, MessageLevel};
+use ruffle_core::backend::ui::SettingsPanel;
+use ruffle_core::settings::{DevicePermission, DEFAULT_STORAGE_QUOTA};
+use ruffle_core::Player;
+
+/// The storage quota offered to domains that need more than the default.
+const LARGE_STORAGE_QUOTA: u64 = 10 * 1024 * 1024;
+
+fn ask(description: &str) -> bool {
+    MessageDialog::new()
+        .set_level(MessageLevel::Info)
+        .set_title("Ruffle - Settings")
+        .set_description(description)
+        .set_buttons(MessageButtons::YesNo)
+        .show()
+}
+
+fn ask_permission(domain: &str, device: &str, permission: DevicePermission) -> DevicePermission {
+    let current = match permission {
+        DevicePermission::Ask => "You will be asked when it tries to.",
+        DevicePermission::Allow => "It is currently allowed to.",
+        DevicePermission::Deny => "It is currently not allowed to.",
+    };
+    if ask(&format!(
+        "Allow {domain} to use your {device}?\n\n{current}"
+    )) {
+        DevicePermission::Allow
+    } else {
+        DevicePermission::Deny
+    }
+}
+
+/// Asks which output device to play sound on, going through them one at a time.
+/// The choice takes effect the next time Ruffle starts.
+fn ask_audio_device() {
+    let devices = match audio::output_device_names() {
+        Ok(devices) => devices,
+        Err(e) => {
+            tracing::warn!("Couldn't list the audio output devices: {:#}", e);
+            return;
+        }
+    };
+    let current = audio::preferred_device();
+    if devices.len() < 2 && current.is_none() {
+        return;
+    }
+
+    let current_text = match &current {
+        Some(name) => format!("the \"{name}\" audio output device"),
+        None => "the default audio output device of the system".to_owned(),
+    };
+    if !ask(&format!(
+        "Sound is played on {current_text}.\n\nChoose another output device?"
+    )) {
+        return;
+    }
+
+    let chosen = devices
+        .iter()
+        .filter(|name| Some(*name) != current.as_ref())
+        .find(|name| ask(&format!("Play sound on \"{name}\"?")))
+        .map(String::as_str);
+    let chosen = match chosen {
+        Some(name) => Some(name),
+        None if current.is_some()
+            && ask("Play sound on the default audio output device of the system?") =>
+        {
+            None
+        }
+        None => return,
+    };
+    match audio::set_preferred_device(chosen) {
+        Ok(()) => show_restart_notice(),
+        Err(e) => tracing::error!("Couldn't save the audio output device: {:#}", e),
+    }
+}
+
+fn show_restart_notice() {
+    MessageDialog::new()
+        .set_level(MessageLevel::Info)
+        .set_title("Ruffle - Settings")
+        .set_description("The new audio output device will be used the next time Ruffle starts.")
+        .set_buttons(MessageButtons::Ok)
+        .show();
+}
+
+fn format_quota(quota: Option<u64>) -> String {
+    match quota {
+        None => "an unlimited amount".to_owned(),
+        Some(0) => "nothing".to_owned(),
+        Some(quota) if quota >= 1024 * 1024 => format!("{} MB", quota / (1024 * 1024)),
+        Some(quota) => format!("{} KB", quota / 1024),
+    }
+}
+
+pub fn show_settings_panel(player: &mut Player, panel: SettingsPanel) {
+    let domain = player.settings_domain();
+    let original = player.domain_settings();
+    let mut settings = original.clone();
+
+    let privacy = matches!(
+        panel,
+        SettingsPanel::Privacy | SettingsPanel::SettingsManager
+    );
+    if privacy || panel == SettingsPanel::Camera {
+        settings.camera = ask_permission(&domain, "camera", settings.camera);
+    }
+    if privacy || panel == SettingsPanel::Microphone {
+        settings.microphone = ask_permission(&domain, "microphone", settings.microphone);
+    }
+
+    if matches!(
+        panel,
+        SettingsPanel::LocalStorage | SettingsPanel::SettingsManager
+    ) {
+        let allow_more = ask(&format!(
+            "{domain} may currently store {} of information on this computer.\n\n\
+            Allow it to store up to {}? Otherwise, it may store up to {}.",
+            format_quota(settings.storage_quota),
+            format_quota(Some(LARGE_STORAGE_QUOTA)),
+            format_quota(Some(DEFAULT_STORAGE_QUOTA)),
+        ));
+        settings.storage_quota = Some(if allow_more {
+            LARGE_STORAGE_QUOTA
+        } else {
+            DEFAULT_STORAGE_QUOTA
+        });
+    }
+
+    if matches!(
+        panel,
+        SettingsPanel::Display | SettingsPanel::SettingsManager
+    ) {
+        let muted = player.volume() == 0.0;
+        let description = if muted {
+            "Sound is currently muted. Unmute it?"
+        } else {
+            "Mute sound?"
+        };
+        if ask(description) {
+            player.set_volume(if muted { 1.0 } else { 0.0 });
+        }
+        ask_audio_device();
+    }
+
+    if settings != original {
+        player.set_domain_settings(&settings);
+    }
+}
@@ -0,0 +1,357 @@
+//! Opens the TCP connections of `XMLSocket`.
+//!
+//! Each connection is run by its own threads: one reads it, reporting what it receives
+//! through a channel that the player polls every frame, and one writes what the movie
+//! sends, so that a slow server never blocks the player.
+//!
+//! Unless the user trusts every movie, a server must allow the movie to connect with a
+//! socket policy file, which is requested first from the master policy port and then
+//! from the port being connected to, like Flash Player does.
+
+use ruffle_core::backend::socket::{SocketBackend, SocketEvent, SocketHandle, SocketPermission};
+use std::collections::HashMap;
+use std::io::{Read, Write};
+use std::net::{Shutdown, TcpStream, ToSocketAddrs};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+/// The port that serves the socket policy file of a whole server.
+const MASTER_POLICY_PORT: u16 = 843;
+
+/// How long to wait for a socket policy file, like Flash Player.
+const POLICY_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Policy files larger than this are rejected.
+const MAX_POLICY_SIZE: usize = 20 * 1024;
+
+/// An open connection.
+struct Connection {
+    /// The data that the movie sends, which the writing thread sends to the server.
+    outgoing: Sender<Vec<u8>>,
+
+    /// The stream, once the connection is established, to close it.
+    stream: Option<TcpStream>,
+}
+
+/// The open connections by handle.
+type Connections = Arc<Mutex<HashMap<SocketHandle, Connection>>>;
+
+pub struct TcpSocketBackend {
+    connections: Connections,
+    next_handle: SocketHandle,
+    sender: Sender<SocketEvent>,
+    receiver: Receiver<SocketEvent>,
+
+    /// Whether every movie may connect to any server, without socket policy files.
+    trust_all: bool,
+}
+
+impl TcpSocketBackend {
+    pub fn new(trust_all: bool) -> Self {
+        let (sender, receiver) = channel();
+        Self {
+            connections: Arc::new(Mutex::new(HashMap::new())),
+            next_handle: 0,
+            sender,
+            receiver,
+            trust_all,
+        }
+    }
+
+    /// Checks the permission, connects to the server, then forwards what it sends until
+    /// the connection is closed.
+    fn run_connection(
+        handle: SocketHandle,
+        host: String,
+        port: u16,
+        permission: SocketPermission,
+        outgoing: Receiver<Vec<u8>>,
+        connections: Connections,
+        sender: Sender<SocketEvent>,
+    ) {
+        let allowed = match &permission {
+            SocketPermission::Trusted => true,
+            SocketPermission::PolicyFile { domain } => {
+                let allowed = [MASTER_POLICY_PORT, port].iter().any(|policy_port| {
+                    fetch_policy(&host, *policy_port).map_or(false, |policy| {
+                        policy_allows(&policy, domain.as_deref(), port)
+                    })
+                });
+                if !allowed {
+                    tracing::warn!(
+                        "{}:{} has no socket policy file that allows this movie to connect",
+                        host,
+                        port
+                    );
+                }
+                allowed
+            }
+        };
+
+        let stream = if allowed {
+            TcpStream::connect((host.as_str(), port))
+                .and_then(|stream| {
+                    let writer = stream.try_clone()?;
+                    let closer = stream.try_clone()?;
+                    Ok((stream, writer, closer))
+                })
+                .map_err(|e| tracing::warn!("Couldn't connect to {}:{}: {}", host, port, e))
+                .ok()
+        } else {
+            None
+        };
+        let Some((mut stream, mut writer, closer)) = stream else {
+            connections
+                .lock()
+                .expect("Socket connections are not poisoned")
+                .remove(&handle);
+            let _ = sender.send(SocketEvent::Connected {
+                handle,
+                success: false,
+            });
+            return;
+        };
+
+        match connections
+            .lock()
+            .expect("Socket connections are not poisoned")
+            .get_mut(&handle)
+        {
+            Some(connection) => connection.stream = Some(closer),
+            // The movie closed the socket while it was connecting.
+            None => {
+                let _ = stream.shutdown(Shutdown::Both);
+                return;
+            }
+        }
+        let _ = sender.send(SocketEvent::Connected {
+            handle,
+            success: true,
+        });
+
+        // Data sent while connecting was queued, and is written first.
+        // This stops once the connection is closed, and the sender dropped.
+        thread::spawn(move || {
+            for data in outgoing {
+                if let Err(e) = writer.write_all(&data) {
+                    tracing::warn!("Couldn't send data through a socket: {}", e);
+                    break;
+                }
+            }
+        });
+
+        let mut buffer = [0; 4096];
+        loop {
+            match stream.read(&mut buffer) {
+                Ok(0) => break,
+                Ok(len) => {
+                    let data = buffer[..len].to_vec();
+                    if sender.send(SocketEvent::Data { handle, data }).is_err() {
+                        return;
+                    }
+                }
+                Err(e) => {
+                    tracing::warn!("Lost the connection to {}:{}: {}", host, port, e);
+                    break;
+                }
+            }
+        }
+        connections
+            .lock()
+            .expect("Socket connections are not poisoned")
+            .remove(&handle);
+        let _ = sender.send(SocketEvent::Closed { handle });
+    }
+}
+
+/// Requests the socket policy file that a port of the server serves, if any.
+fn fetch_policy(host: &str, port: u16) -> Option<String> {
+    let address = (host, port).to_socket_addrs().ok()?.next()?;
+    let mut stream = TcpStream::connect_timeout(&address, POLICY_TIMEOUT).ok()?;
+    stream.set_read_timeout(Some(POLICY_TIMEOUT)).ok()?;
+    stream.set_write_timeout(Some(POLICY_TIMEOUT)).ok()?;
+    stream.write_all(b"<policy-file-request/>\0").ok()?;
+
+    // The policy file is terminated by a zero byte, or by closing the connection.
+    let mut policy = Vec::new();
+    let mut buffer = [0; 1024];
+    loop {
+        match stream.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(len) => {
+                if let Some(end) = buffer[..len].iter().position(|byte| *byte == 0) {
+                    policy.extend_from_slice(&buffer[..end]);
+                    break;
+                }
+                policy.extend_from_slice(&buffer[..len]);
+                if policy.len() > MAX_POLICY_SIZE {
+                    return None;
+                }
+            }
+            Err(_) => return None,
+        }
+    }
+    String::from_utf8(policy).ok()
+}
+
+/// Whether a socket policy file lets movies from `domain` connect to `port`.
+///
+/// Only the `allow-access-from` elements matter, with their `domain` and `to-ports`
+/// attributes. Domains may start with a `*.` wildcard, and ports may be ranges.
+fn policy_allows(policy: &str, domain: Option<&str>, port: u16) -> bool {
+    policy
+        .split("<allow-access-from")
+        .skip(1)
+        .filter_map(|element| element.split('>').next())
+        .any(|attributes| {
+            let domain_allowed = match (attribute(attributes, "domain"), domain) {
+                (Some("*"), _) => true,
+                (Some(pattern), Some(domain)) => match pattern.strip_prefix("*.") {
+                    Some(suffix) => domain
+                        .strip_suffix(suffix)
+                        .map_or(false, |prefix| prefix.is_empty() || prefix.ends_with('.')),
+                    None => pattern.eq_ignore_ascii_case(domain),
+                },
+                _ => false,
+            };
+            let port_allowed = attribute(attributes, "to-ports").map_or(false, |ports| {
+                ports.split(',').map(str::trim).any(|ports| {
+                    if ports == "*" {
+                        return true;
+                    }
+                    match ports.split_once('-') {
+                        Some((start, end)) => match (start.parse::<u16>(), end.parse::<u16>()) {
+                            (Ok(start), Ok(end)) => (start..=end).contains(&port),
+                            _ => false,
+                        },
+                        None => ports.parse() == Ok(port),
+                    }
+                })
+            });
+            domain_allowed && port_allowed
+        })
+}
+
+/// The value of an attribute of an XML element, given everything after its name.
+fn attribute<'a>(attributes: &'a str, name: &str) -> Option<&'a str> {
+    let mut rest = attributes;
+    while let Some(index) = rest.find(name) {
+        let preceded_by_space = rest[..index]
+            .chars()
+            .last()
+            .map_or(false, char::is_whitespace);
+        rest = &rest[index + name.len()..];
+        let Some(value) = rest.trim_start().strip_prefix('=') else {
+            continue;
+        };
+        let value = value.trim_start();
+        let Some(quote) = value.chars().next().filter(|c| *c == '"' || *c == '\'') else {
+            continue;
+        };
+        let value = &value[1..];
+        if preceded_by_space {
+            return value.split(quote).next();
+        }
+    }
+    None
+}
+
+impl SocketBackend for TcpSocketBackend {
+    fn connect(&mut self, host: &str, port: u16, permission: SocketPermission) -> SocketHandle {
+        let handle = self.next_handle;
+        self.next_handle += 1;
+
+        let permission = if self.trust_all {
+            SocketPermission::Trusted
+        } else {
+            permission
+        };
+        let host = host.to_owned();
+        let (outgoing_sender, outgoing) = channel();
+        let connections = self.connections.clone();
+        connections
+            .lock()
+            .expect("Socket connections are not poisoned")
+            .insert(
+                handle,
+                Connection {
+                    outgoing: outgoing_sender,
+                    stream: None,
+                },
+            );
+        let sender = self.sender.clone();
+        thread::spawn(move || {
+            Self::run_connection(
+                handle,
+                host,
+                port,
+                permission,
+                outgoing,
+                connections,
+                sender,
+            )
+        });
+        handle
+    }
+
+    fn send(&mut self, handle: SocketHandle, data: Vec<u8>) {
+        if let Some(connection) = self
+            .connections
+            .lock()
+            .expect("Socket connections are not poisoned")
+            .get(&handle)
+        {
+            let _ = connection.outgoing.send(data);
+        }
+    }
+
+    fn close(&mut self, handle: SocketHandle) {
+        let connection = self
+            .connections
+            .lock()
+            .expect("Socket connections are not poisoned")
+            .remove(&handle);
+        if let Some(Connection {
+            stream: Some(stream),
+            ..
+        }) = connection
+        {
+            // This also stops the threads reading and writing the connection.
+            let _ = stream.shutdown(Shutdown::Both);
+        }
+    }
+
+    fn poll(&mut self) -> Vec<SocketEvent> {
+        self.receiver.try_iter().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const POLICY: &str = r#"<?xml version="1.0"?>
+        <cross-domain-policy>
+            <allow-access-from domain="*.example.com" to-ports="5000-5010,6000" />
+            <allow-access-from to-ports='7000' domain='games.example.org'/>
+        </cross-domain-policy>"#;
+
+    #[test]
+    fn test_policy_allows() {
+        assert!(policy_allows(POLICY, Some("example.com"), 5000));
+        assert!(policy_allows(POLICY, Some("www.example.com"), 5010));
+        assert!(policy_allows(POLICY, Some("www.example.com"), 6000));
+        assert!(!policy_allows(POLICY, Some("www.example.com"), 5011));
+        assert!(!policy_allows(POLICY, Some("notexample.com"), 5000));
+        assert!(policy_allows(POLICY, Some("games.example.org"), 7000));
+        assert!(!policy_allows(POLICY, Some("games.example.org"), 6000));
+        assert!(!policy_allows(POLICY, None, 5000));
+
+        let any = r#"<cross-domain-policy><allow-access-from domain="*" to-ports="*"/></cross-domain-policy>"#;
+        assert!(policy_allows(any, None, 1234));
+        assert!(policy_allows(any, Some("example.net"), 65535));
+        assert!(!policy_allows("<cross-domain-policy/>", None, 1234));
+    }
+}
@@ -0,0 +1,207 @@
+//! SQLite databases of AIR content, backing `flash.data.SQLConnection`
+
+use ruffle_core::backend::storage::{SqlOpenMode, SqlResult, SqlValue};
+use rusqlite::hooks::{AuthAction, AuthContext, Authorization};
+use rusqlite::limits::Limit;
+use rusqlite::types::{Value, ValueRef};
+use rusqlite::{ffi, Connection, OpenFlags};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// The databases opened by the movie, by handle.
+#[derive(Default)]
+pub struct Databases {
+    connections: HashMap<u32, Connection>,
+    next_handle: u32,
+}
+
+impl Databases {
+    pub fn open(&mut self, path: Option<&Path>, mode: SqlOpenMode) -> Result<u32, String> {
+        let connection = match path {
+            Some(path) => {
+                let flags = match mode {
+                    SqlOpenMode::Create => {
+                        OpenFlags::SQLITE_OPEN_READ_WRITE | OpenFlags::SQLITE_OPEN_CREATE
+                    }
+                    SqlOpenMode::Update => OpenFlags::SQLITE_OPEN_READ_WRITE,
+                    SqlOpenMode::Read => OpenFlags::SQLITE_OPEN_READ_ONLY,
+                };
+                Connection::open_with_flags(path, flags | OpenFlags::SQLITE_OPEN_NO_MUTEX)
+            }
+            None => Connection::open_in_memory(),
+        }
+        .map_err(|e| e.to_string())?;
+        sandbox(&connection);
+
+        let handle = self.next_handle;
+        self.next_handle = self.next_handle.wrapping_add(1);
+        self.connections.insert(handle, connection);
+        Ok(handle)
+    }
+
+    pub fn execute(
+        &mut self,
+        handle: u32,
+        sql: &str,
+        parameters: &[(String, SqlValue)],
+    ) -> Result<SqlResult, String> {
+        let connection = self
+            .connections
+            .get(&handle)
+            .ok_or_else(|| "The database is not open".to_string())?;
+        execute(connection, sql, parameters).map_err(|e| e.to_string())
+    }
+
+    pub fn close(&mut self, handle: u32) {
+        self.connections.remove(&handle);
+    }
+}
+
+/// Keeps the SQL of the movie to the database it opened.
+///
+/// Attaching databases would let it read and write any file, and so would `VACUUM INTO`,
+/// which attaches its output. Plain `VACUUM` attaches a temporary database too, so it
+/// isn't available either.
+fn sandbox(connection: &Connection) {
+    connection.set_limit(Limit::SQLITE_LIMIT_ATTACHED, 0);
+    connection.authorizer(Some(authorize));
+}
+
+fn authorize(context: AuthContext<'_>) -> Authorization {
+    match context.action {
+        AuthAction::Attach { .. }
+        | AuthAction::Unknown {
+            code: ffi::SQLITE_ATTACH,
+            ..
+        } => Authorization::Deny,
+        AuthAction::Function { function_name }
+            if function_name.eq_ignore_ascii_case("load_extension") =>
+        {
+            Authorization::Deny
+        }
+        _ => Authorization::Allow,
+    }
+}
+
+fn execute(
+    connection: &Connection,
+    sql: &str,
+    parameters: &[(String, SqlValue)],
+) -> Result<SqlResult, rusqlite::Error> {
+    let mut statement = connection.prepare(sql)?;
+    for (name, value) in parameters {
+        let index = match name.parse::<usize>() {
+            Ok(index) => index + 1,
+            Err(_) => statement
+                .parameter_index(name)?
+                .ok_or_else(|| rusqlite::Error::InvalidParameterName(name.clone()))?,
+        };
+        statement.raw_bind_parameter(index, to_sqlite_value(value))?;
+    }
+
+    let columns: Vec<String> = statement
+        .column_names()
+        .into_iter()
+        .map(String::from)
+        .collect();
+    let mut result_rows = vec![];
+    let mut rows_affected = 0;
+    if columns.is_empty() {
+        rows_affected = statement.raw_execute()? as u64;
+    } else {
+        let mut rows = statement.raw_query();
+        while let Some(row) = rows.next()? {
+            let values = (0..columns.len())
+                .map(|i| row.get_ref(i).map(from_sqlite_value))
+                .collect::<Result<_, _>>()?;
+            result_rows.push(values);
+        }
+    }
+
+    Ok(SqlResult {
+        columns,
+        rows: result_rows,
+        rows_affected,
+        last_insert_row_id: connection.last_insert_rowid(),
+    })
+}
+
+fn to_sqlite_value(value: &SqlValue) -> Value {
+    match value {
+        SqlValue::Null => Value::Null,
+        SqlValue::Integer(value) => Value::Integer(*value),
+        SqlValue::Real(value) => Value::Real(*value),
+        SqlValue::Text(value) => Value::Text(value.clone()),
+        SqlValue::Blob(value) => Value::Blob(value.clone()),
+    }
+}
+
+fn from_sqlite_value(value: ValueRef) -> SqlValue {
+    match value {
+        ValueRef::Null => SqlValue::Null,
+        ValueRef::Integer(value) => SqlValue::Integer(value),
+        ValueRef::Real(value) => SqlValue::Real(value),
+        ValueRef::Text(value) => SqlValue::Text(String::from_utf8_lossy(value).into_owned()),
+        ValueRef::Blob(value) => SqlValue::Blob(value.to_vec()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn open_in_memory() -> (Databases, u32) {
+        let mut databases = Databases::default();
+        let handle = databases.open(None, SqlOpenMode::Create).unwrap();
+        (databases, handle)
+    }
+
+    #[test]
+    fn test_attach_is_denied() {
+        let (mut databases, handle) = open_in_memory();
+        let path = std::env::temp_dir().join("ruffle_sql_attach_test.db");
+        let sql = format!("ATTACH DATABASE '{}' AS other", path.display());
+        assert!(databases.execute(handle, &sql, &[]).is_err());
+        assert!(databases
+            .execute(handle, "ATTACH DATABASE ':memory:' AS other", &[])
+            .is_err());
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_vacuum_into_is_denied() {
+        let (mut databases, handle) = open_in_memory();
+        let path = std::env::temp_dir().join("ruffle_sql_vacuum_test.db");
+        let sql = format!("VACUUM INTO '{}'", path.display());
+        assert!(databases.execute(handle, &sql, &[]).is_err());
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_load_extension_is_denied() {
+        let (mut databases, handle) = open_in_memory();
+        assert!(databases
+            .execute(handle, "SELECT load_extension('ruffle_test')", &[])
+            .is_err());
+    }
+
+    #[test]
+    fn test_queries_are_allowed() {
+        let (mut databases, handle) = open_in_memory();
+        databases
+            .execute(handle, "CREATE TABLE t (value INTEGER)", &[])
+            .unwrap();
+        databases
+            .execute(
+                handle,
+                "INSERT INTO t VALUES (:value)",
+                &[(":value".to_string(), SqlValue::Integer(5))],
+            )
+            .unwrap();
+        let result = databases
+            .execute(handle, "SELECT value FROM t", &[])
+            .unwrap();
+        assert_eq!(result.columns, vec!["value".to_string()]);
+        assert_eq!(result.rows.len(), 1);
+    }
+}
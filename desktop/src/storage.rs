@@ -1,5 +1,8 @@
 use anyhow::{Context, Error};
-use ruffle_core::backend::storage::StorageBackend;
+use ruffle_core::backend::storage::{FileInfo, StorageBackend};
+#[cfg(feature = "sqlite")]
+use ruffle_core::backend::storage::{SqlOpenMode, SqlResult, SqlValue};
+use sha2::{Digest, Sha256};
 use std::fs;
 use std::fs::File;
 use std::io::Write;
@@ -8,6 +11,12 @@ use std::path::{Component, Path, PathBuf};
 pub struct DiskStorageBackend {
     base_path: PathBuf,
     shared_objects_path: PathBuf,
+
+    /// The directory exposed to AIR content through `flash.filesystem`, if any.
+    file_sandbox: Option<PathBuf>,
+
+    #[cfg(feature = "sqlite")]
+    databases: crate::sql::Databases,
 }
 
 impl DiskStorageBackend {
@@ -28,9 +37,56 @@ impl DiskStorageBackend {
         Ok(DiskStorageBackend {
             base_path,
             shared_objects_path,
+            file_sandbox: None,
+            #[cfg(feature = "sqlite")]
+            databases: Default::default(),
         })
     }
 
+    /// The default file sandbox of a movie, used when none is given on the command line.
+    pub fn default_file_sandbox(movie_name: &str) -> Option<PathBuf> {
+        Some(
+            dirs::data_local_dir()?
+                .join("ruffle")
+                .join("AIR")
+                .join(movie_name),
+        )
+    }
+
+    /// Gives AIR content access to the files in the given directory.
+    pub fn with_file_sandbox(mut self, path: PathBuf) -> Self {
+        if let Err(e) = fs::create_dir_all(&path) {
+            tracing::warn!("Unable to create file sandbox {}", e);
+        }
+        self.file_sandbox = Some(path);
+        self
+    }
+
+    fn get_encrypted_store_path(&self, content_id: &str) -> PathBuf {
+        let hash: String = Sha256::digest(content_id.as_bytes())
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect();
+        self.base_path
+            .join("EncryptedLocalStore")
+            .join(format!("{hash}.els"))
+    }
+
+    /// Resolves a path of the file sandbox, rejecting any path escaping it.
+    fn get_sandboxed_path(&self, path: &str) -> Option<PathBuf> {
+        let sandbox = self.file_sandbox.as_ref()?;
+        let relative = Path::new(path.trim_start_matches('/'));
+        if relative
+            .components()
+            .all(|c| matches!(c, Component::Normal(_) | Component::CurDir))
+        {
+            Some(sandbox.join(relative))
+        } else {
+            tracing::warn!("Refusing to access {} outside of the file sandbox", path);
+            None
+        }
+    }
+
     /// Verifies that the path contains no `..` components to prevent accessing files outside of the Ruffle directory.
     fn is_path_allowed(path: &Path) -> bool {
         path.components().all(|c| c != Component::ParentDir)
@@ -48,6 +104,22 @@ impl DiskStorageBackend {
     }
 }
 
+/// Makes a file of the encrypted store readable by the user only.
+///
+/// The store isn't actually encrypted, so this is what keeps it private. The permissions
+/// are set on every write, as the file may have been created with broader ones.
+/// On Windows, this relies on the data_local dir being private to the user instead.
+fn restrict_to_user(file: &File) -> std::io::Result<()> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        file.set_permissions(fs::Permissions::from_mode(0o600))?;
+    }
+    #[cfg(not(unix))]
+    let _ = file;
+    Ok(())
+}
+
 impl StorageBackend for DiskStorageBackend {
     fn get(&self, name: &str) -> Option<Vec<u8>> {
         let path = self.get_shared_object_path(name);
@@ -110,4 +182,221 @@ impl StorageBackend for DiskStorageBackend {
         }
         let _ = fs::remove_file(path);
     }
+
+    fn get_disk_usage(&self, prefix: &str) -> u64 {
+        fn directory_size(path: &Path) -> u64 {
+            let Ok(entries) = fs::read_dir(path) else {
+                return 0;
+            };
+            entries
+                .filter_map(Result::ok)
+                .map(|entry| match entry.file_type() {
+                    Ok(file_type) if file_type.is_dir() => directory_size(&entry.path()),
+                    Ok(_) => entry.metadata().map_or(0, |metadata| metadata.len()),
+                    Err(_) => 0,
+                })
+                .sum()
+        }
+
+        // Prefixes are whole path components, such as the domain of shared objects.
+        let path = self.shared_objects_path.join(prefix);
+        if !Self::is_path_allowed(&path) {
+            return 0;
+        }
+        directory_size(&path)
+    }
+
+    fn read_file(&self, path: &str) -> Option<Vec<u8>> {
+        let path = self.get_sandboxed_path(path)?;
+        match fs::read(path) {
+            Ok(data) => Some(data),
+            Err(e) => {
+                tracing::warn!("Unable to read file {:?}", e);
+                None
+            }
+        }
+    }
+
+    fn write_file(&mut self, path: &str, data: &[u8]) -> bool {
+        let Some(path) = self.get_sandboxed_path(path) else {
+            return false;
+        };
+        if let Some(parent_dir) = path.parent() {
+            if let Err(r) = fs::create_dir_all(parent_dir) {
+                tracing::warn!("Unable to create dir {}", r);
+                return false;
+            }
+        }
+        match fs::write(path, data) {
+            Ok(()) => true,
+            Err(r) => {
+                tracing::warn!("Unable to write file {:?}", r);
+                false
+            }
+        }
+    }
+
+    fn list_directory(&self, path: &str) -> Option<Vec<String>> {
+        let entries = match fs::read_dir(self.get_sandboxed_path(path)?) {
+            Ok(entries) => entries,
+            Err(e) => {
+                tracing::warn!("Unable to list directory {:?}", e);
+                return None;
+            }
+        };
+        let mut names: Vec<String> = entries
+            .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+            .collect();
+        names.sort();
+        Some(names)
+    }
+
+    fn create_directory(&mut self, path: &str) -> bool {
+        let Some(path) = self.get_sandboxed_path(path) else {
+            return false;
+        };
+        match fs::create_dir_all(path) {
+            Ok(()) => true,
+            Err(r) => {
+                tracing::warn!("Unable to create dir {}", r);
+                false
+            }
+        }
+    }
+
+    fn delete_file(&mut self, path: &str, recursive: bool) -> bool {
+        let Some(path) = self.get_sandboxed_path(path) else {
+            return false;
+        };
+        // Never delete the sandbox itself.
+        if Some(&path) == self.file_sandbox.as_ref() {
+            return false;
+        }
+        let result = if path.is_dir() {
+            if recursive {
+                fs::remove_dir_all(path)
+            } else {
+                fs::remove_dir(path)
+            }
+        } else {
+            fs::remove_file(path)
+        };
+        match result {
+            Ok(()) => true,
+            Err(r) => {
+                tracing::warn!("Unable to delete {:?}", r);
+                false
+            }
+        }
+    }
+
+    fn get_encrypted_store(&self, content_id: &str) -> Option<Vec<u8>> {
+        match fs::read(self.get_encrypted_store_path(content_id)) {
+            Ok(data) => Some(data),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+            Err(e) => {
+                tracing::warn!("Unable to read encrypted store {:?}", e);
+                None
+            }
+        }
+    }
+
+    fn put_encrypted_store(&mut self, content_id: &str, data: &[u8]) -> bool {
+        let path = self.get_encrypted_store_path(content_id);
+        if let Some(parent_dir) = path.parent() {
+            if let Err(r) = fs::create_dir_all(parent_dir) {
+                tracing::warn!("Unable to create storage dir {}", r);
+                return false;
+            }
+        }
+
+        let mut options = fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+        match options.open(&path).and_then(|mut file| {
+            restrict_to_user(&file)?;
+            file.write_all(data)
+        }) {
+            Ok(()) => true,
+            Err(r) => {
+                tracing::warn!("Unable to write encrypted store {:?}", r);
+                false
+            }
+        }
+    }
+
+    #[cfg(feature = "sqlite")]
+    fn open_database(&mut self, path: Option<&str>, mode: SqlOpenMode) -> Result<u32, String> {
+        let path = match path {
+            Some(path) => Some(
+                self.get_sandboxed_path(path)
+                    .ok_or_else(|| format!("{path} is outside of the file sandbox"))?,
+            ),
+            None => None,
+        };
+        self.databases.open(path.as_deref(), mode)
+    }
+
+    #[cfg(feature = "sqlite")]
+    fn execute_sql(
+        &mut self,
+        database: u32,
+        sql: &str,
+        parameters: &[(String, SqlValue)],
+    ) -> Result<SqlResult, String> {
+        self.databases.execute(database, sql, parameters)
+    }
+
+    #[cfg(feature = "sqlite")]
+    fn close_database(&mut self, database: u32) {
+        self.databases.close(database);
+    }
+
+    fn file_info(&self, path: &str) -> Option<FileInfo> {
+        let metadata = fs::metadata(self.get_sandboxed_path(path)?).ok()?;
+        Some(FileInfo {
+            is_directory: metadata.is_dir(),
+            size: if metadata.is_file() {
+                metadata.len()
+            } else {
+                0
+            },
+        })
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::os::unix::fs::PermissionsExt;
+
+    #[test]
+    fn test_encrypted_store_is_private() {
+        let base_path = std::env::temp_dir().join(format!("ruffle_storage_{}", std::process::id()));
+        let mut storage = DiskStorageBackend {
+            shared_objects_path: base_path.join("SharedObjects"),
+            base_path: base_path.clone(),
+            file_sandbox: None,
+            #[cfg(feature = "sqlite")]
+            databases: Default::default(),
+        };
+        let content_id = "file:///movie.swf";
+        let path = storage.get_encrypted_store_path(content_id);
+        let mode = |path: &Path| fs::metadata(path).unwrap().permissions().mode() & 0o777;
+
+        assert!(storage.put_encrypted_store(content_id, b"first"));
+        assert_eq!(mode(&path), 0o600);
+
+        // A store with broader permissions is made private again when it's written.
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o644)).unwrap();
+        assert!(storage.put_encrypted_store(content_id, b"second"));
+        assert_eq!(mode(&path), 0o600);
+        assert_eq!(
+            storage.get_encrypted_store(content_id).as_deref(),
+            Some(&b"second"[..])
+        );
+
+        let _ = fs::remove_dir_all(base_path);
+    }
 }
@@ -0,0 +1,241 @@
+//! Simulation of slow network connections, for reproducing issues with
+//! preloaders and streamed content.
+
+use ruffle_core::backend::navigator::{
+    NavigationMethod, NavigatorBackend, OwnedFuture, ProgressCallback, Request, Response,
+};
+use ruffle_core::indexmap::IndexMap;
+use ruffle_core::loader::Error;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+use std::time::{Duration, Instant};
+use url::Url;
+
+/// How often a new chunk of a throttled response arrives.
+const CHUNK_INTERVAL: Duration = Duration::from_millis(50);
+
+/// The network connection to simulate.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct NetworkConditions {
+    /// How long it takes before a response starts arriving.
+    pub latency: Duration,
+
+    /// How many bytes arrive per second, or `None` for no limit.
+    pub bandwidth: Option<f64>,
+}
+
+/// The connections that can be picked while the movie runs, with their names.
+const PRESETS: [(&str, Option<NetworkConditions>); 4] = [
+    ("no throttling", None),
+    (
+        "DSL (2 Mbps, 50 ms)",
+        Some(NetworkConditions {
+            latency: Duration::from_millis(50),
+            bandwidth: Some(250_000.0),
+        }),
+    ),
+    (
+        "ISDN (128 kbps, 100 ms)",
+        Some(NetworkConditions {
+            latency: Duration::from_millis(100),
+            bandwidth: Some(16_000.0),
+        }),
+    ),
+    (
+        "dial-up (56 kbps, 200 ms)",
+        Some(NetworkConditions {
+            latency: Duration::from_millis(200),
+            bandwidth: Some(7_000.0),
+        }),
+    ),
+];
+
+/// The network connection simulated by a `ThrottledNavigatorBackend`, which can be
+/// changed while the movie runs. Requests keep the conditions they started with.
+#[derive(Clone)]
+pub struct NetworkThrottle(Arc<Mutex<Option<NetworkConditions>>>);
+
+impl NetworkThrottle {
+    pub fn new(conditions: Option<NetworkConditions>) -> Self {
+        Self(Arc::new(Mutex::new(conditions)))
+    }
+
+    fn conditions(&self) -> Option<NetworkConditions> {
+        *self.0.lock().expect("Network throttle lock")
+    }
+
+    /// Switches to the next preset connection, and returns its name.
+    ///
+    /// Custom conditions from the command line switch to the first preset.
+    pub fn next_preset(&self) -> &'static str {
+        let mut conditions = self.0.lock().expect("Network throttle lock");
+        let current = PRESETS
+            .iter()
+            .position(|(_, preset)| *preset == *conditions);
+        let (name, preset) = PRESETS[current.map_or(0, |i| (i + 1) % PRESETS.len())];
+        *conditions = preset;
+        name
+    }
+}
+
+/// A thread waking up the requests waiting for throttled data.
+///
+/// Every request of a navigator shares the same thread, which sleeps until the
+/// earliest deadline and stops once no request can wait for it anymore.
+#[derive(Clone)]
+struct Timer(mpsc::Sender<(Instant, Waker)>);
+
+impl Timer {
+    fn new() -> Self {
+        let (sender, receiver) = mpsc::channel::<(Instant, Waker)>();
+        std::thread::Builder::new()
+            .name("Network throttle".to_owned())
+            .spawn(move || {
+                let mut waiting = Vec::new();
+                loop {
+                    let now = Instant::now();
+                    waiting.retain(|(deadline, waker): &(Instant, Waker)| {
+                        if *deadline <= now {
+                            waker.wake_by_ref();
+                            false
+                        } else {
+                            true
+                        }
+                    });
+
+                    let next = waiting.iter().map(|(deadline, _)| *deadline).min();
+                    let received = match next {
+                        Some(deadline) => receiver.recv_timeout(deadline - now),
+                        None => receiver.recv().map_err(|_| RecvTimeoutError::Disconnected),
+                    };
+                    match received {
+                        Ok(entry) => waiting.push(entry),
+                        Err(RecvTimeoutError::Timeout) => {}
+                        Err(RecvTimeoutError::Disconnected) => return,
+                    }
+                }
+            })
+            .expect("Couldn't start the network throttle timer");
+        Self(sender)
+    }
+
+    /// A future that completes once `deadline` has passed.
+    fn sleep_until(&self, deadline: Instant) -> Sleep {
+        Sleep {
+            timer: self.clone(),
+            deadline,
+        }
+    }
+}
+
+struct Sleep {
+    timer: Timer,
+    deadline: Instant,
+}
+
+impl Future for Sleep {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if Instant::now() >= self.deadline {
+            Poll::Ready(())
+        } else {
+            let _ = self.timer.0.send((self.deadline, cx.waker().clone()));
+            Poll::Pending
+        }
+    }
+}
+
+/// A navigator that delays every fetch made through another navigator as if
+/// it went over a slow connection.
+///
+/// The body of a response arrives in chunks at the simulated bandwidth, and the
+/// progress of loaded movies is reported as they do, so that their preloaders
+/// can be seen at work. The root movie is still only started once it's
+/// entirely downloaded.
+pub struct ThrottledNavigatorBackend<N: NavigatorBackend> {
+    inner: N,
+    throttle: NetworkThrottle,
+    timer: Timer,
+}
+
+impl<N: NavigatorBackend> ThrottledNavigatorBackend<N> {
+    pub fn new(inner: N, throttle: NetworkThrottle) -> Self {
+        Self {
+            inner,
+            throttle,
+            timer: Timer::new(),
+        }
+    }
+}
+
+impl<N: NavigatorBackend> NavigatorBackend for ThrottledNavigatorBackend<N> {
+    fn navigate_to_url(
+        &self,
+        url: &str,
+        target: &str,
+        vars_method: Option<(NavigationMethod, IndexMap<String, String>)>,
+    ) {
+        self.inner.navigate_to_url(url, target, vars_method)
+    }
+
+    fn absolute_url(&self, url: &str) -> Option<Url> {
+        self.inner.absolute_url(url)
+    }
+
+    fn fetch(&self, request: Request) -> OwnedFuture<Response, Error> {
+        self.fetch_with_progress(request, Box::new(|_, _| {}))
+    }
+
+    fn fetch_with_progress(
+        &self,
+        request: Request,
+        mut on_progress: ProgressCallback,
+    ) -> OwnedFuture<Response, Error> {
+        let Some(conditions) = self.throttle.conditions() else {
+            return self.inner.fetch_with_progress(request, on_progress);
+        };
+        let timer = self.timer.clone();
+        let url = request.url().to_owned();
+        let response = self.inner.fetch(request);
+
+        Box::pin(async move {
+            timer.sleep_until(Instant::now() + conditions.latency).await;
+            let response = response.await?;
+            let Some(bandwidth) = conditions.bandwidth.filter(|bandwidth| *bandwidth > 0.0) else {
+                return Ok(response);
+            };
+
+            let total = response.body.len();
+            tracing::debug!(
+                "Throttling {} ({} bytes) to {} bytes per second",
+                url,
+                total,
+                bandwidth
+            );
+            on_progress(0, total);
+            let start = Instant::now();
+            let mut deadline = start;
+            loop {
+                deadline += CHUNK_INTERVAL;
+                timer.sleep_until(deadline).await;
+                let loaded = (deadline - start).as_secs_f64() * bandwidth;
+                if loaded >= total as f64 {
+                    return Ok(response);
+                }
+                on_progress(loaded as usize, total);
+            }
+        })
+    }
+
+    fn spawn_future(&mut self, future: OwnedFuture<(), Error>) {
+        self.inner.spawn_future(future)
+    }
+
+    fn pre_process_url(&self, url: Url) -> Url {
+        self.inner.pre_process_url(url)
+    }
+}
@@ -0,0 +1,129 @@
+//! Writes the traces of each movie to its own log file.
+
+use ruffle_core::backend::log::LogBackend;
+use std::cell::RefCell;
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use url::Url;
+
+/// How large a log file may grow before it is rotated, in bytes.
+const MAX_FILE_SIZE: u64 = 1024 * 1024;
+
+/// How many rotated log files are kept for each movie.
+const MAX_ROTATED_FILES: u32 = 3;
+
+struct TraceFile {
+    file: File,
+    size: u64,
+}
+
+/// Log backend writing the traces of each movie to `<host>_<file name>.log`
+/// in a directory. When a log file is full, it is renamed to
+/// `<host>_<file name>.1.log`, and the older ones are shifted in turn.
+pub struct FileLogBackend {
+    directory: PathBuf,
+    files: RefCell<HashMap<String, TraceFile>>,
+}
+
+impl FileLogBackend {
+    pub fn new(directory: PathBuf) -> std::io::Result<Self> {
+        fs::create_dir_all(&directory)?;
+        Ok(Self {
+            directory,
+            files: RefCell::new(HashMap::new()),
+        })
+    }
+
+    /// The name of the log files of a movie, without their extension.
+    fn log_name(movie_url: &str) -> String {
+        let (host, file_name) = match Url::parse(movie_url) {
+            Ok(url) => (
+                url.host_str().unwrap_or("local").to_owned(),
+                url.path_segments()
+                    .and_then(|segments| segments.filter(|s| !s.is_empty()).last())
+                    .unwrap_or("movie")
+                    .to_owned(),
+            ),
+            Err(_) => ("local".to_owned(), "movie".to_owned()),
+        };
+        format!("{host}_{file_name}")
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_') {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect()
+    }
+
+    /// The path of a log file of a movie, 0 being the one written to.
+    fn path(&self, name: &str, index: u32) -> PathBuf {
+        if index == 0 {
+            self.directory.join(format!("{name}.log"))
+        } else {
+            self.directory.join(format!("{name}.{index}.log"))
+        }
+    }
+
+    fn rotate(&self, name: &str) -> std::io::Result<()> {
+        for index in (1..MAX_ROTATED_FILES).rev() {
+            let from = self.path(name, index);
+            if from.exists() {
+                fs::rename(from, self.path(name, index + 1))?;
+            }
+        }
+        fs::rename(self.path(name, 0), self.path(name, 1))
+    }
+
+    fn write(&self, movie_url: &str, message: &str) -> std::io::Result<()> {
+        let name = Self::log_name(movie_url);
+        let line_len = message.len() as u64 + 1;
+        let mut files = self.files.borrow_mut();
+
+        let is_full = files.get(&name).map_or(false, |trace_file| {
+            trace_file.size + line_len > MAX_FILE_SIZE
+        });
+        if is_full {
+            // The file is closed before renaming it, which Windows requires.
+            files.remove(&name);
+            self.rotate(&name)?;
+        }
+
+        let trace_file = match files.entry(name) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => {
+                let file = OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(self.path(entry.key(), 0))?;
+                let size = file.metadata()?.len();
+                entry.insert(TraceFile { file, size })
+            }
+        };
+        writeln!(trace_file.file, "{message}")?;
+        trace_file.size += line_len;
+        Ok(())
+    }
+}
+
+impl LogBackend for FileLogBackend {
+    fn avm_trace(&self, message: &str) {
+        tracing::info!(target: "avm_trace", "{}", message);
+    }
+
+    fn movie_trace(&self, movie_url: &str, message: &str) {
+        self.avm_trace(message);
+        if let Err(e) = self.write(movie_url, message) {
+            tracing::warn!(
+                "Couldn't write a trace of {} to its log file: {}",
+                movie_url,
+                e
+            );
+        }
+    }
+}
@@ -1,36 +1,75 @@
+use crate::custom_event::RuffleEvent;
 use anyhow::{Context, Error};
 use arboard::Clipboard;
 use rfd::{MessageButtons, MessageDialog, MessageLevel};
-use ruffle_core::backend::ui::{FullscreenError, MouseCursor, UiBackend};
+use ruffle_core::backend::ui::{FullscreenError, MouseCursor, SettingsPanel, UiBackend};
+use ruffle_core::i18n::{LanguageIdentifier, Localizations};
 use std::rc::Rc;
 use tracing::error;
+use winit::event_loop::EventLoopProxy;
 use winit::window::{Fullscreen, Window};
 
 pub struct DesktopUiBackend {
     window: Rc<Window>,
     cursor_visible: bool,
     clipboard: Clipboard,
+    event_loop: EventLoopProxy<RuffleEvent>,
+    localizations: Localizations,
+    language: LanguageIdentifier,
 }
 
 impl DesktopUiBackend {
-    pub fn new(window: Rc<Window>) -> Result<Self, Error> {
+    pub fn new(
+        window: Rc<Window>,
+        event_loop: EventLoopProxy<RuffleEvent>,
+        localizations: Localizations,
+        language: LanguageIdentifier,
+    ) -> Result<Self, Error> {
         Ok(Self {
             window,
             cursor_visible: true,
             clipboard: Clipboard::new().context("Couldn't get platform clipboard")?,
+            event_loop,
+            localizations,
+            language,
         })
     }
+
+    fn text(&self, id: &str) -> String {
+        self.localizations.text(&self.language, id)
+    }
 }
 
-// TODO: Move link to https://ruffle.rs/faq or similar
-const UNSUPPORTED_CONTENT_MESSAGE: &str = "\
-The Ruffle emulator may not yet fully support all of ActionScript 3 used by this content.
-Some parts of the content may not work as expected.
+/// The translations of the dialogs of the desktop player.
+// TODO: Move the link of the unsupported content message to https://ruffle.rs/faq or similar
+const TEXTS: &[(&str, &str)] = &[
+    ("en-US", include_str!("../assets/texts/en-US/dialogs.ftl")),
+    ("fr-FR", include_str!("../assets/texts/fr-FR/dialogs.ftl")),
+];
 
-See the following link for more info:
-https://github.com/ruffle-rs/ruffle/wiki/Frequently-Asked-Questions-For-Users";
+/// The translations of the core, along with those of the desktop player.
+pub fn localizations() -> Localizations {
+    let mut localizations = Localizations::new();
+    for (language, source) in TEXTS {
+        localizations
+            .add_resource(language.parse().expect("Valid language"), source)
+            .expect("Built-in texts should be valid");
+    }
+    localizations
+}
 
-const DOWNLOAD_FAILED_MESSAGE: &str = "Ruffle failed to open or download this file.";
+/// The language of the user, from the locale environment variables
+/// (such as `LANG=fr_FR.UTF-8`) on every platform that has them.
+pub fn system_language() -> Option<LanguageIdentifier> {
+    ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .iter()
+        .filter_map(|name| std::env::var(name).ok())
+        .find(|value| !value.is_empty())
+        .and_then(|value| {
+            let language = value.split('.').next().unwrap_or_default();
+            language.replace('_', "-").parse().ok()
+        })
+}
 
 impl UiBackend for DesktopUiBackend {
     fn mouse_visible(&self) -> bool {
@@ -71,8 +110,8 @@ impl UiBackend for DesktopUiBackend {
     fn display_unsupported_message(&self) {
         let dialog = MessageDialog::new()
             .set_level(MessageLevel::Warning)
-            .set_title("Ruffle - Unsupported content")
-            .set_description(UNSUPPORTED_CONTENT_MESSAGE)
+            .set_title(&self.text("unsupported-content-title"))
+            .set_description(&self.text("unsupported-content-message"))
             .set_buttons(MessageButtons::Ok);
         dialog.show();
     }
@@ -80,8 +119,8 @@ impl UiBackend for DesktopUiBackend {
     fn display_root_movie_download_failed_message(&self) {
         let dialog = MessageDialog::new()
             .set_level(MessageLevel::Warning)
-            .set_title("Ruffle - Load failed")
-            .set_description(DOWNLOAD_FAILED_MESSAGE)
+            .set_title(&self.text("download-failed-title"))
+            .set_description(&self.text("download-failed-message"))
             .set_buttons(MessageButtons::Ok);
         dialog.show();
     }
@@ -89,7 +128,7 @@ impl UiBackend for DesktopUiBackend {
     fn message(&self, message: &str) {
         let dialog = MessageDialog::new()
             .set_level(MessageLevel::Info)
-            .set_title("Ruffle")
+            .set_title(&self.text("dialog-title"))
             .set_description(message)
             .set_buttons(MessageButtons::Ok);
         dialog.show();
@@ -97,4 +136,33 @@ impl UiBackend for DesktopUiBackend {
 
     // Unused on desktop
     fn open_virtual_keyboard(&self) {}
+
+    fn exit(&mut self, code: i32) {
+        if self.event_loop.send_event(RuffleEvent::Exit(code)).is_err() {
+            tracing::error!("Couldn't exit: the event loop is gone");
+        }
+    }
+
+    fn display_settings_panel(&mut self, panel: SettingsPanel) {
+        if self
+            .event_loop
+            .send_event(RuffleEvent::ShowSettings(panel))
+            .is_err()
+        {
+            tracing::error!("Couldn't open the settings: the event loop is gone");
+        }
+    }
+
+    fn display_script_timeout_prompt(&mut self, message: &str) -> bool {
+        MessageDialog::new()
+            .set_level(MessageLevel::Warning)
+            .set_title(&self.text("script-timeout-title"))
+            .set_description(message)
+            .set_buttons(MessageButtons::YesNo)
+            .show()
+    }
+
+    fn language(&self) -> LanguageIdentifier {
+        self.language.clone()
+    }
 }
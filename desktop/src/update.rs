@@ -0,0 +1,481 @@
+//! Optional checking for, and downloading of, newer Ruffle builds.
+//!
+//! Releases are discovered through the GitHub releases of the Ruffle
+//! repository. A downloaded build is only kept if it's described by a signed
+//! manifest (the `<asset>.manifest.json` and `<asset>.manifest.json.sig` release
+//! assets). The detached Ed25519 signature must verify against the public key
+//! baked into this build through `RUFFLE_UPDATE_PUBLIC_KEY`.
+//!
+//! The manifest names the release and the date of the build along with the
+//! hash of the build, so that an older build can't be published again as a
+//! newer release.
+
+use anyhow::{anyhow, Context, Error};
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+use isahc::{config::RedirectPolicy, prelude::*, HttpClient};
+use sha2::{Digest, Sha256};
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+
+const RELEASES_URL: &str = "https://api.github.com/repos/ruffle-rs/ruffle/releases?per_page=30";
+
+/// The hex encoded Ed25519 public key that release builds are signed with.
+/// Builds without a key can still tell the user about updates, but will not
+/// download them.
+const PUBLIC_KEY: Option<&str> = option_env!("RUFFLE_UPDATE_PUBLIC_KEY");
+
+/// The suffix of the release asset that contains this platform's build.
+#[cfg(target_os = "windows")]
+const ASSET_SUFFIX: &str = "windows-x86_64.zip";
+#[cfg(target_os = "macos")]
+const ASSET_SUFFIX: &str = "macos-universal.tar.gz";
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+const ASSET_SUFFIX: &str = "linux-x86_64.tar.gz";
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, Eq, PartialEq)]
+pub enum UpdateChannel {
+    /// Full releases only.
+    Stable,
+
+    /// Nightly builds, which are published as pre-releases.
+    Nightly,
+}
+
+impl Default for UpdateChannel {
+    fn default() -> Self {
+        if cfg!(nightly) {
+            Self::Nightly
+        } else {
+            Self::Stable
+        }
+    }
+}
+
+/// A newer build than the one that is running.
+#[derive(Clone, Debug)]
+pub struct Update {
+    /// The name of the release, such as `nightly-2023-05-01`.
+    pub name: String,
+
+    /// The page describing the release, for users to read the changes.
+    pub page_url: String,
+
+    /// The tag of the release, which its manifest must name.
+    tag: String,
+
+    /// The day the release was published, as `YYYY-MM-DD`.
+    published: String,
+
+    /// The commit date of the running build, as `YYYY-MM-DD`.
+    build_date: String,
+
+    /// The build for this platform, if the release has one.
+    asset: Option<Asset>,
+}
+
+#[derive(Clone, Debug)]
+struct Asset {
+    name: String,
+    url: String,
+    manifest_url: Option<String>,
+    signature_url: Option<String>,
+}
+
+impl Update {
+    /// Whether this update can be downloaded and verified by this build.
+    pub fn can_download(&self) -> bool {
+        PUBLIC_KEY.is_some()
+            && self.asset.as_ref().map_or(false, |asset| {
+                asset.manifest_url.is_some() && asset.signature_url.is_some()
+            })
+    }
+}
+
+/// The signed description of a build.
+#[derive(Debug)]
+struct Manifest {
+    /// The tag of the release that the build was made for.
+    tag: String,
+
+    /// The day the build was made, as `YYYY-MM-DD`.
+    date: String,
+
+    /// The name of the release asset containing the build.
+    asset: String,
+
+    /// The hex encoded SHA-256 hash of the build.
+    sha256: String,
+}
+
+impl Manifest {
+    fn parse(data: &[u8]) -> Result<Self, Error> {
+        let manifest: serde_json::Value =
+            serde_json::from_slice(data).context("The update's manifest isn't valid JSON")?;
+        let field = |name: &str| {
+            manifest[name]
+                .as_str()
+                .map(str::to_owned)
+                .ok_or_else(|| anyhow!("The update's manifest has no {name}"))
+        };
+        Ok(Self {
+            tag: field("tag")?,
+            date: field("date")?,
+            asset: field("asset")?,
+            sha256: field("sha256")?,
+        })
+    }
+
+    /// Checks that this manifest describes the given asset of the update, rather than
+    /// an older build.
+    fn check(&self, update: &Update, asset: &Asset) -> Result<(), Error> {
+        if self.tag != update.tag || self.asset != asset.name {
+            return Err(anyhow!(
+                "The update's manifest describes {} of {}",
+                self.asset,
+                self.tag
+            ));
+        }
+        if self.date.len() != 10 || self.date > update.published {
+            return Err(anyhow!(
+                "The update's manifest is dated {}, but it was published on {}",
+                self.date,
+                update.published
+            ));
+        }
+        if self.date <= update.build_date {
+            return Err(anyhow!(
+                "The update was built on {}, which isn't newer than this build",
+                self.date
+            ));
+        }
+        Ok(())
+    }
+
+    /// Checks that the downloaded build is the one this manifest describes.
+    fn check_data(&self, data: &[u8]) -> Result<(), Error> {
+        let expected = decode_hex(&self.sha256)
+            .ok_or_else(|| anyhow!("The update's manifest has an invalid hash"))?;
+        if Sha256::digest(data).as_slice() != expected.as_slice() {
+            return Err(anyhow!("The downloaded update doesn't match its manifest"));
+        }
+        Ok(())
+    }
+}
+
+/// The file remembering whether the user wants to check for updates on startup.
+fn preference_path() -> Option<PathBuf> {
+    Some(
+        dirs::data_local_dir()?
+            .join("ruffle")
+            .join("check_for_updates"),
+    )
+}
+
+/// Whether the user chose to check for updates on startup, or `None` if they weren't asked yet.
+pub fn automatic_checks() -> Option<bool> {
+    std::fs::read_to_string(preference_path()?)
+        .ok()?
+        .trim()
+        .parse()
+        .ok()
+}
+
+/// Remembers whether the user wants to check for updates on startup.
+pub fn set_automatic_checks(enabled: bool) -> Result<(), Error> {
+    let path = preference_path().context("Couldn't find a valid data_local dir")?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(&path, enabled.to_string())
+        .with_context(|| format!("Couldn't write {}", path.display()))
+}
+
+fn http_client() -> Result<HttpClient, Error> {
+    Ok(HttpClient::builder()
+        .redirect_policy(RedirectPolicy::Follow)
+        .default_header("User-Agent", "Ruffle")
+        .build()?)
+}
+
+fn fetch(client: &HttpClient, url: &str) -> Result<Vec<u8>, Error> {
+    let mut response = client
+        .get(url)
+        .with_context(|| format!("Couldn't fetch {url}"))?;
+    if !response.status().is_success() {
+        return Err(anyhow!("Couldn't fetch {url}: {}", response.status()));
+    }
+    let mut body = vec![];
+    response.copy_to(&mut body)?;
+    Ok(body)
+}
+
+/// Extracts the commit date (`YYYY-MM-DD`) from the version string of this build.
+fn build_date(version: &str) -> Option<&str> {
+    let date = version.trim_end().strip_suffix(')')?.rsplit(' ').next()?;
+    (date.len() == 10).then_some(date)
+}
+
+/// Looks for a release on the given channel that was published after this
+/// build was made.
+pub fn check_for_update(channel: UpdateChannel, version: &str) -> Result<Option<Update>, Error> {
+    let Some(build_date) = build_date(version) else {
+        return Err(anyhow!("This build has no version information"));
+    };
+
+    let client = http_client()?;
+    let releases: serde_json::Value = serde_json::from_slice(&fetch(&client, RELEASES_URL)?)?;
+    let releases = releases
+        .as_array()
+        .ok_or_else(|| anyhow!("Unexpected response from {RELEASES_URL}"))?;
+
+    // Releases are listed from newest to oldest.
+    let release = releases.iter().find(|release| {
+        let prerelease = release["prerelease"].as_bool().unwrap_or(false);
+        match channel {
+            UpdateChannel::Stable => !prerelease,
+            UpdateChannel::Nightly => prerelease,
+        }
+    });
+    let Some(release) = release else {
+        return Ok(None);
+    };
+
+    let Some(published) = release["published_at"]
+        .as_str()
+        .and_then(|published| published.get(..10))
+    else {
+        return Ok(None);
+    };
+    if published <= build_date {
+        return Ok(None);
+    }
+
+    let assets = release["assets"]
+        .as_array()
+        .map(Vec::as_slice)
+        .unwrap_or(&[]);
+    let asset_url = |name: &str| {
+        assets
+            .iter()
+            .find(|asset| asset["name"].as_str() == Some(name))
+            .and_then(|asset| asset["browser_download_url"].as_str())
+            .map(str::to_owned)
+    };
+    let asset = assets
+        .iter()
+        .filter_map(|asset| asset["name"].as_str())
+        .find(|name| name.ends_with(ASSET_SUFFIX))
+        .and_then(|name| {
+            Some(Asset {
+                name: name.to_owned(),
+                url: asset_url(name)?,
+                manifest_url: asset_url(&format!("{name}.manifest.json")),
+                signature_url: asset_url(&format!("{name}.manifest.json.sig")),
+            })
+        });
+
+    let tag = release["tag_name"].as_str().unwrap_or_default();
+    Ok(Some(Update {
+        name: release["name"]
+            .as_str()
+            .or_else(|| release["tag_name"].as_str())
+            .unwrap_or("unknown")
+            .to_owned(),
+        page_url: release["html_url"].as_str().unwrap_or_default().to_owned(),
+        tag: tag.to_owned(),
+        published: published.to_owned(),
+        build_date: build_date.to_owned(),
+        asset,
+    }))
+}
+
+fn verify(data: &[u8], signature: &[u8], public_key: &str) -> Result<(), Error> {
+    let public_key = decode_hex(public_key)
+        .and_then(|key| <[u8; 32]>::try_from(key).ok())
+        .ok_or_else(|| anyhow!("Invalid update public key"))?;
+    let public_key = VerifyingKey::from_bytes(&public_key)?;
+    let signature = Signature::from_slice(signature)?;
+    public_key
+        .verify(data, &signature)
+        .context("The update's signature doesn't match")
+}
+
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    let hex = hex.trim();
+    if hex.len() % 2 != 0 {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+/// The name to save an asset as. It comes from the release, so it must not lead out of
+/// the directory it's saved to.
+fn asset_file_name(name: &str) -> Result<&OsStr, Error> {
+    Path::new(name)
+        .file_name()
+        .ok_or_else(|| anyhow!("{name} isn't a valid file name"))
+}
+
+/// Downloads the build for this platform, verifies it against its signed
+/// manifest, and saves it to the user's downloads directory. Returns the path
+/// it was saved to.
+pub fn download_update(update: &Update) -> Result<PathBuf, Error> {
+    let public_key = PUBLIC_KEY.ok_or_else(|| anyhow!("This build can't verify updates"))?;
+    let asset = update
+        .asset
+        .as_ref()
+        .ok_or_else(|| anyhow!("{} has no build for this platform", update.name))?;
+    let (Some(manifest_url), Some(signature_url)) = (&asset.manifest_url, &asset.signature_url)
+    else {
+        return Err(anyhow!("{} isn't signed", asset.name));
+    };
+    let file_name = asset_file_name(&asset.name)?;
+
+    let client = http_client()?;
+    let manifest = fetch(&client, manifest_url)?;
+    let signature = fetch(&client, signature_url)?;
+    verify(&manifest, &signature, public_key)?;
+    let manifest = Manifest::parse(&manifest)?;
+    manifest.check(update, asset)?;
+    let data = fetch(&client, &asset.url)?;
+    manifest.check_data(&data)?;
+
+    let dir = dirs::download_dir()
+        .or_else(|| dirs::data_local_dir().map(|dir| dir.join("ruffle").join("updates")))
+        .ok_or_else(|| anyhow!("Couldn't find a directory to save the update to"))?;
+    std::fs::create_dir_all(&dir)?;
+    let path = dir.join(file_name);
+    std::fs::write(&path, data).with_context(|| format!("Couldn't save {}", path.display()))?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ed25519_dalek::{Signer, SigningKey};
+
+    const BUILD: &[u8] = b"The build of the update";
+
+    fn update() -> (Update, Asset) {
+        let asset = Asset {
+            name: "ruffle-nightly-2023_05_03-linux-x86_64.tar.gz".to_owned(),
+            url: String::new(),
+            manifest_url: None,
+            signature_url: None,
+        };
+        let update = Update {
+            name: "Nightly 2023-05-03".to_owned(),
+            page_url: String::new(),
+            tag: "nightly-2023-05-03".to_owned(),
+            published: "2023-05-03".to_owned(),
+            build_date: "2023-05-01".to_owned(),
+            asset: Some(asset.clone()),
+        };
+        (update, asset)
+    }
+
+    fn manifest_json(tag: &str, date: &str, asset: &str) -> Vec<u8> {
+        let sha256: String = Sha256::digest(BUILD)
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect();
+        serde_json::json!({ "tag": tag, "date": date, "asset": asset, "sha256": sha256 })
+            .to_string()
+            .into_bytes()
+    }
+
+    /// Whether a manifest with the given fields is accepted for the build of `update()`.
+    fn is_accepted(tag: &str, date: &str, asset: &str) -> bool {
+        let (update, update_asset) = update();
+        let manifest = Manifest::parse(&manifest_json(tag, date, asset)).unwrap();
+        manifest.check(&update, &update_asset).is_ok() && manifest.check_data(BUILD).is_ok()
+    }
+
+    #[test]
+    fn test_manifest_of_update() {
+        let (_, asset) = update();
+        assert!(is_accepted("nightly-2023-05-03", "2023-05-02", &asset.name));
+        assert!(is_accepted("nightly-2023-05-03", "2023-05-03", &asset.name));
+    }
+
+    #[test]
+    fn test_manifest_of_other_release() {
+        let (_, asset) = update();
+        assert!(!is_accepted(
+            "nightly-2023-05-02",
+            "2023-05-02",
+            &asset.name
+        ));
+        assert!(!is_accepted(
+            "nightly-2023-05-03",
+            "2023-05-02",
+            "ruffle-nightly-2023_05_03-windows-x86_64.zip"
+        ));
+    }
+
+    #[test]
+    fn test_manifest_of_older_build() {
+        // An old build, published again as a newer release.
+        let (_, asset) = update();
+        assert!(!is_accepted(
+            "nightly-2023-05-03",
+            "2023-04-20",
+            &asset.name
+        ));
+        assert!(!is_accepted(
+            "nightly-2023-05-03",
+            "2023-05-01",
+            &asset.name
+        ));
+        // A manifest can't be signed after the release was published.
+        assert!(!is_accepted(
+            "nightly-2023-05-03",
+            "2023-05-04",
+            &asset.name
+        ));
+    }
+
+    #[test]
+    fn test_manifest_of_other_build() {
+        let (update, asset) = update();
+        let manifest = Manifest::parse(&manifest_json(
+            "nightly-2023-05-03",
+            "2023-05-02",
+            &asset.name,
+        ))
+        .unwrap();
+        assert!(manifest.check(&update, &asset).is_ok());
+        assert!(manifest.check_data(b"Another build").is_err());
+    }
+
+    #[test]
+    fn test_verify_manifest() {
+        let key = SigningKey::from_bytes(&[7; 32]);
+        let public_key: String = key
+            .verifying_key()
+            .as_bytes()
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect();
+        let manifest = manifest_json("nightly-2023-05-03", "2023-05-02", "ruffle.tar.gz");
+        let signature = key.sign(&manifest).to_bytes();
+        assert!(verify(&manifest, &signature, &public_key).is_ok());
+
+        let other = manifest_json("nightly-2023-05-03", "2023-05-03", "ruffle.tar.gz");
+        assert!(verify(&other, &signature, &public_key).is_err());
+    }
+
+    #[test]
+    fn test_asset_file_name() {
+        assert_eq!(
+            asset_file_name("ruffle-linux-x86_64.tar.gz").unwrap(),
+            "ruffle-linux-x86_64.tar.gz"
+        );
+        assert_eq!(asset_file_name("../../.bashrc").unwrap(), ".bashrc");
+        assert!(asset_file_name("..").is_err());
+        assert!(asset_file_name("").is_err());
+    }
+}
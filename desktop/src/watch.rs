@@ -0,0 +1,53 @@
+//! Watches the file of a local movie, to reload it when it's rebuilt during development.
+//!
+//! The modification time and size of the file are polled, and a change is only reported
+//! once they stay the same for a whole poll, so that a movie still being written by an
+//! authoring tool isn't loaded half-written.
+
+use crate::custom_event::RuffleEvent;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use winit::event_loop::EventLoopProxy;
+
+/// How often the file is checked for changes.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// The state of the file that changes are detected from.
+#[derive(Clone, Copy, PartialEq, Eq)]
+struct FileState {
+    modified: SystemTime,
+    len: u64,
+}
+
+impl FileState {
+    fn read(path: &Path) -> Option<Self> {
+        let metadata = std::fs::metadata(path).ok()?;
+        Some(Self {
+            modified: metadata.modified().ok()?,
+            len: metadata.len(),
+        })
+    }
+}
+
+/// Sends `RuffleEvent::MovieChanged` whenever the file at `path` changes, until the event
+/// loop exits.
+pub fn watch_movie(path: PathBuf, event_loop: EventLoopProxy<RuffleEvent>) {
+    std::thread::spawn(move || {
+        let mut loaded = FileState::read(&path);
+        let mut last = loaded;
+        loop {
+            std::thread::sleep(POLL_INTERVAL);
+            let current = FileState::read(&path);
+            // Wait for the file to settle, and ignore it while it's missing, as tools
+            // often delete a movie before writing its new version.
+            if current.is_some() && current == last && current != loaded {
+                loaded = current;
+                tracing::info!("{} changed, reloading", path.display());
+                if event_loop.send_event(RuffleEvent::MovieChanged).is_err() {
+                    return;
+                }
+            }
+            last = current;
+        }
+    });
+}
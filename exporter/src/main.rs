@@ -110,12 +110,10 @@ fn take_screenshot(
         .unwrap_or_else(|| movie.height().to_pixels());
     let height = (height * size.scale).round() as u32;
 
-    let target = TextureTarget::new(&descriptors.device, (width, height))
+    let renderer = WgpuRenderBackend::for_offscreen_with_descriptors((width, height), descriptors)
         .map_err(|e| anyhow!(e.to_string()))?;
     let player = PlayerBuilder::new()
-        .with_renderer(
-            WgpuRenderBackend::new(descriptors, target).map_err(|e| anyhow!(e.to_string()))?,
-        )
+        .with_renderer(renderer)
         .with_movie(movie)
         .with_viewport_dimensions(width, height, size.scale)
         .build();
@@ -13,20 +13,16 @@ use naga::{
 use num_traits::FromPrimitive;
 
 use crate::{
-    types::*, Error, ShaderType, VertexAttributeFormat, MAX_VERTEX_ATTRIBUTES, SHADER_ENTRY_POINT,
+    types::*, Error, ShaderType, VertexAttributeFormat, MAX_TEXTURES, MAX_VERTEX_ATTRIBUTES,
+    SHADER_ENTRY_POINT,
 };
 
 const VERTEX_PROGRAM_CONTANTS: u64 = 128;
 const FRAGMENT_PROGRAM_CONSTANTS: u64 = 28;
 
-const SAMPLER_REPEAT_LINEAR: usize = 0;
-const SAMPLER_REPEAT_NEAREST: usize = 1;
-const SAMPLER_CLAMP_LINEAR: usize = 2;
-const SAMPLER_CLAMP_NEAREST: usize = 3;
-const SAMPLER_CLAMP_U_REPEAT_V_LINEAR: usize = 4;
-const SAMPLER_CLAMP_U_REPEAT_V_NEAREST: usize = 5;
-const SAMPLER_REPEAT_U_CLAMP_V_LINEAR: usize = 6;
-const SAMPLER_REPEAT_U_CLAMP_V_NEAREST: usize = 7;
+// AGAL 2 (used by the 'standard' profiles) has more constant registers.
+const VERTEX_PROGRAM_CONTANTS_AGAL2: u64 = 250;
+const FRAGMENT_PROGRAM_CONSTANTS_AGAL2: u64 = 64;
 
 const TEXTURE_SAMPLER_START_BIND_INDEX: u32 = 2;
 const TEXTURE_START_BIND_INDEX: u32 = 10;
@@ -35,17 +31,6 @@ pub type Result<T> = std::result::Result<T, Error>;
 
 const SWIZZLE_XYZW: u8 = 0b11100100;
 
-struct TextureSamplers {
-    repeat_linear: Handle<Expression>,
-    repeat_nearest: Handle<Expression>,
-    clamp_linear: Handle<Expression>,
-    clamp_nearest: Handle<Expression>,
-    clamp_u_repeat_v_linear: Handle<Expression>,
-    clamp_u_repeat_v_nearest: Handle<Expression>,
-    repeat_u_clamp_v_linear: Handle<Expression>,
-    repeat_u_clamp_v_nearest: Handle<Expression>,
-}
-
 pub(crate) struct NagaBuilder<'a> {
     module: Module,
     func: Function,
@@ -99,7 +84,8 @@ pub(crate) struct NagaBuilder<'a> {
     imagecube: Handle<Type>,
 
     // For a fragment shader, our 4 bound texture samplers
-    texture_samplers: Option<TextureSamplers>,
+    // One sampler per texture sampler register
+    texture_samplers: Option<Vec<Handle<Expression>>>,
 
     // A stack of if/else blocks, using to push statements
     // into the correct block.
@@ -256,6 +242,61 @@ impl VertexAttributeFormat {
     }
 }
 
+fn read_header(data: &mut &[u8]) -> Result<(AgalVersion, ShaderType)> {
+    let mut header = [0; 7];
+    data.read_exact(&mut header)?;
+
+    if header[0] != 0xa0 {
+        return Err(Error::InvalidHeader);
+    }
+    let version = u32::from_le_bytes([header[1], header[2], header[3], header[4]]);
+
+    let version = match version {
+        1 => AgalVersion::Agal1,
+        2 => AgalVersion::Agal2,
+        _ => return Err(Error::InvalidVersion(version)),
+    };
+
+    if header[5] != 0xa1 {
+        return Err(Error::InvalidHeader);
+    }
+
+    let shader_type = match header[6] {
+        0x00 => ShaderType::Vertex,
+        0x01 => ShaderType::Fragment,
+        _ => return Err(Error::InvalidShaderType(header[6])),
+    };
+
+    Ok((version, shader_type))
+}
+
+pub(crate) fn extract_sampler_configs(
+    mut agal: &[u8],
+) -> Result<[Option<SamplerConfig>; MAX_TEXTURES]> {
+    let data = &mut agal;
+    read_header(data)?;
+
+    let mut configs = [None; MAX_TEXTURES];
+    while !data.is_empty() {
+        let mut token = [0; 24];
+        data.read_exact(&mut token)?;
+        let raw_opcode = u32::from_le_bytes(token[0..4].try_into().unwrap());
+        if !matches!(Opcode::from_u32(raw_opcode), Some(Opcode::Tex)) {
+            continue;
+        }
+
+        let sampler = SamplerField::parse(u64::from_le_bytes(token[16..24].try_into().unwrap()))?;
+        if let Some(config) = configs.get_mut(sampler.reg_num as usize) {
+            config.get_or_insert(SamplerConfig {
+                wrapping: sampler.wrapping,
+                filter: sampler.filter,
+                mipmap: sampler.mipmap,
+            });
+        }
+    }
+    Ok(configs)
+}
+
 /// Combines information extracted from the AGAL bytecode itself
 /// with information provided from the AVM side of ruffle
 /// (based on the Context3D methods that ActionSCript called)
@@ -263,7 +304,6 @@ impl VertexAttributeFormat {
 pub struct ShaderConfig<'a> {
     pub shader_type: ShaderType,
     pub vertex_attributes: &'a [Option<VertexAttributeFormat>; 8],
-    pub sampler_overrides: &'a [Option<SamplerOverride>; 8],
     pub version: AgalVersion,
 }
 
@@ -277,38 +317,13 @@ impl<'a> NagaBuilder<'a> {
     pub fn process_agal(
         mut agal: &[u8],
         vertex_attributes: &[Option<VertexAttributeFormat>; MAX_VERTEX_ATTRIBUTES],
-        sampler_overrides: &[Option<SamplerOverride>; 8],
     ) -> Result<Module> {
         let data = &mut agal;
-
-        let mut header = [0; 7];
-        data.read_exact(&mut header)?;
-
-        if header[0] != 0xa0 {
-            return Err(Error::InvalidHeader);
-        }
-        let version = u32::from_le_bytes([header[1], header[2], header[3], header[4]]);
-
-        let version = match version {
-            1 => AgalVersion::Agal1,
-            2 => AgalVersion::Agal2,
-            _ => return Err(Error::InvalidVersion(version)),
-        };
-
-        if header[5] != 0xa1 {
-            return Err(Error::InvalidHeader);
-        }
-
-        let shader_type = match header[6] {
-            0x00 => ShaderType::Vertex,
-            0x01 => ShaderType::Fragment,
-            _ => return Err(Error::InvalidShaderType(header[6])),
-        };
+        let (version, shader_type) = read_header(data)?;
 
         let mut builder = NagaBuilder::new(ShaderConfig {
             shader_type,
             vertex_attributes,
-            sampler_overrides,
             version,
         });
 
@@ -481,10 +496,20 @@ impl<'a> NagaBuilder<'a> {
                 specialization: None,
                 inner: ConstantInner::Scalar {
                     width: 4,
-                    value: ScalarValue::Uint(match shader_config.shader_type {
-                        ShaderType::Vertex => VERTEX_PROGRAM_CONTANTS,
-                        ShaderType::Fragment => FRAGMENT_PROGRAM_CONSTANTS,
-                    }),
+                    value: ScalarValue::Uint(
+                        match (&shader_config.shader_type, &shader_config.version) {
+                            (ShaderType::Vertex, AgalVersion::Agal1) => VERTEX_PROGRAM_CONTANTS,
+                            (ShaderType::Vertex, AgalVersion::Agal2) => {
+                                VERTEX_PROGRAM_CONTANTS_AGAL2
+                            }
+                            (ShaderType::Fragment, AgalVersion::Agal1) => {
+                                FRAGMENT_PROGRAM_CONSTANTS
+                            }
+                            (ShaderType::Fragment, AgalVersion::Agal2) => {
+                                FRAGMENT_PROGRAM_CONSTANTS_AGAL2
+                            }
+                        },
+                    ),
                 },
             },
             Span::UNDEFINED,
@@ -545,16 +570,7 @@ impl<'a> NagaBuilder<'a> {
                         .append(Expression::GlobalVariable(var), Span::UNDEFINED)
                 })
                 .collect::<Vec<_>>();
-            Some(TextureSamplers {
-                clamp_linear: samplers[SAMPLER_CLAMP_LINEAR],
-                clamp_nearest: samplers[SAMPLER_CLAMP_NEAREST],
-                repeat_linear: samplers[SAMPLER_REPEAT_LINEAR],
-                repeat_nearest: samplers[SAMPLER_REPEAT_NEAREST],
-                clamp_u_repeat_v_linear: samplers[SAMPLER_CLAMP_U_REPEAT_V_LINEAR],
-                clamp_u_repeat_v_nearest: samplers[SAMPLER_CLAMP_U_REPEAT_V_NEAREST],
-                repeat_u_clamp_v_linear: samplers[SAMPLER_REPEAT_U_CLAMP_V_LINEAR],
-                repeat_u_clamp_v_nearest: samplers[SAMPLER_REPEAT_U_CLAMP_V_NEAREST],
-            })
+            Some(samplers)
         } else {
             None
         };
@@ -1071,16 +1087,11 @@ impl<'a> NagaBuilder<'a> {
             Opcode::Tex => {
                 let sampler_field = source2.assert_sampler();
 
-                let texture_samplers = self.texture_samplers.as_ref().unwrap();
-
                 let texture_id = sampler_field.reg_num;
                 if sampler_field.reg_type != RegisterType::Sampler {
                     panic!("Invalid sample register type {:?}", sampler_field);
                 }
 
-                let mut filter = sampler_field.filter;
-                let mut wrapping = sampler_field.wrapping;
-
                 // See https://github.com/openfl/openfl/issues/1332
 
                 // FIXME - Flash Player seems to unconditionally use sampler overrides,
@@ -1091,30 +1102,32 @@ impl<'a> NagaBuilder<'a> {
                     panic!("Found ignore_sampler in {:?}", sampler_field);
                 }
 
-                if let Some(sampler_override) =
-                    &self.shader_config.sampler_overrides[texture_id as usize]
-                {
-                    filter = sampler_override.filter;
-                    wrapping = sampler_override.wrapping;
-                }
-
-                let sampler_binding = match (filter, wrapping) {
-                    (Filter::Linear, Wrapping::Clamp) => texture_samplers.clamp_linear,
-                    (Filter::Linear, Wrapping::Repeat) => texture_samplers.repeat_linear,
-                    (Filter::Linear, Wrapping::ClampURepeatV) => {
-                        texture_samplers.clamp_u_repeat_v_linear
-                    }
-                    (Filter::Linear, Wrapping::RepeatUClampV) => {
-                        texture_samplers.repeat_u_clamp_v_linear
-                    }
-                    (Filter::Nearest, Wrapping::Clamp) => texture_samplers.clamp_nearest,
-                    (Filter::Nearest, Wrapping::Repeat) => texture_samplers.repeat_nearest,
-                    (Filter::Nearest, Wrapping::ClampURepeatV) => {
-                        texture_samplers.clamp_u_repeat_v_nearest
-                    }
-                    (Filter::Nearest, Wrapping::RepeatUClampV) => {
-                        texture_samplers.repeat_u_clamp_v_nearest
-                    }
+                // The filtering and wrapping flags are handled by the sampler that
+                // the caller binds for this register - see `extract_sampler_configs`.
+                let sampler_binding = self.texture_samplers.as_ref().unwrap()[texture_id as usize];
+
+                // The bias is stored as a signed fixed-point value, scaled by 8.
+                let level = if sampler_field.texture_lod_bias != 0 {
+                    let bias = self.module.constants.append(
+                        Constant {
+                            name: None,
+                            specialization: None,
+                            inner: ConstantInner::Scalar {
+                                width: 4,
+                                value: ScalarValue::Float(
+                                    sampler_field.texture_lod_bias as f64 / 8.0,
+                                ),
+                            },
+                        },
+                        Span::UNDEFINED,
+                    );
+                    naga::SampleLevel::Bias(
+                        self.func
+                            .expressions
+                            .append(Expression::Constant(bias), Span::UNDEFINED),
+                    )
+                } else {
+                    naga::SampleLevel::Auto
                 };
 
                 let coord = self.emit_source_field_load(source1, false)?;
@@ -1154,8 +1167,7 @@ impl<'a> NagaBuilder<'a> {
                     coordinate: coord,
                     array_index: None,
                     offset: None,
-                    // FIXME - get this from 'LOD_bias' in the sampler field
-                    level: naga::SampleLevel::Auto,
+                    level,
                     depth_ref: None,
                     gather: None,
                 });
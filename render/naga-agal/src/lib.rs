@@ -42,7 +42,7 @@ pub enum ShaderType {
     Fragment,
 }
 
-pub use types::{Filter, Mipmap, SamplerOverride, Wrapping};
+pub use types::{Filter, Mipmap, SamplerConfig, Wrapping};
 
 /**
  * Compiles an Adobe AGAL shader to a Naga Module.
@@ -88,11 +88,27 @@ pub use types::{Filter, Mipmap, SamplerOverride, Wrapping};
  * * Program constants - An AGAL fragment shader has access to 28 program constants.
  *   These are mapped to a single Naga uniform buffer, with a binding id of 1.
  *
+ * * Samplers - Each of the 8 texture sampler registers gets its own Naga sampler,
+ *   with binding ids 2 to 9 (so `fs3` uses the sampler with binding id 5).
+ *   The filtering and wrapping flags encoded in `tex` instructions are *not*
+ *   baked into the shader - the caller is responsible for binding a matching
+ *   sampler (see `extract_sampler_configs`).
+ *
  */
 pub fn agal_to_naga(
     agal: &[u8],
     vertex_attributes: &[Option<VertexAttributeFormat>; MAX_VERTEX_ATTRIBUTES],
-    sampler_overrides: &[Option<SamplerOverride>; MAX_TEXTURES],
 ) -> Result<Module, Error> {
-    NagaBuilder::process_agal(agal, vertex_attributes, sampler_overrides)
+    NagaBuilder::process_agal(agal, vertex_attributes)
+}
+
+/// Returns the sampler state requested by the `tex` instructions in an AGAL shader,
+/// indexed by texture sampler register.
+///
+/// If a register is sampled by multiple `tex` instructions, the first one is used.
+/// Registers that are never sampled are `None`.
+pub fn extract_sampler_configs(
+    agal: &[u8],
+) -> Result<[Option<SamplerConfig>; MAX_TEXTURES], Error> {
+    builder::extract_sampler_configs(agal)
 }
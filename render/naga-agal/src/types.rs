@@ -127,20 +127,24 @@ impl SourceField {
     }
 }
 
-#[derive(FromPrimitive, Debug, Copy, Clone, PartialEq, Eq)]
+#[derive(FromPrimitive, Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub enum Filter {
     Nearest = 0,
     Linear = 1,
+    Anisotropic2x = 2,
+    Anisotropic4x = 3,
+    Anisotropic8x = 4,
+    Anisotropic16x = 5,
 }
 
-#[derive(FromPrimitive, Debug, Copy, Clone, PartialEq, Eq)]
+#[derive(FromPrimitive, Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub enum Mipmap {
     Disable = 0,
     Nearest = 1,
     Linear = 2,
 }
 
-#[derive(FromPrimitive, Debug, Copy, Clone, PartialEq, Eq)]
+#[derive(FromPrimitive, Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub enum Wrapping {
     Clamp = 0,
     Repeat = 1,
@@ -181,13 +185,25 @@ pub struct SamplerField {
     pub reg_type: RegisterType,
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
-pub struct SamplerOverride {
+/// The sampler state used for a texture, either taken from the flags of an AGAL `tex`
+/// instruction or set through `Context3D.setSamplerStateAt`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct SamplerConfig {
     pub wrapping: Wrapping,
     pub filter: Filter,
     pub mipmap: Mipmap,
 }
 
+impl Default for SamplerConfig {
+    fn default() -> Self {
+        SamplerConfig {
+            wrapping: Wrapping::Clamp,
+            filter: Filter::Nearest,
+            mipmap: Mipmap::Disable,
+        }
+    }
+}
+
 impl SamplerField {
     pub fn parse(val: u64) -> Result<SamplerField, Error> {
         let reg_num = (val & 0xFFFF) as u16;
@@ -10,6 +10,7 @@ use downcast_rs::{impl_downcast, Downcast};
 use gc_arena::{Collect, GcCell, MutationContext};
 use ruffle_wstr::WStr;
 use serde::{Deserialize, Serialize};
+use std::any::Any;
 use std::borrow::Cow;
 use std::fmt::Debug;
 use std::rc::Rc;
@@ -59,6 +60,19 @@ pub trait RenderBackend: Downcast {
 
     fn submit_frame(&mut self, clear: swf::Color, commands: CommandList);
 
+    /// Renders a frame like `submit_frame`, when only `region` of the viewport
+    /// changed since the previous frame, so that only that part needs to be presented.
+    ///
+    /// Backends that can't present part of a frame present all of it.
+    fn submit_frame_region(
+        &mut self,
+        clear: swf::Color,
+        commands: CommandList,
+        _region: PixelRegion,
+    ) {
+        self.submit_frame(clear, commands)
+    }
+
     fn register_bitmap(&mut self, bitmap: Bitmap) -> Result<BitmapHandle, Error>;
     fn update_texture(
         &mut self,
@@ -67,7 +81,25 @@ pub trait RenderBackend: Downcast {
         region: PixelRegion,
     ) -> Result<(), Error>;
 
-    fn create_context3d(&mut self) -> Result<Box<dyn Context3D>, Error>;
+    /// Wraps a texture created outside of Ruffle, such as a decoded video or
+    /// camera frame, as a bitmap without copying its pixels.
+    ///
+    /// `texture` is a texture of the backend's graphics API, such as a `wgpu::Texture`,
+    /// holding `width` by `height` RGBA pixels. The bitmap can only be drawn.
+    fn import_texture(
+        &mut self,
+        _texture: Box<dyn Any>,
+        _width: u32,
+        _height: u32,
+    ) -> Result<BitmapHandle, Error> {
+        Err(Error::Unimplemented(
+            format!("Importing textures into the {} renderer", self.name()).into(),
+        ))
+    }
+
+    /// Creates a new `Context3D` supporting at least the features of `profile`,
+    /// or returns `Error::UnsupportedContext3DProfile` if the device can't satisfy it.
+    fn create_context3d(&mut self, profile: Context3DProfile) -> Result<Box<dyn Context3D>, Error>;
     fn context3d_present(&mut self, context: &mut dyn Context3D) -> Result<(), Error>;
 
     fn debug_info(&self) -> Cow<'static, str>;
@@ -170,7 +202,7 @@ pub enum BufferUsage {
     StaticDraw,
 }
 
-#[derive(Collect)]
+#[derive(Collect, Copy, Clone, Debug)]
 #[collect(require_static)]
 pub enum ProgramType {
     Vertex,
@@ -183,6 +215,9 @@ pub trait Context3D: Collect + Downcast {
     // Whether or not we should actually render the texture
     // as part of stage rendering
     fn should_render(&self) -> bool;
+    // The BitmapHandle and size of the back buffer that we're currently drawing into.
+    // This is `None` if `configureBackBuffer` hasn't been called yet.
+    fn back_buffer(&self) -> Option<(BitmapHandle, u32, u32)>;
 
     // Get a 'disposed' handle - this is what we store in all IndexBuffer3D
     // objects after dispose() has been called.
@@ -336,6 +371,96 @@ impl Context3DTextureFilter {
     }
 }
 
+/// The feature level requested with `Stage3D.requestContext3D`.
+/// Variants are ordered from least to most capable.
+#[derive(Collect, Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[collect(require_static)]
+pub enum Context3DProfile {
+    BaselineConstrained,
+    Baseline,
+    BaselineExtended,
+    StandardConstrained,
+    Standard,
+    StandardExtended,
+}
+
+impl Context3DProfile {
+    pub fn from_wstr(s: &WStr) -> Option<Self> {
+        if s == b"baselineConstrained" {
+            Some(Context3DProfile::BaselineConstrained)
+        } else if s == b"baseline" {
+            Some(Context3DProfile::Baseline)
+        } else if s == b"baselineExtended" {
+            Some(Context3DProfile::BaselineExtended)
+        } else if s == b"standardConstrained" {
+            Some(Context3DProfile::StandardConstrained)
+        } else if s == b"standard" {
+            Some(Context3DProfile::Standard)
+        } else if s == b"standardExtended" {
+            Some(Context3DProfile::StandardExtended)
+        } else {
+            None
+        }
+    }
+
+    pub fn to_str(self) -> &'static str {
+        match self {
+            Context3DProfile::BaselineConstrained => "baselineConstrained",
+            Context3DProfile::Baseline => "baseline",
+            Context3DProfile::BaselineExtended => "baselineExtended",
+            Context3DProfile::StandardConstrained => "standardConstrained",
+            Context3DProfile::Standard => "standard",
+            Context3DProfile::StandardExtended => "standardExtended",
+        }
+    }
+
+    /// Whether this profile allows AGAL 2 programs and floating-point textures.
+    pub fn is_standard(self) -> bool {
+        self >= Context3DProfile::StandardConstrained
+    }
+
+    /// The largest width and height of a texture, or of the back buffer.
+    pub fn max_texture_size(self) -> u32 {
+        if self >= Context3DProfile::BaselineExtended {
+            4096
+        } else {
+            2048
+        }
+    }
+
+    /// The number of constant registers available to a program of the given type.
+    pub fn max_constant_registers(self, program_type: ProgramType) -> u32 {
+        match (program_type, self.is_standard()) {
+            (ProgramType::Vertex, false) => 128,
+            (ProgramType::Vertex, true) => 250,
+            (ProgramType::Fragment, false) => 28,
+            (ProgramType::Fragment, true) => 64,
+        }
+    }
+}
+
+#[derive(Collect, Copy, Clone, Debug)]
+#[collect(require_static)]
+pub enum Context3DMipFilter {
+    MipLinear,
+    MipNearest,
+    MipNone,
+}
+
+impl Context3DMipFilter {
+    pub fn from_wstr(s: &WStr) -> Option<Self> {
+        if s == b"miplinear" {
+            Some(Context3DMipFilter::MipLinear)
+        } else if s == b"mipnearest" {
+            Some(Context3DMipFilter::MipNearest)
+        } else if s == b"mipnone" {
+            Some(Context3DMipFilter::MipNone)
+        } else {
+            None
+        }
+    }
+}
+
 #[derive(Collect)]
 #[collect(no_drop)]
 pub enum Context3DCommand<'gc> {
@@ -413,6 +538,7 @@ pub enum Context3DCommand<'gc> {
         source: BitmapHandle,
         dest: Rc<dyn Texture>,
         layer: u32,
+        mip_level: u32,
     },
     SetTextureAt {
         sampler: u32,
@@ -437,6 +563,7 @@ pub enum Context3DCommand<'gc> {
         sampler: u32,
         wrap: Context3DWrapMode,
         filter: Context3DTextureFilter,
+        mip_filter: Context3DMipFilter,
     },
 }
 
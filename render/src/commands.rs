@@ -15,6 +15,22 @@ pub trait CommandHandler {
     fn pop_mask(&mut self);
 
     fn blend(&mut self, commands: CommandList, blend_mode: BlendMode);
+
+    /// Draws `maskee`, using the alpha channel of `mask` as an anti-aliased mask.
+    ///
+    /// Backends which can't composite alpha masks use a regular 1-bit stencil mask instead.
+    fn alpha_mask(&mut self, maskee: CommandList, mask: CommandList)
+    where
+        Self: Sized,
+    {
+        self.push_mask();
+        mask.clone().execute(self);
+        self.activate_mask();
+        maskee.execute(self);
+        self.deactivate_mask();
+        mask.execute(self);
+        self.pop_mask();
+    }
 }
 
 #[derive(Debug, Default, Clone)]
@@ -50,6 +66,7 @@ impl CommandList {
                 Command::DeactivateMask => handler.deactivate_mask(),
                 Command::PopMask => handler.pop_mask(),
                 Command::Blend(commands, blend_mode) => handler.blend(commands, blend_mode),
+                Command::AlphaMask { maskee, mask } => handler.alpha_mask(maskee, mask),
             }
         }
     }
@@ -128,6 +145,13 @@ impl CommandHandler for CommandList {
             self.commands.push(Command::Blend(commands, blend_mode));
         }
     }
+
+    #[inline]
+    fn alpha_mask(&mut self, maskee: CommandList, mask: CommandList) {
+        if self.maskers_in_progress <= 1 {
+            self.commands.push(Command::AlphaMask { maskee, mask });
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -154,4 +178,8 @@ pub enum Command {
     DeactivateMask,
     PopMask,
     Blend(CommandList, BlendMode),
+    AlphaMask {
+        maskee: CommandList,
+        mask: CommandList,
+    },
 }
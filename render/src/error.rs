@@ -2,6 +2,7 @@ use std::borrow::Cow;
 
 use thiserror::Error;
 
+use crate::backend::Context3DProfile;
 use crate::bitmap::BitmapHandle;
 
 #[derive(Error, Debug)]
@@ -39,4 +40,7 @@ pub enum Error {
 
     #[error("Not yet implemented: {0}")]
     Unimplemented(Cow<'static, str>),
+
+    #[error("Context3D profile {0:?} is not supported by the rendering device")]
+    UnsupportedContext3DProfile(Context3DProfile),
 }
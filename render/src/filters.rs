@@ -1,5 +1,5 @@
 use crate::bitmap::BitmapHandle;
-use swf::Color;
+use swf::{Color, Fixed16, Rectangle, Twips};
 
 #[derive(Debug, Clone)]
 pub enum Filter {
@@ -39,6 +39,128 @@ impl From<&swf::Filter> for Filter {
     }
 }
 
+impl Filter {
+    /// Calculates the area affected by this filter, when it's applied to `source_rect`.
+    ///
+    /// Blurs, glows and shadows extend past the edges of their source, so this can be larger
+    /// than `source_rect`. This is used by `BitmapData.generateFilterRect`, and to decide how
+    /// large a target is needed to apply the filter.
+    pub fn calculate_dest_rect(&self, source_rect: Rectangle<Twips>) -> Rectangle<Twips> {
+        match self {
+            Filter::BlurFilter(filter) => blur_rect(
+                source_rect,
+                filter.blur_x,
+                filter.blur_y,
+                filter.num_passes(),
+            ),
+            Filter::GlowFilter(filter) if !filter.is_inner() => blur_rect(
+                source_rect,
+                filter.blur_x,
+                filter.blur_y,
+                filter.num_passes(),
+            ),
+            Filter::DropShadowFilter(filter) if !filter.is_inner() => shadow_rect(
+                source_rect,
+                filter.blur_x,
+                filter.blur_y,
+                filter.num_passes(),
+                filter.angle,
+                filter.distance,
+                false,
+            ),
+            Filter::BevelFilter(filter) if !filter.is_inner() => shadow_rect(
+                source_rect,
+                filter.blur_x,
+                filter.blur_y,
+                filter.num_passes(),
+                filter.angle,
+                filter.distance,
+                true,
+            ),
+            Filter::GradientGlowFilter(filter) if !filter.is_inner() => shadow_rect(
+                source_rect,
+                filter.blur_x,
+                filter.blur_y,
+                filter.num_passes(),
+                filter.angle,
+                filter.distance,
+                false,
+            ),
+            Filter::GradientBevelFilter(filter) if !filter.is_inner() => shadow_rect(
+                source_rect,
+                filter.blur_x,
+                filter.blur_y,
+                filter.num_passes(),
+                filter.angle,
+                filter.distance,
+                true,
+            ),
+            // Inner effects and the remaining filters only change pixels inside of the source.
+            _ => source_rect,
+        }
+    }
+}
+
+/// Expands `rect` by the distance covered by a blur.
+fn blur_rect(
+    rect: Rectangle<Twips>,
+    blur_x: Fixed16,
+    blur_y: Fixed16,
+    passes: u8,
+) -> Rectangle<Twips> {
+    if !rect.is_valid() {
+        return rect;
+    }
+    let passes = f64::from(passes.max(1));
+    let pad_x = Twips::from_pixels((blur_x.to_f64() * passes / 2.0).ceil());
+    let pad_y = Twips::from_pixels((blur_y.to_f64() * passes / 2.0).ceil());
+    Rectangle {
+        x_min: rect.x_min - pad_x,
+        x_max: rect.x_max + pad_x,
+        y_min: rect.y_min - pad_y,
+        y_max: rect.y_max + pad_y,
+    }
+}
+
+/// Returns the union of `rect` and its blurred shadow, which is moved by `distance`
+/// in the direction of `angle`. Bevels also have a highlight in the opposite direction.
+fn shadow_rect(
+    rect: Rectangle<Twips>,
+    blur_x: Fixed16,
+    blur_y: Fixed16,
+    passes: u8,
+    angle: Fixed16,
+    distance: Fixed16,
+    is_bevel: bool,
+) -> Rectangle<Twips> {
+    if !rect.is_valid() {
+        return rect;
+    }
+    let blurred = blur_rect(rect.clone(), blur_x, blur_y, passes);
+    let (sin, cos) = angle.to_f64().sin_cos();
+    let distance = distance.to_f64();
+    let offset_x = Twips::from_pixels((cos * distance).round());
+    let offset_y = Twips::from_pixels((sin * distance).round());
+    let shadow = Rectangle {
+        x_min: blurred.x_min + offset_x,
+        x_max: blurred.x_max + offset_x,
+        y_min: blurred.y_min + offset_y,
+        y_max: blurred.y_max + offset_y,
+    };
+    let rect = rect.union(&shadow);
+    if is_bevel {
+        let highlight = Rectangle {
+            x_min: blurred.x_min - offset_x,
+            x_max: blurred.x_max - offset_x,
+            y_min: blurred.y_min - offset_y,
+            y_max: blurred.y_max - offset_y,
+        };
+        rect.union(&highlight)
+    } else {
+        rect
+    }
+}
+
 impl Default for Filter {
     fn default() -> Self {
         // A default colormatrix is a filter that essentially does nothing,
@@ -89,3 +211,55 @@ impl Default for DisplacementMapFilter {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use swf::{BlurFilterFlags, DropShadowFilterFlags, Fixed8};
+
+    fn rect(x: i32, y: i32, width: i32, height: i32) -> Rectangle<Twips> {
+        Rectangle {
+            x_min: Twips::from_pixels_i32(x),
+            y_min: Twips::from_pixels_i32(y),
+            x_max: Twips::from_pixels_i32(x + width),
+            y_max: Twips::from_pixels_i32(y + height),
+        }
+    }
+
+    #[test]
+    fn blur_dest_rect() {
+        let filter = Filter::BlurFilter(swf::BlurFilter {
+            blur_x: Fixed16::from_f64(4.0),
+            blur_y: Fixed16::from_f64(8.0),
+            flags: BlurFilterFlags::from_passes(2),
+        });
+        assert_eq!(
+            filter.calculate_dest_rect(rect(10, 10, 100, 50)),
+            rect(6, 2, 108, 66)
+        );
+    }
+
+    #[test]
+    fn drop_shadow_dest_rect() {
+        let mut filter = swf::DropShadowFilter {
+            color: Color::BLACK,
+            blur_x: Fixed16::from_f64(2.0),
+            blur_y: Fixed16::from_f64(2.0),
+            angle: Fixed16::ZERO,
+            distance: Fixed16::from_f64(10.0),
+            strength: Fixed8::ONE,
+            flags: DropShadowFilterFlags::from_passes(1),
+        };
+        assert_eq!(
+            Filter::DropShadowFilter(filter.clone()).calculate_dest_rect(rect(0, 0, 100, 100)),
+            rect(0, -1, 111, 102)
+        );
+
+        // Inner shadows never leave the source.
+        filter.flags |= DropShadowFilterFlags::INNER_SHADOW;
+        assert_eq!(
+            Filter::DropShadowFilter(filter).calculate_dest_rect(rect(0, 0, 100, 100)),
+            rect(0, 0, 100, 100)
+        );
+    }
+}
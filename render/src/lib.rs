@@ -6,6 +6,7 @@ pub mod error;
 pub mod filters;
 pub mod matrix;
 pub mod shape_utils;
+pub mod svg;
 pub mod transform;
 pub mod utils;
 
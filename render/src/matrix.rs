@@ -166,6 +166,23 @@ impl Matrix {
             None
         }
     }
+
+    /// Linearly interpolates between this matrix and `other`.
+    ///
+    /// A `ratio` of `0.0` returns `self`, and a `ratio` of `1.0` returns `other`.
+    pub fn lerp(&self, other: &Self, ratio: f32) -> Self {
+        let lerp = |a: f32, b: f32| a + (b - a) * ratio;
+        let lerp_twips =
+            |a: Twips, b: Twips| Twips::new(round_to_i32(lerp(a.get() as f32, b.get() as f32)));
+        Matrix {
+            a: lerp(self.a, other.a),
+            b: lerp(self.b, other.b),
+            c: lerp(self.c, other.c),
+            d: lerp(self.d, other.d),
+            tx: lerp_twips(self.tx, other.tx),
+            ty: lerp_twips(self.ty, other.ty),
+        }
+    }
 }
 
 impl std::ops::Mul for Matrix {
@@ -906,6 +923,32 @@ mod tests {
             PointDelta::new(Twips::new(141), Twips::new(-7)),
         ),
     );
+
+    #[test]
+    fn lerp() {
+        let start = Matrix::translate(Twips::new(0), Twips::new(100));
+        let end = Matrix {
+            a: 3.0,
+            b: 0.0,
+            c: 0.0,
+            d: 2.0,
+            tx: Twips::new(200),
+            ty: Twips::new(-100),
+        };
+        assert_ulps_eq!(start.lerp(&end, 0.0), start);
+        assert_ulps_eq!(start.lerp(&end, 1.0), end);
+        assert_ulps_eq!(
+            start.lerp(&end, 0.5),
+            Matrix {
+                a: 2.0,
+                b: 0.0,
+                c: 0.0,
+                d: 1.5,
+                tx: Twips::new(100),
+                ty: Twips::new(0),
+            }
+        );
+    }
 }
 
 impl From<swf::Matrix> for Matrix {
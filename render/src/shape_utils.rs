@@ -561,7 +561,9 @@ impl<'a> ShapeConverter<'a> {
                 }
                 self.commands.push(DrawPath::Stroke {
                     style,
-                    is_closed: segment.is_closed(),
+                    // With the `NoClose` flag, Flash draws caps instead of a join where
+                    // the stroke ends at its starting point.
+                    is_closed: style.allow_close() && segment.is_closed(),
                     commands: segment.to_draw_commands().collect(),
                 });
             }
@@ -722,6 +724,72 @@ mod tests {
         }];
         assert_eq!(commands, expected);
     }
+
+    /// Builds a horizontal line from (0, 0) to (100, 0) with the given line style.
+    fn build_line(line_style: LineStyle) -> swf::Shape {
+        let mut shape = build_shape(vec![
+            ShapeRecord::StyleChange(Box::new(swf::StyleChangeData {
+                move_to: Some((Twips::ZERO, Twips::ZERO)),
+                fill_style_0: None,
+                fill_style_1: None,
+                line_style: Some(1),
+                new_styles: None,
+            })),
+            ShapeRecord::StraightEdge {
+                delta_x: Twips::from_pixels(100.0),
+                delta_y: Twips::ZERO,
+            },
+        ]);
+        shape.styles.line_styles = vec![line_style];
+        shape
+    }
+
+    fn hit_test_point(shape: &swf::Shape, x: f64, y: f64, local_matrix: &Matrix) -> bool {
+        shape_hit_test(
+            shape,
+            (Twips::from_pixels(x), Twips::from_pixels(y)),
+            local_matrix,
+        )
+    }
+
+    /// Points within half of the stroke width of a line hit it.
+    #[test]
+    fn hit_test_stroke_width() {
+        let shape = build_line(LineStyle::new().with_width(Twips::from_pixels(10.0)));
+        let identity = Matrix::IDENTITY;
+        assert!(hit_test_point(&shape, 50.0, 4.0, &identity));
+        assert!(hit_test_point(&shape, 50.0, -4.0, &identity));
+        assert!(!hit_test_point(&shape, 50.0, 6.0, &identity));
+        assert!(!hit_test_point(&shape, 110.0, 0.0, &identity));
+    }
+
+    /// Hairlines are hit as if they were 1 pixel wide on the stage.
+    #[test]
+    fn hit_test_hairline() {
+        let shape = build_line(LineStyle::new().with_width(Twips::new(1)));
+        assert!(hit_test_point(&shape, 50.0, 0.4, &Matrix::IDENTITY));
+        // When the shape is scaled up by 2, a stage pixel is half of a pixel in the shape.
+        let local_matrix = Matrix::scale(0.5, 0.5);
+        assert!(hit_test_point(&shape, 50.0, 0.2, &local_matrix));
+        assert!(!hit_test_point(&shape, 50.0, 0.4, &local_matrix));
+    }
+
+    /// Strokes that don't scale keep their width on the stage.
+    #[test]
+    fn hit_test_non_scaling_stroke() {
+        let line_style = LineStyle::new().with_width(Twips::from_pixels(10.0));
+        let scaling = build_line(line_style.clone());
+        let non_scaling = build_line(
+            line_style
+                .with_allow_scale_x(false)
+                .with_allow_scale_y(false),
+        );
+        // The shape is scaled up by 2.
+        let local_matrix = Matrix::scale(0.5, 0.5);
+        assert!(hit_test_point(&scaling, 50.0, 4.0, &local_matrix));
+        assert!(hit_test_point(&non_scaling, 50.0, 2.0, &local_matrix));
+        assert!(!hit_test_point(&non_scaling, 50.0, 4.0, &local_matrix));
+    }
 }
 
 /* SHAPEFLAG HITTEST (point-in-contour)
@@ -744,7 +812,8 @@ mod tests {
  *
  * For strokes, we calculate the distance to the line segment or curve and compare it to the stroke width.
  * Note that Flash renders with a minimum stroke width of 1px (20 twips) that we must account for.
- * TODO: We currently don't consider non-round endcaps or joins, or stroke scaling flags.
+ * Stroke scaling flags are respected the same way as when rendering.
+ * TODO: We currently don't consider non-round endcaps or joins.
  */
 
 /// Test whether the given point in object space is contained within the contour of the given shape.
@@ -762,7 +831,6 @@ pub fn shape_hit_test(
     let mut has_fill_style0: bool = false;
     let mut has_fill_style1: bool = false;
 
-    let min_width = stroke_minimum_width(local_matrix);
     let mut stroke_width = None;
     let mut line_styles = &shape.styles.line_styles;
 
@@ -792,14 +860,9 @@ pub fn shape_hit_test(
                 }
                 if let Some(i) = style_change.line_style {
                     stroke_width = if i > 0 {
-                        // Flash renders strokes with a 1px minimum width.
-                        if let Some(line_style) = line_styles.get(i as usize - 1) {
-                            let width = line_style.width().get() as f64;
-                            let scaled_width = 0.5 * width.max(min_width);
-                            Some((scaled_width, scaled_width * scaled_width))
-                        } else {
-                            None
-                        }
+                        line_styles
+                            .get(i as usize - 1)
+                            .map(|line_style| stroke_hit_test_width(line_style, local_matrix))
                     } else {
                         None
                     };
@@ -897,13 +960,11 @@ pub fn draw_command_fill_hit_test(commands: &[DrawCommand], test_point: (Twips,
 /// local_matrix is used to calculate the minimum stroke width.
 pub fn draw_command_stroke_hit_test(
     commands: &[DrawCommand],
-    stroke_width: Twips,
+    line_style: &LineStyle,
     (point_x, point_y): (Twips, Twips),
     local_matrix: &Matrix,
 ) -> bool {
-    let stroke_min_width = stroke_minimum_width(local_matrix);
-    let stroke_width = 0.5 * f64::max(stroke_width.get().into(), stroke_min_width);
-    let stroke_widths = (stroke_width, stroke_width * stroke_width);
+    let stroke_widths = stroke_hit_test_width(line_style, local_matrix);
     let mut x = Twips::default();
     let mut y = Twips::default();
     for command in commands {
@@ -938,15 +999,33 @@ pub fn draw_command_stroke_hit_test(
     false
 }
 
-/// Given a matrix, calculates the scale for stroke widths.
-/// TODO: Verify the actual behavior; I think it's more like the average between scaleX and scaleY.
-/// Does not yet support vertical/horizontal stroke scaling flags.
-/// This might be better to add as a method to Matrix.
-fn stroke_minimum_width(matrix: &Matrix) -> f64 {
-    let sx = (matrix.a * matrix.a + matrix.b * matrix.b).sqrt();
-    let sy = (matrix.c * matrix.c + matrix.d * matrix.d).sqrt();
-    let scale: f64 = sx.max(sy).into();
-    20.0 * scale
+/// Calculates the half-width of a stroke in local space, and its square, for use in hit tests.
+///
+/// The width is scaled the same way as when the stroke is rendered: it respects the
+/// scale mode of the line style, and is at least 1 pixel wide on the stage.
+fn stroke_hit_test_width(line_style: &LineStyle, local_matrix: &Matrix) -> (f64, f64) {
+    let width = f64::from(line_style.width().get());
+    let Some(world_matrix) = local_matrix.inverse() else {
+        return (0.5 * width, 0.25 * width * width);
+    };
+    let line_scale_x = f64::from((world_matrix.a + world_matrix.c).abs());
+    let line_scale_y = f64::from((world_matrix.b + world_matrix.d).abs());
+    let line_scale = ((line_scale_x * line_scale_x + line_scale_y * line_scale_y) / 2.0).sqrt();
+    let stage_width = match LineScaleMode::from(line_style) {
+        LineScaleMode::None => width,
+        LineScaleMode::Horizontal => width * line_scale_x,
+        LineScaleMode::Vertical => width * line_scale_y,
+        LineScaleMode::Both => width * line_scale,
+    };
+    // Flash draws all strokes with a minimum width of 1 pixel.
+    let stage_width = stage_width.max(Twips::TWIPS_PER_PIXEL as f64);
+    let local_width = if line_scale > 0.0 {
+        stage_width / line_scale
+    } else {
+        width
+    };
+    let half_width = 0.5 * local_width;
+    (half_width, half_width * half_width)
 }
 
 /// Returns whether the given point is inside the stroked line segment.
@@ -1360,6 +1439,17 @@ pub enum LineScaleMode {
     Both,
 }
 
+impl From<&LineStyle> for LineScaleMode {
+    fn from(style: &LineStyle) -> Self {
+        match (style.allow_scale_x(), style.allow_scale_y()) {
+            (false, false) => LineScaleMode::None,
+            (true, false) => LineScaleMode::Horizontal,
+            (false, true) => LineScaleMode::Vertical,
+            (true, true) => LineScaleMode::Both,
+        }
+    }
+}
+
 /// Helper type for calculating line widths for a transformed shape.
 pub struct LineScales<'a> {
     matrix: &'a Matrix,
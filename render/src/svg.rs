@@ -0,0 +1,351 @@
+//! Converting shapes to SVG images, for exporting them as vectors.
+//!
+//! Fills and strokes keep their colors and gradients. Bitmap fills need the
+//! bitmap itself, which isn't part of the shape, so they are drawn in gray.
+
+use crate::shape_utils::{DistilledShape, DrawCommand, DrawPath, FillRule};
+use std::fmt::Write;
+use swf::{
+    Color, FillStyle, Gradient, GradientInterpolation, GradientSpread, LineCapStyle, LineJoinStyle,
+    LineStyle, Matrix, Twips,
+};
+
+/// The color drawn in place of bitmap fills.
+const BITMAP_PLACEHOLDER_COLOR: Color = Color::from_rgb(0x808080, 255);
+
+/// Gradients are defined in a square of 32768 by 32768 twips centered on the origin,
+/// which their matrix maps to the shape.
+const GRADIENT_HALF_SIZE: f64 = 16384.0 / 20.0;
+
+/// Converts a shape to a standalone SVG image, in pixels.
+pub fn shape_to_svg(shape: &DistilledShape) -> String {
+    let bounds = &shape.shape_bounds;
+    let mut svg = String::new();
+    let _ = writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="{} {} {} {}" width="{}" height="{}">"#,
+        bounds.x_min.to_pixels(),
+        bounds.y_min.to_pixels(),
+        bounds.width().to_pixels(),
+        bounds.height().to_pixels(),
+        bounds.width().to_pixels(),
+        bounds.height().to_pixels(),
+    );
+    let mut writer = SvgWriter {
+        svg,
+        num_gradients: 0,
+    };
+    for path in &shape.paths {
+        writer.write_path(path);
+    }
+    writer.svg.push_str("</svg>\n");
+    writer.svg
+}
+
+struct SvgWriter {
+    svg: String,
+
+    /// Used to give each gradient a unique id.
+    num_gradients: usize,
+}
+
+impl SvgWriter {
+    fn write_path(&mut self, path: &DrawPath) {
+        match path {
+            DrawPath::Fill {
+                style,
+                commands,
+                winding_rule,
+            } => {
+                let paint = self.write_paint(style, "fill");
+                let fill_rule = match winding_rule {
+                    FillRule::EvenOdd => "evenodd",
+                    FillRule::NonZero => "nonzero",
+                };
+                let _ = writeln!(
+                    self.svg,
+                    r#"  <path d="{}" {paint} fill-rule="{fill_rule}"/>"#,
+                    path_data(commands, false),
+                );
+            }
+            DrawPath::Stroke {
+                style,
+                is_closed,
+                commands,
+            } => {
+                let paint = self.write_paint(style.fill_style(), "stroke");
+                let _ = writeln!(
+                    self.svg,
+                    r#"  <path d="{}" fill="none" {paint} {}/>"#,
+                    path_data(commands, *is_closed),
+                    stroke_attributes(style),
+                );
+            }
+        }
+    }
+
+    /// Writes the definition of the paint used by `attribute` if it needs one,
+    /// and returns the attributes that use it.
+    fn write_paint(&mut self, style: &FillStyle, attribute: &str) -> String {
+        match style {
+            FillStyle::Color(color) => color_attributes(attribute, color),
+            FillStyle::LinearGradient(gradient) => {
+                let id = self.next_gradient_id();
+                let _ = writeln!(
+                    self.svg,
+                    r#"  <linearGradient id="{id}" x1="{}" y1="0" x2="{}" y2="0" {}>"#,
+                    -GRADIENT_HALF_SIZE,
+                    GRADIENT_HALF_SIZE,
+                    gradient_attributes(gradient),
+                );
+                self.write_gradient_stops(gradient);
+                self.svg.push_str("  </linearGradient>\n");
+                format!(r#"{attribute}="url(#{id})""#)
+            }
+            FillStyle::RadialGradient(gradient) => {
+                self.write_radial_gradient(gradient, 0.0, attribute)
+            }
+            FillStyle::FocalGradient {
+                gradient,
+                focal_point,
+            } => self.write_radial_gradient(gradient, focal_point.to_f64(), attribute),
+            FillStyle::Bitmap { .. } => color_attributes(attribute, &BITMAP_PLACEHOLDER_COLOR),
+        }
+    }
+
+    fn write_radial_gradient(
+        &mut self,
+        gradient: &Gradient,
+        focal_point: f64,
+        attribute: &str,
+    ) -> String {
+        let id = self.next_gradient_id();
+        let _ = writeln!(
+            self.svg,
+            r#"  <radialGradient id="{id}" cx="0" cy="0" r="{}" fx="{}" fy="0" {}>"#,
+            GRADIENT_HALF_SIZE,
+            focal_point * GRADIENT_HALF_SIZE,
+            gradient_attributes(gradient),
+        );
+        self.write_gradient_stops(gradient);
+        self.svg.push_str("  </radialGradient>\n");
+        format!(r#"{attribute}="url(#{id})""#)
+    }
+
+    fn write_gradient_stops(&mut self, gradient: &Gradient) {
+        for record in &gradient.records {
+            let _ = writeln!(
+                self.svg,
+                r#"    <stop offset="{}" stop-color="{}" stop-opacity="{}"/>"#,
+                f32::from(record.ratio) / 255.0,
+                hex_color(&record.color),
+                opacity(&record.color),
+            );
+        }
+    }
+
+    fn next_gradient_id(&mut self) -> String {
+        self.num_gradients += 1;
+        format!("gradient{}", self.num_gradients)
+    }
+}
+
+fn path_data(commands: &[DrawCommand], is_closed: bool) -> String {
+    let mut data = String::new();
+    for command in commands {
+        let _ = match command {
+            DrawCommand::MoveTo { x, y } => {
+                write!(data, "M{} {} ", x.to_pixels(), y.to_pixels())
+            }
+            DrawCommand::LineTo { x, y } => {
+                write!(data, "L{} {} ", x.to_pixels(), y.to_pixels())
+            }
+            DrawCommand::CurveTo { x1, y1, x2, y2 } => write!(
+                data,
+                "Q{} {} {} {} ",
+                x1.to_pixels(),
+                y1.to_pixels(),
+                x2.to_pixels(),
+                y2.to_pixels()
+            ),
+        };
+    }
+    // Closing the path joins its ends instead of capping them.
+    if is_closed {
+        data.push('Z');
+    }
+    data.truncate(data.trim_end().len());
+    data
+}
+
+fn stroke_attributes(style: &LineStyle) -> String {
+    // SVG has one cap for both ends of the stroke.
+    let cap = match style.start_cap() {
+        LineCapStyle::Round => "round",
+        LineCapStyle::None => "butt",
+        LineCapStyle::Square => "square",
+    };
+    let join = match style.join_style() {
+        LineJoinStyle::Round => "round",
+        LineJoinStyle::Bevel => "bevel",
+        LineJoinStyle::Miter(_) => "miter",
+    };
+    let mut attributes = format!(r#"stroke-linecap="{cap}" stroke-linejoin="{join}""#);
+    if let LineJoinStyle::Miter(limit) = style.join_style() {
+        // SVG doesn't allow miter limits below 1.
+        let _ = write!(
+            attributes,
+            r#" stroke-miterlimit="{}""#,
+            limit.to_f32().max(1.0)
+        );
+    }
+
+    // Hairlines are one pixel wide at every scale.
+    let width = style.width();
+    if width == Twips::ZERO {
+        attributes.push_str(r#" stroke-width="1" vector-effect="non-scaling-stroke""#);
+    } else {
+        let _ = write!(attributes, r#" stroke-width="{}""#, width.to_pixels());
+        if !style.allow_scale_x() && !style.allow_scale_y() {
+            attributes.push_str(r#" vector-effect="non-scaling-stroke""#);
+        }
+    }
+    attributes
+}
+
+fn gradient_attributes(gradient: &Gradient) -> String {
+    let spread = match gradient.spread {
+        GradientSpread::Pad => "pad",
+        GradientSpread::Reflect => "reflect",
+        GradientSpread::Repeat => "repeat",
+    };
+    let interpolation = match gradient.interpolation {
+        GradientInterpolation::Rgb => "sRGB",
+        GradientInterpolation::LinearRgb => "linearRGB",
+    };
+    format!(
+        r#"gradientUnits="userSpaceOnUse" gradientTransform="{}" spreadMethod="{spread}" color-interpolation="{interpolation}""#,
+        svg_matrix(&gradient.matrix),
+    )
+}
+
+fn svg_matrix(matrix: &Matrix) -> String {
+    format!(
+        "matrix({} {} {} {} {} {})",
+        matrix.a.to_f32(),
+        matrix.b.to_f32(),
+        matrix.c.to_f32(),
+        matrix.d.to_f32(),
+        matrix.tx.to_pixels(),
+        matrix.ty.to_pixels(),
+    )
+}
+
+fn color_attributes(attribute: &str, color: &Color) -> String {
+    format!(
+        r#"{attribute}="{}" {attribute}-opacity="{}""#,
+        hex_color(color),
+        opacity(color)
+    )
+}
+
+fn hex_color(color: &Color) -> String {
+    format!("#{:02x}{:02x}{:02x}", color.r, color.g, color.b)
+}
+
+fn opacity(color: &Color) -> f32 {
+    f32::from(color.a) / 255.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use swf::{GradientRecord, Rectangle};
+
+    fn square(paths: Vec<DrawPath>) -> DistilledShape {
+        DistilledShape {
+            paths,
+            shape_bounds: Rectangle {
+                x_min: Twips::ZERO,
+                x_max: Twips::from_pixels(10.0),
+                y_min: Twips::ZERO,
+                y_max: Twips::from_pixels(10.0),
+            },
+            edge_bounds: Default::default(),
+            id: 1,
+        }
+    }
+
+    fn square_commands() -> Vec<DrawCommand> {
+        let ten = Twips::from_pixels(10.0);
+        vec![
+            DrawCommand::MoveTo {
+                x: Twips::ZERO,
+                y: Twips::ZERO,
+            },
+            DrawCommand::LineTo {
+                x: ten,
+                y: Twips::ZERO,
+            },
+            DrawCommand::CurveTo {
+                x1: ten,
+                y1: ten,
+                x2: Twips::ZERO,
+                y2: ten,
+            },
+            DrawCommand::LineTo {
+                x: Twips::ZERO,
+                y: Twips::ZERO,
+            },
+        ]
+    }
+
+    #[test]
+    fn solid_fill() {
+        let style = FillStyle::Color(Color::from_rgb(0xff8000, 128));
+        let svg = shape_to_svg(&square(vec![DrawPath::Fill {
+            style: &style,
+            commands: square_commands(),
+            winding_rule: FillRule::EvenOdd,
+        }]));
+        assert_eq!(
+            svg,
+            concat!(
+                r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 10 10" width="10" height="10">"#,
+                "\n",
+                r##"  <path d="M0 0 L10 0 Q10 10 0 10 L0 0" fill="#ff8000" fill-opacity="0.5019608" fill-rule="evenodd"/>"##,
+                "\n</svg>\n"
+            )
+        );
+    }
+
+    #[test]
+    fn gradient_stroke() {
+        let style = LineStyle::new()
+            .with_width(Twips::from_pixels(2.0))
+            .with_join_style(LineJoinStyle::Bevel)
+            .with_fill_style(FillStyle::LinearGradient(Gradient {
+                matrix: Matrix::IDENTITY,
+                spread: GradientSpread::Reflect,
+                interpolation: GradientInterpolation::Rgb,
+                records: vec![
+                    GradientRecord {
+                        ratio: 0,
+                        color: Color::BLACK,
+                    },
+                    GradientRecord {
+                        ratio: 255,
+                        color: Color::WHITE,
+                    },
+                ],
+            }));
+        let svg = shape_to_svg(&square(vec![DrawPath::Stroke {
+            style: &style,
+            is_closed: true,
+            commands: square_commands(),
+        }]));
+        assert!(svg.contains(r#"<linearGradient id="gradient1" x1="-819.2" y1="0" x2="819.2" y2="0" gradientUnits="userSpaceOnUse" gradientTransform="matrix(1 0 0 1 0 0)" spreadMethod="reflect""#));
+        assert!(svg.contains(r##"<stop offset="1" stop-color="#ffffff" stop-opacity="1"/>"##));
+        assert!(svg.contains(r#"d="M0 0 L10 0 Q10 10 0 10 L0 0 Z" fill="none" stroke="url(#gradient1)" stroke-linecap="round" stroke-linejoin="bevel" stroke-width="2"/>"#));
+    }
+}
@@ -1,5 +1,5 @@
 use crate::bitmap::BitmapSource;
-use crate::shape_utils::{DistilledShape, DrawCommand, DrawPath, GradientType};
+use crate::shape_utils::{DistilledShape, DrawCommand, DrawPath, GradientType, LineScaleMode};
 use lyon::path::Path;
 use lyon::tessellation::{
     self,
@@ -131,8 +131,15 @@ impl ShapeTessellator {
             }
             self.is_stroke = next_is_stroke;
 
+            let stroke = match &path {
+                DrawPath::Stroke { style, .. } => Some((
+                    style.width().to_pixels() as f32,
+                    LineScaleMode::from(*style),
+                )),
+                DrawPath::Fill { .. } => None,
+            };
             let mut buffers_builder =
-                BuffersBuilder::new(&mut self.lyon_mesh, RuffleVertexCtor { color });
+                BuffersBuilder::new(&mut self.lyon_mesh, RuffleVertexCtor { color, stroke });
             let result = match path {
                 DrawPath::Fill { winding_rule, .. } => self.fill_tess.tessellate_path(
                     &lyon_path,
@@ -140,8 +147,10 @@ impl ShapeTessellator {
                     &mut buffers_builder,
                 ),
                 DrawPath::Stroke { style, .. } => {
-                    // TODO(Herschel): 0 width indicates "hairline".
-                    let width = (style.width().to_pixels() as f32).max(1.0);
+                    // Renderers which don't use `Vertex::stroke` draw strokes with this width,
+                    // scaled by the transform. This makes hairlines (with a width of 0 or 1 twip)
+                    // at least 1 pixel wide when drawn without scaling.
+                    let width = stroke_tessellation_width(style.width().to_pixels() as f32);
                     let mut stroke_options = StrokeOptions::default()
                         .with_line_width(width)
                         .with_start_cap(match style.start_cap() {
@@ -159,14 +168,9 @@ impl ShapeTessellator {
                         swf::LineJoinStyle::Round => tessellation::LineJoin::Round,
                         swf::LineJoinStyle::Bevel => tessellation::LineJoin::Bevel,
                         swf::LineJoinStyle::Miter(limit) => {
-                            // Avoid lyon assert with small miter limits.
-                            let limit = limit.to_f32();
-                            if limit >= StrokeOptions::MINIMUM_MITER_LIMIT {
-                                stroke_options = stroke_options.with_miter_limit(limit);
-                                tessellation::LineJoin::MiterClip
-                            } else {
-                                tessellation::LineJoin::Bevel
-                            }
+                            stroke_options =
+                                stroke_options.with_miter_limit(lyon_miter_limit(limit));
+                            tessellation::LineJoin::MiterClip
                         }
                     };
                     stroke_options = stroke_options.with_line_join(line_join);
@@ -262,6 +266,35 @@ pub struct Vertex {
     pub x: f32,
     pub y: f32,
     pub color: swf::Color,
+
+    /// How this vertex is offset from the center line of its stroke,
+    /// or `None` if this vertex isn't part of a stroke.
+    pub stroke: Option<StrokeOffset>,
+}
+
+/// Describes a vertex of a stroke, so that renderers can adjust the width of the stroke
+/// to the transform it is drawn with.
+///
+/// The position of the vertex is computed for a stroke which is `stroke_tessellation_width(width)`
+/// pixels wide. Moving it by `-offset * stroke_tessellation_width(width)` gives the point on the
+/// center line of the stroke, and moving it from there by `offset * new_width` instead gives the
+/// vertex of a stroke which is `new_width` pixels wide.
+#[derive(Clone, Copy, Debug)]
+pub struct StrokeOffset {
+    /// The offset from the center line of the stroke, for a stroke with a width of 1 pixel.
+    pub offset: [f32; 2],
+
+    /// The width of the stroke as defined by its line style, in pixels.
+    pub width: f32,
+
+    /// How the width of the stroke is affected by the transform.
+    pub scale_mode: LineScaleMode,
+}
+
+/// Returns the width that a stroke with the given width is tessellated with.
+/// Strokes are always at least 1 pixel wide.
+pub fn stroke_tessellation_width(width: f32) -> f32 {
+    width.max(1.0)
 }
 
 #[derive(Clone, Debug)]
@@ -326,6 +359,15 @@ fn swf_bitmap_to_gl_matrix(
     [[a, d, 0.0], [b, e, 0.0], [c, f, 1.0]]
 }
 
+/// Converts a Flash miter limit to a lyon miter limit.
+///
+/// Flash cuts a miter off once it extends `limit * width` beyond the point where the lines
+/// meet, with limits below 1 treated as 1. Lyon follows SVG, which measures the miter
+/// relative to half of the width of the stroke.
+fn lyon_miter_limit(limit: swf::Fixed8) -> f32 {
+    (limit.to_f32().max(1.0) * 2.0).max(StrokeOptions::MINIMUM_MITER_LIMIT)
+}
+
 fn ruffle_path_to_lyon_path(commands: &[DrawCommand], is_closed: bool) -> Path {
     fn point(x: swf::Twips, y: swf::Twips) -> lyon::math::Point {
         lyon::math::Point::new(x.to_pixels() as f32, y.to_pixels() as f32)
@@ -333,6 +375,9 @@ fn ruffle_path_to_lyon_path(commands: &[DrawCommand], is_closed: bool) -> Path {
 
     let mut builder = Path::builder();
     let mut move_to = Some((swf::Twips::default(), swf::Twips::default()));
+    // Whether the current subpath goes anywhere other than its starting point.
+    let mut is_degenerate = true;
+    let mut start = (swf::Twips::default(), swf::Twips::default());
     for cmd in commands {
         match *cmd {
             DrawCommand::MoveTo { x, y } => {
@@ -344,20 +389,28 @@ fn ruffle_path_to_lyon_path(commands: &[DrawCommand], is_closed: bool) -> Path {
             DrawCommand::LineTo { x, y } => {
                 if let Some((x, y)) = move_to.take() {
                     builder.begin(point(x, y));
+                    start = (x, y);
+                    is_degenerate = true;
                 }
+                is_degenerate &= (x, y) == start;
                 builder.line_to(point(x, y));
             }
             DrawCommand::CurveTo { x1, y1, x2, y2 } => {
                 if let Some((x, y)) = move_to.take() {
                     builder.begin(point(x, y));
+                    start = (x, y);
+                    is_degenerate = true;
                 }
+                is_degenerate &= (x1, y1) == start && (x2, y2) == start;
                 builder.quadratic_bezier_to(point(x1, y1), point(x2, y2));
             }
         }
     }
 
     if move_to.is_none() {
-        if is_closed {
+        // A stroke of zero length is left open, so that Flash draws it as a dot
+        // using its caps.
+        if is_closed && !is_degenerate {
             builder.close();
         } else {
             builder.end(false);
@@ -385,6 +438,9 @@ fn swf_gradient_to_uniforms(
 
 struct RuffleVertexCtor {
     color: swf::Color,
+
+    /// The width and scale mode of the stroke being tessellated, if any.
+    stroke: Option<(f32, LineScaleMode)>,
 }
 
 impl FillVertexConstructor<Vertex> for RuffleVertexCtor {
@@ -393,16 +449,130 @@ impl FillVertexConstructor<Vertex> for RuffleVertexCtor {
             x: vertex.position().x,
             y: vertex.position().y,
             color: self.color.clone(),
+            stroke: None,
         }
     }
 }
 
 impl StrokeVertexConstructor<Vertex> for RuffleVertexCtor {
     fn new_vertex(&mut self, vertex: StrokeVertex) -> Vertex {
+        // Lyon places stroke vertices at `position_on_path + normal * width / 2`.
+        let normal = vertex.normal();
         Vertex {
             x: vertex.position().x,
             y: vertex.position().y,
             color: self.color.clone(),
+            stroke: self.stroke.map(|(width, scale_mode)| StrokeOffset {
+                offset: [normal.x * 0.5, normal.y * 0.5],
+                width,
+                scale_mode,
+            }),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::null::NullBitmapSource;
+    use swf::{LineCapStyle, LineJoinStyle, LineStyle, Rectangle, Twips};
+
+    fn point(x: f64, y: f64) -> (Twips, Twips) {
+        (Twips::from_pixels(x), Twips::from_pixels(y))
+    }
+
+    fn line_to((x, y): (Twips, Twips)) -> DrawCommand {
+        DrawCommand::LineTo { x, y }
+    }
+
+    /// Tessellates a single stroke, and returns the bounds of the resulting vertices
+    /// as `[x_min, y_min, x_max, y_max]` in pixels.
+    fn stroke_bounds(style: &LineStyle, points: &[(Twips, Twips)], is_closed: bool) -> [f32; 4] {
+        let (x, y) = points[0];
+        let mut commands = vec![DrawCommand::MoveTo { x, y }];
+        commands.extend(points[1..].iter().copied().map(line_to));
+        let shape = DistilledShape {
+            paths: vec![DrawPath::Stroke {
+                style,
+                is_closed,
+                commands,
+            }],
+            shape_bounds: Rectangle::default(),
+            edge_bounds: Rectangle::default(),
+            id: 1,
+        };
+        let mesh = ShapeTessellator::new().tessellate_shape(shape, &NullBitmapSource);
+        mesh.iter().flat_map(|draw| &draw.vertices).fold(
+            [f32::MAX, f32::MAX, f32::MIN, f32::MIN],
+            |[x_min, y_min, x_max, y_max], vertex| {
+                [
+                    x_min.min(vertex.x),
+                    y_min.min(vertex.y),
+                    x_max.max(vertex.x),
+                    y_max.max(vertex.y),
+                ]
+            },
+        )
+    }
+
+    fn assert_close(actual: f32, expected: f32) {
+        assert!(
+            (actual - expected).abs() < 0.01,
+            "expected {expected}, got {actual}"
+        );
+    }
+
+    #[test]
+    fn caps() {
+        let line = [point(0.0, 0.0), point(100.0, 0.0)];
+        let style = |cap| {
+            LineStyle::new()
+                .with_width(Twips::from_pixels(10.0))
+                .with_start_cap(cap)
+                .with_end_cap(cap)
+        };
+
+        let [x_min, _, x_max, _] = stroke_bounds(&style(LineCapStyle::None), &line, false);
+        assert_close(x_min, 0.0);
+        assert_close(x_max, 100.0);
+
+        let [x_min, _, x_max, _] = stroke_bounds(&style(LineCapStyle::Square), &line, false);
+        assert_close(x_min, -5.0);
+        assert_close(x_max, 105.0);
+
+        let [x_min, _, x_max, _] = stroke_bounds(&style(LineCapStyle::Round), &line, false);
+        assert!(x_min < -4.9 && x_max > 104.9);
+    }
+
+    /// A stroke which ends at its starting point is drawn as a dot.
+    #[test]
+    fn zero_length_stroke() {
+        let style = LineStyle::new()
+            .with_width(Twips::from_pixels(10.0))
+            .with_start_cap(LineCapStyle::Square)
+            .with_end_cap(LineCapStyle::Square);
+        let dot = [point(50.0, 50.0), point(50.0, 50.0)];
+        let [x_min, y_min, x_max, y_max] = stroke_bounds(&style, &dot, true);
+        assert_close(x_min, 45.0);
+        assert_close(y_min, 45.0);
+        assert_close(x_max, 55.0);
+        assert_close(y_max, 55.0);
+    }
+
+    /// Flash cuts off miters which extend more than `limit * width` past the joint.
+    #[test]
+    fn miter_limit() {
+        let corner = [point(0.0, 0.0), point(100.0, 10.0), point(0.0, 20.0)];
+        let style = |limit| {
+            LineStyle::new()
+                .with_width(Twips::from_pixels(10.0))
+                .with_join_style(LineJoinStyle::Miter(swf::Fixed8::from_f32(limit)))
+        };
+
+        let [_, _, clipped, _] = stroke_bounds(&style(1.0), &corner, false);
+        assert!(clipped > 100.0 && clipped <= 110.01, "{clipped}");
+
+        let [_, _, unclipped, _] = stroke_bounds(&style(100.0), &corner, false);
+        assert!(unclipped > clipped, "{unclipped}");
+    }
+}
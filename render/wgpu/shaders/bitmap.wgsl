@@ -50,5 +50,9 @@ fn main_fragment(in: VertexOutput) -> @location(0) vec4<f32> {
             color = saturate(color);
         #endif
     }
+    #if opaque == true
+        // Stage3D back buffers are composited without their alpha channel.
+        color = vec4<f32>(color.rgb, 1.0);
+    #endif
     return color;
 }
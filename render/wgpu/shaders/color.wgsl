@@ -5,6 +5,9 @@
 struct VertexInput {
     @location(0) position: vec2<f32>,
     @location(1) color: vec4<f32>,
+    /// The offset from the center line for a stroke width of 1 pixel, the width of the stroke,
+    /// and its scale mode. The width is negative for vertices which aren't part of a stroke.
+    @location(2) stroke: vec4<f32>,
 };
 
 struct VertexOutput {
@@ -19,13 +22,32 @@ struct VertexOutput {
     @group(2) @binding(0) var<uniform> colorTransforms: common::ColorTransforms;
 #endif
 
+/// Moves a vertex of a stroke, so that the stroke has the width required by its scale mode
+/// after being transformed by `world_matrix`.
+fn stroke_position(position: vec2<f32>, stroke: vec4<f32>, world_matrix: mat4x4<f32>) -> vec2<f32> {
+    let width = stroke.z;
+    if (width < 0.0) {
+        return position;
+    }
+    let line_scale = abs(world_matrix[0].xy + world_matrix[1].xy);
+    let line_scale_both = sqrt((line_scale.x * line_scale.x + line_scale.y * line_scale.y) / 2.0);
+    var scales = array<f32, 4>(1.0, line_scale.x, line_scale.y, line_scale_both);
+    // Flash draws all strokes with a minimum width of 1 pixel.
+    let scaled_width = max(width * scales[u32(stroke.w)], 1.0);
+    // The vertex was tessellated for a stroke which is at least 1 pixel wide.
+    let center = position - stroke.xy * max(width, 1.0);
+    // The world matrix scales the stroke again, so undo that here.
+    return center + stroke.xy * scaled_width / max(line_scale_both, 0.0001);
+}
+
 @vertex
 fn main_vertex(in: VertexInput) -> VertexOutput {
     #if use_push_constants == true
         var transforms = pc.transforms;
         var colorTransforms = pc.colorTransforms;
     #endif
-    let pos = common::globals.view_matrix * transforms.world_matrix * vec4<f32>(in.position.x, in.position.y, 0.0, 1.0);
+    let position = stroke_position(in.position, in.stroke, transforms.world_matrix);
+    let pos = common::globals.view_matrix * transforms.world_matrix * vec4<f32>(position.x, position.y, 0.0, 1.0);
     let color = saturate(in.color * colorTransforms.mult_color + colorTransforms.add_color);
     return VertexOutput(pos, vec4<f32>(color.rgb * color.a, color.a));
 }
@@ -0,0 +1,68 @@
+/// Shader used by the gradient glow and gradient bevel filters.
+/// The alpha channel of the blurred source is mapped to a color of the gradient,
+/// which is then composited with the source.
+
+#import filter
+
+struct Filter {
+    // Converts UV coordinates of the source to UV coordinates of the blurred texture.
+    source_to_blurred: vec4<f32>,
+    offset: vec2<f32>,
+    strength: f32,
+    is_bevel: i32,
+    is_inner: i32,
+    is_outer: i32,
+    is_knockout: i32,
+    _padding: i32,
+}
+
+#if use_push_constants == true
+    @group(2) @binding(0) var<uniform> filter_args: Filter;
+    @group(2) @binding(1) var blurred: texture_2d<f32>;
+    @group(2) @binding(2) var gradient: texture_2d<f32>;
+    @group(2) @binding(3) var filter_sampler: sampler;
+#else
+    @group(4) @binding(0) var<uniform> filter_args: Filter;
+    @group(4) @binding(1) var blurred: texture_2d<f32>;
+    @group(4) @binding(2) var gradient: texture_2d<f32>;
+    @group(4) @binding(3) var filter_sampler: sampler;
+#endif
+
+@vertex
+fn main_vertex(in: filter::FilterVertexInput) -> filter::VertexOutput {
+    return filter::main_vertex(in);
+}
+
+@fragment
+fn main_fragment(in: filter::VertexOutput) -> @location(0) vec4<f32> {
+    let f = filter_args;
+    let source = textureSample(filter::texture, filter::texture_sampler, in.uv);
+    let blurred_uv = (in.uv - f.source_to_blurred.xy) / f.source_to_blurred.zw;
+    let highlight = textureSample(blurred, filter_sampler, blurred_uv - f.offset).a;
+    let shadow = textureSample(blurred, filter_sampler, blurred_uv + f.offset).a;
+
+    var position: f32;
+    if (f.is_bevel != 0) {
+        // The middle of the gradient is used where the highlight and shadow cancel out.
+        position = saturate((highlight - shadow) * f.strength * 0.5 + 0.5);
+    } else {
+        position = saturate(highlight * f.strength);
+    }
+    let gradient_color = textureSample(gradient, filter_sampler, vec2<f32>(position, 0.5));
+    var color = vec4<f32>(gradient_color.rgb * gradient_color.a, gradient_color.a);
+
+    if (f.is_inner != 0) {
+        color *= source.a;
+    } else if (f.is_outer != 0) {
+        color *= 1.0 - source.a;
+    }
+
+    if (f.is_knockout != 0) {
+        return color;
+    }
+    if (f.is_outer != 0) {
+        // Outer effects are drawn behind the source.
+        return source + color;
+    }
+    return color + source * (1.0 - color.a);
+}
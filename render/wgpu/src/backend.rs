@@ -12,7 +12,7 @@ use crate::{
     QueueSyncHandle, RenderTarget, SwapChainTarget, Texture, Transforms,
 };
 use image::imageops::FilterType;
-use ruffle_render::backend::Context3D;
+use ruffle_render::backend::{Context3D, Context3DProfile};
 use ruffle_render::backend::{RenderBackend, ShapeHandle, ViewportDimensions};
 use ruffle_render::bitmap::{
     Bitmap, BitmapFormat, BitmapHandle, BitmapSource, PixelRegion, SyncHandle,
@@ -23,6 +23,7 @@ use ruffle_render::filters::Filter;
 use ruffle_render::quality::StageQuality;
 use ruffle_render::shape_utils::DistilledShape;
 use ruffle_render::tessellator::ShapeTessellator;
+use std::any::Any;
 use std::borrow::Cow;
 use std::cell::Cell;
 use std::mem;
@@ -49,16 +50,43 @@ pub struct WgpuRenderBackend<T: RenderTarget> {
     texture_pool: TexturePool,
     offscreen_texture_pool: TexturePool,
     offscreen_buffer_pool: Arc<BufferPool<wgpu::Buffer, BufferDimensions>>,
+    opaque_sorting: bool,
 }
 
 impl WgpuRenderBackend<SwapChainTarget> {
     #[cfg(target_family = "wasm")]
-    pub async fn for_canvas(canvas: web_sys::HtmlCanvasElement) -> Result<Self, Error> {
+    pub async fn for_canvas(
+        canvas: web_sys::HtmlCanvasElement,
+        is_transparent: bool,
+    ) -> Result<Self, Error> {
         let instance = wgpu::Instance::new(wgpu::InstanceDescriptor {
             backends: wgpu::Backends::BROWSER_WEBGPU | wgpu::Backends::GL,
             dx12_shader_compiler: wgpu::Dx12Compiler::default(),
         });
         let surface = instance.create_surface_from_canvas(canvas)?;
+        Self::for_web_surface(instance, surface, is_transparent).await
+    }
+
+    /// Creates a renderer drawing to an `OffscreenCanvas`, which allows rendering from a Web Worker.
+    #[cfg(target_family = "wasm")]
+    pub async fn for_offscreen_canvas(
+        canvas: web_sys::OffscreenCanvas,
+        is_transparent: bool,
+    ) -> Result<Self, Error> {
+        let instance = wgpu::Instance::new(wgpu::InstanceDescriptor {
+            backends: wgpu::Backends::BROWSER_WEBGPU | wgpu::Backends::GL,
+            dx12_shader_compiler: wgpu::Dx12Compiler::default(),
+        });
+        let surface = instance.create_surface_from_offscreen_canvas(canvas)?;
+        Self::for_web_surface(instance, surface, is_transparent).await
+    }
+
+    #[cfg(target_family = "wasm")]
+    async fn for_web_surface(
+        instance: wgpu::Instance,
+        surface: wgpu::Surface,
+        is_transparent: bool,
+    ) -> Result<Self, Error> {
         let (adapter, device, queue) = Self::request_device(
             wgpu::Backends::BROWSER_WEBGPU | wgpu::Backends::GL,
             instance,
@@ -68,8 +96,13 @@ impl WgpuRenderBackend<SwapChainTarget> {
         )
         .await?;
         let descriptors = Descriptors::new(adapter, device, queue);
-        let target =
-            SwapChainTarget::new(surface, &descriptors.adapter, (1, 1), &descriptors.device);
+        let target = SwapChainTarget::new(
+            surface,
+            &descriptors.adapter,
+            (1, 1),
+            &descriptors.device,
+            is_transparent,
+        );
         Self::new(Arc::new(descriptors), target)
     }
 
@@ -102,9 +135,42 @@ impl WgpuRenderBackend<SwapChainTarget> {
             trace_path,
         ))?;
         let descriptors = Descriptors::new(adapter, device, queue);
-        let target = SwapChainTarget::new(surface, &descriptors.adapter, size, &descriptors.device);
+        let target = SwapChainTarget::new(
+            surface,
+            &descriptors.adapter,
+            size,
+            &descriptors.device,
+            false,
+        );
         Self::new(Arc::new(descriptors), target)
     }
+
+    /// Creates a renderer for a window that shares the device and pipelines of
+    /// other renderers, such as those of the other windows of the process.
+    ///
+    /// `instance` must be the instance that the adapter of `descriptors` was requested from.
+    #[cfg(not(target_family = "wasm"))]
+    pub fn for_window_with_descriptors<
+        W: raw_window_handle::HasRawWindowHandle + raw_window_handle::HasRawDisplayHandle,
+    >(
+        window: &W,
+        size: (u32, u32),
+        instance: &wgpu::Instance,
+        descriptors: Arc<Descriptors>,
+    ) -> Result<Self, Error> {
+        let surface = unsafe { instance.create_surface(window) }?;
+        if !descriptors.adapter.is_surface_supported(&surface) {
+            return Err("The shared graphics device cannot present to this window".into());
+        }
+        let target = SwapChainTarget::new(
+            surface,
+            &descriptors.adapter,
+            size,
+            &descriptors.device,
+            false,
+        );
+        Self::new(descriptors, target)
+    }
 }
 
 #[cfg(not(target_family = "wasm"))]
@@ -133,8 +199,18 @@ impl WgpuRenderBackend<crate::target::TextureTarget> {
             trace_path,
         ))?;
         let descriptors = Descriptors::new(adapter, device, queue);
+        Self::for_offscreen_with_descriptors(size, Arc::new(descriptors))
+    }
+
+    /// Creates an offscreen renderer that shares the device and pipelines of
+    /// other renderers, so that several players can render in one process
+    /// without each of them initializing the device again.
+    pub fn for_offscreen_with_descriptors(
+        size: (u32, u32),
+        descriptors: Arc<Descriptors>,
+    ) -> Result<Self, Error> {
         let target = crate::target::TextureTarget::new(&descriptors.device, size)?;
-        Self::new(Arc::new(descriptors), target)
+        Self::new(descriptors, target)
     }
 
     pub fn capture_frame(&self) -> Option<image::RgbaImage> {
@@ -205,6 +281,7 @@ impl<T: RenderTarget> WgpuRenderBackend<T> {
             texture_pool: TexturePool::new(),
             offscreen_texture_pool: TexturePool::new(),
             offscreen_buffer_pool: Arc::new(offscreen_buffer_pool),
+            opaque_sorting: false,
         })
     }
 
@@ -332,6 +409,17 @@ impl<T: RenderTarget> WgpuRenderBackend<T> {
     pub fn device(&self) -> &wgpu::Device {
         &self.descriptors.device
     }
+
+    /// Enables drawing the fully opaque bitmaps and rectangles of the stage
+    /// front to back with a depth test, before everything else, so that the
+    /// GPU skips whatever they cover.
+    ///
+    /// This saves fill rate for movies with layered full-screen backgrounds,
+    /// at the cost of a depth buffer.
+    pub fn set_opaque_sorting(&mut self, enabled: bool) {
+        self.opaque_sorting = enabled;
+        self.surface.set_opaque_sorting(&self.descriptors, enabled);
+    }
 }
 
 impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
@@ -361,6 +449,8 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
             height,
             self.target.format(),
         );
+        self.surface
+            .set_opaque_sorting(&self.descriptors, self.opaque_sorting);
 
         self.viewport_scale_factor = dimensions.scale_factor;
         self.texture_pool = TexturePool::new();
@@ -368,7 +458,14 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
 
     fn create_context3d(
         &mut self,
+        profile: Context3DProfile,
     ) -> Result<Box<dyn ruffle_render::backend::Context3D>, BitmapError> {
+        // Textures and the back buffer can be as large as the profile allows.
+        // Everything else we need (float textures, multiple render targets)
+        // is guaranteed by wgpu.
+        if self.descriptors.limits.max_texture_dimension_2d < profile.max_texture_size() {
+            return Err(BitmapError::UnsupportedContext3DProfile(profile));
+        }
         Ok(Box::new(WgpuContext3D::new(self.descriptors.clone())))
     }
 
@@ -428,6 +525,8 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
             self.surface.size().height,
             self.target.format(),
         );
+        self.surface
+            .set_opaque_sorting(&self.descriptors, self.opaque_sorting);
     }
 
     fn viewport_dimensions(&self) -> ViewportDimensions {
@@ -466,10 +565,11 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
 
         let command_buffers = self.surface.draw_commands_to(
             frame_output.view(),
+            // Render targets hold premultiplied colors, which matters for a transparent clear.
             RenderTargetMode::FreshBuffer(wgpu::Color {
-                r: f64::from(clear.r) / 255.0,
-                g: f64::from(clear.g) / 255.0,
-                b: f64::from(clear.b) / 255.0,
+                r: f64::from(clear.r) * f64::from(clear.a) / (255.0 * 255.0),
+                g: f64::from(clear.g) * f64::from(clear.a) / (255.0 * 255.0),
+                b: f64::from(clear.b) * f64::from(clear.a) / (255.0 * 255.0),
                 a: f64::from(clear.a) / 255.0,
             }),
             &self.descriptors,
@@ -544,11 +644,45 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
             width: bitmap.width(),
             height: bitmap.height(),
             copy_count: Cell::new(0),
+            opaque: Cell::new(is_opaque(&bitmap)),
         }));
 
         Ok(handle)
     }
 
+    fn import_texture(
+        &mut self,
+        texture: Box<dyn Any>,
+        width: u32,
+        height: u32,
+    ) -> Result<BitmapHandle, BitmapError> {
+        let texture = texture.downcast::<wgpu::Texture>().map_err(|_| {
+            BitmapError::Unimplemented("Importing textures other than wgpu::Texture".into())
+        })?;
+        if texture.format() != wgpu::TextureFormat::Rgba8Unorm
+            || !texture
+                .usage()
+                .contains(wgpu::TextureUsages::TEXTURE_BINDING)
+            || texture.width() != width
+            || texture.height() != height
+        {
+            return Err(BitmapError::Unimplemented(
+                "Importing textures that aren't sampled Rgba8Unorm textures of the given size"
+                    .into(),
+            ));
+        }
+
+        Ok(BitmapHandle(Arc::new(Texture {
+            texture: Arc::new(*texture),
+            bind_linear: Default::default(),
+            bind_nearest: Default::default(),
+            width,
+            height,
+            copy_count: Cell::new(0),
+            opaque: Cell::new(false),
+        })))
+    }
+
     #[instrument(level = "debug", skip_all)]
     fn update_texture(
         &mut self,
@@ -591,6 +725,7 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
             },
             extent,
         );
+        texture.opaque.set(is_opaque(&bitmap));
 
         Ok(())
     }
@@ -604,6 +739,7 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
         bounds: PixelRegion,
     ) -> Option<Box<dyn SyncHandle>> {
         let texture = as_texture(&handle);
+        texture.opaque.set(false);
 
         let extent = wgpu::Extent3d {
             width: texture.width,
@@ -686,7 +822,13 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
     }
 
     fn is_filter_supported(&self, filter: &Filter) -> bool {
-        matches!(filter, Filter::BlurFilter(_) | Filter::ColorMatrixFilter(_))
+        matches!(
+            filter,
+            Filter::BlurFilter(_)
+                | Filter::ColorMatrixFilter(_)
+                | Filter::GradientGlowFilter(_)
+                | Filter::GradientBevelFilter(_)
+        )
     }
 
     fn apply_filter(
@@ -826,6 +968,11 @@ async fn request_device(
         .await
 }
 
+/// Whether every pixel of a bitmap is fully opaque.
+fn is_opaque(bitmap: &Bitmap) -> bool {
+    bitmap.data().chunks_exact(4).all(|pixel| pixel[3] == 255)
+}
+
 /// Determines how we choose our frame buffer
 #[derive(Clone)]
 pub enum RenderTargetMode {
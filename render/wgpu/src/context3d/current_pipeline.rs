@@ -1,19 +1,20 @@
+use fnv::FnvHashMap;
 use naga::valid::{Capabilities, ValidationFlags, Validator};
-use naga_agal::{Filter, SamplerOverride, Wrapping};
+use naga_agal::{Filter, Mipmap, SamplerConfig, Wrapping, MAX_TEXTURES};
 use ruffle_render::backend::{
-    Context3DTextureFilter, Context3DTriangleFace, Context3DVertexBufferFormat, Context3DWrapMode,
-    Texture,
+    Context3DMipFilter, Context3DTextureFilter, Context3DTriangleFace, Context3DVertexBufferFormat,
+    Context3DWrapMode, Texture,
 };
 
 use wgpu::{
-    BindGroupEntry, BindingResource, BufferDescriptor, BufferUsages, FrontFace, SamplerBindingType,
-    TextureView,
+    AddressMode, BindGroupEntry, BindingResource, BufferDescriptor, BufferUsages, FilterMode,
+    FrontFace, SamplerBindingType, TextureView,
 };
 use wgpu::{Buffer, DepthStencilState, StencilFaceState};
 use wgpu::{ColorTargetState, RenderPipelineDescriptor, TextureFormat, VertexState};
 
 use std::borrow::Cow;
-use std::cell::Cell;
+use std::cell::{Cell, RefCell};
 use std::num::NonZeroU64;
 use std::rc::Rc;
 
@@ -22,8 +23,9 @@ use crate::descriptors::Descriptors;
 
 use super::{ShaderModuleAgal, VertexAttributeInfo, MAX_VERTEX_ATTRIBUTES};
 
-const AGAL_NUM_VERTEX_CONSTANTS: u64 = 128;
-const AGAL_NUM_FRAGMENT_CONSTANTS: u64 = 28;
+// Large enough for AGAL 2 programs - AGAL 1 programs only use the first 128 and 28 registers.
+const AGAL_NUM_VERTEX_CONSTANTS: u64 = 250;
+const AGAL_NUM_FRAGMENT_CONSTANTS: u64 = 64;
 pub(super) const AGAL_FLOATS_PER_REGISTER: u64 = 4;
 
 const VERTEX_SHADER_UNIFORMS_BUFFER_SIZE: u64 =
@@ -31,14 +33,7 @@ const VERTEX_SHADER_UNIFORMS_BUFFER_SIZE: u64 =
 const FRAGMENT_SHADER_UNIFORMS_BUFFER_SIZE: u64 =
     AGAL_NUM_FRAGMENT_CONSTANTS * AGAL_FLOATS_PER_REGISTER * std::mem::size_of::<f32>() as u64;
 
-const SAMPLER_REPEAT_LINEAR: u32 = 2;
-const SAMPLER_REPEAT_NEAREST: u32 = 3;
-const SAMPLER_CLAMP_LINEAR: u32 = 4;
-const SAMPLER_CLAMP_NEAREST: u32 = 5;
-const SAMPLER_CLAMP_U_REPEAT_V_LINEAR: u32 = 6;
-const SAMPLER_CLAMP_U_REPEAT_V_NEAREST: u32 = 7;
-const SAMPLER_REPEAT_U_CLAMP_V_LINEAR: u32 = 8;
-const SAMPLER_REPEAT_U_CLAMP_V_NEAREST: u32 = 9;
+const SAMPLER_START_BIND_INDEX: u32 = 2;
 
 const TEXTURE_START_BIND_INDEX: u32 = 10;
 
@@ -83,7 +78,10 @@ pub struct CurrentPipeline {
 
     dirty: Cell<bool>,
 
-    sampler_override: [Option<SamplerOverride>; 8],
+    sampler_override: [Option<SamplerConfig>; 8],
+
+    // Samplers are immutable, so we only ever need one for each distinct `SamplerConfig`.
+    samplers: RefCell<FnvHashMap<SamplerConfig, Rc<wgpu::Sampler>>>,
 }
 
 pub struct BoundTextureData {
@@ -131,6 +129,7 @@ impl CurrentPipeline {
             sample_count: 1,
 
             sampler_override: [None; 8],
+            samplers: RefCell::new(FnvHashMap::default()),
         }
     }
     pub fn set_vertex_shader(&mut self, shader: Rc<ShaderModuleAgal>) {
@@ -243,58 +242,18 @@ impl CurrentPipeline {
                 },
                 count: None,
             },
-            // One sampler per filter/wrapping combination - see BitmapFilters
-            // An AGAL shader can use any of these samplers, so
-            // we need to bind them all.
-            wgpu::BindGroupLayoutEntry {
-                binding: SAMPLER_REPEAT_LINEAR,
-                visibility: wgpu::ShaderStages::FRAGMENT,
-                ty: wgpu::BindingType::Sampler(SamplerBindingType::Filtering),
-                count: None,
-            },
-            wgpu::BindGroupLayoutEntry {
-                binding: SAMPLER_REPEAT_NEAREST,
-                visibility: wgpu::ShaderStages::FRAGMENT,
-                ty: wgpu::BindingType::Sampler(SamplerBindingType::Filtering),
-                count: None,
-            },
-            wgpu::BindGroupLayoutEntry {
-                binding: SAMPLER_CLAMP_LINEAR,
-                visibility: wgpu::ShaderStages::FRAGMENT,
-                ty: wgpu::BindingType::Sampler(SamplerBindingType::Filtering),
-                count: None,
-            },
-            wgpu::BindGroupLayoutEntry {
-                binding: SAMPLER_CLAMP_NEAREST,
-                visibility: wgpu::ShaderStages::FRAGMENT,
-                ty: wgpu::BindingType::Sampler(SamplerBindingType::Filtering),
-                count: None,
-            },
-            wgpu::BindGroupLayoutEntry {
-                binding: SAMPLER_CLAMP_U_REPEAT_V_LINEAR,
-                visibility: wgpu::ShaderStages::FRAGMENT,
-                ty: wgpu::BindingType::Sampler(SamplerBindingType::Filtering),
-                count: None,
-            },
-            wgpu::BindGroupLayoutEntry {
-                binding: SAMPLER_CLAMP_U_REPEAT_V_NEAREST,
-                visibility: wgpu::ShaderStages::FRAGMENT,
-                ty: wgpu::BindingType::Sampler(SamplerBindingType::Filtering),
-                count: None,
-            },
-            wgpu::BindGroupLayoutEntry {
-                binding: SAMPLER_REPEAT_U_CLAMP_V_LINEAR,
-                visibility: wgpu::ShaderStages::FRAGMENT,
-                ty: wgpu::BindingType::Sampler(SamplerBindingType::Filtering),
-                count: None,
-            },
-            wgpu::BindGroupLayoutEntry {
-                binding: SAMPLER_REPEAT_U_CLAMP_V_NEAREST,
+        ];
+
+        // One sampler per texture sampler register, matching the bindings
+        // generated by `naga_agal`.
+        for i in 0..MAX_TEXTURES {
+            layout_entries.push(wgpu::BindGroupLayoutEntry {
+                binding: SAMPLER_START_BIND_INDEX + i as u32,
                 visibility: wgpu::ShaderStages::FRAGMENT,
                 ty: wgpu::BindingType::Sampler(SamplerBindingType::Filtering),
                 count: None,
-            },
-        ];
+            });
+        }
 
         for (i, bound_texture) in self.bound_textures.iter().enumerate() {
             if let Some(bound_texture) = bound_texture {
@@ -325,6 +284,25 @@ impl CurrentPipeline {
                     entries: &layout_entries,
                 });
 
+        let fragment_agal = &self
+            .fragment_shader
+            .as_ref()
+            .expect("Missing fragment shader")
+            .0;
+
+        // Samplers set with `Context3D.setSamplerStateAt` take priority over
+        // the flags in the shader's `tex` instructions.
+        let shader_sampler_configs = naga_agal::extract_sampler_configs(fragment_agal)
+            .expect("Fragment shader failed to compile");
+        let samplers: Vec<Rc<wgpu::Sampler>> = (0..MAX_TEXTURES)
+            .map(|i| {
+                let config = self.sampler_override[i]
+                    .or(shader_sampler_configs[i])
+                    .unwrap_or_default();
+                self.get_sampler(descriptors, config)
+            })
+            .collect();
+
         let bind_group_label = create_debug_label!("Bind group");
 
         let mut bind_group_entries = vec![
@@ -344,48 +322,15 @@ impl CurrentPipeline {
                     size: Some(NonZeroU64::new(FRAGMENT_SHADER_UNIFORMS_BUFFER_SIZE).unwrap()),
                 }),
             },
-            BindGroupEntry {
-                binding: SAMPLER_REPEAT_LINEAR,
-                resource: BindingResource::Sampler(&descriptors.bitmap_samplers.repeat_linear),
-            },
-            BindGroupEntry {
-                binding: SAMPLER_REPEAT_NEAREST,
-                resource: BindingResource::Sampler(&descriptors.bitmap_samplers.repeat_nearest),
-            },
-            BindGroupEntry {
-                binding: SAMPLER_CLAMP_LINEAR,
-                resource: BindingResource::Sampler(&descriptors.bitmap_samplers.clamp_linear),
-            },
-            BindGroupEntry {
-                binding: SAMPLER_CLAMP_NEAREST,
-                resource: BindingResource::Sampler(&descriptors.bitmap_samplers.clamp_nearest),
-            },
-            BindGroupEntry {
-                binding: SAMPLER_CLAMP_U_REPEAT_V_LINEAR,
-                resource: BindingResource::Sampler(
-                    &descriptors.bitmap_samplers.clamp_u_repeat_v_linear,
-                ),
-            },
-            BindGroupEntry {
-                binding: SAMPLER_CLAMP_U_REPEAT_V_NEAREST,
-                resource: BindingResource::Sampler(
-                    &descriptors.bitmap_samplers.clamp_u_repeat_v_nearest,
-                ),
-            },
-            BindGroupEntry {
-                binding: SAMPLER_REPEAT_U_CLAMP_V_LINEAR,
-                resource: BindingResource::Sampler(
-                    &descriptors.bitmap_samplers.repeat_u_clamp_v_linear,
-                ),
-            },
-            BindGroupEntry {
-                binding: SAMPLER_REPEAT_U_CLAMP_V_NEAREST,
-                resource: BindingResource::Sampler(
-                    &descriptors.bitmap_samplers.repeat_u_clamp_v_nearest,
-                ),
-            },
         ];
 
+        for (i, sampler) in samplers.iter().enumerate() {
+            bind_group_entries.push(BindGroupEntry {
+                binding: SAMPLER_START_BIND_INDEX + i as u32,
+                resource: BindingResource::Sampler(sampler),
+            });
+        }
+
         for (i, bound_texture) in self.bound_textures.iter().enumerate() {
             if let Some(bound_texture) = bound_texture {
                 bind_group_entries.push(BindGroupEntry {
@@ -423,8 +368,6 @@ impl CurrentPipeline {
             })
         });
 
-        let sampler_overrides = &self.sampler_override;
-
         let vertex_naga = naga_agal::agal_to_naga(
             &self
                 .vertex_shader
@@ -432,20 +375,11 @@ impl CurrentPipeline {
                 .expect("Missing vertex shader!")
                 .0,
             &agal_attributes,
-            sampler_overrides,
         )
         .expect("Vertex shader failed to compile");
 
-        let fragment_naga = naga_agal::agal_to_naga(
-            &self
-                .fragment_shader
-                .as_ref()
-                .expect("Missing fragment shader")
-                .0,
-            &[None; 8],
-            sampler_overrides,
-        )
-        .expect("Fragment shader failed to compile");
+        let fragment_naga = naga_agal::agal_to_naga(fragment_agal, &[None; 8])
+            .expect("Fragment shader failed to compile");
 
         let vertex_module = descriptors
             .device
@@ -632,13 +566,59 @@ impl CurrentPipeline {
         }
     }
 
+    /// Returns a sampler matching `config`, creating it if we haven't needed one before.
+    fn get_sampler(&self, descriptors: &Descriptors, config: SamplerConfig) -> Rc<wgpu::Sampler> {
+        self.samplers
+            .borrow_mut()
+            .entry(config)
+            .or_insert_with(|| {
+                let (address_mode_u, address_mode_v) = match config.wrapping {
+                    Wrapping::Clamp => (AddressMode::ClampToEdge, AddressMode::ClampToEdge),
+                    Wrapping::Repeat => (AddressMode::Repeat, AddressMode::Repeat),
+                    Wrapping::ClampURepeatV => (AddressMode::ClampToEdge, AddressMode::Repeat),
+                    Wrapping::RepeatUClampV => (AddressMode::Repeat, AddressMode::ClampToEdge),
+                };
+                let (filter, anisotropy_clamp) = match config.filter {
+                    Filter::Nearest => (FilterMode::Nearest, 1),
+                    Filter::Linear => (FilterMode::Linear, 1),
+                    Filter::Anisotropic2x => (FilterMode::Linear, 2),
+                    Filter::Anisotropic4x => (FilterMode::Linear, 4),
+                    Filter::Anisotropic8x => (FilterMode::Linear, 8),
+                    Filter::Anisotropic16x => (FilterMode::Linear, 16),
+                };
+                // wgpu requires every filter to be linear when anisotropic filtering is used.
+                let (mipmap_filter, lod_max_clamp) = match config.mipmap {
+                    Mipmap::Disable if anisotropy_clamp > 1 => (FilterMode::Linear, 0.0),
+                    Mipmap::Disable => (FilterMode::Nearest, 0.0),
+                    Mipmap::Nearest if anisotropy_clamp > 1 => (FilterMode::Linear, 32.0),
+                    Mipmap::Nearest => (FilterMode::Nearest, 32.0),
+                    Mipmap::Linear => (FilterMode::Linear, 32.0),
+                };
+                Rc::new(descriptors.device.create_sampler(&wgpu::SamplerDescriptor {
+                    label: create_debug_label!("Context3D sampler {:?}", config).as_deref(),
+                    address_mode_u,
+                    address_mode_v,
+                    address_mode_w: AddressMode::ClampToEdge,
+                    mag_filter: filter,
+                    min_filter: filter,
+                    mipmap_filter,
+                    lod_min_clamp: 0.0,
+                    lod_max_clamp,
+                    anisotropy_clamp,
+                    ..Default::default()
+                }))
+            })
+            .clone()
+    }
+
     pub(crate) fn update_sampler_state_at(
         &mut self,
         sampler: usize,
-        wrap: ruffle_render::backend::Context3DWrapMode,
-        filter: ruffle_render::backend::Context3DTextureFilter,
+        wrap: Context3DWrapMode,
+        filter: Context3DTextureFilter,
+        mip_filter: Context3DMipFilter,
     ) {
-        let sampler_override = SamplerOverride {
+        let sampler_override = SamplerConfig {
             wrapping: match wrap {
                 Context3DWrapMode::Clamp => Wrapping::Clamp,
                 Context3DWrapMode::Repeat => Wrapping::Repeat,
@@ -648,10 +628,16 @@ impl CurrentPipeline {
             filter: match filter {
                 Context3DTextureFilter::Linear => Filter::Linear,
                 Context3DTextureFilter::Nearest => Filter::Nearest,
-                _ => unimplemented!(),
+                Context3DTextureFilter::Anisotropic2X => Filter::Anisotropic2x,
+                Context3DTextureFilter::Anisotropic4X => Filter::Anisotropic4x,
+                Context3DTextureFilter::Anisotropic8X => Filter::Anisotropic8x,
+                Context3DTextureFilter::Anisotropic16X => Filter::Anisotropic16x,
+            },
+            mipmap: match mip_filter {
+                Context3DMipFilter::MipNone => Mipmap::Disable,
+                Context3DMipFilter::MipNearest => Mipmap::Nearest,
+                Context3DMipFilter::MipLinear => Mipmap::Linear,
             },
-            // FIXME - implement this
-            mipmap: naga_agal::Mipmap::Disable,
         };
         if self.sampler_override[sampler] != Some(sampler_override) {
             self.dirty.set(true);
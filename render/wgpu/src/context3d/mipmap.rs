@@ -0,0 +1,133 @@
+use crate::descriptors::Descriptors;
+use crate::globals::Globals;
+use crate::utils::create_buffer_with_data;
+use crate::Transforms;
+use wgpu::CommandEncoder;
+
+/// Returns the number of mip levels in a full mip chain for a texture of the given size.
+pub fn mip_level_count(width: u32, height: u32) -> u32 {
+    u32::BITS - width.max(height).max(1).leading_zeros()
+}
+
+/// Creates a view of a single mip level of a single layer of `texture`,
+/// suitable for use as a render attachment.
+pub fn single_level_view(texture: &wgpu::Texture, mip_level: u32, layer: u32) -> wgpu::TextureView {
+    texture.create_view(&wgpu::TextureViewDescriptor {
+        label: create_debug_label!("Context3D texture level {} layer {}", mip_level, layer)
+            .as_deref(),
+        dimension: Some(wgpu::TextureViewDimension::D2),
+        base_mip_level: mip_level,
+        mip_level_count: Some(1),
+        base_array_layer: layer,
+        array_layer_count: Some(1),
+        ..Default::default()
+    })
+}
+
+/// Fills in every mip level (after the first) of one layer of `texture`,
+/// by repeatedly downsampling the previous level with a linear filter.
+pub fn generate_mipmaps(
+    descriptors: &Descriptors,
+    encoder: &mut CommandEncoder,
+    texture: &wgpu::Texture,
+    layer: u32,
+) {
+    let pipeline = descriptors.copy_pipeline(texture.format(), 1);
+
+    for level in 1..texture.mip_level_count() {
+        let source = single_level_view(texture, level - 1, layer);
+        let destination = single_level_view(texture, level, layer);
+        let width = (texture.width() >> level).max(1);
+        let height = (texture.height() >> level).max(1);
+
+        let globals = Globals::new(
+            &descriptors.device,
+            &descriptors.bind_layouts.globals,
+            width,
+            height,
+        );
+        let transforms = Transforms {
+            world_matrix: [
+                [width as f32, 0.0, 0.0, 0.0],
+                [0.0, height as f32, 0.0, 0.0],
+                [0.0, 0.0, 1.0, 0.0],
+                [0.0, 0.0, 0.0, 1.0],
+            ],
+        };
+        let transforms_buffer = create_buffer_with_data(
+            &descriptors.device,
+            bytemuck::cast_slice(&[transforms]),
+            wgpu::BufferUsages::UNIFORM,
+            create_debug_label!("Mipmap transforms buffer"),
+        );
+        let transforms_bind_group =
+            descriptors
+                .device
+                .create_bind_group(&wgpu::BindGroupDescriptor {
+                    layout: &descriptors.bind_layouts.transforms,
+                    entries: &[wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: transforms_buffer.as_entire_binding(),
+                    }],
+                    label: create_debug_label!("Mipmap transforms bind group").as_deref(),
+                });
+        let bitmap_bind_group = descriptors
+            .device
+            .create_bind_group(&wgpu::BindGroupDescriptor {
+                layout: &descriptors.bind_layouts.bitmap,
+                entries: &[
+                    wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: descriptors.quad.texture_transforms.as_entire_binding(),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 1,
+                        resource: wgpu::BindingResource::TextureView(&source),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 2,
+                        resource: wgpu::BindingResource::Sampler(
+                            descriptors.bitmap_samplers.get_sampler(false, true),
+                        ),
+                    },
+                ],
+                label: create_debug_label!("Mipmap bitmap bind group").as_deref(),
+            });
+
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: &destination,
+                ops: wgpu::Operations {
+                    // Every pixel gets overwritten, so this doesn't matter.
+                    load: wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT),
+                    store: true,
+                },
+                resolve_target: None,
+            })],
+            depth_stencil_attachment: None,
+            label: create_debug_label!("Generate mip level {}", level).as_deref(),
+        });
+
+        render_pass.set_pipeline(&pipeline);
+        render_pass.set_bind_group(0, globals.bind_group(), &[]);
+
+        if descriptors.limits.max_push_constant_size > 0 {
+            render_pass.set_push_constants(
+                wgpu::ShaderStages::VERTEX,
+                0,
+                bytemuck::cast_slice(&[transforms]),
+            );
+            render_pass.set_bind_group(1, &bitmap_bind_group, &[]);
+        } else {
+            render_pass.set_bind_group(1, &transforms_bind_group, &[0]);
+            render_pass.set_bind_group(2, &bitmap_bind_group, &[]);
+        }
+
+        render_pass.set_vertex_buffer(0, descriptors.quad.vertices_pos.slice(..));
+        render_pass.set_index_buffer(
+            descriptors.quad.indices.slice(..),
+            wgpu::IndexFormat::Uint32,
+        );
+        render_pass.draw_indexed(0..6, 0, 0..1);
+    }
+}
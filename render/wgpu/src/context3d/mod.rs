@@ -24,6 +24,7 @@ use std::rc::Rc;
 use std::sync::Arc;
 
 mod current_pipeline;
+mod mipmap;
 
 use current_pipeline::CurrentPipeline;
 
@@ -118,6 +119,7 @@ impl WgpuContext3D {
                 width: 0,
                 height: 0,
                 copy_count: Cell::new(0),
+                opaque: Cell::new(false),
             }))
         };
 
@@ -316,6 +318,20 @@ pub struct ShaderModuleAgal(Vec<u8>);
 pub struct TextureWrapper {
     texture: wgpu::Texture,
     format: wgpu::TextureFormat,
+    // Set once ActionScript uploads any mip level other than 0.
+    // Until then, we generate the rest of the mip chain ourselves
+    // whenever level 0 changes.
+    has_explicit_mipmaps: Cell<bool>,
+}
+
+impl TextureWrapper {
+    fn new(texture: wgpu::Texture, format: wgpu::TextureFormat) -> Self {
+        Self {
+            texture,
+            format,
+            has_explicit_mipmaps: Cell::new(false),
+        }
+    }
 }
 
 impl IndexBuffer for IndexBufferWrapper {}
@@ -342,6 +358,15 @@ impl Context3D for WgpuContext3D {
         // If this is None, we haven't called configureBackBuffer yet.
         self.current_texture_view.is_some()
     }
+    fn back_buffer(&self) -> Option<(BitmapHandle, u32, u32)> {
+        self.back_buffer_texture_view.as_ref()?;
+        let texture = as_texture(&self.back_buffer_raw_texture_handle);
+        Some((
+            self.back_buffer_raw_texture_handle.clone(),
+            texture.width,
+            texture.height,
+        ))
+    }
 
     fn create_index_buffer(
         &mut self,
@@ -400,6 +425,14 @@ impl Context3D for WgpuContext3D {
             ));
         }
 
+        // `Texture` always has power-of-two dimensions and may be mipmapped,
+        // while `RectangleTexture` never is.
+        let mip_level_count = if width.is_power_of_two() && height.is_power_of_two() {
+            mipmap::mip_level_count(width, height)
+        } else {
+            1
+        };
+
         let texture = self.descriptors.device.create_texture(&TextureDescriptor {
             label: None,
             size: Extent3d {
@@ -407,7 +440,7 @@ impl Context3D for WgpuContext3D {
                 height,
                 depth_or_array_layers: 1,
             },
-            mip_level_count: 1,
+            mip_level_count,
             sample_count: 1,
             dimension: TextureDimension::D2,
             format,
@@ -416,7 +449,7 @@ impl Context3D for WgpuContext3D {
                 | TextureUsages::COPY_DST
                 | TextureUsages::RENDER_ATTACHMENT,
         });
-        Ok(Rc::new(TextureWrapper { texture, format }))
+        Ok(Rc::new(TextureWrapper::new(texture, format)))
     }
 
     fn create_cube_texture(
@@ -442,7 +475,7 @@ impl Context3D for WgpuContext3D {
                 height: size,
                 depth_or_array_layers: 6,
             },
-            mip_level_count: 1,
+            mip_level_count: mipmap::mip_level_count(size, size),
             sample_count: 1,
             dimension: TextureDimension::D2,
             format,
@@ -454,7 +487,7 @@ impl Context3D for WgpuContext3D {
                 | TextureUsages::COPY_DST
                 | TextureUsages::RENDER_ATTACHMENT,
         });
-        Ok(Rc::new(TextureWrapper { texture, format }))
+        Ok(Rc::new(TextureWrapper::new(texture, format)))
     }
 
     fn process_command<'gc>(
@@ -604,6 +637,7 @@ impl Context3D for WgpuContext3D {
                         width,
                         height,
                         copy_count: Cell::new(0),
+                        opaque: Cell::new(false),
                     }));
                     self.front_buffer_raw_texture_handle = BitmapHandle(Arc::new(Texture {
                         texture: Arc::new(front_buffer_resolve_texture.unwrap()),
@@ -612,6 +646,7 @@ impl Context3D for WgpuContext3D {
                         width,
                         height,
                         copy_count: Cell::new(0),
+                        opaque: Cell::new(false),
                     }));
                 } else {
                     // In non-multisample mode, we don't have a separate resolve buffer,
@@ -624,6 +659,7 @@ impl Context3D for WgpuContext3D {
                         width,
                         height,
                         copy_count: Cell::new(0),
+                        opaque: Cell::new(false),
                     }));
                     self.front_buffer_raw_texture_handle = BitmapHandle(Arc::new(Texture {
                         texture: Arc::new(front_buffer_texture),
@@ -632,6 +668,7 @@ impl Context3D for WgpuContext3D {
                         width,
                         height,
                         copy_count: Cell::new(0),
+                        opaque: Cell::new(false),
                     }));
                     self.current_texture_resolve_view = None;
                 }
@@ -691,7 +728,7 @@ impl Context3D for WgpuContext3D {
                 texture,
                 enable_depth_and_stencil,
                 anti_alias,
-                surface_selector: _,
+                surface_selector,
             } => {
                 let mut sample_count = anti_alias;
                 if sample_count == 0 {
@@ -723,21 +760,27 @@ impl Context3D for WgpuContext3D {
                                     | wgpu::TextureUsages::TEXTURE_BINDING,
                             });
 
-                    self.current_texture_resolve_view = Some(Rc::new(
-                        texture_wrapper.texture.create_view(&Default::default()),
-                    ));
+                    self.current_texture_resolve_view = Some(Rc::new(mipmap::single_level_view(
+                        &texture_wrapper.texture,
+                        0,
+                        surface_selector,
+                    )));
                     self.current_texture_view =
                         Some(Rc::new(msaa_texture.create_view(&Default::default())));
                 } else {
                     self.current_texture_resolve_view = None;
-                    self.current_texture_view = Some(Rc::new(
-                        texture_wrapper.texture.create_view(&Default::default()),
-                    ));
+                    self.current_texture_view = Some(Rc::new(mipmap::single_level_view(
+                        &texture_wrapper.texture,
+                        0,
+                        surface_selector,
+                    )));
                 }
 
-                self.current_texture_view = Some(Rc::new(
-                    texture_wrapper.texture.create_view(&Default::default()),
-                ));
+                self.current_texture_view = Some(Rc::new(mipmap::single_level_view(
+                    &texture_wrapper.texture,
+                    0,
+                    surface_selector,
+                )));
 
                 if enable_depth_and_stencil {
                     self.current_depth_texture_view = Some(self.create_depth_texture(
@@ -923,6 +966,7 @@ impl Context3D for WgpuContext3D {
                 source,
                 dest,
                 layer,
+                mip_level,
             } => {
                 let dest = dest.as_any().downcast_ref::<TextureWrapper>().unwrap();
 
@@ -930,7 +974,18 @@ impl Context3D for WgpuContext3D {
                     unimplemented!("Trying to copy to texture format {:?}", dest.format);
                 }
 
+                if mip_level >= dest.texture.mip_level_count() {
+                    tracing::warn!(
+                        "CopyBitmapToTexture: mip level {} is out of range (texture has {})",
+                        mip_level,
+                        dest.texture.mip_level_count()
+                    );
+                    return;
+                }
+
                 let source_texture = as_texture(&source);
+                let level_width = (dest.texture.width() >> mip_level).max(1);
+                let level_height = (dest.texture.height() >> mip_level).max(1);
 
                 self.buffer_command_encoder.copy_texture_to_texture(
                     ImageCopyTexture {
@@ -941,7 +996,7 @@ impl Context3D for WgpuContext3D {
                     },
                     ImageCopyTexture {
                         texture: &dest.texture,
-                        mip_level: 0,
+                        mip_level,
                         origin: wgpu::Origin3d {
                             x: 0,
                             y: 0,
@@ -950,11 +1005,22 @@ impl Context3D for WgpuContext3D {
                         aspect: wgpu::TextureAspect::All,
                     },
                     Extent3d {
-                        width: source_texture.width,
-                        height: source_texture.height,
+                        width: source_texture.width.min(level_width),
+                        height: source_texture.height.min(level_height),
                         depth_or_array_layers: 1,
                     },
                 );
+
+                if mip_level != 0 {
+                    dest.has_explicit_mipmaps.set(true);
+                } else if !dest.has_explicit_mipmaps.get() {
+                    mipmap::generate_mipmaps(
+                        &self.descriptors,
+                        &mut self.buffer_command_encoder,
+                        &dest.texture,
+                        layer,
+                    );
+                }
             }
             Context3DCommand::SetTextureAt {
                 sampler,
@@ -1079,9 +1145,14 @@ impl Context3D for WgpuContext3D {
                 sampler,
                 wrap,
                 filter,
+                mip_filter,
             } => {
-                self.current_pipeline
-                    .update_sampler_state_at(sampler as usize, wrap, filter);
+                self.current_pipeline.update_sampler_state_at(
+                    sampler as usize,
+                    wrap,
+                    filter,
+                    mip_filter,
+                );
             }
         }
     }
@@ -1,5 +1,5 @@
 use crate::layouts::BindLayouts;
-use crate::pipelines::VERTEX_BUFFERS_DESCRIPTION_POS;
+use crate::pipelines::{DepthSortedPipelines, VERTEX_BUFFERS_DESCRIPTION_POS};
 use crate::shaders::Shaders;
 use crate::{
     create_buffer_with_data, BitmapSamplers, Pipelines, PosColorVertex, PosVertex,
@@ -10,6 +10,11 @@ use std::fmt::Debug;
 use std::mem;
 use std::sync::{Arc, Mutex};
 
+/// The device of a renderer, along with the resources and pipelines that don't
+/// depend on what is being rendered.
+///
+/// These can be shared by any number of renderers, even on different threads,
+/// to avoid creating a device and compiling pipelines for every player.
 pub struct Descriptors {
     pub adapter: wgpu::Adapter,
     pub device: wgpu::Device,
@@ -22,6 +27,8 @@ pub struct Descriptors {
     copy_srgb_pipeline: Mutex<FnvHashMap<(u32, wgpu::TextureFormat), Arc<wgpu::RenderPipeline>>>,
     shaders: Shaders,
     pipelines: Mutex<FnvHashMap<(u32, wgpu::TextureFormat), Arc<Pipelines>>>,
+    depth_sorted_pipelines:
+        Mutex<FnvHashMap<(u32, wgpu::TextureFormat), Arc<DepthSortedPipelines>>>,
     pub default_color_bind_group: wgpu::BindGroup,
 }
 
@@ -65,6 +72,7 @@ impl Descriptors {
             copy_srgb_pipeline: Default::default(),
             shaders,
             pipelines: Default::default(),
+            depth_sorted_pipelines: Default::default(),
             default_color_bind_group,
         }
     }
@@ -245,6 +253,29 @@ impl Descriptors {
             })
             .clone()
     }
+
+    pub fn depth_sorted_pipelines(
+        &self,
+        msaa_sample_count: u32,
+        format: wgpu::TextureFormat,
+    ) -> Arc<DepthSortedPipelines> {
+        let mut pipelines = self
+            .depth_sorted_pipelines
+            .lock()
+            .expect("Pipelines should not be already locked");
+        pipelines
+            .entry((msaa_sample_count, format))
+            .or_insert_with(|| {
+                Arc::new(DepthSortedPipelines::new(
+                    &self.device,
+                    &self.shaders,
+                    format,
+                    msaa_sample_count,
+                    &self.bind_layouts,
+                ))
+            })
+            .clone()
+    }
 }
 
 pub struct Quad {
@@ -274,18 +305,22 @@ impl Quad {
             PosColorVertex {
                 position: [0.0, 0.0],
                 color: [1.0, 1.0, 1.0, 1.0],
+                stroke: PosColorVertex::NO_STROKE,
             },
             PosColorVertex {
                 position: [1.0, 0.0],
                 color: [1.0, 1.0, 1.0, 1.0],
+                stroke: PosColorVertex::NO_STROKE,
             },
             PosColorVertex {
                 position: [1.0, 1.0],
                 color: [1.0, 1.0, 1.0, 1.0],
+                stroke: PosColorVertex::NO_STROKE,
             },
             PosColorVertex {
                 position: [0.0, 1.0],
                 color: [1.0, 1.0, 1.0, 1.0],
+                stroke: PosColorVertex::NO_STROKE,
             },
         ];
         let indices: [u32; 6] = [0, 1, 2, 0, 2, 3];
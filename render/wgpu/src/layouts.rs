@@ -1,5 +1,7 @@
 use crate::globals::GlobalsUniform;
-use crate::{ColorAdjustments, GradientUniforms, TextureTransforms, Transforms};
+use crate::{
+    ColorAdjustments, GradientFilterUniforms, GradientUniforms, TextureTransforms, Transforms,
+};
 
 #[derive(Debug)]
 pub struct BindLayouts {
@@ -11,6 +13,7 @@ pub struct BindLayouts {
     pub blend: wgpu::BindGroupLayout,
     pub color_matrix_filter: wgpu::BindGroupLayout,
     pub blur_filter: wgpu::BindGroupLayout,
+    pub gradient_filter: wgpu::BindGroupLayout,
 }
 
 impl BindLayouts {
@@ -211,6 +214,50 @@ impl BindLayouts {
             label: create_debug_label!("Blur filter binds").as_deref(),
         });
 
+        let gradient_filter = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: wgpu::BufferSize::new(std::mem::size_of::<
+                            GradientFilterUniforms,
+                        >() as u64),
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        multisampled: false,
+                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        multisampled: false,
+                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 3,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
+            ],
+            label: create_debug_label!("Gradient filter binds").as_deref(),
+        });
+
         Self {
             globals,
             transforms,
@@ -220,6 +267,7 @@ impl BindLayouts {
             blend,
             color_matrix_filter,
             blur_filter,
+            gradient_filter,
         }
     }
 }
@@ -120,6 +120,13 @@ impl From<TessVertex> for PosVertex {
 struct PosColorVertex {
     position: [f32; 2],
     color: [f32; 4],
+    /// The offset from the center line for a stroke width of 1 pixel, the width of the stroke,
+    /// and its `LineScaleMode`. The width is negative for vertices which aren't part of a stroke.
+    stroke: [f32; 4],
+}
+
+impl PosColorVertex {
+    const NO_STROKE: [f32; 4] = [0.0, 0.0, -1.0, 0.0];
 }
 
 impl From<TessVertex> for PosColorVertex {
@@ -132,10 +139,36 @@ impl From<TessVertex> for PosColorVertex {
                 f32::from(vertex.color.b) / 255.0,
                 f32::from(vertex.color.a) / 255.0,
             ],
+            stroke: match vertex.stroke {
+                Some(stroke) => [
+                    stroke.offset[0],
+                    stroke.offset[1],
+                    stroke.width,
+                    stroke.scale_mode as u32 as f32,
+                ],
+                None => Self::NO_STROKE,
+            },
         }
     }
 }
 
+/// Arguments of the gradient glow and gradient bevel filters.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Pod, Zeroable)]
+struct GradientFilterUniforms {
+    /// Converts UV coordinates of the source texture to UV coordinates of the blurred texture,
+    /// as `(x_offset, y_offset, x_scale, y_scale)`.
+    source_to_blurred: [f32; 4],
+    /// The offset of the effect in the blurred texture, from `angle` and `distance`.
+    offset: [f32; 2],
+    strength: f32,
+    is_bevel: i32,
+    is_inner: i32,
+    is_outer: i32,
+    is_knockout: i32,
+    _padding: i32,
+}
+
 #[repr(C)]
 #[derive(Copy, Clone, Debug, Pod, Zeroable)]
 struct GradientUniforms {
@@ -278,6 +311,9 @@ pub struct Texture {
     width: u32,
     height: u32,
     copy_count: Cell<u8>,
+
+    /// Whether every pixel of the texture is known to be fully opaque.
+    opaque: Cell<bool>,
 }
 
 impl Texture {
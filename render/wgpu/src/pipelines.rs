@@ -4,7 +4,7 @@ use crate::shaders::Shaders;
 use crate::{MaskState, PosColorVertex, PosVertex, PushConstants, Transforms};
 use enum_map::{enum_map, Enum, EnumMap};
 use std::mem;
-use wgpu::{vertex_attr_array, BlendState};
+use wgpu::vertex_attr_array;
 
 pub const VERTEX_BUFFERS_DESCRIPTION_POS: [wgpu::VertexBufferLayout; 1] =
     [wgpu::VertexBufferLayout {
@@ -22,6 +22,7 @@ pub const VERTEX_BUFFERS_DESCRIPTION_COLOR: [wgpu::VertexBufferLayout; 1] =
         attributes: &vertex_attr_array![
             0 => Float32x2,
             1 => Float32x4,
+            2 => Float32x4,
         ],
     }];
 
@@ -34,20 +35,35 @@ pub struct ShapePipeline {
 #[derive(Debug)]
 pub struct Pipelines {
     pub color: ShapePipeline,
-    /// Renders a bitmap without any blending, and does
-    /// not write to the alpha channel. This is used for
-    /// drawing a finished Stage3D buffer onto the backgroud.
-    pub bitmap_opaque: wgpu::RenderPipeline,
-    /// Like `bitmap_opaque`, but with a no-op `DepthStencilState`.
-    /// This is used when we're inside a `RenderPass` that is
-    /// using a depth buffer, but we don't want to write to it
-    /// or use it any any way.
-    pub bitmap_opaque_dummy_depth: wgpu::RenderPipeline,
+    /// Renders a bitmap without any blending, as fully opaque.
+    /// This is used for drawing a finished Stage3D buffer
+    /// onto the background. Like every other pipeline,
+    /// it respects any active masks.
+    pub bitmap_opaque: ShapePipeline,
     pub bitmap: EnumMap<TrivialBlend, ShapePipeline>,
     pub gradients: ShapePipeline,
     pub complex_blends: EnumMap<ComplexBlend, ShapePipeline>,
     pub color_matrix_filter: wgpu::RenderPipeline,
     pub blur_filter: wgpu::RenderPipeline,
+    pub gradient_filter: wgpu::RenderPipeline,
+}
+
+/// The depth buffer format used when sorting opaque draws.
+pub const DEPTH_SORTING_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Depth32Float;
+
+/// Variants of the shape pipelines that test against a depth buffer, for
+/// drawing the fully opaque draws of a chunk front to back before the rest.
+/// These don't support masks, as chunks with masks aren't sorted.
+#[derive(Debug)]
+pub struct DepthSortedPipelines {
+    /// Draws fully opaque rectangles, writing their depth.
+    pub opaque_color: wgpu::RenderPipeline,
+    /// Draws fully opaque bitmaps, writing their depth.
+    pub opaque_bitmap: wgpu::RenderPipeline,
+    pub color: wgpu::RenderPipeline,
+    pub bitmap_opaque: wgpu::RenderPipeline,
+    pub bitmap: EnumMap<TrivialBlend, wgpu::RenderPipeline>,
+    pub gradients: wgpu::RenderPipeline,
 }
 
 impl ShapePipeline {
@@ -207,55 +223,17 @@ impl Pipelines {
             .try_into()
             .unwrap();
 
-        let bitmap_opaque_pipeline_layout_label =
-            create_debug_label!("Opaque bitmap pipeline layout");
-        let bitmap_opaque_pipeline_layout =
-            device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
-                label: bitmap_opaque_pipeline_layout_label.as_deref(),
-                bind_group_layouts: &bitmap_blend_bindings,
-                push_constant_ranges: full_push_constants,
-            });
-
-        let bitmap_opaque = device.create_render_pipeline(&create_pipeline_descriptor(
-            create_debug_label!("Bitmap opaque copy").as_deref(),
-            &shaders.bitmap_late_saturate_shader,
-            &shaders.bitmap_late_saturate_shader,
-            &bitmap_opaque_pipeline_layout,
-            None,
-            &[Some(wgpu::ColorTargetState {
-                format,
-                blend: Some(BlendState::REPLACE),
-                write_mask: wgpu::ColorWrites::COLOR,
-            })],
-            &VERTEX_BUFFERS_DESCRIPTION_POS,
+        let bitmap_opaque = create_shape_pipeline(
+            "Bitmap opaque copy",
+            device,
+            format,
+            &shaders.bitmap_opaque_shader,
             msaa_sample_count,
-        ));
-
-        let bitmap_opaque_dummy_depth = device.create_render_pipeline(&create_pipeline_descriptor(
-            create_debug_label!("Bitmap opaque copy").as_deref(),
-            &shaders.bitmap_shader,
-            &shaders.bitmap_shader,
-            &bitmap_opaque_pipeline_layout,
-            Some(wgpu::DepthStencilState {
-                format: wgpu::TextureFormat::Stencil8,
-                depth_write_enabled: false,
-                depth_compare: wgpu::CompareFunction::Always,
-                stencil: wgpu::StencilState {
-                    front: wgpu::StencilFaceState::IGNORE,
-                    back: wgpu::StencilFaceState::IGNORE,
-                    read_mask: 0,
-                    write_mask: 0,
-                },
-                bias: wgpu::DepthBiasState::default(),
-            }),
-            &[Some(wgpu::ColorTargetState {
-                format,
-                blend: Some(BlendState::REPLACE),
-                write_mask: wgpu::ColorWrites::COLOR,
-            })],
             &VERTEX_BUFFERS_DESCRIPTION_POS,
-            msaa_sample_count,
-        ));
+            &bitmap_blend_bindings,
+            wgpu::BlendState::REPLACE,
+            full_push_constants,
+        );
 
         let color_matrix_filter_bindings = if device.limits().max_push_constant_size > 0 {
             vec![
@@ -364,15 +342,187 @@ impl Pipelines {
             multiview: None,
         });
 
+        let gradient_filter_bindings = if device.limits().max_push_constant_size > 0 {
+            vec![
+                &bind_layouts.globals,
+                &bind_layouts.bitmap,
+                &bind_layouts.gradient_filter,
+            ]
+        } else {
+            vec![
+                &bind_layouts.globals,
+                &bind_layouts.transforms,
+                &bind_layouts.color_transforms,
+                &bind_layouts.bitmap,
+                &bind_layouts.gradient_filter,
+            ]
+        };
+
+        let gradient_filter_layout =
+            device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: None,
+                bind_group_layouts: &gradient_filter_bindings,
+                push_constant_ranges: full_push_constants,
+            });
+
+        let gradient_filter = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: create_debug_label!("Gradient Filter").as_deref(),
+            layout: Some(&gradient_filter_layout),
+            vertex: wgpu::VertexState {
+                module: &shaders.gradient_filter,
+                entry_point: "main_vertex",
+                buffers: &VERTEX_BUFFERS_DESCRIPTION_POS,
+            },
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                strip_index_format: None,
+                front_face: wgpu::FrontFace::Ccw,
+                cull_mode: None,
+                polygon_mode: wgpu::PolygonMode::default(),
+                unclipped_depth: false,
+                conservative: false,
+            },
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState {
+                count: msaa_sample_count,
+                mask: !0,
+                alpha_to_coverage_enabled: false,
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shaders.gradient_filter,
+                entry_point: "main_fragment",
+                targets: &[Some(format.into())],
+            }),
+            multiview: None,
+        });
+
         Self {
             color: color_pipelines,
             bitmap: EnumMap::from_array(bitmap_pipelines),
             bitmap_opaque,
-            bitmap_opaque_dummy_depth,
             gradients: gradient_pipeline,
             complex_blends: complex_blend_pipelines,
             color_matrix_filter,
             blur_filter,
+            gradient_filter,
+        }
+    }
+}
+
+impl DepthSortedPipelines {
+    pub fn new(
+        device: &wgpu::Device,
+        shaders: &Shaders,
+        format: wgpu::TextureFormat,
+        msaa_sample_count: u32,
+        bind_layouts: &BindLayouts,
+    ) -> Self {
+        let push_constants = &if device.limits().max_push_constant_size > 0 {
+            vec![wgpu::PushConstantRange {
+                stages: wgpu::ShaderStages::VERTEX_FRAGMENT,
+                range: 0..mem::size_of::<PushConstants>() as u32,
+            }]
+        } else {
+            vec![]
+        };
+
+        let color_bindings = if device.limits().max_push_constant_size > 0 {
+            vec![&bind_layouts.globals]
+        } else {
+            vec![
+                &bind_layouts.globals,
+                &bind_layouts.transforms,
+                &bind_layouts.color_transforms,
+            ]
+        };
+
+        let gradient_bindings = if device.limits().max_push_constant_size > 0 {
+            vec![&bind_layouts.globals, &bind_layouts.gradient]
+        } else {
+            vec![
+                &bind_layouts.globals,
+                &bind_layouts.transforms,
+                &bind_layouts.color_transforms,
+                &bind_layouts.gradient,
+            ]
+        };
+
+        let bitmap_bindings = if device.limits().max_push_constant_size > 0 {
+            vec![&bind_layouts.globals, &bind_layouts.bitmap]
+        } else {
+            vec![
+                &bind_layouts.globals,
+                &bind_layouts.transforms,
+                &bind_layouts.color_transforms,
+                &bind_layouts.bitmap,
+            ]
+        };
+
+        let color_pipeline = |name: &str, write_depth| {
+            create_depth_sorted_pipeline(
+                name,
+                device,
+                format,
+                &shaders.color_shader,
+                msaa_sample_count,
+                &VERTEX_BUFFERS_DESCRIPTION_COLOR,
+                &color_bindings,
+                wgpu::BlendState::PREMULTIPLIED_ALPHA_BLENDING,
+                push_constants,
+                write_depth,
+            )
+        };
+
+        let bitmap_pipeline = |name: &str, shader: &wgpu::ShaderModule, blend, write_depth| {
+            create_depth_sorted_pipeline(
+                name,
+                device,
+                format,
+                shader,
+                msaa_sample_count,
+                &VERTEX_BUFFERS_DESCRIPTION_POS,
+                &bitmap_bindings,
+                blend,
+                push_constants,
+                write_depth,
+            )
+        };
+
+        Self {
+            opaque_color: color_pipeline("Opaque color", true),
+            opaque_bitmap: bitmap_pipeline(
+                "Opaque bitmap",
+                &shaders.bitmap_shader,
+                TrivialBlend::Normal.blend_state(),
+                true,
+            ),
+            color: color_pipeline("Color", false),
+            bitmap_opaque: bitmap_pipeline(
+                "Bitmap opaque copy",
+                &shaders.bitmap_opaque_shader,
+                wgpu::BlendState::REPLACE,
+                false,
+            ),
+            bitmap: enum_map! {
+                blend => bitmap_pipeline(
+                    &format!("Bitmap ({blend:?})"),
+                    &shaders.bitmap_shader,
+                    blend.blend_state(),
+                    false,
+                )
+            },
+            gradients: create_depth_sorted_pipeline(
+                "Gradient",
+                device,
+                format,
+                &shaders.gradient_shader,
+                msaa_sample_count,
+                &VERTEX_BUFFERS_DESCRIPTION_POS,
+                &gradient_bindings,
+                wgpu::BlendState::PREMULTIPLIED_ALPHA_BLENDING,
+                push_constants,
+                false,
+            ),
         }
     }
 }
@@ -420,6 +570,50 @@ fn create_pipeline_descriptor<'a>(
     }
 }
 
+/// Creates a pipeline that only draws where it's in front of what was drawn
+/// before, according to the depth buffer, optionally writing its own depth.
+#[allow(clippy::too_many_arguments)]
+fn create_depth_sorted_pipeline(
+    name: &str,
+    device: &wgpu::Device,
+    format: wgpu::TextureFormat,
+    shader: &wgpu::ShaderModule,
+    msaa_sample_count: u32,
+    vertex_buffers_layout: &[wgpu::VertexBufferLayout<'_>],
+    bind_group_layouts: &[&wgpu::BindGroupLayout],
+    blend: wgpu::BlendState,
+    push_constant_ranges: &[wgpu::PushConstantRange],
+    write_depth: bool,
+) -> wgpu::RenderPipeline {
+    let pipeline_layout_label = create_debug_label!("{} depth sorted pipeline layout", name);
+    let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+        label: pipeline_layout_label.as_deref(),
+        bind_group_layouts,
+        push_constant_ranges,
+    });
+
+    device.create_render_pipeline(&create_pipeline_descriptor(
+        create_debug_label!("{} depth sorted pipeline", name).as_deref(),
+        shader,
+        shader,
+        &pipeline_layout,
+        Some(wgpu::DepthStencilState {
+            format: DEPTH_SORTING_FORMAT,
+            depth_write_enabled: write_depth,
+            depth_compare: wgpu::CompareFunction::Less,
+            stencil: Default::default(),
+            bias: Default::default(),
+        }),
+        &[Some(wgpu::ColorTargetState {
+            format,
+            blend: Some(blend),
+            write_mask: wgpu::ColorWrites::ALL,
+        })],
+        vertex_buffers_layout,
+        msaa_sample_count,
+    ))
+}
+
 #[allow(clippy::too_many_arguments)]
 fn create_shape_pipeline(
     name: &str,
@@ -11,18 +11,20 @@ pub struct Shaders {
     pub color_shader: wgpu::ShaderModule,
     pub bitmap_shader: wgpu::ShaderModule,
     /// Like `bitmap_shader` but performs saturation after we've
-    /// re-multiplied the alpha. This is used for the Stage3D
-    /// `bitmap_opaque` pipeline, which needs to able to
-    /// avoid changing initially-in-range rgb values (regadless
-    /// of whether dividing by the alpha value would produce
-    /// an out-of-range value).
-    pub bitmap_late_saturate_shader: wgpu::ShaderModule,
+    /// re-multiplied the alpha, and always outputs an alpha of 1.
+    /// This is used for the Stage3D `bitmap_opaque` pipeline,
+    /// which needs to able to avoid changing initially-in-range
+    /// rgb values (regadless of whether dividing by the alpha value
+    /// would produce an out-of-range value), and needs to cover
+    /// whatever is underneath it even when drawn into a fresh buffer.
+    pub bitmap_opaque_shader: wgpu::ShaderModule,
     pub gradient_shader: wgpu::ShaderModule,
     pub copy_srgb_shader: wgpu::ShaderModule,
     pub copy_shader: wgpu::ShaderModule,
     pub blend_shaders: EnumMap<ComplexBlend, wgpu::ShaderModule>,
     pub color_matrix_filter: wgpu::ShaderModule,
     pub blur_filter: wgpu::ShaderModule,
+    pub gradient_filter: wgpu::ShaderModule,
 }
 
 impl Shaders {
@@ -34,9 +36,11 @@ impl Shaders {
             ShaderDefValue::Bool(device.limits().max_push_constant_size > 0),
         );
         shader_defs.insert("early_saturate".to_owned(), ShaderDefValue::Bool(true));
+        shader_defs.insert("opaque".to_owned(), ShaderDefValue::Bool(false));
 
-        let mut late_saturate_shader_defs = shader_defs.clone();
-        late_saturate_shader_defs.insert("early_saturate".to_owned(), ShaderDefValue::Bool(false));
+        let mut opaque_shader_defs = shader_defs.clone();
+        opaque_shader_defs.insert("early_saturate".to_owned(), ShaderDefValue::Bool(false));
+        opaque_shader_defs.insert("opaque".to_owned(), ShaderDefValue::Bool(true));
 
         let color_shader = make_shader(
             device,
@@ -52,10 +56,10 @@ impl Shaders {
             "bitmap.wgsl",
             include_str!("../shaders/bitmap.wgsl"),
         );
-        let bitmap_late_saturate_shader = make_shader(
+        let bitmap_opaque_shader = make_shader(
             device,
             &mut composer,
-            &late_saturate_shader_defs,
+            &opaque_shader_defs,
             "bitmap.wgsl",
             include_str!("../shaders/bitmap.wgsl"),
         );
@@ -87,6 +91,13 @@ impl Shaders {
             "filter/blur.wgsl",
             include_str!("../shaders/filter/blur.wgsl"),
         );
+        let gradient_filter = make_shader(
+            device,
+            &mut composer,
+            &shader_defs,
+            "filter/gradient.wgsl",
+            include_str!("../shaders/filter/gradient.wgsl"),
+        );
         let gradient_shader = make_shader(
             device,
             &mut composer,
@@ -109,13 +120,14 @@ impl Shaders {
         Self {
             color_shader,
             bitmap_shader,
-            bitmap_late_saturate_shader,
+            bitmap_opaque_shader,
             gradient_shader,
             copy_srgb_shader,
             copy_shader,
             blend_shaders,
             color_matrix_filter,
             blur_filter,
+            gradient_filter,
         }
     }
 }
@@ -4,19 +4,24 @@ pub mod target;
 use crate::backend::RenderTargetMode;
 use crate::blend::ComplexBlend;
 use crate::buffer_pool::TexturePool;
-use crate::mesh::Mesh;
-use crate::surface::commands::{chunk_blends, Chunk, CommandRenderer, LayerRef};
+use crate::mesh::{create_gradient_texture, Mesh};
+use crate::pipelines::DepthSortedPipelines;
+use crate::surface::commands::{chunk_blends, Chunk, CommandRenderer, DrawCommand, LayerRef};
 use crate::uniform_buffer::BufferStorage;
 use crate::utils::{remove_srgb, supported_sample_count};
 use crate::{
-    ColorAdjustments, Descriptors, MaskState, Pipelines, PushConstants, Texture, TextureTransforms,
-    Transforms, UniformBuffer, DEFAULT_COLOR_ADJUSTMENTS,
+    ColorAdjustments, Descriptors, GradientFilterUniforms, MaskState, Pipelines, PushConstants,
+    Texture, TextureTransforms, Transforms, UniformBuffer, DEFAULT_COLOR_ADJUSTMENTS,
 };
 use ruffle_render::commands::CommandList;
 use ruffle_render::filters::Filter;
 use ruffle_render::quality::StageQuality;
+use std::cell::Cell;
 use std::sync::Arc;
-use swf::{BlurFilter, ColorMatrixFilter};
+use swf::{
+    BlurFilter, BlurFilterFlags, ColorMatrixFilter, GradientFilter, GradientInterpolation,
+    Rectangle, Twips,
+};
 use target::CommandTarget;
 use tracing::instrument;
 use wgpu::util::DeviceExt;
@@ -29,6 +34,9 @@ pub struct Surface {
     quality: StageQuality,
     sample_count: u32,
     pipelines: Arc<Pipelines>,
+
+    /// The pipelines used to sort opaque draws, if that's enabled.
+    depth_sorted_pipelines: Option<Arc<DepthSortedPipelines>>,
     format: wgpu::TextureFormat,
     actual_surface_format: wgpu::TextureFormat,
 }
@@ -56,11 +64,23 @@ impl Surface {
             quality,
             sample_count,
             pipelines,
+            depth_sorted_pipelines: None,
             format: frame_buffer_format,
             actual_surface_format: surface_format,
         }
     }
 
+    /// Sets whether chunks of draws without masks are drawn with their fully
+    /// opaque draws first, front to back, so that the depth test skips
+    /// whatever they cover in the draws behind them.
+    ///
+    /// Only the draws made directly on this surface are sorted, and not those
+    /// in the layers of blend modes.
+    pub fn set_opaque_sorting(&mut self, descriptors: &Descriptors, enabled: bool) {
+        self.depth_sorted_pipelines =
+            enabled.then(|| descriptors.depth_sorted_pipelines(self.sample_count, self.format));
+    }
+
     #[allow(clippy::too_many_arguments)]
     #[instrument(level = "debug", skip_all)]
     pub fn draw_commands_to(
@@ -186,12 +206,18 @@ impl Surface {
         for chunk in chunks {
             match chunk {
                 Chunk::Draw(chunk, needs_depth) => {
+                    let depth_sorted_pipelines = self
+                        .depth_sorted_pipelines
+                        .as_deref()
+                        .filter(|_| !needs_depth && chunk.iter().any(DrawCommand::is_opaque));
                     let mut render_pass =
                         draw_encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
                             label: create_debug_label!(
                                 "Chunked draw calls {}",
                                 if needs_depth {
                                     "(with depth)"
+                                } else if depth_sorted_pipelines.is_some() {
+                                    "(Sorted by depth)"
                                 } else {
                                     "(Depthless)"
                                 }
@@ -200,6 +226,8 @@ impl Surface {
                             color_attachments: &[target.color_attachments()],
                             depth_stencil_attachment: if needs_depth {
                                 target.depth_attachment(descriptors, texture_pool)
+                            } else if depth_sorted_pipelines.is_some() {
+                                Some(target.sorting_depth_attachment(descriptors, texture_pool))
                             } else {
                                 None
                             },
@@ -217,8 +245,12 @@ impl Surface {
                         needs_depth,
                     );
 
-                    for command in &chunk {
-                        renderer.execute(command);
+                    if let Some(depth_sorted_pipelines) = depth_sorted_pipelines {
+                        renderer.execute_sorted(&chunk, depth_sorted_pipelines);
+                    } else {
+                        for command in &chunk {
+                            renderer.execute(command);
+                        }
                     }
 
                     num_masks = renderer.num_masks();
@@ -383,6 +415,38 @@ impl Surface {
         dest_point: (u32, u32),
         filter: Filter,
     ) {
+        // Filters such as blurs and shadows extend past the edges of the source,
+        // so the source is padded with transparent pixels to leave room for them.
+        let dest_rect = filter.calculate_dest_rect(Rectangle {
+            x_min: Twips::ZERO,
+            y_min: Twips::ZERO,
+            x_max: Twips::from_pixels_i32(source_size.0 as i32),
+            y_max: Twips::from_pixels_i32(source_size.1 as i32),
+        });
+        let padding = (
+            (-dest_rect.x_min.to_pixels()) as u32,
+            (-dest_rect.y_min.to_pixels()) as u32,
+        );
+        let padded_size = (
+            dest_rect.width().to_pixels() as u32,
+            dest_rect.height().to_pixels() as u32,
+        );
+        let padded_texture;
+        let (source_texture, source_point, source_size) = if padded_size != source_size {
+            padded_texture = self.pad_filter_source(
+                descriptors,
+                draw_encoder,
+                source_texture,
+                source_point,
+                source_size,
+                padding,
+                padded_size,
+            );
+            (&padded_texture, (0, 0), padded_size)
+        } else {
+            (source_texture, source_point, source_size)
+        };
+
         let target = match filter {
             Filter::ColorMatrixFilter(filter) => self.apply_color_matrix(
                 descriptors,
@@ -402,6 +466,26 @@ impl Surface {
                 source_size,
                 &filter,
             ),
+            Filter::GradientGlowFilter(filter) => self.apply_gradient_filter(
+                descriptors,
+                texture_pool,
+                draw_encoder,
+                source_texture,
+                source_point,
+                source_size,
+                &filter,
+                false,
+            ),
+            Filter::GradientBevelFilter(filter) => self.apply_gradient_filter(
+                descriptors,
+                texture_pool,
+                draw_encoder,
+                source_texture,
+                source_point,
+                source_size,
+                &filter,
+                true,
+            ),
             _ => {
                 tracing::warn!("Unsupported filter {filter:?}");
                 // Apply a default color matrix - it's essentially a blit
@@ -422,31 +506,123 @@ impl Surface {
         // the background clear color applied)
         target.ensure_cleared(draw_encoder);
 
+        // The padding is placed around `dest_point`, and cut off at the edges of the destination.
+        let target_origin = (
+            padding.0.saturating_sub(dest_point.0),
+            padding.1.saturating_sub(dest_point.1),
+        );
+        let dest_origin = (
+            dest_point.0.saturating_sub(padding.0),
+            dest_point.1.saturating_sub(padding.1),
+        );
+        if target_origin.0 >= target.width()
+            || target_origin.1 >= target.height()
+            || dest_origin.0 >= dest_texture.width
+            || dest_origin.1 >= dest_texture.height
+        {
+            return;
+        }
+
         draw_encoder.copy_texture_to_texture(
             wgpu::ImageCopyTexture {
                 texture: target.color_texture(),
                 mip_level: 0,
-                origin: wgpu::Origin3d { x: 0, y: 0, z: 0 },
+                origin: wgpu::Origin3d {
+                    x: target_origin.0,
+                    y: target_origin.1,
+                    z: 0,
+                },
                 aspect: Default::default(),
             },
             wgpu::ImageCopyTexture {
                 texture: &dest_texture.texture,
                 mip_level: 0,
                 origin: wgpu::Origin3d {
-                    x: dest_point.0,
-                    y: dest_point.1,
+                    x: dest_origin.0,
+                    y: dest_origin.1,
                     z: 0,
                 },
                 aspect: Default::default(),
             },
             wgpu::Extent3d {
-                width: (target.width()).min(dest_texture.width - dest_point.0),
-                height: (target.height()).min(dest_texture.height - dest_point.1),
+                width: (target.width() - target_origin.0).min(dest_texture.width - dest_origin.0),
+                height: (target.height() - target_origin.1)
+                    .min(dest_texture.height - dest_origin.1),
                 depth_or_array_layers: 1,
             },
         )
     }
 
+    /// Copies a region of `source_texture` into the middle of a new, transparent texture,
+    /// leaving `padding` pixels around it.
+    #[allow(clippy::too_many_arguments)]
+    fn pad_filter_source(
+        &self,
+        descriptors: &Descriptors,
+        draw_encoder: &mut wgpu::CommandEncoder,
+        source_texture: &Texture,
+        source_point: (u32, u32),
+        source_size: (u32, u32),
+        padding: (u32, u32),
+        padded_size: (u32, u32),
+    ) -> Texture {
+        // New textures are always zero-initialized, so only the source needs to be copied.
+        let texture = descriptors.device.create_texture(&wgpu::TextureDescriptor {
+            label: create_debug_label!("Padded filter source").as_deref(),
+            size: wgpu::Extent3d {
+                width: padded_size.0,
+                height: padded_size.1,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Rgba8Unorm,
+            view_formats: &[wgpu::TextureFormat::Rgba8Unorm],
+            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+        });
+        draw_encoder.copy_texture_to_texture(
+            wgpu::ImageCopyTexture {
+                texture: &source_texture.texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d {
+                    x: source_point.0,
+                    y: source_point.1,
+                    z: 0,
+                },
+                aspect: Default::default(),
+            },
+            wgpu::ImageCopyTexture {
+                texture: &texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d {
+                    x: padding.0,
+                    y: padding.1,
+                    z: 0,
+                },
+                aspect: Default::default(),
+            },
+            wgpu::Extent3d {
+                width: source_size
+                    .0
+                    .min(source_texture.width.saturating_sub(source_point.0)),
+                height: source_size
+                    .1
+                    .min(source_texture.height.saturating_sub(source_point.1)),
+                depth_or_array_layers: 1,
+            },
+        );
+        Texture {
+            texture: Arc::new(texture),
+            bind_linear: Default::default(),
+            bind_nearest: Default::default(),
+            width: padded_size.0,
+            height: padded_size.1,
+            copy_count: Cell::new(0),
+            opaque: Cell::new(false),
+        }
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub fn apply_color_matrix(
         &self,
@@ -710,6 +886,172 @@ impl Surface {
             .last()
             .expect("Targets should not be empty")
     }
+
+    /// Applies a gradient glow (or a gradient bevel, if `is_bevel` is set).
+    /// The alpha channel of the blurred source is used to look up a color in the gradient.
+    #[allow(clippy::too_many_arguments)]
+    pub fn apply_gradient_filter(
+        &self,
+        descriptors: &Descriptors,
+        texture_pool: &mut TexturePool,
+        draw_encoder: &mut wgpu::CommandEncoder,
+        source_texture: &Texture,
+        source_point: (u32, u32),
+        source_size: (u32, u32),
+        filter: &GradientFilter,
+        is_bevel: bool,
+    ) -> CommandTarget {
+        let blurred = self.apply_blur(
+            descriptors,
+            texture_pool,
+            draw_encoder,
+            source_texture,
+            source_point,
+            source_size,
+            &BlurFilter {
+                blur_x: filter.blur_x,
+                blur_y: filter.blur_y,
+                flags: BlurFilterFlags::from_passes(filter.num_passes()),
+            },
+        );
+        let target = CommandTarget::new(
+            descriptors,
+            texture_pool,
+            wgpu::Extent3d {
+                width: source_size.0,
+                height: source_size.1,
+                depth_or_array_layers: 1,
+            },
+            self.format,
+            self.sample_count,
+            RenderTargetMode::FreshBuffer(wgpu::Color::TRANSPARENT),
+            draw_encoder,
+        );
+        let texture_transform =
+            make_texture_transform(descriptors, source_size, source_point, source_texture);
+        let source_view = source_texture.texture.create_view(&Default::default());
+        let bitmap_group = descriptors
+            .device
+            .create_bind_group(&wgpu::BindGroupDescriptor {
+                label: create_debug_label!("Bitmap copy group").as_deref(),
+                layout: &descriptors.bind_layouts.bitmap,
+                entries: &[
+                    wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: texture_transform.as_entire_binding(),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 1,
+                        resource: wgpu::BindingResource::TextureView(&source_view),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 2,
+                        resource: wgpu::BindingResource::Sampler(
+                            descriptors.bitmap_samplers.get_sampler(false, false),
+                        ),
+                    },
+                ],
+            });
+
+        let gradient_texture =
+            create_gradient_texture(descriptors, &filter.colors, GradientInterpolation::Rgb);
+        let gradient_view = gradient_texture.create_view(&Default::default());
+        let angle = filter.angle.to_f32();
+        let distance = filter.distance.to_f32();
+        let uniforms = GradientFilterUniforms {
+            source_to_blurred: [
+                source_point.0 as f32 / source_texture.width as f32,
+                source_point.1 as f32 / source_texture.height as f32,
+                source_size.0 as f32 / source_texture.width as f32,
+                source_size.1 as f32 / source_texture.height as f32,
+            ],
+            offset: [
+                angle.cos() * distance / blurred.width() as f32,
+                angle.sin() * distance / blurred.height() as f32,
+            ],
+            strength: filter.strength.to_f32(),
+            is_bevel: is_bevel as i32,
+            is_inner: filter.is_inner() as i32,
+            // Effects which are neither inner nor on top of the source are outer effects.
+            is_outer: (!filter.is_inner() && !filter.is_on_top()) as i32,
+            is_knockout: filter.is_knockout() as i32,
+            _padding: 0,
+        };
+        let buffer = descriptors
+            .device
+            .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: create_debug_label!("Filter arguments").as_deref(),
+                contents: bytemuck::cast_slice(&[uniforms]),
+                usage: wgpu::BufferUsages::UNIFORM,
+            });
+        let filter_group = descriptors
+            .device
+            .create_bind_group(&wgpu::BindGroupDescriptor {
+                label: create_debug_label!("Filter group").as_deref(),
+                layout: &descriptors.bind_layouts.gradient_filter,
+                entries: &[
+                    wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: buffer.as_entire_binding(),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 1,
+                        resource: wgpu::BindingResource::TextureView(blurred.color_view()),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 2,
+                        resource: wgpu::BindingResource::TextureView(&gradient_view),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 3,
+                        resource: wgpu::BindingResource::Sampler(
+                            descriptors.bitmap_samplers.get_sampler(false, true),
+                        ),
+                    },
+                ],
+            });
+        let mut render_pass = draw_encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: create_debug_label!("Gradient filter").as_deref(),
+            color_attachments: &[target.color_attachments()],
+            depth_stencil_attachment: None,
+        });
+        render_pass.set_pipeline(&self.pipelines.gradient_filter);
+
+        render_pass.set_bind_group(0, target.globals().bind_group(), &[]);
+        if descriptors.limits.max_push_constant_size > 0 {
+            render_pass.set_push_constants(
+                wgpu::ShaderStages::VERTEX_FRAGMENT,
+                0,
+                bytemuck::cast_slice(&[PushConstants {
+                    transforms: Transforms {
+                        world_matrix: [
+                            [target.width() as f32, 0.0, 0.0, 0.0],
+                            [0.0, target.height() as f32, 0.0, 0.0],
+                            [0.0, 0.0, 1.0, 0.0],
+                            [0.0, 0.0, 0.0, 1.0],
+                        ],
+                    },
+                    colors: DEFAULT_COLOR_ADJUSTMENTS,
+                }]),
+            );
+            render_pass.set_bind_group(1, &bitmap_group, &[]);
+            render_pass.set_bind_group(2, &filter_group, &[]);
+        } else {
+            render_pass.set_bind_group(1, target.whole_frame_bind_group(descriptors), &[0]);
+            render_pass.set_bind_group(2, &descriptors.default_color_bind_group, &[0]);
+            render_pass.set_bind_group(3, &bitmap_group, &[]);
+            render_pass.set_bind_group(4, &filter_group, &[]);
+        }
+
+        render_pass.set_vertex_buffer(0, descriptors.quad.vertices_pos.slice(..));
+        render_pass.set_index_buffer(
+            descriptors.quad.indices.slice(..),
+            wgpu::IndexFormat::Uint32,
+        );
+        render_pass.draw_indexed(0..6, 0, 0..1);
+        drop(render_pass);
+        target
+    }
 }
 
 fn make_texture_transform(
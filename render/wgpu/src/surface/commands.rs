@@ -4,6 +4,7 @@ use crate::blend::{BlendType, ComplexBlend};
 use crate::buffer_pool::TexturePool;
 use crate::globals::Globals;
 use crate::mesh::{as_mesh, DrawType, Mesh};
+use crate::pipelines::DepthSortedPipelines;
 use crate::surface::target::CommandTarget;
 use crate::surface::Surface;
 use crate::{
@@ -31,6 +32,13 @@ pub struct CommandRenderer<'pass, 'frame: 'pass, 'global: 'frame> {
     color_buffers: &'frame mut UniformBuffer<'global, ColorAdjustments>,
     uniform_encoder: &'frame mut wgpu::CommandEncoder,
     needs_depth: bool,
+
+    /// The pipelines to use while drawing a chunk sorted by depth.
+    depth_sorted_pipelines: Option<&'frame DepthSortedPipelines>,
+    /// Whether the fully opaque draws of a sorted chunk are being drawn.
+    opaque_pass: bool,
+    /// The depth of the next draw, from 1.0 at the back to 0.0 at the front.
+    depth: f32,
 }
 
 impl<'pass, 'frame: 'pass, 'global: 'frame> CommandRenderer<'pass, 'frame, 'global> {
@@ -56,9 +64,46 @@ impl<'pass, 'frame: 'pass, 'global: 'frame> CommandRenderer<'pass, 'frame, 'glob
             color_buffers,
             uniform_encoder,
             needs_depth,
+            depth_sorted_pipelines: None,
+            opaque_pass: false,
+            depth: 0.0,
         }
     }
 
+    /// Executes a chunk without masks, drawing its fully opaque draws first,
+    /// front to back, and then the rest, back to front.
+    ///
+    /// Every draw is given a depth by its place in the chunk, so the depth test
+    /// skips whatever is covered by an opaque draw in front of it.
+    pub fn execute_sorted(
+        &mut self,
+        chunk: &'frame [DrawCommand],
+        pipelines: &'frame DepthSortedPipelines,
+    ) {
+        debug_assert!(!self.needs_depth && self.mask_state == MaskState::NoMask);
+        let depth_step = 1.0 / (chunk.len() + 1) as f32;
+        self.depth_sorted_pipelines = Some(pipelines);
+
+        self.opaque_pass = true;
+        for (index, command) in chunk.iter().enumerate().rev() {
+            if command.is_opaque() {
+                self.depth = 1.0 - (index + 1) as f32 * depth_step;
+                self.execute(command);
+            }
+        }
+
+        self.opaque_pass = false;
+        for (index, command) in chunk.iter().enumerate() {
+            if !command.is_opaque() {
+                self.depth = 1.0 - (index + 1) as f32 * depth_step;
+                self.execute(command);
+            }
+        }
+
+        self.depth_sorted_pipelines = None;
+        self.depth = 0.0;
+    }
+
     pub fn execute(&mut self, command: &'frame DrawCommand) {
         if self.needs_depth {
             match self.mask_state {
@@ -99,7 +144,13 @@ impl<'pass, 'frame: 'pass, 'global: 'frame> CommandRenderer<'pass, 'frame, 'glob
     }
 
     pub fn prep_color(&mut self) {
-        if self.needs_depth {
+        if let Some(pipelines) = self.depth_sorted_pipelines {
+            self.render_pass.set_pipeline(if self.opaque_pass {
+                &pipelines.opaque_color
+            } else {
+                &pipelines.color
+            });
+        } else if self.needs_depth {
             self.render_pass
                 .set_pipeline(self.pipelines.color.pipeline_for(self.mask_state));
         } else {
@@ -109,7 +160,9 @@ impl<'pass, 'frame: 'pass, 'global: 'frame> CommandRenderer<'pass, 'frame, 'glob
     }
 
     pub fn prep_gradient(&mut self, bind_group: &'pass wgpu::BindGroup) {
-        if self.needs_depth {
+        if let Some(pipelines) = self.depth_sorted_pipelines {
+            self.render_pass.set_pipeline(&pipelines.gradients);
+        } else if self.needs_depth {
             self.render_pass
                 .set_pipeline(self.pipelines.gradients.pipeline_for(self.mask_state));
         } else {
@@ -134,22 +187,24 @@ impl<'pass, 'frame: 'pass, 'global: 'frame> CommandRenderer<'pass, 'frame, 'glob
         blend_mode: TrivialBlend,
         render_stage3d: bool,
     ) {
-        match (self.needs_depth, render_stage3d) {
-            (true, true) => {
-                self.render_pass
-                    .set_pipeline(&self.pipelines.bitmap_opaque_dummy_depth);
-            }
-            (true, false) => {
-                self.render_pass
-                    .set_pipeline(self.pipelines.bitmap[blend_mode].pipeline_for(self.mask_state));
-            }
-            (false, true) => {
-                self.render_pass.set_pipeline(&self.pipelines.bitmap_opaque);
-            }
-            (false, false) => {
-                self.render_pass
-                    .set_pipeline(self.pipelines.bitmap[blend_mode].depthless_pipeline());
-            }
+        let pipeline = if render_stage3d {
+            &self.pipelines.bitmap_opaque
+        } else {
+            &self.pipelines.bitmap[blend_mode]
+        };
+        if let Some(pipelines) = self.depth_sorted_pipelines {
+            self.render_pass.set_pipeline(if self.opaque_pass {
+                &pipelines.opaque_bitmap
+            } else if render_stage3d {
+                &pipelines.bitmap_opaque
+            } else {
+                &pipelines.bitmap[blend_mode]
+            });
+        } else if self.needs_depth {
+            self.render_pass
+                .set_pipeline(pipeline.pipeline_for(self.mask_state));
+        } else {
+            self.render_pass.set_pipeline(pipeline.depthless_pipeline());
         }
 
         self.render_pass.set_bind_group(
@@ -184,7 +239,7 @@ impl<'pass, 'frame: 'pass, 'global: 'frame> CommandRenderer<'pass, 'frame, 'glob
             [
                 matrix.tx.to_pixels() as f32,
                 matrix.ty.to_pixels() as f32,
-                0.0,
+                self.depth,
                 1.0,
             ],
         ];
@@ -437,6 +492,27 @@ pub enum DrawCommand {
     PopMask,
 }
 
+impl DrawCommand {
+    /// Whether this draw covers everything it's drawn over with fully opaque pixels.
+    pub fn is_opaque(&self) -> bool {
+        match self {
+            DrawCommand::RenderBitmap {
+                bitmap,
+                transform,
+                blend_mode: TrivialBlend::Normal,
+                render_stage3d: false,
+                ..
+            } => {
+                as_texture(bitmap).opaque.get()
+                    && transform.color_transform.a_multiply >= Fixed8::ONE
+                    && transform.color_transform.a_add >= 0
+            }
+            DrawCommand::DrawRect { color, .. } => color.a == 255,
+            _ => false,
+        }
+    }
+}
+
 #[derive(Copy, Clone)]
 pub enum LayerRef<'a> {
     None,
@@ -468,7 +544,16 @@ pub fn chunk_blends<'a>(
 
     for command in commands {
         match command {
-            Command::Blend(commands, blend_mode) => {
+            command @ (Command::Blend(..) | Command::AlphaMask { .. }) => {
+                let (commands, blend_mode) = match command {
+                    Command::Blend(commands, blend_mode) => (commands, blend_mode),
+                    Command::AlphaMask { mut maskee, mask } => {
+                        // Draw the maskee in its own layer, and then multiply it by the alpha of the mask.
+                        maskee.commands.push(Command::Blend(mask, BlendMode::Alpha));
+                        (maskee, BlendMode::Layer)
+                    }
+                    _ => unreachable!(),
+                };
                 let mut surface = Surface::new(
                     descriptors,
                     quality,
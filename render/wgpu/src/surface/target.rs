@@ -2,6 +2,7 @@ use crate::backend::RenderTargetMode;
 use crate::buffer_pool::{AlwaysCompatible, PoolEntry, TexturePool};
 use crate::descriptors::Descriptors;
 use crate::globals::Globals;
+use crate::pipelines::DEPTH_SORTING_FORMAT;
 use crate::surface::commands::run_copy_pipeline;
 use crate::utils::create_buffer_with_data;
 use crate::Transforms;
@@ -171,13 +172,14 @@ impl DepthBuffer {
         descriptors: &Descriptors,
         msaa_sample_count: u32,
         size: wgpu::Extent3d,
+        format: wgpu::TextureFormat,
         pool: &mut TexturePool,
     ) -> Self {
         let texture = pool.get_texture(
             descriptors,
             size,
             wgpu::TextureUsages::RENDER_ATTACHMENT,
-            wgpu::TextureFormat::Stencil8,
+            format,
             msaa_sample_count,
         );
 
@@ -194,6 +196,7 @@ pub struct CommandTarget {
     blend_buffer: OnceCell<BlendBuffer>,
     resolve_buffer: Option<ResolveBuffer>,
     depth: OnceCell<DepthBuffer>,
+    sorting_depth: OnceCell<DepthBuffer>,
     globals: Arc<Globals>,
     size: wgpu::Extent3d,
     format: wgpu::TextureFormat,
@@ -291,6 +294,7 @@ impl CommandTarget {
             blend_buffer: OnceCell::new(),
             resolve_buffer,
             depth: OnceCell::new(),
+            sorting_depth: OnceCell::new(),
             globals,
             size,
             format,
@@ -358,9 +362,15 @@ impl CommandTarget {
         pool: &mut TexturePool,
     ) -> Option<wgpu::RenderPassDepthStencilAttachment> {
         let new_buffer = self.depth.get().is_none();
-        let depth = self
-            .depth
-            .get_or_init(|| DepthBuffer::new(descriptors, self.sample_count, self.size, pool));
+        let depth = self.depth.get_or_init(|| {
+            DepthBuffer::new(
+                descriptors,
+                self.sample_count,
+                self.size,
+                wgpu::TextureFormat::Stencil8,
+                pool,
+            )
+        });
         Some(wgpu::RenderPassDepthStencilAttachment {
             view: depth.view(),
             depth_ops: Some(wgpu::Operations {
@@ -378,6 +388,32 @@ impl CommandTarget {
         })
     }
 
+    /// The depth buffer of a pass that sorts its opaque draws, which starts
+    /// out cleared for every pass.
+    pub fn sorting_depth_attachment(
+        &self,
+        descriptors: &Descriptors,
+        pool: &mut TexturePool,
+    ) -> wgpu::RenderPassDepthStencilAttachment {
+        let depth = self.sorting_depth.get_or_init(|| {
+            DepthBuffer::new(
+                descriptors,
+                self.sample_count,
+                self.size,
+                DEPTH_SORTING_FORMAT,
+                pool,
+            )
+        });
+        wgpu::RenderPassDepthStencilAttachment {
+            view: depth.view(),
+            depth_ops: Some(wgpu::Operations {
+                load: wgpu::LoadOp::Clear(1.0),
+                store: false,
+            }),
+            stencil_ops: None,
+        }
+    }
+
     pub fn update_blend_buffer(
         &self,
         descriptors: &Descriptors,
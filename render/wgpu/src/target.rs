@@ -63,6 +63,7 @@ impl SwapChainTarget {
         adapter: &wgpu::Adapter,
         (width, height): (u32, u32),
         device: &wgpu::Device,
+        transparent: bool,
     ) -> Self {
         // Ideally we want to use an RGBA non-sRGB surface format, because Flash colors and
         // blending are done in sRGB space -- we don't want the GPU to adjust the colors.
@@ -83,13 +84,35 @@ impl SwapChainTarget {
             // No surface (rendering to texture), default to linear RBGA.
             .unwrap_or(wgpu::TextureFormat::Rgba8Unorm);
 
+        // Our render targets hold premultiplied colors, so a transparent surface must be
+        // composited as premultiplied for the content behind it to show through correctly.
+        let wanted_alpha_mode = if transparent {
+            wgpu::CompositeAlphaMode::PreMultiplied
+        } else {
+            wgpu::CompositeAlphaMode::Opaque
+        };
+        let alpha_mode = if capabilities.alpha_modes.contains(&wanted_alpha_mode) {
+            wanted_alpha_mode
+        } else {
+            if transparent {
+                tracing::warn!(
+                    "Surface doesn't support premultiplied alpha compositing, transparency will be ignored"
+                );
+            }
+            capabilities
+                .alpha_modes
+                .first()
+                .copied()
+                .unwrap_or(wgpu::CompositeAlphaMode::Auto)
+        };
+
         let surface_config = wgpu::SurfaceConfiguration {
             usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
             format,
             width,
             height,
             present_mode: wgpu::PresentMode::Fifo,
-            alpha_mode: capabilities.alpha_modes[0],
+            alpha_mode,
             view_formats: vec![format],
         };
         surface.configure(device, &surface_config);
@@ -5,19 +5,28 @@ use std::{marker::PhantomData, mem};
 use typed_arena::Arena;
 use wgpu::util::StagingBelt;
 
-/// A simple chunked bump allacator for managing dynamic uniforms that change per-draw.
-/// Each draw call may use `UniformBuffer::write_uniforms` can be used to queue
+/// A ring buffer for managing dynamic uniforms that change per-draw.
+/// Each draw call may use `UniformBuffer::write_uniforms` to queue
 /// the upload of uniform data to the GPU.
+///
+/// Every draw binds the same buffer, at its own dynamic offset. The offsets
+/// carry on from where the previous frame left off, wrapping around at the end
+/// of the buffer. Uploads are copies recorded on the queue, so they're always
+/// ordered after the draws of earlier frames that read the same part of the buffer.
 pub struct UniformBuffer<'a, T: Pod> {
     buffers: &'a BufferStorage<T>,
-    cur_block: usize,
+    ring: Option<&'a Ring>,
     cur_offset: u32,
+
+    /// How many bytes of the ring were used in this frame, including the
+    /// bytes skipped at the end of the ring when wrapping around.
+    used: u32,
 }
 
 #[self_referencing]
 pub struct BufferStorage<T: Pod> {
     phantom: PhantomData<T>,
-    arena: Arena<Block>,
+    arena: Arena<Ring>,
 
     #[borrows(arena)]
     #[not_covariant]
@@ -27,15 +36,25 @@ pub struct BufferStorage<T: Pod> {
     aligned_uniforms_size: u32,
 }
 struct Allocator<'a> {
-    arena: &'a Arena<Block>,
-    blocks: Vec<&'a Block>,
+    arena: &'a Arena<Ring>,
+
+    /// The ring in use, which is the largest one allocated so far.
+    /// Smaller rings are kept alive as the draws of the frame that outgrew
+    /// them may still refer to them.
+    ring: Option<&'a Ring>,
+
+    /// The offset that the next frame starts at.
+    head: u32,
 }
 
 impl<T: Pod> BufferStorage<T> {
-    /// The size of each block.
-    /// Uniforms are copied into each block until it reaches capacity, at which point a new
-    /// block will be allocated.
-    pub const BLOCK_SIZE: u32 = 65536;
+    /// The size of the first ring.
+    /// When a frame needs more uniforms than fit in the ring, a ring twice as
+    /// large replaces it.
+    pub const INITIAL_RING_SIZE: u32 = 1 << 20;
+
+    /// The size of each chunk of the staging belt that uniforms are uploaded through.
+    pub const STAGING_CHUNK_SIZE: u64 = 32768;
 
     /// The uniform data size for a single draw call.
     pub const UNIFORMS_SIZE: u64 = mem::size_of::<T>() as u64;
@@ -45,31 +64,32 @@ impl<T: Pod> BufferStorage<T> {
         let align_mask = uniform_alignment - 1;
         let aligned_uniforms_size = (Self::UNIFORMS_SIZE as u32 + align_mask) & !align_mask;
         BufferStorageBuilder {
-            arena: Arena::with_capacity(8),
+            arena: Arena::with_capacity(4),
             allocator_builder: |arena| {
                 RefCell::new(Allocator {
                     arena,
-                    blocks: Vec::with_capacity(8),
+                    ring: None,
+                    head: 0,
                 })
             },
-            staging_belt: RefCell::new(StagingBelt::new(u64::from(Self::BLOCK_SIZE) / 2)),
+            staging_belt: RefCell::new(StagingBelt::new(Self::STAGING_CHUNK_SIZE)),
             aligned_uniforms_size,
             phantom: PhantomData,
         }
         .build()
     }
 
-    /// Adds a newly allocated buffer to the block list, and returns it.
-    pub fn allocate_block(&self, device: &wgpu::Device, layout: &wgpu::BindGroupLayout) {
-        let buffer_label = create_debug_label!("Dynamic buffer");
+    /// Replaces the ring with a newly allocated one of the given size.
+    fn allocate_ring(&self, device: &wgpu::Device, layout: &wgpu::BindGroupLayout, size: u32) {
+        let buffer_label = create_debug_label!("Dynamic uniform ring buffer");
         let buffer = device.create_buffer(&wgpu::BufferDescriptor {
             label: buffer_label.as_deref(),
             usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
-            size: Self::BLOCK_SIZE.into(),
+            size: size.into(),
             mapped_at_creation: false,
         });
 
-        let bind_group_label = create_debug_label!("Dynamic buffer bind group");
+        let bind_group_label = create_debug_label!("Dynamic uniform ring buffer bind group");
         let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
             label: bind_group_label.as_deref(),
             layout,
@@ -85,8 +105,13 @@ impl<T: Pod> BufferStorage<T> {
 
         self.with_allocator(|alloc| {
             let mut alloc = alloc.borrow_mut();
-            let block = alloc.arena.alloc(Block { buffer, bind_group });
-            alloc.blocks.push(block);
+            let ring = alloc.arena.alloc(Ring {
+                buffer,
+                bind_group,
+                size,
+            });
+            alloc.ring = Some(ring);
+            alloc.head = 0;
         });
     }
 
@@ -98,11 +123,55 @@ impl<T: Pod> BufferStorage<T> {
 impl<'a, T: Pod> UniformBuffer<'a, T> {
     /// Creates a new `UniformBuffer` with the given uniform layout.
     pub fn new(buffers: &'a mut BufferStorage<T>) -> Self {
+        let buffers: &'a BufferStorage<T> = buffers;
+        let (ring, head) = buffers.with_allocator(|alloc| {
+            let alloc = alloc.borrow();
+            (alloc.ring, alloc.head)
+        });
         Self {
             buffers,
-            cur_block: 0,
-            cur_offset: 0,
+            ring,
+            cur_offset: head,
+            used: 0,
+        }
+    }
+
+    /// Finds room for the uniforms of a draw, growing the ring if this frame filled it.
+    fn reserve(
+        &mut self,
+        device: &wgpu::Device,
+        layout: &wgpu::BindGroupLayout,
+    ) -> (&'a Ring, u32) {
+        let size = *self.buffers.borrow_aligned_uniforms_size();
+
+        if let Some(ring) = self.ring {
+            let mut offset = self.cur_offset;
+            let mut used = self.used;
+            // Wrap around if the uniforms don't fit at the end of the ring.
+            if offset + size > ring.size {
+                used += ring.size - offset;
+                offset = 0;
+            }
+            if used + size <= ring.size {
+                self.cur_offset = offset + size;
+                self.used = used + size;
+                return (ring, offset);
+            }
         }
+
+        let ring_size = self
+            .ring
+            .map_or(BufferStorage::<T>::INITIAL_RING_SIZE, |ring| ring.size * 2)
+            .max(size);
+        self.buffers.allocate_ring(device, layout, ring_size);
+        let ring = self
+            .buffers
+            .with_allocator(|alloc| alloc.borrow().ring)
+            .expect("Ring was just allocated");
+        self.ring = Some(ring);
+        self.cur_offset = size;
+        self.used = size;
+        (ring, 0)
     }
 
     /// Enqueue `data` for upload into the given command encoder, and set the bind group on `render_pass`
@@ -118,26 +187,15 @@ impl<'a, T: Pod> UniformBuffer<'a, T> {
     ) where
         'a: 'b,
     {
-        // Allocate a new block if we've exceeded our capacity.
-        if self.cur_block
-            >= self
-                .buffers
-                .with_allocator(|alloc| alloc.borrow().blocks.len())
-        {
-            self.buffers.allocate_block(device, layout);
-        }
-
-        let block: &'a Block = self
-            .buffers
-            .with_allocator(|alloc| alloc.borrow().blocks[self.cur_block]);
+        let (ring, offset) = self.reserve(device, layout);
 
         // Copy the data into the buffer via the staging belt.
         self.buffers.with_staging_belt(|belt| {
             belt.borrow_mut()
                 .write_buffer(
                     command_encoder,
-                    &block.buffer,
-                    self.cur_offset.into(),
+                    &ring.buffer,
+                    offset.into(),
                     BufferStorage::<T>::UNIFORMS_SIZE.try_into().unwrap(),
                     device,
                 )
@@ -145,29 +203,23 @@ impl<'a, T: Pod> UniformBuffer<'a, T> {
         });
 
         // Set the bind group to the final uniform location.
-        render_pass.set_bind_group(bind_group_index, &block.bind_group, &[self.cur_offset]);
-
-        // Advance offset.
-        self.cur_offset += self.buffers.borrow_aligned_uniforms_size();
-        // Advance to next buffer if we are out of room in this buffer.
-        if BufferStorage::<T>::BLOCK_SIZE - self.cur_offset
-            < *self.buffers.borrow_aligned_uniforms_size()
-        {
-            self.cur_block += 1;
-            self.cur_offset = 0;
-        }
+        render_pass.set_bind_group(bind_group_index, &ring.bind_group, &[offset]);
     }
 
     /// Should be called at the end of a frame.
     pub fn finish(self) {
+        let head = self.cur_offset;
+        self.buffers
+            .with_allocator(|alloc| alloc.borrow_mut().head = head);
         self.buffers
             .with_staging_belt(|belt| belt.borrow_mut().finish());
     }
 }
 
-/// A block of GPU memory that will contain our uniforms.
+/// A buffer of GPU memory that will contain our uniforms.
 #[derive(Debug)]
-struct Block {
+struct Ring {
     buffer: wgpu::Buffer,
     bind_group: wgpu::BindGroup,
+    size: u32,
 }
@@ -2,8 +2,12 @@
 
 use crate::cli_options::AnalyzeOpt;
 use crate::file_results::{FileResults, Step};
+use std::collections::HashMap;
 use std::fs::File;
 
+/// How many of the most used APIs to list.
+const NUM_TOP_APIS: usize = 50;
+
 /// Generate and print statistics related to a scan's results
 pub fn analyze(results: impl Iterator<Item = FileResults>) {
     let mut total = 0;
@@ -13,10 +17,15 @@ pub fn analyze(results: impl Iterator<Item = FileResults>) {
     let mut parse = 0;
     let mut execute = 0;
     let mut complete = 0;
+    let mut api_usage: HashMap<String, usize> = HashMap::new();
 
     for result in results {
         total += 1;
 
+        for api in result.apis {
+            *api_usage.entry(api).or_default() += 1;
+        }
+
         match result.progress {
             Step::Start => start += 1,
             Step::Read => read += 1,
@@ -48,6 +57,19 @@ pub fn analyze(results: impl Iterator<Item = FileResults>) {
     println!("{execute:>digits$} movies failed to execute");
     println!("{complete:>digits$} movies completed without errors");
     println!();
+
+    if !api_usage.is_empty() {
+        let mut api_usage: Vec<_> = api_usage.into_iter().collect();
+        api_usage.sort_by(|(a_name, a_count), (b_name, b_count)| {
+            b_count.cmp(a_count).then_with(|| a_name.cmp(b_name))
+        });
+
+        println!("Most used APIs:");
+        for (api, count) in api_usage.iter().take(NUM_TOP_APIS) {
+            println!("{count:>digits$} {api}");
+        }
+        println!();
+    }
 }
 
 pub fn analyze_main(opt: AnalyzeOpt) -> Result<(), std::io::Error> {
@@ -2,6 +2,7 @@
 
 use crate::cli_options::ExecuteReportOpt;
 use crate::file_results::{AvmType, FileResults, Step};
+use crate::fingerprint::Fingerprint;
 use crate::logging::{ScanLogBackend, ThreadLocalScanLogger, LOCAL_LOGGER};
 use ruffle_core::backend::navigator::{NullExecutor, NullNavigatorBackend};
 use ruffle_core::limits::ExecutionLimit;
@@ -128,6 +129,10 @@ pub fn execute_report_main(execute_report_opt: ExecuteReportOpt) -> Result<(), s
                     true => AvmType::Avm2,
                     false => AvmType::Avm1,
                 });
+
+                let fingerprint = Fingerprint::new(&swf, &swf_buf.data);
+                file_result.tags = fingerprint.tags.into_iter().collect();
+                file_result.apis = fingerprint.apis.into_iter().collect();
             }
             Err(e) => {
                 file_result.error = Some(format!("Parse error: {e}"));
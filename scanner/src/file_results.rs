@@ -126,6 +126,22 @@ pub struct FileResults {
     /// The AVM type of the movie.
     #[serde(rename = "AVM Version")]
     pub vm_type: Option<AvmType>,
+
+    /// The tags used by the movie.
+    #[serde(
+        rename = "Tags",
+        serialize_with = "into_list",
+        deserialize_with = "from_list"
+    )]
+    pub tags: Vec<String>,
+
+    /// The built-in ActionScript APIs referenced by the movie.
+    #[serde(
+        rename = "APIs",
+        serialize_with = "into_list",
+        deserialize_with = "from_list"
+    )]
+    pub apis: Vec<String>,
 }
 
 impl Default for FileResults {
@@ -153,6 +169,8 @@ impl FileResults {
             use_gpu: None,
             use_network_sandbox: None,
             vm_type: None,
+            tags: vec![],
+            apis: vec![],
         }
     }
 }
@@ -170,6 +188,23 @@ where
     s.serialize_str(&out)
 }
 
+/// Formats a list as a single space-separated string
+fn into_list<S>(list: &[String], s: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    s.serialize_str(&list.join(" "))
+}
+
+/// Parses space-separated strings into a list
+fn from_list<'de, D>(d: D) -> Result<Vec<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let list = String::deserialize(d)?;
+    Ok(list.split_whitespace().map(str::to_string).collect())
+}
+
 /// Parses hex strings into data
 fn from_hex<'de, D>(d: D) -> Result<Vec<u8>, D::Error>
 where
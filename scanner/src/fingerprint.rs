@@ -0,0 +1,303 @@
+//! Static analysis of SWF files.
+//!
+//! A fingerprint lists the tags used by a movie, and the built-in ActionScript APIs
+//! referenced by its bytecode. Across a whole archive, this tells which unimplemented
+//! features would affect the most content.
+
+use std::collections::BTreeSet;
+use swf::avm1::read::Reader as Avm1Reader;
+use swf::avm1::types::{Action, Value as Avm1Value};
+use swf::avm2::read::Reader as Avm2Reader;
+use swf::avm2::types::{AbcFile, Index, Multiname, Namespace, Op};
+use swf::extensions::ReadSwfExt;
+use swf::read::Reader;
+use swf::{Swf, SwfStr, Tag, TagCode};
+
+/// Global classes and functions of AVM1, including the ones that Ruffle doesn't implement.
+const AVM1_GLOBALS: &[&str] = &[
+    "ASSetPropFlags",
+    "Accessibility",
+    "Array",
+    "AsBroadcaster",
+    "Boolean",
+    "Button",
+    "Camera",
+    "Color",
+    "ContextMenu",
+    "ContextMenuItem",
+    "CustomActions",
+    "Date",
+    "Error",
+    "Function",
+    "Key",
+    "LoadVars",
+    "LocalConnection",
+    "Math",
+    "Microphone",
+    "Mouse",
+    "MovieClip",
+    "MovieClipLoader",
+    "NetConnection",
+    "NetStream",
+    "Number",
+    "Object",
+    "PrintJob",
+    "Selection",
+    "SharedObject",
+    "Sound",
+    "Stage",
+    "String",
+    "System",
+    "TextField",
+    "TextFormat",
+    "TextSnapshot",
+    "Video",
+    "XML",
+    "XMLNode",
+    "XMLSocket",
+    "clearInterval",
+    "clearTimeout",
+    "escape",
+    "flash",
+    "isFinite",
+    "isNaN",
+    "parseFloat",
+    "parseInt",
+    "setInterval",
+    "setTimeout",
+    "trace",
+    "unescape",
+    "updateAfterEvent",
+];
+
+/// Packages of AVM2 built-in classes.
+fn is_builtin_package(package: &str) -> bool {
+    package.starts_with("flash.") || package == "adobe.utils" || package == "authoring"
+}
+
+/// The tags and built-in APIs used by a movie.
+#[derive(Debug, Default)]
+pub struct Fingerprint {
+    pub tags: BTreeSet<String>,
+    pub apis: BTreeSet<String>,
+}
+
+impl Fingerprint {
+    /// Analyzes a parsed SWF.
+    ///
+    /// `data` is the decompressed data of the SWF, used to list the tags as they're
+    /// stored, including unknown ones.
+    pub fn new(swf: &Swf<'_>, data: &[u8]) -> Self {
+        let mut fingerprint = Self::default();
+        fingerprint.add_tag_codes(data, swf.header.version());
+        fingerprint.add_tags(&swf.tags, swf.header.version());
+        fingerprint
+    }
+
+    fn add_tag_codes(&mut self, data: &[u8], version: u8) {
+        let mut reader = Reader::new(data, version);
+        while let Ok((tag_code, length)) = reader.read_tag_code_and_length() {
+            let Ok(tag_data) = reader.read_slice(length) else {
+                break;
+            };
+
+            self.tags.insert(TagCode::format(tag_code));
+            match TagCode::from_u16(tag_code) {
+                Some(TagCode::End) => break,
+                // Skip the sprite ID and frame count to get to the tags of the sprite.
+                Some(TagCode::DefineSprite) if tag_data.len() > 4 => {
+                    self.add_tag_codes(&tag_data[4..], version)
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn add_tags(&mut self, tags: &[Tag<'_>], version: u8) {
+        for tag in tags {
+            match tag {
+                Tag::DoAction(action_data) | Tag::DoInitAction { action_data, .. } => {
+                    self.add_avm1_actions(action_data, version)
+                }
+                Tag::PlaceObject(place_object) => {
+                    for clip_action in place_object.clip_actions.iter().flatten() {
+                        self.add_avm1_actions(clip_action.action_data, version);
+                    }
+                }
+                Tag::DefineButton(button) | Tag::DefineButton2(button) => {
+                    for button_action in &button.actions {
+                        self.add_avm1_actions(button_action.action_data, version);
+                    }
+                }
+                Tag::DefineSprite(sprite) => self.add_tags(&sprite.tags, version),
+                Tag::DoAbc(data) => self.add_abc(data),
+                Tag::DoAbc2(do_abc) => self.add_abc(do_abc.data),
+                _ => {}
+            }
+        }
+    }
+
+    /// Records the global classes and functions referenced by AVM1 bytecode.
+    ///
+    /// This tracks the values pushed on the stack within runs of simple actions, which is
+    /// enough to catch the usual `Key.isDown(...)` or `new XMLSocket()` patterns.
+    fn add_avm1_actions(&mut self, action_data: &[u8], version: u8) {
+        #[derive(Clone)]
+        enum Tracked {
+            Str(String),
+            Global(String),
+            Unknown,
+        }
+
+        let encoding = SwfStr::encoding_for_version(version);
+        let mut constant_pool: Vec<String> = Vec::new();
+        let mut stack: Vec<Tracked> = Vec::new();
+        let mut reader = Avm1Reader::new(action_data, version);
+
+        while !reader.get_ref().is_empty() {
+            let Ok(action) = reader.read_action() else {
+                break;
+            };
+
+            match action {
+                Action::ConstantPool(pool) => {
+                    constant_pool = pool
+                        .strings
+                        .iter()
+                        .map(|s| s.to_string_lossy(encoding))
+                        .collect();
+                }
+                Action::Push(push) => {
+                    for value in push.values {
+                        stack.push(match value {
+                            Avm1Value::Str(s) => Tracked::Str(s.to_string_lossy(encoding)),
+                            Avm1Value::ConstantPool(i) => constant_pool
+                                .get(usize::from(i))
+                                .map_or(Tracked::Unknown, |s| Tracked::Str(s.clone())),
+                            _ => Tracked::Unknown,
+                        });
+                    }
+                }
+                Action::GetVariable | Action::CallFunction | Action::NewObject => {
+                    match stack.pop() {
+                        Some(Tracked::Str(name)) if AVM1_GLOBALS.contains(&name.as_str()) => {
+                            self.apis.insert(name.clone());
+                            stack.clear();
+                            if matches!(action, Action::GetVariable) {
+                                stack.push(Tracked::Global(name));
+                            }
+                        }
+                        _ => stack.clear(),
+                    }
+                }
+                Action::GetMember | Action::CallMethod | Action::NewMethod => {
+                    let member = stack.pop();
+                    let object = stack.pop();
+                    match (object, member) {
+                        (Some(Tracked::Global(object)), Some(Tracked::Str(member))) => {
+                            let name = format!("{object}.{member}");
+                            self.apis.insert(name.clone());
+                            stack.clear();
+                            if matches!(action, Action::GetMember) {
+                                stack.push(Tracked::Global(name));
+                            }
+                        }
+                        _ => stack.clear(),
+                    }
+                }
+                Action::DefineFunction(function) => {
+                    stack.clear();
+                    self.add_avm1_actions(function.actions, version);
+                }
+                Action::DefineFunction2(function) => {
+                    stack.clear();
+                    self.add_avm1_actions(function.actions, version);
+                }
+                Action::With(with) => {
+                    stack.clear();
+                    self.add_avm1_actions(with.actions, version);
+                }
+                Action::Try(try_block) => {
+                    stack.clear();
+                    self.add_avm1_actions(try_block.try_body, version);
+                    if let Some((_, catch_body)) = try_block.catch_body {
+                        self.add_avm1_actions(catch_body, version);
+                    }
+                    if let Some(finally_body) = try_block.finally_body {
+                        self.add_avm1_actions(finally_body, version);
+                    }
+                }
+                _ => stack.clear(),
+            }
+        }
+    }
+
+    /// Records the built-in classes and functions referenced by an ABC file.
+    fn add_abc(&mut self, data: &[u8]) {
+        let Ok(abc) = Avm2Reader::new(data).read() else {
+            return;
+        };
+
+        for instance in &abc.instances {
+            self.add_multiname(&abc, instance.super_name);
+            for interface in &instance.interfaces {
+                self.add_multiname(&abc, *interface);
+            }
+        }
+
+        for body in &abc.method_bodies {
+            let mut reader = Avm2Reader::new(&body.code);
+            while let Ok(op) = reader.read_op() {
+                match op {
+                    Op::CallProperty { index, .. }
+                    | Op::CallPropLex { index, .. }
+                    | Op::CallPropVoid { index, .. }
+                    | Op::Coerce { index }
+                    | Op::ConstructProp { index, .. }
+                    | Op::FindDef { index }
+                    | Op::FindProperty { index }
+                    | Op::FindPropStrict { index }
+                    | Op::GetLex { index }
+                    | Op::GetProperty { index }
+                    | Op::IsType { index }
+                    | Op::SetProperty { index } => self.add_multiname(&abc, index),
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    /// Records a QName if it belongs to a built-in package, such as `flash.net::XMLSocket`.
+    fn add_multiname(&mut self, abc: &AbcFile, index: Index<Multiname>) {
+        let string = |index: Index<String>| {
+            let index = index.0.checked_sub(1)? as usize;
+            abc.constant_pool.strings.get(index)
+        };
+
+        let Some(multiname) = index
+            .0
+            .checked_sub(1)
+            .and_then(|i| abc.constant_pool.multinames.get(i as usize))
+        else {
+            return;
+        };
+        let (Multiname::QName { namespace, name } | Multiname::QNameA { namespace, name }) =
+            multiname
+        else {
+            return;
+        };
+        let Some(Namespace::Package(package)) = namespace
+            .0
+            .checked_sub(1)
+            .and_then(|i| abc.constant_pool.namespaces.get(i as usize))
+        else {
+            return;
+        };
+
+        if let (Some(package), Some(name)) = (string(*package), string(*name)) {
+            if is_builtin_package(package) {
+                self.apis.insert(format!("{package}::{name}"));
+            }
+        }
+    }
+}
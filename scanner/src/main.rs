@@ -8,6 +8,7 @@ mod analyze;
 mod cli_options;
 mod execute;
 mod file_results;
+mod fingerprint;
 mod logging;
 mod scan;
 mod ser_bridge;
@@ -0,0 +1,21 @@
+//! Parses a block of AVM1 actions, as done while executing `DoAction` tags.
+
+#![no_main]
+
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+
+#[derive(Arbitrary, Debug)]
+struct ActionInput<'a> {
+    swf_version: u8,
+    data: &'a [u8],
+}
+
+fuzz_target!(|input: ActionInput<'_>| {
+    let mut reader = swf::avm1::read::Reader::new(input.data, input.swf_version);
+    while !reader.get_ref().is_empty() {
+        if reader.read_action().is_err() {
+            break;
+        }
+    }
+});
@@ -0,0 +1,16 @@
+//! Parses an ABC file, and the bytecode of all of its method bodies.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(abc) = swf::avm2::read::Reader::new(data).read() else {
+        return;
+    };
+
+    for body in &abc.method_bodies {
+        let mut reader = swf::avm2::read::Reader::new(&body.code);
+        while reader.read_op().is_ok() {}
+    }
+});
@@ -0,0 +1,11 @@
+//! Decompresses and parses entire SWF files.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(swf_buf) = swf::decompress_swf(data) {
+        let _ = swf::parse_swf(&swf_buf);
+    }
+});
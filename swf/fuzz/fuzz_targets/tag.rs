@@ -0,0 +1,24 @@
+//! Parses a single tag, with a valid header so that the fuzzer can focus on the tag contents.
+
+#![no_main]
+
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+
+#[derive(Arbitrary, Debug)]
+struct TagInput<'a> {
+    swf_version: u8,
+    tag_code: u16,
+    data: &'a [u8],
+}
+
+fuzz_target!(|input: TagInput<'_>| {
+    // Always use a long tag header, which can hold any tag length.
+    let mut tag = Vec::with_capacity(input.data.len() + 6);
+    tag.extend_from_slice(&((input.tag_code << 6) | 0b111111).to_le_bytes());
+    tag.extend_from_slice(&(input.data.len() as u32).to_le_bytes());
+    tag.extend_from_slice(input.data);
+
+    let mut reader = swf::read::Reader::new(&tag, input.swf_version);
+    let _ = reader.read_tag();
+});
@@ -40,36 +40,36 @@ impl<'a> Reader<'a> {
         let constant_pool = self.read_constant_pool()?;
 
         let len = self.read_u30()?;
-        let mut methods = Vec::with_capacity(len as usize);
+        let mut methods = Vec::with_capacity(self.capacity_hint(len));
         for _ in 0..len {
             methods.push(self.read_method()?);
         }
 
         let len = self.read_u30()?;
-        let mut metadata = Vec::with_capacity(len as usize);
+        let mut metadata = Vec::with_capacity(self.capacity_hint(len));
         for _ in 0..len {
             metadata.push(self.read_metadata()?);
         }
 
         let len = self.read_u30()?;
-        let mut instances = Vec::with_capacity(len as usize);
+        let mut instances = Vec::with_capacity(self.capacity_hint(len));
         for _ in 0..len {
             instances.push(self.read_instance()?);
         }
 
-        let mut classes = Vec::with_capacity(len as usize);
+        let mut classes = Vec::with_capacity(self.capacity_hint(len));
         for _ in 0..len {
             classes.push(self.read_class()?);
         }
 
         let len = self.read_u30()?;
-        let mut scripts = Vec::with_capacity(len as usize);
+        let mut scripts = Vec::with_capacity(self.capacity_hint(len));
         for _ in 0..len {
             scripts.push(self.read_script()?);
         }
 
         let len = self.read_u30()?;
-        let mut method_bodies = Vec::with_capacity(len as usize);
+        let mut method_bodies = Vec::with_capacity(self.capacity_hint(len));
         for _ in 0..len {
             method_bodies.push(self.read_method_body()?);
         }
@@ -105,7 +105,7 @@ impl<'a> Reader<'a> {
     fn read_string(&mut self) -> Result<String> {
         let len = self.read_u30()?;
         // TODO: Avoid allocating a String.
-        let mut s = String::with_capacity(len as usize);
+        let mut s = String::with_capacity(self.capacity_hint(len));
         self.read_slice(len as usize)?.read_to_string(&mut s)?;
         Ok(s)
     }
@@ -134,7 +134,7 @@ impl<'a> Reader<'a> {
 
     fn read_namespace_set(&mut self) -> Result<NamespaceSet> {
         let len = self.read_u30()?;
-        let mut namespace_set = Vec::with_capacity(len as usize);
+        let mut namespace_set = Vec::with_capacity(self.capacity_hint(len));
         for _ in 0..len {
             namespace_set.push(self.read_index()?);
         }
@@ -177,7 +177,7 @@ impl<'a> Reader<'a> {
             0x1d => {
                 let base_type = self.read_index()?;
                 let count = self.read_u30()?;
-                let mut parameters = Vec::with_capacity(count as usize);
+                let mut parameters = Vec::with_capacity(self.capacity_hint(count));
 
                 for _ in 0..count {
                     parameters.push(self.read_index()?);
@@ -194,43 +194,43 @@ impl<'a> Reader<'a> {
 
     fn read_constant_pool(&mut self) -> Result<ConstantPool> {
         let len = self.read_u30()?.saturating_sub(1);
-        let mut ints = Vec::with_capacity(len as usize);
+        let mut ints = Vec::with_capacity(self.capacity_hint(len));
         for _ in 0..len {
             ints.push(self.read_i32()?);
         }
 
         let len = self.read_u30()?.saturating_sub(1);
-        let mut uints = Vec::with_capacity(len as usize);
+        let mut uints = Vec::with_capacity(self.capacity_hint(len));
         for _ in 0..len {
             uints.push(self.read_u30()?);
         }
 
         let len = self.read_u30()?.saturating_sub(1);
-        let mut doubles = Vec::with_capacity(len as usize);
+        let mut doubles = Vec::with_capacity(self.capacity_hint(len));
         for _ in 0..len {
             doubles.push(self.read_f64()?);
         }
 
         let len = self.read_u30()?.saturating_sub(1);
-        let mut strings = Vec::with_capacity(len as usize);
+        let mut strings = Vec::with_capacity(self.capacity_hint(len));
         for _ in 0..len {
             strings.push(self.read_string()?);
         }
 
         let len = self.read_u30()?.saturating_sub(1);
-        let mut namespaces = Vec::with_capacity(len as usize);
+        let mut namespaces = Vec::with_capacity(self.capacity_hint(len));
         for _ in 0..len {
             namespaces.push(self.read_namespace()?);
         }
 
         let len = self.read_u30()?.saturating_sub(1);
-        let mut namespace_sets = Vec::with_capacity(len as usize);
+        let mut namespace_sets = Vec::with_capacity(self.capacity_hint(len));
         for _ in 0..len {
             namespace_sets.push(self.read_namespace_set()?);
         }
 
         let len = self.read_u30()?.saturating_sub(1);
-        let mut multinames = Vec::with_capacity(len as usize);
+        let mut multinames = Vec::with_capacity(self.capacity_hint(len));
         for _ in 0..len {
             multinames.push(self.read_multiname()?);
         }
@@ -249,7 +249,7 @@ impl<'a> Reader<'a> {
     fn read_method(&mut self) -> Result<Method> {
         let num_params = self.read_u30()?;
         let return_type = self.read_index()?;
-        let mut params = Vec::with_capacity(num_params as usize);
+        let mut params = Vec::with_capacity(self.capacity_hint(num_params));
         for _ in 0..num_params {
             params.push(MethodParam {
                 kind: self.read_index()?,
@@ -336,7 +336,7 @@ impl<'a> Reader<'a> {
     fn read_metadata(&mut self) -> Result<Metadata> {
         let name = self.read_index()?;
         let num_items = self.read_u30()?;
-        let mut items = Vec::with_capacity(num_items as usize);
+        let mut items = Vec::with_capacity(self.capacity_hint(num_items));
         for _ in 0..num_items {
             items.push(MetadataItem {
                 key: self.read_index()?,
@@ -358,7 +358,7 @@ impl<'a> Reader<'a> {
         };
 
         let num_interfaces = self.read_u30()?;
-        let mut interfaces = Vec::with_capacity(num_interfaces as usize);
+        let mut interfaces = Vec::with_capacity(self.capacity_hint(num_interfaces));
         for _ in 0..num_interfaces {
             interfaces.push(self.read_index()?);
         }
@@ -366,7 +366,7 @@ impl<'a> Reader<'a> {
         let init_method = self.read_index()?;
 
         let num_traits = self.read_u30()?;
-        let mut traits = Vec::with_capacity(num_traits as usize);
+        let mut traits = Vec::with_capacity(self.capacity_hint(num_traits));
         for _ in 0..num_traits {
             traits.push(self.read_trait()?);
         }
@@ -387,7 +387,7 @@ impl<'a> Reader<'a> {
     fn read_class(&mut self) -> Result<Class> {
         let init_method = self.read_index()?;
         let num_traits = self.read_u30()?;
-        let mut traits = Vec::with_capacity(num_traits as usize);
+        let mut traits = Vec::with_capacity(self.capacity_hint(num_traits));
         for _ in 0..num_traits {
             traits.push(self.read_trait()?);
         }
@@ -400,7 +400,7 @@ impl<'a> Reader<'a> {
     fn read_script(&mut self) -> Result<Script> {
         let init_method = self.read_index()?;
         let num_traits = self.read_u30()?;
-        let mut traits = Vec::with_capacity(num_traits as usize);
+        let mut traits = Vec::with_capacity(self.capacity_hint(num_traits));
         for _ in 0..num_traits {
             traits.push(self.read_trait()?);
         }
@@ -478,13 +478,13 @@ impl<'a> Reader<'a> {
         let code = self.read_slice(code_len as usize)?.to_vec();
 
         let num_exceptions = self.read_u30()?;
-        let mut exceptions = Vec::with_capacity(num_exceptions as usize);
+        let mut exceptions = Vec::with_capacity(self.capacity_hint(num_exceptions));
         for _ in 0..num_exceptions {
             exceptions.push(self.read_exception()?);
         }
 
         let num_traits = self.read_u30()?;
-        let mut traits = Vec::with_capacity(num_traits as usize);
+        let mut traits = Vec::with_capacity(self.capacity_hint(num_traits));
         for _ in 0..num_traits {
             traits.push(self.read_trait()?);
         }
@@ -746,8 +746,8 @@ impl<'a> Reader<'a> {
             OpCode::LookupSwitch => Op::LookupSwitch {
                 default_offset: self.read_i24()?,
                 case_offsets: {
-                    let num_cases = self.read_u30()? + 1;
-                    let mut case_offsets = Vec::with_capacity(num_cases as usize);
+                    let num_cases = self.read_u30()?.saturating_add(1);
+                    let mut case_offsets = Vec::with_capacity(self.capacity_hint(num_cases));
                     for _ in 0..num_cases {
                         case_offsets.push(self.read_i24()?);
                     }
@@ -83,6 +83,15 @@ pub trait ReadSwfExt<'a> {
         Ok(val)
     }
 
+    /// Returns the capacity to reserve for a list of `len` items read from this reader.
+    ///
+    /// Every item takes at least one byte, so this is clamped to the remaining data
+    /// to avoid huge allocations caused by malformed lengths.
+    #[inline]
+    fn capacity_hint(&self, len: u32) -> usize {
+        (len as usize).min(self.as_slice().len())
+    }
+
     fn read_slice(&mut self, len: usize) -> Result<&'a [u8]> {
         let slice = self.as_mut_slice();
         if slice.len() >= len {
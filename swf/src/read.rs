@@ -10,6 +10,10 @@ use byteorder::{LittleEndian, ReadBytesExt};
 use simple_asn1::ASN1Block;
 use std::io::{self, Read};
 
+/// The maximum amount of memory preallocated for decompressing an SWF, based on the
+/// length in its header. Larger SWFs are still supported, but grow their buffer as needed.
+const MAX_PREALLOCATED_LEN: u32 = 64 * 1024 * 1024;
+
 /// Parse a decompressed SWF.
 ///
 /// # Example
@@ -94,12 +98,13 @@ pub fn decompress_swf<'a, R: Read + 'a>(mut input: R) -> Result<SwfBuf> {
             }
             // Uncompressed length includes the 4-byte header and 4-byte uncompressed length itself,
             // subtract it here.
-            make_lzma_reader(input, uncompressed_len - 8)?
+            make_lzma_reader(input, uncompressed_len.saturating_sub(8))?
         }
     };
 
     // Decompress the entire SWF.
-    let mut data = Vec::with_capacity(uncompressed_len as usize);
+    // Don't trust the header for huge preallocations.
+    let mut data = Vec::with_capacity(uncompressed_len.min(MAX_PREALLOCATED_LEN) as usize);
     if let Err(e) = decompress_stream.read_to_end(&mut data) {
         log::error!("Error decompressing SWF: {}", e);
     }
@@ -210,7 +215,7 @@ fn make_lzma_reader<'a, R: Read + 'a>(
     let _ = input.read_u32::<LittleEndian>()?;
 
     // TODO: Switch to lzma-rs streaming API when stable.
-    let mut output = Vec::with_capacity(uncompressed_length as usize);
+    let mut output = Vec::with_capacity(uncompressed_length.min(MAX_PREALLOCATED_LEN) as usize);
     lzma_decompress_with_options(
         &mut io::BufReader::new(input),
         &mut output,
@@ -1031,16 +1036,17 @@ impl<'a> Reader<'a> {
             // GlyphShapeTable
             for (i, glyph) in glyphs.iter_mut().enumerate() {
                 // The glyph shapes are assumed to be positioned per the offset table.
-                // Panic on debug builds if this assumption is wrong, maybe we need to
-                // seek into these offsets instead?
-                debug_assert_eq!(self.pos(offsets_ref), offsets[i] as usize);
+                // Maybe we need to seek into these offsets instead?
+                if self.pos(offsets_ref) != offsets[i] as usize {
+                    log::warn!("Glyph shape isn't positioned per the offset table");
+                }
 
                 // The glyph shapes must not overlap. Avoid exceeding to the next one.
                 // TODO: What happens on decreasing offsets?
                 let available_bytes = if i < num_glyphs - 1 {
-                    offsets[i + 1] - offsets[i]
+                    offsets[i + 1].saturating_sub(offsets[i])
                 } else {
-                    code_table_offset - offsets[i]
+                    code_table_offset.saturating_sub(offsets[i])
                 };
 
                 if available_bytes == 0 {
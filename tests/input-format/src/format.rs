@@ -8,7 +8,7 @@ use serde::{Deserialize, Serialize};
 /// pixels) or at 2x the size will see mouse clicks at its bottom right corner
 /// on (1280x960), relative to the window. That coordinate needs to be scaled
 /// down to match the desired stage.
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct MousePosition(pub f64, pub f64);
 
 /// Which mouse button is being pressed or released.
@@ -23,7 +23,7 @@ pub enum MouseButton {
 ///
 /// A FlashTAS input file consists of a string of `AutomatedEvent`s which are
 /// played back by FlashTAS.
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(tag = "type")]
 pub enum AutomatedEvent {
     /// End the current frame's input and wait for the next frame before
@@ -47,4 +47,7 @@ pub enum AutomatedEvent {
 
     /// Press a key
     KeyDown { key_code: u8 },
+
+    /// Release a key
+    KeyUp { key_code: u8 },
 }
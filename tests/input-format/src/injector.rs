@@ -69,6 +69,16 @@ impl InputInjector {
         }
     }
 
+    /// Create an input injector from a list of events, delimited by
+    /// `AutomatedEvent::Wait` for every frame.
+    pub fn from_events(items: Vec<AutomatedEvent>) -> Self {
+        Self {
+            items,
+            pos: 0,
+            buttons: MouseButtons::empty(),
+        }
+    }
+
     /// Run the next frame's worth of events.
     pub fn next<Sink>(&mut self, mut event_sink: Sink)
     where
@@ -81,7 +91,9 @@ impl InputInjector {
 
                 match event {
                     AutomatedEvent::Wait => break,
-                    AutomatedEvent::MouseMove { .. } | AutomatedEvent::KeyDown { .. } => {}
+                    AutomatedEvent::MouseMove { .. }
+                    | AutomatedEvent::KeyDown { .. }
+                    | AutomatedEvent::KeyUp { .. } => {}
                     AutomatedEvent::MouseDown { btn, .. } => {
                         self.buttons |= (*btn).into();
                     }
@@ -0,0 +1,40 @@
+use serde::{Deserialize, Serialize};
+use serde_json::from_reader;
+use std::fs::File;
+use std::io;
+use std::path::Path;
+
+/// A step of the script that a simulated socket server follows.
+///
+/// A socket script consists of a list of `SocketEvent`s, which the server
+/// goes through in order as the movie connects to it and talks to it.
+/// Data is given as strings; `XMLSocket` messages end with a `"\u0000"`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(tag = "type")]
+pub enum SocketEvent {
+    /// Wait for the movie to connect, and accept the connection.
+    WaitForConnection,
+
+    /// Wait for the movie to send data, which must be `expected`.
+    Receive { expected: String },
+
+    /// Send data to the movie.
+    Send { payload: String },
+
+    /// Wait for the movie to close the connection.
+    WaitForDisconnect,
+
+    /// Close the connection from the server.
+    Disconnect,
+}
+
+impl SocketEvent {
+    /// Reads a socket script from a JSON file.
+    pub fn from_file<P>(path: P) -> Result<Vec<Self>, io::Error>
+    where
+        P: AsRef<Path>,
+    {
+        let file = File::open(path)?;
+        Ok(from_reader(file)?)
+    }
+}
@@ -0,0 +1,25 @@
+package {
+	import flash.display.MovieClip;
+	import flash.system.ApplicationDomain;
+	import flash.utils.getDefinitionByName;
+
+	public class Test extends MovieClip {
+		public function Test() {
+			var names:Array = [
+				"flash.data.EncryptedLocalStore",
+				"flash.desktop.NativeApplication",
+				"flash.filesystem.File",
+				"flash.display.MovieClip"
+			];
+			for each (var name:String in names) {
+				trace(name + " hasDefinition: " + ApplicationDomain.currentDomain.hasDefinition(name));
+				try {
+					getDefinitionByName(name);
+					trace(name + " getDefinitionByName: found");
+				} catch (e:ReferenceError) {
+					trace(name + " getDefinitionByName: " + e.errorID);
+				}
+			}
+		}
+	}
+}
@@ -0,0 +1,89 @@
+package {
+	import flash.display.MovieClip;
+	import flash.errors.EOFError;
+	import flash.utils.ByteArray;
+
+	public class Test extends MovieClip {
+		public function Test() {
+			trace("default objectEncoding: " + new ByteArray().objectEncoding);
+
+			var amf0:ByteArray = new ByteArray();
+			amf0.objectEncoding = 0;
+			amf0.writeObject(1.5);
+			amf0.writeObject("hi");
+			amf0.writeObject(true);
+			amf0.writeObject(null);
+			amf0.writeObject(undefined);
+			trace("AMF0: " + hex(amf0));
+
+			amf0.position = 0;
+			trace("AMF0 read: " + amf0.readObject() + ", " + amf0.readObject() + ", " + amf0.readObject() + ", " + amf0.readObject() + ", " + amf0.readObject());
+
+			// AMF0 has no type for byte arrays, so they're written in AMF3.
+			var bytes:ByteArray = new ByteArray();
+			bytes.writeByte(7);
+			bytes.writeByte(8);
+			bytes.writeByte(9);
+			var nested:ByteArray = new ByteArray();
+			nested.objectEncoding = 0;
+			nested.writeObject(bytes);
+			trace("AMF0 ByteArray: " + hex(nested));
+			nested.position = 0;
+			var readBytes:ByteArray = nested.readObject();
+			trace("AMF0 ByteArray read: " + readBytes.length + " bytes, " + readBytes[0] + " " + readBytes[1] + " " + readBytes[2]);
+
+			amf0.clear();
+			amf0.writeObject({a: "x", b: [1, 2]});
+			amf0.position = 0;
+			var object:Object = amf0.readObject();
+			trace("AMF0 object read: " + object.a + ", " + object.b);
+
+			var amf3:ByteArray = new ByteArray();
+			amf3.writeObject(5);
+			amf3.writeObject("hi");
+			trace("AMF3: " + hex(amf3));
+
+			// Every call starts with fresh reference tables.
+			amf3.clear();
+			var array:Array = [1];
+			amf3.writeObject(array);
+			amf3.writeObject(array);
+			trace("AMF3 twice: " + hex(amf3));
+
+			// Switching the encoding applies to the next call.
+			amf3.clear();
+			amf3.writeObject("hi");
+			amf3.objectEncoding = 0;
+			amf3.writeObject("hi");
+			trace("AMF3 then AMF0: " + hex(amf3));
+			amf3.position = 0;
+			amf3.objectEncoding = 3;
+			trace("read AMF3: " + amf3.readObject());
+			amf3.objectEncoding = 0;
+			trace("read AMF0: " + amf3.readObject());
+
+			var truncated:ByteArray = new ByteArray();
+			truncated.objectEncoding = 0;
+			truncated.writeByte(2);
+			truncated.writeByte(0);
+			truncated.writeByte(5);
+			truncated.writeByte(0x61);
+			truncated.position = 0;
+			try {
+				truncated.readObject();
+				trace("truncated: no error");
+			} catch (e:EOFError) {
+				trace("truncated: EOFError " + e.errorID);
+			}
+		}
+
+		private function hex(bytes:ByteArray):String {
+			var result:Array = [];
+			for (var i:int = 0; i < bytes.length; i++) {
+				var byte:String = bytes[i].toString(16);
+				result.push(byte.length == 1 ? "0" + byte : byte);
+			}
+			return result.join(" ");
+		}
+	}
+}
@@ -0,0 +1,40 @@
+package {
+	import flash.display.MovieClip;
+
+	// Objects store their first dynamic properties differently from the rest.
+	// The order of enumeration isn't specified, so the keys are sorted.
+	public class Test extends MovieClip {
+		public function Test() {
+			var o:Object = {};
+			var names:Array = ["a", "b", "c", "d", "e", "f", "g"];
+			for (var i:int = 0; i < names.length; i++) {
+				o[names[i]] = i;
+			}
+			dump("filled", o);
+
+			delete o.b;
+			delete o.f;
+			dump("removed b and f", o);
+
+			o.f = "f again";
+			o.g = "g replaced";
+			o.b = "b again";
+			dump("reinserted", o);
+
+			trace("has b: " + o.hasOwnProperty("b"));
+			trace("has h: " + o.hasOwnProperty("h"));
+		}
+
+		private function dump(label:String, o:Object):void {
+			var keys:Array = [];
+			for (var key:String in o) {
+				keys.push(key);
+			}
+			keys.sort();
+			trace(label + ":");
+			for each (var sortedKey:String in keys) {
+				trace("  " + sortedKey + " = " + o[sortedKey]);
+			}
+		}
+	}
+}
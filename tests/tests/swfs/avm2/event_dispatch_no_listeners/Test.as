@@ -0,0 +1,40 @@
+package {
+	import flash.display.MovieClip;
+	import flash.display.Sprite;
+	import flash.events.Event;
+	import flash.events.EventDispatcher;
+
+	// Events of a type that nothing listens to must end up like fully dispatched ones.
+	public class Test extends MovieClip {
+		public function Test() {
+			var parent:Sprite = new Sprite();
+			var child:Sprite = new Sprite();
+			child.name = "child";
+			parent.addChild(child);
+			var other:EventDispatcher = new EventDispatcher();
+
+			dispatch("no listeners", child, new Event("custom", true, false));
+			dispatch("no listeners, cancelable", child, new Event("custom", false, true));
+
+			var listener:Function = function(e:Event):void {
+				trace("  listener called");
+			};
+			other.addEventListener("custom", listener);
+			dispatch("listener on another object", child, new Event("custom", true, false));
+			dispatch("listener on another object, cancelable", child, new Event("custom", false, true));
+
+			other.removeEventListener("custom", listener);
+			dispatch("listener removed", child, new Event("custom", true, false));
+
+			other.dispatchEvent(new Event("custom"));
+		}
+
+		private function dispatch(label:String, target:EventDispatcher, event:Event):void {
+			var result:Boolean = target.dispatchEvent(event);
+			trace(label + ":");
+			trace("  result: " + result);
+			trace("  eventPhase: " + event.eventPhase);
+			trace("  target: " + (event.target == target));
+		}
+	}
+}
@@ -0,0 +1,56 @@
+package {
+	import flash.display.MovieClip;
+	import flash.events.Event;
+
+	// The timeline of this movie is given a second, empty frame after
+	// compiling, so that the frame scripts run on alternating frames.
+	public class Test extends MovieClip {
+		private var frame:int = 0;
+
+		public function Test() {
+			trace("// constructor");
+			addEventListener(Event.ENTER_FRAME, onEnterFrame);
+			addEventListener(Event.FRAME_CONSTRUCTED, onFrameConstructed);
+			addEventListener(Event.EXIT_FRAME, onExitFrame);
+			addEventListener(Event.RENDER, onRender);
+			addFrameScript(0, frameScript1, 1, frameScript2);
+		}
+
+		private function onEnterFrame(event:Event):void {
+			trace("enterFrame");
+			if (frame == 1) {
+				trace("stage.invalidate() in enterFrame");
+				stage.invalidate();
+			}
+		}
+
+		private function onFrameConstructed(event:Event):void {
+			frame++;
+			trace("frameConstructed " + frame);
+		}
+
+		private function frameScript1():void {
+			trace("frame script 1 (frame " + frame + ")");
+			if (frame == 3) {
+				trace("stage.invalidate() in frame script");
+				stage.invalidate();
+			}
+		}
+
+		private function frameScript2():void {
+			trace("frame script 2 (frame " + frame + ")");
+		}
+
+		private function onExitFrame(event:Event):void {
+			trace("exitFrame " + frame);
+			if (frame == 4) {
+				trace("stage.invalidate() in exitFrame");
+				stage.invalidate();
+			}
+		}
+
+		private function onRender(event:Event):void {
+			trace("render " + frame);
+		}
+	}
+}
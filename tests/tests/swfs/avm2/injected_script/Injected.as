@@ -0,0 +1,13 @@
+package {
+	import flash.utils.getDefinitionByName;
+
+	public function injectedMain(root:Object):void {
+		trace("injected: root.secret = " + root.secret);
+		trace("injected: root is a Test: " + (root is (getDefinitionByName("Test") as Class)));
+		root.addFrameScript(1, function():void {
+			trace("injected frame script on frame 2");
+		});
+	}
+}
+
+trace("injected: script initializer");
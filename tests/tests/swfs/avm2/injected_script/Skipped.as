@@ -0,0 +1,5 @@
+package {
+}
+
+// This script is only injected into movies from example.com.
+trace("skipped: script initializer");
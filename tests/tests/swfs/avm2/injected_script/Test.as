@@ -0,0 +1,18 @@
+package {
+	import flash.display.MovieClip;
+
+	// The timeline of this movie is given a second, empty frame after
+	// compiling, for the injected script to add a frame script to.
+	public class Test extends MovieClip {
+		public var secret:String = "host value";
+
+		public function Test() {
+			trace("// Test constructor");
+			addFrameScript(0, hostFrameScript);
+		}
+
+		private function hostFrameScript():void {
+			trace("host frame script on frame 1");
+		}
+	}
+}
@@ -0,0 +1,59 @@
+package {
+	import flash.display.MovieClip;
+
+	// The same call sites see receivers of unrelated classes, whose traits
+	// are at different slots, and of a subclass overriding a method and a getter.
+	public class Test extends MovieClip {
+		public function Test() {
+			var receivers:Array = [new A(), new B(), new C(), new A(), new C(), new B()];
+			for (var i:int = 0; i < receivers.length; i++) {
+				var receiver:* = receivers[i];
+				trace(receiver.describe());
+				trace("value: " + receiver.value);
+				receiver.value = receiver.value + 10;
+				trace("value after set: " + receiver.value);
+				trace("label: " + receiver.label);
+			}
+		}
+	}
+}
+
+class A {
+	public var value:int = 1;
+
+	public function describe():String {
+		return "A.describe";
+	}
+
+	public function get label():String {
+		return "A.label";
+	}
+}
+
+class B {
+	public var padding1:String = "padding";
+	public var padding2:Number = 0.5;
+	public var value:int = 2;
+
+	public function get label():String {
+		return "B.label";
+	}
+
+	public function describe():String {
+		return "B.describe";
+	}
+}
+
+class C extends A {
+	public function C() {
+		value = 3;
+	}
+
+	override public function describe():String {
+		return "C.describe, overriding " + super.describe();
+	}
+
+	override public function get label():String {
+		return "C.label";
+	}
+}
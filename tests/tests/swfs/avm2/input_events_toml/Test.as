@@ -0,0 +1,36 @@
+package {
+	import flash.display.Sprite;
+	import flash.events.Event;
+	import flash.events.KeyboardEvent;
+	import flash.events.MouseEvent;
+
+	public class Test extends Sprite {
+		private var frame:int = 0;
+
+		public function Test() {
+			graphics.beginFill(0xFF0000);
+			graphics.drawRect(0, 0, 550, 400);
+			graphics.endFill();
+
+			addEventListener(Event.EXIT_FRAME, onExitFrame);
+			addEventListener(MouseEvent.MOUSE_DOWN, onMouseEvent);
+			addEventListener(MouseEvent.MOUSE_UP, onMouseEvent);
+			addEventListener(MouseEvent.CLICK, onMouseEvent);
+			stage.addEventListener(KeyboardEvent.KEY_DOWN, onKeyEvent);
+			stage.addEventListener(KeyboardEvent.KEY_UP, onKeyEvent);
+		}
+
+		private function onExitFrame(event:Event):void {
+			frame++;
+			trace("exitFrame " + frame);
+		}
+
+		private function onMouseEvent(event:MouseEvent):void {
+			trace(event.type + " at " + event.stageX + ", " + event.stageY);
+		}
+
+		private function onKeyEvent(event:KeyboardEvent):void {
+			trace(event.type + " " + event.keyCode);
+		}
+	}
+}
@@ -0,0 +1,9 @@
+package {
+	import flash.display.MovieClip;
+
+	public class Child extends MovieClip {
+		public function Child() {
+			trace("child constructed");
+		}
+	}
+}
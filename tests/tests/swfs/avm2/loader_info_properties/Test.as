@@ -0,0 +1,71 @@
+package {
+	import flash.display.Loader;
+	import flash.display.LoaderInfo;
+	import flash.display.MovieClip;
+	import flash.events.Event;
+	import flash.net.URLRequest;
+
+	public class Test extends MovieClip {
+		private var urls:Array = [
+			"http://example.com/child.swf?foo=bar&x=1",
+			"http://other.example.org/child.swf",
+			"http://example.com/image.png"
+		];
+
+		public function Test() {
+			trace("root sameDomain: " + loaderInfo.sameDomain);
+			trace("root contentType: " + loaderInfo.contentType);
+			loadNext();
+		}
+
+		private function loadNext():void {
+			if (urls.length == 0) {
+				return;
+			}
+			var url:String = urls.shift();
+			var loader:Loader = new Loader();
+			loader.contentLoaderInfo.addEventListener(Event.COMPLETE, function(e:Event):void {
+				describe(url, loader.contentLoaderInfo);
+				loadNext();
+			});
+			loader.load(new URLRequest(url));
+		}
+
+		private function describe(url:String, info:LoaderInfo):void {
+			trace("loaded " + url);
+			trace("  contentType: " + info.contentType);
+			trace("  loaderURL: " + info.loaderURL);
+			trace("  url: " + info.url);
+			trace("  sameDomain: " + info.sameDomain);
+			trace("  childAllowsParent: " + info.childAllowsParent);
+			trace("  parentAllowsChild: " + info.parentAllowsChild);
+			try {
+				trace("  swfVersion: " + info.swfVersion);
+				trace("  actionScriptVersion: " + info.actionScriptVersion);
+			} catch (error:Error) {
+				trace("  swfVersion: Error " + error.errorID);
+			}
+			try {
+				info.actionScriptVersion;
+			} catch (error:Error) {
+				trace("  actionScriptVersion: Error " + error.errorID);
+			}
+
+			var keys:Array = [];
+			for (var key:String in info.parameters) {
+				keys.push(key);
+			}
+			keys.sort();
+			for each (var sortedKey:String in keys) {
+				trace("  parameters." + sortedKey + ": " + info.parameters[sortedKey]);
+			}
+
+			// Flash denies access to the bytes of movies from other domains.
+			if (info.sameDomain && info.contentType == "application/x-shockwave-flash") {
+				trace("  bytes: " + info.bytes.length + " bytes, like bytesTotal: " + (info.bytes.length == info.bytesTotal));
+				info.bytes.position = 0;
+				trace("  signature: " + info.bytes.readUTFBytes(3));
+			}
+		}
+	}
+}
@@ -0,0 +1,14 @@
+package {
+	import flash.display.Sprite;
+	import flash.events.Event;
+
+	public class Child extends Sprite {
+		public function Child() {
+			addEventListener(Event.ADDED_TO_STAGE, onAddedToStage);
+		}
+
+		private function onAddedToStage(event:Event):void {
+			trace("Child: this.parent = " + this.parent);
+		}
+	}
+}
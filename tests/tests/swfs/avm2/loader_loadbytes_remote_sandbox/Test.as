@@ -0,0 +1,74 @@
+package {
+	import flash.display.Loader;
+	import flash.display.MovieClip;
+	import flash.events.Event;
+	import flash.utils.ByteArray;
+
+	// Loads Child.swf from bytes while playing in the remote sandbox, and checks that
+	// the loaded content and its loader can script each other.
+	public class Test extends MovieClip {
+		// Child.swf, compiled from Child.as
+		private static const CHILD:Array = [
+			0x46, 0x57, 0x53, 0x0a, 0xb8, 0x02, 0x00, 0x00, 0x78, 0x00, 0x00, 0xfa, 0x00, 0x00, 0x03, 0xe8,
+			0x00, 0x00, 0x18, 0x01, 0x00, 0x44, 0x11, 0x08, 0x00, 0x00, 0x00, 0x3f, 0x12, 0x89, 0x02, 0x00,
+			0x00, 0x10, 0x00, 0x2e, 0x00, 0x00, 0x00, 0x00, 0x19, 0x05, 0x43, 0x68, 0x69, 0x6c, 0x64, 0x00,
+			0x10, 0x61, 0x64, 0x64, 0x45, 0x76, 0x65, 0x6e, 0x74, 0x4c, 0x69, 0x73, 0x74, 0x65, 0x6e, 0x65,
+			0x72, 0x0a, 0x43, 0x68, 0x69, 0x6c, 0x64, 0x2e, 0x61, 0x73, 0x24, 0x32, 0x14, 0x66, 0x6c, 0x61,
+			0x73, 0x68, 0x2e, 0x64, 0x69, 0x73, 0x70, 0x6c, 0x61, 0x79, 0x3a, 0x53, 0x70, 0x72, 0x69, 0x74,
+			0x65, 0x24, 0x66, 0x6c, 0x61, 0x73, 0x68, 0x2e, 0x64, 0x69, 0x73, 0x70, 0x6c, 0x61, 0x79, 0x3a,
+			0x44, 0x69, 0x73, 0x70, 0x6c, 0x61, 0x79, 0x4f, 0x62, 0x6a, 0x65, 0x63, 0x74, 0x43, 0x6f, 0x6e,
+			0x74, 0x61, 0x69, 0x6e, 0x65, 0x72, 0x1f, 0x66, 0x6c, 0x61, 0x73, 0x68, 0x2e, 0x64, 0x69, 0x73,
+			0x70, 0x6c, 0x61, 0x79, 0x3a, 0x49, 0x6e, 0x74, 0x65, 0x72, 0x61, 0x63, 0x74, 0x69, 0x76, 0x65,
+			0x4f, 0x62, 0x6a, 0x65, 0x63, 0x74, 0x1b, 0x66, 0x6c, 0x61, 0x73, 0x68, 0x2e, 0x64, 0x69, 0x73,
+			0x70, 0x6c, 0x61, 0x79, 0x3a, 0x44, 0x69, 0x73, 0x70, 0x6c, 0x61, 0x79, 0x4f, 0x62, 0x6a, 0x65,
+			0x63, 0x74, 0x1c, 0x66, 0x6c, 0x61, 0x73, 0x68, 0x2e, 0x65, 0x76, 0x65, 0x6e, 0x74, 0x73, 0x3a,
+			0x45, 0x76, 0x65, 0x6e, 0x74, 0x44, 0x69, 0x73, 0x70, 0x61, 0x74, 0x63, 0x68, 0x65, 0x72, 0x05,
+			0x45, 0x76, 0x65, 0x6e, 0x74, 0x0c, 0x66, 0x6c, 0x61, 0x73, 0x68, 0x2e, 0x65, 0x76, 0x65, 0x6e,
+			0x74, 0x73, 0x0e, 0x41, 0x44, 0x44, 0x45, 0x44, 0x5f, 0x54, 0x4f, 0x5f, 0x53, 0x54, 0x41, 0x47,
+			0x45, 0x0e, 0x6f, 0x6e, 0x41, 0x64, 0x64, 0x65, 0x64, 0x54, 0x6f, 0x53, 0x74, 0x61, 0x67, 0x65,
+			0x05, 0x74, 0x72, 0x61, 0x63, 0x65, 0x15, 0x43, 0x68, 0x69, 0x6c, 0x64, 0x3a, 0x20, 0x74, 0x68,
+			0x69, 0x73, 0x2e, 0x70, 0x61, 0x72, 0x65, 0x6e, 0x74, 0x20, 0x3d, 0x20, 0x06, 0x70, 0x61, 0x72,
+			0x65, 0x6e, 0x74, 0x04, 0x76, 0x6f, 0x69, 0x64, 0x0d, 0x66, 0x6c, 0x61, 0x73, 0x68, 0x2e, 0x64,
+			0x69, 0x73, 0x70, 0x6c, 0x61, 0x79, 0x06, 0x53, 0x70, 0x72, 0x69, 0x74, 0x65, 0x06, 0x4f, 0x62,
+			0x6a, 0x65, 0x63, 0x74, 0x0f, 0x45, 0x76, 0x65, 0x6e, 0x74, 0x44, 0x69, 0x73, 0x70, 0x61, 0x74,
+			0x63, 0x68, 0x65, 0x72, 0x0d, 0x44, 0x69, 0x73, 0x70, 0x6c, 0x61, 0x79, 0x4f, 0x62, 0x6a, 0x65,
+			0x63, 0x74, 0x11, 0x49, 0x6e, 0x74, 0x65, 0x72, 0x61, 0x63, 0x74, 0x69, 0x76, 0x65, 0x4f, 0x62,
+			0x6a, 0x65, 0x63, 0x74, 0x16, 0x44, 0x69, 0x73, 0x70, 0x6c, 0x61, 0x79, 0x4f, 0x62, 0x6a, 0x65,
+			0x63, 0x74, 0x43, 0x6f, 0x6e, 0x74, 0x61, 0x69, 0x6e, 0x65, 0x72, 0x0e, 0x05, 0x01, 0x05, 0x04,
+			0x16, 0x02, 0x17, 0x02, 0x18, 0x01, 0x1a, 0x01, 0x1a, 0x05, 0x1a, 0x06, 0x1a, 0x07, 0x1a, 0x08,
+			0x1a, 0x09, 0x16, 0x0b, 0x16, 0x12, 0x04, 0x0b, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08,
+			0x09, 0x0a, 0x0b, 0x0c, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c,
+			0x04, 0x02, 0x03, 0x04, 0x0d, 0x12, 0x09, 0x03, 0x01, 0x09, 0x0a, 0x02, 0x09, 0x0c, 0x01, 0x09,
+			0x0d, 0x01, 0x09, 0x0e, 0x01, 0x09, 0x10, 0x01, 0x07, 0x03, 0x11, 0x07, 0x0c, 0x0a, 0x07, 0x01,
+			0x0d, 0x07, 0x03, 0x01, 0x07, 0x0d, 0x13, 0x07, 0x03, 0x14, 0x07, 0x0c, 0x15, 0x07, 0x0d, 0x16,
+			0x07, 0x0d, 0x17, 0x07, 0x0d, 0x18, 0x09, 0x13, 0x03, 0x04, 0x00, 0x00, 0x02, 0x00, 0x00, 0x00,
+			0x02, 0x00, 0x01, 0x07, 0x08, 0x02, 0x00, 0x00, 0x00, 0x02, 0x00, 0x00, 0x01, 0x0a, 0x0b, 0x09,
+			0x05, 0x00, 0x01, 0x01, 0x09, 0x01, 0x00, 0x02, 0x00, 0x00, 0x01, 0x03, 0x01, 0x0a, 0x04, 0x01,
+			0x00, 0x04, 0x00, 0x01, 0x01, 0x08, 0x09, 0x03, 0xd0, 0x30, 0x47, 0x00, 0x00, 0x01, 0x03, 0x01,
+			0x09, 0x0a, 0x15, 0xd0, 0x30, 0xd0, 0x49, 0x00, 0x5d, 0x01, 0x5d, 0x02, 0x66, 0x02, 0x66, 0x03,
+			0xd0, 0x66, 0x04, 0x46, 0x01, 0x02, 0x29, 0x47, 0x00, 0x00, 0x02, 0x03, 0x02, 0x09, 0x0a, 0x0f,
+			0xd0, 0x30, 0x5d, 0x05, 0x2c, 0x0f, 0xd0, 0x66, 0x06, 0xa0, 0x46, 0x05, 0x01, 0x29, 0x47, 0x00,
+			0x00, 0x03, 0x02, 0x01, 0x01, 0x08, 0x31, 0xd0, 0x30, 0x65, 0x00, 0x5d, 0x0c, 0x66, 0x0c, 0x30,
+			0x5d, 0x0d, 0x66, 0x0d, 0x30, 0x5d, 0x0e, 0x66, 0x0e, 0x30, 0x5d, 0x0f, 0x66, 0x0f, 0x30, 0x5d,
+			0x10, 0x66, 0x10, 0x30, 0x5d, 0x0b, 0x66, 0x0b, 0x30, 0x5d, 0x11, 0x66, 0x11, 0x58, 0x00, 0x1d,
+			0x1d, 0x1d, 0x1d, 0x1d, 0x1d, 0x68, 0x0a, 0x47, 0x00, 0x00, 0x0a, 0x13, 0x01, 0x00, 0x00, 0x00,
+			0x43, 0x68, 0x69, 0x6c, 0x64, 0x00, 0x40, 0x00
+		];
+
+		public function Test() {
+			var bytes:ByteArray = new ByteArray();
+			for each (var byte:int in CHILD) {
+				bytes.writeByte(byte);
+			}
+
+			var loader:Loader = new Loader();
+			addChild(loader);
+			loader.contentLoaderInfo.addEventListener(Event.COMPLETE, function(event:Event):void {
+				trace("loader.content = " + loader.content);
+				trace("loader.content.parent == loader: " + (loader.content.parent == loader));
+				trace("contentLoaderInfo.url == loaderInfo.url: " + (loader.contentLoaderInfo.url == loaderInfo.url));
+			});
+			loader.loadBytes(bytes);
+		}
+	}
+}
@@ -0,0 +1,52 @@
+package {
+	import flash.display.MovieClip;
+	import flash.display.Sprite;
+	import flash.events.MouseEvent;
+
+	public class Test extends MovieClip {
+		private var moves:int = 0;
+
+		public function Test() {
+			var a:Sprite = makeSprite("a");
+			a.graphics.beginFill(0xff0000);
+			a.graphics.drawRect(0, 0, 200, 200);
+			addChild(a);
+
+			// Drawn above `a`, within its bounds.
+			var b:Sprite = makeSprite("b");
+			b.graphics.beginFill(0x0000ff);
+			b.graphics.drawRect(100, 100, 50, 50);
+			addChild(b);
+
+			// Not a rectangle: the corners of its bounds are outside of it.
+			var c:Sprite = makeSprite("c");
+			c.graphics.beginFill(0x00ff00);
+			c.graphics.drawCircle(300, 100, 50);
+			addChild(c);
+
+			// Moving the mouse over `a` again adds another object above it.
+			a.addEventListener(MouseEvent.MOUSE_MOVE, function(e:MouseEvent):void {
+				moves++;
+				if (moves != 2) {
+					return;
+				}
+				var d:Sprite = makeSprite("d");
+				d.graphics.beginFill(0xffff00);
+				d.graphics.drawRect(10, 10, 30, 30);
+				addChild(d);
+			});
+		}
+
+		private function makeSprite(name:String):Sprite {
+			var sprite:Sprite = new Sprite();
+			sprite.name = name;
+			sprite.addEventListener(MouseEvent.ROLL_OVER, function(e:MouseEvent):void {
+				trace("rollOver " + name);
+			});
+			sprite.addEventListener(MouseEvent.ROLL_OUT, function(e:MouseEvent):void {
+				trace("rollOut " + name);
+			});
+			return sprite;
+		}
+	}
+}
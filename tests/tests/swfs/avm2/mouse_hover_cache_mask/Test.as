@@ -0,0 +1,29 @@
+package {
+	import flash.display.MovieClip;
+	import flash.display.Sprite;
+	import flash.events.MouseEvent;
+
+	public class Test extends MovieClip {
+		public function Test() {
+			// `inner` is larger than the mask of its parent.
+			var holder:Sprite = new Sprite();
+			var mask:Sprite = new Sprite();
+			mask.graphics.beginFill(0x000000);
+			mask.graphics.drawRect(0, 0, 100, 100);
+			addChild(mask);
+			holder.mask = mask;
+			addChild(holder);
+
+			var inner:Sprite = new Sprite();
+			inner.graphics.beginFill(0xff0000);
+			inner.graphics.drawRect(0, 0, 200, 200);
+			inner.addEventListener(MouseEvent.ROLL_OVER, function(e:MouseEvent):void {
+				trace("rollOver inner");
+			});
+			inner.addEventListener(MouseEvent.ROLL_OUT, function(e:MouseEvent):void {
+				trace("rollOut inner");
+			});
+			holder.addChild(inner);
+		}
+	}
+}
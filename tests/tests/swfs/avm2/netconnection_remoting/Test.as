@@ -0,0 +1,39 @@
+package {
+	import flash.display.MovieClip;
+	import flash.events.NetStatusEvent;
+	import flash.net.NetConnection;
+	import flash.net.Responder;
+
+	public class Test extends MovieClip {
+		public function Test() {
+			var gateway:NetConnection = new NetConnection();
+			gateway.addEventListener(NetStatusEvent.NET_STATUS, function(e:NetStatusEvent):void {
+				trace("gateway netStatus: " + e.info.code + " (" + e.info.level + ")");
+			});
+			trace("// gateway.connect(\"http://localhost/gateway\")");
+			gateway.connect("http://localhost/gateway");
+			trace("connected: " + gateway.connected);
+			trace("uri: " + gateway.uri);
+
+			trace("// gateway.call(\"Service.echo\", responder, \"hello\", 5)");
+			gateway.call("Service.echo", responder("Service.echo"), "hello", 5);
+			trace("// gateway.call(\"Service.fail\", responder)");
+			gateway.call("Service.fail", responder("Service.fail"));
+
+			var rtmp:NetConnection = new NetConnection();
+			rtmp.addEventListener(NetStatusEvent.NET_STATUS, function(e:NetStatusEvent):void {
+				trace("rtmp netStatus: " + e.info.code + " (" + e.info.level + ")");
+			});
+			trace("// rtmp.connect(\"rtmp://localhost/app\")");
+			rtmp.connect("rtmp://localhost/app");
+		}
+
+		private function responder(name:String):Responder {
+			return new Responder(function(result:Object):void {
+				trace(name + " result: " + result);
+			}, function(status:Object):void {
+				trace(name + " status: " + status.code);
+			});
+		}
+	}
+}
@@ -0,0 +1,37 @@
+package {
+	import flash.display.MovieClip;
+
+	public class Test extends MovieClip {
+		public function Test() {
+			var obj:Shape = new Shape();
+			var names:Array = ["first", "second", "first"];
+
+			// Each of these instructions runs with different names,
+			// which must not reuse the trait resolved the first time.
+			for each (var name:String in names) {
+				obj[name]();
+			}
+			for each (name in names) {
+				trace(name + ": " + obj[name + "Value"]);
+			}
+			for each (name in names) {
+				obj[name + "Value"] = name.toUpperCase();
+			}
+			trace("firstValue: " + obj.firstValue);
+			trace("secondValue: " + obj.secondValue);
+		}
+	}
+}
+
+class Shape {
+	public var firstValue:String = "one";
+	public var secondValue:String = "two";
+
+	public function first():void {
+		trace("first() called");
+	}
+
+	public function second():void {
+		trace("second() called");
+	}
+}
@@ -0,0 +1,41 @@
+package {
+	import flash.display.MovieClip;
+	import flash.events.Event;
+	import flash.events.IOErrorEvent;
+	import flash.events.SecurityErrorEvent;
+	import flash.net.URLLoader;
+	import flash.net.URLRequest;
+	import flash.system.Security;
+
+	// Plays in the local-with-networking sandbox, which may only load network URLs.
+	public class Test extends MovieClip {
+		private var urls:Array = ["data.txt", "http://example.com/data.txt"];
+
+		public function Test() {
+			trace("Security.sandboxType: " + Security.sandboxType);
+			loadNext();
+		}
+
+		// The URLs are loaded one after the other, so that their events are traced in order.
+		private function loadNext():void {
+			if (urls.length == 0) {
+				return;
+			}
+			var url:String = urls.shift();
+			var loader:URLLoader = new URLLoader();
+			loader.addEventListener(Event.COMPLETE, function(event:Event):void {
+				trace(url + ": complete, data = " + loader.data);
+				loadNext();
+			});
+			loader.addEventListener(IOErrorEvent.IO_ERROR, function(event:IOErrorEvent):void {
+				trace(url + ": ioError");
+				loadNext();
+			});
+			loader.addEventListener(SecurityErrorEvent.SECURITY_ERROR, function(event:SecurityErrorEvent):void {
+				trace(url + ": securityError, errorID = " + event.errorID);
+				loadNext();
+			});
+			loader.load(new URLRequest(url));
+		}
+	}
+}
@@ -0,0 +1,52 @@
+package {
+	import flash.display.MovieClip;
+	import flash.display.Shape;
+	import flash.display.SimpleButton;
+	import flash.display.Sprite;
+	import flash.events.Event;
+	import flash.events.KeyboardEvent;
+	import flash.events.MouseEvent;
+
+	public class Test extends MovieClip {
+		public function Test() {
+			var button:SimpleButton = new SimpleButton(shape(), shape(), shape(), shape());
+			button.name = "button";
+			watch(button);
+			addChild(button);
+
+			var sprite:Sprite = new Sprite();
+			sprite.name = "sprite";
+			sprite.buttonMode = true;
+			watch(sprite);
+			addChild(sprite);
+
+			trace("// stage.focus = button");
+			stage.focus = button;
+
+			button.addEventListener(MouseEvent.CLICK, function(e:MouseEvent):void {
+				trace("// stage.focus = sprite");
+				stage.focus = sprite;
+			});
+		}
+
+		private function shape():Shape {
+			var shape:Shape = new Shape();
+			shape.graphics.beginFill(0xFF0000);
+			shape.graphics.drawRect(0, 0, 50, 50);
+			shape.graphics.endFill();
+			return shape;
+		}
+
+		private function watch(target:Object):void {
+			var handler:Function = function(e:Event):void {
+				var key:String = e is KeyboardEvent ? " " + KeyboardEvent(e).keyCode : "";
+				trace(e.currentTarget.name + ": " + e.type + key);
+			};
+			target.addEventListener(KeyboardEvent.KEY_DOWN, handler);
+			target.addEventListener(KeyboardEvent.KEY_UP, handler);
+			target.addEventListener(MouseEvent.MOUSE_DOWN, handler);
+			target.addEventListener(MouseEvent.MOUSE_UP, handler);
+			target.addEventListener(MouseEvent.CLICK, handler);
+		}
+	}
+}
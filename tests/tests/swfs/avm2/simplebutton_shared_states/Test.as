@@ -0,0 +1,63 @@
+package {
+	import flash.display.MovieClip;
+	import flash.display.SimpleButton;
+	import flash.display.Sprite;
+	import flash.events.Event;
+	import flash.events.MouseEvent;
+
+	public class Test extends MovieClip {
+		private var button:SimpleButton;
+		private var shared:Sprite;
+		private var down:Sprite;
+		private var lastDisplayed:String;
+
+		public function Test() {
+			// The same object is used for the up, over and hit test states.
+			shared = box("shared", 0xff0000);
+			down = box("down", 0x0000ff);
+			button = new SimpleButton(shared, shared, down, shared);
+			addChild(button);
+			traceDisplayed();
+
+			trace("// button.overState = other");
+			button.overState = box("other", 0x00ff00);
+			trace("shared.parent == button: " + (shared.parent == button));
+
+			trace("// button.overState = shared");
+			button.overState = shared;
+			trace("shared.parent == button: " + (shared.parent == button));
+
+			for each (var type:String in [MouseEvent.MOUSE_OVER, MouseEvent.MOUSE_DOWN, MouseEvent.MOUSE_UP, MouseEvent.CLICK, MouseEvent.MOUSE_OUT]) {
+				button.addEventListener(type, function(e:MouseEvent):void {
+					trace(e.type);
+				});
+			}
+			addEventListener(Event.ENTER_FRAME, function(e:Event):void {
+				traceDisplayed();
+			});
+		}
+
+		private function box(name:String, color:uint):Sprite {
+			var sprite:Sprite = new Sprite();
+			sprite.name = name;
+			sprite.graphics.beginFill(color);
+			sprite.graphics.drawRect(0, 0, 100, 100);
+			sprite.addEventListener(Event.ADDED, function(e:Event):void {
+				trace("added " + e.target.name);
+			});
+			sprite.addEventListener(Event.REMOVED, function(e:Event):void {
+				trace("removed " + e.target.name);
+			});
+			return sprite;
+		}
+
+		// Traces the displayed state whenever it changes.
+		private function traceDisplayed():void {
+			var displayed:String = shared.parent == button ? "shared" : down.parent == button ? "down" : "none";
+			if (displayed != lastDisplayed) {
+				trace("displayed: " + displayed);
+				lastDisplayed = displayed;
+			}
+		}
+	}
+}
@@ -0,0 +1,18 @@
+package {
+	import flash.display.MovieClip;
+	import flash.display.Shape;
+
+	public class Test extends MovieClip {
+		namespace ruffle = "__ruffle__";
+
+		public function Test() {
+			var shape:Shape = new Shape();
+			trace("default: " + shape.ruffle::smoothPlayback);
+			shape.ruffle::smoothPlayback = false;
+			trace("after opting out: " + shape.ruffle::smoothPlayback);
+			shape.ruffle::smoothPlayback = true;
+			trace("after opting in: " + shape.ruffle::smoothPlayback);
+			trace("parent: " + this.ruffle::smoothPlayback);
+		}
+	}
+}
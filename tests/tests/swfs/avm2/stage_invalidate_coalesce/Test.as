@@ -0,0 +1,41 @@
+package {
+	import flash.display.Sprite;
+	import flash.events.Event;
+	import flash.events.MouseEvent;
+
+	public class Test extends Sprite {
+		private var frame:int = 0;
+
+		public function Test() {
+			graphics.beginFill(0xFF0000);
+			graphics.drawRect(0, 0, 550, 400);
+			graphics.endFill();
+
+			addEventListener(Event.EXIT_FRAME, onExitFrame);
+			addEventListener(Event.RENDER, onRender);
+			addEventListener(MouseEvent.MOUSE_DOWN, onMouseDown);
+		}
+
+		private function onExitFrame(event:Event):void {
+			frame++;
+			trace("exitFrame " + frame);
+			if (frame == 3) {
+				trace("stage.invalidate() twice in exitFrame");
+				stage.invalidate();
+				stage.invalidate();
+			}
+		}
+
+		private function onMouseDown(event:MouseEvent):void {
+			trace("mouseDown");
+			trace("stage.invalidate() twice in mouseDown");
+			stage.invalidate();
+			stage.invalidate();
+			event.updateAfterEvent();
+		}
+
+		private function onRender(event:Event):void {
+			trace("render");
+		}
+	}
+}
@@ -0,0 +1,35 @@
+package {
+	import flash.display.Sprite;
+	import flash.events.Event;
+
+	public class Test extends Sprite {
+		private var frame:int = 0;
+		private var renders:int = 0;
+
+		public function Test() {
+			addEventListener(Event.EXIT_FRAME, onExitFrame);
+			addEventListener(Event.RENDER, onRender);
+		}
+
+		private function onExitFrame(event:Event):void {
+			frame++;
+			trace("exitFrame " + frame);
+			if (frame == 1) {
+				trace("stage.invalidate() in exitFrame");
+				stage.invalidate();
+			}
+		}
+
+		// Invalidating the stage again from a 'render' listener
+		// dispatches another 'render' before the next frame is drawn.
+		private function onRender(event:Event):void {
+			renders++;
+			trace("render " + renders);
+			if (renders < 3) {
+				trace("stage.invalidate() twice in render");
+				stage.invalidate();
+				stage.invalidate();
+			}
+		}
+	}
+}
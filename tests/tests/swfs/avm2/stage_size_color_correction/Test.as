@@ -0,0 +1,41 @@
+package {
+	import flash.display.MovieClip;
+	import flash.display.StageScaleMode;
+	import flash.events.Event;
+
+	public class Test extends MovieClip {
+		public function Test() {
+			trace("colorCorrection: " + stage.colorCorrection);
+			trace("colorCorrectionSupport: " + stage.colorCorrectionSupport);
+			stage.colorCorrection = "off";
+			trace("// stage.colorCorrection = \"off\"");
+			trace("colorCorrection: " + stage.colorCorrection);
+			try {
+				stage.colorCorrection = "bogus";
+			} catch (e:ArgumentError) {
+				trace(e);
+			}
+			trace("colorCorrection: " + stage.colorCorrection);
+			trace("wmodeGPU: " + stage.wmodeGPU);
+
+			stage.addEventListener(Event.RESIZE, function(e:Event):void {
+				trace("resize: " + stage.stageWidth + "x" + stage.stageHeight);
+			});
+
+			// The stage size can only be set in the noScale scale mode.
+			stage.stageWidth = 100;
+			trace("// stage.stageWidth = 100");
+			trace("stage size: " + stage.stageWidth + "x" + stage.stageHeight);
+			stage.scaleMode = StageScaleMode.NO_SCALE;
+			trace("// stage.scaleMode = StageScaleMode.NO_SCALE");
+			trace("stage size: " + stage.stageWidth + "x" + stage.stageHeight);
+			trace("// stage.stageWidth = 300");
+			stage.stageWidth = 300;
+			trace("// stage.stageHeight = -5");
+			stage.stageHeight = -5;
+			trace("// stage.stageWidth = 300");
+			stage.stageWidth = 300;
+			trace("stage size: " + stage.stageWidth + "x" + stage.stageHeight);
+		}
+	}
+}
@@ -0,0 +1,40 @@
+package {
+	import flash.display.Sprite;
+	import flash.events.Event;
+	import flash.events.IOErrorEvent;
+	import flash.net.URLLoader;
+	import flash.net.URLRequest;
+
+	public class Test extends Sprite {
+		// Each request is made once the previous one finished, to keep the output in order.
+		private var urls:Array = ["inline.txt", "fixture.txt", "disk.txt", "missing.txt", "delayed.txt"];
+		private var frame:int = 0;
+
+		public function Test() {
+			addEventListener(Event.EXIT_FRAME, onExitFrame);
+			loadNext();
+		}
+
+		private function onExitFrame(event:Event):void {
+			frame++;
+			trace("exitFrame " + frame);
+		}
+
+		private function loadNext():void {
+			if (urls.length == 0) {
+				return;
+			}
+			var url:String = urls.shift();
+			var loader:URLLoader = new URLLoader();
+			loader.addEventListener(Event.COMPLETE, function(event:Event):void {
+				trace(url + ": complete, data: " + loader.data);
+				loadNext();
+			});
+			loader.addEventListener(IOErrorEvent.IO_ERROR, function(event:IOErrorEvent):void {
+				trace(url + ": ioError");
+				loadNext();
+			});
+			loader.load(new URLRequest(url));
+		}
+	}
+}
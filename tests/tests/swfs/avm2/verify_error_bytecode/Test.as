@@ -0,0 +1,37 @@
+package {
+	import flash.display.MovieClip;
+
+	// The bodies of the `bad` methods are replaced after compiling:
+	//  * badMultiname: `getlocal0; pushscope; getlex 1000; returnvalue`
+	//  * truncatedBody: `getlocal0; pushscope; pushbyte`, missing its operand
+	//  * jumpOutOfRange: `getlocal0; pushscope; jump 4096; returnvoid`
+	public class Test extends MovieClip {
+		public function Test() {
+			run("badMultiname", badMultiname);
+			run("truncatedBody", truncatedBody);
+			run("jumpOutOfRange", jumpOutOfRange);
+			run("badMultiname again", badMultiname);
+		}
+
+		private function run(name:String, f:Function):void {
+			try {
+				f();
+				trace(name + ": no error");
+			} catch (e:VerifyError) {
+				trace(name + ": VerifyError " + e.errorID);
+			}
+		}
+
+		public function badMultiname():* {
+			return 1;
+		}
+
+		public function truncatedBody():* {
+			return 2;
+		}
+
+		public function jumpOutOfRange():* {
+			return 3;
+		}
+	}
+}
@@ -0,0 +1,27 @@
+package {
+	import flash.display.MovieClip;
+	import flash.events.Event;
+
+	// Fills the stage with a different color on every frame,
+	// so that only the second frame matches `frame2.png`.
+	public class Test extends MovieClip {
+		private static const COLORS:Array = [0x0000ff, 0xff0000, 0x00ff00];
+
+		private var frame:int = 1;
+
+		public function Test() {
+			fill();
+			addEventListener(Event.ENTER_FRAME, function(e:Event):void {
+				frame++;
+				fill();
+			});
+		}
+
+		private function fill():void {
+			trace("frame " + frame);
+			graphics.clear();
+			graphics.beginFill(COLORS[frame - 1]);
+			graphics.drawRect(-10, -10, 570, 420);
+		}
+	}
+}
@@ -5,6 +5,7 @@ pub mod environment;
 pub mod navigator;
 pub mod options;
 pub mod runner;
+pub mod socket;
 pub mod test;
 
 /// Wrapper around string slice that makes debug output `{:?}` to print string same way as `{}`.
@@ -1,3 +1,4 @@
+use crate::util::options::ResponseFixture;
 use crate::util::runner::TestLogBackend;
 use ruffle_core::backend::log::LogBackend;
 use ruffle_core::backend::navigator::{
@@ -5,16 +6,87 @@ use ruffle_core::backend::navigator::{
 };
 use ruffle_core::indexmap::IndexMap;
 use ruffle_core::loader::Error;
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::future::Future;
 use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::task::{Poll, Waker};
 use url::Url;
 
+/// Counts the frames run by a test, so that responses can be delayed deterministically.
+#[derive(Clone, Default)]
+pub struct FrameClock(Rc<RefCell<FrameClockState>>);
+
+#[derive(Default)]
+struct FrameClockState {
+    frame: u32,
+    wakers: Vec<Waker>,
+}
+
+impl FrameClock {
+    /// Advances the clock by one frame, waking up everything waiting on it.
+    pub fn advance(&self) {
+        let mut state = self.0.borrow_mut();
+        state.frame += 1;
+        for waker in state.wakers.drain(..) {
+            waker.wake();
+        }
+    }
+
+    /// Whether anything is still waiting for frames to pass.
+    pub fn has_waiters(&self) -> bool {
+        !self.0.borrow().wakers.is_empty()
+    }
+
+    /// Waits until the given amount of frames have passed.
+    fn wait(&self, frames: u32) -> impl Future<Output = ()> {
+        let clock = self.clone();
+        let target_frame = self.0.borrow().frame + frames;
+        futures::future::poll_fn(move |context| {
+            let mut state = clock.0.borrow_mut();
+            if state.frame >= target_frame {
+                Poll::Ready(())
+            } else {
+                state.wakers.push(context.waker().clone());
+                Poll::Pending
+            }
+        })
+    }
+}
+
+/// Counts the futures spawned by a test that haven't finished yet.
+#[derive(Clone, Default)]
+pub struct PendingFutures(Rc<Cell<usize>>);
+
+impl PendingFutures {
+    pub fn count(&self) -> usize {
+        self.0.get()
+    }
+
+    fn track(&self, future: OwnedFuture<(), Error>) -> OwnedFuture<(), Error> {
+        let pending = self.clone();
+        pending.0.set(pending.0.get() + 1);
+        Box::pin(async move {
+            let result = future.await;
+            pending.0.set(pending.0.get() - 1);
+            result
+        })
+    }
+}
+
 /// A `NavigatorBackend` used by tests that supports logging fetch requests.
 ///
 /// This can be used by tests that fetch data to verify that the request is correct.
+/// Requests are served from the test directory, unless the test declares a canned
+/// response for their URL.
 pub struct TestNavigatorBackend {
     spawner: NullSpawner,
     relative_base_path: PathBuf,
     log: Option<TestLogBackend>,
+    responses: HashMap<String, ResponseFixture>,
+    clock: FrameClock,
+    pending: PendingFutures,
 }
 
 impl TestNavigatorBackend {
@@ -22,11 +94,44 @@ impl TestNavigatorBackend {
         path: &Path,
         executor: &NullExecutor,
         log: Option<TestLogBackend>,
+        responses: HashMap<String, ResponseFixture>,
+        clock: FrameClock,
+        pending: PendingFutures,
     ) -> Result<Self, std::io::Error> {
         Ok(Self {
             spawner: executor.spawner(),
             relative_base_path: path.canonicalize()?,
             log,
+            responses,
+            clock,
+            pending,
+        })
+    }
+
+    fn fetch_fixture(&self, url: &str, fixture: ResponseFixture) -> OwnedFuture<Response, Error> {
+        let url = fixture.redirect.clone().unwrap_or_else(|| url.to_string());
+        let file = fixture
+            .file
+            .as_ref()
+            .map(|file| self.relative_base_path.join(file));
+        let delay = self.clock.wait(fixture.delay_frames);
+
+        Box::pin(async move {
+            delay.await;
+
+            if !(200..300).contains(&fixture.status) {
+                return Err(Error::FetchError(format!(
+                    "HTTP status is not ok, got {}",
+                    fixture.status
+                )));
+            }
+
+            let body = match file {
+                Some(file) => std::fs::read(file).map_err(|e| Error::FetchError(e.to_string()))?,
+                None => fixture.body.into_bytes(),
+            };
+
+            Ok(Response { url, body })
         })
     }
 
@@ -72,6 +177,10 @@ impl NavigatorBackend for TestNavigatorBackend {
             }
         }
 
+        if let Some(fixture) = self.responses.get(request.url()) {
+            return self.fetch_fixture(request.url(), fixture.clone());
+        }
+
         let mut path = self.relative_base_path.clone();
         path.push(request.url());
 
@@ -87,7 +196,7 @@ impl NavigatorBackend for TestNavigatorBackend {
     }
 
     fn spawn_future(&mut self, future: OwnedFuture<(), Error>) {
-        self.spawner.spawn_local(future);
+        self.spawner.spawn_local(self.pending.track(future));
     }
 
     fn pre_process_url(&self, url: Url) -> Url {
@@ -3,11 +3,14 @@ use crate::util::runner::TestAudioBackend;
 use anyhow::{anyhow, Result};
 use approx::assert_relative_eq;
 use regex::Regex;
+use ruffle_core::injected_script::InjectedScript;
 use ruffle_core::tag_utils::SwfMovie;
 use ruffle_core::{PlayerBuilder, ViewportDimensions};
+use ruffle_input_format::{AutomatedEvent, InputInjector};
 use ruffle_render::quality::StageQuality;
 use ruffle_render_wgpu::wgpu;
 use serde::Deserialize;
+use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::time::Duration;
@@ -19,10 +22,17 @@ pub struct TestOptions {
     pub output_path: PathBuf,
     pub sleep_to_meet_frame_rate: bool,
     pub image_comparison: Option<ImageComparison>,
+    pub input: Option<InputOptions>,
     pub ignore: bool,
     pub approximations: Option<Approximations>,
     pub player_options: PlayerOptions,
     pub log_fetch: bool,
+    pub responses: HashMap<String, ResponseFixture>,
+    pub url: Option<String>,
+    pub socket: Option<SocketOptions>,
+    pub save_state: Option<SaveStateOptions>,
+    pub reset_after_frame: Option<u32>,
+    pub injected_scripts: Vec<InjectedScriptOptions>,
 }
 
 impl Default for TestOptions {
@@ -32,17 +42,28 @@ impl Default for TestOptions {
             output_path: PathBuf::from("output.txt"),
             sleep_to_meet_frame_rate: false,
             image_comparison: None,
+            input: None,
             ignore: false,
             approximations: None,
             player_options: PlayerOptions::default(),
             log_fetch: false,
+            responses: HashMap::new(),
+            url: None,
+            socket: None,
+            save_state: None,
+            reset_after_frame: None,
+            injected_scripts: Vec::new(),
         }
     }
 }
 
 impl TestOptions {
     pub fn read<P: AsRef<Path>>(path: P) -> Result<Self> {
-        Ok(toml::from_str(&fs::read_to_string(path)?)?)
+        let options: Self = toml::from_str(&fs::read_to_string(path)?)?;
+        if let Some(image_comparison) = &options.image_comparison {
+            image_comparison.validate(options.num_frames)?;
+        }
+        Ok(options)
     }
 
     pub fn output_path(&self, test_directory: &Path) -> PathBuf {
@@ -50,6 +71,134 @@ impl TestOptions {
     }
 }
 
+/// A canned response served by the test navigator for a given URL.
+#[derive(Deserialize, Clone)]
+#[serde(default, deny_unknown_fields)]
+pub struct ResponseFixture {
+    /// The HTTP status code. Requests fail unless this is a 2xx status.
+    pub status: u16,
+
+    /// The response body.
+    pub body: String,
+
+    /// A file (relative to the test directory) used as the response body, instead of `body`.
+    pub file: Option<PathBuf>,
+
+    /// The final URL of the response, as if the request was redirected.
+    pub redirect: Option<String>,
+
+    /// The number of frames to wait before responding.
+    pub delay_frames: u32,
+}
+
+impl Default for ResponseFixture {
+    fn default() -> Self {
+        Self {
+            status: 200,
+            body: String::new(),
+            file: None,
+            redirect: None,
+            delay_frames: 0,
+        }
+    }
+}
+
+/// A simulated socket server, which the movie may connect to.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SocketOptions {
+    /// The host that the movie must connect to.
+    pub host: String,
+
+    /// The port that the movie must connect to.
+    pub port: u16,
+
+    /// The file (relative to the test directory) of the script that the server follows.
+    #[serde(default = "SocketOptions::default_script")]
+    pub script: PathBuf,
+}
+
+impl SocketOptions {
+    fn default_script() -> PathBuf {
+        PathBuf::from("socket.json")
+    }
+
+    pub fn script_path(&self, test_directory: &Path) -> PathBuf {
+        test_directory.join(&self.script)
+    }
+}
+
+/// An ActionScript 3.0 script injected into the movie by the embedder.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct InjectedScriptOptions {
+    /// The ABC file of the script, relative to the test directory.
+    pub file: PathBuf,
+
+    /// Only inject the script into movies loaded from this host.
+    pub domain: Option<String>,
+
+    /// The function called with the root display object once the script is loaded.
+    pub entry_point: Option<String>,
+}
+
+impl InjectedScriptOptions {
+    pub fn script(&self, test_directory: &Path) -> Result<InjectedScript> {
+        let mut script = InjectedScript::new(fs::read(test_directory.join(&self.file))?);
+        if let Some(domain) = &self.domain {
+            script = script.with_domain(domain);
+        }
+        if let Some(entry_point) = &self.entry_point {
+            script = script.with_entry_point(entry_point);
+        }
+        Ok(script)
+    }
+}
+
+/// Saves the state of the player after a frame, and restores it after a later frame.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SaveStateOptions {
+    /// The frame after which the state is saved.
+    pub save_after_frame: u32,
+
+    /// The frame after which the saved state is restored.
+    pub load_after_frame: u32,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct InputOptions {
+    events: Vec<TimedEvent>,
+}
+
+#[derive(Deserialize)]
+struct TimedEvent {
+    frame: u32,
+    #[serde(flatten)]
+    event: AutomatedEvent,
+}
+
+impl InputOptions {
+    /// Creates an injector which feeds every event after the frame it's scheduled for has run.
+    pub fn injector(&self) -> InputInjector {
+        let mut timed_events: Vec<_> = self.events.iter().collect();
+        timed_events.sort_by_key(|timed_event| timed_event.frame);
+
+        let mut events = Vec::with_capacity(timed_events.len());
+        let mut frame = 1;
+        for timed_event in timed_events {
+            while frame < timed_event.frame {
+                events.push(AutomatedEvent::Wait);
+                frame += 1;
+            }
+            events.push(timed_event.event.clone());
+        }
+
+        InputInjector::from_events(events)
+    }
+}
+
 #[derive(Deserialize, Default)]
 #[serde(default, deny_unknown_fields)]
 pub struct Approximations {
@@ -91,6 +240,7 @@ pub struct PlayerOptions {
     with_renderer: Option<RenderOptions>,
     with_audio: bool,
     with_video: bool,
+    enforce_sandbox: bool,
 }
 
 impl PlayerOptions {
@@ -119,12 +269,14 @@ impl PlayerOptions {
 
         if let Some(render_options) = &self.with_renderer {
             use ruffle_render_wgpu::backend::WgpuRenderBackend;
-            use ruffle_render_wgpu::target::TextureTarget;
 
             if let Some(descriptors) = WGPU.clone() {
                 if render_options.is_supported(&descriptors.adapter) {
-                    let target = TextureTarget::new(&descriptors.device, (width, height))
-                        .map_err(|e| anyhow!(e.to_string()))?;
+                    let renderer = WgpuRenderBackend::for_offscreen_with_descriptors(
+                        (width, height),
+                        descriptors,
+                    )
+                    .map_err(|e| anyhow!(e.to_string()))?;
 
                     player_builder = player_builder
                         .with_quality(match render_options.sample_count {
@@ -134,10 +286,7 @@ impl PlayerOptions {
                             2 => StageQuality::Medium,
                             _ => StageQuality::Low,
                         })
-                        .with_renderer(
-                            WgpuRenderBackend::new(descriptors, target)
-                                .map_err(|e| anyhow!(e.to_string()))?,
-                        );
+                        .with_renderer(renderer);
                 }
             }
         }
@@ -146,6 +295,10 @@ impl PlayerOptions {
             player_builder = player_builder.with_audio(TestAudioBackend::new());
         }
 
+        if self.enforce_sandbox {
+            player_builder = player_builder.with_sandbox_enforcement(true);
+        }
+
         #[cfg(feature = "imgtests")]
         if self.with_video {
             use ruffle_video_software::backend::SoftwareVideoBackend;
@@ -173,11 +326,24 @@ impl PlayerOptions {
     }
 }
 
-#[derive(Deserialize, Default)]
+#[derive(Deserialize)]
 #[serde(default, deny_unknown_fields)]
 pub struct ImageComparison {
     tolerance: u8,
     max_outliers: usize,
+    frame: Option<u32>,
+    reference: PathBuf,
+}
+
+impl Default for ImageComparison {
+    fn default() -> Self {
+        Self {
+            tolerance: 0,
+            max_outliers: 0,
+            frame: None,
+            reference: PathBuf::from("expected.png"),
+        }
+    }
 }
 
 #[cfg(feature = "imgtests")]
@@ -186,6 +352,28 @@ fn calc_difference(lhs: u8, rhs: u8) -> u8 {
 }
 
 impl ImageComparison {
+    /// Whether the image should be captured after running the given frame (starting at 1).
+    pub fn is_captured_at(&self, frame: u32, num_frames: u32) -> bool {
+        self.frame.unwrap_or(num_frames) == frame
+    }
+
+    /// Checks that the image is captured after a frame that is run.
+    fn validate(&self, num_frames: u32) -> Result<()> {
+        match self.frame {
+            Some(frame) if frame == 0 || frame > num_frames => Err(anyhow!(
+                "Image comparison frame {} is not between 1 and num_frames ({})",
+                frame,
+                num_frames
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// The path of the reference image to compare against.
+    pub fn reference_path(&self, test_directory: &Path) -> PathBuf {
+        test_directory.join(&self.reference)
+    }
+
     #[cfg(feature = "imgtests")]
     pub fn test(
         &self,
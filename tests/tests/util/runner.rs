@@ -1,4 +1,6 @@
-use crate::util::navigator::TestNavigatorBackend;
+use crate::util::navigator::{FrameClock, PendingFutures, TestNavigatorBackend};
+use crate::util::options::ImageComparison;
+use crate::util::socket::TestSocketBackend;
 use crate::util::test::Test;
 use anyhow::{anyhow, Result};
 use ruffle_core::backend::audio::{
@@ -14,6 +16,7 @@ use ruffle_core::limits::ExecutionLimit;
 use ruffle_core::tag_utils::SwfMovie;
 use ruffle_core::{Player, PlayerBuilder, PlayerEvent};
 use ruffle_input_format::{AutomatedEvent, InputInjector, MouseButton as InputMouseButton};
+use ruffle_socket_format::SocketEvent;
 use std::cell::RefCell;
 use std::path::Path;
 use std::rc::Rc;
@@ -85,18 +88,36 @@ pub fn run_swf(
 ) -> Result<String> {
     let base_path = Path::new(&test.output_path).parent().unwrap();
     let mut executor = NullExecutor::new();
-    let movie = SwfMovie::from_path(&test.swf_path, None).map_err(|e| anyhow!(e.to_string()))?;
+    let mut movie =
+        SwfMovie::from_path(&test.swf_path, None).map_err(|e| anyhow!(e.to_string()))?;
+    if let Some(url) = &test.options.url {
+        movie.set_url(url.clone());
+    }
     let frame_time = 1000.0 / movie.frame_rate().to_f64();
     let frame_time_duration = Duration::from_millis(frame_time as u64);
 
     let log = TestLogBackend::new();
+    let clock = FrameClock::default();
+    let pending_futures = PendingFutures::default();
     let navigator = TestNavigatorBackend::new(
         base_path,
         &executor,
         test.options.log_fetch.then(|| log.clone()),
+        test.options.responses.clone(),
+        clock.clone(),
+        pending_futures.clone(),
     )?;
 
-    let builder = PlayerBuilder::new()
+    let sockets = match &test.options.socket {
+        Some(socket) => Some(TestSocketBackend::new(
+            socket.host.clone(),
+            socket.port,
+            SocketEvent::from_file(socket.script_path(base_path))?,
+        )),
+        None => None,
+    };
+
+    let mut builder = PlayerBuilder::new()
         .with_log(log.clone())
         .with_navigator(navigator)
         .with_max_execution_duration(Duration::from_secs(300))
@@ -105,6 +126,12 @@ pub fn run_swf(
             movie.height().to_pixels() as u32,
             1.0,
         );
+    if let Some(sockets) = &sockets {
+        builder = builder.with_sockets(sockets.clone());
+    }
+    for script in &test.options.injected_scripts {
+        builder = builder.with_injected_script(script.script(base_path)?);
+    }
 
     // Test player options may override anything set above
     let player = test
@@ -116,7 +143,8 @@ pub fn run_swf(
 
     before_start(player.clone())?;
 
-    for _ in 0..test.options.num_frames {
+    let mut saved_state = None;
+    for frame in 1..=test.options.num_frames {
         // If requested, ensure that the 'expected' amount of
         // time actually elapses between frames. This is useful for
         // tests that call 'flash.utils.getTimer()' and use
@@ -142,7 +170,9 @@ pub fn run_swf(
 
         player.lock().unwrap().run_frame();
         player.lock().unwrap().update_timers(frame_time);
-        executor.run();
+        player.lock().unwrap().poll_sockets();
+        clock.advance();
+        executor.run_until_stalled();
 
         injector.next(|evt, _btns_down| {
             player.lock().unwrap().handle_event(match evt {
@@ -169,53 +199,111 @@ pub fn run_swf(
                     key_code: KeyCode::from_u8(*key_code).expect("Invalid keycode in test"),
                     key_char: None,
                 },
+                AutomatedEvent::KeyUp { key_code } => PlayerEvent::KeyUp {
+                    key_code: KeyCode::from_u8(*key_code).expect("Invalid keycode in test"),
+                    key_char: None,
+                },
                 AutomatedEvent::Wait => unreachable!(),
             });
         });
         // Rendering has side-effects (such as processing 'DisplayObject.scrollRect' updates)
         player.lock().unwrap().render();
-    }
 
-    // Render the image to disk
-    // FIXME: Determine how we want to compare against on on-disk image
-    #[cfg(feature = "imgtests")]
-    if let Some(image_comparison) = &test.options.image_comparison {
-        if crate::util::environment::WGPU.is_some() {
-            use anyhow::Context;
-            use ruffle_render_wgpu::backend::WgpuRenderBackend;
-            use ruffle_render_wgpu::target::TextureTarget;
-
-            let mut player_lock = player.lock().unwrap();
-            player_lock.render();
-            let renderer = player_lock
-                .renderer_mut()
-                .downcast_mut::<WgpuRenderBackend<TextureTarget>>()
-                .unwrap();
-
-            let actual_image = renderer.capture_frame().expect("Failed to capture image");
-
-            let expected_image_path = base_path.join("expected.png");
-            if expected_image_path.is_file() {
-                let expected_image = image::open(&expected_image_path)
-                    .context("Failed to open expected image")?
-                    .into_rgba8();
-
-                image_comparison.test(
-                    actual_image,
-                    expected_image,
-                    base_path,
-                    renderer.descriptors().adapter.get_info(),
-                )?;
-            } else {
-                actual_image.save(expected_image_path)?;
+        if let Some(image_comparison) = &test.options.image_comparison {
+            if image_comparison.is_captured_at(frame, test.options.num_frames) {
+                compare_image(&player, image_comparison, base_path)?;
             }
         }
+
+        if let Some(save_state) = &test.options.save_state {
+            if frame == save_state.save_after_frame {
+                saved_state = Some(player.lock().unwrap().save_state()?);
+            }
+            if frame == save_state.load_after_frame {
+                let data = saved_state
+                    .as_ref()
+                    .ok_or_else(|| anyhow!("No state was saved before frame {frame}"))?;
+                player.lock().unwrap().load_state(data)?;
+            }
+        }
+
+        if test.options.reset_after_frame == Some(frame) {
+            player.lock().unwrap().reset();
+        }
     }
 
     before_end(player)?;
 
-    executor.run();
+    executor.run_until_stalled();
+
+    if clock.has_waiters() {
+        return Err(anyhow!(
+            "Canned responses were still delayed at the end of the test, increase num_frames"
+        ));
+    }
+    if pending_futures.count() > 0 {
+        return Err(anyhow!(
+            "{} futures were still pending at the end of the test",
+            pending_futures.count()
+        ));
+    }
+
+    if let Some(sockets) = &sockets {
+        sockets.finish()?;
+    }
 
     let trace = log.trace_output();
     Ok(trace)
 }
+
+/// Captures the current frame and compares it against the reference image of the test.
+/// If there's no reference image yet, the captured frame is saved as the reference.
+#[cfg(feature = "imgtests")]
+fn compare_image(
+    player: &Arc<Mutex<Player>>,
+    image_comparison: &ImageComparison,
+    base_path: &Path,
+) -> Result<()> {
+    use anyhow::Context;
+    use ruffle_render_wgpu::backend::WgpuRenderBackend;
+    use ruffle_render_wgpu::target::TextureTarget;
+
+    if crate::util::environment::WGPU.is_none() {
+        return Ok(());
+    }
+
+    let mut player_lock = player.lock().unwrap();
+    let renderer = player_lock
+        .renderer_mut()
+        .downcast_mut::<WgpuRenderBackend<TextureTarget>>()
+        .unwrap();
+
+    let actual_image = renderer.capture_frame().expect("Failed to capture image");
+
+    let expected_image_path = image_comparison.reference_path(base_path);
+    if expected_image_path.is_file() {
+        let expected_image = image::open(&expected_image_path)
+            .context("Failed to open expected image")?
+            .into_rgba8();
+
+        image_comparison.test(
+            actual_image,
+            expected_image,
+            base_path,
+            renderer.descriptors().adapter.get_info(),
+        )?;
+    } else {
+        actual_image.save(expected_image_path)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(not(feature = "imgtests"))]
+fn compare_image(
+    _player: &Arc<Mutex<Player>>,
+    _image_comparison: &ImageComparison,
+    _base_path: &Path,
+) -> Result<()> {
+    Ok(())
+}
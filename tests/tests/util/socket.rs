@@ -0,0 +1,175 @@
+use anyhow::{anyhow, Result};
+use ruffle_core::backend::socket::{SocketBackend, SocketEvent, SocketHandle, SocketPermission};
+use ruffle_socket_format::SocketEvent as ScriptEvent;
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::rc::Rc;
+
+/// A `SocketBackend` used by tests, which simulates a server in-process.
+///
+/// The server only accepts connections to its host and port, one at a time,
+/// and follows its script as the movie talks to it. Anything the movie does
+/// that the script doesn't expect fails the test once it's over.
+#[derive(Clone)]
+pub struct TestSocketBackend(Rc<RefCell<TestSocketServer>>);
+
+struct TestSocketServer {
+    host: String,
+    port: u16,
+
+    /// The remaining steps of the script.
+    script: VecDeque<ScriptEvent>,
+
+    next_handle: SocketHandle,
+
+    /// The connection of the movie, while it's open.
+    connection: Option<SocketHandle>,
+
+    /// Whether the movie closed its connection.
+    closed_by_movie: bool,
+
+    /// The data that the movie sent and the script didn't receive yet.
+    received: Vec<u8>,
+
+    /// The events to report to the player.
+    events: Vec<SocketEvent>,
+
+    errors: Vec<String>,
+}
+
+impl TestSocketBackend {
+    pub fn new(host: String, port: u16, script: Vec<ScriptEvent>) -> Self {
+        Self(Rc::new(RefCell::new(TestSocketServer {
+            host,
+            port,
+            script: script.into(),
+            next_handle: 0,
+            connection: None,
+            closed_by_movie: false,
+            received: Vec::new(),
+            events: Vec::new(),
+            errors: Vec::new(),
+        })))
+    }
+
+    /// Checks that the movie went through the whole script, as expected.
+    pub fn finish(&self) -> Result<()> {
+        let server = self.0.borrow();
+        if !server.errors.is_empty() {
+            return Err(anyhow!(
+                "Socket script failed: {}",
+                server.errors.join("; ")
+            ));
+        }
+        if let Some(event) = server.script.front() {
+            return Err(anyhow!(
+                "Socket script didn't complete, stuck at {:?}",
+                event
+            ));
+        }
+        if !server.received.is_empty() {
+            return Err(anyhow!(
+                "Socket server received unexpected data: {:?}",
+                String::from_utf8_lossy(&server.received)
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl TestSocketServer {
+    /// Runs the script until it has to wait for the movie.
+    fn advance(&mut self) {
+        while let Some(event) = self.script.front() {
+            match event {
+                ScriptEvent::WaitForConnection => return,
+                ScriptEvent::Receive { expected } => {
+                    let expected = expected.as_bytes();
+                    if self.received.len() < expected.len() {
+                        return;
+                    }
+                    let received: Vec<u8> = self.received.drain(..expected.len()).collect();
+                    if received != expected {
+                        self.errors.push(format!(
+                            "Expected {:?}, but received {:?}",
+                            String::from_utf8_lossy(expected),
+                            String::from_utf8_lossy(&received)
+                        ));
+                    }
+                }
+                ScriptEvent::Send { payload } => {
+                    let Some(handle) = self.connection else {
+                        return;
+                    };
+                    self.events.push(SocketEvent::Data {
+                        handle,
+                        data: payload.as_bytes().to_vec(),
+                    });
+                }
+                ScriptEvent::WaitForDisconnect => {
+                    if !self.closed_by_movie {
+                        return;
+                    }
+                }
+                ScriptEvent::Disconnect => {
+                    if let Some(handle) = self.connection.take() {
+                        self.events.push(SocketEvent::Closed { handle });
+                    }
+                }
+            }
+            self.script.pop_front();
+        }
+    }
+}
+
+impl SocketBackend for TestSocketBackend {
+    fn connect(&mut self, host: &str, port: u16, _permission: SocketPermission) -> SocketHandle {
+        let mut server = self.0.borrow_mut();
+        let handle = server.next_handle;
+        server.next_handle += 1;
+
+        let accepted = host == server.host
+            && port == server.port
+            && server.connection.is_none()
+            && server.script.front() == Some(&ScriptEvent::WaitForConnection);
+        if accepted {
+            server.script.pop_front();
+            server.connection = Some(handle);
+            server.closed_by_movie = false;
+        }
+        server.events.push(SocketEvent::Connected {
+            handle,
+            success: accepted,
+        });
+        server.advance();
+        handle
+    }
+
+    fn send(&mut self, handle: SocketHandle, data: Vec<u8>) {
+        let mut server = self.0.borrow_mut();
+        if server.connection == Some(handle) {
+            server.received.extend(data);
+            server.advance();
+        }
+    }
+
+    fn close(&mut self, handle: SocketHandle) {
+        let mut server = self.0.borrow_mut();
+        if server.connection == Some(handle) {
+            server.connection = None;
+            server.closed_by_movie = true;
+            server.events.retain(|event| match event {
+                SocketEvent::Connected { handle: h, .. }
+                | SocketEvent::Data { handle: h, .. }
+                | SocketEvent::Closed { handle: h } => *h != handle,
+            });
+            server.advance();
+        }
+    }
+
+    fn poll(&mut self) -> Vec<SocketEvent> {
+        let mut server = self.0.borrow_mut();
+        server.advance();
+        std::mem::take(&mut server.events)
+    }
+}
@@ -2,7 +2,7 @@ use crate::assert_eq;
 use crate::set_logger;
 use crate::util::options::TestOptions;
 use crate::util::runner::run_swf;
-use anyhow::{Context, Result};
+use anyhow::{anyhow, Context, Result};
 use ruffle_core::Player;
 use ruffle_input_format::InputInjector;
 use std::path::{Path, PathBuf};
@@ -46,10 +46,15 @@ impl Test {
         before_end: impl FnOnce(Arc<Mutex<Player>>) -> Result<()>,
     ) -> std::result::Result<(), libtest_mimic::Failed> {
         set_logger();
-        let injector = if self.input_path.is_file() {
-            InputInjector::from_file(&self.input_path)?
-        } else {
-            InputInjector::empty()
+        let injector = match (self.input_path.is_file(), &self.options.input) {
+            (true, Some(_)) => {
+                return Err(
+                    anyhow!("Test can't have both an input.json and an [input] section").into(),
+                )
+            }
+            (true, None) => InputInjector::from_file(&self.input_path)?,
+            (false, Some(input)) => input.injector(),
+            (false, None) => InputInjector::empty(),
         };
         let output = run_swf(&self, injector, before_start, before_end)?;
         self.compare_output(&output)?;
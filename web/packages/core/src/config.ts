@@ -40,4 +40,5 @@ export const DEFAULT_CONFIG: Required<BaseLoadOptions> = {
     preferredRenderer: null,
     openUrlMode: OpenURLMode.Allow,
     allowNetworking: NetworkingAccessMode.All,
+    useWorker: false,
 };
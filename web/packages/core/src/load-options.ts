@@ -353,8 +353,8 @@ export interface BaseLoadOptions {
     splashScreen?: boolean;
 
     /**
-     * Maximum amount of time a script can take before scripting
-     * is disabled.
+     * Maximum amount of time a script can take before the user is
+     * asked whether to abort it.
      *
      * @default 15
      */
@@ -387,6 +387,9 @@ export interface BaseLoadOptions {
     /**
      * This is equivalent to Stage.quality.
      *
+     * The "autolow" and "autohigh" values of the Flash Player embed
+     * parameter are also accepted, and behave like "low" and "high".
+     *
      * @default "high"
      */
     quality?: string;
@@ -476,6 +479,18 @@ export interface BaseLoadOptions {
      * @default NetworkingAccessMode.All
      */
     allowNetworking?: NetworkingAccessMode;
+
+    /**
+     * Whether to run the player in a Web Worker, so that slow movies don't block the page.
+     *
+     * This requires a browser that can render to an `OffscreenCanvas` with WebGL
+     * or WebGPU, and falls back to running on the page otherwise. Players in a
+     * worker don't play audio, can't use `ExternalInterface` or open URLs, and don't
+     * keep their shared objects once closed.
+     *
+     * @default false
+     */
+    useWorker?: boolean;
 }
 
 /**
@@ -19,26 +19,21 @@ declare global {
 
 type ProgressCallback = (bytesLoaded: number, bytesTotal: number) => void;
 
+type RuffleModule =
+    | typeof import("../dist/ruffle_web")
+    | typeof import("../dist/ruffle_web-wasm_extensions");
+
 /**
- * Load ruffle from an automatically-detected location.
+ * Import and initialize the Ruffle module, from `__webpack_public_path__`.
  *
- * This function returns a new instance of Ruffle and downloads it every time.
- * You should not use it directly; this module will memoize the resource
- * download.
+ * This doesn't touch the DOM, so that it can also be used in a Web Worker.
  *
- * @param config The `window.RufflePlayer.config` object.
  * @param progressCallback The callback that will be run with Ruffle's download progress.
- * @returns A ruffle constructor that may be used to create new Ruffle
- * instances.
+ * @returns The initialized Ruffle module.
  */
-async function fetchRuffle(
-    config: URLLoadOptions | DataLoadOptions | object,
+export async function importRuffle(
     progressCallback?: ProgressCallback
-): Promise<typeof Ruffle> {
-    // Apply some pure JavaScript polyfills to prevent conflicts with external
-    // libraries, if needed.
-    setPolyfillsOnLoad();
-
+): Promise<RuffleModule> {
     // NOTE: Keep this list in sync with $RUSTFLAGS in the CI build config!
     const extensionsSupported: boolean = (
         await Promise.all([
@@ -56,11 +51,9 @@ async function fetchRuffle(
         );
     }
 
-    __webpack_public_path__ = publicPath(config);
-
     // Note: The argument passed to import() has to be a simple string literal,
     // otherwise some bundler will get confused and won't include the module?
-    const { default: init, Ruffle } = await (extensionsSupported
+    const ruffleModule = await (extensionsSupported
         ? import("../dist/ruffle_web-wasm_extensions")
         : import("../dist/ruffle_web"));
     let response;
@@ -101,8 +94,34 @@ async function fetchRuffle(
         response = wasmResponse;
     }
 
-    await init(response);
+    await ruffleModule.default(response);
+
+    return ruffleModule;
+}
+
+/**
+ * Load ruffle from an automatically-detected location.
+ *
+ * This function returns a new instance of Ruffle and downloads it every time.
+ * You should not use it directly; this module will memoize the resource
+ * download.
+ *
+ * @param config The `window.RufflePlayer.config` object.
+ * @param progressCallback The callback that will be run with Ruffle's download progress.
+ * @returns A ruffle constructor that may be used to create new Ruffle
+ * instances.
+ */
+async function fetchRuffle(
+    config: URLLoadOptions | DataLoadOptions | object,
+    progressCallback?: ProgressCallback
+): Promise<Ruffle> {
+    // Apply some pure JavaScript polyfills to prevent conflicts with external
+    // libraries, if needed.
+    setPolyfillsOnLoad();
+
+    __webpack_public_path__ = publicPath(config);
 
+    const { Ruffle } = await importRuffle(progressCallback);
     return Ruffle;
 }
 
@@ -43,3 +43,51 @@ export interface MovieMetadata {
      */
     readonly uncompressedLength: number;
 }
+
+/**
+ * The properties of a fully loaded SWF file.
+ */
+export interface MovieInfo {
+    /**
+     * The width of the movie in pixels.
+     */
+    readonly width: number;
+
+    /**
+     * The height of the movie in pixels.
+     */
+    readonly height: number;
+
+    /**
+     * The frame rate of the movie in frames per second.
+     */
+    readonly frameRate: number;
+
+    /**
+     * The number of frames on the root timeline of the movie.
+     */
+    readonly numFrames: number;
+
+    /**
+     * The SWF version of the movie.
+     */
+    readonly swfVersion: number;
+
+    /**
+     * Whether this movie is an ActionScript 3.0 movie.
+     */
+    readonly isActionScript3: boolean;
+
+    /**
+     * The XMP metadata of the movie, stored in its Metadata tag.
+     * May be `null` if the movie has no metadata.
+     */
+    readonly metadata: string | null;
+
+    /**
+     * An estimate of the duration of the movie in seconds, assuming its
+     * root timeline plays each frame once.
+     * May be `null` if the movie has no frame rate.
+     */
+    readonly duration: number | null;
+}
@@ -6,7 +6,11 @@ import {
     RufflePlayer,
     workaroundYoutubeMixedContent,
 } from "./ruffle-player";
-import { NetworkingAccessMode, WindowMode } from "./load-options";
+import {
+    NetworkingAccessMode,
+    URLLoadOptions,
+    WindowMode,
+} from "./load-options";
 import { registerElement } from "./register-element";
 import { isSwfFilename, isSwfMimeType } from "./swf-utils";
 
@@ -37,40 +41,45 @@ export class RuffleEmbed extends RufflePlayer {
         super.connectedCallback();
         const src = this.attributes.getNamedItem("src");
         if (src) {
-            const allowScriptAccess =
-                this.attributes.getNamedItem("allowScriptAccess")?.value ??
-                null;
-            const menu = this.attributes.getNamedItem("menu")?.value ?? null;
-
             // Kick off the SWF download.
-            this.load({
-                url: src.value,
-                allowScriptAccess: isScriptAccessAllowed(
-                    allowScriptAccess,
-                    src.value
-                ),
-                parameters:
-                    this.attributes.getNamedItem("flashvars")?.value ?? null,
-                backgroundColor:
-                    this.attributes.getNamedItem("bgcolor")?.value ?? null,
-                base: this.attributes.getNamedItem("base")?.value ?? null,
-                menu: isBuiltInContextMenuVisible(menu),
-                salign: this.attributes.getNamedItem("salign")?.value ?? "",
-                quality:
-                    this.attributes.getNamedItem("quality")?.value ?? "high",
-                scale:
-                    this.attributes.getNamedItem("scale")?.value ?? "showAll",
-                wmode:
-                    (this.attributes.getNamedItem("wmode")
-                        ?.value as WindowMode) ?? WindowMode.Window,
-                allowNetworking:
-                    (this.attributes.getNamedItem("allowNetworking")
-                        ?.value as NetworkingAccessMode) ??
-                    NetworkingAccessMode.All,
-            });
+            this.load(this.loadOptions(src.value));
         }
     }
 
+    /**
+     * Builds the options to load the given SWF URL with, from the
+     * attributes of this element.
+     *
+     * @param url The URL of the SWF to load.
+     * @returns The options to load the SWF with.
+     */
+    private loadOptions(url: string): URLLoadOptions {
+        const allowScriptAccess =
+            this.attributes.getNamedItem("allowScriptAccess")?.value ?? null;
+        const menu = this.attributes.getNamedItem("menu")?.value ?? null;
+
+        return {
+            url,
+            allowScriptAccess: isScriptAccessAllowed(allowScriptAccess, url),
+            parameters:
+                this.attributes.getNamedItem("flashvars")?.value ?? null,
+            backgroundColor:
+                this.attributes.getNamedItem("bgcolor")?.value ?? null,
+            base: this.attributes.getNamedItem("base")?.value ?? null,
+            menu: isBuiltInContextMenuVisible(menu),
+            salign: this.attributes.getNamedItem("salign")?.value ?? "",
+            quality: this.attributes.getNamedItem("quality")?.value ?? "high",
+            scale: this.attributes.getNamedItem("scale")?.value ?? "showAll",
+            wmode:
+                (this.attributes.getNamedItem("wmode")?.value as WindowMode) ??
+                WindowMode.Window,
+            allowNetworking:
+                (this.attributes.getNamedItem("allowNetworking")
+                    ?.value as NetworkingAccessMode) ??
+                NetworkingAccessMode.All,
+        };
+    }
+
     /**
      * Polyfill of HTMLObjectElement.
      *
@@ -118,13 +127,7 @@ export class RuffleEmbed extends RufflePlayer {
         if (this.isConnected && name === "src") {
             const src = this.attributes.getNamedItem("src");
             if (src) {
-                this.load({
-                    url: src.value,
-                    parameters:
-                        this.attributes.getNamedItem("flashvars")?.value ??
-                        null,
-                    base: this.attributes.getNamedItem("base")?.value ?? null,
-                });
+                this.load(this.loadOptions(src.value));
             }
         }
     }
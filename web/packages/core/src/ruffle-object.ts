@@ -88,99 +88,116 @@ export class RuffleObject extends RufflePlayer {
 
         this.params = paramsOf(this);
 
-        let url = null;
-        if (this.attributes.getNamedItem("data")) {
-            url = this.attributes.getNamedItem("data")?.value;
-        } else if (this.params["movie"]) {
-            url = this.params["movie"];
+        const url = this.movieUrl();
+        if (url) {
+            // Kick off the SWF download.
+            this.load(this.loadOptions(url));
         }
+    }
 
-        const allowScriptAccess = findCaseInsensitive(
-            this.params,
-            "allowScriptAccess",
-            null
-        );
-
-        const parameters = findCaseInsensitive(
-            this.params,
-            "flashvars",
-            this.getAttribute("flashvars")
-        );
-
-        const backgroundColor = findCaseInsensitive(
-            this.params,
-            "bgcolor",
-            this.getAttribute("bgcolor")
+    /**
+     * The URL of the SWF to load, from the `data` attribute or the `movie` parameter.
+     *
+     * @returns The URL of the SWF, if any.
+     */
+    private movieUrl(): string | null {
+        return (
+            this.attributes.getNamedItem("data")?.value ??
+            findCaseInsensitive(this.params, "movie", null)
         );
+    }
 
-        const allowNetworking = findCaseInsensitive(
-            this.params,
-            "allowNetworking",
-            this.getAttribute("allowNetworking")
-        );
+    /**
+     * Builds the options to load the given SWF URL with, from the parameters
+     * of this element, or its attributes of the same name.
+     *
+     * @param url The URL of the SWF to load.
+     * @returns The options to load the SWF with.
+     */
+    private loadOptions(url: string): URLLoadOptions {
+        const param = (key: string, defaultValue: string | null) =>
+            findCaseInsensitive(
+                this.params,
+                key,
+                this.getAttribute(key) ?? defaultValue
+            );
 
-        const base = findCaseInsensitive(
-            this.params,
-            "base",
-            this.getAttribute("base")
+        const allowScriptAccess = param("allowScriptAccess", null);
+        const parameters = param("flashvars", null);
+        const backgroundColor = param("bgcolor", null);
+        const allowNetworking = param("allowNetworking", null);
+        const base = param("base", null);
+        const menu = param("menu", null);
+        const salign = param("salign", "");
+        const quality = param("quality", "high");
+        const scale = param("scale", "showAll");
+        const wmode = param("wmode", "window");
+
+        const options: URLLoadOptions = { url };
+        options.allowScriptAccess = isScriptAccessAllowed(
+            allowScriptAccess,
+            url
         );
+        if (parameters) {
+            options.parameters = parameters;
+        }
+        if (backgroundColor) {
+            options.backgroundColor = backgroundColor;
+        }
+        if (base) {
+            options.base = base;
+        }
+        options.menu = isBuiltInContextMenuVisible(menu);
+        if (salign) {
+            options.salign = salign;
+        }
+        if (quality) {
+            options.quality = quality;
+        }
+        if (scale) {
+            options.scale = scale;
+        }
+        if (wmode) {
+            options.wmode = wmode as WindowMode;
+        }
+        if (allowNetworking) {
+            options.allowNetworking = allowNetworking as NetworkingAccessMode;
+        }
+        return options;
+    }
 
-        const menu = findCaseInsensitive(this.params, "menu", null);
-        const salign = findCaseInsensitive(this.params, "salign", "");
-        const quality = findCaseInsensitive(this.params, "quality", "high");
-        const scale = findCaseInsensitive(this.params, "scale", "showAll");
-        const wmode = findCaseInsensitive(this.params, "wmode", "window");
+    /**
+     * @ignore
+     * @internal
+     */
+    static override get observedAttributes(): string[] {
+        return ["data", "width", "height"];
+    }
 
-        if (url) {
-            const options: URLLoadOptions = { url };
-            options.allowScriptAccess = isScriptAccessAllowed(
-                allowScriptAccess,
-                url
-            );
-            if (parameters) {
-                options.parameters = parameters;
-            }
-            if (backgroundColor) {
-                options.backgroundColor = backgroundColor;
-            }
-            if (base) {
-                options.base = base;
-            }
-            options.menu = isBuiltInContextMenuVisible(menu);
-            if (salign) {
-                options.salign = salign;
-            }
-            if (quality) {
-                options.quality = quality;
-            }
-            if (scale) {
-                options.scale = scale;
-            }
-            if (wmode) {
-                options.wmode = wmode as WindowMode;
-            }
-            if (allowNetworking) {
-                options.allowNetworking =
-                    allowNetworking as NetworkingAccessMode;
+    /**
+     * @ignore
+     * @internal
+     */
+    override attributeChangedCallback(
+        name: string,
+        oldValue: string | undefined,
+        newValue: string | undefined
+    ): void {
+        super.attributeChangedCallback(name, oldValue, newValue);
+        if (this.isConnected && name === "data") {
+            // Like a native `<object>`, reload the movie with its current parameters.
+            this.params = paramsOf(this);
+            const url = this.movieUrl();
+            if (url) {
+                this.load(this.loadOptions(url));
             }
-
-            // Kick off the SWF download.
-            this.load(options);
         }
     }
 
     protected override debugPlayerInfo(): string {
         let errorText = super.debugPlayerInfo();
         errorText += "Player type: Object\n";
-
-        let url = null;
-
-        if (this.attributes.getNamedItem("data")) {
-            url = this.attributes.getNamedItem("data")?.value;
-        } else if (this.params["movie"]) {
-            url = this.params["movie"];
-        }
-        errorText += `SWF URL: ${url}\n`;
+        errorText += `SWF URL: ${this.movieUrl()}\n`;
 
         Object.keys(this.params).forEach((key) => {
             errorText += `Param ${key}: ${this.params[key]}\n`;
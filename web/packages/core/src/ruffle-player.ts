@@ -5,9 +5,10 @@ import { lookupElement } from "./register-element";
 import { DEFAULT_CONFIG } from "./config";
 import type { DataLoadOptions, URLLoadOptions } from "./load-options";
 import { AutoPlay, UnmuteOverlay, WindowMode } from "./load-options";
-import type { MovieMetadata } from "./movie-metadata";
+import type { MovieInfo, MovieMetadata } from "./movie-metadata";
 import { swfFileName } from "./swf-utils";
 import { buildInfo } from "./build-info";
+import { isWorkerSupported, WorkerInstance } from "./worker-instance";
 
 const RUFFLE_ORIGIN = "https://ruffle.rs";
 const DIMENSION_REGEX = /^\s*(\d+(\.\d+)?(%)?)/;
@@ -122,6 +123,7 @@ export class RufflePlayer extends HTMLElement {
     private readonly splashScreen: HTMLElement;
     private readonly virtualKeyboard: HTMLInputElement;
     private readonly saveManager: HTMLDivElement;
+    private readonly settingsPanel: HTMLDivElement;
 
     // Firefox has a read-only "contextMenu" property,
     // so avoid shadowing it.
@@ -141,7 +143,7 @@ export class RufflePlayer extends HTMLElement {
     private loadedConfig?: URLLoadOptions | DataLoadOptions;
 
     private swfUrl?: URL;
-    private instance: Ruffle | null;
+    private instance: Ruffle | WorkerInstance | null;
     private lastActivePlayingState: boolean;
 
     private _metadata: MovieMetadata | null;
@@ -205,6 +207,20 @@ export class RufflePlayer extends HTMLElement {
         return this._metadata;
     }
 
+    /**
+     * The properties of the playing movie, including its XMP metadata and an
+     * estimate of its duration, such as for building a catalog of movies.
+     *
+     * @returns The properties of the movie, or `null` if the movie has not
+     * fully loaded yet.
+     */
+    get movieInfo(): MovieInfo | null {
+        if (this._readyState !== ReadyState.Loaded) {
+            return null;
+        }
+        return this.instance?.movie_info() ?? null;
+    }
+
     /**
      * Constructs a new Ruffle flash player for insertion onto the page.
      */
@@ -252,6 +268,33 @@ export class RufflePlayer extends HTMLElement {
         if (backupSaves) {
             backupSaves.addEventListener("click", this.backupSaves.bind(this));
         }
+        this.settingsPanel = <HTMLDivElement>(
+            this.shadow.getElementById("settings-panel")!
+        );
+        this.settingsPanel.addEventListener("click", () =>
+            this.settingsPanel.classList.add("hidden")
+        );
+        this.settingsPanel
+            .querySelector("#settings-area")!
+            .addEventListener("click", (event) => event.stopPropagation());
+        this.settingsPanel
+            .querySelector("#close-settings")!
+            .addEventListener("click", () =>
+                this.settingsPanel.classList.add("hidden")
+            );
+        this.settingsPanel
+            .querySelectorAll("select")
+            .forEach((select) =>
+                select.addEventListener(
+                    "change",
+                    this.saveDomainSettings.bind(this)
+                )
+            );
+        this.settingsPanel
+            .querySelector("#settings-mute")!
+            .addEventListener("change", (event) => {
+                this.volume = (<HTMLInputElement>event.target).checked ? 0 : 1;
+            });
 
         this.contextMenuElement = this.shadow.getElementById("context-menu")!;
         window.addEventListener("pointerdown", this.checkIfTouch.bind(this));
@@ -463,6 +506,16 @@ export class RufflePlayer extends HTMLElement {
                     "'{secs: 15, nanos: 0}'."
             );
         }
+        if (this.loadedConfig?.useWorker) {
+            if (isWorkerSupported()) {
+                await this.createWorkerInstance(this.loadedConfig);
+                return;
+            }
+            console.warn(
+                "This browser can't run Ruffle in a Web Worker, running it on the page instead"
+            );
+        }
+
         const ruffleConstructor = await loadRuffle(
             this.loadedConfig || {},
             this.onRuffleDownloadProgress.bind(this)
@@ -586,6 +639,40 @@ export class RufflePlayer extends HTMLElement {
         }
     }
 
+    /**
+     * Creates a player that runs in a Web Worker, drawing to a canvas transferred to it.
+     *
+     * @param config The config of the player.
+     */
+    private async createWorkerInstance(
+        config: URLLoadOptions | DataLoadOptions
+    ): Promise<void> {
+        this.instance = await WorkerInstance.create(
+            this.container,
+            {
+                setMetadata: (metadata) => this.setMetadata(metadata),
+                panic: (error) => this.panic(error),
+            },
+            config
+        );
+        this._cachedDebugInfo = this.instance.renderer_debug_info();
+
+        console.log(
+            "%c" +
+                "New Ruffle instance created in a Web Worker (Used renderer: " +
+                (this.instance.renderer_name() ?? "") +
+                ")",
+            "background: #37528C; color: #FFAD33"
+        );
+
+        // Workers play no audio, so movies may always autoplay.
+        if (config.autoplay === AutoPlay.Off) {
+            this.playButton.style.display = "block";
+        } else {
+            this.play();
+        }
+    }
+
     /**
      * Uploads the splash screen progress bar.
      *
@@ -731,10 +818,10 @@ export class RufflePlayer extends HTMLElement {
                 console.log(`Loading SWF file ${options.url}`);
                 this.swfUrl = new URL(options.url, document.baseURI);
 
+                // Like Flash Player, pass the query string of the SWF URL
+                // as FlashVars.
                 const parameters = {
-                    ...sanitizeParameters(
-                        options.url.substring(options.url.indexOf("?"))
-                    ),
+                    ...sanitizeParameters(this.swfUrl.search),
                     ...sanitizeParameters(options.parameters),
                 };
 
@@ -768,6 +855,17 @@ export class RufflePlayer extends HTMLElement {
         }
     }
 
+    /**
+     * Restarts the movie from the beginning, as if it had just been loaded.
+     *
+     * All script state is discarded, but the movie isn't downloaded again.
+     */
+    reset(): void {
+        if (this.instance) {
+            this.instance.reset();
+        }
+    }
+
     /**
      * Whether this player is currently playing.
      *
@@ -803,6 +901,30 @@ export class RufflePlayer extends HTMLElement {
         }
     }
 
+    /**
+     * Returns the playback speed multiplier of the player.
+     *
+     * @returns The playback speed. 1.0 is normal speed.
+     */
+    get playbackSpeed(): number {
+        if (this.instance) {
+            return this.instance.playback_speed();
+        }
+        return 1.0;
+    }
+
+    /**
+     * Sets the playback speed multiplier of the player, for fast-forwarding
+     * or slowing down the movie. Audio is muted while not at normal speed.
+     *
+     * @param value The playback speed. 1.0 is normal speed.
+     */
+    set playbackSpeed(value: number) {
+        if (this.instance) {
+            this.instance.set_playback_speed(value);
+        }
+    }
+
     /**
      * Checks if this player is allowed to be fullscreen by the browser.
      *
@@ -1142,6 +1264,64 @@ export class RufflePlayer extends HTMLElement {
             console.error("SWF download failed");
         }
     }
+    /**
+     * Opens the settings panel, as requested by the content through `Security.showSettings`.
+     *
+     * @param panel The name of the `SecurityPanel` to show, or "settingsManager" for all of them.
+     */
+    protected displaySettingsPanel(panel: string): void {
+        const settings = this.instance?.domain_settings();
+        if (!settings) {
+            return;
+        }
+
+        this.settingsPanel.querySelector("#settings-domain")!.textContent =
+            settings.domain;
+        (<HTMLSelectElement>(
+            this.settingsPanel.querySelector("#settings-camera")
+        )).value = settings.camera;
+        (<HTMLSelectElement>(
+            this.settingsPanel.querySelector("#settings-microphone")
+        )).value = settings.microphone;
+        (<HTMLSelectElement>(
+            this.settingsPanel.querySelector("#settings-storage-quota")
+        )).value =
+            settings.storageQuota === null
+                ? "unlimited"
+                : settings.storageQuota.toString();
+        (<HTMLInputElement>(
+            this.settingsPanel.querySelector("#settings-mute")
+        )).checked = this.volume === 0;
+
+        this.settingsPanel
+            .querySelectorAll<HTMLElement>(".setting")
+            .forEach((setting) => {
+                const panels = setting.dataset["panels"]!.split(" ");
+                setting.classList.toggle(
+                    "hidden",
+                    panel !== "settingsManager" && !panels.includes(panel)
+                );
+            });
+        this.settingsPanel.classList.remove("hidden");
+    }
+
+    /**
+     * Saves the settings chosen in the settings panel.
+     */
+    private saveDomainSettings(): void {
+        const value = (id: string) =>
+            (<HTMLSelectElement>this.settingsPanel.querySelector(id)).value;
+        const storageQuota = value("#settings-storage-quota");
+        this.instance?.set_domain_settings({
+            domain: this.settingsPanel.querySelector("#settings-domain")!
+                .textContent,
+            storageQuota:
+                storageQuota === "unlimited" ? null : Number(storageQuota),
+            camera: value("#settings-camera"),
+            microphone: value("#settings-microphone"),
+        });
+    }
+
     private virtualKeyboardInput() {
         const input = this.virtualKeyboard;
         const string = input.value;
@@ -1574,14 +1754,49 @@ export class RufflePlayer extends HTMLElement {
     /**
      * Sets a trace observer on this flash player.
      *
-     * The observer will be called, as a function, for each message that the playing movie will "trace" (output).
+     * The observer will be called, as a function, for each message that the playing movie will "trace" (output),
+     * along with the URL of the movie that traced it, which may be a movie loaded by the main one.
      *
      * @param observer The observer that will be called for each trace.
      */
-    set traceObserver(observer: ((message: string) => void) | null) {
+    set traceObserver(
+        observer: ((message: string, movieUrl: string) => void) | null
+    ) {
         this.instance?.set_trace_observer(observer);
     }
 
+    /**
+     * Changes the language that the text of the player, such as its context menu, is shown in.
+     *
+     * By default, the language of the browser is used.
+     *
+     * @param language The language, such as "fr-FR".
+     */
+    setLanguage(language: string): void {
+        this.instance?.set_language(language);
+    }
+
+    /**
+     * Adds translations of the text of the player, written as a Fluent (.ftl) file.
+     *
+     * Messages added later take precedence, so this can also change the built-in text.
+     *
+     * @param language The language of the translations, such as "fr-FR".
+     * @param source The content of the Fluent file.
+     */
+    addLocale(language: string, source: string): void {
+        this.instance?.add_locale(language, source);
+    }
+
+    /**
+     * Returns the latest messages traced by the playing movies, from the oldest to the latest.
+     *
+     * @returns The latest traces, along with the URL of the movie that traced each of them.
+     */
+    recentTraces(): { movieUrl: string; message: string }[] {
+        return this.instance?.recent_traces() ?? [];
+    }
+
     /**
      * Get data included in any panic of this ruffle-player
      *
@@ -1990,6 +2205,18 @@ export class RufflePlayer extends HTMLElement {
         };
     }
 
+    /**
+     * Ask the user whether to abort a script that has been running for too long.
+     *
+     * This has to block until the user answers, as the script is still running.
+     *
+     * @param message The question to ask, in the language of the user.
+     * @returns True if the script should be aborted.
+     */
+    protected displayScriptTimeoutPrompt(message: string): boolean {
+        return window.confirm(message);
+    }
+
     protected debugPlayerInfo(): string {
         let result = `Allows script access: ${
             this.loadedConfig ? this.loadedConfig.allowScriptAccess : false
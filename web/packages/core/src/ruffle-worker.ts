@@ -0,0 +1,125 @@
+/**
+ * The entry point of the Web Workers that run players, see `WorkerInstance`.
+ *
+ * @ignore
+ * @internal
+ */
+
+import type { RuffleWorker } from "../dist/ruffle_web";
+import { importRuffle } from "./load-ruffle";
+import type { ToWorkerMessage } from "./worker-instance";
+
+// The DOM and the worker type definitions can't be used together.
+const scope = self as unknown as {
+    postMessage(message: unknown): void;
+    addEventListener(
+        type: "message",
+        listener: (event: MessageEvent<ToWorkerMessage>) => void
+    ): void;
+    close(): void;
+};
+
+let player: Promise<RuffleWorker> | null = null;
+
+/**
+ * Reports an error to the page, which panics.
+ *
+ * @param error The error that was thrown.
+ */
+function panic(error: unknown): void {
+    scope.postMessage({
+        type: "panic",
+        value: error instanceof Error ? error.message : String(error),
+    });
+}
+
+/**
+ * Creates the player, once the page gave it its canvas.
+ *
+ * @param message The init message of the page.
+ * @returns The player.
+ */
+async function createPlayer(
+    message: Extract<ToWorkerMessage, { type: "init" }>
+): Promise<RuffleWorker> {
+    __webpack_public_path__ = message.publicPath;
+    const { RuffleWorker } = await importRuffle();
+    const player = await new RuffleWorker(message.canvas, message.config);
+    player.set_trace_observer((message: string, movieUrl: string) =>
+        scope.postMessage({ type: "trace", value: { message, movieUrl } })
+    );
+    scope.postMessage({
+        type: "ready",
+        value: {
+            rendererName: player.renderer_name(),
+            rendererDebugInfo: player.renderer_debug_info(),
+        },
+    });
+    return player;
+}
+
+/**
+ * Runs a message of the page on the player.
+ *
+ * @param player The player.
+ * @param message The message of the page.
+ */
+function handleMessage(player: RuffleWorker, message: ToWorkerMessage): void {
+    switch (message.type) {
+        case "init":
+            break;
+        case "stream":
+            player.stream_from(message.url, message.parameters);
+            break;
+        case "loadData":
+            player.load_data(
+                new Uint8Array(message.data),
+                message.parameters,
+                message.url
+            );
+            break;
+        case "input":
+            scope.postMessage({
+                type: "preventScrolling",
+                value: player.handle_input(message.event),
+            });
+            break;
+        case "resize":
+            player.resize(message.width, message.height, message.scaleFactor);
+            break;
+        case "play":
+            player.play();
+            break;
+        case "pause":
+            player.pause();
+            break;
+        case "reset":
+            player.reset();
+            break;
+        case "setVolume":
+            player.set_volume(message.value);
+            break;
+        case "setPlaybackSpeed":
+            player.set_playback_speed(message.value);
+            break;
+        case "setFullscreen":
+            player.set_fullscreen(message.value);
+            break;
+        case "destroy":
+            player.destroy();
+            player.free();
+            scope.close();
+            break;
+    }
+}
+
+scope.addEventListener("message", (event) => {
+    const message = event.data;
+    if (message.type === "init") {
+        player = createPlayer(message);
+    }
+    // Messages sent while the player is created wait for it, in order.
+    player
+        ?.then((player) => handleMessage(player, message))
+        .catch(panic);
+});
@@ -355,6 +355,40 @@ ruffleShadowTemplate.innerHTML = `
         #local-saves tr:nth-child(even) {
             background-color: #f2f2f2;
         }
+
+        #settings-panel {
+            height: inherit;
+        }
+        #settings-area {
+            position: sticky;
+            background: white;
+            width: fit-content;
+            padding: 16px;
+            border: 3px solid black;
+            margin: auto;
+            max-height: calc(100% - 38px);
+            overflow-y: auto;
+        }
+
+        #settings-area h3 {
+            margin: 0 2em 12px 0;
+            word-break: break-all;
+        }
+
+        .setting {
+            display: flex;
+            justify-content: space-between;
+            gap: 1em;
+            padding: 6px 0;
+        }
+
+        #close-settings {
+            position: absolute;
+            top: 5px;
+            right: 10px;
+            cursor: pointer;
+            font-size: x-large;
+        }
     </style>
     <style id="dynamic_styles"></style>
 
@@ -380,5 +414,42 @@ ruffleShadowTemplate.innerHTML = `
             <table id="local-saves"></table>
         </div>
     </div>
+    <div id="settings-panel" class="hidden">
+        <div id="settings-area">
+            <span id="close-settings">&times;</span>
+            <h3>Settings for <span id="settings-domain"></span></h3>
+            <label class="setting" data-panels="privacy camera">
+                Camera
+                <select id="settings-camera">
+                    <option value="ask">Ask</option>
+                    <option value="allow">Allow</option>
+                    <option value="deny">Deny</option>
+                </select>
+            </label>
+            <label class="setting" data-panels="privacy microphone">
+                Microphone
+                <select id="settings-microphone">
+                    <option value="ask">Ask</option>
+                    <option value="allow">Allow</option>
+                    <option value="deny">Deny</option>
+                </select>
+            </label>
+            <label class="setting" data-panels="localStorage">
+                Local storage
+                <select id="settings-storage-quota">
+                    <option value="0">None</option>
+                    <option value="10240">10 KB</option>
+                    <option value="102400">100 KB</option>
+                    <option value="1048576">1 MB</option>
+                    <option value="10485760">10 MB</option>
+                    <option value="unlimited">Unlimited</option>
+                </select>
+            </label>
+            <label class="setting" data-panels="display">
+                Mute
+                <input id="settings-mute" type="checkbox">
+            </label>
+        </div>
+    </div>
     <ul id="context-menu"></ul>
 `;
@@ -0,0 +1,487 @@
+import type { BaseLoadOptions } from "./load-options";
+import type { MovieInfo, MovieMetadata } from "./movie-metadata";
+import { publicPath } from "./public-path";
+
+/**
+ * How many traces are kept for `recent_traces`, like in the main thread player.
+ */
+const MAX_RECENT_TRACES = 500;
+
+/**
+ * An input event of the canvas, in device pixels.
+ *
+ * @internal
+ */
+export type InputEvent =
+    | { type: "mouseEnter" }
+    | { type: "mouseLeave" }
+    | { type: "mouseMove"; x: number; y: number }
+    | { type: "mouseDown"; x: number; y: number; button: number }
+    | { type: "mouseUp"; x: number; y: number; button: number }
+    | { type: "mouseWheel"; mode: number; delta: number }
+    | { type: "keyDown"; code: string; key: string }
+    | { type: "keyUp"; code: string; key: string };
+
+/**
+ * A message from the page to the worker of a player.
+ *
+ * @internal
+ */
+export type ToWorkerMessage =
+    | {
+          type: "init";
+          publicPath: string;
+          config: BaseLoadOptions;
+          canvas: OffscreenCanvas;
+      }
+    | { type: "stream"; url: string; parameters: Record<string, string> }
+    | {
+          type: "loadData";
+          data: ArrayBuffer;
+          url: string;
+          parameters: Record<string, string>;
+      }
+    | { type: "input"; event: InputEvent }
+    | { type: "resize"; width: number; height: number; scaleFactor: number }
+    | { type: "play" }
+    | { type: "pause" }
+    | { type: "reset" }
+    | { type: "setVolume"; value: number }
+    | { type: "setPlaybackSpeed"; value: number }
+    | { type: "setFullscreen"; value: boolean }
+    | { type: "destroy" };
+
+type FromWorkerMessage =
+    | {
+          type: "ready";
+          value: {
+              rendererName: string | null;
+              rendererDebugInfo: string | null;
+          };
+      }
+    | { type: "metadata"; value: MovieMetadata }
+    | { type: "trace"; value: { message: string; movieUrl: string } }
+    | { type: "preventScrolling"; value: boolean }
+    | { type: "panic"; value: string };
+
+/**
+ * The callbacks of the player that owns a `WorkerInstance`.
+ *
+ * @internal
+ */
+export interface WorkerInstanceHost {
+    setMetadata(metadata: MovieMetadata): void;
+    panic(error: Error): void;
+}
+
+/**
+ * Whether players can run in a Web Worker in this browser.
+ *
+ * @returns True if canvases can be transferred to workers.
+ * @internal
+ */
+export function isWorkerSupported(): boolean {
+    return (
+        typeof Worker !== "undefined" &&
+        typeof HTMLCanvasElement.prototype.transferControlToOffscreen ===
+            "function"
+    );
+}
+
+/**
+ * A player running in a Web Worker.
+ *
+ * This mirrors the methods of `Ruffle` that `RufflePlayer` uses. The page keeps
+ * the canvas and forwards its input events and size to the worker, while state
+ * that the page reads synchronously is kept here.
+ *
+ * @internal
+ */
+export class WorkerInstance {
+    private readonly worker: Worker;
+    private readonly canvas: HTMLCanvasElement;
+    private readonly resizeObserver: ResizeObserver;
+    private readonly abortController = new AbortController();
+
+    private rendererName: string | null = null;
+    private rendererDebugInfo: string | null = null;
+    private playing = false;
+    private currentVolume = 1;
+    private currentPlaybackSpeed = 1;
+    private hasFocus = false;
+    private preventScrolling = false;
+    private traceObserver:
+        | ((message: string, movieUrl: string) => void)
+        | null = null;
+    private readonly traces: { movieUrl: string; message: string }[] = [];
+    private onReady: (() => void) | null = null;
+
+    private constructor(
+        container: HTMLElement,
+        private readonly host: WorkerInstanceHost,
+        config: BaseLoadOptions
+    ) {
+        // Workers have no document, so the base URL must be absolute.
+        const base = new URL(config.base ?? ".", document.baseURI).href;
+        __webpack_public_path__ = publicPath(config);
+
+        this.worker = new Worker(
+            new URL("./ruffle-worker.js", import.meta.url)
+        );
+        this.worker.addEventListener("message", (event) =>
+            this.onMessage(event.data)
+        );
+        this.worker.addEventListener("error", (event) =>
+            this.host.panic(new Error(event.message))
+        );
+
+        this.canvas = document.createElement("canvas");
+        this.canvas.style.touchAction = "none";
+        container.appendChild(this.canvas);
+        const offscreenCanvas = this.canvas.transferControlToOffscreen();
+        this.post(
+            {
+                type: "init",
+                publicPath: __webpack_public_path__,
+                config: { ...config, base },
+                canvas: offscreenCanvas,
+            },
+            [offscreenCanvas]
+        );
+
+        this.resizeObserver = new ResizeObserver(() => this.resize());
+        this.resizeObserver.observe(this.canvas);
+        this.resize();
+        this.addEventListeners();
+    }
+
+    /**
+     * Creates a player in a new worker, once its renderer is ready.
+     *
+     * @param container The element to add the canvas to.
+     * @param host The player that owns this instance.
+     * @param config The config of the player.
+     * @returns The new instance.
+     */
+    static create(
+        container: HTMLElement,
+        host: WorkerInstanceHost,
+        config: BaseLoadOptions
+    ): Promise<WorkerInstance> {
+        return new Promise((resolve, reject) => {
+            const instance = new WorkerInstance(
+                container,
+                {
+                    setMetadata: (metadata) => host.setMetadata(metadata),
+                    panic: (error) => {
+                        reject(error);
+                        host.panic(error);
+                    },
+                },
+                config
+            );
+            instance.onReady = () => resolve(instance);
+        });
+    }
+
+    private post(message: ToWorkerMessage, transfer: Transferable[] = []) {
+        this.worker.postMessage(message, transfer);
+    }
+
+    private onMessage(message: FromWorkerMessage): void {
+        switch (message.type) {
+            case "ready":
+                this.rendererName = message.value.rendererName;
+                this.rendererDebugInfo = message.value.rendererDebugInfo;
+                this.onReady?.();
+                break;
+            case "metadata":
+                this.host.setMetadata(message.value);
+                break;
+            case "trace":
+                this.traces.push(message.value);
+                if (this.traces.length > MAX_RECENT_TRACES) {
+                    this.traces.shift();
+                }
+                this.traceObserver?.(
+                    message.value.message,
+                    message.value.movieUrl
+                );
+                break;
+            case "preventScrolling":
+                this.preventScrolling = message.value;
+                break;
+            case "panic":
+                this.host.panic(new Error(message.value));
+                break;
+        }
+    }
+
+    private resize(): void {
+        // The viewport is scaled by DPI, bigger than CSS pixels.
+        const scaleFactor = window.devicePixelRatio;
+        this.post({
+            type: "resize",
+            width: Math.floor(this.canvas.clientWidth * scaleFactor),
+            height: Math.floor(this.canvas.clientHeight * scaleFactor),
+            scaleFactor,
+        });
+    }
+
+    private input(event: InputEvent): void {
+        this.post({ type: "input", event });
+    }
+
+    private addEventListeners(): void {
+        const options = { signal: this.abortController.signal };
+        const canvas = this.canvas;
+        const position = (event: PointerEvent) => ({
+            x: event.offsetX * window.devicePixelRatio,
+            y: event.offsetY * window.devicePixelRatio,
+        });
+
+        canvas.addEventListener(
+            "pointermove",
+            (event) => {
+                this.input({ type: "mouseMove", ...position(event) });
+                if (this.hasFocus) {
+                    event.preventDefault();
+                }
+            },
+            options
+        );
+        canvas.addEventListener(
+            "pointerenter",
+            () => this.input({ type: "mouseEnter" }),
+            options
+        );
+        canvas.addEventListener(
+            "pointerleave",
+            () => this.input({ type: "mouseLeave" }),
+            options
+        );
+        canvas.addEventListener(
+            "pointerdown",
+            (event) => {
+                canvas.setPointerCapture(event.pointerId);
+                // Fix issue with touch events on mobile devices
+                // when the game is base on move events to calculate.
+                if (
+                    event.pointerType === "touch" ||
+                    event.pointerType === "pen"
+                ) {
+                    this.input({ type: "mouseMove", ...position(event) });
+                }
+                this.input({
+                    type: "mouseDown",
+                    ...position(event),
+                    button: event.button,
+                });
+                event.preventDefault();
+            },
+            options
+        );
+        canvas.addEventListener(
+            "pointerup",
+            (event) => {
+                canvas.releasePointerCapture(event.pointerId);
+                this.input({
+                    type: "mouseUp",
+                    ...position(event),
+                    button: event.button,
+                });
+                if (this.hasFocus) {
+                    event.preventDefault();
+                }
+            },
+            options
+        );
+        // The worker answers asynchronously whether the movie handles the wheel,
+        // so the answer to the previous event is used.
+        canvas.addEventListener(
+            "wheel",
+            (event) => {
+                this.input({
+                    type: "mouseWheel",
+                    mode: event.deltaMode,
+                    delta: event.deltaY,
+                });
+                if (this.preventScrolling) {
+                    event.preventDefault();
+                }
+            },
+            { ...options, passive: false }
+        );
+
+        // If we actually clicked on the player, this will be reset to true
+        // after the event bubbles down to the player.
+        window.addEventListener(
+            "pointerdown",
+            () => (this.hasFocus = false),
+            { ...options, capture: true }
+        );
+        canvas.parentElement?.addEventListener(
+            "pointerdown",
+            () => {
+                this.hasFocus = true;
+                window.focus();
+            },
+            options
+        );
+        window.addEventListener(
+            "keydown",
+            (event) => {
+                if (this.hasFocus) {
+                    this.input({
+                        type: "keyDown",
+                        code: event.code,
+                        key: event.key,
+                    });
+                    event.preventDefault();
+                }
+            },
+            options
+        );
+        window.addEventListener(
+            "keyup",
+            (event) => {
+                if (this.hasFocus) {
+                    this.input({
+                        type: "keyUp",
+                        code: event.code,
+                        key: event.key,
+                    });
+                    event.preventDefault();
+                }
+            },
+            options
+        );
+    }
+
+    stream_from(url: string, parameters: Record<string, string>): void {
+        this.post({ type: "stream", url, parameters });
+    }
+
+    load_data(
+        data: Uint8Array,
+        parameters: Record<string, string>,
+        swfName: string
+    ): void {
+        // Like the main thread player, name the movie after the page's directory.
+        const pageUrl = new URL(window.location.href);
+        pageUrl.search = "";
+        pageUrl.hash = "";
+        const url = new URL(swfName, pageUrl).href;
+        const buffer = data.slice().buffer;
+        this.post({ type: "loadData", data: buffer, url, parameters }, [
+            buffer,
+        ]);
+    }
+
+    play(): void {
+        this.playing = true;
+        this.post({ type: "play" });
+    }
+
+    pause(): void {
+        this.playing = false;
+        this.post({ type: "pause" });
+    }
+
+    reset(): void {
+        this.post({ type: "reset" });
+    }
+
+    is_playing(): boolean {
+        return this.playing;
+    }
+
+    volume(): number {
+        return this.currentVolume;
+    }
+
+    set_volume(value: number): void {
+        this.currentVolume = value;
+        this.post({ type: "setVolume", value });
+    }
+
+    playback_speed(): number {
+        return this.currentPlaybackSpeed;
+    }
+
+    set_playback_speed(value: number): void {
+        this.currentPlaybackSpeed = value;
+        this.post({ type: "setPlaybackSpeed", value });
+    }
+
+    set_fullscreen(isFullscreen: boolean): void {
+        this.post({ type: "setFullscreen", value: isFullscreen });
+    }
+
+    renderer_name(): string | null {
+        return this.rendererName;
+    }
+
+    renderer_debug_info(): string | null {
+        return this.rendererDebugInfo;
+    }
+
+    set_trace_observer(
+        observer: ((message: string, movieUrl: string) => void) | null
+    ): void {
+        this.traceObserver = observer;
+    }
+
+    recent_traces(): { movieUrl: string; message: string }[] {
+        return [...this.traces];
+    }
+
+    destroy(): void {
+        this.abortController.abort();
+        this.resizeObserver.disconnect();
+        this.canvas.remove();
+        this.post({ type: "destroy" });
+    }
+
+    // The following can't be answered synchronously by the worker, and aren't supported.
+
+    movie_info(): MovieInfo | null {
+        return null;
+    }
+
+    audio_context(): AudioContext | undefined {
+        return undefined;
+    }
+
+    domain_settings(): null {
+        return null;
+    }
+
+    set_domain_settings(_settings: unknown): void {
+        // The settings panel is never shown.
+    }
+
+    prepare_context_menu(): [] {
+        return [];
+    }
+
+    run_context_menu_callback(_index: number): void {
+        // There are no custom menu items.
+    }
+
+    clear_custom_menu_items(): void {
+        // There are no custom menu items.
+    }
+
+    call_exposed_callback(_name: string, _args: unknown[]): undefined {
+        return undefined;
+    }
+
+    set_language(_language: string): void {
+        // The player shows no text of its own.
+    }
+
+    add_locale(_language: string, _source: string): void {
+        // The player shows no text of its own.
+    }
+}
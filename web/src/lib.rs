@@ -6,9 +6,11 @@ mod log_adapter;
 mod navigator;
 mod storage;
 mod ui;
+mod worker;
 
 use generational_arena::{Arena, Index};
 use js_sys::{Array, Error as JsError, Function, Object, Promise, Uint8Array};
+use ruffle_core::backend::log::TraceBuffer;
 use ruffle_core::backend::navigator::OpenURLMode;
 use ruffle_core::compatibility_rules::CompatibilityRules;
 use ruffle_core::config::{Letterbox, NetworkingAccessMode};
@@ -17,6 +19,8 @@ use ruffle_core::events::{KeyCode, MouseButton, MouseWheelDelta};
 use ruffle_core::external::{
     ExternalInterfaceMethod, ExternalInterfaceProvider, Value as ExternalValue, Value,
 };
+use ruffle_core::i18n::{LanguageIdentifier, FALLBACK_LANGUAGE};
+use ruffle_core::settings::DevicePermission;
 use ruffle_core::tag_utils::SwfMovie;
 use ruffle_core::{
     Color, Player, PlayerBuilder, PlayerEvent, SandboxType, StageScaleMode, StaticCallstack,
@@ -27,6 +31,7 @@ use ruffle_video_software::backend::SoftwareVideoBackend;
 use ruffle_web_common::JsResult;
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
+use std::rc::Rc;
 use std::str::FromStr;
 use std::sync::Once;
 use std::sync::{Arc, Mutex};
@@ -44,6 +49,9 @@ use web_sys::{
 
 static RUFFLE_GLOBAL_PANIC: Once = Once::new();
 
+/// How many traces are kept for `recent_traces`.
+const MAX_RECENT_TRACES: usize = 500;
+
 thread_local! {
     /// We store the actual instances of the ruffle core in a static pool.
     /// This gives us a clear boundary between the JS side and Rust side, avoiding
@@ -81,10 +89,14 @@ struct RuffleInstance {
     unload_callback: Option<Closure<dyn FnMut(Event)>>,
     has_focus: bool,
     trace_observer: Arc<RefCell<JsValue>>,
+    traces: TraceBuffer,
+    language: Rc<RefCell<LanguageIdentifier>>,
     log_subscriber: Arc<Layered<WASMLayer, Registry>>,
 }
 
-#[wasm_bindgen(raw_module = "./ruffle-player")]
+// This isn't imported from "./ruffle-player", which can't be loaded in a Web Worker:
+// the player is only ever given to Ruffle by the page, and its methods are structural.
+#[wasm_bindgen]
 extern "C" {
     #[wasm_bindgen(extends = EventTarget)]
     #[derive(Clone)]
@@ -119,6 +131,12 @@ extern "C" {
 
     #[wasm_bindgen(method, js_name = "openVirtualKeyboard")]
     fn open_virtual_keyboard(this: &JavascriptPlayer);
+
+    #[wasm_bindgen(method, js_name = "displaySettingsPanel")]
+    fn display_settings_panel(this: &JavascriptPlayer, panel: &str);
+
+    #[wasm_bindgen(method, js_name = "displayScriptTimeoutPrompt")]
+    fn display_script_timeout_prompt(this: &JavascriptPlayer, message: &str) -> bool;
 }
 
 struct JavascriptInterface {
@@ -299,6 +317,34 @@ struct MovieMetadata {
     uncompressed_len: u32,
 }
 
+/// The properties of the loaded SWF file, including the ones only known once it's fully loaded.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct MovieInfo {
+    width: f64,
+    height: f64,
+    frame_rate: f32,
+    num_frames: u16,
+    swf_version: u8,
+    is_action_script_3: bool,
+    metadata: Option<String>,
+    /// The estimated duration in seconds.
+    duration: Option<f64>,
+}
+
+/// The settings of the domain of the loaded SWF file, as edited in the settings panel.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PanelSettings {
+    domain: String,
+    /// How much data the domain may store, in bytes, or `None` for no limit.
+    storage_quota: Option<f64>,
+    /// Whether the camera may be used: `"ask"`, `"allow"` or `"deny"`.
+    camera: String,
+    /// Whether the microphone may be used: `"ask"`, `"allow"` or `"deny"`.
+    microphone: String,
+}
+
 /// An opaque handle to a `RuffleInstance` inside the pool.
 ///
 /// This type is exported to JS, and is used to interact with the library.
@@ -390,6 +436,12 @@ impl Ruffle {
         });
     }
 
+    pub fn reset(&mut self) {
+        let _ = self.with_core_mut(|core| {
+            core.reset();
+        });
+    }
+
     pub fn is_playing(&mut self) -> bool {
         self.with_core(|core| core.is_playing()).unwrap_or_default()
     }
@@ -402,11 +454,98 @@ impl Ruffle {
         let _ = self.with_core_mut(|core| core.set_volume(value));
     }
 
+    pub fn playback_speed(&self) -> f64 {
+        self.with_core(|core| core.playback_speed()).unwrap_or(1.0)
+    }
+
+    pub fn set_playback_speed(&mut self, value: f64) {
+        let _ = self.with_core_mut(|core| core.set_playback_speed(value));
+    }
+
+    /// Changes the language that the text of the player is shown in.
+    pub fn set_language(&self, language: &str) -> Result<(), JsValue> {
+        let language: LanguageIdentifier = language
+            .parse()
+            .map_err(|e| JsValue::from_str(&format!("Invalid language {language}: {e}")))?;
+        let _ = self.with_instance(|instance| {
+            *instance.language.borrow_mut() = language;
+        });
+        Ok(())
+    }
+
+    /// Adds the translations of a Fluent file written for `language`.
+    pub fn add_locale(&self, language: &str, source: &str) -> Result<(), JsValue> {
+        let language: LanguageIdentifier = language
+            .parse()
+            .map_err(|e| JsValue::from_str(&format!("Invalid language {language}: {e}")))?;
+        self.with_core_mut(|core| core.localizations_mut().add_resource(language, source))
+            .map_err(|e| JsValue::from_str(&e.to_string()))?
+            .map_err(|e| JsValue::from_str(&e))
+    }
+
+    /// Shows or hides an overlay with the frame rate and frame time breakdown.
+    pub fn set_show_perf_hud(&mut self, show: bool) {
+        let _ = self.with_core_mut(|core| core.set_show_perf_hud(show));
+    }
+
     pub fn renderer_debug_info(&self) -> JsValue {
         self.with_core(|core| JsValue::from_str(&core.renderer().debug_info()))
             .unwrap_or(JsValue::NULL)
     }
 
+    pub fn movie_info(&self) -> JsValue {
+        self.with_core(|core| {
+            let info = core.movie_info();
+            let info = MovieInfo {
+                width: info.width,
+                height: info.height,
+                frame_rate: info.frame_rate,
+                num_frames: info.num_frames,
+                swf_version: info.swf_version,
+                is_action_script_3: info.is_action_script_3,
+                duration: info.duration().map(|duration| duration.as_secs_f64()),
+                metadata: info.metadata,
+            };
+            serde_wasm_bindgen::to_value(&info).unwrap_or(JsValue::NULL)
+        })
+        .unwrap_or(JsValue::NULL)
+    }
+
+    pub fn domain_settings(&self) -> JsValue {
+        self.with_core(|core| {
+            let settings = core.domain_settings();
+            let settings = PanelSettings {
+                domain: core.settings_domain(),
+                storage_quota: settings.storage_quota.map(|quota| quota as f64),
+                camera: settings.camera.name().to_owned(),
+                microphone: settings.microphone.name().to_owned(),
+            };
+            serde_wasm_bindgen::to_value(&settings).unwrap_or(JsValue::NULL)
+        })
+        .unwrap_or(JsValue::NULL)
+    }
+
+    pub fn set_domain_settings(&mut self, settings: JsValue) {
+        let settings: PanelSettings = match serde_wasm_bindgen::from_value(settings) {
+            Ok(settings) => settings,
+            Err(e) => {
+                tracing::error!("Invalid settings: {}", e);
+                return;
+            }
+        };
+        let _ = self.with_core_mut(|core| {
+            let mut domain_settings = core.domain_settings();
+            domain_settings.storage_quota = settings.storage_quota.map(|quota| quota as u64);
+            if let Some(camera) = DevicePermission::from_name(&settings.camera) {
+                domain_settings.camera = camera;
+            }
+            if let Some(microphone) = DevicePermission::from_name(&settings.microphone) {
+                domain_settings.microphone = microphone;
+            }
+            core.set_domain_settings(&domain_settings);
+        });
+    }
+
     pub fn renderer_name(&self) -> JsValue {
         self.with_core(|core| JsValue::from_str(core.renderer().name()))
             .unwrap_or(JsValue::NULL)
@@ -584,6 +723,23 @@ impl Ruffle {
         });
     }
 
+    /// Returns the latest traces, as `{ movieUrl, message }` objects.
+    pub fn recent_traces(&self) -> Array {
+        let array = Array::new();
+        let _ = self.with_instance(|instance| {
+            for entry in instance.traces.entries() {
+                let properties = Array::of2(
+                    &Array::of2(&"movieUrl".into(), &JsValue::from_str(&entry.movie_url)),
+                    &Array::of2(&"message".into(), &JsValue::from_str(&entry.message)),
+                );
+                if let Ok(object) = Object::from_entries(&properties) {
+                    array.push(&object);
+                }
+            }
+        });
+        array
+    }
+
     /// Returns the web AudioContext used by this player.
     /// Returns `None` if the audio backend does not use Web Audio.
     pub fn audio_context(&self) -> Option<web_sys::AudioContext> {
@@ -678,9 +834,23 @@ impl Ruffle {
         };
 
         let trace_observer = Arc::new(RefCell::new(JsValue::UNDEFINED));
+        let traces = TraceBuffer::new(MAX_RECENT_TRACES);
+        let language = window
+            .navigator()
+            .language()
+            .and_then(|language| language.parse().ok())
+            .unwrap_or(FALLBACK_LANGUAGE);
+        let language = Rc::new(RefCell::new(language));
         let core = builder
-            .with_log(log_adapter::WebLogBackend::new(trace_observer.clone()))
-            .with_ui(ui::WebUiBackend::new(js_player.clone(), &canvas))
+            .with_log(log_adapter::WebLogBackend::new(
+                trace_observer.clone(),
+                traces.clone(),
+            ))
+            .with_ui(ui::WebUiBackend::new(
+                js_player.clone(),
+                &canvas,
+                language.clone(),
+            ))
             .with_video(SoftwareVideoBackend::new())
             .with_letterbox(config.letterbox)
             .with_max_execution_duration(config.max_execution_duration)
@@ -694,7 +864,7 @@ impl Ruffle {
             .with_quality(
                 config
                     .quality
-                    .and_then(|q| StageQuality::from_str(&q).ok())
+                    .and_then(|q| parse_embed_quality(&q))
                     .unwrap_or(default_quality),
             )
             .with_scale_mode(
@@ -750,6 +920,8 @@ impl Ruffle {
             timestamp: None,
             has_focus: false,
             trace_observer,
+            traces,
+            language,
             log_subscriber,
         };
 
@@ -1472,6 +1644,17 @@ fn external_to_js_value(external: ExternalValue) -> JsValue {
     }
 }
 
+/// Parses the `quality` embed parameter, which also accepts the `autolow` and `autohigh`
+/// values of Flash Player. Ruffle doesn't adjust the quality at runtime, so these behave
+/// like their starting quality.
+fn parse_embed_quality(quality: &str) -> Option<StageQuality> {
+    match quality.to_ascii_lowercase().as_str() {
+        "autolow" => Some(StageQuality::Low),
+        "autohigh" => Some(StageQuality::High),
+        quality => StageQuality::from_str(quality).ok(),
+    }
+}
+
 async fn create_renderer(
     builder: PlayerBuilder,
     document: &web_sys::Document,
@@ -1485,7 +1668,7 @@ async fn create_renderer(
     )))]
     std::compile_error!("You must enable one of the render backend features (e.g., webgl).");
 
-    let _is_transparent = config.wmode.as_deref() == Some("transparent");
+    let is_transparent = config.wmode.as_deref() == Some("transparent");
 
     let mut renderer_list = vec!["webgpu", "wgpu-webgl", "webgl", "canvas"];
     if let Some(preferred_renderer) = &config.preferred_renderer {
@@ -1519,8 +1702,11 @@ async fn create_renderer(
                         .dyn_into()
                         .map_err(|_| "Expected HtmlCanvasElement")?;
 
-                    match ruffle_render_wgpu::backend::WgpuRenderBackend::for_canvas(canvas.clone())
-                        .await
+                    match ruffle_render_wgpu::backend::WgpuRenderBackend::for_canvas(
+                        canvas.clone(),
+                        is_transparent,
+                    )
+                    .await
                     {
                         Ok(renderer) => {
                             return Ok((builder.with_renderer(renderer), canvas));
@@ -1540,8 +1726,11 @@ async fn create_renderer(
                     .dyn_into()
                     .map_err(|_| "Expected HtmlCanvasElement")?;
 
-                match ruffle_render_wgpu::backend::WgpuRenderBackend::for_canvas(canvas.clone())
-                    .await
+                match ruffle_render_wgpu::backend::WgpuRenderBackend::for_canvas(
+                    canvas.clone(),
+                    is_transparent,
+                )
+                .await
                 {
                     Ok(renderer) => {
                         return Ok((builder.with_renderer(renderer), canvas));
@@ -1559,7 +1748,7 @@ async fn create_renderer(
                     .into_js_result()?
                     .dyn_into()
                     .map_err(|_| "Expected HtmlCanvasElement")?;
-                match ruffle_render_webgl::WebGlRenderBackend::new(&canvas, _is_transparent) {
+                match ruffle_render_webgl::WebGlRenderBackend::new(&canvas, is_transparent) {
                     Ok(renderer) => {
                         return Ok((builder.with_renderer(renderer), canvas));
                     }
@@ -1574,7 +1763,7 @@ async fn create_renderer(
                     .into_js_result()?
                     .dyn_into()
                     .map_err(|_| "Expected HtmlCanvasElement")?;
-                match ruffle_render_canvas::WebCanvasRenderBackend::new(&canvas, _is_transparent) {
+                match ruffle_render_canvas::WebCanvasRenderBackend::new(&canvas, is_transparent) {
                     Ok(renderer) => {
                         return Ok((builder.with_renderer(renderer), canvas));
                     }
@@ -1,24 +1,37 @@
 use js_sys::Function;
-use ruffle_core::backend::log::LogBackend;
+use ruffle_core::backend::log::{LogBackend, TraceBuffer};
 use std::cell::RefCell;
 use std::sync::Arc;
 use wasm_bindgen::{JsCast, JsValue};
 
 pub struct WebLogBackend {
     trace_observer: Arc<RefCell<JsValue>>,
+    traces: TraceBuffer,
 }
 
 impl WebLogBackend {
-    pub fn new(trace_observer: Arc<RefCell<JsValue>>) -> Self {
-        Self { trace_observer }
+    pub fn new(trace_observer: Arc<RefCell<JsValue>>, traces: TraceBuffer) -> Self {
+        Self {
+            trace_observer,
+            traces,
+        }
     }
 }
 
 impl LogBackend for WebLogBackend {
     fn avm_trace(&self, message: &str) {
+        self.movie_trace("", message);
+    }
+
+    fn movie_trace(&self, movie_url: &str, message: &str) {
         tracing::info!(target: "avm_trace", "{}", message);
+        self.traces.push(movie_url, message);
         if let Some(function) = self.trace_observer.borrow().dyn_ref::<Function>() {
-            let _ = function.call1(function, &JsValue::from_str(message));
+            let _ = function.call2(
+                function,
+                &JsValue::from_str(message),
+                &JsValue::from_str(movie_url),
+            );
         }
     }
 }
@@ -16,7 +16,7 @@ use wasm_bindgen::JsCast;
 use wasm_bindgen_futures::{spawn_local, JsFuture};
 use web_sys::{
     window, Blob, BlobPropertyBag, HtmlFormElement, HtmlInputElement, Request as WebRequest,
-    RequestInit, Response as WebResponse,
+    RequestInit, Response as WebResponse, WorkerGlobalScope,
 };
 
 pub struct WebNavigatorBackend {
@@ -37,15 +37,23 @@ impl WebNavigatorBackend {
         log_subscriber: Arc<Layered<WASMLayer, Registry>>,
         open_url_mode: OpenURLMode,
     ) -> Self {
-        let window = web_sys::window().expect("window()");
+        // In a Web Worker, there is no window and the location is the worker script's.
+        let window = web_sys::window();
+        let protocol = match &window {
+            Some(window) => window.location().protocol().expect("protocol()"),
+            None => js_sys::global()
+                .unchecked_into::<WorkerGlobalScope>()
+                .location()
+                .protocol(),
+        };
 
         // Upgrade to HTTPS takes effect if the current page is hosted on HTTPS.
-        let upgrade_to_https =
-            upgrade_to_https && window.location().protocol().expect("protocol()") == "https:";
+        let upgrade_to_https = upgrade_to_https && protocol == "https:";
 
         // Retrieve and parse `document.baseURI`.
+        // Workers have no document, so the page must give them an absolute base URL.
         let document_base_uri = || {
-            let document = window.document().expect("document()");
+            let document = window.as_ref()?.document()?;
             if let Ok(Some(base_uri)) = document.base_uri() {
                 return Url::parse(&base_uri).ok();
             }
@@ -91,6 +99,16 @@ impl WebNavigatorBackend {
     }
 }
 
+/// Fetches a request from the window, or from the worker global scope in a Web Worker.
+fn fetch_with_request(request: &WebRequest) -> js_sys::Promise {
+    match window() {
+        Some(window) => window.fetch_with_request(request),
+        None => js_sys::global()
+            .unchecked_into::<WorkerGlobalScope>()
+            .fetch_with_request(request),
+    }
+}
+
 impl NavigatorBackend for WebNavigatorBackend {
     fn navigate_to_url(
         &self,
@@ -122,7 +140,10 @@ impl NavigatorBackend for WebNavigatorBackend {
             false
         };
 
-        let window = window().expect("window()");
+        let Some(window) = window() else {
+            tracing::warn!("SWF tried to open a URL, but there is no window to open it in");
+            return;
+        };
 
         if !js_call {
             if self.open_url_mode == OpenURLMode::Confirm {
@@ -193,6 +214,10 @@ impl NavigatorBackend for WebNavigatorBackend {
         };
     }
 
+    fn absolute_url(&self, url: &str) -> Option<Url> {
+        Url::parse(&self.resolve_url(url)).ok()
+    }
+
     fn fetch(&self, request: Request) -> OwnedFuture<Response, Error> {
         let url = self.resolve_url(request.url()).into_owned();
 
@@ -216,8 +241,7 @@ impl NavigatorBackend for WebNavigatorBackend {
             let request = WebRequest::new_with_str_and_init(&url, &init)
                 .map_err(|_| Error::FetchError(format!("Unable to create request for {url}")))?;
 
-            let window = web_sys::window().expect("window()");
-            let fetchval = JsFuture::from(window.fetch_with_request(&request))
+            let fetchval = JsFuture::from(fetch_with_request(&request))
                 .await
                 .map_err(|_| Error::FetchError("Got JS error".to_string()))?;
 
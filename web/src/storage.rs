@@ -33,4 +33,14 @@ impl StorageBackend for LocalStorageBackend {
     fn remove_key(&mut self, name: &str) {
         let _ = self.storage.delete(name);
     }
+
+    fn get_disk_usage(&self, prefix: &str) -> u64 {
+        let length = self.storage.length().unwrap_or_default();
+        (0..length)
+            .filter_map(|index| self.storage.key(index).ok().flatten())
+            .filter(|name| name.starts_with(prefix))
+            .filter_map(|name| self.get(&name))
+            .map(|data| data.len() as u64)
+            .sum()
+    }
 }
@@ -1,7 +1,10 @@
 use super::JavascriptPlayer;
-use ruffle_core::backend::ui::{FullscreenError, MouseCursor, UiBackend};
+use ruffle_core::backend::ui::{FullscreenError, MouseCursor, SettingsPanel, UiBackend};
+use ruffle_core::i18n::LanguageIdentifier;
 use ruffle_web_common::JsResult;
 use std::borrow::Cow;
+use std::cell::RefCell;
+use std::rc::Rc;
 use wasm_bindgen::JsCast;
 use web_sys::{HtmlCanvasElement, HtmlDocument, HtmlTextAreaElement};
 
@@ -11,15 +14,23 @@ pub struct WebUiBackend {
     canvas: HtmlCanvasElement,
     cursor_visible: bool,
     cursor: MouseCursor,
+
+    /// The language of the user, which the page can change at any time.
+    language: Rc<RefCell<LanguageIdentifier>>,
 }
 
 impl WebUiBackend {
-    pub fn new(js_player: JavascriptPlayer, canvas: &HtmlCanvasElement) -> Self {
+    pub fn new(
+        js_player: JavascriptPlayer,
+        canvas: &HtmlCanvasElement,
+        language: Rc<RefCell<LanguageIdentifier>>,
+    ) -> Self {
         Self {
             js_player,
             canvas: canvas.clone(),
             cursor_visible: true,
             cursor: MouseCursor::Arrow,
+            language,
         }
     }
 
@@ -116,4 +127,21 @@ impl UiBackend for WebUiBackend {
     fn open_virtual_keyboard(&self) {
         self.js_player.open_virtual_keyboard()
     }
+
+    fn exit(&mut self, code: i32) {
+        // A page can't be closed by its content.
+        tracing::warn!("Ignoring request to exit with code {}", code);
+    }
+
+    fn display_settings_panel(&mut self, panel: SettingsPanel) {
+        self.js_player.display_settings_panel(panel.name());
+    }
+
+    fn display_script_timeout_prompt(&mut self, message: &str) -> bool {
+        self.js_player.display_script_timeout_prompt(message)
+    }
+
+    fn language(&self) -> LanguageIdentifier {
+        self.language.borrow().clone()
+    }
 }
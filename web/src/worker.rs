@@ -0,0 +1,485 @@
+//! Runs a player inside a Web Worker, drawing to an `OffscreenCanvas`.
+//!
+//! The page keeps the DOM: it transfers the control of its canvas to the worker,
+//! forwards the input events of the canvas and reports when it's resized. Workers have
+//! no access to the DOM or to Web Audio, so these players have no audio and no
+//! ExternalInterface, can't open URLs, and keep their shared objects in memory.
+
+use crate::{
+    log_adapter, navigator, parse_embed_quality, parse_movie_parameters, set_panic_handler,
+    web_key_to_codepoint, web_to_ruffle_key_code, Config, MovieMetadata, MAX_RECENT_TRACES,
+    RUFFLE_GLOBAL_PANIC,
+};
+use js_sys::{Object, Promise, Reflect, Uint8Array};
+use ruffle_core::backend::log::TraceBuffer;
+use ruffle_core::compatibility_rules::CompatibilityRules;
+use ruffle_core::events::{MouseButton, MouseWheelDelta};
+use ruffle_core::tag_utils::SwfMovie;
+use ruffle_core::{
+    Player, PlayerBuilder, PlayerEvent, SandboxType, StageScaleMode, ViewportDimensions,
+};
+use ruffle_render::quality::StageQuality;
+use ruffle_video_software::backend::SoftwareVideoBackend;
+use serde::Deserialize;
+use std::cell::RefCell;
+use std::error::Error;
+use std::rc::{Rc, Weak};
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use tracing_subscriber::layer::{Layered, SubscriberExt};
+use tracing_subscriber::registry::Registry;
+use tracing_wasm::{ConsoleConfig, WASMLayer, WASMLayerConfigBuilder};
+use wasm_bindgen::prelude::*;
+use web_sys::{DedicatedWorkerGlobalScope, OffscreenCanvas, WheelEvent};
+
+/// An input event of the page's canvas, forwarded to the worker.
+/// Coordinates are in device pixels.
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+enum InputEvent {
+    MouseEnter,
+    MouseLeave,
+    MouseMove {
+        x: f64,
+        y: f64,
+    },
+    MouseDown {
+        x: f64,
+        y: f64,
+        button: i16,
+    },
+    MouseUp {
+        x: f64,
+        y: f64,
+        button: i16,
+    },
+    /// `mode` is the `deltaMode` of the wheel event.
+    MouseWheel {
+        mode: u32,
+        delta: f64,
+    },
+    KeyDown {
+        code: String,
+        key: String,
+    },
+    KeyUp {
+        code: String,
+        key: String,
+    },
+}
+
+fn mouse_button(button: i16) -> MouseButton {
+    match button {
+        0 => MouseButton::Left,
+        1 => MouseButton::Middle,
+        2 => MouseButton::Right,
+        _ => MouseButton::Unknown,
+    }
+}
+
+/// A player running in the worker that created it.
+#[wasm_bindgen]
+#[derive(Clone)]
+pub struct RuffleWorker(Rc<RefCell<WorkerInstance>>);
+
+struct WorkerInstance {
+    core: Arc<Mutex<Player>>,
+    canvas: OffscreenCanvas,
+    /// The viewport to apply before the next render, set when the page is resized.
+    new_dimensions: Option<ViewportDimensions>,
+    timestamp: Option<f64>,
+    /// The `setTimeout` callback ticking the player.
+    tick_callback: Option<Closure<dyn FnMut()>>,
+    timeout_id: Option<i32>,
+    trace_observer: Arc<RefCell<JsValue>>,
+    log_subscriber: Arc<Layered<WASMLayer, Registry>>,
+}
+
+fn global() -> DedicatedWorkerGlobalScope {
+    js_sys::global().unchecked_into()
+}
+
+/// Sends a message of the given type to the page.
+fn post_message(message_type: &str, value: &JsValue) {
+    let message = Object::new();
+    let _ = Reflect::set(&message, &"type".into(), &message_type.into());
+    let _ = Reflect::set(&message, &"value".into(), value);
+    if let Err(e) = global().post_message(&message) {
+        tracing::warn!("Couldn't post a message to the page: {:?}", e);
+    }
+}
+
+#[wasm_bindgen]
+impl RuffleWorker {
+    /// Creates a player drawing to a canvas whose control was transferred to this worker.
+    #[allow(clippy::new_ret_no_self)]
+    #[wasm_bindgen(constructor)]
+    pub fn new(canvas: OffscreenCanvas, config: JsValue) -> Promise {
+        wasm_bindgen_futures::future_to_promise(async move {
+            let config: Config = serde_wasm_bindgen::from_value(config)
+                .map_err(|e| format!("Error parsing config: {e}"))?;
+
+            if RUFFLE_GLOBAL_PANIC.is_completed() {
+                return Err("Ruffle is panicking!".into());
+            }
+            set_panic_handler();
+
+            let worker = RuffleWorker::new_internal(canvas, config)
+                .await
+                .map_err(|err| JsValue::from(format!("Error creating player: {}", err)))?;
+            Ok(JsValue::from(worker))
+        })
+    }
+
+    /// Streams a movie from a URL, which must be absolute.
+    pub fn stream_from(&self, movie_url: String, parameters: JsValue) {
+        let parameters = parse_movie_parameters(&parameters);
+        let _ = self.with_core_mut(|core| {
+            core.fetch_root_movie(movie_url, parameters, Box::new(Self::on_metadata));
+        });
+    }
+
+    /// Plays a movie from its data. `movie_url` must be absolute, as the worker
+    /// doesn't know the URL of the page.
+    pub fn load_data(
+        &self,
+        swf_data: Uint8Array,
+        parameters: JsValue,
+        movie_url: String,
+    ) -> Result<(), JsValue> {
+        let mut movie = SwfMovie::from_data(&swf_data.to_vec(), movie_url, None)
+            .map_err(|e| format!("Error loading movie: {e}"))?;
+        movie.append_parameters(parse_movie_parameters(&parameters));
+        Self::on_metadata(movie.header());
+        let _ = self.with_core_mut(move |core| core.set_root_movie(movie));
+        Ok(())
+    }
+
+    pub fn play(&self) {
+        let _ = self.with_core_mut(|core| core.set_is_playing(true));
+    }
+
+    pub fn pause(&self) {
+        let _ = self.with_core_mut(|core| core.set_is_playing(false));
+    }
+
+    pub fn reset(&self) {
+        let _ = self.with_core_mut(|core| core.reset());
+    }
+
+    pub fn set_volume(&self, value: f32) {
+        let _ = self.with_core_mut(|core| core.set_volume(value));
+    }
+
+    pub fn set_playback_speed(&self, value: f64) {
+        let _ = self.with_core_mut(|core| core.set_playback_speed(value));
+    }
+
+    pub fn set_fullscreen(&self, is_fullscreen: bool) {
+        let _ = self.with_core_mut(|core| core.set_fullscreen(is_fullscreen));
+    }
+
+    pub fn renderer_name(&self) -> JsValue {
+        self.with_core_mut(|core| JsValue::from_str(core.renderer().name()))
+            .unwrap_or(JsValue::NULL)
+    }
+
+    pub fn renderer_debug_info(&self) -> JsValue {
+        self.with_core_mut(|core| JsValue::from_str(&core.renderer().debug_info()))
+            .unwrap_or(JsValue::NULL)
+    }
+
+    pub fn set_trace_observer(&self, observer: JsValue) {
+        *self.0.borrow().trace_observer.borrow_mut() = observer;
+    }
+
+    /// Handles an input event forwarded by the page.
+    ///
+    /// Returns whether the page should prevent the default action of wheel events.
+    pub fn handle_input(&self, event: JsValue) -> Result<bool, JsValue> {
+        let event: InputEvent = serde_wasm_bindgen::from_value(event)
+            .map_err(|e| format!("Invalid input event: {e}"))?;
+        Ok(self
+            .with_core_mut(|core| {
+                match event {
+                    InputEvent::MouseEnter => core.set_mouse_in_stage(true),
+                    InputEvent::MouseLeave => {
+                        core.set_mouse_in_stage(false);
+                        core.handle_event(PlayerEvent::MouseLeave);
+                    }
+                    InputEvent::MouseMove { x, y } => {
+                        core.handle_event(PlayerEvent::MouseMove { x, y });
+                    }
+                    InputEvent::MouseDown { x, y, button } => {
+                        core.handle_event(PlayerEvent::MouseDown {
+                            x,
+                            y,
+                            button: mouse_button(button),
+                        });
+                    }
+                    InputEvent::MouseUp { x, y, button } => {
+                        core.handle_event(PlayerEvent::MouseUp {
+                            x,
+                            y,
+                            button: mouse_button(button),
+                        });
+                    }
+                    InputEvent::MouseWheel { mode, delta } => {
+                        let delta = match mode {
+                            WheelEvent::DOM_DELTA_LINE => MouseWheelDelta::Lines(-delta),
+                            WheelEvent::DOM_DELTA_PIXEL => MouseWheelDelta::Pixels(-delta),
+                            _ => return false,
+                        };
+                        core.handle_event(PlayerEvent::MouseWheel { delta });
+                        return core.should_prevent_scrolling();
+                    }
+                    InputEvent::KeyDown { code, key } => {
+                        let key_code = web_to_ruffle_key_code(&code);
+                        let key_char = web_key_to_codepoint(&key);
+                        core.handle_event(PlayerEvent::KeyDown { key_code, key_char });
+                        if let Some(codepoint) = key_char {
+                            core.handle_event(PlayerEvent::TextInput { codepoint });
+                        }
+                    }
+                    InputEvent::KeyUp { code, key } => {
+                        let key_code = web_to_ruffle_key_code(&code);
+                        let key_char = web_key_to_codepoint(&key);
+                        core.handle_event(PlayerEvent::KeyUp { key_code, key_char });
+                    }
+                }
+                false
+            })
+            .unwrap_or_default())
+    }
+
+    /// Resizes the canvas, in device pixels, before the next render.
+    pub fn resize(&self, width: u32, height: u32, scale_factor: f64) {
+        self.0.borrow_mut().new_dimensions = Some(ViewportDimensions {
+            width,
+            height,
+            scale_factor,
+        });
+    }
+
+    pub fn destroy(&self) {
+        let mut instance = self.0.borrow_mut();
+        if let Some(id) = instance.timeout_id.take() {
+            global().clear_timeout_with_handle(id);
+        }
+        // Dropping the callback stops the player, which only the callback kept alive.
+        instance.tick_callback = None;
+        if let Ok(mut core) = instance.core.try_lock() {
+            core.flush_shared_objects();
+        }
+    }
+}
+
+impl RuffleWorker {
+    async fn new_internal(
+        canvas: OffscreenCanvas,
+        config: Config,
+    ) -> Result<RuffleWorker, Box<dyn Error>> {
+        let _ = tracing_log::LogTracer::builder()
+            .ignore_crate("wgpu_hal")
+            .ignore_crate("wgpu_core")
+            .init();
+
+        let log_subscriber = Arc::new(
+            Registry::default().with(WASMLayer::new(
+                WASMLayerConfigBuilder::new()
+                    .set_report_logs_in_timings(cfg!(feature = "profiling"))
+                    .set_console_config(if cfg!(feature = "avm_debug") {
+                        ConsoleConfig::ReportWithConsoleColor
+                    } else {
+                        ConsoleConfig::NoReporting
+                    })
+                    .set_max_level(config.log_level)
+                    .build(),
+            )),
+        );
+        let _subscriber = tracing::subscriber::set_default(log_subscriber.clone());
+
+        let is_transparent = config.wmode.as_deref() == Some("transparent");
+        let builder = create_renderer(PlayerBuilder::new(), &canvas, is_transparent).await?;
+
+        // The page must resolve the base URL, as workers have no document.
+        let builder = builder.with_navigator(navigator::WebNavigatorBackend::new(
+            config.allow_script_access,
+            config.allow_networking,
+            config.upgrade_to_https,
+            config.base_url,
+            log_subscriber.clone(),
+            config.open_url_mode,
+        ));
+
+        let trace_observer = Arc::new(RefCell::new(JsValue::UNDEFINED));
+        let core = builder
+            .with_log(log_adapter::WebLogBackend::new(
+                trace_observer.clone(),
+                TraceBuffer::new(MAX_RECENT_TRACES),
+            ))
+            .with_video(SoftwareVideoBackend::new())
+            .with_letterbox(config.letterbox)
+            .with_max_execution_duration(config.max_execution_duration)
+            .with_warn_on_unsupported_content(config.warn_on_unsupported_content)
+            .with_player_version(config.player_version)
+            .with_compatibility_rules(if config.compatibility_rules {
+                CompatibilityRules::default()
+            } else {
+                CompatibilityRules::empty()
+            })
+            .with_quality(
+                config
+                    .quality
+                    .and_then(|q| parse_embed_quality(&q))
+                    .unwrap_or(StageQuality::High),
+            )
+            .with_scale_mode(
+                config
+                    .scale
+                    .and_then(|s| StageScaleMode::from_str(&s).ok())
+                    .unwrap_or(StageScaleMode::ShowAll),
+                config.force_scale,
+            )
+            .with_frame_rate(config.frame_rate)
+            .with_sandbox_type(SandboxType::Remote)
+            .build();
+
+        if let Ok(mut core) = core.try_lock() {
+            core.set_background_color(config.background_color);
+            core.set_show_menu(config.show_menu);
+            core.set_stage_align(config.salign.as_deref().unwrap_or(""));
+            core.set_window_mode(config.wmode.as_deref().unwrap_or("window"));
+        }
+
+        let worker = RuffleWorker(Rc::new(RefCell::new(WorkerInstance {
+            core,
+            canvas,
+            new_dimensions: None,
+            timestamp: None,
+            tick_callback: None,
+            timeout_id: None,
+            trace_observer,
+            log_subscriber,
+        })));
+
+        // The callback only holds a weak reference, so that the player is dropped
+        // once it's destroyed.
+        let weak = Rc::downgrade(&worker.0);
+        worker.0.borrow_mut().tick_callback = Some(Closure::new(move || {
+            if let Some(instance) = Weak::upgrade(&weak) {
+                RuffleWorker(instance).tick();
+            }
+        }));
+        worker.schedule_tick(0);
+
+        Ok(worker)
+    }
+
+    fn with_core_mut<F, O>(&self, f: F) -> Option<O>
+    where
+        F: FnOnce(&mut Player) -> O,
+    {
+        let instance = self.0.borrow();
+        let _subscriber = tracing::subscriber::set_default(instance.log_subscriber.clone());
+        let mut core = instance.core.try_lock().ok()?;
+        Some(f(&mut core))
+    }
+
+    fn schedule_tick(&self, delay: i32) {
+        let mut instance = self.0.borrow_mut();
+        let id = instance.tick_callback.as_ref().and_then(|callback| {
+            global()
+                .set_timeout_with_callback_and_timeout_and_arguments_0(
+                    callback.as_ref().unchecked_ref(),
+                    delay,
+                )
+                .ok()
+        });
+        instance.timeout_id = id;
+    }
+
+    fn tick(&self) {
+        let timestamp = js_sys::Date::now();
+        let (dt, new_dimensions) = {
+            let mut instance = self.0.borrow_mut();
+            let dt = instance
+                .timestamp
+                .map_or(0.0, |prev_timestamp| timestamp - prev_timestamp);
+            instance.timestamp = Some(timestamp);
+            let new_dimensions = instance.new_dimensions.take();
+            if let Some(dimensions) = &new_dimensions {
+                instance.canvas.set_width(dimensions.width);
+                instance.canvas.set_height(dimensions.height);
+            }
+            (dt, new_dimensions)
+        };
+
+        let delay = self.with_core_mut(|core| {
+            if let Some(dimensions) = new_dimensions {
+                core.set_viewport_dimensions(dimensions);
+            }
+
+            core.tick(dt);
+
+            if core.needs_render() || new_dimensions.is_some() {
+                core.render();
+            }
+            core.time_til_next_frame()
+        });
+
+        // Without a window, there are no animation frames to wait for.
+        let delay = delay.map_or(0, |delay| delay.as_millis().min(i32::MAX as u128) as i32);
+        self.schedule_tick(delay.max(1));
+    }
+
+    fn on_metadata(swf_header: &ruffle_core::swf::HeaderExt) {
+        let metadata = MovieMetadata {
+            width: swf_header.stage_size().width().to_pixels(),
+            height: swf_header.stage_size().height().to_pixels(),
+            frame_rate: swf_header.frame_rate().to_f32(),
+            num_frames: swf_header.num_frames(),
+            uncompressed_len: swf_header.uncompressed_len(),
+            swf_version: swf_header.version(),
+            background_color: swf_header
+                .background_color()
+                .map(|color| format!("#{:06X}", color.to_rgb())),
+            is_action_script_3: swf_header.is_action_script_3(),
+        };
+        if let Ok(value) = serde_wasm_bindgen::to_value(&metadata) {
+            post_message("metadata", &value);
+        }
+    }
+}
+
+/// Only the wgpu renderer can draw to an `OffscreenCanvas`.
+#[cfg(all(
+    any(feature = "webgpu", feature = "wgpu-webgl"),
+    target_family = "wasm"
+))]
+async fn create_renderer(
+    builder: PlayerBuilder,
+    canvas: &OffscreenCanvas,
+    is_transparent: bool,
+) -> Result<PlayerBuilder, Box<dyn Error>> {
+    tracing::info!("Creating wgpu renderer for an OffscreenCanvas...");
+    let renderer = ruffle_render_wgpu::backend::WgpuRenderBackend::for_offscreen_canvas(
+        canvas.clone(),
+        is_transparent,
+    )
+    .await
+    .map_err(|e| format!("Error creating wgpu renderer: {e}"))?;
+    Ok(builder.with_renderer(renderer))
+}
+
+#[cfg(not(all(
+    any(feature = "webgpu", feature = "wgpu-webgl"),
+    target_family = "wasm"
+)))]
+async fn create_renderer(
+    _builder: PlayerBuilder,
+    _canvas: &OffscreenCanvas,
+    _is_transparent: bool,
+) -> Result<PlayerBuilder, Box<dyn Error>> {
+    Err("Running in a worker requires the wgpu renderer".into())
+}